@@ -0,0 +1,144 @@
+//! Keyframed property animation API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use strom_types::animation::{
+    PropertyAnimation, PropertyAnimationsResponse, SetPropertyAnimationRequest,
+};
+use strom_types::{api::ErrorResponse, FlowId};
+use tracing::error;
+
+use crate::gst::animation::AnimationError;
+use crate::state::AppState;
+
+/// Map an animation error to an HTTP error response.
+fn animation_error(context: &str, e: AnimationError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        AnimationError::NotRunning(_)
+        | AnimationError::ElementNotFound(_)
+        | AnimationError::PadNotFound(_)
+        | AnimationError::NotFound(_) => StatusCode::NOT_FOUND,
+        AnimationError::NotAnimatable(..)
+        | AnimationError::InvalidKeyframes(_)
+        | AnimationError::PositionQueryFailed => StatusCode::BAD_REQUEST,
+    };
+    error!("{}: {}", context, e);
+    (
+        status,
+        Json(ErrorResponse::with_details(context, e.to_string())),
+    )
+}
+
+/// List the keyframed property animations of a running flow.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/animations",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Animations of the flow", body = PropertyAnimationsResponse),
+    )
+)]
+pub async fn list_animations(
+    State(state): State<AppState>,
+    Path(flow_id): Path<FlowId>,
+) -> Json<PropertyAnimationsResponse> {
+    Json(PropertyAnimationsResponse {
+        animations: state.list_property_animations(&flow_id),
+    })
+}
+
+/// Create or replace a keyframed property animation.
+///
+/// Animates a numeric, controllable property of an element or one of its
+/// pads in a running flow, e.g. `volume` of an audio gain or `alpha` of a
+/// compositor pad. Keyframe times are relative to the start; the easing of
+/// a keyframe shapes the curve to the next one. Any other animation of the
+/// same property is cancelled. Starts right away unless `autostart` is
+/// false. State changes are reported with `PropertyAnimationChanged`
+/// WebSocket events.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/animations/{animation_id}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("animation_id" = String, Path, description = "Animation ID chosen by the client")
+    ),
+    request_body = SetPropertyAnimationRequest,
+    responses(
+        (status = 200, description = "Animation created", body = PropertyAnimation),
+        (status = 400, description = "Property cannot be animated or invalid keyframes", body = ErrorResponse),
+        (status = 404, description = "Flow not running or element not found", body = ErrorResponse),
+    )
+)]
+pub async fn set_animation(
+    State(state): State<AppState>,
+    Path((flow_id, animation_id)): Path<(FlowId, String)>,
+    ValidatedJson(req): ValidatedJson<SetPropertyAnimationRequest>,
+) -> Result<Json<PropertyAnimation>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .set_property_animation(&flow_id, &animation_id, req)
+        .await
+        .map(Json)
+        .map_err(|e| animation_error("Failed to set animation", e))
+}
+
+/// Start an animation from its first keyframe.
+///
+/// Restarts an animation that is running or has finished.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/animations/{animation_id}/start",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("animation_id" = String, Path, description = "Animation ID")
+    ),
+    responses(
+        (status = 200, description = "Animation started", body = PropertyAnimation),
+        (status = 400, description = "Animation could not start", body = ErrorResponse),
+        (status = 404, description = "Animation not found", body = ErrorResponse),
+    )
+)]
+pub async fn start_animation(
+    State(state): State<AppState>,
+    Path((flow_id, animation_id)): Path<(FlowId, String)>,
+) -> Result<Json<PropertyAnimation>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .start_property_animation(&flow_id, &animation_id)
+        .map(Json)
+        .map_err(|e| animation_error("Failed to start animation", e))
+}
+
+/// Cancel and remove an animation.
+///
+/// The property keeps the value it had when the animation was cancelled.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/animations/{animation_id}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("animation_id" = String, Path, description = "Animation ID")
+    ),
+    responses(
+        (status = 204, description = "Animation cancelled"),
+        (status = 404, description = "Animation not found", body = ErrorResponse),
+    )
+)]
+pub async fn cancel_animation(
+    State(state): State<AppState>,
+    Path((flow_id, animation_id)): Path<(FlowId, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .cancel_property_animation(&flow_id, &animation_id)
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| animation_error("Failed to cancel animation", e))
+}
@@ -0,0 +1,173 @@
+//! Audio router preset and salvo API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use strom_types::audio_router::{
+    AudioRouterResponse, AudioRouterSalvoRequest, AudioRoutingResponse,
+    SaveAudioRoutingPresetRequest,
+};
+use strom_types::{api::ErrorResponse, FlowId};
+use tracing::error;
+
+use crate::blocks::builtin::audiorouter::RoutingError;
+use crate::state::AppState;
+
+/// Map a routing error to an HTTP error response.
+fn routing_error(context: &str, e: RoutingError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        RoutingError::FlowNotFound(_) | RoutingError::PresetNotFound(_) => StatusCode::NOT_FOUND,
+        RoutingError::NotRouter(_) | RoutingError::InvalidRouting(_) => StatusCode::BAD_REQUEST,
+        RoutingError::NotLive(_) => StatusCode::CONFLICT,
+    };
+    error!("{}: {}", context, e);
+    (
+        status,
+        Json(ErrorResponse::with_details(context, e.to_string())),
+    )
+}
+
+/// Get the active routing and the presets of an audio router block.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/routing",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio router block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Active routing and presets", body = AudioRoutingResponse),
+        (status = 400, description = "Block is not an audio router", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_routing(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<AudioRoutingResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .get_audio_routing(&flow_id, &block_id)
+        .await
+        .map(Json)
+        .map_err(|e| routing_error("Failed to get routing", e))
+}
+
+/// Store a routing preset on an audio router block.
+///
+/// Replaces an existing preset with the same name. Without `routing` the
+/// active routing is stored. Crosspoints used only by this preset are
+/// built on the next flow start.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/routing/presets/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio router block instance ID"),
+        ("name" = String, Path, description = "Preset name")
+    ),
+    request_body = SaveAudioRoutingPresetRequest,
+    responses(
+        (status = 200, description = "Preset saved", body = AudioRouterResponse),
+        (status = 400, description = "Invalid routing or block is not an audio router", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn save_preset(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+    ValidatedJson(req): ValidatedJson<SaveAudioRoutingPresetRequest>,
+) -> Result<Json<AudioRouterResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let routing = state
+        .save_audio_routing_preset(&flow_id, &block_id, &name, req.routing)
+        .await
+        .map_err(|e| routing_error("Failed to save routing preset", e))?;
+
+    Ok(Json(AudioRouterResponse {
+        message: format!("Preset '{}' saved", name),
+        routing,
+        live: false,
+    }))
+}
+
+/// Delete a routing preset from an audio router block.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/routing/presets/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio router block instance ID"),
+        ("name" = String, Path, description = "Preset name")
+    ),
+    responses(
+        (status = 204, description = "Preset deleted"),
+        (status = 400, description = "Block is not an audio router", body = ErrorResponse),
+        (status = 404, description = "Flow or preset not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_preset(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .delete_audio_routing_preset(&flow_id, &block_id, &name)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| routing_error("Failed to delete routing preset", e))
+}
+
+/// Apply a complete routing to an audio router block (salvo).
+///
+/// Recalls a stored preset or applies an explicit routing. On a running
+/// flow every crosspoint switches together with a short crossfade; a routing
+/// that needs crosspoints that were not built at start (routes outside the
+/// routing matrix and all presets) is refused with 409. On a stopped flow
+/// the routing is stored and `live` is false.
+/// The new routing is reported with an `AudioRoutingChanged` WebSocket event.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/routing/salvo",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio router block instance ID")
+    ),
+    request_body = AudioRouterSalvoRequest,
+    responses(
+        (status = 200, description = "Routing applied", body = AudioRouterResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Flow or preset not found", body = ErrorResponse),
+        (status = 409, description = "Flow running without the crosspoints the routing needs", body = ErrorResponse),
+    )
+)]
+pub async fn salvo(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    ValidatedJson(req): ValidatedJson<AudioRouterSalvoRequest>,
+) -> Result<Json<AudioRouterResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (routing, live) = state
+        .audio_router_salvo(
+            &flow_id,
+            &block_id,
+            req.preset.as_deref(),
+            req.routing,
+            req.fade_ms,
+        )
+        .await
+        .map_err(|e| routing_error("Failed to apply routing", e))?;
+
+    Ok(Json(AudioRouterResponse {
+        message: if live {
+            "Routing applied".to_string()
+        } else {
+            "Routing stored, applies on next start".to_string()
+        },
+        routing,
+        live,
+    }))
+}
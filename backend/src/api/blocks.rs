@@ -24,7 +24,9 @@ use crate::state::AppState;
     )
 )]
 pub async fn list_blocks(State(state): State<AppState>) -> Json<BlockListResponse> {
-    let blocks = state.blocks().get_all().await;
+    let mut blocks = state.blocks().get_all().await;
+    let devices = state.discovery().get_devices().await;
+    crate::blocks::builtin::device::populate_device_options(&mut blocks, &devices);
     Json(BlockListResponse { blocks })
 }
 
@@ -46,7 +48,13 @@ pub async fn get_block(
     Path(id): Path<String>,
 ) -> Result<Json<BlockResponse>, (StatusCode, Json<ErrorResponse>)> {
     match state.blocks().get_by_id(&id).await {
-        Some(block) => Ok(Json(BlockResponse { block })),
+        Some(block) => {
+            let mut blocks = [block];
+            let devices = state.discovery().get_devices().await;
+            crate::blocks::builtin::device::populate_device_options(&mut blocks, &devices);
+            let [block] = blocks;
+            Ok(Json(BlockResponse { block }))
+        }
         None => Err((
             StatusCode::NOT_FOUND,
             Json(ErrorResponse::new("Block not found")),
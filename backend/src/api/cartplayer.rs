@@ -0,0 +1,266 @@
+//! Cart player API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+pub use strom_types::cartplayer::{
+    CartSlotState, CartSlotStatus, CartStateResponse, SetCartSlotsRequest,
+};
+use strom_types::{api::ErrorResponse, element::PropertyValue, FlowId};
+use tracing::info;
+
+use crate::blocks::builtin::cartplayer::{
+    parse_slots, CartPlayerKey, CartPlayerState, CART_PLAYER_REGISTRY,
+};
+use crate::state::AppState;
+use std::sync::Arc;
+
+/// Look up a running cart player.
+fn running_player(
+    flow_id: FlowId,
+    block_id: String,
+) -> Result<Arc<CartPlayerState>, (StatusCode, Json<ErrorResponse>)> {
+    CART_PLAYER_REGISTRY
+        .get(&CartPlayerKey { flow_id, block_id })
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new("Cart player not found")),
+        ))
+}
+
+/// Slots of a cart player: live state when running, the stored settings
+/// otherwise.
+async fn current_slots(
+    state: &AppState,
+    flow_id: FlowId,
+    block_id: &str,
+) -> Result<CartStateResponse, (StatusCode, Json<ErrorResponse>)> {
+    let key = CartPlayerKey {
+        flow_id,
+        block_id: block_id.to_string(),
+    };
+    if let Some(player) = CART_PLAYER_REGISTRY.get(&key) {
+        return Ok(CartStateResponse {
+            slots: player.slot_states(),
+        });
+    }
+
+    let flow = state.get_flow(&flow_id).await.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Flow not found")),
+    ))?;
+    let block = flow.blocks.iter().find(|b| b.id == block_id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Block not found")),
+    ))?;
+    let slots = match block.properties.get("slots") {
+        Some(PropertyValue::String(json)) => parse_slots(json).unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    Ok(CartStateResponse {
+        slots: slots
+            .into_iter()
+            .enumerate()
+            .map(|(index, slot)| CartSlotState {
+                index,
+                status: if slot.uri.is_empty() {
+                    CartSlotStatus::Empty
+                } else {
+                    CartSlotStatus::Loading
+                },
+                slot,
+                error: None,
+                duration_ns: 0,
+                voices: 0,
+            })
+            .collect(),
+    })
+}
+
+/// Get the slots of a cart player block.
+///
+/// Clips are loaded when the flow starts; until then assigned slots are
+/// reported as loading.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/cart/state",
+    tag = "cart_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    responses(
+        (status = 200, description = "Cart slots", body = CartStateResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_cart_state(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<CartStateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    current_slots(&state, flow_id, &block_id).await.map(Json)
+}
+
+/// Set the slots of a cart player block.
+///
+/// The slots are stored as a block property. A running player cuts its
+/// voices and loads the new clips into memory.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/cart/slots",
+    tag = "cart_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    request_body = SetCartSlotsRequest,
+    responses(
+        (status = 200, description = "Slots set", body = CartStateResponse),
+        (status = 400, description = "Invalid slots or not a cart player", body = ErrorResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn set_cart_slots(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    ValidatedJson(req): ValidatedJson<SetCartSlotsRequest>,
+) -> Result<Json<CartStateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let slots = req.into_slots().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Invalid slots", e)),
+        )
+    })?;
+    info!("Setting {} slots for cart player {}", slots.len(), block_id);
+
+    let mut flow = state.get_flow(&flow_id).await.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Flow not found")),
+    ))?;
+    let block = flow.blocks.iter_mut().find(|b| b.id == block_id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Block not found")),
+    ))?;
+    if block.block_definition_id != "builtin.cart_player" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new("Block is not a cart player")),
+        ));
+    }
+
+    let slots_json = serde_json::to_string(&slots).unwrap_or_else(|_| "[]".to_string());
+    block
+        .properties
+        .insert("slots".to_string(), PropertyValue::String(slots_json));
+
+    if let Err(e) = state.upsert_flow(flow).await {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::with_details(
+                "Failed to save flow",
+                e.to_string(),
+            )),
+        ));
+    }
+
+    let key = CartPlayerKey {
+        flow_id,
+        block_id: block_id.clone(),
+    };
+    if let Some(player) = CART_PLAYER_REGISTRY.get(&key) {
+        player.set_slots(slots);
+    }
+
+    current_slots(&state, flow_id, &block_id).await.map(Json)
+}
+
+/// Fire a cart player slot.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/cart/slots/{slot}/fire",
+    tag = "cart_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID"),
+        ("slot" = usize, Path, description = "Slot index (0-based)")
+    ),
+    responses(
+        (status = 200, description = "Slot fired"),
+        (status = 400, description = "No such slot or clip not loaded", body = ErrorResponse),
+        (status = 404, description = "Cart player not found", body = ErrorResponse)
+    )
+)]
+pub async fn fire_cart_slot(
+    State(_state): State<AppState>,
+    Path((flow_id, block_id, slot)): Path<(FlowId, String, usize)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let player = running_player(flow_id, block_id)?;
+    player.fire(slot).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Fire failed", e)),
+        )
+    })?;
+    Ok(StatusCode::OK)
+}
+
+/// Stop a cart player slot (with a short fade).
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/cart/slots/{slot}/stop",
+    tag = "cart_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID"),
+        ("slot" = usize, Path, description = "Slot index (0-based)")
+    ),
+    responses(
+        (status = 200, description = "Slot stopped"),
+        (status = 400, description = "No such slot", body = ErrorResponse),
+        (status = 404, description = "Cart player not found", body = ErrorResponse)
+    )
+)]
+pub async fn stop_cart_slot(
+    State(_state): State<AppState>,
+    Path((flow_id, block_id, slot)): Path<(FlowId, String, usize)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let player = running_player(flow_id, block_id)?;
+    player.stop(Some(slot)).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Stop failed", e)),
+        )
+    })?;
+    Ok(StatusCode::OK)
+}
+
+/// Stop all slots of a cart player.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/cart/stop",
+    tag = "cart_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    responses(
+        (status = 200, description = "All slots stopped"),
+        (status = 404, description = "Cart player not found", body = ErrorResponse)
+    )
+)]
+pub async fn stop_cart(
+    State(_state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let player = running_player(flow_id, block_id)?;
+    player.stop(None).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Stop failed", e)),
+        )
+    })?;
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,165 @@
+//! Compositor layout preset API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use strom_types::compositor::{
+    CompositorLayoutResponse, CompositorLayoutsResponse, RecallLayoutRequest,
+    SaveLayoutPresetRequest,
+};
+use strom_types::{api::ErrorResponse, FlowId};
+use tracing::error;
+
+use crate::blocks::builtin::compositor::LayoutError;
+use crate::state::AppState;
+
+/// Map a layout error to an HTTP error response.
+fn layout_error(context: &str, e: LayoutError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        LayoutError::FlowNotFound(_) | LayoutError::PresetNotFound(_) => StatusCode::NOT_FOUND,
+        LayoutError::NotCompositor(_) | LayoutError::InvalidLayout(_) => StatusCode::BAD_REQUEST,
+    };
+    error!("{}: {}", context, e);
+    (
+        status,
+        Json(ErrorResponse::with_details(context, e.to_string())),
+    )
+}
+
+/// Get the current layout and the layout presets of a compositor block.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/layouts",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Compositor block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Current layout and presets", body = CompositorLayoutsResponse),
+        (status = 400, description = "Block is not a compositor", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_layouts(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<CompositorLayoutsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .get_compositor_layouts(&flow_id, &block_id)
+        .await
+        .map(Json)
+        .map_err(|e| layout_error("Failed to get layouts", e))
+}
+
+/// Store a layout preset on a compositor block.
+///
+/// Replaces an existing preset with the same name. Without `layout` the
+/// current layout is stored; inputs missing from a partial layout keep
+/// their current placement.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/layouts/presets/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Compositor block instance ID"),
+        ("name" = String, Path, description = "Preset name")
+    ),
+    request_body = SaveLayoutPresetRequest,
+    responses(
+        (status = 200, description = "Preset saved", body = CompositorLayoutResponse),
+        (status = 400, description = "Invalid layout or block is not a compositor", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn save_preset(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+    ValidatedJson(req): ValidatedJson<SaveLayoutPresetRequest>,
+) -> Result<Json<CompositorLayoutResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let layout = state
+        .save_compositor_layout_preset(&flow_id, &block_id, &name, req.layout)
+        .await
+        .map_err(|e| layout_error("Failed to save layout preset", e))?;
+
+    Ok(Json(CompositorLayoutResponse {
+        message: format!("Preset '{}' saved", name),
+        layout,
+        live: false,
+    }))
+}
+
+/// Delete a layout preset from a compositor block.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/layouts/presets/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Compositor block instance ID"),
+        ("name" = String, Path, description = "Preset name")
+    ),
+    responses(
+        (status = 204, description = "Preset deleted"),
+        (status = 400, description = "Block is not a compositor", body = ErrorResponse),
+        (status = 404, description = "Flow or preset not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_preset(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .delete_compositor_layout_preset(&flow_id, &block_id, &name)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| layout_error("Failed to delete layout preset", e))
+}
+
+/// Recall a layout preset on a compositor block.
+///
+/// On a running flow every input glides from its current placement to the
+/// preset's position, size and alpha over `duration_ms`; z-order changes
+/// when the move starts. On a stopped flow the layout is stored and `live`
+/// is false. The recall is reported with a `CompositorLayoutRecalled`
+/// WebSocket event.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/layouts/presets/{name}/recall",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Compositor block instance ID"),
+        ("name" = String, Path, description = "Preset name")
+    ),
+    request_body = RecallLayoutRequest,
+    responses(
+        (status = 200, description = "Layout recalled", body = CompositorLayoutResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Flow or preset not found", body = ErrorResponse),
+    )
+)]
+pub async fn recall_preset(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+    ValidatedJson(req): ValidatedJson<RecallLayoutRequest>,
+) -> Result<Json<CompositorLayoutResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (layout, live) = state
+        .recall_compositor_layout(&flow_id, &block_id, &name, req.duration_ms)
+        .await
+        .map_err(|e| layout_error("Failed to recall layout preset", e))?;
+
+    Ok(Json(CompositorLayoutResponse {
+        message: if live {
+            format!("Layout '{}' recalled", name)
+        } else {
+            format!("Layout '{}' stored, applies on next start", name)
+        },
+        layout,
+        live,
+    }))
+}
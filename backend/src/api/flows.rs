@@ -22,6 +22,7 @@ use strom_types::{
 };
 use tracing::{debug, error, info, trace, warn};
 
+use crate::gst::PipelineError;
 use crate::layout;
 use crate::state::AppState;
 
@@ -1401,6 +1402,11 @@ pub async fn get_flow_pad_caps(
 /// - `slide_right`: New input slides in from the left
 /// - `slide_up`: New input slides in from the bottom
 /// - `slide_down`: New input slides in from the top
+/// - `dip_to_black`: Fade out to black, then in to the new input
+/// - `stinger`: Play the vision mixer's stinger clip and cut underneath at its cut point
+/// - `wipe_bar`, `wipe_bar_v`, `wipe_box`, `wipe_diamond`, `wipe_iris`, `wipe_clock`:
+///   SMPTE pattern wipes (also `smpte_<code>`, e.g. `smpte_201`); append `_reverse` to
+///   reverse the direction. Requires `enable_wipes` on the block.
 #[utoipa::path(
     post,
     path = "/api/flows/{flow_id}/blocks/{block_id}/transition",
@@ -1667,6 +1673,135 @@ pub async fn toggle_dsk(
     }))
 }
 
+/// Toggle a USK (Upstream Keyer) layer on a vision mixer block.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/usk",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID")
+    ),
+    request_body = strom_types::api::UskToggleRequest,
+    responses(
+        (status = 200, description = "USK toggled", body = strom_types::api::UskToggleResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
+pub async fn toggle_usk(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    Json(req): Json<strom_types::api::UskToggleRequest>,
+) -> Result<Json<strom_types::api::UskToggleResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if req.usk < 1 || req.usk > strom_types::vision_mixer::MAX_UPSTREAM_KEYERS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details(
+                "Invalid USK number",
+                format!(
+                    "USK must be 1-{}, got {}",
+                    strom_types::vision_mixer::MAX_UPSTREAM_KEYERS,
+                    req.usk
+                ),
+            )),
+        ));
+    }
+
+    info!(
+        "Toggling USK {} {} on vision mixer {} in flow {}",
+        req.usk,
+        if req.enabled { "on" } else { "off" },
+        block_id,
+        flow_id
+    );
+
+    state
+        .set_usk_enabled(&flow_id, &block_id, req.usk - 1, req.enabled)
+        .await
+        .map_err(|e| {
+            error!("Failed to toggle USK: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::with_details(
+                    "Failed to toggle USK",
+                    e.to_string(),
+                )),
+            )
+        })?;
+
+    Ok(Json(strom_types::api::UskToggleResponse {
+        message: format!(
+            "USK {} {}",
+            req.usk,
+            if req.enabled { "enabled" } else { "disabled" }
+        ),
+        usk: req.usk,
+        enabled: req.enabled,
+    }))
+}
+
+/// Route a source to an AUX output of a vision mixer block.
+///
+/// The source can be any input, PGM, PVW (follows preview changes) or the
+/// clean feed (PGM without DSK layers).
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/aux",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID")
+    ),
+    request_body = strom_types::api::SetAuxSourceRequest,
+    responses(
+        (status = 200, description = "AUX source changed", body = strom_types::api::SetAuxSourceResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
+pub async fn set_aux_source(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    ValidatedJson(req): ValidatedJson<strom_types::api::SetAuxSourceRequest>,
+) -> Result<Json<strom_types::api::SetAuxSourceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "Setting AUX {} to {} ({}) on vision mixer {} in flow {}",
+        req.aux,
+        req.source.as_property(),
+        req.transition_type,
+        block_id,
+        flow_id
+    );
+
+    // Convert 1-based AUX number to 0-based internal index
+    let inputs = state
+        .set_vision_mixer_aux(
+            &flow_id,
+            &block_id,
+            req.aux - 1,
+            req.source,
+            &req.transition_type,
+            req.duration_ms,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to set AUX source: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::with_details(
+                    "Failed to set AUX source",
+                    e.to_string(),
+                )),
+            )
+        })?;
+
+    Ok(Json(strom_types::api::SetAuxSourceResponse {
+        message: format!("AUX {} set to {}", req.aux, req.source.as_property()),
+        aux: req.aux,
+        source: req.source,
+        inputs,
+    }))
+}
+
 /// Toggle Fade to Black on a vision mixer block.
 #[utoipa::path(
     post,
@@ -1779,6 +1914,68 @@ pub async fn recorder_split_now(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Inject an SCTE-35 cue (splice_insert or time_signal) into an MPEG-TS output block.
+///
+/// The cue is written on the muxer's SCTE-35 PID, either immediately or with
+/// the given splice PTS.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/scte35",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block instance ID")
+    ),
+    request_body = strom_types::scte35::InsertScte35Request,
+    responses(
+        (status = 200, description = "Cue inserted", body = strom_types::scte35::InsertScte35Response),
+        (status = 400, description = "Invalid cue or block has no SCTE-35 enabled muxer", body = ErrorResponse),
+        (status = 404, description = "Flow not running or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn insert_scte35(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    Json(req): Json<strom_types::scte35::InsertScte35Request>,
+) -> Result<Json<strom_types::scte35::InsertScte35Response>, (StatusCode, Json<ErrorResponse>)> {
+    let cue = req.to_cue().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Invalid SCTE-35 cue", e)),
+        )
+    })?;
+
+    info!(
+        "Inserting SCTE-35 {} on block {} in flow {}",
+        cue.command, block_id, flow_id
+    );
+
+    state
+        .insert_scte35(&flow_id, &block_id, cue.clone())
+        .await
+        .map_err(|e| {
+            error!("Failed to insert SCTE-35 cue: {}", e);
+            let status = match e {
+                PipelineError::InvalidFlow(_) | PipelineError::ElementNotFound(_) => {
+                    StatusCode::NOT_FOUND
+                }
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (
+                status,
+                Json(ErrorResponse::with_details(
+                    "Failed to insert SCTE-35 cue",
+                    e.to_string(),
+                )),
+            )
+        })?;
+
+    Ok(Json(strom_types::scte35::InsertScte35Response {
+        message: format!("SCTE-35 {} inserted", cue.command),
+        cue,
+    }))
+}
+
 /// Animate a single input's position and/or size.
 ///
 /// Smoothly animates the specified input from its current position/size
@@ -0,0 +1,259 @@
+//! Vision mixer macro API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use strom_types::macros::{
+    MacroListResponse, MacroResponse, SaveMacroRequest, StartMacroRecordingRequest,
+};
+use strom_types::{api::ErrorResponse, FlowId};
+use tracing::{error, info};
+
+use crate::macro_registry::MacroError;
+use crate::state::AppState;
+
+/// Map a macro error to an HTTP error response.
+fn macro_error(context: &str, e: MacroError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        MacroError::FlowNotFound(_) | MacroError::NotFound(_) => StatusCode::NOT_FOUND,
+        MacroError::AlreadyRecording(_) | MacroError::AlreadyRunning(_) => StatusCode::CONFLICT,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    error!("{}: {}", context, e);
+    (
+        status,
+        Json(ErrorResponse::with_details(context, e.to_string())),
+    )
+}
+
+/// List the macros of a vision mixer block.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Macros and recording/playback status", body = MacroListResponse),
+        (status = 400, description = "Block is not a vision mixer", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn list_macros(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<MacroListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .list_macros(&flow_id, &block_id)
+        .await
+        .map(Json)
+        .map_err(|e| macro_error("Failed to list macros", e))
+}
+
+/// Create or replace a macro on a vision mixer block.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID"),
+        ("name" = String, Path, description = "Macro name")
+    ),
+    request_body = SaveMacroRequest,
+    responses(
+        (status = 200, description = "Macro saved", body = MacroResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn save_macro(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+    ValidatedJson(req): ValidatedJson<SaveMacroRequest>,
+) -> Result<Json<MacroResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let steps = req.steps.len();
+    state
+        .save_macro(&flow_id, &block_id, &name, req.steps)
+        .await
+        .map_err(|e| macro_error("Failed to save macro", e))?;
+
+    Ok(Json(MacroResponse {
+        message: format!("Macro saved with {} steps", steps),
+        name,
+    }))
+}
+
+/// Delete a macro from a vision mixer block.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID"),
+        ("name" = String, Path, description = "Macro name")
+    ),
+    responses(
+        (status = 200, description = "Macro deleted", body = MacroResponse),
+        (status = 404, description = "Flow or macro not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_macro(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+) -> Result<Json<MacroResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .delete_macro(&flow_id, &block_id, &name)
+        .await
+        .map_err(|e| macro_error("Failed to delete macro", e))?;
+
+    Ok(Json(MacroResponse {
+        message: "Macro deleted".to_string(),
+        name,
+    }))
+}
+
+/// Start playing back a macro on a vision mixer block.
+///
+/// Returns immediately; progress is reported with `VisionMixerMacroRunning`
+/// WebSocket events.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros/{name}/run",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID"),
+        ("name" = String, Path, description = "Macro name")
+    ),
+    responses(
+        (status = 200, description = "Macro started", body = MacroResponse),
+        (status = 400, description = "Flow not running", body = ErrorResponse),
+        (status = 404, description = "Flow or macro not found", body = ErrorResponse),
+        (status = 409, description = "A macro is already running on this block", body = ErrorResponse),
+    )
+)]
+pub async fn run_macro(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+) -> Result<Json<MacroResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .run_macro(&flow_id, &block_id, &name)
+        .await
+        .map_err(|e| macro_error("Failed to run macro", e))?;
+
+    Ok(Json(MacroResponse {
+        message: "Macro started".to_string(),
+        name,
+    }))
+}
+
+/// Cancel the macro playing on a vision mixer block.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros/stop",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Macro playback cancelled", body = MacroResponse),
+        (status = 400, description = "No macro is running", body = ErrorResponse),
+    )
+)]
+pub async fn stop_macro(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<MacroResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let name = state
+        .list_macros(&flow_id, &block_id)
+        .await
+        .ok()
+        .and_then(|list| list.running)
+        .unwrap_or_default();
+    state
+        .stop_macro(&flow_id, &block_id)
+        .map_err(|e| macro_error("Failed to stop macro", e))?;
+
+    Ok(Json(MacroResponse {
+        message: "Macro cancelled".to_string(),
+        name,
+    }))
+}
+
+/// Start recording switcher actions into a macro.
+///
+/// Transitions, preview and background selection, DSK and USK toggles,
+/// overlay alpha and Fade to Black performed on the block are recorded with
+/// their timing until recording is stopped.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros/record",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID")
+    ),
+    request_body = StartMacroRecordingRequest,
+    responses(
+        (status = 200, description = "Recording started", body = MacroResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 409, description = "Already recording", body = ErrorResponse),
+    )
+)]
+pub async fn start_recording(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    ValidatedJson(req): ValidatedJson<StartMacroRecordingRequest>,
+) -> Result<Json<MacroResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .start_macro_recording(&flow_id, &block_id, &req.name)
+        .await
+        .map_err(|e| macro_error("Failed to start macro recording", e))?;
+
+    Ok(Json(MacroResponse {
+        message: "Recording started".to_string(),
+        name: req.name,
+    }))
+}
+
+/// Stop recording and save the macro to the flow.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/macros/record/stop",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Vision mixer block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Recording stopped and macro saved", body = MacroResponse),
+        (status = 400, description = "Not recording", body = ErrorResponse),
+    )
+)]
+pub async fn stop_recording(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<MacroResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let recorded = state
+        .stop_macro_recording(&flow_id, &block_id)
+        .await
+        .map_err(|e| macro_error("Failed to stop macro recording", e))?;
+
+    info!(
+        "Saved macro '{}' on vision mixer {} in flow {}",
+        recorded.name, block_id, flow_id
+    );
+
+    Ok(Json(MacroResponse {
+        message: format!("Recorded {} steps", recorded.steps.len()),
+        name: recorded.name,
+    }))
+}
@@ -2,18 +2,21 @@
 
 use crate::json_rejection::{JsonBody, ValidatedJson};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
+use serde::Deserialize;
 pub use strom_types::mediaplayer::{
-    GotoRequest, PlayerAction, PlayerControlRequest, PlayerStateResponse, SeekRequest,
-    SetPlaylistRequest,
+    AsRunResponse, GotoRequest, PlayerAction, PlayerControlRequest, PlayerStateResponse,
+    PlayoutSchedule, ScheduleEvent, ScheduleResponse, SeekRequest, SetPlaylistRequest,
 };
 use strom_types::{api::ErrorResponse, element::PropertyValue, FlowId};
 use tracing::info;
 
-use crate::blocks::builtin::mediaplayer::{MediaPlayerKey, MEDIA_PLAYER_REGISTRY};
+use crate::blocks::builtin::mediaplayer::{
+    parse_schedule, prepare_schedule, read_as_run, MediaPlayerKey, MEDIA_PLAYER_REGISTRY,
+};
 use crate::state::AppState;
 
 /// Get the current state of a media player block.
@@ -52,6 +55,9 @@ pub async fn get_player_state(
         total_files: playlist.len(),
         current_file: player.current_file(),
         playlist,
+        items: player.playlist_items(),
+        loop_pass: player.loop_pass(),
+        cued: player.cued.load(std::sync::atomic::Ordering::SeqCst),
         loop_playlist: player
             .loop_playlist
             .load(std::sync::atomic::Ordering::SeqCst),
@@ -59,6 +65,9 @@ pub async fn get_player_state(
 }
 
 /// Set the playlist for a media player block.
+///
+/// Items may carry in/out points, a loop count, an end action and
+/// cue-on-load. Plain `files` are played whole, once.
 #[utoipa::path(
     post,
     path = "/api/flows/{flow_id}/blocks/{block_id}/player/playlist",
@@ -70,6 +79,7 @@ pub async fn get_player_state(
     request_body = SetPlaylistRequest,
     responses(
         (status = 200, description = "Playlist set"),
+        (status = 400, description = "Invalid playlist", body = ErrorResponse),
         (status = 404, description = "Flow or block not found", body = ErrorResponse)
     )
 )]
@@ -78,10 +88,16 @@ pub async fn set_playlist(
     Path((flow_id, block_id)): Path<(FlowId, String)>,
     ValidatedJson(req): ValidatedJson<SetPlaylistRequest>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let items = req.into_items().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Invalid playlist", e)),
+        )
+    })?;
     info!(
         "Setting playlist for player {}: {} files",
         block_id,
-        req.files.len()
+        items.len()
     );
 
     // Always store playlist as a block property so it persists
@@ -97,7 +113,7 @@ pub async fn set_playlist(
     ))?;
 
     // Store playlist as JSON string in properties
-    let playlist_json = serde_json::to_string(&req.files).unwrap_or_else(|_| "[]".to_string());
+    let playlist_json = serde_json::to_string(&items).unwrap_or_else(|_| "[]".to_string());
     block
         .properties
         .insert("playlist".to_string(), PropertyValue::String(playlist_json));
@@ -121,7 +137,7 @@ pub async fn set_playlist(
 
     if let Some(player) = MEDIA_PLAYER_REGISTRY.get(&key) {
         let was_stopped = player.state() == strom_types::mediaplayer::PlayerState::Stopped;
-        player.set_playlist(req.files);
+        player.set_items(items);
 
         // Only auto-start from the beginning if the player was stopped
         if was_stopped && player.playlist_len() > 0 {
@@ -283,3 +299,287 @@ pub async fn goto_file(
 
     Ok(StatusCode::OK)
 }
+
+/// Query parameters for reading the as-run log.
+#[derive(Debug, Deserialize)]
+pub struct AsRunQuery {
+    /// Day to read (YYYY-MM-DD, UTC). Defaults to today.
+    pub date: Option<String>,
+}
+
+/// The stored schedule of a media player block, and the schedule and on-air
+/// record of the running player.
+async fn current_schedule(
+    state: &AppState,
+    flow_id: FlowId,
+    block_id: &str,
+) -> Result<ScheduleResponse, (StatusCode, Json<ErrorResponse>)> {
+    let key = MediaPlayerKey {
+        flow_id,
+        block_id: block_id.to_string(),
+    };
+    if let Some(player) = MEDIA_PLAYER_REGISTRY.get(&key) {
+        if let Ok(scheduler) = player.schedule.lock() {
+            let schedule = scheduler.schedule();
+            return Ok(ScheduleResponse {
+                events: schedule.events,
+                filler: schedule.filler,
+                on_air: scheduler.on_air(),
+            });
+        }
+    }
+
+    let flow = state.get_flow(&flow_id).await.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Flow not found")),
+    ))?;
+    let block = flow.blocks.iter().find(|b| b.id == block_id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Block not found")),
+    ))?;
+    let schedule = match block.properties.get("schedule") {
+        Some(PropertyValue::String(json)) => parse_schedule(json).unwrap_or_default(),
+        _ => PlayoutSchedule::default(),
+    };
+    Ok(ScheduleResponse {
+        events: schedule.events,
+        filler: schedule.filler,
+        on_air: None,
+    })
+}
+
+/// Check a schedule, store it as a block property and hand it to the
+/// running player.
+async fn store_schedule(
+    state: &AppState,
+    flow_id: FlowId,
+    block_id: &str,
+    schedule: PlayoutSchedule,
+) -> Result<ScheduleResponse, (StatusCode, Json<ErrorResponse>)> {
+    let schedule = prepare_schedule(schedule).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Invalid schedule", e)),
+        )
+    })?;
+
+    let mut flow = state.get_flow(&flow_id).await.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Flow not found")),
+    ))?;
+    let block = flow.blocks.iter_mut().find(|b| b.id == block_id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Block not found")),
+    ))?;
+    if block.block_definition_id != "builtin.media_player" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new("Block is not a media player")),
+        ));
+    }
+
+    let schedule_json = serde_json::to_string(&schedule).unwrap_or_else(|_| "{}".to_string());
+    block
+        .properties
+        .insert("schedule".to_string(), PropertyValue::String(schedule_json));
+
+    if let Err(e) = state.upsert_flow(flow).await {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::with_details(
+                "Failed to save flow",
+                e.to_string(),
+            )),
+        ));
+    }
+
+    let key = MediaPlayerKey {
+        flow_id,
+        block_id: block_id.to_string(),
+    };
+    if let Some(player) = MEDIA_PLAYER_REGISTRY.get(&key) {
+        if let Ok(mut scheduler) = player.schedule.lock() {
+            scheduler.set_schedule(schedule);
+        }
+    }
+
+    current_schedule(state, flow_id, block_id).await
+}
+
+/// Get the playout schedule of a media player block.
+///
+/// While the flow runs, only upcoming events are listed, together with what
+/// is on air.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/player/schedule",
+    tag = "media_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    responses(
+        (status = 200, description = "Playout schedule", body = ScheduleResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<ScheduleResponse>, (StatusCode, Json<ErrorResponse>)> {
+    current_schedule(&state, flow_id, &block_id).await.map(Json)
+}
+
+/// Replace the playout schedule of a media player block.
+///
+/// Events get an ID when none is given. On a running flow the new events
+/// take effect immediately; what is on air stays.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/player/schedule",
+    tag = "media_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    request_body = PlayoutSchedule,
+    responses(
+        (status = 200, description = "Schedule stored", body = ScheduleResponse),
+        (status = 400, description = "Invalid schedule", body = ErrorResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn set_schedule(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    JsonBody(schedule): JsonBody<PlayoutSchedule>,
+) -> Result<Json<ScheduleResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "Setting schedule for player {}: {} events, {} filler items",
+        block_id,
+        schedule.events.len(),
+        schedule.filler.len()
+    );
+    store_schedule(&state, flow_id, &block_id, schedule)
+        .await
+        .map(Json)
+}
+
+/// Add an event to the playout schedule of a media player block.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/player/schedule/events",
+    tag = "media_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    request_body = ScheduleEvent,
+    responses(
+        (status = 201, description = "Event added", body = ScheduleResponse),
+        (status = 400, description = "Invalid event", body = ErrorResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn add_schedule_event(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    JsonBody(event): JsonBody<ScheduleEvent>,
+) -> Result<(StatusCode, Json<ScheduleResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let current = current_schedule(&state, flow_id, &block_id).await?;
+    let mut events = current.events;
+    events.push(event);
+    let schedule = PlayoutSchedule {
+        events,
+        filler: current.filler,
+    };
+    store_schedule(&state, flow_id, &block_id, schedule)
+        .await
+        .map(|response| (StatusCode::CREATED, Json(response)))
+}
+
+/// Remove an event from the playout schedule of a media player block.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/player/schedule/events/{event_id}",
+    tag = "media_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID"),
+        ("event_id" = String, Path, description = "Event ID")
+    ),
+    responses(
+        (status = 204, description = "Event removed"),
+        (status = 404, description = "Flow, block or event not found", body = ErrorResponse)
+    )
+)]
+pub async fn delete_schedule_event(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, event_id)): Path<(FlowId, String, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let current = current_schedule(&state, flow_id, &block_id).await?;
+    let before = current.events.len();
+    let events: Vec<ScheduleEvent> = current
+        .events
+        .into_iter()
+        .filter(|e| e.id != event_id)
+        .collect();
+    if events.len() == before {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new("Event not found")),
+        ));
+    }
+    let schedule = PlayoutSchedule {
+        events,
+        filler: current.filler,
+    };
+    store_schedule(&state, flow_id, &block_id, schedule)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+/// Get the as-run log of a media player block for one day.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/player/as-run",
+    tag = "media_player",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID"),
+        ("date" = Option<String>, Query, description = "Day to read (YYYY-MM-DD, UTC), defaults to today")
+    ),
+    responses(
+        (status = 200, description = "As-run log", body = AsRunResponse),
+        (status = 400, description = "Invalid date", body = ErrorResponse),
+        (status = 500, description = "Failed to read the log", body = ErrorResponse)
+    )
+)]
+pub async fn get_as_run(
+    State(_state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    Query(query): Query<AsRunQuery>,
+) -> Result<Json<AsRunResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let date = match query.date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::with_details("Invalid date", e.to_string())),
+            )
+        })?,
+        None => chrono::Utc::now().date_naive(),
+    };
+    let date = date.format("%Y-%m-%d").to_string();
+
+    let entries = read_as_run(&flow_id, &block_id, &date).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::with_details(
+                "Failed to read as-run log",
+                e.to_string(),
+            )),
+        )
+    })?;
+
+    Ok(Json(AsRunResponse { date, entries }))
+}
@@ -0,0 +1,116 @@
+//! Audio mixer automation API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use strom_types::mixer::{AutomationLane, MixerAutomationResponse, SetAutomationLaneRequest};
+use strom_types::{api::ErrorResponse, FlowId};
+use tracing::error;
+
+use crate::blocks::builtin::mixer::automation::AutomationError;
+use crate::state::AppState;
+
+/// Map an automation error to an HTTP error response.
+fn automation_error(context: &str, e: AutomationError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        AutomationError::FlowNotFound(_) | AutomationError::NotFound(_) => StatusCode::NOT_FOUND,
+        AutomationError::NotMixer(_) | AutomationError::InvalidParameter(_) => {
+            StatusCode::BAD_REQUEST
+        }
+    };
+    error!("{}: {}", context, e);
+    (
+        status,
+        Json(ErrorResponse::with_details(context, e.to_string())),
+    )
+}
+
+/// List the automation lanes of an audio mixer block.
+///
+/// While the flow runs, lanes include the points recorded so far.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/automation",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Automation lanes of the block", body = MixerAutomationResponse),
+        (status = 400, description = "Block is not an audio mixer", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn list_automation(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<MixerAutomationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .list_mixer_automation(&flow_id, &block_id)
+        .await
+        .map(|lanes| Json(MixerAutomationResponse { lanes }))
+        .map_err(|e| automation_error("Failed to list automation", e))
+}
+
+/// Create or update the automation lane of a mixer parameter.
+///
+/// `parameter` is a fader or pan property such as `ch1_fader`, `ch1_pan` or
+/// `main_fader`. The mode applies immediately on a running flow; recorded
+/// points are kept unless `points` is given.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/automation/{parameter}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID"),
+        ("parameter" = String, Path, description = "Automated mixer property")
+    ),
+    request_body = SetAutomationLaneRequest,
+    responses(
+        (status = 200, description = "Lane updated", body = AutomationLane),
+        (status = 400, description = "Invalid request or parameter", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn set_automation_lane(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, parameter)): Path<(FlowId, String, String)>,
+    ValidatedJson(req): ValidatedJson<SetAutomationLaneRequest>,
+) -> Result<Json<AutomationLane>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .set_mixer_automation_lane(&flow_id, &block_id, &parameter, req)
+        .await
+        .map(Json)
+        .map_err(|e| automation_error("Failed to set automation lane", e))
+}
+
+/// Delete the automation lane of a mixer parameter.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/automation/{parameter}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID"),
+        ("parameter" = String, Path, description = "Automated mixer property")
+    ),
+    responses(
+        (status = 204, description = "Lane deleted"),
+        (status = 404, description = "Flow or lane not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_automation_lane(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, parameter)): Path<(FlowId, String, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .delete_mixer_automation_lane(&flow_id, &block_id, &parameter)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| automation_error("Failed to delete automation lane", e))
+}
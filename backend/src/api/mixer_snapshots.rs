@@ -0,0 +1,158 @@
+//! Audio mixer snapshot API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use strom_types::mixer::{
+    MixerSnapshotListResponse, MixerSnapshotResponse, RecallMixerSnapshotRequest,
+};
+use strom_types::{api::ErrorResponse, FlowId};
+use tracing::error;
+
+use crate::blocks::builtin::mixer::snapshot::SnapshotError;
+use crate::state::AppState;
+
+/// Map a snapshot error to an HTTP error response.
+fn snapshot_error(context: &str, e: SnapshotError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        SnapshotError::FlowNotFound(_) | SnapshotError::NotFound(_) => StatusCode::NOT_FOUND,
+        SnapshotError::NotMixer(_) => StatusCode::BAD_REQUEST,
+    };
+    error!("{}: {}", context, e);
+    (
+        status,
+        Json(ErrorResponse::with_details(context, e.to_string())),
+    )
+}
+
+/// List the snapshots of an audio mixer block.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/snapshots",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID")
+    ),
+    responses(
+        (status = 200, description = "Snapshots stored for the block", body = MixerSnapshotListResponse),
+        (status = 400, description = "Block is not an audio mixer", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn list_snapshots(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<MixerSnapshotListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .list_mixer_snapshots(&flow_id, &block_id)
+        .await
+        .map(|snapshots| Json(MixerSnapshotListResponse { snapshots }))
+        .map_err(|e| snapshot_error("Failed to list snapshots", e))
+}
+
+/// Store the current state of an audio mixer block as a snapshot.
+///
+/// Replaces an existing snapshot with the same name.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/snapshots/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID"),
+        ("name" = String, Path, description = "Snapshot name")
+    ),
+    responses(
+        (status = 200, description = "Snapshot saved", body = MixerSnapshotResponse),
+        (status = 400, description = "Block is not an audio mixer", body = ErrorResponse),
+        (status = 404, description = "Flow not found", body = ErrorResponse),
+    )
+)]
+pub async fn save_snapshot(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+) -> Result<Json<MixerSnapshotResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let properties = state
+        .save_mixer_snapshot(&flow_id, &block_id, &name)
+        .await
+        .map_err(|e| snapshot_error("Failed to save snapshot", e))?;
+
+    Ok(Json(MixerSnapshotResponse {
+        message: "Snapshot saved".to_string(),
+        name,
+        properties,
+    }))
+}
+
+/// Delete a snapshot from an audio mixer block.
+#[utoipa::path(
+    delete,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/snapshots/{name}",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID"),
+        ("name" = String, Path, description = "Snapshot name")
+    ),
+    responses(
+        (status = 200, description = "Snapshot deleted", body = MixerSnapshotResponse),
+        (status = 404, description = "Flow or snapshot not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_snapshot(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+) -> Result<Json<MixerSnapshotResponse>, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .delete_mixer_snapshot(&flow_id, &block_id, &name)
+        .await
+        .map_err(|e| snapshot_error("Failed to delete snapshot", e))?;
+
+    Ok(Json(MixerSnapshotResponse {
+        message: "Snapshot deleted".to_string(),
+        name,
+        properties: 0,
+    }))
+}
+
+/// Recall a snapshot on an audio mixer block.
+///
+/// Channels marked recall safe are left untouched. Faders, aux sends and
+/// pans ramp over `crossfade_ms`; every changed property is reported with a
+/// `PropertyChanged` WebSocket event whose `element_id` is the block ID.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/snapshots/{name}/recall",
+    tag = "flows",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Audio mixer block instance ID"),
+        ("name" = String, Path, description = "Snapshot name")
+    ),
+    request_body = RecallMixerSnapshotRequest,
+    responses(
+        (status = 200, description = "Snapshot recalled", body = MixerSnapshotResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Flow or snapshot not found", body = ErrorResponse),
+    )
+)]
+pub async fn recall_snapshot(
+    State(state): State<AppState>,
+    Path((flow_id, block_id, name)): Path<(FlowId, String, String)>,
+    ValidatedJson(req): ValidatedJson<RecallMixerSnapshotRequest>,
+) -> Result<Json<MixerSnapshotResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let properties = state
+        .recall_mixer_snapshot(&flow_id, &block_id, &name, req.crossfade_ms)
+        .await
+        .map_err(|e| snapshot_error("Failed to recall snapshot", e))?;
+
+    Ok(Json(MixerSnapshotResponse {
+        message: format!("Snapshot recalled, {} properties changed", properties),
+        name,
+        properties,
+    }))
+}
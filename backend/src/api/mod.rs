@@ -1,15 +1,23 @@
 //! API handlers.
 
+pub mod animations;
+pub mod audio_router;
 pub mod blocks;
+pub mod cartplayer;
+pub mod compositor_layouts;
 pub mod discovery;
 pub mod elements;
 pub mod flows;
 pub mod gst_launch;
+pub mod macros;
 pub mod mcp;
 pub mod media;
 pub mod mediaplayer;
+pub mod mixer_automation;
+pub mod mixer_snapshots;
 pub mod network;
 pub mod probes;
+pub mod recorder;
 pub mod sdp_transform;
 pub mod version;
 pub mod vision_mixer_page;
@@ -0,0 +1,204 @@
+//! Recorder control API handlers.
+
+use crate::json_rejection::ValidatedJson;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+pub use strom_types::recorder::{
+    RecorderAction, RecorderControlRequest, RecorderState, RecorderStatusResponse, RecorderTrigger,
+    RecorderTriggerSource, SetRecorderTriggerRequest,
+};
+use strom_types::{api::ErrorResponse, element::PropertyValue, FlowId};
+use tracing::info;
+
+use crate::blocks::builtin::recorder::{parse_trigger, RecorderKey, RECORDER_REGISTRY};
+use crate::state::AppState;
+
+/// Status of a recorder: live when running, from the stored settings
+/// otherwise.
+async fn current_status(
+    state: &AppState,
+    flow_id: FlowId,
+    block_id: &str,
+) -> Result<RecorderStatusResponse, (StatusCode, Json<ErrorResponse>)> {
+    let key = RecorderKey {
+        flow_id,
+        block_id: block_id.to_string(),
+    };
+    if let Some(recorder) = RECORDER_REGISTRY.get(&key) {
+        return Ok(recorder.status());
+    }
+
+    let flow = state.get_flow(&flow_id).await.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Flow not found")),
+    ))?;
+    let block = flow.blocks.iter().find(|b| b.id == block_id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Block not found")),
+    ))?;
+    let trigger = match block.properties.get("trigger") {
+        Some(PropertyValue::String(json)) => parse_trigger(json),
+        _ => None,
+    };
+    Ok(RecorderStatusResponse {
+        state: RecorderState::Idle,
+        armed: false,
+        trigger,
+        filename: None,
+        takes: 0,
+    })
+}
+
+/// Get the recording state of a recorder block.
+#[utoipa::path(
+    get,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/recorder/state",
+    tag = "recorder",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    responses(
+        (status = 200, description = "Recorder state", body = RecorderStatusResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_recorder_state(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+) -> Result<Json<RecorderStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    current_status(&state, flow_id, &block_id).await.map(Json)
+}
+
+/// Arm, start, stop, pause or resume a recorder without stopping the flow.
+///
+/// Stopping finalizes the file; the next start writes a new file. An armed
+/// recorder is started and stopped by its trigger.
+#[utoipa::path(
+    post,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/recorder/control",
+    tag = "recorder",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    request_body = RecorderControlRequest,
+    responses(
+        (status = 200, description = "Action applied", body = RecorderStatusResponse),
+        (status = 400, description = "Action not possible in the current state", body = ErrorResponse),
+        (status = 404, description = "Recorder not running", body = ErrorResponse)
+    )
+)]
+pub async fn control_recorder(
+    State(_state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    Json(req): Json<RecorderControlRequest>,
+) -> Result<Json<RecorderStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let recorder = RECORDER_REGISTRY
+        .get(&RecorderKey { flow_id, block_id })
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::with_details(
+                "Recorder not found",
+                "The flow is not running, or the recorder is in ts_passthrough mode",
+            )),
+        ))?;
+    info!("Recorder {} control: {:?}", recorder.block_id, req.action);
+    recorder.apply(req.action).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::with_details("Recorder control failed", e)),
+        )
+    })?;
+    Ok(Json(recorder.status()))
+}
+
+/// Set or clear the trigger of a recorder block.
+///
+/// The trigger is stored as a block property and applies to a running
+/// recorder right away. Clearing it disarms the recorder.
+#[utoipa::path(
+    put,
+    path = "/api/flows/{flow_id}/blocks/{block_id}/recorder/trigger",
+    tag = "recorder",
+    params(
+        ("flow_id" = String, Path, description = "Flow ID (UUID)"),
+        ("block_id" = String, Path, description = "Block ID")
+    ),
+    request_body = SetRecorderTriggerRequest,
+    responses(
+        (status = 200, description = "Trigger set", body = RecorderStatusResponse),
+        (status = 400, description = "Invalid trigger or not a recorder", body = ErrorResponse),
+        (status = 404, description = "Flow or block not found", body = ErrorResponse)
+    )
+)]
+pub async fn set_recorder_trigger(
+    State(state): State<AppState>,
+    Path((flow_id, block_id)): Path<(FlowId, String)>,
+    ValidatedJson(req): ValidatedJson<SetRecorderTriggerRequest>,
+) -> Result<Json<RecorderStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if let Some(trigger) = &req.trigger {
+        trigger.validate().map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::with_details("Invalid trigger", e)),
+            )
+        })?;
+    }
+
+    let mut flow = state.get_flow(&flow_id).await.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Flow not found")),
+    ))?;
+    let block = flow.blocks.iter_mut().find(|b| b.id == block_id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new("Block not found")),
+    ))?;
+    if block.block_definition_id != "builtin.recorder" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new("Block is not a recorder")),
+        ));
+    }
+
+    match &req.trigger {
+        Some(trigger) => {
+            let json = serde_json::to_string(trigger).unwrap_or_default();
+            block
+                .properties
+                .insert("trigger".to_string(), PropertyValue::String(json));
+        }
+        None => {
+            block.properties.remove("trigger");
+        }
+    }
+
+    if let Err(e) = state.upsert_flow(flow).await {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::with_details(
+                "Failed to save flow",
+                e.to_string(),
+            )),
+        ));
+    }
+
+    let key = RecorderKey {
+        flow_id,
+        block_id: block_id.clone(),
+    };
+    if let Some(recorder) = RECORDER_REGISTRY.get(&key) {
+        info!("Recorder {}: trigger set to {:?}", block_id, req.trigger);
+        recorder.set_trigger(req.trigger).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::with_details("Failed to set trigger", e)),
+            )
+        })?;
+    }
+
+    current_status(&state, flow_id, &block_id).await.map(Json)
+}
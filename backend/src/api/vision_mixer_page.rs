@@ -87,6 +87,7 @@ pub async fn vision_mixer_page(
     let num_inputs = vm_props::parse_num_inputs(&vm_block.properties);
     let labels = vm_props::parse_input_labels(&vm_block.properties, num_inputs);
     let num_dsk_inputs = vm_props::parse_num_dsk_inputs(&vm_block.properties);
+    let num_usk = vm_props::parse_num_upstream_keyers(&vm_block.properties);
 
     // Get current state from live overlay state or fall back to defaults
     let overlay = overlay::get_overlay_state(block_id);
@@ -115,6 +116,14 @@ pub async fn vision_mixer_page(
                 .collect()
         })
         .unwrap_or_else(|| vec![true; num_dsk_inputs]);
+    let usk_states: Vec<bool> = overlay
+        .as_ref()
+        .map(|s| {
+            (0..s.usk_enabled.len())
+                .map(|k| s.usk_is_enabled(k))
+                .collect()
+        })
+        .unwrap_or_else(|| vec![false; num_usk]);
     let background_input: Option<usize> = overlay.as_ref().and_then(|s| s.background_input());
     let overlay_alpha = overlay.as_ref().map(|s| s.overlay_alpha()).unwrap_or(1.0);
 
@@ -131,6 +140,8 @@ pub async fn vision_mixer_page(
         "num_dsk_inputs": num_dsk_inputs,
         "ftb_active": ftb_active,
         "dsk_states": dsk_states,
+        "num_usk": num_usk,
+        "usk_states": usk_states,
         "background_input": background_input,
         "overlay_alpha": overlay_alpha,
     });
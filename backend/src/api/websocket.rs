@@ -6,10 +6,11 @@ use axum::response::Response;
 use futures::{sink::SinkExt, stream::StreamExt};
 use std::net::SocketAddr;
 use std::time::Duration;
+use strom_types::api::ClientMessage;
 use strom_types::StromEvent;
 use tokio::select;
 use tokio::time::interval;
-use tracing::{debug, error, info, trace, Instrument};
+use tracing::{debug, error, info, trace, warn, Instrument};
 
 use crate::state::AppState;
 
@@ -183,8 +184,9 @@ async fn handle_socket_inner(socket: WebSocket, state: AppState) {
                                 error!("Failed to send pong: {}", e);
                                 break;
                             }
+                        } else if let Ok(command) = serde_json::from_str::<ClientMessage>(&text) {
+                            handle_client_message(command);
                         }
-                        // For future: handle other client commands here
                     }
                     Some(Ok(_)) => {
                         debug!("Received other message type from client");
@@ -205,6 +207,37 @@ async fn handle_socket_inner(socket: WebSocket, state: AppState) {
     info!("WebSocket client disconnected");
 }
 
+/// Handle a command from the client. Cart player triggers are handled here
+/// rather than through REST to keep their latency low.
+fn handle_client_message(message: ClientMessage) {
+    use crate::blocks::builtin::cartplayer::{CartPlayerKey, CART_PLAYER_REGISTRY};
+
+    let result = match message {
+        ClientMessage::CartFire {
+            flow_id,
+            block_id,
+            slot,
+        } => CART_PLAYER_REGISTRY
+            .get(&CartPlayerKey { flow_id, block_id })
+            .ok_or_else(|| "Cart player not found".to_string())
+            .and_then(|player| player.fire(slot)),
+        ClientMessage::CartStop {
+            flow_id,
+            block_id,
+            slot,
+        } => CART_PLAYER_REGISTRY
+            .get(&CartPlayerKey { flow_id, block_id })
+            .ok_or_else(|| "Cart player not found".to_string())
+            .and_then(|player| player.stop(slot)),
+        ClientMessage::Subscribe { .. }
+        | ClientMessage::Unsubscribe { .. }
+        | ClientMessage::Ping => Ok(()),
+    };
+    if let Err(e) = result {
+        warn!("Client command failed: {}", e);
+    }
+}
+
 /// Send an event to the client as a JSON message, tracking bytes sent.
 async fn send_event(
     sender: &mut futures::stream::SplitSink<WebSocket, Message>,
@@ -7,24 +7,31 @@
 //! - Fan-out: one input channel can route to multiple outputs (via tee)
 //! - Mixing: multiple input channels can be summed to one output (via audiomixer)
 //!   Note: audiomixer is only used when multiple inputs route to the same output channel
+//! - Named routing presets stored with the block, recalled live as a salvo
 //!
 //! Pipeline structure (simple routing):
 //! ```text
-//! Input_N → identity_N → deinterleave_N → [tee] → queue → interleave_M → capssetter_M → queue_out_M → Output_M
+//! Input_N → identity_N → deinterleave_N → [tee] → queue → xp → interleave_M → capssetter_M → queue_out_M → Output_M
 //! ```
 //!
 //! Pipeline structure (when mixing needed):
 //! ```text
-//! Input_N → identity_N → deinterleave_N → [tee] → queue → audiomixer → interleave_M → capssetter_M → queue_out_M → Output_M
+//! Input_N → identity_N → deinterleave_N → [tee] → queue → xp → audiomixer → interleave_M → capssetter_M → queue_out_M → Output_M
 //! ```
 //!
+//! Every crosspoint (`xp`) used by the routing matrix or by any preset is a
+//! `volume` element that is either open (1.0) or closed (0.0). A salvo only
+//! moves these volumes, so a whole matrix switches in one step without
+//! relinking; routes that no preset uses are not built at all.
+//!
 //! The capssetter fixes channel-mask: 1ch=0x1, 2ch=0x3, 3+ch=0x0 (unpositioned)
 
+use crate::blocks::common::parse_json_property;
 use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
 use gstreamer as gst;
 use gstreamer::prelude::*;
-use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, Mutex};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use strom_types::audio_router::AudioRouting;
 use strom_types::{block::*, element::ElementPadRef, PropertyValue, *};
 use tracing::{error, info, warn};
 
@@ -43,6 +50,53 @@ struct RouteDest {
 /// Routing matrix: maps (input_idx, channel_idx) -> Vec<RouteDest>
 type RoutingMatrix = HashMap<(usize, usize), Vec<RouteDest>>;
 
+/// One route from an input channel to an output channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Crosspoint {
+    pub input: usize,
+    pub input_channel: usize,
+    pub output: usize,
+    pub output_channel: usize,
+}
+
+impl Crosspoint {
+    /// Name of the crosspoint volume element within the block.
+    pub fn element_name(&self) -> String {
+        format!(
+            "xp_i{}c{}_o{}c{}",
+            self.input, self.input_channel, self.output, self.output_channel
+        )
+    }
+
+    /// Parse a crosspoint from its element name (e.g. `xp_i0c1_o2c0`).
+    pub fn from_element_name(name: &str) -> Option<Self> {
+        let (source, dest) = name.strip_prefix("xp_")?.split_once('_')?;
+        let (input, input_channel) = parse_routing_key(source, 'i')?;
+        let (output, output_channel) = parse_routing_key(dest, 'o')?;
+        Some(Self {
+            input,
+            input_channel,
+            output,
+            output_channel,
+        })
+    }
+}
+
+/// Errors from routing preset and salvo operations.
+#[derive(Debug, thiserror::Error)]
+pub enum RoutingError {
+    #[error("Flow not found: {0}")]
+    FlowNotFound(FlowId),
+    #[error("Block {0} is not an audio router")]
+    NotRouter(String),
+    #[error("Preset not found: {0}")]
+    PresetNotFound(String),
+    #[error("Invalid routing: {0}")]
+    InvalidRouting(String),
+    #[error("Routing cannot be switched live: {0}")]
+    NotLive(String),
+}
+
 /// Audio Router block builder.
 pub struct AudioRouterBuilder;
 
@@ -96,27 +150,42 @@ impl BlockBuilder for AudioRouterBuilder {
         let output_channels: Vec<usize> = (0..num_outputs)
             .map(|i| parse_channels(properties, &format!("output_{}_channels", i), 2))
             .collect();
-        let routing_matrix = parse_routing_matrix(properties);
+        let active = crosspoints(&parse_routing_matrix(properties));
+
+        // Build the crosspoints of every preset too, so a salvo never relinks
+        let mut all_crosspoints = active.clone();
+        for routing in routing_presets(properties).values() {
+            all_crosspoints.extend(crosspoints(&routing_matrix_from(routing)));
+        }
+        all_crosspoints.retain(|xp| {
+            xp.input < num_inputs
+                && xp.input_channel < input_channels[xp.input]
+                && xp.output < num_outputs
+                && xp.output_channel < output_channels[xp.output]
+        });
 
         let total_input_channels: usize = input_channels.iter().sum();
         let total_output_channels: usize = output_channels.iter().sum();
 
         info!(
-            "AudioRouter config: {} inputs ({} total ch), {} outputs ({} total ch)",
-            num_inputs, total_input_channels, num_outputs, total_output_channels
+            "AudioRouter config: {} inputs ({} total ch), {} outputs ({} total ch), {} crosspoints ({} active)",
+            num_inputs,
+            total_input_channels,
+            num_outputs,
+            total_output_channels,
+            all_crosspoints.len(),
+            active.len()
         );
 
         // ========================================================================
         // Analyze routing to determine which output channels need audiomixer
-        // Only use audiomixer when multiple inputs route to the same output
+        // Only use audiomixer when multiple crosspoints feed the same output
         // ========================================================================
         let mut output_input_count: HashMap<(usize, usize), usize> = HashMap::new();
-        for destinations in routing_matrix.values() {
-            for dest in destinations {
-                *output_input_count
-                    .entry((dest.output_idx, dest.channel_idx))
-                    .or_insert(0) += 1;
-            }
+        for xp in &all_crosspoints {
+            *output_input_count
+                .entry((xp.output, xp.output_channel))
+                .or_insert(0) += 1;
         }
 
         // Outputs that need audiomixer (more than one input)
@@ -131,18 +200,11 @@ impl BlockBuilder for AudioRouterBuilder {
             outputs_needing_mixer.len()
         );
 
-        // Determine which output channels have NO routing at all (need silence)
-        let mut routed_outputs: HashSet<(usize, usize)> = HashSet::new();
-        for destinations in routing_matrix.values() {
-            for dest in destinations {
-                routed_outputs.insert((dest.output_idx, dest.channel_idx));
-            }
-        }
-
+        // Determine which output channels have NO crosspoint at all (need silence)
         let mut unrouted_outputs: Vec<(usize, usize)> = Vec::new();
         for (out_idx, &out_ch_count) in output_channels.iter().enumerate().take(num_outputs) {
             for out_ch in 0..out_ch_count {
-                if !routed_outputs.contains(&(out_idx, out_ch)) {
+                if !output_input_count.contains_key(&(out_idx, out_ch)) {
                     unrouted_outputs.push((out_idx, out_ch));
                 }
             }
@@ -162,17 +224,6 @@ impl BlockBuilder for AudioRouterBuilder {
         // Create OUTPUT side (audiomixers only where needed, and interleaves)
         // ========================================================================
 
-        // Store audiomixers for output channels that need mixing
-        // Key: (output_idx, channel_idx), Value: audiomixer element
-        let audiomixers: Arc<Mutex<HashMap<(usize, usize), gst::Element>>> =
-            Arc::new(Mutex::new(HashMap::new()));
-
-        // Store interleaves for each output stream
-        let interleaves: Arc<Mutex<Vec<gst::Element>>> = Arc::new(Mutex::new(Vec::new()));
-
-        // Track which output channels need mixer (shared with pad-added callback)
-        let outputs_needing_mixer = Arc::new(outputs_needing_mixer);
-
         for (out_idx, &out_ch_count) in output_channels.iter().enumerate().take(num_outputs) {
             // Create audiomixer ONLY for output channels that have multiple inputs
             for out_ch in 0..out_ch_count {
@@ -189,8 +240,7 @@ impl BlockBuilder for AudioRouterBuilder {
                         "Creating audiomixer for output {} channel {} (multiple inputs)",
                         out_idx, out_ch
                     );
-                    elements.push((mixer_id, mixer.clone()));
-                    audiomixers.lock().unwrap().insert((out_idx, out_ch), mixer);
+                    elements.push((mixer_id, mixer));
                 }
             }
 
@@ -212,8 +262,7 @@ impl BlockBuilder for AudioRouterBuilder {
                 })?;
             }
 
-            elements.push((interleave_id.clone(), interleave.clone()));
-            interleaves.lock().unwrap().push(interleave.clone());
+            elements.push((interleave_id.clone(), interleave));
 
             // Create capssetter to fix channel-mask for downstream elements
             // 1 channel: 0x1 (front center), 2 channels: 0x3 (front left + right), 3+: 0x0 (unpositioned)
@@ -367,6 +416,64 @@ impl BlockBuilder for AudioRouterBuilder {
             }
         }
 
+        // ========================================================================
+        // Create CROSSPOINTS (queue → volume → audiomixer or interleave)
+        // ========================================================================
+
+        // Route queues fed by each input channel, for the pad-added callbacks
+        let mut input_routes: HashMap<(usize, usize), Vec<String>> = HashMap::new();
+
+        for xp in &all_crosspoints {
+            let route_queue_id = format!(
+                "{}:queue_route_i{}c{}_o{}c{}",
+                instance_id, xp.input, xp.input_channel, xp.output, xp.output_channel
+            );
+            let route_queue = gst::ElementFactory::make("queue")
+                .name(&route_queue_id)
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("queue: {}", e)))?;
+
+            let xp_id = format!("{}:{}", instance_id, xp.element_name());
+            let gain = if active.contains(xp) { 1.0 } else { 0.0 };
+            let volume = gst::ElementFactory::make("volume")
+                .name(&xp_id)
+                .property("volume", gain)
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("volume: {}", e)))?;
+
+            elements.push((route_queue_id.clone(), route_queue));
+            elements.push((xp_id.clone(), volume));
+
+            // Link: route queue → crosspoint volume
+            internal_links.push((
+                ElementPadRef::pad(&route_queue_id, "src"),
+                ElementPadRef::pad(&xp_id, "sink"),
+            ));
+
+            // Link: crosspoint → audiomixer (mixing) or interleave sink_Y (direct)
+            if outputs_needing_mixer.contains(&(xp.output, xp.output_channel)) {
+                let mixer_id = format!(
+                    "{}:audiomixer_o{}c{}",
+                    instance_id, xp.output, xp.output_channel
+                );
+                internal_links.push((
+                    ElementPadRef::pad(&xp_id, "src"),
+                    ElementPadRef::pad(&mixer_id, "sink_%u"),
+                ));
+            } else {
+                let interleave_id = format!("{}:interleave_{}", instance_id, xp.output);
+                internal_links.push((
+                    ElementPadRef::pad(&xp_id, "src"),
+                    ElementPadRef::pad(&interleave_id, format!("sink_{}", xp.output_channel)),
+                ));
+            }
+
+            input_routes
+                .entry((xp.input, xp.input_channel))
+                .or_default()
+                .push(route_queue_id);
+        }
+
         // ========================================================================
         // Create INPUT side (identity pass-through → deinterleave)
         // ========================================================================
@@ -398,10 +505,7 @@ impl BlockBuilder for AudioRouterBuilder {
 
             // Setup pad-added callback for deinterleave
             // This is called when deinterleave creates dynamic src pads
-            let audiomixers_clone = audiomixers.clone();
-            let interleaves_clone = interleaves.clone();
-            let outputs_needing_mixer_clone = outputs_needing_mixer.clone();
-            let routing_matrix_clone = routing_matrix.clone();
+            let input_routes_clone = input_routes.clone();
             let instance_id_owned = instance_id.to_string();
             let current_in_idx = in_idx;
 
@@ -432,10 +536,10 @@ impl BlockBuilder for AudioRouterBuilder {
                     return;
                 };
 
-                // Look up routing for this input channel
+                // Look up crosspoints fed by this input channel
                 let key = (current_in_idx, channel_idx);
-                let destinations = match routing_matrix_clone.get(&key) {
-                    Some(dests) if !dests.is_empty() => dests.clone(),
+                let route_queues = match input_routes_clone.get(&key) {
+                    Some(queues) if !queues.is_empty() => queues,
                     _ => {
                         info!(
                             "No routing configured for input {} channel {} - pad will be unlinked",
@@ -445,9 +549,9 @@ impl BlockBuilder for AudioRouterBuilder {
                     }
                 };
 
-                // If this input routes to multiple outputs (fan-out), we need a tee
+                // If this input feeds multiple crosspoints (fan-out), we need a tee
                 // Otherwise, we can connect directly
-                let needs_tee = destinations.len() > 1;
+                let needs_tee = route_queues.len() > 1;
 
                 let tee = if needs_tee {
                     // Create tee for fan-out
@@ -496,42 +600,11 @@ impl BlockBuilder for AudioRouterBuilder {
                     None
                 };
 
-                let mixers = audiomixers_clone.lock().unwrap();
-                let interleaves = interleaves_clone.lock().unwrap();
-
-                for dest in destinations {
-                    let dest_key = (dest.output_idx, dest.channel_idx);
-                    let needs_mixer = outputs_needing_mixer_clone.contains(&dest_key);
-
-                    // Create routing queue
-                    let route_queue_id = format!(
-                        "{}:queue_route_i{}c{}_o{}c{}",
-                        instance_id_owned,
-                        current_in_idx,
-                        channel_idx,
-                        dest.output_idx,
-                        dest.channel_idx
-                    );
-                    let route_queue = match gst::ElementFactory::make("queue")
-                        .name(&route_queue_id)
-                        .build()
-                    {
-                        Ok(q) => q,
-                        Err(e) => {
-                            error!("Failed to create route queue: {}", e);
-                            continue;
-                        }
-                    };
-
-                    if bin.add(&route_queue).is_err() {
-                        error!("Failed to add route queue to bin");
-                        continue;
-                    }
-
-                    if route_queue.sync_state_with_parent().is_err() {
-                        error!("Failed to sync route queue state");
+                for route_queue_id in route_queues {
+                    let Some(route_queue) = bin.by_name(route_queue_id) else {
+                        error!("Route queue {} not found", route_queue_id);
                         continue;
-                    }
+                    };
 
                     // Get queue sink pad
                     let Some(queue_sink) = route_queue.static_pad("sink") else {
@@ -558,74 +631,10 @@ impl BlockBuilder for AudioRouterBuilder {
                         }
                     }
 
-                    // Get queue src pad
-                    let Some(queue_src) = route_queue.static_pad("src") else {
-                        error!("Route queue has no src pad");
-                        continue;
-                    };
-
-                    // Link queue to output (via audiomixer if mixing, otherwise direct to interleave)
-                    if needs_mixer {
-                        // Multiple inputs to this output → use audiomixer
-                        let Some(mixer) = mixers.get(&dest_key) else {
-                            error!(
-                                "No audiomixer found for output {} channel {}",
-                                dest.output_idx, dest.channel_idx
-                            );
-                            continue;
-                        };
-
-                        let Some(mixer_sink) = mixer.request_pad_simple("sink_%u") else {
-                            error!("Failed to request sink pad from audiomixer");
-                            continue;
-                        };
-
-                        if queue_src.link(&mixer_sink).is_err() {
-                            error!("Failed to link route queue to audiomixer");
-                            continue;
-                        }
-
-                        info!(
-                            "Routed input {} ch {} → audiomixer → output {} ch {}",
-                            current_in_idx, channel_idx, dest.output_idx, dest.channel_idx
-                        );
-                    } else {
-                        // Single input to this output → direct to interleave
-                        let Some(interleave) = interleaves.get(dest.output_idx) else {
-                            error!("No interleave found for output {}", dest.output_idx);
-                            continue;
-                        };
-
-                        let interleave_sink_name = format!("sink_{}", dest.channel_idx);
-                        // Find pad in pads list - request pads aren't accessible via static_pad()
-                        let interleave_sink = interleave
-                            .pads()
-                            .into_iter()
-                            .find(|p| p.name() == interleave_sink_name);
-                        let Some(interleave_sink) = interleave_sink else {
-                            error!(
-                                "Interleave {} has no pad {} (available: {:?})",
-                                dest.output_idx,
-                                interleave_sink_name,
-                                interleave
-                                    .pads()
-                                    .iter()
-                                    .map(|p| p.name().to_string())
-                                    .collect::<Vec<_>>()
-                            );
-                            continue;
-                        };
-
-                        if queue_src.link(&interleave_sink).is_err() {
-                            error!("Failed to link route queue to interleave");
-                            continue;
-                        }
-
-                        info!(
-                            "Routed input {} ch {} → output {} ch {} (direct)",
-                            current_in_idx, channel_idx, dest.output_idx, dest.channel_idx
-                        );
-                    }
+                    info!(
+                        "Routed input {} ch {} → {}",
+                        current_in_idx, channel_idx, route_queue_id
+                    );
                 }
             });
 
@@ -685,27 +694,33 @@ fn parse_channels(properties: &HashMap<String, PropertyValue>, key: &str, defaul
 /// Format: `{"i0c0": ["o0c0", "o1c0"], "i0c1": ["o0c1"]}`
 /// Where iXcY = input X channel Y, oXcY = output X channel Y
 fn parse_routing_matrix(properties: &HashMap<String, PropertyValue>) -> RoutingMatrix {
-    let mut matrix = RoutingMatrix::new();
-
     let json_str = match properties.get("routing_matrix") {
         Some(PropertyValue::String(s)) => s.clone(),
-        _ => return matrix,
+        _ => return RoutingMatrix::new(),
     };
 
     if json_str.is_empty() || json_str == "{}" {
-        return matrix;
+        return RoutingMatrix::new();
     }
 
     // Parse JSON
-    let parsed: Result<HashMap<String, Vec<String>>, _> = serde_json::from_str(&json_str);
-    let Ok(json_matrix) = parsed else {
+    let Ok(routing) = serde_json::from_str::<AudioRouting>(&json_str) else {
         warn!("Failed to parse routing matrix JSON: {}", json_str);
-        return matrix;
+        return RoutingMatrix::new();
     };
 
-    for (src_key, dest_list) in json_matrix {
+    let matrix = routing_matrix_from(&routing);
+    info!("Parsed routing matrix with {} entries", matrix.len());
+    matrix
+}
+
+/// Convert a routing in JSON form to a routing matrix, skipping invalid keys.
+fn routing_matrix_from(routing: &AudioRouting) -> RoutingMatrix {
+    let mut matrix = RoutingMatrix::new();
+
+    for (src_key, dest_list) in routing {
         // Parse source key (e.g., "i0c1" -> input 0, channel 1)
-        let Some((in_idx, in_ch)) = parse_routing_key(&src_key, 'i') else {
+        let Some((in_idx, in_ch)) = parse_routing_key(src_key, 'i') else {
             warn!("Invalid routing source key: {}", src_key);
             continue;
         };
@@ -713,7 +728,7 @@ fn parse_routing_matrix(properties: &HashMap<String, PropertyValue>) -> RoutingM
         let mut destinations = Vec::new();
         for dest_key in dest_list {
             // Parse destination key (e.g., "o1c0" -> output 1, channel 0)
-            let Some((out_idx, out_ch)) = parse_routing_key(&dest_key, 'o') else {
+            let Some((out_idx, out_ch)) = parse_routing_key(dest_key, 'o') else {
                 warn!("Invalid routing destination key: {}", dest_key);
                 continue;
             };
@@ -728,10 +743,124 @@ fn parse_routing_matrix(properties: &HashMap<String, PropertyValue>) -> RoutingM
         }
     }
 
-    info!("Parsed routing matrix with {} entries", matrix.len());
     matrix
 }
 
+/// All crosspoints of a routing matrix.
+fn crosspoints(matrix: &RoutingMatrix) -> BTreeSet<Crosspoint> {
+    matrix
+        .iter()
+        .flat_map(|(&(input, input_channel), dests)| {
+            dests.iter().map(move |dest| Crosspoint {
+                input,
+                input_channel,
+                output: dest.output_idx,
+                output_channel: dest.channel_idx,
+            })
+        })
+        .collect()
+}
+
+/// Routing in JSON form for a set of crosspoints.
+pub fn routing_from_crosspoints(crosspoints: &BTreeSet<Crosspoint>) -> AudioRouting {
+    let mut routing = AudioRouting::new();
+    for xp in crosspoints {
+        routing
+            .entry(format!("i{}c{}", xp.input, xp.input_channel))
+            .or_default()
+            .push(format!("o{}c{}", xp.output, xp.output_channel));
+    }
+    routing
+}
+
+/// Active routing of a block, normalized from its `routing_matrix` property.
+pub fn current_routing(properties: &HashMap<String, PropertyValue>) -> AudioRouting {
+    routing_from_crosspoints(&crosspoints(&parse_routing_matrix(properties)))
+}
+
+/// Routing presets stored in the `routing_presets` property.
+pub fn routing_presets(
+    properties: &HashMap<String, PropertyValue>,
+) -> BTreeMap<String, AudioRouting> {
+    parse_json_property(properties, "routing_presets")
+}
+
+/// Check a routing against the block's configured inputs and outputs.
+///
+/// Unlike the lenient parsing used when building, every key must be valid
+/// and in range. Returns the routing's crosspoints.
+pub fn validate_routing(
+    properties: &HashMap<String, PropertyValue>,
+    routing: &AudioRouting,
+) -> Result<BTreeSet<Crosspoint>, RoutingError> {
+    let num_inputs = parse_num_streams(properties, "num_inputs", 2);
+    let num_outputs = parse_num_streams(properties, "num_outputs", 2);
+    let check = |key: &str, prefix: char, streams: usize, channels_key: &str| {
+        let (stream, channel) = parse_routing_key(key, prefix)
+            .ok_or_else(|| RoutingError::InvalidRouting(format!("invalid key '{}'", key)))?;
+        if stream >= streams
+            || channel
+                >= parse_channels(
+                    properties,
+                    &format!("{}_{}_channels", channels_key, stream),
+                    2,
+                )
+        {
+            return Err(RoutingError::InvalidRouting(format!(
+                "'{}' is out of range",
+                key
+            )));
+        }
+        Ok((stream, channel))
+    };
+
+    let mut result = BTreeSet::new();
+    for (src_key, dest_list) in routing {
+        let (input, input_channel) = check(src_key, 'i', num_inputs, "input")?;
+        for dest_key in dest_list {
+            let (output, output_channel) = check(dest_key, 'o', num_outputs, "output")?;
+            result.insert(Crosspoint {
+                input,
+                input_channel,
+                output,
+                output_channel,
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Name of the preset whose routing equals the given crosspoints, if any.
+pub fn active_preset(
+    crosspoints_now: &BTreeSet<Crosspoint>,
+    presets: &BTreeMap<String, AudioRouting>,
+) -> Option<String> {
+    presets
+        .iter()
+        .find(|(_, routing)| crosspoints(&routing_matrix_from(routing)) == *crosspoints_now)
+        .map(|(name, _)| name.clone())
+}
+
+/// Crosspoint volumes for a salvo on a running router.
+///
+/// `built` are the crosspoints present in the pipeline. Returns `None` when
+/// the target needs a crosspoint that was not built, otherwise the gain of
+/// every built crosspoint.
+pub fn plan_salvo(
+    built: &BTreeSet<Crosspoint>,
+    target: &BTreeSet<Crosspoint>,
+) -> Option<Vec<(Crosspoint, f64)>> {
+    if !target.is_subset(built) {
+        return None;
+    }
+    Some(
+        built
+            .iter()
+            .map(|xp| (*xp, if target.contains(xp) { 1.0 } else { 0.0 }))
+            .collect(),
+    )
+}
+
 /// Parse a routing key like "i0c1" or "o2c3" into (stream_idx, channel_idx).
 fn parse_routing_key(key: &str, prefix: char) -> Option<(usize, usize)> {
     if !key.starts_with(prefix) {
@@ -843,6 +972,21 @@ fn audiorouter_definition() -> BlockDefinition {
         live: false,
     });
 
+    // Routing presets (JSON format), recalled with the salvo API
+    exposed_properties.push(ExposedProperty {
+        name: "routing_presets".to_string(),
+        label: "Routing Presets".to_string(),
+        description: "JSON map of named routing matrices: {\"name\": {\"i0c0\": [\"o0c0\"]}}. Crosspoints used by any preset are built at start so a salvo can switch to it live".to_string(),
+        property_type: PropertyType::Multiline,
+        default_value: Some(PropertyValue::String("{}".to_string())),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: "routing_presets".to_string(),
+            transform: None,
+        },
+        live: false,
+    });
+
     BlockDefinition {
         id: "builtin.audiorouter".to_string(),
         name: "Audio Router".to_string(),
@@ -892,3 +1036,104 @@ fn audiorouter_definition() -> BlockDefinition {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(routing: &str, presets: &str) -> HashMap<String, PropertyValue> {
+        HashMap::from([
+            (
+                "routing_matrix".to_string(),
+                PropertyValue::String(routing.to_string()),
+            ),
+            (
+                "routing_presets".to_string(),
+                PropertyValue::String(presets.to_string()),
+            ),
+        ])
+    }
+
+    fn xp(input: usize, input_channel: usize, output: usize, output_channel: usize) -> Crosspoint {
+        Crosspoint {
+            input,
+            input_channel,
+            output,
+            output_channel,
+        }
+    }
+
+    #[test]
+    fn test_crosspoint_element_name_roundtrip() {
+        let point = xp(1, 12, 0, 3);
+        assert_eq!(point.element_name(), "xp_i1c12_o0c3");
+        assert_eq!(Crosspoint::from_element_name("xp_i1c12_o0c3"), Some(point));
+        assert_eq!(
+            Crosspoint::from_element_name("queue_route_i1c12_o0c3"),
+            None
+        );
+        assert_eq!(Crosspoint::from_element_name("xp_o0c3_i1c12"), None);
+    }
+
+    #[test]
+    fn test_current_routing_and_active_preset() {
+        let properties = props(
+            r#"{"i0c1": ["o1c0", "o0c1"], "i0c0": ["o0c0"]}"#,
+            r#"{"swap": {"i0c0": ["o0c1"], "i0c1": ["o0c0"]}, "main": {"i0c0": ["o0c0"], "i0c1": ["o0c1", "o1c0"]}}"#,
+        );
+        let routing = current_routing(&properties);
+        assert_eq!(routing["i0c1"], vec!["o0c1", "o1c0"]);
+
+        let presets = routing_presets(&properties);
+        assert_eq!(presets.len(), 2);
+        let active = validate_routing(&properties, &routing).unwrap();
+        assert_eq!(active_preset(&active, &presets), Some("main".to_string()));
+        assert_eq!(active_preset(&BTreeSet::new(), &presets), None);
+    }
+
+    #[test]
+    fn test_validate_routing_rejects_bad_keys() {
+        let properties = props("{}", "{}");
+        let ok = AudioRouting::from([("i1c1".to_string(), vec!["o0c0".to_string()])]);
+        assert_eq!(
+            validate_routing(&properties, &ok).unwrap(),
+            BTreeSet::from([xp(1, 1, 0, 0)])
+        );
+
+        for (src, dest) in [
+            ("i2c0", "o0c0"),
+            ("i0c2", "o0c0"),
+            ("i0c0", "o0x0"),
+            ("x", "o0c0"),
+        ] {
+            let bad = AudioRouting::from([(src.to_string(), vec![dest.to_string()])]);
+            assert!(
+                matches!(
+                    validate_routing(&properties, &bad),
+                    Err(RoutingError::InvalidRouting(_))
+                ),
+                "{} -> {} should be rejected",
+                src,
+                dest
+            );
+        }
+    }
+
+    #[test]
+    fn test_plan_salvo() {
+        let built = BTreeSet::from([xp(0, 0, 0, 0), xp(0, 0, 0, 1), xp(1, 0, 0, 0)]);
+
+        let plan = plan_salvo(&built, &BTreeSet::from([xp(1, 0, 0, 0)])).unwrap();
+        assert_eq!(
+            plan,
+            vec![
+                (xp(0, 0, 0, 0), 0.0),
+                (xp(0, 0, 0, 1), 0.0),
+                (xp(1, 0, 0, 0), 1.0)
+            ]
+        );
+
+        // A route that was not built cannot be switched live
+        assert!(plan_salvo(&built, &BTreeSet::from([xp(1, 1, 0, 0)])).is_none());
+    }
+}
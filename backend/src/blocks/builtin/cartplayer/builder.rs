@@ -0,0 +1,210 @@
+//! Cart player block builder — creates the output appsrc and starts loading clips.
+
+use super::clip::audio_caps;
+use super::definition::DEFAULT_MAX_CLIP_SECS;
+use super::mixer::CartMixer;
+use super::parse_slots;
+use super::state::{CartPlayerKey, CartPlayerState, CART_PLAYER_REGISTRY};
+use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex, OnceLock};
+use strom_types::cartplayer::CartSlot;
+use strom_types::element::ElementPadRef;
+use strom_types::{FlowId, PropertyValue};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Cart player block builder.
+pub struct CartPlayerBuilder;
+
+impl BlockBuilder for CartPlayerBuilder {
+    fn build(
+        &self,
+        instance_id: &str,
+        properties: &HashMap<String, PropertyValue>,
+        ctx: &BlockBuildContext,
+    ) -> Result<BlockBuildResult, BlockBuildError> {
+        info!("Building Cart Player block instance: {}", instance_id);
+
+        let sample_rate = properties
+            .get("sample_rate")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => s.parse::<u32>().ok(),
+                PropertyValue::Int(i) => u32::try_from(*i).ok(),
+                _ => None,
+            })
+            .filter(|rate| *rate > 0)
+            .unwrap_or(48000);
+
+        let channels = properties
+            .get("channels")
+            .and_then(|v| match v {
+                PropertyValue::Int(i) => Some((*i).clamp(1, 8) as u32),
+                _ => None,
+            })
+            .unwrap_or(2);
+
+        let max_clip_secs = properties
+            .get("max_clip_secs")
+            .and_then(|v| match v {
+                PropertyValue::UInt(n) => Some(*n),
+                PropertyValue::Int(n) => u64::try_from(*n).ok(),
+                _ => None,
+            })
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_MAX_CLIP_SECS);
+
+        let flow_id: FlowId = properties
+            .get("_flow_id")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => Uuid::parse_str(s).ok(),
+                _ => None,
+            })
+            .unwrap_or_else(Uuid::nil);
+
+        let media_path: std::path::PathBuf = properties
+            .get("_media_path")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => Some(std::path::PathBuf::from(s)),
+                _ => None,
+            })
+            .unwrap_or_else(|| std::path::PathBuf::from("./media"));
+
+        let slots: Vec<CartSlot> = properties
+            .get("slots")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => parse_slots(s),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        info!(
+            "Cart Player {}: {} slots, {} Hz, {} channels",
+            instance_id,
+            slots.len(),
+            sample_rate,
+            channels
+        );
+
+        build_cart_player(
+            ctx,
+            instance_id,
+            flow_id,
+            sample_rate,
+            channels,
+            max_clip_secs,
+            slots,
+            media_path,
+        )
+    }
+}
+
+/// Build the cart player block.
+///
+/// A live appsrc paced by the pipeline clock pushes the mixed voices in
+/// [`CHUNK_MS`](super::state::CHUNK_MS) chunks; clips are decoded into
+/// memory by background threads.
+#[allow(clippy::too_many_arguments)]
+fn build_cart_player(
+    ctx: &BlockBuildContext,
+    instance_id: &str,
+    flow_id: FlowId,
+    sample_rate: u32,
+    channels: u32,
+    max_clip_secs: u64,
+    slots: Vec<CartSlot>,
+    media_path: std::path::PathBuf,
+) -> Result<BlockBuildResult, BlockBuildError> {
+    let appsrc_id = format!("{}:appsrc", instance_id);
+    let queue_id = format!("{}:queue", instance_id);
+    let audio_out_id = format!("{}:audio_out", instance_id);
+
+    let state = Arc::new(CartPlayerState {
+        block_id: instance_id.to_string(),
+        flow_id,
+        sample_rate,
+        channels,
+        max_clip_duration: std::time::Duration::from_secs(max_clip_secs),
+        media_path,
+        mixer: Mutex::new(CartMixer::new(channels as usize, sample_rate, Vec::new())),
+        loads: Mutex::new(Vec::new()),
+        generation: AtomicU64::new(0),
+        next_pts: Mutex::new(None),
+        reported: Mutex::new(Vec::new()),
+        events: OnceLock::new(),
+    });
+    state.set_slots(slots);
+
+    // One chunk is produced per need-data, after waiting for its end on the clock
+    let chunk_ns = super::state::CHUNK_MS * 1_000_000;
+    let appsrc = gst_app::AppSrc::builder()
+        .name(&appsrc_id)
+        .caps(&audio_caps(sample_rate, channels))
+        .format(gst::Format::Time)
+        .is_live(true)
+        .automatic_eos(false)
+        .min_latency(chunk_ns as i64)
+        .max_latency(chunk_ns as i64)
+        .build();
+
+    let state_weak = Arc::downgrade(&state);
+    appsrc.set_callbacks(
+        gst_app::AppSrcCallbacks::builder()
+            .need_data(move |appsrc, _length| {
+                let Some(state) = state_weak.upgrade() else {
+                    return;
+                };
+                if let Err(e) = state.push_chunk(appsrc) {
+                    debug!("Cart Player {}: Chunk not pushed: {:?}", state.block_id, e);
+                }
+            })
+            .build(),
+    );
+
+    let queue = gst::ElementFactory::make("queue")
+        .name(&queue_id)
+        .build()
+        .map_err(|e| BlockBuildError::ElementCreation(format!("queue: {}", e)))?;
+
+    let audio_out = gst::ElementFactory::make("identity")
+        .name(&audio_out_id)
+        .build()
+        .map_err(|e| BlockBuildError::ElementCreation(format!("audio_out: {}", e)))?;
+
+    CART_PLAYER_REGISTRY.register(
+        CartPlayerKey {
+            flow_id,
+            block_id: instance_id.to_string(),
+        },
+        Arc::clone(&state),
+    );
+
+    // Slot playing changes are broadcast once the pipeline runs
+    ctx.register_element_setup(Box::new(move |_flow_id, events| {
+        let _ = state.events.set(events);
+    }));
+
+    Ok(BlockBuildResult {
+        elements: vec![
+            (appsrc_id.clone(), appsrc.upcast()),
+            (queue_id.clone(), queue),
+            (audio_out_id.clone(), audio_out),
+        ],
+        internal_links: vec![
+            (
+                ElementPadRef::pad(&appsrc_id, "src"),
+                ElementPadRef::pad(&queue_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&queue_id, "src"),
+                ElementPadRef::pad(&audio_out_id, "sink"),
+            ),
+        ],
+        bus_message_handler: None,
+        pad_properties: HashMap::new(),
+    })
+}
@@ -0,0 +1,155 @@
+//! Clip loading: files decoded into memory at the output format.
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Longest a clip may take to decode.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Output caps of the cart player (interleaved F32LE).
+pub fn audio_caps(sample_rate: u32, channels: u32) -> gst::Caps {
+    gst::Caps::builder("audio/x-raw")
+        .field("format", "F32LE")
+        .field("layout", "interleaved")
+        .field("rate", sample_rate as i32)
+        .field("channels", channels as i32)
+        .build()
+}
+
+/// Decode the audio of a file into interleaved samples at the output format.
+///
+/// Only audio is decoded; other streams of the file are left undecoded.
+/// Clips longer than `max_duration` are rejected.
+pub fn decode_clip(
+    uri: &str,
+    sample_rate: u32,
+    channels: u32,
+    max_duration: Duration,
+) -> Result<Vec<f32>, String> {
+    let pipeline = gst::Pipeline::new();
+    let make = |factory: &str| {
+        gst::ElementFactory::make(factory)
+            .build()
+            .map_err(|e| format!("{}: {}", factory, e))
+    };
+    let source = make("uridecodebin")?;
+    source.set_property("uri", uri);
+    // Stop at raw audio and don't plug decoders for anything else
+    source.set_property("caps", gst::Caps::new_empty_simple("audio/x-raw"));
+    source.set_property("expose-all-streams", false);
+    let convert = make("audioconvert")?;
+    let resample = make("audioresample")?;
+    let appsink = gst_app::AppSink::builder()
+        .caps(&audio_caps(sample_rate, channels))
+        .sync(false)
+        .build();
+
+    pipeline
+        .add_many([&source, &convert, &resample, appsink.upcast_ref()])
+        .map_err(|e| format!("add elements: {}", e))?;
+    gst::Element::link_many([&convert, &resample, appsink.upcast_ref()])
+        .map_err(|e| format!("link elements: {}", e))?;
+
+    let max_samples =
+        (max_duration.as_secs_f64() * sample_rate as f64) as usize * channels as usize;
+    let too_long = Arc::new(AtomicBool::new(false));
+    let too_long_for_sink = Arc::clone(&too_long);
+    let samples = Arc::new(Mutex::new(Vec::new()));
+    let samples_for_sink = Arc::clone(&samples);
+    appsink.set_callbacks(
+        gst_app::AppSinkCallbacks::builder()
+            .new_sample(move |sink| {
+                let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
+                let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+                if let Ok(mut samples) = samples_for_sink.lock() {
+                    samples.extend(
+                        map.chunks_exact(4)
+                            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                    );
+                    // Stop decoding as soon as the clip is known to be too long
+                    if samples.len() > max_samples {
+                        too_long_for_sink.store(true, Ordering::SeqCst);
+                        return Err(gst::FlowError::Error);
+                    }
+                }
+                Ok(gst::FlowSuccess::Ok)
+            })
+            .build(),
+    );
+
+    // First audio stream into the converter, further ones into a fakesink
+    let pipeline_weak = pipeline.downgrade();
+    let convert_sink = convert
+        .static_pad("sink")
+        .ok_or_else(|| "audioconvert has no sink pad".to_string())?;
+    source.connect_pad_added(move |_src, pad| {
+        let is_audio = pad
+            .current_caps()
+            .unwrap_or_else(|| pad.query_caps(None))
+            .structure(0)
+            .is_some_and(|s| s.name().starts_with("audio/"));
+        if is_audio && !convert_sink.is_linked() {
+            if let Err(e) = pad.link(&convert_sink) {
+                tracing::warn!("Cart player: failed to link audio stream: {:?}", e);
+            }
+            return;
+        }
+        let Some(pipeline) = pipeline_weak.upgrade() else {
+            return;
+        };
+        let Ok(fakesink) = gst::ElementFactory::make("fakesink")
+            .property("sync", false)
+            .property("async", false)
+            .build()
+        else {
+            return;
+        };
+        if pipeline.add(&fakesink).is_ok() {
+            let _ = fakesink.sync_state_with_parent();
+            if let Some(sink_pad) = fakesink.static_pad("sink") {
+                let _ = pad.link(&sink_pad);
+            }
+        }
+    });
+
+    let result = run_to_eos(&pipeline);
+    let _ = pipeline.set_state(gst::State::Null);
+    if too_long.load(Ordering::SeqCst) {
+        return Err(format!("clip longer than {}s", max_duration.as_secs()));
+    }
+    result?;
+
+    let samples = std::mem::take(&mut *samples.lock().map_err(|e| e.to_string())?);
+    if samples.is_empty() {
+        return Err("no audio in file".to_string());
+    }
+    Ok(samples)
+}
+
+/// Play the loading pipeline until EOS or an error.
+fn run_to_eos(pipeline: &gst::Pipeline) -> Result<(), String> {
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "pipeline has no bus".to_string())?;
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|e| format!("failed to start decoding: {:?}", e))?;
+
+    let message = bus.timed_pop_filtered(
+        gst::ClockTime::from_nseconds(LOAD_TIMEOUT.as_nanos() as u64),
+        &[gst::MessageType::Eos, gst::MessageType::Error],
+    );
+    match message.as_ref().map(|m| m.view()) {
+        Some(gst::MessageView::Eos(_)) => Ok(()),
+        Some(gst::MessageView::Error(err)) => Err(err.error().to_string()),
+        _ => Err(format!(
+            "decoding took longer than {}s",
+            LOAD_TIMEOUT.as_secs()
+        )),
+    }
+}
@@ -0,0 +1,98 @@
+//! Block definition (metadata) for the cart player block.
+
+use strom_types::block::*;
+use strom_types::{MediaType, PropertyValue};
+
+/// Default longest clip, in seconds.
+pub const DEFAULT_MAX_CLIP_SECS: u64 = 300;
+
+/// Get metadata for Cart Player blocks (for UI/API).
+pub fn get_blocks() -> Vec<BlockDefinition> {
+    vec![cart_player_definition()]
+}
+
+/// Get Cart Player block definition (metadata only).
+pub fn cart_player_definition() -> BlockDefinition {
+    BlockDefinition {
+        id: "builtin.cart_player".to_string(),
+        name: "Cart Player".to_string(),
+        description: "Jingle/cart machine: slots of clips preloaded into memory, fired instantly through the API and mixed into one audio output. Only the audio of each file is played; video streams are ignored.".to_string(),
+        category: "Inputs".to_string(),
+        exposed_properties: vec![
+            ExposedProperty {
+                name: "sample_rate".to_string(),
+                label: "Sample Rate".to_string(),
+                description: "Output sample rate. Clips are resampled to it when loaded."
+                    .to_string(),
+                property_type: PropertyType::Enum {
+                    values: vec![
+                        EnumValue {
+                            value: "44100".to_string(),
+                            label: Some("44.1 kHz".to_string()),
+                        },
+                        EnumValue {
+                            value: "48000".to_string(),
+                            label: Some("48 kHz".to_string()),
+                        },
+                        EnumValue {
+                            value: "96000".to_string(),
+                            label: Some("96 kHz".to_string()),
+                        },
+                    ],
+                },
+                default_value: Some(PropertyValue::String("48000".to_string())),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "sample_rate".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "channels".to_string(),
+                label: "Channels".to_string(),
+                description: "Output channels. Clips are up- or downmixed to it when loaded."
+                    .to_string(),
+                property_type: PropertyType::Int,
+                default_value: Some(PropertyValue::Int(2)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "channels".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "max_clip_secs".to_string(),
+                label: "Max Clip Length (s)".to_string(),
+                description: "Longest clip that is loaded. Clips are held in memory, longer ones are rejected."
+                    .to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(DEFAULT_MAX_CLIP_SECS)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "max_clip_secs".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+        ],
+        external_pads: ExternalPads {
+            inputs: vec![],
+            outputs: vec![ExternalPad {
+                label: None,
+                name: "audio_out".to_string(),
+                media_type: MediaType::Audio,
+                internal_element_id: "audio_out".to_string(),
+                internal_pad_name: "src".to_string(),
+            }],
+        },
+        built_in: true,
+        ui_metadata: Some(BlockUIMetadata {
+            icon: None,
+            width: Some(2.5),
+            height: Some(1.5),
+            ..Default::default()
+        }),
+    }
+}
@@ -0,0 +1,177 @@
+//! Voice mixer of the cart player: clips in memory, played and summed sample
+//! by sample into the output chunks.
+
+use std::sync::Arc;
+use strom_types::cartplayer::{CartRetrigger, CartSlot};
+
+/// Length of the fade when a voice is stopped, to avoid clicks.
+pub const STOP_FADE_MS: u64 = 10;
+
+/// Voices playing at once; the oldest is dropped beyond this.
+const MAX_VOICES: usize = 32;
+
+/// A clip being played.
+struct Voice {
+    slot: usize,
+    /// Next frame to play
+    frame: usize,
+    gain: f32,
+    looping: bool,
+    /// Frames left of the stop fade, if stopping
+    fade: Option<usize>,
+}
+
+/// Slot clips and the voices playing them.
+pub struct CartMixer {
+    channels: usize,
+    fade_frames: usize,
+    slots: Vec<CartSlot>,
+    /// Interleaved samples of each slot's clip, once loaded
+    clips: Vec<Option<Arc<[f32]>>>,
+    voices: Vec<Voice>,
+}
+
+impl CartMixer {
+    pub fn new(channels: usize, sample_rate: u32, slots: Vec<CartSlot>) -> Self {
+        let mut mixer = Self {
+            channels: channels.max(1),
+            fade_frames: (sample_rate as u64 * STOP_FADE_MS / 1000).max(1) as usize,
+            slots: Vec::new(),
+            clips: Vec::new(),
+            voices: Vec::new(),
+        };
+        mixer.set_slots(slots);
+        mixer
+    }
+
+    /// Replace the slots. Voices are cut and clips must be loaded again.
+    pub fn set_slots(&mut self, slots: Vec<CartSlot>) {
+        self.voices.clear();
+        self.clips = vec![None; slots.len()];
+        self.slots = slots;
+    }
+
+    pub fn slots(&self) -> &[CartSlot] {
+        &self.slots
+    }
+
+    /// Set the interleaved samples of a slot's clip.
+    pub fn set_clip(&mut self, slot: usize, samples: Arc<[f32]>) {
+        if let Some(clip) = self.clips.get_mut(slot) {
+            *clip = Some(samples);
+        }
+    }
+
+    /// Fire a slot: stop its choke group and play the clip, or apply its
+    /// retrigger mode if it is playing.
+    pub fn fire(&mut self, slot: usize) -> Result<(), String> {
+        let settings = self
+            .slots
+            .get(slot)
+            .ok_or_else(|| format!("No slot {}", slot))?;
+        if self.clips[slot].is_none() {
+            return Err(format!("Slot {} has no clip loaded", slot));
+        }
+        let gain = 10f32.powf(settings.gain_db as f32 / 20.0);
+        let looping = settings.looping;
+        let retrigger = settings.retrigger;
+
+        if let Some(group) = settings.choke_group.clone() {
+            let choked: Vec<usize> = (0..self.slots.len())
+                .filter(|&i| i != slot && self.slots[i].choke_group.as_ref() == Some(&group))
+                .collect();
+            for i in choked {
+                self.stop(i);
+            }
+        }
+
+        if self.voices(slot) > 0 {
+            match retrigger {
+                CartRetrigger::Restart => self.stop(slot),
+                CartRetrigger::Overlap => {}
+                CartRetrigger::Stop => {
+                    self.stop(slot);
+                    return Ok(());
+                }
+            }
+        }
+
+        if self.voices.len() >= MAX_VOICES {
+            self.voices.remove(0);
+        }
+        self.voices.push(Voice {
+            slot,
+            frame: 0,
+            gain,
+            looping,
+            fade: None,
+        });
+        Ok(())
+    }
+
+    /// Fade out the voices of a slot.
+    pub fn stop(&mut self, slot: usize) {
+        for voice in self.voices.iter_mut().filter(|v| v.slot == slot) {
+            if voice.fade.is_none() {
+                voice.fade = Some(self.fade_frames);
+            }
+        }
+    }
+
+    /// Fade out all voices.
+    pub fn stop_all(&mut self) {
+        for slot in 0..self.slots.len() {
+            self.stop(slot);
+        }
+    }
+
+    /// Voices of a slot playing, not counting those fading out after a stop.
+    pub fn voices(&self, slot: usize) -> usize {
+        self.voices
+            .iter()
+            .filter(|v| v.slot == slot && v.fade.is_none())
+            .count()
+    }
+
+    /// Mix the next chunk of interleaved samples into `out`, overwriting it.
+    pub fn mix(&mut self, out: &mut [f32]) {
+        out.fill(0.0);
+        let channels = self.channels;
+        let fade_frames = self.fade_frames as f32;
+        let clips = &self.clips;
+
+        self.voices.retain_mut(|voice| {
+            let Some(clip) = clips.get(voice.slot).and_then(|c| c.as_ref()) else {
+                return false;
+            };
+            let frames = clip.len() / channels;
+            if frames == 0 {
+                return false;
+            }
+            for out_frame in out.chunks_exact_mut(channels) {
+                if voice.frame >= frames {
+                    if !voice.looping {
+                        return false;
+                    }
+                    voice.frame = 0;
+                }
+                let gain = match voice.fade.as_mut() {
+                    Some(0) => return false,
+                    Some(left) => {
+                        *left -= 1;
+                        voice.gain * (*left as f32 / fade_frames)
+                    }
+                    None => voice.gain,
+                };
+                let start = voice.frame * channels;
+                for (sample, clip_sample) in
+                    out_frame.iter_mut().zip(&clip[start..start + channels])
+                {
+                    *sample += clip_sample * gain;
+                }
+                voice.frame += 1;
+            }
+            voice.frame < frames || voice.looping
+        });
+    }
+}
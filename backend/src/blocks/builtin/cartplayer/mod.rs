@@ -0,0 +1,141 @@
+//! Cart player block for instantly triggered jingles, stings and idents.
+//!
+//! Each slot's file is decoded into memory at the output format when the
+//! block is built (or the slots change). Firing a slot adds a voice to the
+//! mixer, which sums all voices into 10 ms chunks pushed by a live appsrc
+//! paced by the pipeline clock, so a fired clip is heard within a chunk.
+//!
+//! Slots have a retrigger mode (restart, overlap or stop) and an optional
+//! choke group: firing a slot fades out the other slots of its group.
+//! Slot playing changes are broadcast as `CartSlotPlaying` events.
+//!
+//! The block has a single audio output. Clips may be audio+video files,
+//! but only their first audio stream is decoded and played. Clips are held
+//! in memory, so those longer than `max_clip_secs` are rejected.
+
+mod builder;
+mod clip;
+mod definition;
+mod mixer;
+mod state;
+
+pub use builder::CartPlayerBuilder;
+pub use definition::get_blocks;
+pub use state::{CartPlayerKey, CartPlayerState, CART_PLAYER_REGISTRY};
+
+use strom_types::cartplayer::CartSlot;
+
+/// Parse the slots stored in the `slots` block property.
+pub fn parse_slots(json: &str) -> Option<Vec<CartSlot>> {
+    serde_json::from_str(json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mixer::CartMixer;
+    use super::*;
+    use std::sync::Arc;
+    use strom_types::cartplayer::CartRetrigger;
+
+    const RATE: u32 = 1000;
+
+    fn slot(retrigger: CartRetrigger, choke_group: Option<&str>) -> CartSlot {
+        CartSlot {
+            uri: "clip.wav".to_string(),
+            retrigger,
+            choke_group: choke_group.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    /// Mono mixer with a clip of `frames` samples of 0.5 in every slot.
+    fn mixer(slots: Vec<CartSlot>, frames: usize) -> CartMixer {
+        let count = slots.len();
+        let mut mixer = CartMixer::new(1, RATE, slots);
+        for i in 0..count {
+            mixer.set_clip(i, Arc::from(vec![0.5f32; frames]));
+        }
+        mixer
+    }
+
+    #[test]
+    fn test_parse_slots() {
+        let slots = parse_slots(r#"[{"uri":"a.wav","choke_group":"beds"},{}]"#).unwrap();
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].choke_group.as_deref(), Some("beds"));
+        assert_eq!(slots[0].retrigger, CartRetrigger::Restart);
+        assert!(slots[1].uri.is_empty());
+        assert!(parse_slots("not json").is_none());
+    }
+
+    #[test]
+    fn test_cart_mixer_plays_clip_to_end() {
+        let mut mixer = mixer(vec![slot(CartRetrigger::Restart, None)], 15);
+        assert!(mixer.fire(1).is_err());
+        mixer.fire(0).unwrap();
+        assert_eq!(mixer.voices(0), 1);
+
+        let mut out = [0.0f32; 10];
+        mixer.mix(&mut out);
+        assert!(out.iter().all(|s| *s == 0.5));
+        mixer.mix(&mut out);
+        assert_eq!(out[4], 0.5);
+        assert_eq!(out[5], 0.0);
+        assert_eq!(mixer.voices(0), 0);
+    }
+
+    #[test]
+    fn test_cart_mixer_retrigger_modes() {
+        let mut mixer = mixer(
+            vec![
+                slot(CartRetrigger::Overlap, None),
+                slot(CartRetrigger::Stop, None),
+                slot(CartRetrigger::Restart, None),
+            ],
+            100,
+        );
+        mixer.fire(0).unwrap();
+        mixer.fire(0).unwrap();
+        assert_eq!(mixer.voices(0), 2);
+        let mut out = [0.0f32; 1];
+        mixer.mix(&mut out);
+        assert_eq!(out[0], 1.0);
+
+        mixer.fire(1).unwrap();
+        mixer.fire(1).unwrap();
+        assert_eq!(mixer.voices(1), 0);
+
+        mixer.fire(2).unwrap();
+        mixer.fire(2).unwrap();
+        assert_eq!(mixer.voices(2), 1);
+    }
+
+    #[test]
+    fn test_cart_mixer_choke_group_and_stop_fade() {
+        let mut mixer = mixer(
+            vec![
+                slot(CartRetrigger::Restart, Some("beds")),
+                slot(CartRetrigger::Restart, Some("beds")),
+                slot(CartRetrigger::Restart, None),
+            ],
+            1000,
+        );
+        mixer.fire(0).unwrap();
+        mixer.fire(2).unwrap();
+        mixer.fire(1).unwrap();
+        assert_eq!(mixer.voices(0), 0);
+        assert_eq!(mixer.voices(1), 1);
+        assert_eq!(mixer.voices(2), 1);
+
+        // Slot 0 fades out over 10 ms (10 samples at 1 kHz)
+        let mut out = [0.0f32; 20];
+        mixer.mix(&mut out);
+        assert!(out[0] > 1.0 && out[0] < 1.5);
+        assert_eq!(out[15], 1.0);
+
+        mixer.stop_all();
+        mixer.mix(&mut out);
+        mixer.mix(&mut out);
+        assert!(out.iter().all(|s| *s == 0.0));
+    }
+}
@@ -0,0 +1,340 @@
+//! Cart player runtime state, global registry and clip loading.
+
+use super::clip::decode_clip;
+use super::mixer::CartMixer;
+use crate::blocks::builtin::mediaplayer::normalize_uri;
+use crate::events::EventBroadcaster;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, RwLock};
+use strom_types::cartplayer::{CartSlot, CartSlotState, CartSlotStatus};
+use strom_types::{FlowId, StromEvent};
+use tracing::{info, warn};
+
+/// Length of an output chunk. Fired clips are heard within one chunk plus
+/// the downstream latency.
+pub const CHUNK_MS: u64 = 10;
+
+/// Output falling further behind the clock than this skips ahead.
+const MAX_LAG_NS: u64 = 100_000_000;
+
+/// Global registry of cart player instances for API access.
+pub static CART_PLAYER_REGISTRY: LazyLock<CartPlayerRegistry> =
+    LazyLock::new(CartPlayerRegistry::new);
+
+/// Registry key for looking up cart player instances.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CartPlayerKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// Loading state of a slot's clip.
+#[derive(Debug, Clone)]
+pub struct SlotLoad {
+    pub status: CartSlotStatus,
+    pub error: Option<String>,
+    pub duration_ns: u64,
+}
+
+impl SlotLoad {
+    fn new(slot: &CartSlot) -> Self {
+        Self {
+            status: if slot.uri.is_empty() {
+                CartSlotStatus::Empty
+            } else {
+                CartSlotStatus::Loading
+            },
+            error: None,
+            duration_ns: 0,
+        }
+    }
+}
+
+/// Runtime state for a cart player instance.
+pub struct CartPlayerState {
+    /// Block ID for event broadcasting
+    pub block_id: String,
+    /// Flow ID for event broadcasting
+    pub flow_id: FlowId,
+    pub sample_rate: u32,
+    pub channels: u32,
+    /// Longest clip that is loaded
+    pub max_clip_duration: std::time::Duration,
+    /// Configured media files directory (for resolving relative slot paths)
+    pub media_path: std::path::PathBuf,
+    /// Slot clips and playing voices
+    pub mixer: Mutex<CartMixer>,
+    /// Loading state of each slot
+    pub loads: Mutex<Vec<SlotLoad>>,
+    /// Bumped when the slots change, so loads of replaced slots are dropped
+    pub generation: AtomicU64,
+    /// Running time of the next output chunk
+    pub next_pts: Mutex<Option<u64>>,
+    /// Voices per slot last broadcast
+    pub reported: Mutex<Vec<usize>>,
+    /// Event broadcaster, set when the main pipeline starts
+    pub events: OnceLock<EventBroadcaster>,
+}
+
+impl CartPlayerState {
+    /// Replace the slots and load their clips in the background.
+    pub fn set_slots(self: &Arc<Self>, slots: Vec<CartSlot>) {
+        if let Ok(mut loads) = self.loads.lock() {
+            *loads = slots.iter().map(SlotLoad::new).collect();
+        }
+        if let Ok(mut reported) = self.reported.lock() {
+            // Voices are cut: report playing slots as stopped on the next chunk
+            reported.resize(slots.len(), 0);
+        }
+        if let Ok(mut mixer) = self.mixer.lock() {
+            mixer.set_slots(slots);
+        }
+        self.load_clips();
+    }
+
+    /// Decode the clips of all assigned slots into memory, one thread per clip.
+    pub fn load_clips(self: &Arc<Self>) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let slots = match self.mixer.lock() {
+            Ok(mixer) => mixer.slots().to_vec(),
+            Err(_) => return,
+        };
+
+        for (index, slot) in slots.into_iter().enumerate() {
+            if slot.uri.is_empty() {
+                continue;
+            }
+            let state = Arc::clone(self);
+            let uri = normalize_uri(&slot.uri, &self.media_path);
+            let spawned = std::thread::Builder::new()
+                .name(format!("cart-load-{}", index))
+                .spawn(move || {
+                    let result = decode_clip(
+                        &uri,
+                        state.sample_rate,
+                        state.channels,
+                        state.max_clip_duration,
+                    );
+                    state.finish_load(generation, index, &uri, result);
+                });
+            if let Err(e) = spawned {
+                warn!(
+                    "Cart player {}: Failed to spawn loader: {}",
+                    self.block_id, e
+                );
+            }
+        }
+    }
+
+    /// Store a decoded clip, unless the slots changed meanwhile.
+    fn finish_load(
+        &self,
+        generation: u64,
+        index: usize,
+        uri: &str,
+        result: Result<Vec<f32>, String>,
+    ) {
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let Ok(mut loads) = self.loads.lock() else {
+            return;
+        };
+        let Some(load) = loads.get_mut(index) else {
+            return;
+        };
+        match result {
+            Ok(samples) => {
+                let frames = samples.len() as u64 / self.channels as u64;
+                load.duration_ns = frames * 1_000_000_000 / self.sample_rate as u64;
+                load.status = CartSlotStatus::Ready;
+                load.error = None;
+                info!(
+                    "Cart player {}: Slot {} loaded {} ({:.1}s, {} KiB)",
+                    self.block_id,
+                    index,
+                    uri,
+                    load.duration_ns as f64 / 1e9,
+                    samples.len() * 4 / 1024
+                );
+                if let Ok(mut mixer) = self.mixer.lock() {
+                    mixer.set_clip(index, samples.into());
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "Cart player {}: Slot {} failed to load {}: {}",
+                    self.block_id, index, uri, e
+                );
+                load.status = CartSlotStatus::Error;
+                load.error = Some(e);
+            }
+        }
+    }
+
+    /// Fire a slot.
+    pub fn fire(&self, slot: usize) -> Result<(), String> {
+        self.mixer.lock().map_err(|e| e.to_string())?.fire(slot)
+    }
+
+    /// Stop a slot, or all slots.
+    pub fn stop(&self, slot: Option<usize>) -> Result<(), String> {
+        let mut mixer = self.mixer.lock().map_err(|e| e.to_string())?;
+        match slot {
+            Some(slot) if slot >= mixer.slots().len() => Err(format!("No slot {}", slot)),
+            Some(slot) => {
+                mixer.stop(slot);
+                Ok(())
+            }
+            None => {
+                mixer.stop_all();
+                Ok(())
+            }
+        }
+    }
+
+    /// Settings, loading state and voices of every slot.
+    pub fn slot_states(&self) -> Vec<CartSlotState> {
+        let loads = self.loads.lock().map(|l| l.clone()).unwrap_or_default();
+        let Ok(mixer) = self.mixer.lock() else {
+            return Vec::new();
+        };
+        mixer
+            .slots()
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                let load = loads
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| SlotLoad::new(slot));
+                CartSlotState {
+                    index,
+                    slot: slot.clone(),
+                    status: load.status,
+                    error: load.error,
+                    duration_ns: load.duration_ns,
+                    voices: mixer.voices(index),
+                }
+            })
+            .collect()
+    }
+
+    /// Samples per output chunk.
+    fn chunk_frames(&self) -> usize {
+        (self.sample_rate as u64 * CHUNK_MS / 1000) as usize
+    }
+
+    /// Mix and push the next output chunk, once the clock reaches its end
+    /// (called from the appsrc's need-data, like a live capture source).
+    pub fn push_chunk(&self, appsrc: &gst_app::AppSrc) -> Result<(), gst::FlowError> {
+        let (Some(clock), Some(base_time)) = (appsrc.clock(), appsrc.base_time()) else {
+            return Err(gst::FlowError::Flushing);
+        };
+        let frames = self.chunk_frames();
+        let duration_ns = frames as u64 * 1_000_000_000 / self.sample_rate as u64;
+        let now = clock.time().saturating_sub(base_time).nseconds();
+
+        let pts = {
+            let mut next_pts = self.next_pts.lock().map_err(|_| gst::FlowError::Error)?;
+            let pts = match *next_pts {
+                Some(pts) if pts + MAX_LAG_NS >= now => pts,
+                _ => now,
+            };
+            *next_pts = Some(pts + duration_ns);
+            pts
+        };
+
+        let _ = clock
+            .new_single_shot_id(base_time + gst::ClockTime::from_nseconds(pts + duration_ns))
+            .wait();
+
+        let mut samples = vec![0f32; frames * self.channels as usize];
+        self.mixer
+            .lock()
+            .map_err(|_| gst::FlowError::Error)?
+            .mix(&mut samples);
+
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut buffer = gst::Buffer::from_mut_slice(bytes);
+        if let Some(buffer) = buffer.get_mut() {
+            buffer.set_pts(gst::ClockTime::from_nseconds(pts));
+            buffer.set_duration(gst::ClockTime::from_nseconds(duration_ns));
+        }
+        appsrc.push_buffer(buffer)?;
+
+        self.report_voices();
+        Ok(())
+    }
+
+    /// Broadcast the slots that started or stopped playing.
+    fn report_voices(&self) {
+        let Some(events) = self.events.get() else {
+            return;
+        };
+        let (Ok(mixer), Ok(mut reported)) = (self.mixer.lock(), self.reported.lock()) else {
+            return;
+        };
+        reported.resize(mixer.slots().len(), 0);
+        for (slot, last) in reported.iter_mut().enumerate() {
+            let voices = mixer.voices(slot);
+            if voices != *last {
+                *last = voices;
+                events.broadcast(StromEvent::CartSlotPlaying {
+                    flow_id: self.flow_id,
+                    block_id: self.block_id.clone(),
+                    slot,
+                    voices,
+                });
+            }
+        }
+    }
+}
+
+/// Thread-safe registry of active cart player instances.
+pub struct CartPlayerRegistry {
+    players: RwLock<HashMap<CartPlayerKey, Arc<CartPlayerState>>>,
+}
+
+impl CartPlayerRegistry {
+    pub fn new() -> Self {
+        Self {
+            players: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, key: CartPlayerKey, state: Arc<CartPlayerState>) {
+        if let Ok(mut players) = self.players.write() {
+            players.insert(key, state);
+        }
+    }
+
+    pub fn get(&self, key: &CartPlayerKey) -> Option<Arc<CartPlayerState>> {
+        self.players.read().ok()?.get(key).cloned()
+    }
+
+    /// Remove all cart player entries for a given flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        if let Ok(mut players) = self.players.write() {
+            let before = players.len();
+            players.retain(|k, _| k.flow_id != *flow_id);
+            let removed = before - players.len();
+            if removed > 0 {
+                info!(
+                    "Unregistered {} cart player(s) for flow {}",
+                    removed, flow_id
+                );
+            }
+        }
+    }
+}
+
+impl Default for CartPlayerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
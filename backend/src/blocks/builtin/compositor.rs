@@ -13,20 +13,40 @@
 //! - Configurable output canvas size
 //! - Multiple background types (black, white, transparent)
 //! - Automatic fallback from GPU to CPU when OpenGL is unavailable
+//! - Optional SMPTE pattern wipes (`enable_wipes`)
+//! - Optional per-input crop, rotation, border, drop shadow and rounded
+//!   corners (`enable_styling`), adjustable while running
+//! - Optional per-input chroma and luma keys with a key preview output
+//!   (`enable_keying`), adjustable while running
 //!
-//! GPU backend chain: queue -> glupload -> glcolorconvert -> [thumb_tee] -> glvideomixerelement -> gldownload -> capsfilter
-//! CPU backend chain: queue -> videoconvert -> [thumb_tee] -> compositor -> capsfilter
+//! GPU backend chain: queue -> glupload -> glcolorconvert -> [glvideoflip] -> [thumb_tee] -> [glshader -> key_tee] -> [glshader] -> [glshader] -> glvideomixerelement -> gldownload -> capsfilter
+//! CPU backend chain: queue -> videoconvert -> [videocrop -> videoflip] -> [thumb_tee] -> [videoconvert -> capsfilter -> key_tee] -> [videoconvert -> capsfilter] -> [videoconvert -> capsfilter] -> compositor -> capsfilter
+//!
+//! The flip/crop stages and the style stage after the thumbnail tee are only
+//! added when styling is enabled (see [`crate::gst::styling`]); the key stage
+//! only when keying is enabled (see [`crate::gst::keying`]) and the wipe
+//! stage only when wipes are enabled (see [`crate::gst::wipes`]).
+//!
+//! With keying, every key tee also feeds `key_preview_select`, which shows
+//! the matte of the input selected by `key_preview_input` on `key_preview_out`.
 
+use crate::blocks::common::parse_json_property;
 use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
 use crate::gpu;
+use crate::gst::keying::{self, KeySettings, Keyer};
+use crate::gst::styling::{self, InputStyle, StyleKey, StyledInput, STYLE_REGISTRY};
+use crate::gst::wipes;
 use gstreamer as gst;
 use gstreamer::prelude::*;
-use std::collections::HashMap;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use strom_types::compositor::{CompositorLayout, InputLayout};
 use strom_types::{
     block::*, common_video_resolution_enum_values, element::ElementPadRef, parse_resolution_string,
     PropertyValue, *,
 };
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Backend selection for the compositor.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -102,23 +122,31 @@ impl BlockBuilder for CompositorBuilder {
             });
         }
 
-        Some(ExternalPads {
-            inputs,
-            outputs: vec![ExternalPad {
-                label: Some("V0".to_string()),
-                name: "video_out".to_string(),
+        let mut outputs = vec![ExternalPad {
+            label: Some("V0".to_string()),
+            name: "video_out".to_string(),
+            media_type: MediaType::Video,
+            internal_element_id: "capsfilter".to_string(),
+            internal_pad_name: "src".to_string(),
+        }];
+        if parse_enable_keying(properties) {
+            outputs.push(ExternalPad {
+                label: Some("KEY".to_string()),
+                name: "key_preview_out".to_string(),
                 media_type: MediaType::Video,
-                internal_element_id: "capsfilter".to_string(),
+                internal_element_id: keying::KEY_PREVIEW.to_string(),
                 internal_pad_name: "src".to_string(),
-            }],
-        })
+            });
+        }
+
+        Some(ExternalPads { inputs, outputs })
     }
 
     fn build(
         &self,
         instance_id: &str,
         properties: &HashMap<String, PropertyValue>,
-        _ctx: &BlockBuildContext,
+        ctx: &BlockBuildContext,
     ) -> Result<BlockBuildResult, BlockBuildError> {
         info!("Building Compositor block instance: {}", instance_id);
 
@@ -138,6 +166,9 @@ impl BlockBuilder for CompositorBuilder {
         let use_queues = parse_use_queues(properties);
         let force_live = parse_force_live(properties);
         let gl_output = parse_gl_output(properties);
+        let enable_wipes = parse_enable_wipes(properties);
+        let enable_styling = parse_enable_styling(properties);
+        let enable_keying = parse_enable_keying(properties);
 
         info!(
             "Creating compositor: {} inputs, {}x{} output, background={:?}, backend={:?}",
@@ -145,7 +176,7 @@ impl BlockBuilder for CompositorBuilder {
         );
 
         // Build the pipeline based on selected backend
-        match backend {
+        let mut result = match backend {
             CompositorBackend::OpenGL => build_opengl_compositor(
                 instance_id,
                 properties,
@@ -156,6 +187,9 @@ impl BlockBuilder for CompositorBuilder {
                 use_queues,
                 force_live,
                 gl_output,
+                enable_wipes,
+                enable_styling,
+                enable_keying,
             ),
             CompositorBackend::Software => build_software_compositor(
                 instance_id,
@@ -166,8 +200,25 @@ impl BlockBuilder for CompositorBuilder {
                 background,
                 use_queues,
                 force_live,
+                enable_wipes,
+                enable_styling,
+                enable_keying,
             ),
+        }?;
+
+        if enable_keying {
+            add_key_preview(
+                instance_id,
+                properties,
+                num_inputs,
+                backend,
+                gl_output,
+                ctx,
+                &mut result,
+            )?;
         }
+
+        Ok(result)
     }
 }
 
@@ -239,6 +290,9 @@ fn build_opengl_compositor(
     use_queues: bool,
     force_live: bool,
     gl_output: bool,
+    enable_wipes: bool,
+    enable_styling: bool,
+    enable_keying: bool,
 ) -> Result<BlockBuildResult, BlockBuildError> {
     // Create the main mixer element
     let mixer_id = format!("{}:mixer", instance_id);
@@ -339,6 +393,8 @@ fn build_opengl_compositor(
     // Build element list and internal links
     let mut elements = vec![(mixer_id.clone(), mixer)];
     let mut internal_links = Vec::new();
+    let mut styled_inputs = Vec::new();
+    let mut keyers = Vec::new();
 
     // Create input chain for each input
     for (i, sink_pad) in mixer_sink_pads.iter().enumerate() {
@@ -371,43 +427,83 @@ fn build_opengl_compositor(
             .map_err(|e| BlockBuildError::ElementCreation(format!("thumb_tee_{}: {}", i, e)))?;
         elements.push((thumb_tee_id.clone(), thumb_tee));
 
+        // Link: glcolorconvert -> [glvideoflip] -> thumb_tee
+        let mut tee_feed = ElementPadRef::pad(&colorconvert_id, "src");
+        let mut style_stages = None;
+        if enable_styling {
+            let style = InputStyle::from_properties(properties, i);
+            let flip_id = format!("{}:flip_{}", instance_id, i);
+            let flip = styling::make_flip(&flip_id, &style, true)
+                .map_err(|e| BlockBuildError::ElementCreation(format!("flip_{}: {}", i, e)))?;
+            let style_id = format!("{}:style_{}", instance_id, i);
+            let shader = styling::make_gl_style(
+                &style_id,
+                &style,
+                (
+                    sink_pad.property::<i32>("width"),
+                    sink_pad.property::<i32>("height"),
+                ),
+            )
+            .map_err(|e| BlockBuildError::ElementCreation(format!("style_{}: {}", i, e)))?;
+            styled_inputs.push(StyledInput::new(
+                Arc::new(Mutex::new(style)),
+                sink_pad.clone(),
+                None,
+                flip.clone(),
+                Some(shader.clone()),
+            ));
+            elements.push((flip_id.clone(), flip));
+            internal_links.push((tee_feed, ElementPadRef::pad(&flip_id, "sink")));
+            tee_feed = ElementPadRef::pad(&flip_id, "src");
+            style_stages = Some((style_id, shader));
+        }
+
+        // Link: [thumb_tee] -> [key -> key_tee] -> [style] -> [wipe] -> mixer
+        let mut mixer_feed = ElementPadRef::pad(&thumb_tee_id, "src_0");
+        if enable_keying {
+            mixer_feed = add_key_stage(
+                instance_id,
+                i,
+                properties,
+                CompositorBackend::OpenGL,
+                mixer_feed,
+                &mut elements,
+                &mut internal_links,
+                &mut keyers,
+            )?;
+        }
+        if let Some((style_id, shader)) = style_stages {
+            elements.push((style_id.clone(), shader));
+            internal_links.push((mixer_feed, ElementPadRef::pad(&style_id, "sink")));
+            mixer_feed = ElementPadRef::pad(&style_id, "src");
+        }
+        if enable_wipes {
+            let wipe_id = format!("{}:wipe_{}", instance_id, i);
+            let wipe = wipes::make_gl_wipe(&wipe_id)
+                .map_err(|e| BlockBuildError::ElementCreation(format!("wipe_{}: {}", i, e)))?;
+            elements.push((wipe_id.clone(), wipe));
+            internal_links.push((mixer_feed, ElementPadRef::pad(&wipe_id, "sink")));
+            mixer_feed = ElementPadRef::pad(&wipe_id, "src");
+        }
+        internal_links.push((mixer_feed, ElementPadRef::pad(&mixer_id, &mixer_pad_name)));
+
         if use_queues {
             let queue_id = format!("{}:queue_{}", instance_id, i);
             let queue = create_input_queue(&queue_id, i)?;
             elements.push((queue_id.clone(), queue));
 
-            // Link: queue -> glupload -> glcolorconvert -> [thumb_tee] -> mixer
+            // Link: queue -> glupload -> glcolorconvert
             internal_links.push((
                 ElementPadRef::pad(&queue_id, "src"),
                 ElementPadRef::pad(&upload_id, "sink"),
             ));
-            internal_links.push((
-                ElementPadRef::pad(&upload_id, "src"),
-                ElementPadRef::pad(&colorconvert_id, "sink"),
-            ));
-            internal_links.push((
-                ElementPadRef::pad(&colorconvert_id, "src"),
-                ElementPadRef::pad(&thumb_tee_id, "sink"),
-            ));
-            internal_links.push((
-                ElementPadRef::pad(&thumb_tee_id, "src_0"),
-                ElementPadRef::pad(&mixer_id, &mixer_pad_name),
-            ));
-        } else {
-            // Link: glupload -> glcolorconvert -> [thumb_tee] -> mixer
-            internal_links.push((
-                ElementPadRef::pad(&upload_id, "src"),
-                ElementPadRef::pad(&colorconvert_id, "sink"),
-            ));
-            internal_links.push((
-                ElementPadRef::pad(&colorconvert_id, "src"),
-                ElementPadRef::pad(&thumb_tee_id, "sink"),
-            ));
-            internal_links.push((
-                ElementPadRef::pad(&thumb_tee_id, "src_0"),
-                ElementPadRef::pad(&mixer_id, &mixer_pad_name),
-            ));
         }
+        // Link: glupload -> glcolorconvert, [flip] -> thumb_tee
+        internal_links.push((
+            ElementPadRef::pad(&upload_id, "src"),
+            ElementPadRef::pad(&colorconvert_id, "sink"),
+        ));
+        internal_links.push((tee_feed, ElementPadRef::pad(&thumb_tee_id, "sink")));
     }
 
     // Add output elements and links
@@ -436,6 +532,13 @@ fn build_opengl_compositor(
         info!("GL output chain: mixer -> capsfilter (GL memory)");
     }
 
+    if enable_styling {
+        register_styles(instance_id, properties, styled_inputs);
+    }
+    if enable_keying {
+        keying::register_keyers(instance_id, properties, keyers);
+    }
+
     info!("OpenGL compositor created: {} inputs", num_inputs);
 
     Ok(BlockBuildResult {
@@ -457,6 +560,9 @@ fn build_software_compositor(
     background: &'static str,
     use_queues: bool,
     force_live: bool,
+    enable_wipes: bool,
+    enable_styling: bool,
+    enable_keying: bool,
 ) -> Result<BlockBuildResult, BlockBuildError> {
     // Create the main mixer element
     let mixer_id = format!("{}:mixer", instance_id);
@@ -520,6 +626,8 @@ fn build_software_compositor(
     // Build element list and internal links
     let mut elements = vec![(mixer_id.clone(), mixer)];
     let mut internal_links = Vec::new();
+    let mut styled_inputs = Vec::new();
+    let mut keyers = Vec::new();
 
     // Create input chain for each input
     for (i, sink_pad) in mixer_sink_pads.iter().enumerate() {
@@ -542,35 +650,121 @@ fn build_software_compositor(
             .map_err(|e| BlockBuildError::ElementCreation(format!("thumb_tee_{}: {}", i, e)))?;
         elements.push((thumb_tee_id.clone(), thumb_tee));
 
+        // Link: videoconvert -> [videocrop -> videoflip] -> thumb_tee
+        let mut tee_feed = ElementPadRef::pad(&convert_id, "src");
+        let mut style_stages = None;
+        if enable_styling {
+            let style = InputStyle::from_properties(properties, i);
+            let crop_id = format!("{}:crop_{}", instance_id, i);
+            let crop = styling::make_crop(&crop_id, &style)
+                .map_err(|e| BlockBuildError::ElementCreation(format!("crop_{}: {}", i, e)))?;
+            let flip_id = format!("{}:flip_{}", instance_id, i);
+            let flip = styling::make_flip(&flip_id, &style, false)
+                .map_err(|e| BlockBuildError::ElementCreation(format!("flip_{}: {}", i, e)))?;
+            let style_convert_id = format!("{}:style_convert_{}", instance_id, i);
+            let style_convert = gst::ElementFactory::make("videoconvert")
+                .name(&style_convert_id)
+                .build()
+                .map_err(|e| {
+                    BlockBuildError::ElementCreation(format!("style_convert_{}: {}", i, e))
+                })?;
+            let style_caps_id = format!("{}:style_caps_{}", instance_id, i);
+            let style_caps = gst::ElementFactory::make("capsfilter")
+                .name(&style_caps_id)
+                .property("caps", styling::cpu_style_caps())
+                .build()
+                .map_err(|e| {
+                    BlockBuildError::ElementCreation(format!("style_caps_{}: {}", i, e))
+                })?;
+
+            let style = Arc::new(Mutex::new(style));
+            let probe_pad = style_caps.static_pad("src").ok_or_else(|| {
+                BlockBuildError::ElementCreation(format!("style_caps_{} has no src pad", i))
+            })?;
+            styling::add_cpu_probe(&probe_pad, Arc::clone(&style), sink_pad).ok_or_else(|| {
+                BlockBuildError::ElementCreation(format!("Failed to add style probe {}", i))
+            })?;
+            styled_inputs.push(StyledInput::new(
+                style,
+                sink_pad.clone(),
+                Some(crop.clone()),
+                flip.clone(),
+                None,
+            ));
+
+            elements.push((crop_id.clone(), crop));
+            elements.push((flip_id.clone(), flip));
+            internal_links.push((tee_feed, ElementPadRef::pad(&crop_id, "sink")));
+            internal_links.push((
+                ElementPadRef::pad(&crop_id, "src"),
+                ElementPadRef::pad(&flip_id, "sink"),
+            ));
+            tee_feed = ElementPadRef::pad(&flip_id, "src");
+            style_stages = Some([
+                (style_convert_id, style_convert),
+                (style_caps_id, style_caps),
+            ]);
+        }
+
+        // Link: [thumb_tee] -> [key convert -> key caps -> key_tee] -> [style convert -> style caps] -> [wipe convert -> wipe caps] -> mixer
+        let mut mixer_feed = ElementPadRef::pad(&thumb_tee_id, "src_0");
+        if enable_keying {
+            mixer_feed = add_key_stage(
+                instance_id,
+                i,
+                properties,
+                CompositorBackend::Software,
+                mixer_feed,
+                &mut elements,
+                &mut internal_links,
+                &mut keyers,
+            )?;
+        }
+        if let Some(stages) = style_stages {
+            for (stage_id, stage) in stages {
+                elements.push((stage_id.clone(), stage));
+                internal_links.push((mixer_feed, ElementPadRef::pad(&stage_id, "sink")));
+                mixer_feed = ElementPadRef::pad(&stage_id, "src");
+            }
+        }
+        if enable_wipes {
+            let wipe_convert_id = format!("{}:wipe_convert_{}", instance_id, i);
+            let wipe_convert = gst::ElementFactory::make("videoconvert")
+                .name(&wipe_convert_id)
+                .build()
+                .map_err(|e| {
+                    BlockBuildError::ElementCreation(format!("wipe_convert_{}: {}", i, e))
+                })?;
+            let wipe_caps_id = format!("{}:wipe_caps_{}", instance_id, i);
+            let wipe_caps = gst::ElementFactory::make("capsfilter")
+                .name(&wipe_caps_id)
+                .property("caps", wipes::cpu_wipe_caps())
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("wipe_caps_{}: {}", i, e)))?;
+            elements.push((wipe_convert_id.clone(), wipe_convert));
+            elements.push((wipe_caps_id.clone(), wipe_caps));
+            internal_links.push((mixer_feed, ElementPadRef::pad(&wipe_convert_id, "sink")));
+            internal_links.push((
+                ElementPadRef::pad(&wipe_convert_id, "src"),
+                ElementPadRef::pad(&wipe_caps_id, "sink"),
+            ));
+            mixer_feed = ElementPadRef::pad(&wipe_caps_id, "src");
+        }
+        internal_links.push((mixer_feed, ElementPadRef::pad(&mixer_id, &mixer_pad_name)));
+
         if use_queues {
             let queue_id = format!("{}:queue_{}", instance_id, i);
             let queue = create_input_queue(&queue_id, i)?;
             elements.push((queue_id.clone(), queue));
 
-            // Link: queue -> videoconvert -> [thumb_tee] -> mixer
+            // Link: queue -> videoconvert
             internal_links.push((
                 ElementPadRef::pad(&queue_id, "src"),
                 ElementPadRef::pad(&convert_id, "sink"),
             ));
-            internal_links.push((
-                ElementPadRef::pad(&convert_id, "src"),
-                ElementPadRef::pad(&thumb_tee_id, "sink"),
-            ));
-            internal_links.push((
-                ElementPadRef::pad(&thumb_tee_id, "src_0"),
-                ElementPadRef::pad(&mixer_id, &mixer_pad_name),
-            ));
-        } else {
-            // Link: videoconvert -> [thumb_tee] -> mixer
-            internal_links.push((
-                ElementPadRef::pad(&convert_id, "src"),
-                ElementPadRef::pad(&thumb_tee_id, "sink"),
-            ));
-            internal_links.push((
-                ElementPadRef::pad(&thumb_tee_id, "src_0"),
-                ElementPadRef::pad(&mixer_id, &mixer_pad_name),
-            ));
         }
+        // Link: videoconvert, [flip] -> thumb_tee
+        internal_links.push((tee_feed, ElementPadRef::pad(&thumb_tee_id, "sink")));
     }
 
     // Add output elements and links
@@ -583,6 +777,13 @@ fn build_software_compositor(
 
     info!("CPU output chain: mixer -> capsfilter");
 
+    if enable_styling {
+        register_styles(instance_id, properties, styled_inputs);
+    }
+    if enable_keying {
+        keying::register_keyers(instance_id, properties, keyers);
+    }
+
     info!("Software compositor created: {} inputs", num_inputs);
 
     Ok(BlockBuildResult {
@@ -593,6 +794,160 @@ fn build_software_compositor(
     })
 }
 
+/// Register the style stages of a running compositor for live updates.
+fn register_styles(
+    instance_id: &str,
+    properties: &HashMap<String, PropertyValue>,
+    inputs: Vec<StyledInput>,
+) {
+    match properties.get("_flow_id").and_then(|v| match v {
+        PropertyValue::String(s) => s.parse::<FlowId>().ok(),
+        _ => None,
+    }) {
+        Some(flow_id) => STYLE_REGISTRY.register(
+            StyleKey {
+                flow_id,
+                block_id: instance_id.to_string(),
+            },
+            inputs,
+        ),
+        None => warn!(
+            "Compositor {} has no flow ID, input styles are not live",
+            instance_id
+        ),
+    }
+}
+
+/// Insert the key stage of input `i` after `feed` and return the pad that
+/// continues to the mixer.
+///
+/// GPU: key shader. CPU: videoconvert + capsfilter forcing BGRA for the key
+/// probe. Both end in `key_tee_i`, whose second branch feeds the key preview.
+#[allow(clippy::too_many_arguments)]
+fn add_key_stage(
+    instance_id: &str,
+    i: usize,
+    properties: &HashMap<String, PropertyValue>,
+    backend: CompositorBackend,
+    feed: ElementPadRef,
+    elements: &mut Vec<(String, gst::Element)>,
+    internal_links: &mut Vec<(ElementPadRef, ElementPadRef)>,
+    keyers: &mut Vec<Keyer>,
+) -> Result<ElementPadRef, BlockBuildError> {
+    let settings = KeySettings::from_properties(properties, &format!("input_{}_", i));
+    let key_id = format!("{}:{}", instance_id, keying::key_stage_name(i));
+    let mut stages = Vec::new();
+    match backend {
+        CompositorBackend::OpenGL => {
+            let shader = keying::make_gl_key(&key_id, &settings)
+                .map_err(|e| BlockBuildError::ElementCreation(format!("key_{}: {}", i, e)))?;
+            keyers.push(Keyer::new(
+                Arc::new(Mutex::new(settings)),
+                Some(shader.clone()),
+            ));
+            stages.push((key_id, shader));
+        }
+        CompositorBackend::Software => {
+            let convert_id = format!("{}:key_convert_{}", instance_id, i);
+            let convert = gst::ElementFactory::make("videoconvert")
+                .name(&convert_id)
+                .build()
+                .map_err(|e| {
+                    BlockBuildError::ElementCreation(format!("key_convert_{}: {}", i, e))
+                })?;
+            let caps = gst::ElementFactory::make("capsfilter")
+                .name(&key_id)
+                .property("caps", keying::cpu_key_caps())
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("key_{}: {}", i, e)))?;
+            let settings = Arc::new(Mutex::new(settings));
+            let probe_pad = caps.static_pad("src").ok_or_else(|| {
+                BlockBuildError::ElementCreation(format!("key_{} has no src pad", i))
+            })?;
+            keying::add_cpu_probe(&probe_pad, Arc::clone(&settings)).ok_or_else(|| {
+                BlockBuildError::ElementCreation(format!("Failed to add key probe {}", i))
+            })?;
+            keyers.push(Keyer::new(settings, None));
+            stages.push((convert_id, convert));
+            stages.push((key_id, caps));
+        }
+    }
+
+    let mut feed = feed;
+    for (stage_id, stage) in stages {
+        elements.push((stage_id.clone(), stage));
+        internal_links.push((feed, ElementPadRef::pad(&stage_id, "sink")));
+        feed = ElementPadRef::pad(&stage_id, "src");
+    }
+
+    let key_tee_id = format!("{}:key_tee_{}", instance_id, i);
+    let key_tee = gst::ElementFactory::make("tee")
+        .name(&key_tee_id)
+        .property("allow-not-linked", true)
+        .build()
+        .map_err(|e| BlockBuildError::ElementCreation(format!("key_tee_{}: {}", i, e)))?;
+    elements.push((key_tee_id.clone(), key_tee));
+    internal_links.push((feed, ElementPadRef::pad(&key_tee_id, "sink")));
+    Ok(ElementPadRef::pad(&key_tee_id, "src_0"))
+}
+
+/// Add the key preview: `key_tee_i.src_1` of every input feeds
+/// `key_preview_select`, followed by the matte stages.
+///
+/// There is no queue in front of the selector: the inactive inputs return
+/// not-linked, which the key tees ignore.
+fn add_key_preview(
+    instance_id: &str,
+    properties: &HashMap<String, PropertyValue>,
+    num_inputs: usize,
+    backend: CompositorBackend,
+    gl_output: bool,
+    ctx: &BlockBuildContext,
+    result: &mut BlockBuildResult,
+) -> Result<(), BlockBuildError> {
+    let select_id = format!("{}:{}", instance_id, keying::KEY_PREVIEW_SELECT);
+    let selector = keying::make_selector(&select_id)
+        .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", select_id, e)))?;
+    result.elements.push((select_id.clone(), selector.clone()));
+    for i in 0..num_inputs {
+        result.internal_links.push((
+            ElementPadRef::pad(format!("{}:key_tee_{}", instance_id, i), "src_1"),
+            ElementPadRef::pad(&select_id, format!("sink_{}", i)),
+        ));
+    }
+
+    let stages = keying::make_key_preview_stages(
+        instance_id,
+        backend == CompositorBackend::OpenGL,
+        gl_output,
+    )
+    .map_err(|e| BlockBuildError::ElementCreation(format!("key preview: {}", e)))?;
+    let mut feed = ElementPadRef::pad(&select_id, "src");
+    for (stage_id, stage) in stages {
+        result.elements.push((stage_id.clone(), stage));
+        result
+            .internal_links
+            .push((feed, ElementPadRef::pad(&stage_id, "sink")));
+        feed = ElementPadRef::pad(&stage_id, "src");
+    }
+
+    let preview_input = properties
+        .get("key_preview_input")
+        .and_then(|v| match v {
+            PropertyValue::UInt(u) => Some(*u as usize),
+            PropertyValue::Int(i) if *i >= 0 => Some(*i as usize),
+            _ => None,
+        })
+        .unwrap_or(0)
+        .min(num_inputs.saturating_sub(1));
+    ctx.register_element_setup(Box::new(move |_flow_id, _events| {
+        if let Err(e) = keying::select_input(&selector, preview_input) {
+            warn!("Key preview: {}", e);
+        }
+    }));
+    Ok(())
+}
+
 /// Create an input queue element with standard settings.
 fn create_input_queue(queue_id: &str, index: usize) -> Result<gst::Element, BlockBuildError> {
     gst::ElementFactory::make("queue")
@@ -610,80 +965,194 @@ fn set_common_pad_properties(
     output_width: u32,
     output_height: u32,
 ) {
+    let layout = input_layout(properties, index, output_width, output_height);
+    sink_pad.set_property_from_str("xpos", &layout.xpos.to_string());
+    sink_pad.set_property_from_str("ypos", &layout.ypos.to_string());
+    sink_pad.set_property_from_str("width", &layout.width.to_string());
+    sink_pad.set_property_from_str("height", &layout.height.to_string());
+    sink_pad.set_property_from_str("alpha", &layout.alpha.to_string());
+    sink_pad.set_property_from_str("zorder", &layout.zorder.to_string());
+
+    info!(
+        "Pad {} properties: xpos={}, ypos={}, width={}, height={}, alpha={}, zorder={}",
+        sink_pad.name(),
+        layout.xpos,
+        layout.ypos,
+        layout.width,
+        layout.height,
+        layout.alpha,
+        layout.zorder
+    );
+}
+
+/// Placement of input `index` from the `input_N_*` properties, falling back
+/// to the default tiered layout.
+fn input_layout(
+    properties: &HashMap<String, PropertyValue>,
+    index: usize,
+    output_width: u32,
+    output_height: u32,
+) -> InputLayout {
     let (default_xpos, default_ypos, default_width, default_height) =
         calculate_default_layout(index, output_width, output_height);
+    let int = |name: &str, default: i64| {
+        properties
+            .get(&format!("input_{}_{}", index, name))
+            .and_then(|v| match v {
+                PropertyValue::Int(i) => Some(*i),
+                _ => None,
+            })
+            .unwrap_or(default)
+            .clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    };
 
-    // XPos
-    let xpos = properties
-        .get(&format!("input_{}_xpos", index))
-        .and_then(|v| match v {
-            PropertyValue::Int(i) => Some(*i),
-            _ => None,
-        })
-        .unwrap_or(default_xpos);
-    sink_pad.set_property_from_str("xpos", &xpos.to_string());
+    InputLayout {
+        input: index,
+        xpos: int("xpos", default_xpos),
+        ypos: int("ypos", default_ypos),
+        width: int("width", default_width),
+        height: int("height", default_height),
+        alpha: properties
+            .get(&format!("input_{}_alpha", index))
+            .and_then(|v| match v {
+                PropertyValue::Float(f) => Some(*f),
+                _ => None,
+            })
+            .unwrap_or(1.0),
+        zorder: properties
+            .get(&format!("input_{}_zorder", index))
+            .and_then(|v| match v {
+                PropertyValue::UInt(u) => Some(*u as u32),
+                PropertyValue::Int(i) if *i >= 0 => Some(*i as u32),
+                _ => None,
+            })
+            .unwrap_or(index as u32),
+    }
+}
 
-    // YPos
-    let ypos = properties
-        .get(&format!("input_{}_ypos", index))
-        .and_then(|v| match v {
-            PropertyValue::Int(i) => Some(*i),
-            _ => None,
-        })
-        .unwrap_or(default_ypos);
-    sink_pad.set_property_from_str("ypos", &ypos.to_string());
+// ============================================================================
+// Layout Presets
+// ============================================================================
 
-    // Width
-    let width = properties
-        .get(&format!("input_{}_width", index))
-        .and_then(|v| match v {
-            PropertyValue::Int(i) => Some(*i),
-            _ => None,
-        })
-        .unwrap_or(default_width);
-    sink_pad.set_property_from_str("width", &width.to_string());
+/// Errors from layout preset operations.
+#[derive(Debug, thiserror::Error)]
+pub enum LayoutError {
+    #[error("Flow not found: {0}")]
+    FlowNotFound(FlowId),
+    #[error("Block {0} is not a compositor")]
+    NotCompositor(String),
+    #[error("Preset not found: {0}")]
+    PresetNotFound(String),
+    #[error("Invalid layout: {0}")]
+    InvalidLayout(String),
+}
 
-    // Height
-    let height = properties
-        .get(&format!("input_{}_height", index))
-        .and_then(|v| match v {
-            PropertyValue::Int(i) => Some(*i),
-            _ => None,
-        })
-        .unwrap_or(default_height);
-    sink_pad.set_property_from_str("height", &height.to_string());
+/// Current layout of every input of a compositor block.
+pub fn current_layout(properties: &HashMap<String, PropertyValue>) -> CompositorLayout {
+    let (output_width, output_height) = parse_output_resolution(properties);
+    (0..parse_num_inputs(properties))
+        .map(|i| input_layout(properties, i, output_width, output_height))
+        .collect()
+}
 
-    // Alpha
-    let alpha = properties
-        .get(&format!("input_{}_alpha", index))
-        .and_then(|v| match v {
-            PropertyValue::Float(f) => Some(*f),
-            _ => None,
-        })
-        .unwrap_or(1.0);
-    sink_pad.set_property_from_str("alpha", &alpha.to_string());
+/// Layout presets stored in the `layout_presets` property.
+pub fn layout_presets(
+    properties: &HashMap<String, PropertyValue>,
+) -> BTreeMap<String, CompositorLayout> {
+    parse_json_property(properties, "layout_presets")
+}
 
-    // Z-Order
-    let zorder = properties
-        .get(&format!("input_{}_zorder", index))
-        .and_then(|v| match v {
-            PropertyValue::UInt(u) => Some(*u as u32),
-            PropertyValue::Int(i) if *i >= 0 => Some(*i as u32),
-            _ => None,
+/// Complete a partial layout with the current placement of the inputs it
+/// leaves out.
+///
+/// Every entry must name a configured input at most once, with a
+/// non-negative size and an alpha between 0.0 and 1.0.
+pub fn complete_layout(
+    properties: &HashMap<String, PropertyValue>,
+    layout: &[InputLayout],
+) -> Result<CompositorLayout, LayoutError> {
+    let mut complete = current_layout(properties);
+    let mut seen = vec![false; complete.len()];
+    for entry in layout {
+        let Some(slot) = complete.get_mut(entry.input) else {
+            return Err(LayoutError::InvalidLayout(format!(
+                "input {} out of range (block has {} inputs)",
+                entry.input,
+                seen.len()
+            )));
+        };
+        if std::mem::replace(&mut seen[entry.input], true) {
+            return Err(LayoutError::InvalidLayout(format!(
+                "input {} listed twice",
+                entry.input
+            )));
+        }
+        if entry.width < 0 || entry.height < 0 {
+            return Err(LayoutError::InvalidLayout(format!(
+                "input {} has a negative size",
+                entry.input
+            )));
+        }
+        if !(0.0..=1.0).contains(&entry.alpha) {
+            return Err(LayoutError::InvalidLayout(format!(
+                "input {} alpha {} is outside 0.0-1.0",
+                entry.input, entry.alpha
+            )));
+        }
+        *slot = *entry;
+    }
+    Ok(complete)
+}
+
+/// Name of the first preset whose layout equals `layout`.
+///
+/// Presets saved with fewer inputs than the block now has only compare the
+/// inputs they hold.
+pub fn active_preset(
+    layout: &[InputLayout],
+    presets: &BTreeMap<String, CompositorLayout>,
+) -> Option<String> {
+    presets
+        .iter()
+        .find(|(_, preset)| {
+            preset
+                .iter()
+                .all(|entry| layout.get(entry.input) == Some(entry))
         })
-        .unwrap_or(index as u32);
-    sink_pad.set_property_from_str("zorder", &zorder.to_string());
+        .map(|(name, _)| name.clone())
+}
 
-    info!(
-        "Pad {} properties: xpos={}, ypos={}, width={}, height={}, alpha={}, zorder={}",
-        sink_pad.name(),
-        xpos,
-        ypos,
-        width,
-        height,
-        alpha,
-        zorder
-    );
+/// Block properties (`input_N_xpos`, ...) holding a layout.
+pub fn layout_properties(layout: &[InputLayout]) -> Vec<(String, PropertyValue)> {
+    layout
+        .iter()
+        .flat_map(|l| {
+            let i = l.input;
+            [
+                (
+                    format!("input_{}_xpos", i),
+                    PropertyValue::Int(l.xpos as i64),
+                ),
+                (
+                    format!("input_{}_ypos", i),
+                    PropertyValue::Int(l.ypos as i64),
+                ),
+                (
+                    format!("input_{}_width", i),
+                    PropertyValue::Int(l.width as i64),
+                ),
+                (
+                    format!("input_{}_height", i),
+                    PropertyValue::Int(l.height as i64),
+                ),
+                (format!("input_{}_alpha", i), PropertyValue::Float(l.alpha)),
+                (
+                    format!("input_{}_zorder", i),
+                    PropertyValue::UInt(l.zorder as u64),
+                ),
+            ]
+        })
+        .collect()
 }
 
 /// Set latency properties on a mixer element.
@@ -820,6 +1289,39 @@ fn parse_gl_output(properties: &HashMap<String, PropertyValue>) -> bool {
         .unwrap_or(false)
 }
 
+/// Parse enable_wipes from properties.
+fn parse_enable_wipes(properties: &HashMap<String, PropertyValue>) -> bool {
+    properties
+        .get("enable_wipes")
+        .and_then(|v| match v {
+            PropertyValue::Bool(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
+/// Parse enable_styling from properties.
+fn parse_enable_styling(properties: &HashMap<String, PropertyValue>) -> bool {
+    properties
+        .get("enable_styling")
+        .and_then(|v| match v {
+            PropertyValue::Bool(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
+/// Parse enable_keying from properties.
+fn parse_enable_keying(properties: &HashMap<String, PropertyValue>) -> bool {
+    properties
+        .get("enable_keying")
+        .and_then(|v| match v {
+            PropertyValue::Bool(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
 /// Calculate default position and size for an input based on output resolution.
 ///
 /// Creates a 3-row tiered layout:
@@ -862,6 +1364,274 @@ fn calculate_default_layout(
 // ============================================================================
 
 /// Get metadata for Compositor block (for UI/API).
+/// Exposed properties controlling SMPTE pattern wipes.
+///
+/// Shared with the vision mixer, which builds the same wipe stages in front
+/// of its distribution compositor.
+pub(crate) fn wipe_properties() -> Vec<ExposedProperty> {
+    vec![
+        ExposedProperty {
+            name: "enable_wipes".to_string(),
+            label: "Enable Wipes".to_string(),
+            description: "Add a wipe stage per input for SMPTE pattern wipes (wipe_bar, wipe_box, wipe_diamond, wipe_iris, wipe_clock, smpte_<code>). On the CPU backend the output format must carry alpha (e.g. Auto or BGRA).".to_string(),
+            property_type: PropertyType::Bool,
+            default_value: Some(PropertyValue::Bool(false)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "enable_wipes".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        ExposedProperty {
+            name: "wipe_softness".to_string(),
+            label: "Wipe Softness".to_string(),
+            description: "Soft-edge width of wipes as a fraction of the wipe travel (0 = hard edge)".to_string(),
+            property_type: PropertyType::Float,
+            default_value: Some(PropertyValue::Float(0.02)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "wipe_softness".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        ExposedProperty {
+            name: "wipe_border_width".to_string(),
+            label: "Wipe Border Width".to_string(),
+            description: "Border width of wipes as a fraction of the wipe travel (0 = no border)".to_string(),
+            property_type: PropertyType::Float,
+            default_value: Some(PropertyValue::Float(0.0)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "wipe_border_width".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        ExposedProperty {
+            name: "wipe_border_color".to_string(),
+            label: "Wipe Border Color".to_string(),
+            description: "Border colour of wipes as #RRGGBB".to_string(),
+            property_type: PropertyType::String,
+            default_value: Some(PropertyValue::String("#FFFFFF".to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "wipe_border_color".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+    ]
+}
+
+/// Per-input style properties, used when `enable_styling` is set.
+///
+/// Names mirror the mixer pad properties handled by [`crate::gst::styling`].
+fn input_style_properties(i: usize) -> Vec<ExposedProperty> {
+    let style = |name: &str,
+                 label: &str,
+                 description: String,
+                 property_type: PropertyType,
+                 default_value: PropertyValue| ExposedProperty {
+        name: format!("input_{}_{}", i, name),
+        label: format!("Input {} {}", i, label),
+        description,
+        property_type,
+        default_value: Some(default_value),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: format!("input_{}_{}", i, name),
+            transform: None,
+        },
+        live: false,
+    };
+    let crop = |side: &str, label: &str| {
+        style(
+            &format!("crop_{}", side),
+            label,
+            format!("Source pixels cut from the {} of input {}", side, i),
+            PropertyType::UInt,
+            PropertyValue::UInt(0),
+        )
+    };
+
+    vec![
+        crop("top", "Crop Top"),
+        crop("bottom", "Crop Bottom"),
+        crop("left", "Crop Left"),
+        crop("right", "Crop Right"),
+        style(
+            "rotation",
+            "Rotation",
+            format!("Clockwise rotation of input {} in degrees", i),
+            PropertyType::Enum {
+                values: ["0", "90", "180", "270"]
+                    .into_iter()
+                    .map(|deg| EnumValue {
+                        value: deg.to_string(),
+                        label: Some(format!("{}°", deg)),
+                    })
+                    .collect(),
+            },
+            PropertyValue::String("0".to_string()),
+        ),
+        style(
+            "border_width",
+            "Border Width",
+            format!("Border width of input {} in canvas pixels (0 = none)", i),
+            PropertyType::Float,
+            PropertyValue::Float(0.0),
+        ),
+        style(
+            "border_color",
+            "Border Color",
+            format!("Border colour of input {} as #RRGGBB", i),
+            PropertyType::String,
+            PropertyValue::String("#FFFFFF".to_string()),
+        ),
+        style(
+            "corner_radius",
+            "Corner Radius",
+            format!("Corner radius of input {} in canvas pixels", i),
+            PropertyType::Float,
+            PropertyValue::Float(0.0),
+        ),
+        style(
+            "shadow_size",
+            "Shadow Size",
+            format!(
+                "Drop shadow offset and blur of input {} in canvas pixels (0 = none)",
+                i
+            ),
+            PropertyType::Float,
+            PropertyValue::Float(0.0),
+        ),
+        style(
+            "shadow_opacity",
+            "Shadow Opacity",
+            format!("Drop shadow opacity of input {} (0.0-1.0)", i),
+            PropertyType::Float,
+            PropertyValue::Float(0.5),
+        ),
+    ]
+}
+
+/// Key properties of one input or upstream keyer, used when keying is enabled.
+///
+/// `prefix` is `input_N_` on the compositor and `usk_N_` on the vision
+/// mixer; names mirror the properties handled by [`crate::gst::keying`].
+/// `live` maps them to the `key_N` stage so they apply while running.
+pub(crate) fn key_properties(
+    prefix: &str,
+    label: &str,
+    index: usize,
+    live: bool,
+) -> Vec<ExposedProperty> {
+    let key = |name: &str,
+               what: &str,
+               description: String,
+               property_type: PropertyType,
+               default_value: PropertyValue| ExposedProperty {
+        name: format!("{}{}", prefix, name),
+        label: format!("{} {}", label, what),
+        description,
+        property_type,
+        default_value: Some(default_value),
+        mapping: if live {
+            PropertyMapping {
+                element_id: keying::key_stage_name(index),
+                property_name: name.replace('_', "-"),
+                transform: None,
+            }
+        } else {
+            PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("{}{}", prefix, name),
+                transform: None,
+            }
+        },
+        live,
+    };
+    let defaults = KeySettings::default();
+
+    vec![
+        key(
+            "key_mode",
+            "Key",
+            format!("Keyer type of {}", label),
+            PropertyType::Enum {
+                values: [("none", "None"), ("chroma", "Chroma"), ("luma", "Luma")]
+                    .into_iter()
+                    .map(|(value, label)| EnumValue {
+                        value: value.to_string(),
+                        label: Some(label.to_string()),
+                    })
+                    .collect(),
+            },
+            PropertyValue::String("none".to_string()),
+        ),
+        key(
+            "key_color",
+            "Key Color",
+            format!("Chroma key colour of {} as #RRGGBB", label),
+            PropertyType::String,
+            PropertyValue::String("#00FF00".to_string()),
+        ),
+        key(
+            "key_tolerance",
+            "Key Tolerance",
+            format!(
+                "Colour distance from the key colour that is fully keyed on {} (0.0-1.0)",
+                label
+            ),
+            PropertyType::Float,
+            PropertyValue::Float(defaults.tolerance as f64),
+        ),
+        key(
+            "key_softness",
+            "Key Softness",
+            format!("Width of the chroma key edge on {} (0.0-1.0)", label),
+            PropertyType::Float,
+            PropertyValue::Float(defaults.softness as f64),
+        ),
+        key(
+            "key_spill",
+            "Spill Suppression",
+            format!(
+                "Amount of key colour removed from the kept picture of {} (0.0-1.0)",
+                label
+            ),
+            PropertyType::Float,
+            PropertyValue::Float(defaults.spill as f64),
+        ),
+        key(
+            "key_clip",
+            "Luma Clip",
+            format!(
+                "Luma level where the luma key of {} is half transparent (0.0-1.0)",
+                label
+            ),
+            PropertyType::Float,
+            PropertyValue::Float(defaults.clip as f64),
+        ),
+        key(
+            "key_gain",
+            "Luma Gain",
+            format!("Steepness of the luma key of {}", label),
+            PropertyType::Float,
+            PropertyValue::Float(defaults.gain as f64),
+        ),
+        key(
+            "key_invert",
+            "Luma Invert",
+            format!("Key out bright instead of dark areas of {}", label),
+            PropertyType::Bool,
+            PropertyValue::Bool(false),
+        ),
+    ]
+}
+
 pub fn get_blocks() -> Vec<BlockDefinition> {
     vec![compositor_definition()]
 }
@@ -1030,6 +1800,62 @@ fn compositor_definition() -> BlockDefinition {
             live: false,
         },
     ];
+    exposed_properties.extend(wipe_properties());
+    exposed_properties.push(ExposedProperty {
+        name: "enable_styling".to_string(),
+        label: "Enable Input Styling".to_string(),
+        description: "Add per-input stages for crop, rotation, borders, drop shadows and rounded corners, all adjustable while running. On the CPU backend each input is converted to BGRA for styling.".to_string(),
+        property_type: PropertyType::Bool,
+        default_value: Some(PropertyValue::Bool(false)),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: "enable_styling".to_string(),
+            transform: None,
+        },
+        live: false,
+    });
+
+    exposed_properties.push(ExposedProperty {
+        name: "enable_keying".to_string(),
+        label: "Enable Input Keying".to_string(),
+        description: "Add per-input chroma and luma keys, adjustable while running, and a key preview output showing the matte of one input. On the CPU backend each input is converted to BGRA for keying.".to_string(),
+        property_type: PropertyType::Bool,
+        default_value: Some(PropertyValue::Bool(false)),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: "enable_keying".to_string(),
+            transform: None,
+        },
+        live: false,
+    });
+    exposed_properties.push(ExposedProperty {
+        name: "key_preview_input".to_string(),
+        label: "Key Preview Input".to_string(),
+        description: "Input whose key matte is shown on the key preview output".to_string(),
+        property_type: PropertyType::UInt,
+        default_value: Some(PropertyValue::UInt(0)),
+        mapping: PropertyMapping {
+            element_id: keying::KEY_PREVIEW_SELECT.to_string(),
+            property_name: keying::SELECT_PROPERTY.to_string(),
+            transform: None,
+        },
+        live: true,
+    });
+
+    // Layout presets (JSON format), recalled with the layout API
+    exposed_properties.push(ExposedProperty {
+        name: "layout_presets".to_string(),
+        label: "Layout Presets".to_string(),
+        description: "JSON map of named layouts: {\"name\": [{\"input\": 0, \"xpos\": 0, \"ypos\": 0, \"width\": 960, \"height\": 540, \"alpha\": 1.0, \"zorder\": 0}]}. Recalled with an animated move while running".to_string(),
+        property_type: PropertyType::Multiline,
+        default_value: Some(PropertyValue::String("{}".to_string())),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: "layout_presets".to_string(),
+            transform: None,
+        },
+        live: false,
+    });
 
     // Generate per-input properties
     for i in 0..MAX_INPUTS {
@@ -1154,6 +1980,14 @@ fn compositor_definition() -> BlockDefinition {
             },
             live: false,
         });
+
+        exposed_properties.extend(input_style_properties(i));
+        exposed_properties.extend(key_properties(
+            &format!("input_{}_", i),
+            &format!("Input {}", i),
+            i,
+            false,
+        ));
     }
 
     BlockDefinition {
@@ -1196,3 +2030,79 @@ fn compositor_definition() -> BlockDefinition {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::common::json_property;
+
+    fn entry(input: usize, xpos: i32, width: i32, zorder: u32) -> InputLayout {
+        InputLayout {
+            input,
+            xpos,
+            ypos: 0,
+            width,
+            height: width * 9 / 16,
+            alpha: 1.0,
+            zorder,
+        }
+    }
+
+    fn props() -> HashMap<String, PropertyValue> {
+        HashMap::from([
+            ("num_inputs".to_string(), PropertyValue::UInt(2)),
+            (
+                "output_resolution".to_string(),
+                PropertyValue::String("1920x1080".to_string()),
+            ),
+            ("input_1_xpos".to_string(), PropertyValue::Int(1440)),
+            ("input_1_zorder".to_string(), PropertyValue::UInt(5)),
+        ])
+    }
+
+    #[test]
+    fn test_current_layout_uses_properties_and_defaults() {
+        let layout = current_layout(&props());
+        assert_eq!(layout.len(), 2);
+        assert_eq!(layout[0], entry(0, 0, 960, 0));
+        assert_eq!(layout[1].xpos, 1440);
+        assert_eq!(layout[1].width, 960);
+        assert_eq!(layout[1].zorder, 5);
+    }
+
+    #[test]
+    fn test_complete_layout_validates_and_fills_missing_inputs() {
+        let properties = props();
+        let pip = entry(1, 1400, 480, 10);
+        let layout = complete_layout(&properties, &[pip]).unwrap();
+        assert_eq!(layout[0], current_layout(&properties)[0]);
+        assert_eq!(layout[1], pip);
+
+        assert!(complete_layout(&properties, &[entry(2, 0, 100, 0)]).is_err());
+        assert!(complete_layout(&properties, &[pip, pip]).is_err());
+        assert!(complete_layout(&properties, &[InputLayout { alpha: 1.5, ..pip }]).is_err());
+        assert!(complete_layout(&properties, &[entry(0, 0, -1, 0)]).is_err());
+    }
+
+    #[test]
+    fn test_layout_presets_roundtrip_through_properties() {
+        let two_up = vec![entry(0, 0, 960, 0), entry(1, 960, 960, 1)];
+        let pip = vec![entry(0, 0, 1920, 0), entry(1, 1400, 480, 1)];
+        let mut properties = props();
+        properties.insert(
+            "layout_presets".to_string(),
+            json_property(&BTreeMap::from([
+                ("2up".to_string(), two_up.clone()),
+                ("pip".to_string(), pip.clone()),
+            ])),
+        );
+        let presets = layout_presets(&properties);
+        assert_eq!(presets["pip"], pip);
+
+        properties.extend(layout_properties(&pip));
+        let layout = current_layout(&properties);
+        assert_eq!(layout, pip);
+        assert_eq!(active_preset(&layout, &presets).as_deref(), Some("pip"));
+        assert_eq!(active_preset(&two_up, &presets).as_deref(), Some("2up"));
+    }
+}
@@ -0,0 +1,749 @@
+//! Local capture and playback device blocks backed by GStreamer's DeviceMonitor.
+//!
+//! The source/sink element is created with the discovered device's own
+//! `create_element()`, so any provider the monitor knows about works
+//! (V4L2, PipeWire, PulseAudio, ALSA, ...).
+//!
+//! Local sound cards run on their own clock rather than PTP, so the audio
+//! output exposes the audiobasesink buffering and slaving settings used to
+//! absorb drift against the pipeline clock.
+//!
+//! The `device` and `caps` enums are empty in the static definitions and are
+//! filled from the currently discovered devices when block definitions are
+//! served (see [`populate_device_options`]). Hot-plug is reported through
+//! `DeviceAdded`/`DeviceRemoved` events so clients can refresh them.
+
+use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
+use crate::discovery::device::find_device;
+use crate::discovery::{DeviceCategory, DiscoveredDevice};
+use crate::gpu::video_convert_mode;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::collections::HashMap;
+use strom_types::{block::*, element::ElementPadRef, PropertyValue, *};
+use tracing::{info, warn};
+
+/// Device category offered by a device block, or `None` for other blocks.
+fn device_category(block_definition_id: &str) -> Option<DeviceCategory> {
+    match block_definition_id {
+        "builtin.device_video_input" => Some(DeviceCategory::VideoSource),
+        "builtin.device_audio_input" => Some(DeviceCategory::AudioSource),
+        "builtin.device_audio_output" => Some(DeviceCategory::AudioSink),
+        _ => None,
+    }
+}
+
+/// Fill the `device` and `caps` enums of device blocks from discovered devices.
+pub fn populate_device_options(blocks: &mut [BlockDefinition], devices: &[DiscoveredDevice]) {
+    for block in blocks.iter_mut() {
+        let Some(category) = device_category(&block.id) else {
+            continue;
+        };
+
+        let mut matching: Vec<&DiscoveredDevice> =
+            devices.iter().filter(|d| d.category == category).collect();
+        matching.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for prop in block.exposed_properties.iter_mut() {
+            let mut values = vec![EnumValue {
+                value: String::new(),
+                label: Some(if prop.name == "caps" { "Auto" } else { "-" }.to_string()),
+            }];
+            match prop.name.as_str() {
+                "device" => values.extend(matching.iter().map(|d| EnumValue {
+                    value: d.id.clone(),
+                    label: Some(d.name.clone()),
+                })),
+                "caps" => {
+                    // One entry per mode, labelled with every device that
+                    // advertises it. The build checks it against the
+                    // selected device.
+                    let mut modes: Vec<(&String, Vec<&str>)> = Vec::new();
+                    for device in &matching {
+                        for caps in &device.caps {
+                            match modes.iter_mut().find(|(c, _)| *c == caps) {
+                                Some((_, names)) => names.push(device.name.as_str()),
+                                None => modes.push((caps, vec![device.name.as_str()])),
+                            }
+                        }
+                    }
+                    values.extend(modes.into_iter().map(|(caps, names)| EnumValue {
+                        value: caps.clone(),
+                        label: Some(format!("{}: {}", names.join(", "), caps)),
+                    }));
+                }
+                _ => continue,
+            }
+            prop.property_type = PropertyType::Enum { values };
+        }
+    }
+}
+
+fn parse_string(properties: &HashMap<String, PropertyValue>, key: &str) -> String {
+    properties
+        .get(key)
+        .and_then(|v| match v {
+            PropertyValue::String(s) => Some(s.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+fn parse_u64(properties: &HashMap<String, PropertyValue>, key: &str, default: u64) -> u64 {
+    properties
+        .get(key)
+        .and_then(|v| match v {
+            PropertyValue::UInt(n) => Some(*n),
+            PropertyValue::Int(n) if *n >= 0 => Some(*n as u64),
+            _ => None,
+        })
+        .unwrap_or(default)
+}
+
+/// Look up the device selected by the `device` property.
+fn selected_device(
+    properties: &HashMap<String, PropertyValue>,
+) -> Result<gst::Device, BlockBuildError> {
+    let device_id = parse_string(properties, "device");
+    if device_id.is_empty() {
+        return Err(BlockBuildError::InvalidConfiguration(
+            "No device selected".to_string(),
+        ));
+    }
+
+    find_device(&device_id).ok_or_else(|| {
+        BlockBuildError::InvalidConfiguration(format!(
+            "Device '{}' not found (unplugged?)",
+            device_id
+        ))
+    })
+}
+
+/// Create the source element for the selected device and the caps to request from it.
+fn create_device_source(
+    instance_id: &str,
+    properties: &HashMap<String, PropertyValue>,
+) -> Result<(gst::Element, gst::Caps), BlockBuildError> {
+    let device = selected_device(properties)?;
+
+    let caps_str = parse_string(properties, "caps");
+    let caps = if caps_str.is_empty() {
+        gst::Caps::new_any()
+    } else {
+        let caps = caps_str.parse::<gst::Caps>().map_err(|_| {
+            BlockBuildError::InvalidConfiguration(format!("Invalid caps: {}", caps_str))
+        })?;
+        // The format enum lists the modes of all discovered devices, so the
+        // chosen one may belong to another device.
+        if !device.caps().is_some_and(|c| c.can_intersect(&caps)) {
+            return Err(BlockBuildError::InvalidConfiguration(format!(
+                "Format '{}' is not advertised by device '{}', select one of its formats or Auto",
+                caps_str,
+                device.display_name()
+            )));
+        }
+        caps
+    };
+
+    let source_id = format!("{}:source", instance_id);
+    let source = device.create_element(Some(&source_id)).map_err(|e| {
+        BlockBuildError::ElementCreation(format!("{}: {}", device.display_name(), e))
+    })?;
+
+    info!(
+        "Device block {}: '{}' ({}) with caps {}",
+        instance_id,
+        device.display_name(),
+        source
+            .factory()
+            .map(|f| f.name().to_string())
+            .unwrap_or_default(),
+        if caps_str.is_empty() {
+            "auto"
+        } else {
+            &caps_str
+        }
+    );
+
+    Ok((source, caps))
+}
+
+/// Device Video Input block builder.
+pub struct DeviceVideoInputBuilder;
+
+impl BlockBuilder for DeviceVideoInputBuilder {
+    fn build(
+        &self,
+        instance_id: &str,
+        properties: &HashMap<String, PropertyValue>,
+        _ctx: &BlockBuildContext,
+    ) -> Result<BlockBuildResult, BlockBuildError> {
+        info!(
+            "Building Device Video Input block instance: {}",
+            instance_id
+        );
+
+        let (source, caps) = create_device_source(instance_id, properties)?;
+        let is_jpeg = caps
+            .structure(0)
+            .map(|s| s.name() == "image/jpeg")
+            .unwrap_or(false);
+
+        let source_id = format!("{}:source", instance_id);
+        let capsfilter_id = format!("{}:capsfilter", instance_id);
+        let decoder_id = format!("{}:decoder", instance_id);
+        let convert_id = format!("{}:videoconvert", instance_id);
+
+        let capsfilter = gst::ElementFactory::make("capsfilter")
+            .name(&capsfilter_id)
+            .property("caps", &caps)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("capsfilter: {}", e)))?;
+
+        // MJPEG modes (common on USB webcams) need decoding before conversion
+        let decoder = if is_jpeg { "jpegdec" } else { "identity" };
+        let decoder_element = gst::ElementFactory::make(decoder)
+            .name(&decoder_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", decoder, e)))?;
+
+        let convert_element_name = video_convert_mode().element_name();
+        let videoconvert = gst::ElementFactory::make(convert_element_name)
+            .name(&convert_id)
+            .build()
+            .map_err(|e| {
+                BlockBuildError::ElementCreation(format!("{}: {}", convert_element_name, e))
+            })?;
+
+        let internal_links = vec![
+            (
+                ElementPadRef::pad(&source_id, "src"),
+                ElementPadRef::pad(&capsfilter_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&capsfilter_id, "src"),
+                ElementPadRef::pad(&decoder_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&decoder_id, "src"),
+                ElementPadRef::pad(&convert_id, "sink"),
+            ),
+        ];
+
+        Ok(BlockBuildResult {
+            elements: vec![
+                (source_id, source),
+                (capsfilter_id, capsfilter),
+                (decoder_id, decoder_element),
+                (convert_id, videoconvert),
+            ],
+            internal_links,
+            bus_message_handler: None,
+            pad_properties: HashMap::new(),
+        })
+    }
+}
+
+/// Device Audio Input block builder.
+pub struct DeviceAudioInputBuilder;
+
+impl BlockBuilder for DeviceAudioInputBuilder {
+    fn build(
+        &self,
+        instance_id: &str,
+        properties: &HashMap<String, PropertyValue>,
+        _ctx: &BlockBuildContext,
+    ) -> Result<BlockBuildResult, BlockBuildError> {
+        info!(
+            "Building Device Audio Input block instance: {}",
+            instance_id
+        );
+
+        let (source, caps) = create_device_source(instance_id, properties)?;
+
+        let source_id = format!("{}:source", instance_id);
+        let capsfilter_id = format!("{}:capsfilter", instance_id);
+        let convert_id = format!("{}:audioconvert", instance_id);
+        let resample_id = format!("{}:audioresample", instance_id);
+
+        let capsfilter = gst::ElementFactory::make("capsfilter")
+            .name(&capsfilter_id)
+            .property("caps", &caps)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("capsfilter: {}", e)))?;
+
+        let audioconvert = gst::ElementFactory::make("audioconvert")
+            .name(&convert_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("audioconvert: {}", e)))?;
+
+        let audioresample = gst::ElementFactory::make("audioresample")
+            .name(&resample_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("audioresample: {}", e)))?;
+
+        let internal_links = vec![
+            (
+                ElementPadRef::pad(&source_id, "src"),
+                ElementPadRef::pad(&capsfilter_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&capsfilter_id, "src"),
+                ElementPadRef::pad(&convert_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&convert_id, "src"),
+                ElementPadRef::pad(&resample_id, "sink"),
+            ),
+        ];
+
+        Ok(BlockBuildResult {
+            elements: vec![
+                (source_id, source),
+                (capsfilter_id, capsfilter),
+                (convert_id, audioconvert),
+                (resample_id, audioresample),
+            ],
+            internal_links,
+            bus_message_handler: None,
+            pad_properties: HashMap::new(),
+        })
+    }
+}
+
+/// Device Audio Output block builder.
+pub struct DeviceAudioOutputBuilder;
+
+impl BlockBuilder for DeviceAudioOutputBuilder {
+    fn build(
+        &self,
+        instance_id: &str,
+        properties: &HashMap<String, PropertyValue>,
+        _ctx: &BlockBuildContext,
+    ) -> Result<BlockBuildResult, BlockBuildError> {
+        info!(
+            "Building Device Audio Output block instance: {}",
+            instance_id
+        );
+
+        let device = selected_device(properties)?;
+
+        let buffer_time_ms = parse_u64(properties, "buffer_time_ms", 200);
+        let latency_time_ms = parse_u64(properties, "latency_time_ms", 10);
+        let drift_tolerance_ms = parse_u64(properties, "drift_tolerance_ms", 40);
+        let slave_method = match parse_string(properties, "slave_method").as_str() {
+            "" | "resample" => "resample",
+            "skew" => "skew",
+            "none" => "none",
+            other => {
+                return Err(BlockBuildError::InvalidProperty(format!(
+                    "slave_method must be resample, skew or none, got '{}'",
+                    other
+                )))
+            }
+        };
+        let sync = properties
+            .get("sync")
+            .and_then(|v| match v {
+                PropertyValue::Bool(b) => Some(*b),
+                _ => None,
+            })
+            .unwrap_or(true);
+
+        if latency_time_ms == 0 || buffer_time_ms < latency_time_ms {
+            return Err(BlockBuildError::InvalidConfiguration(format!(
+                "Buffer time ({} ms) must be at least the latency time ({} ms), which must be non-zero",
+                buffer_time_ms, latency_time_ms
+            )));
+        }
+
+        let queue_id = format!("{}:queue", instance_id);
+        let convert_id = format!("{}:audioconvert", instance_id);
+        let resample_id = format!("{}:audioresample", instance_id);
+        let sink_id = format!("{}:sink", instance_id);
+
+        let queue = gst::ElementFactory::make("queue")
+            .name(&queue_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("queue: {}", e)))?;
+
+        let audioconvert = gst::ElementFactory::make("audioconvert")
+            .name(&convert_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("audioconvert: {}", e)))?;
+
+        let audioresample = gst::ElementFactory::make("audioresample")
+            .name(&resample_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("audioresample: {}", e)))?;
+
+        let sink = device.create_element(Some(&sink_id)).map_err(|e| {
+            BlockBuildError::ElementCreation(format!("{}: {}", device.display_name(), e))
+        })?;
+        sink.set_property("sync", sync);
+
+        // Buffering and clock slaving only exist on audiobasesink-derived sinks
+        // (ALSA, PulseAudio, ...); other sinks manage this themselves.
+        if sink.find_property("slave-method").is_some() {
+            sink.set_property("buffer-time", (buffer_time_ms * 1000) as i64);
+            sink.set_property("latency-time", (latency_time_ms * 1000) as i64);
+            sink.set_property("drift-tolerance", (drift_tolerance_ms * 1000) as i64);
+            sink.set_property_from_str("slave-method", slave_method);
+        } else {
+            warn!(
+                "Device sink for '{}' is not an audiobasesink, ignoring buffer/drift settings",
+                device.display_name()
+            );
+        }
+
+        info!(
+            "Device Audio Output {}: '{}' buffer={}ms latency={}ms slave-method={} drift-tolerance={}ms",
+            instance_id,
+            device.display_name(),
+            buffer_time_ms,
+            latency_time_ms,
+            slave_method,
+            drift_tolerance_ms
+        );
+
+        let internal_links = vec![
+            (
+                ElementPadRef::pad(&queue_id, "src"),
+                ElementPadRef::pad(&convert_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&convert_id, "src"),
+                ElementPadRef::pad(&resample_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&resample_id, "src"),
+                ElementPadRef::pad(&sink_id, "sink"),
+            ),
+        ];
+
+        Ok(BlockBuildResult {
+            elements: vec![
+                (queue_id, queue),
+                (convert_id, audioconvert),
+                (resample_id, audioresample),
+                (sink_id, sink),
+            ],
+            internal_links,
+            bus_message_handler: None,
+            pad_properties: HashMap::new(),
+        })
+    }
+}
+
+/// Get metadata for device blocks (for UI/API).
+pub fn get_blocks() -> Vec<BlockDefinition> {
+    vec![
+        device_video_input_definition(),
+        device_audio_input_definition(),
+        device_audio_output_definition(),
+    ]
+}
+
+/// `device` property shared by all device blocks.
+///
+/// The enum values are filled in by [`populate_device_options`].
+fn device_property(kind: &str) -> ExposedProperty {
+    ExposedProperty {
+        name: "device".to_string(),
+        label: "Device".to_string(),
+        description: format!("Discovered {} device to use", kind),
+        property_type: PropertyType::Enum { values: vec![] },
+        default_value: Some(PropertyValue::String("".to_string())),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: "device".to_string(),
+            transform: None,
+        },
+        live: false,
+    }
+}
+
+/// `caps` property of the input blocks, filled like [`device_property`].
+fn caps_property() -> ExposedProperty {
+    ExposedProperty {
+        name: "caps".to_string(),
+        label: "Format".to_string(),
+        description: "Mode advertised by the selected device. Auto lets the device negotiate."
+            .to_string(),
+        property_type: PropertyType::Enum { values: vec![] },
+        default_value: Some(PropertyValue::String("".to_string())),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: "caps".to_string(),
+            transform: None,
+        },
+        live: false,
+    }
+}
+
+/// Get Device Video Input block definition (metadata only).
+fn device_video_input_definition() -> BlockDefinition {
+    BlockDefinition {
+        id: "builtin.device_video_input".to_string(),
+        name: "Video Device Input".to_string(),
+        description: "Captures video from a local device discovered by the device monitor (V4L2, PipeWire, ...).".to_string(),
+        category: "Inputs".to_string(),
+        exposed_properties: vec![device_property("video capture"), caps_property()],
+        external_pads: ExternalPads {
+            inputs: vec![],
+            outputs: vec![ExternalPad {
+                label: None,
+                name: "video_out".to_string(),
+                media_type: MediaType::Video,
+                internal_element_id: "videoconvert".to_string(),
+                internal_pad_name: "src".to_string(),
+            }],
+        },
+        built_in: true,
+        ui_metadata: Some(BlockUIMetadata {
+            icon: Some("📷".to_string()),
+            width: Some(1.5),
+            height: Some(1.5),
+            ..Default::default()
+        }),
+    }
+}
+
+/// Get Device Audio Input block definition (metadata only).
+fn device_audio_input_definition() -> BlockDefinition {
+    BlockDefinition {
+        id: "builtin.device_audio_input".to_string(),
+        name: "Audio Device Input".to_string(),
+        description: "Captures audio from a local device discovered by the device monitor (PipeWire, PulseAudio, ALSA, ...).".to_string(),
+        category: "Inputs".to_string(),
+        exposed_properties: vec![device_property("audio capture"), caps_property()],
+        external_pads: ExternalPads {
+            inputs: vec![],
+            outputs: vec![ExternalPad {
+                label: None,
+                name: "audio_out".to_string(),
+                media_type: MediaType::Audio,
+                internal_element_id: "audioresample".to_string(),
+                internal_pad_name: "src".to_string(),
+            }],
+        },
+        built_in: true,
+        ui_metadata: Some(BlockUIMetadata {
+            icon: Some("🎤".to_string()),
+            width: Some(1.5),
+            height: Some(1.5),
+            ..Default::default()
+        }),
+    }
+}
+
+/// Get Device Audio Output block definition (metadata only).
+fn device_audio_output_definition() -> BlockDefinition {
+    BlockDefinition {
+        id: "builtin.device_audio_output".to_string(),
+        name: "Audio Device Output".to_string(),
+        description: "Plays audio on a local sound card or virtual sink discovered by the device monitor. The device clock is slaved to the pipeline clock to absorb drift.".to_string(),
+        category: "Outputs".to_string(),
+        exposed_properties: vec![
+            device_property("audio playback"),
+            ExposedProperty {
+                name: "buffer_time_ms".to_string(),
+                label: "Buffer Time (ms)".to_string(),
+                description: "Size of the device ring buffer. Larger values tolerate more scheduling jitter at the cost of latency.".to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(200)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "buffer_time_ms".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "latency_time_ms".to_string(),
+                label: "Latency Time (ms)".to_string(),
+                description: "Size of one ring buffer segment (device period)".to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(10)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "latency_time_ms".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "slave_method".to_string(),
+                label: "Drift Handling".to_string(),
+                description: "How the device clock follows the pipeline clock".to_string(),
+                property_type: PropertyType::Enum {
+                    values: vec![
+                        EnumValue {
+                            value: "resample".to_string(),
+                            label: Some("Resample (smooth)".to_string()),
+                        },
+                        EnumValue {
+                            value: "skew".to_string(),
+                            label: Some("Skew (drop/insert samples)".to_string()),
+                        },
+                        EnumValue {
+                            value: "none".to_string(),
+                            label: Some("None (free-running)".to_string()),
+                        },
+                    ],
+                },
+                default_value: Some(PropertyValue::String("resample".to_string())),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "slave_method".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "drift_tolerance_ms".to_string(),
+                label: "Drift Tolerance (ms)".to_string(),
+                description: "Timestamp error tolerated before the sink resyncs".to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(40)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "drift_tolerance_ms".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "sync".to_string(),
+                label: "Sync".to_string(),
+                description: "Synchronize playback to the pipeline clock".to_string(),
+                property_type: PropertyType::Bool,
+                default_value: Some(PropertyValue::Bool(true)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "sync".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+        ],
+        external_pads: ExternalPads {
+            inputs: vec![ExternalPad {
+                label: None,
+                name: "audio_in".to_string(),
+                media_type: MediaType::Audio,
+                internal_element_id: "queue".to_string(),
+                internal_pad_name: "sink".to_string(),
+            }],
+            outputs: vec![],
+        },
+        built_in: true,
+        ui_metadata: Some(BlockUIMetadata {
+            icon: Some("🔊".to_string()),
+            width: Some(1.5),
+            height: Some(1.5),
+            ..Default::default()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn device(id: &str, name: &str, category: DeviceCategory, caps: &[&str]) -> DiscoveredDevice {
+        DiscoveredDevice {
+            id: id.to_string(),
+            name: name.to_string(),
+            device_class: String::new(),
+            category,
+            provider: String::new(),
+            properties: HashMap::new(),
+            caps: caps.iter().map(|c| c.to_string()).collect(),
+            first_seen: Instant::now(),
+            last_seen: Instant::now(),
+        }
+    }
+
+    fn enum_values(block: &BlockDefinition, prop: &str) -> Vec<String> {
+        let prop = block
+            .exposed_properties
+            .iter()
+            .find(|p| p.name == prop)
+            .unwrap();
+        match &prop.property_type {
+            PropertyType::Enum { values } => values.iter().map(|v| v.value.clone()).collect(),
+            _ => panic!("not an enum"),
+        }
+    }
+
+    #[test]
+    fn populate_filters_devices_by_category() {
+        let mut blocks = get_blocks();
+        let devices = vec![
+            device(
+                "dev-b",
+                "Webcam",
+                DeviceCategory::VideoSource,
+                &["video/x-raw"],
+            ),
+            device(
+                "dev-a",
+                "Mic",
+                DeviceCategory::AudioSource,
+                &["audio/x-raw"],
+            ),
+            device(
+                "dev-c",
+                "Loopback",
+                DeviceCategory::VideoSource,
+                &["video/x-raw"],
+            ),
+            device(
+                "dev-d",
+                "Speakers",
+                DeviceCategory::AudioSink,
+                &["audio/x-raw"],
+            ),
+        ];
+        populate_device_options(&mut blocks, &devices);
+
+        assert_eq!(
+            enum_values(&blocks[0], "device"),
+            vec!["", "dev-c", "dev-b"]
+        );
+        assert_eq!(enum_values(&blocks[1], "device"), vec!["", "dev-a"]);
+        assert_eq!(enum_values(&blocks[2], "device"), vec!["", "dev-d"]);
+    }
+
+    #[test]
+    fn populate_deduplicates_caps() {
+        let mut blocks = get_blocks();
+        let devices = vec![
+            device(
+                "dev-a",
+                "A",
+                DeviceCategory::VideoSource,
+                &["image/jpeg", "video/x-raw"],
+            ),
+            device("dev-b", "B", DeviceCategory::VideoSource, &["video/x-raw"]),
+        ];
+        populate_device_options(&mut blocks, &devices);
+
+        assert_eq!(
+            enum_values(&blocks[0], "caps"),
+            vec!["", "image/jpeg", "video/x-raw"]
+        );
+        let prop = blocks[0]
+            .exposed_properties
+            .iter()
+            .find(|p| p.name == "caps")
+            .unwrap();
+        let PropertyType::Enum { values } = &prop.property_type else {
+            panic!("not an enum");
+        };
+        assert_eq!(values[1].label.as_deref(), Some("A: image/jpeg"));
+        assert_eq!(values[2].label.as_deref(), Some("A, B: video/x-raw"));
+    }
+}
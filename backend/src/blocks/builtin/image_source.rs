@@ -0,0 +1,634 @@
+//! Still image source block.
+//!
+//! Decodes a PNG or JPEG from the media directory and outputs it as a live
+//! video stream. Chain: appsrc -> imagefreeze -> capsfilter.
+//!
+//! The image is scaled to the output resolution here (letterbox, crop or
+//! stretch) and pushed to appsrc as a single RGBA frame; imagefreeze repeats
+//! it at the output framerate. Changing `image` or `fit` while the flow is
+//! running renders a replacement frame, optionally cross-faded from the
+//! current one over `crossfade_ms`.
+
+use crate::blocks::builtin::vision_mixer::properties as vm_props;
+use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use image::imageops::FilterType;
+use image::{Rgba, RgbaImage};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, RwLock, Weak};
+use std::time::Duration;
+use strom_types::{
+    block::*, common_video_framerate_enum_values, common_video_resolution_enum_values,
+    element::ElementPadRef, FlowId, PropertyValue, *,
+};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+const DEFAULT_RESOLUTION: &str = "1920x1080";
+const DEFAULT_FRAMERATE: &str = "30/1";
+
+/// Global registry of running image sources, used to apply live property changes.
+pub static IMAGE_SOURCE_REGISTRY: LazyLock<ImageSourceRegistry> =
+    LazyLock::new(ImageSourceRegistry::new);
+
+/// Registry key for looking up image source instances.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ImageSourceKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// How the image is fitted to the output resolution when aspect ratios differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Scale to fit inside the frame, padding with transparent black bars.
+    Letterbox,
+    /// Scale to fill the frame, cropping the overflow (centered).
+    Crop,
+    /// Scale to the exact output size, ignoring aspect ratio.
+    Stretch,
+}
+
+impl ImageFit {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "letterbox" => Some(Self::Letterbox),
+            "crop" => Some(Self::Crop),
+            "stretch" => Some(Self::Stretch),
+            _ => None,
+        }
+    }
+}
+
+/// Compute the scaled image size and its offset within the output frame.
+///
+/// Returns `(width, height, x, y)`. For `Crop` the offsets are negative
+/// (the scaled image overflows the frame).
+fn fit_rect(src: (u32, u32), dst: (u32, u32), fit: ImageFit) -> (u32, u32, i64, i64) {
+    let (sw, sh) = (src.0.max(1) as f64, src.1.max(1) as f64);
+    let (dw, dh) = (dst.0 as f64, dst.1 as f64);
+    let scale = match fit {
+        ImageFit::Stretch => return (dst.0, dst.1, 0, 0),
+        ImageFit::Letterbox => (dw / sw).min(dh / sh),
+        ImageFit::Crop => (dw / sw).max(dh / sh),
+    };
+    let w = ((sw * scale).round() as u32).max(1);
+    let h = ((sh * scale).round() as u32).max(1);
+    let x = (dst.0 as i64 - w as i64) / 2;
+    let y = (dst.1 as i64 - h as i64) / 2;
+    (w, h, x, y)
+}
+
+/// Decode `path` and render it into an RGBA frame of the given size.
+fn render_image(path: &Path, size: (u32, u32), fit: ImageFit) -> Result<Vec<u8>, String> {
+    let decoded = image::open(path)
+        .map_err(|e| format!("Failed to decode {}: {}", path.display(), e))?
+        .to_rgba8();
+
+    let (w, h, x, y) = fit_rect(decoded.dimensions(), size, fit);
+    let scaled = image::imageops::resize(&decoded, w, h, FilterType::Triangle);
+
+    let frame = match fit {
+        ImageFit::Stretch => scaled,
+        ImageFit::Letterbox => {
+            let mut canvas = RgbaImage::from_pixel(size.0, size.1, Rgba([0, 0, 0, 0]));
+            image::imageops::replace(&mut canvas, &scaled, x, y);
+            canvas
+        }
+        ImageFit::Crop => {
+            image::imageops::crop_imm(&scaled, (-x) as u32, (-y) as u32, size.0, size.1).to_image()
+        }
+    };
+    Ok(frame.into_raw())
+}
+
+/// Linear blend between two equally sized frames, `t` in 0.0..=1.0.
+fn blend_frames(from: &[u8], to: &[u8], t: f64) -> Vec<u8> {
+    let w = (t.clamp(0.0, 1.0) * 256.0) as u32;
+    from.iter()
+        .zip(to)
+        .map(|(&a, &b)| ((a as u32 * (256 - w) + b as u32 * w) >> 8) as u8)
+        .collect()
+}
+
+/// Frame memory shared between the state and buffers handed to GStreamer.
+struct FrameData(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for FrameData {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+struct ImageSettings {
+    image: String,
+    fit: ImageFit,
+    crossfade_ms: u64,
+}
+
+/// Runtime state of one image source instance.
+pub struct ImageSourceState {
+    appsrc: gst_app::AppSrc,
+    media_path: PathBuf,
+    size: (u32, u32),
+    framerate: (i32, i32),
+    settings: Mutex<ImageSettings>,
+    /// Last frame pushed to appsrc (the starting point of a cross-fade).
+    current_frame: Mutex<Arc<Vec<u8>>>,
+    /// Bumped on every image change so a running cross-fade can bail out.
+    fade_generation: AtomicU64,
+}
+
+impl ImageSourceState {
+    /// Resolve an image path relative to the media directory.
+    fn resolve_path(&self, image: &str) -> PathBuf {
+        let clean = image
+            .strip_prefix("./media/")
+            .or_else(|| image.strip_prefix("media/"))
+            .unwrap_or(image);
+        if Path::new(clean).is_absolute() {
+            PathBuf::from(clean)
+        } else {
+            self.media_path.join(clean)
+        }
+    }
+
+    fn render(&self, image: &str, fit: ImageFit) -> Result<Vec<u8>, String> {
+        if image.is_empty() {
+            return Ok(vec![0; (self.size.0 * self.size.1 * 4) as usize]);
+        }
+        render_image(&self.resolve_path(image), self.size, fit)
+    }
+
+    /// Switch to a new image, cross-fading if `crossfade_ms` is set.
+    pub fn set_image(self: &Arc<Self>, image: &str) -> Result<(), String> {
+        let fit = self.settings.lock().fit;
+        let frame = self.render(image, fit)?;
+        self.settings.lock().image = image.to_string();
+        info!("Image source switching to '{}'", image);
+        self.show(frame);
+        Ok(())
+    }
+
+    /// Change the fit mode and re-render the current image.
+    pub fn set_fit(self: &Arc<Self>, fit: ImageFit) -> Result<(), String> {
+        let image = self.settings.lock().image.clone();
+        let frame = self.render(&image, fit)?;
+        self.settings.lock().fit = fit;
+        self.show(frame);
+        Ok(())
+    }
+
+    pub fn set_crossfade_ms(&self, crossfade_ms: u64) {
+        self.settings.lock().crossfade_ms = crossfade_ms;
+    }
+
+    fn show(self: &Arc<Self>, frame: Vec<u8>) {
+        let generation = self.fade_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let crossfade_ms = self.settings.lock().crossfade_ms;
+        let target = Arc::new(frame);
+
+        if crossfade_ms == 0 {
+            self.push_frame(target);
+            return;
+        }
+
+        let (fps_n, fps_d) = self.framerate;
+        let steps = ((crossfade_ms * fps_n as u64) / (fps_d as u64 * 1000)).max(1);
+        let interval = Duration::from_millis(crossfade_ms) / steps as u32;
+        let from = Arc::clone(&self.current_frame.lock());
+        let weak = Arc::downgrade(self);
+        let mut step = 0;
+
+        // Each tick pushes one blended frame; a newer image change or a
+        // dropped source ends the fade.
+        gst::glib::timeout_add(interval, move || {
+            let Some(state) = weak.upgrade() else {
+                return gst::glib::ControlFlow::Break;
+            };
+            if state.fade_generation.load(Ordering::SeqCst) != generation {
+                return gst::glib::ControlFlow::Break;
+            }
+            step += 1;
+            if step >= steps {
+                state.push_frame(Arc::clone(&target));
+                return gst::glib::ControlFlow::Break;
+            }
+            let blended = blend_frames(&from, &target, step as f64 / steps as f64);
+            state.push_frame(Arc::new(blended));
+            gst::glib::ControlFlow::Continue
+        });
+    }
+
+    fn push_frame(&self, frame: Arc<Vec<u8>>) {
+        *self.current_frame.lock() = Arc::clone(&frame);
+        let buffer = gst::Buffer::from_slice(FrameData(frame));
+        if let Err(e) = self.appsrc.push_buffer(buffer) {
+            debug!("Image source appsrc rejected frame: {:?}", e);
+        }
+    }
+}
+
+/// Global registry for image source instances.
+pub struct ImageSourceRegistry {
+    sources: RwLock<HashMap<ImageSourceKey, Arc<ImageSourceState>>>,
+}
+
+impl ImageSourceRegistry {
+    pub fn new() -> Self {
+        Self {
+            sources: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, key: ImageSourceKey, state: Arc<ImageSourceState>) {
+        if let Ok(mut sources) = self.sources.write() {
+            sources.insert(key, state);
+        }
+    }
+
+    pub fn get(&self, key: &ImageSourceKey) -> Option<Arc<ImageSourceState>> {
+        self.sources.read().ok()?.get(key).cloned()
+    }
+
+    /// Remove all image source entries for a given flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        if let Ok(mut sources) = self.sources.write() {
+            sources.retain(|k, _| k.flow_id != *flow_id);
+        }
+    }
+}
+
+impl Default for ImageSourceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply a live property change to a running image source.
+///
+/// Returns `None` if `element_id`/`property_name` is not an image source
+/// property, so the caller can fall through to the regular property path.
+pub fn update_property(
+    flow_id: &FlowId,
+    element_id: &str,
+    property_name: &str,
+    value: &PropertyValue,
+) -> Option<Result<(), String>> {
+    let block_id = element_id.strip_suffix(":source")?;
+    if !matches!(property_name, "image" | "fit" | "crossfade_ms") {
+        return None;
+    }
+    let state = IMAGE_SOURCE_REGISTRY.get(&ImageSourceKey {
+        flow_id: *flow_id,
+        block_id: block_id.to_string(),
+    })?;
+
+    Some(match (property_name, value) {
+        ("image", PropertyValue::String(image)) => state.set_image(image),
+        ("fit", PropertyValue::String(fit)) => match ImageFit::parse(fit) {
+            Some(fit) => state.set_fit(fit),
+            None => Err(format!("Unknown fit mode '{}'", fit)),
+        },
+        ("crossfade_ms", PropertyValue::UInt(ms)) => {
+            state.set_crossfade_ms(*ms);
+            Ok(())
+        }
+        ("crossfade_ms", PropertyValue::Int(ms)) if *ms >= 0 => {
+            state.set_crossfade_ms(*ms as u64);
+            Ok(())
+        }
+        _ => Err(format!("Unexpected value {:?}", value)),
+    })
+}
+
+/// Image Source block builder.
+pub struct ImageSourceBuilder;
+
+impl BlockBuilder for ImageSourceBuilder {
+    fn build(
+        &self,
+        instance_id: &str,
+        properties: &HashMap<String, PropertyValue>,
+        _ctx: &BlockBuildContext,
+    ) -> Result<BlockBuildResult, BlockBuildError> {
+        info!("Building Image Source block instance: {}", instance_id);
+
+        let image = properties
+            .get("image")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => Some(s.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let fit = properties
+            .get("fit")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => ImageFit::parse(s),
+                _ => None,
+            })
+            .unwrap_or(ImageFit::Letterbox);
+
+        let (width, height) =
+            vm_props::parse_resolution(properties, "resolution", DEFAULT_RESOLUTION);
+        let framerate = vm_props::parse_framerate(properties, "framerate", DEFAULT_FRAMERATE);
+        let crossfade_ms = vm_props::parse_u64(properties, "crossfade_ms", 0);
+
+        let flow_id: FlowId = properties
+            .get("_flow_id")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => Uuid::parse_str(s).ok(),
+                _ => None,
+            })
+            .unwrap_or_else(Uuid::nil);
+
+        let media_path: PathBuf = properties
+            .get("_media_path")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => Some(PathBuf::from(s)),
+                _ => None,
+            })
+            .unwrap_or_else(|| PathBuf::from("./media"));
+
+        let source_id = format!("{}:source", instance_id);
+        let freeze_id = format!("{}:freeze", instance_id);
+        let output_id = format!("{}:output", instance_id);
+
+        // appsrc carries still frames only; imagefreeze is the live clock source
+        let source_caps = gst::Caps::builder("video/x-raw")
+            .field("format", "RGBA")
+            .field("width", width as i32)
+            .field("height", height as i32)
+            .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+            .field("framerate", gst::Fraction::new(0, 1))
+            .build();
+
+        let appsrc = gst_app::AppSrc::builder()
+            .name(&source_id)
+            .caps(&source_caps)
+            .format(gst::Format::Time)
+            .is_live(false)
+            .automatic_eos(false)
+            .build();
+
+        let freeze = gst::ElementFactory::make("imagefreeze")
+            .name(&freeze_id)
+            .property("is-live", true)
+            .property("allow-replace", true)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("imagefreeze: {}", e)))?;
+
+        let output_caps = gst::Caps::builder("video/x-raw")
+            .field("format", "RGBA")
+            .field("width", width as i32)
+            .field("height", height as i32)
+            .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+            .field("framerate", gst::Fraction::new(framerate.0, framerate.1))
+            .build();
+
+        let output = gst::ElementFactory::make("capsfilter")
+            .name(&output_id)
+            .property("caps", &output_caps)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("capsfilter: {}", e)))?;
+
+        let state = Arc::new(ImageSourceState {
+            appsrc: appsrc.clone(),
+            media_path,
+            size: (width, height),
+            framerate,
+            settings: Mutex::new(ImageSettings {
+                image: image.clone(),
+                fit,
+                crossfade_ms,
+            }),
+            current_frame: Mutex::new(Arc::new(Vec::new())),
+            fade_generation: AtomicU64::new(0),
+        });
+
+        let initial_frame = state
+            .render(&image, fit)
+            .map_err(BlockBuildError::InvalidConfiguration)?;
+        *state.current_frame.lock() = Arc::new(initial_frame);
+
+        // Push the initial frame once appsrc is running; later frames are
+        // pushed directly on property changes.
+        let weak_state: Weak<ImageSourceState> = Arc::downgrade(&state);
+        let initial_sent = AtomicBool::new(false);
+        appsrc.set_callbacks(
+            gst_app::AppSrcCallbacks::builder()
+                .need_data(move |_, _| {
+                    if initial_sent.swap(true, Ordering::SeqCst) {
+                        return;
+                    }
+                    match weak_state.upgrade() {
+                        Some(state) => {
+                            let frame = Arc::clone(&state.current_frame.lock());
+                            state.push_frame(frame);
+                        }
+                        None => warn!("Image source state dropped before first frame"),
+                    }
+                })
+                .build(),
+        );
+
+        IMAGE_SOURCE_REGISTRY.register(
+            ImageSourceKey {
+                flow_id,
+                block_id: instance_id.to_string(),
+            },
+            state,
+        );
+
+        info!(
+            "Image Source {}: '{}' at {}x{} @ {}/{} ({:?})",
+            instance_id, image, width, height, framerate.0, framerate.1, fit
+        );
+
+        let internal_links = vec![
+            (
+                ElementPadRef::pad(&source_id, "src"),
+                ElementPadRef::pad(&freeze_id, "sink"),
+            ),
+            (
+                ElementPadRef::pad(&freeze_id, "src"),
+                ElementPadRef::pad(&output_id, "sink"),
+            ),
+        ];
+
+        Ok(BlockBuildResult {
+            elements: vec![
+                (source_id, appsrc.upcast()),
+                (freeze_id, freeze),
+                (output_id, output),
+            ],
+            internal_links,
+            bus_message_handler: None,
+            pad_properties: HashMap::new(),
+        })
+    }
+}
+
+/// Get metadata for Image Source blocks (for UI/API).
+pub fn get_blocks() -> Vec<BlockDefinition> {
+    vec![image_source_definition()]
+}
+
+/// Get Image Source block definition (metadata only).
+fn image_source_definition() -> BlockDefinition {
+    BlockDefinition {
+        id: "builtin.image_source".to_string(),
+        name: "Image Source".to_string(),
+        description: "Outputs a still image (PNG/JPEG) from the media directory as a live video stream. Useful for holding slates and static backgrounds. The image can be swapped while running, with an optional cross-fade.".to_string(),
+        category: "Inputs".to_string(),
+        exposed_properties: vec![
+            ExposedProperty {
+                name: "image".to_string(),
+                label: "Image".to_string(),
+                description: "Image file path, relative to the media directory. Leave empty for a blank frame.".to_string(),
+                property_type: PropertyType::String,
+                default_value: Some(PropertyValue::String("".to_string())),
+                mapping: PropertyMapping {
+                    element_id: "source".to_string(),
+                    property_name: "image".to_string(),
+                    transform: None,
+                },
+                live: true,
+            },
+            ExposedProperty {
+                name: "resolution".to_string(),
+                label: "Resolution".to_string(),
+                description: "Output video resolution".to_string(),
+                property_type: PropertyType::Enum {
+                    values: common_video_resolution_enum_values(false),
+                },
+                default_value: Some(PropertyValue::String(DEFAULT_RESOLUTION.to_string())),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "resolution".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "framerate".to_string(),
+                label: "Framerate".to_string(),
+                description: "Output framerate".to_string(),
+                property_type: PropertyType::Enum {
+                    values: common_video_framerate_enum_values(false),
+                },
+                default_value: Some(PropertyValue::String(DEFAULT_FRAMERATE.to_string())),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "framerate".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "fit".to_string(),
+                label: "Aspect Ratio".to_string(),
+                description: "How to handle images whose aspect ratio differs from the output".to_string(),
+                property_type: PropertyType::Enum {
+                    values: vec![
+                        EnumValue {
+                            value: "letterbox".to_string(),
+                            label: Some("Letterbox (fit inside)".to_string()),
+                        },
+                        EnumValue {
+                            value: "crop".to_string(),
+                            label: Some("Crop (fill frame)".to_string()),
+                        },
+                        EnumValue {
+                            value: "stretch".to_string(),
+                            label: Some("Stretch".to_string()),
+                        },
+                    ],
+                },
+                default_value: Some(PropertyValue::String("letterbox".to_string())),
+                mapping: PropertyMapping {
+                    element_id: "source".to_string(),
+                    property_name: "fit".to_string(),
+                    transform: None,
+                },
+                live: true,
+            },
+            ExposedProperty {
+                name: "crossfade_ms".to_string(),
+                label: "Cross-fade (ms)".to_string(),
+                description: "Cross-fade duration when the image changes while running (0 = cut)".to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(0)),
+                mapping: PropertyMapping {
+                    element_id: "source".to_string(),
+                    property_name: "crossfade_ms".to_string(),
+                    transform: None,
+                },
+                live: true,
+            },
+        ],
+        external_pads: ExternalPads {
+            inputs: vec![],
+            outputs: vec![ExternalPad {
+                label: None,
+                name: "video_out".to_string(),
+                media_type: MediaType::Video,
+                internal_element_id: "output".to_string(),
+                internal_pad_name: "src".to_string(),
+            }],
+        },
+        built_in: true,
+        ui_metadata: Some(BlockUIMetadata {
+            icon: Some("🖼".to_string()),
+            width: Some(1.5),
+            height: Some(1.5),
+            ..Default::default()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_rect_letterbox_pads_narrow_image() {
+        // 4:3 into 16:9 → pillarbox
+        assert_eq!(
+            fit_rect((800, 600), (1920, 1080), ImageFit::Letterbox),
+            (1440, 1080, 240, 0)
+        );
+    }
+
+    #[test]
+    fn fit_rect_crop_fills_frame() {
+        // 4:3 into 16:9 → scale to width, crop top/bottom
+        assert_eq!(
+            fit_rect((800, 600), (1920, 1080), ImageFit::Crop),
+            (1920, 1440, 0, -180)
+        );
+    }
+
+    #[test]
+    fn fit_rect_stretch_uses_output_size() {
+        assert_eq!(
+            fit_rect((800, 600), (1280, 720), ImageFit::Stretch),
+            (1280, 720, 0, 0)
+        );
+    }
+
+    #[test]
+    fn blend_frames_endpoints_and_midpoint() {
+        let a = [0u8, 100, 255, 255];
+        let b = [255u8, 200, 0, 255];
+        assert_eq!(blend_frames(&a, &b, 0.0), a.to_vec());
+        assert_eq!(blend_frames(&a, &b, 1.0), b.to_vec());
+        assert_eq!(blend_frames(&a, &b, 0.5), vec![127, 150, 127, 255]);
+    }
+}
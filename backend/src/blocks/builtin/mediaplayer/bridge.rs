@@ -2,17 +2,18 @@
 //!
 //! The internal pipeline runs `uridecodebin` (decode mode) or `urisourcebin` (passthrough
 //! mode) with `clocksync` for real-time pacing. Appsink callbacks push samples to the
-//! corresponding appsrc in the main pipeline.
+//! corresponding appsrc in the main pipeline. Each decode slot has its own internal
+//! pipeline.
 
-use super::state::MediaPlayerState;
+use super::slot::{DecodeSlot, LiveSource};
+use super::state::{ItemEnd, MediaPlayerState};
 use crate::blocks::BlockBuildError;
 use crate::events::EventBroadcaster;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
-use std::sync::atomic::Ordering;
-use std::sync::Arc;
-use strom_types::{FlowId, StromEvent};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 use tracing::{debug, error, info, warn};
 
 /// Create the internal pipeline for decode mode.
@@ -21,10 +22,11 @@ use tracing::{debug, error, info, warn};
 /// The appsink callbacks push samples to the corresponding appsrc in the main pipeline.
 pub fn create_decode_pipeline(
     instance_id: &str,
+    index: usize,
     state: &Arc<MediaPlayerState>,
     initial_uri: Option<&str>,
-) -> Result<gst::Pipeline, BlockBuildError> {
-    let pipeline_name = format!("mediaplayer-internal-{}", instance_id);
+) -> Result<Arc<DecodeSlot>, BlockBuildError> {
+    let pipeline_name = format!("mediaplayer-internal-{}-{}", instance_id, index);
     let pipeline = gst::Pipeline::builder().name(&pipeline_name).build();
 
     let source_id = format!("{}_uridecodebin", instance_id);
@@ -37,15 +39,15 @@ pub fn create_decode_pipeline(
         source.set_property("uri", uri);
     }
 
-    // Store weak ref to source element in state
-    state.source_element.set(Some(&source));
-
     pipeline
         .add(&source)
         .map_err(|e| BlockBuildError::ElementCreation(format!("add uridecodebin: {}", e)))?;
 
+    let slot = Arc::new(DecodeSlot::new(index, pipeline.clone(), &source));
+
     // Connect pad-added to dynamically create clocksync → appsink chains
     let pipeline_weak = pipeline.downgrade();
+    let slot_weak = Arc::downgrade(&slot);
     let state_weak = Arc::downgrade(state);
     let instance_id_owned = instance_id.to_string();
     let sync = state.sync;
@@ -58,9 +60,9 @@ pub fn create_decode_pipeline(
             Some(p) => p,
             None => return,
         };
-        let state = match state_weak.upgrade() {
-            Some(s) => s,
-            None => return,
+        let (state, slot) = match (state_weak.upgrade(), slot_weak.upgrade()) {
+            (Some(s), Some(slot)) => (s, slot),
+            _ => return,
         };
 
         let caps = pad.current_caps().or_else(|| Some(pad.query_caps(None)));
@@ -78,13 +80,15 @@ pub fn create_decode_pipeline(
             .map(|n| n.starts_with("audio/"))
             .unwrap_or(false);
 
-        if is_video && !state.video_linked.load(Ordering::SeqCst) {
-            if let Some(ref appsrc) = state.video_appsrc {
+        if is_video && !slot.video_linked.load(Ordering::SeqCst) {
+            if let Some(ref appsrc) = state.appsrc_for(slot.index, true) {
                 if let Err(e) = link_pad_through_clocksync(
                     &pipeline,
                     pad,
                     appsrc,
                     &state,
+                    &slot.ts_offset,
+                    &slot.continue_at_ns,
                     &format!("{}_clocksync_video", instance_id_owned),
                     &format!("{}_appsink_video", instance_id_owned),
                     sync,
@@ -92,20 +96,22 @@ pub fn create_decode_pipeline(
                 ) {
                     error!("Media Player: Failed to link video chain: {}", e);
                 } else {
-                    state.video_linked.store(true, Ordering::SeqCst);
+                    slot.video_linked.store(true, Ordering::SeqCst);
                     info!(
                         "Media Player {}: Linked internal video chain",
                         instance_id_owned
                     );
                 }
             }
-        } else if is_audio && !state.audio_linked.load(Ordering::SeqCst) {
-            if let Some(ref appsrc) = state.audio_appsrc {
+        } else if is_audio && !slot.audio_linked.load(Ordering::SeqCst) {
+            if let Some(ref appsrc) = state.appsrc_for(slot.index, false) {
                 if let Err(e) = link_pad_through_clocksync(
                     &pipeline,
                     pad,
                     appsrc,
                     &state,
+                    &slot.ts_offset,
+                    &slot.continue_at_ns,
                     &format!("{}_clocksync_audio", instance_id_owned),
                     &format!("{}_appsink_audio", instance_id_owned),
                     sync,
@@ -113,7 +119,7 @@ pub fn create_decode_pipeline(
                 ) {
                     error!("Media Player: Failed to link audio chain: {}", e);
                 } else {
-                    state.audio_linked.store(true, Ordering::SeqCst);
+                    slot.audio_linked.store(true, Ordering::SeqCst);
                     info!(
                         "Media Player {}: Linked internal audio chain",
                         instance_id_owned
@@ -123,7 +129,7 @@ pub fn create_decode_pipeline(
         }
     });
 
-    Ok(pipeline)
+    Ok(slot)
 }
 
 /// Create the internal pipeline for passthrough mode.
@@ -131,10 +137,11 @@ pub fn create_decode_pipeline(
 /// Pipeline: `urisourcebin(parse-streams=true)` → (pad-added) → `clocksync` → `appsink`
 pub fn create_passthrough_pipeline(
     instance_id: &str,
+    index: usize,
     state: &Arc<MediaPlayerState>,
     initial_uri: Option<&str>,
-) -> Result<gst::Pipeline, BlockBuildError> {
-    let pipeline_name = format!("mediaplayer-internal-{}", instance_id);
+) -> Result<Arc<DecodeSlot>, BlockBuildError> {
+    let pipeline_name = format!("mediaplayer-internal-{}-{}", instance_id, index);
     let pipeline = gst::Pipeline::builder().name(&pipeline_name).build();
 
     let source_id = format!("{}_urisourcebin", instance_id);
@@ -148,15 +155,15 @@ pub fn create_passthrough_pipeline(
         source.set_property("uri", uri);
     }
 
-    // Store weak ref to source element in state
-    state.source_element.set(Some(&source));
-
     pipeline
         .add(&source)
         .map_err(|e| BlockBuildError::ElementCreation(format!("add urisourcebin: {}", e)))?;
 
+    let slot = Arc::new(DecodeSlot::new(index, pipeline.clone(), &source));
+
     // Connect pad-added for passthrough streams
     let pipeline_weak = pipeline.downgrade();
+    let slot_weak = Arc::downgrade(&slot);
     let state_weak = Arc::downgrade(state);
     let instance_id_owned = instance_id.to_string();
     let sync = state.sync;
@@ -176,9 +183,9 @@ pub fn create_passthrough_pipeline(
             Some(p) => p,
             None => return,
         };
-        let state = match state_weak.upgrade() {
-            Some(s) => s,
-            None => return,
+        let (state, slot) = match (state_weak.upgrade(), slot_weak.upgrade()) {
+            (Some(s), Some(slot)) => (s, slot),
+            _ => return,
         };
 
         let caps = pad.current_caps().or_else(|| {
@@ -209,13 +216,15 @@ pub fn create_passthrough_pipeline(
             .map(|n| n.starts_with("audio/"))
             .unwrap_or(false);
 
-        if is_video && !state.video_linked.load(Ordering::SeqCst) {
-            if let Some(ref appsrc) = state.video_appsrc {
+        if is_video && !slot.video_linked.load(Ordering::SeqCst) {
+            if let Some(ref appsrc) = state.appsrc_for(slot.index, true) {
                 if let Err(e) = link_pad_through_clocksync(
                     &pipeline,
                     pad,
                     appsrc,
                     &state,
+                    &slot.ts_offset,
+                    &slot.continue_at_ns,
                     &format!("{}_clocksync_video", instance_id_owned),
                     &format!("{}_appsink_video", instance_id_owned),
                     sync,
@@ -223,20 +232,22 @@ pub fn create_passthrough_pipeline(
                 ) {
                     error!("Media Player: Failed to link video chain: {}", e);
                 } else {
-                    state.video_linked.store(true, Ordering::SeqCst);
+                    slot.video_linked.store(true, Ordering::SeqCst);
                     info!(
                         "Media Player {}: Linked internal video chain (passthrough)",
                         instance_id_owned
                     );
                 }
             }
-        } else if is_audio && !state.audio_linked.load(Ordering::SeqCst) {
-            if let Some(ref appsrc) = state.audio_appsrc {
+        } else if is_audio && !slot.audio_linked.load(Ordering::SeqCst) {
+            if let Some(ref appsrc) = state.appsrc_for(slot.index, false) {
                 if let Err(e) = link_pad_through_clocksync(
                     &pipeline,
                     pad,
                     appsrc,
                     &state,
+                    &slot.ts_offset,
+                    &slot.continue_at_ns,
                     &format!("{}_clocksync_audio", instance_id_owned),
                     &format!("{}_appsink_audio", instance_id_owned),
                     sync,
@@ -244,7 +255,7 @@ pub fn create_passthrough_pipeline(
                 ) {
                     error!("Media Player: Failed to link audio chain: {}", e);
                 } else {
-                    state.audio_linked.store(true, Ordering::SeqCst);
+                    slot.audio_linked.store(true, Ordering::SeqCst);
                     info!(
                         "Media Player {}: Linked internal audio chain (passthrough)",
                         instance_id_owned
@@ -258,13 +269,15 @@ pub fn create_passthrough_pipeline(
                 instance_id_owned, pad_name
             );
 
-            if !state.video_linked.load(Ordering::SeqCst) {
-                if let Some(ref appsrc) = state.video_appsrc {
+            if !slot.video_linked.load(Ordering::SeqCst) {
+                if let Some(ref appsrc) = state.appsrc_for(slot.index, true) {
                     if link_pad_through_clocksync(
                         &pipeline,
                         pad,
                         appsrc,
                         &state,
+                        &slot.ts_offset,
+                        &slot.continue_at_ns,
                         &format!("{}_clocksync_video", instance_id_owned),
                         &format!("{}_appsink_video", instance_id_owned),
                         sync,
@@ -272,7 +285,7 @@ pub fn create_passthrough_pipeline(
                     )
                     .is_ok()
                     {
-                        state.video_linked.store(true, Ordering::SeqCst);
+                        slot.video_linked.store(true, Ordering::SeqCst);
                         info!(
                             "Media Player {}: Linked unknown pad {} to video (heuristic)",
                             instance_id_owned, pad_name
@@ -282,13 +295,15 @@ pub fn create_passthrough_pipeline(
                 }
             }
 
-            if !state.audio_linked.load(Ordering::SeqCst) {
-                if let Some(ref appsrc) = state.audio_appsrc {
+            if !slot.audio_linked.load(Ordering::SeqCst) {
+                if let Some(ref appsrc) = state.appsrc_for(slot.index, false) {
                     if link_pad_through_clocksync(
                         &pipeline,
                         pad,
                         appsrc,
                         &state,
+                        &slot.ts_offset,
+                        &slot.continue_at_ns,
                         &format!("{}_clocksync_audio", instance_id_owned),
                         &format!("{}_appsink_audio", instance_id_owned),
                         sync,
@@ -296,7 +311,7 @@ pub fn create_passthrough_pipeline(
                     )
                     .is_ok()
                     {
-                        state.audio_linked.store(true, Ordering::SeqCst);
+                        slot.audio_linked.store(true, Ordering::SeqCst);
                         info!(
                             "Media Player {}: Linked unknown pad {} to audio (heuristic)",
                             instance_id_owned, pad_name
@@ -310,13 +325,86 @@ pub fn create_passthrough_pipeline(
                 "Media Player {}: Could not link pad {} (video_linked={}, audio_linked={})",
                 instance_id_owned,
                 pad_name,
-                state.video_linked.load(Ordering::SeqCst),
-                state.audio_linked.load(Ordering::SeqCst)
+                slot.video_linked.load(Ordering::SeqCst),
+                slot.audio_linked.load(Ordering::SeqCst)
             );
         }
     });
 
-    Ok(pipeline)
+    Ok(slot)
+}
+
+/// Create the internal pipeline of a live breakaway.
+///
+/// Pipeline: `intersrc` (per channel) → `clocksync` → `appsink`, bridged to the appsrcs
+/// of the active decode slot. The pipeline is left in NULL.
+pub fn create_live_pipeline(
+    state: &MediaPlayerState,
+    video_channel: Option<&str>,
+    audio_channel: Option<&str>,
+) -> Result<LiveSource, String> {
+    let block_id = &state.block_id;
+    let pipeline = gst::Pipeline::builder()
+        .name(format!("mediaplayer-live-{}", block_id))
+        .build();
+    let live = LiveSource::new(pipeline.clone());
+    let slot_index = state.active_slot().map(|s| s.index).unwrap_or(0);
+
+    for (channel, video) in [(video_channel, true), (audio_channel, false)] {
+        let Some(channel) = channel.filter(|c| !c.is_empty()) else {
+            continue;
+        };
+        let media_type = if video { "video" } else { "audio" };
+        let appsrc = state
+            .appsrc_for(slot_index, video)
+            .ok_or_else(|| format!("No {} appsrc", media_type))?;
+
+        let intersrc = gst::ElementFactory::make("intersrc")
+            .name(format!("{}_intersrc_{}", block_id, media_type))
+            .property("producer-name", channel)
+            .build()
+            .map_err(|e| format!("intersrc: {}", e))?;
+        pipeline
+            .add(&intersrc)
+            .map_err(|e| format!("add intersrc: {}", e))?;
+        let src_pad = intersrc
+            .static_pad("src")
+            .ok_or("intersrc has no src pad")?;
+
+        link_pad_through_clocksync(
+            &pipeline,
+            &src_pad,
+            &appsrc,
+            state,
+            &live.ts_offset,
+            &live.continue_at_ns,
+            &format!("{}_live_clocksync_{}", block_id, media_type),
+            &format!("{}_live_appsink_{}", block_id, media_type),
+            state.sync,
+            media_type,
+        )?;
+        info!(
+            "Media Player {}: Live {} from channel '{}'",
+            block_id, media_type, channel
+        );
+    }
+
+    if let Some(bus) = pipeline.bus() {
+        bus.add_signal_watch();
+        let block_id = block_id.clone();
+        bus.connect_message(None, move |_bus, msg| {
+            if let gst::MessageView::Error(err) = msg.view() {
+                error!(
+                    "Media Player {}: Live pipeline error: {} ({:?})",
+                    block_id,
+                    err.error(),
+                    err.debug()
+                );
+            }
+        });
+    }
+
+    Ok(live)
 }
 
 /// Link a dynamic pad through clocksync → appsink, with appsink bridging to the given appsrc.
@@ -324,13 +412,17 @@ pub fn create_passthrough_pipeline(
 /// The bridge computes a timestamp offset from the first buffer:
 ///   `offset = main_running_time - buffer_pts`
 /// and applies it to all subsequent buffers so PTS aligns with the main pipeline clock.
-/// The offset is shared (via `ts_offset`) between audio and video streams for A/V sync.
+/// The offset is shared (via `ts_offset`) between the slot's audio and video streams
+/// for A/V sync. After a gapless switch the first buffer continues where the previous
+/// item's output ended (`continue_at_ns`), so the switch is frame-accurate.
 #[allow(clippy::too_many_arguments)]
 fn link_pad_through_clocksync(
     pipeline: &gst::Pipeline,
     src_pad: &gst::Pad,
     appsrc: &gst_app::AppSrc,
-    state: &Arc<MediaPlayerState>,
+    state: &MediaPlayerState,
+    ts_offset: &Arc<AtomicI64>,
+    continue_at_ns: &Arc<AtomicU64>,
     clocksync_name: &str,
     appsink_name: &str,
     sync: bool,
@@ -376,7 +468,9 @@ fn link_pad_through_clocksync(
     // Set up bridge callback: appsink → appsrc with timestamp offset
     let appsrc_weak = appsrc.downgrade();
     let media_type_owned = media_type.to_string();
-    let ts_offset = Arc::clone(&state.ts_offset);
+    let ts_offset = Arc::clone(ts_offset);
+    let continue_at_ns = Arc::clone(continue_at_ns);
+    let output_end_ns = Arc::clone(&state.output_end_ns);
     let main_pipeline_weak = state.main_pipeline.clone();
     let pushed_count = Arc::new(std::sync::atomic::AtomicU64::new(0));
 
@@ -409,8 +503,12 @@ fn link_pad_through_clocksync(
                         let clock = main_pipe.clock();
                         let base_time = main_pipe.base_time();
                         if let (Some(clock), Some(base_time)) = (clock, base_time) {
-                            let running = clock.time().saturating_sub(base_time);
-                            let offset = running.nseconds() as i64 - pts_val.nseconds() as i64;
+                            let running = clock
+                                .time()
+                                .saturating_sub(base_time)
+                                .nseconds()
+                                .max(continue_at_ns.load(Ordering::Relaxed));
+                            let offset = running as i64 - pts_val.nseconds() as i64;
                             ts_offset.store(offset, Ordering::Relaxed);
                             offset
                         } else {
@@ -452,6 +550,11 @@ fn link_pad_through_clocksync(
                 // on the next one — the appsrc will accept data once it's ready.
                 match push_result {
                     Ok(_) => {
+                        if let Some(pts_val) = pts {
+                            let end = (pts_val.nseconds() as i64 + offset_ns).max(0) as u64
+                                + buffer.duration().map(|d| d.nseconds()).unwrap_or(0);
+                            output_end_ns.fetch_max(end, Ordering::Relaxed);
+                        }
                         if pushed_count.fetch_add(1, Ordering::Relaxed) == 0 {
                             info!(
                                 "Media Player bridge: {} first sample delivered, pts={:?}",
@@ -492,18 +595,19 @@ fn link_pad_through_clocksync(
     Ok(())
 }
 
-/// Watch the internal pipeline's bus for EOS, errors, and state changes.
+/// Watch a decode slot's bus for EOS, errors, and state changes.
 ///
-/// EOS triggers advancing to the next file instead of propagating downstream.
-/// State changes are broadcast as `MediaPlayerStateChanged` events.
+/// EOS of the active slot ends the current item: it loops, holds or advances to the
+/// next file instead of propagating downstream. ASYNC_DONE continues loading prerolled
+/// items. State changes of the active slot are broadcast as `MediaPlayerStateChanged`
+/// events.
 pub fn watch_internal_bus(
-    pipeline: &gst::Pipeline,
+    slot: &Arc<DecodeSlot>,
     state: Arc<MediaPlayerState>,
-    flow_id: FlowId,
     block_id: String,
     events: EventBroadcaster,
 ) {
-    let bus = match pipeline.bus() {
+    let bus = match slot.pipeline.bus() {
         Some(b) => b,
         None => {
             warn!("Media Player {}: Internal pipeline has no bus", block_id);
@@ -515,10 +619,15 @@ pub fn watch_internal_bus(
 
     let state_for_bus = Arc::clone(&state);
     let block_id_for_bus = block_id.clone();
+    let slot_weak: Weak<DecodeSlot> = Arc::downgrade(slot);
 
     bus.connect_message(None, move |_bus, msg| {
         use gst::MessageView;
 
+        let Some(slot) = slot_weak.upgrade() else {
+            return;
+        };
+
         match msg.view() {
             MessageView::Eos(_) => {
                 // Ignore EOS during file switch — the Ready→Playing transition
@@ -533,30 +642,58 @@ pub fn watch_internal_bus(
                     );
                     return;
                 }
+                // The outgoing slot of a crossfade ends on its own
+                if !state_for_bus.is_active(&slot) {
+                    debug!(
+                        "Media Player {}: Ignoring EOS of standby slot {}",
+                        block_id_for_bus, slot.index
+                    );
+                    return;
+                }
 
                 info!("Media Player {}: Internal pipeline EOS", block_id_for_bus);
 
-                match state_for_bus.next() {
-                    Ok(_) => {
+                match state_for_bus.on_item_end() {
+                    Ok(ItemEnd::Loop) => {
+                        info!(
+                            "Media Player {}: Looping item (pass {})",
+                            block_id_for_bus,
+                            state_for_bus.loop_pass()
+                        );
+                    }
+                    Ok(ItemEnd::Hold) => {
+                        info!(
+                            "Media Player {}: Holding last frame until play",
+                            block_id_for_bus
+                        );
+                    }
+                    Ok(ItemEnd::Next) => {
                         info!("Media Player {}: Advanced to next file", block_id_for_bus);
                     }
                     Err(e) => {
                         info!("Media Player {}: End of playlist: {}", block_id_for_bus, e);
-                        events.broadcast(StromEvent::MediaPlayerStateChanged {
-                            flow_id,
-                            block_id: block_id_for_bus.clone(),
-                            state: strom_types::mediaplayer::PlayerState::Stopped,
-                            current_file: None,
-                        });
+                        events.broadcast(state_for_bus.state_changed_event(
+                            strom_types::mediaplayer::PlayerState::Stopped,
+                            true,
+                        ));
                     }
                 }
             }
-            MessageView::StateChanged(state_msg) => {
+            MessageView::AsyncDone(_) => {
                 let is_pipeline = msg
                     .src()
                     .map(|s| s.type_() == gst::Pipeline::static_type())
                     .unwrap_or(false);
                 if is_pipeline {
+                    state_for_bus.on_preroll(&slot);
+                }
+            }
+            MessageView::StateChanged(state_msg) => {
+                let is_pipeline = msg
+                    .src()
+                    .map(|s| s.type_() == gst::Pipeline::static_type())
+                    .unwrap_or(false);
+                if is_pipeline && state_for_bus.is_active(&slot) {
                     let new_state = state_msg.current();
                     let player_state = match new_state {
                         gst::State::Playing => strom_types::mediaplayer::PlayerState::Playing,
@@ -564,12 +701,7 @@ pub fn watch_internal_bus(
                         _ => strom_types::mediaplayer::PlayerState::Stopped,
                     };
 
-                    events.broadcast(StromEvent::MediaPlayerStateChanged {
-                        flow_id,
-                        block_id: block_id.clone(),
-                        state: player_state,
-                        current_file: state_for_bus.current_file(),
-                    });
+                    events.broadcast(state_for_bus.state_changed_event(player_state, false));
                 }
             }
             MessageView::Error(err) => {
@@ -1,8 +1,10 @@
 //! Media player block builder — creates main pipeline elements and internal pipeline.
 
 use super::bridge;
-use super::normalize_uri;
+use super::schedule::{parse_schedule, Scheduler, SCHEDULE_TICK_MS};
+use super::slot::{Crossfade, DecodeSlot, ItemTransition};
 use super::state::{MediaPlayerKey, MediaPlayerState, MEDIA_PLAYER_REGISTRY};
+use super::{normalize_uri, parse_playlist};
 use crate::blocks::{
     BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder, BusMessageConnectFn,
 };
@@ -11,11 +13,13 @@ use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicI64};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 use strom_types::element::ElementPadRef;
+use strom_types::mediaplayer::{PlaylistItem, PlayoutSchedule};
 use strom_types::{FlowId, PropertyValue, StromEvent};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 /// Media player block builder.
@@ -62,6 +66,30 @@ impl BlockBuilder for MediaPlayerBuilder {
             })
             .unwrap_or(200);
 
+        let mut transition = properties
+            .get("transition")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => Some(ItemTransition::from_property(s)),
+                _ => None,
+            })
+            .unwrap_or(ItemTransition::Cut);
+
+        let crossfade_duration_ms = properties
+            .get("crossfade_duration")
+            .and_then(|v| match v {
+                PropertyValue::Int(i) => Some((*i).max(0) as u64),
+                _ => None,
+            })
+            .unwrap_or(500);
+
+        if transition == ItemTransition::Crossfade && !decode {
+            warn!(
+                "Media Player {}: Crossfade needs decode=true, using gapless transitions",
+                instance_id
+            );
+            transition = ItemTransition::Gapless;
+        }
+
         let flow_id: FlowId = properties
             .get("_flow_id")
             .and_then(|v| match v {
@@ -92,10 +120,18 @@ impl BlockBuilder for MediaPlayerBuilder {
             })
             .unwrap_or_else(|| std::path::PathBuf::from("./media"));
 
-        let initial_playlist: Vec<String> = properties
+        let initial_playlist: Vec<PlaylistItem> = properties
             .get("playlist")
             .and_then(|v| match v {
-                PropertyValue::String(s) => serde_json::from_str(s).ok(),
+                PropertyValue::String(s) => parse_playlist(s),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let schedule: PlayoutSchedule = properties
+            .get("schedule")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => parse_schedule(s),
                 _ => None,
             })
             .unwrap_or_default();
@@ -116,7 +152,10 @@ impl BlockBuilder for MediaPlayerBuilder {
             decode,
             sync,
             position_update_interval_ms,
+            transition,
+            Duration::from_millis(crossfade_duration_ms),
             initial_playlist,
+            schedule,
             media_path,
         )
     }
@@ -126,6 +165,8 @@ impl BlockBuilder for MediaPlayerBuilder {
 ///
 /// Creates appsrc+identity elements for the main pipeline, and an internal pipeline
 /// with uridecodebin/urisourcebin + clocksync + appsink for isolated playback.
+/// Gapless and crossfade transitions get a second internal pipeline; crossfades
+/// also mix both through a compositor and an audiomixer.
 #[allow(clippy::too_many_arguments)]
 fn build_media_player(
     instance_id: &str,
@@ -135,7 +176,10 @@ fn build_media_player(
     decode: bool,
     sync: bool,
     position_update_interval_ms: u64,
-    initial_playlist: Vec<String>,
+    transition: ItemTransition,
+    crossfade_duration: Duration,
+    initial_playlist: Vec<PlaylistItem>,
+    schedule: PlayoutSchedule,
     media_path: std::path::PathBuf,
 ) -> Result<BlockBuildResult, BlockBuildError> {
     // --- Main pipeline elements: appsrc → identity ---
@@ -168,53 +212,76 @@ fn build_media_player(
         .build()
         .map_err(|e| BlockBuildError::ElementCreation(format!("audio_out: {}", e)))?;
 
+    // --- Crossfade mixers: slot 1 gets its own appsrcs, mixed with slot 0 ---
+    let crossfade = if transition == ItemTransition::Crossfade {
+        Some(build_crossfade_elements(instance_id, crossfade_duration)?)
+    } else {
+        None
+    };
+
     // --- Create shared state ---
     let player_instance_id = Uuid::new_v4();
-    let source_element_weak = gst::glib::WeakRef::new();
-    let ts_offset = Arc::new(AtomicI64::new(i64::MIN));
     let state = Arc::new(MediaPlayerState {
         instance_id: player_instance_id,
-        source_element: source_element_weak,
-        internal_pipeline: RwLock::new(None),
+        active: RwLock::new(None),
+        standby: RwLock::new(None),
+        transition,
+        crossfade: crossfade.as_ref().map(|c| Arc::clone(&c.crossfade)),
         video_appsrc: Some(appsrc_video.clone()),
         audio_appsrc: Some(appsrc_audio.clone()),
         playlist: RwLock::new(super::state::Playlist {
-            files: initial_playlist.clone(),
+            items: initial_playlist.clone(),
             current_index: 0,
+            loop_pass: 0,
         }),
         is_paused: AtomicBool::new(false),
         loop_playlist: AtomicBool::new(loop_playlist),
         block_id: block_id.to_string(),
         flow_id,
         switching_file: AtomicBool::new(false),
-        video_linked: AtomicBool::new(false),
-        audio_linked: AtomicBool::new(false),
         decode,
         sync,
         media_path: media_path.clone(),
-        ts_offset,
+        output_end_ns: Arc::new(AtomicU64::new(0)),
         main_pipeline: gst::glib::WeakRef::new(),
+        play_after_preroll: AtomicBool::new(false),
+        cued: AtomicBool::new(false),
+        held: AtomicBool::new(false),
+        ended: AtomicBool::new(false),
+        schedule: Mutex::new(Scheduler::new(schedule, chrono::Utc::now())),
+        live: Mutex::new(None),
     });
 
     // --- Resolve initial URI ---
     let initial_uri = initial_playlist
         .first()
-        .map(|f| normalize_uri(f, &media_path));
+        .map(|item| normalize_uri(&item.uri, &media_path));
 
     if let Some(ref uri) = initial_uri {
         info!("Media Player {}: Initial URI: {}", instance_id, uri);
     }
 
-    // --- Create internal pipeline ---
-    let internal_pipeline = if decode {
-        bridge::create_decode_pipeline(instance_id, &state, initial_uri.as_deref())?
+    // --- Create internal pipelines (decode slots) ---
+    let create_slot = |index: usize, uri: Option<&str>| {
+        if decode {
+            bridge::create_decode_pipeline(instance_id, index, &state, uri)
+        } else {
+            bridge::create_passthrough_pipeline(instance_id, index, &state, uri)
+        }
+    };
+    let active_slot = create_slot(0, initial_uri.as_deref())?;
+    let standby_slot = if transition == ItemTransition::Cut {
+        None
     } else {
-        bridge::create_passthrough_pipeline(instance_id, &state, initial_uri.as_deref())?
+        Some(create_slot(1, None)?)
     };
 
-    // Store internal pipeline in state
-    if let Ok(mut guard) = state.internal_pipeline.write() {
-        *guard = Some(internal_pipeline.clone());
+    // Store decode slots in state
+    if let Ok(mut guard) = state.active.write() {
+        *guard = Some(Arc::clone(&active_slot));
+    }
+    if let Ok(mut guard) = state.standby.write() {
+        guard.clone_from(&standby_slot);
     }
 
     // Register in global registry
@@ -228,7 +295,8 @@ fn build_media_player(
     // Starts the internal pipeline, sets up its bus watch, and starts position polling.
     let state_for_handler = Arc::clone(&state);
     let block_id_for_handler = block_id.to_string();
-    let internal_pipeline_for_handler = internal_pipeline.clone();
+    let slots_for_handler: Vec<Arc<DecodeSlot>> =
+        std::iter::once(active_slot).chain(standby_slot).collect();
 
     let bus_message_handler: BusMessageConnectFn = Box::new(
         move |bus: &gst::Bus, flow_id: FlowId, events: EventBroadcaster| {
@@ -239,7 +307,7 @@ fn build_media_player(
                 Arc::clone(&state_for_handler),
                 block_id_for_handler.clone(),
                 position_update_interval_ms,
-                internal_pipeline_for_handler.clone(),
+                slots_for_handler.clone(),
             )
         },
     );
@@ -260,45 +328,168 @@ fn build_media_player(
         .build()
         .map_err(|e| BlockBuildError::ElementCreation(format!("queue_audio: {}", e)))?;
 
-    // --- Static links: appsrc → queue → identity ---
-    let internal_links = vec![
+    let mut elements = vec![
+        (appsrc_video_id.clone(), appsrc_video.upcast()),
+        (queue_video_id.clone(), queue_video),
+        (video_out_id.clone(), video_out),
+        (appsrc_audio_id.clone(), appsrc_audio.upcast()),
+        (queue_audio_id.clone(), queue_audio),
+        (audio_out_id.clone(), audio_out),
+    ];
+
+    // --- Static links: appsrc → queue → identity (through the mixers when crossfading) ---
+    let mut internal_links = vec![
         (
             ElementPadRef::pad(&appsrc_video_id, "src"),
             ElementPadRef::pad(&queue_video_id, "sink"),
         ),
-        (
-            ElementPadRef::pad(&queue_video_id, "src"),
-            ElementPadRef::pad(&video_out_id, "sink"),
-        ),
         (
             ElementPadRef::pad(&appsrc_audio_id, "src"),
             ElementPadRef::pad(&queue_audio_id, "sink"),
         ),
-        (
-            ElementPadRef::pad(&queue_audio_id, "src"),
-            ElementPadRef::pad(&audio_out_id, "sink"),
-        ),
     ];
+    match crossfade {
+        Some(mix) => {
+            internal_links.extend([
+                (
+                    ElementPadRef::pad(&queue_video_id, "src"),
+                    ElementPadRef::pad(&mix.video_mixer_id, "sink_0"),
+                ),
+                (
+                    ElementPadRef::pad(&queue_audio_id, "src"),
+                    ElementPadRef::pad(&mix.audio_mixer_id, "sink_0"),
+                ),
+                (
+                    ElementPadRef::pad(&mix.video_mixer_id, "src"),
+                    ElementPadRef::pad(&video_out_id, "sink"),
+                ),
+                (
+                    ElementPadRef::pad(&mix.audio_mixer_id, "src"),
+                    ElementPadRef::pad(&audio_out_id, "sink"),
+                ),
+            ]);
+            internal_links.extend(mix.links);
+            elements.extend(mix.elements);
+        }
+        None => {
+            internal_links.extend([
+                (
+                    ElementPadRef::pad(&queue_video_id, "src"),
+                    ElementPadRef::pad(&video_out_id, "sink"),
+                ),
+                (
+                    ElementPadRef::pad(&queue_audio_id, "src"),
+                    ElementPadRef::pad(&audio_out_id, "sink"),
+                ),
+            ]);
+        }
+    }
 
     Ok(BlockBuildResult {
-        elements: vec![
-            (appsrc_video_id, appsrc_video.upcast()),
-            (queue_video_id, queue_video),
-            (video_out_id, video_out),
-            (appsrc_audio_id, appsrc_audio.upcast()),
-            (queue_audio_id, queue_audio),
-            (audio_out_id, audio_out),
-        ],
+        elements,
         internal_links,
         bus_message_handler: Some(bus_message_handler),
         pad_properties: HashMap::new(),
     })
 }
 
+/// Main pipeline elements for crossfade transitions.
+struct CrossfadeElements {
+    crossfade: Arc<Crossfade>,
+    video_mixer_id: String,
+    audio_mixer_id: String,
+    elements: Vec<(String, gst::Element)>,
+    links: Vec<(ElementPadRef, ElementPadRef)>,
+}
+
+/// Create slot 1's appsrcs and the compositor/audiomixer blending it with slot 0.
+///
+/// The mixers' `sink_0` inputs are left for slot 0's queues; `sink_1` is linked
+/// here to slot 1's appsrc → queue chains.
+fn build_crossfade_elements(
+    instance_id: &str,
+    duration: Duration,
+) -> Result<CrossfadeElements, BlockBuildError> {
+    let mut elements = Vec::new();
+    let mut links = Vec::new();
+    let mut appsrcs = Vec::new();
+    let mut pads = Vec::new();
+    let mut mixer_ids = Vec::new();
+
+    for (media, factory) in [("video", "compositor"), ("audio", "audiomixer")] {
+        let appsrc_id = format!("{}:appsrc_{}_b", instance_id, media);
+        let queue_id = format!("{}:queue_{}_b", instance_id, media);
+        let mixer_id = format!("{}:mix_{}", instance_id, media);
+
+        let appsrc = gst_app::AppSrc::builder()
+            .name(&appsrc_id)
+            .format(gst::Format::Time)
+            .is_live(true)
+            .automatic_eos(false)
+            .build();
+        let queue = gst::ElementFactory::make("queue")
+            .name(&queue_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", queue_id, e)))?;
+        let mixer = gst::ElementFactory::make(factory)
+            .name(&mixer_id)
+            .build()
+            .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", factory, e)))?;
+
+        // Request both inputs now so the mixer levels can be set before linking
+        let slot_pads = [
+            mixer.request_pad_simple("sink_%u"),
+            mixer.request_pad_simple("sink_%u"),
+        ];
+        let [Some(pad_0), Some(pad_1)] = slot_pads else {
+            return Err(BlockBuildError::ElementCreation(format!(
+                "{}: failed to request sink pads",
+                factory
+            )));
+        };
+
+        links.push((
+            ElementPadRef::pad(&appsrc_id, "src"),
+            ElementPadRef::pad(&queue_id, "sink"),
+        ));
+        links.push((
+            ElementPadRef::pad(&queue_id, "src"),
+            ElementPadRef::pad(&mixer_id, pad_1.name().as_str()),
+        ));
+
+        appsrcs.push(appsrc.clone());
+        pads.push([pad_0, pad_1]);
+        mixer_ids.push(mixer_id.clone());
+        elements.push((appsrc_id, appsrc.upcast()));
+        elements.push((queue_id, queue));
+        elements.push((mixer_id, mixer));
+    }
+
+    let [video_pads, audio_pads]: [[gst::Pad; 2]; 2] = pads
+        .try_into()
+        .map_err(|_| BlockBuildError::ElementCreation("crossfade mixer pads".to_string()))?;
+    let crossfade = Crossfade::new(
+        duration,
+        appsrcs[0].clone(),
+        appsrcs[1].clone(),
+        video_pads,
+        audio_pads,
+    );
+
+    Ok(CrossfadeElements {
+        crossfade: Arc::new(crossfade),
+        video_mixer_id: mixer_ids[0].clone(),
+        audio_mixer_id: mixer_ids[1].clone(),
+        elements,
+        links,
+    })
+}
+
 /// Handler called when the main pipeline's bus is connected.
 ///
-/// Starts the internal pipeline, watches its bus for EOS/errors, and starts
-/// the position polling timer.
+/// Starts the active decode slot, prerolls the standby slot, watches their buses
+/// for EOS/errors, and starts the position polling timer (which also starts
+/// crossfades).
 fn connect_main_pipeline_handler(
     main_bus: &gst::Bus,
     flow_id: FlowId,
@@ -306,7 +497,7 @@ fn connect_main_pipeline_handler(
     state: Arc<MediaPlayerState>,
     block_id: String,
     position_update_interval_ms: u64,
-    internal_pipeline: gst::Pipeline,
+    slots: Vec<Arc<DecodeSlot>>,
 ) -> gst::glib::SignalHandlerId {
     info!(
         "Media Player {}: Starting internal pipeline and position timer",
@@ -328,23 +519,45 @@ fn connect_main_pipeline_handler(
         }
     }
 
-    // Start the internal pipeline
-    if let Err(e) = internal_pipeline.set_state(gst::State::Playing) {
-        tracing::error!(
-            "Media Player {}: Failed to start internal pipeline: {:?}",
-            block_id,
-            e
-        );
+    // Watch internal pipeline buses for EOS, errors, state changes
+    for slot in &slots {
+        bridge::watch_internal_bus(slot, Arc::clone(&state), block_id.clone(), events.clone());
     }
 
-    // Watch internal pipeline bus for EOS, errors, state changes
-    bridge::watch_internal_bus(
-        &internal_pipeline,
-        Arc::clone(&state),
+    // Start the active slot, prerolling first if the first item is trimmed or cued
+    if let Some(slot) = state.active_slot() {
+        let target = state.prepare_current_item(&slot);
+        if let Err(e) = slot.pipeline.set_state(target) {
+            tracing::error!(
+                "Media Player {}: Failed to start internal pipeline: {:?}",
+                block_id,
+                e
+            );
+        }
+    }
+    state.prepare_standby();
+
+    // Start the playout schedule timer
+    let events_for_schedule = events.clone();
+    let state_for_schedule = Arc::clone(&state);
+    let schedule_key = MediaPlayerKey {
         flow_id,
-        block_id.clone(),
-        events.clone(),
-    );
+        block_id: block_id.clone(),
+    };
+    let schedule_instance_id = state.instance_id;
+
+    gst::glib::timeout_add(Duration::from_millis(SCHEDULE_TICK_MS), move || {
+        let is_current_instance = MEDIA_PLAYER_REGISTRY
+            .get(&schedule_key)
+            .map(|s| s.instance_id == schedule_instance_id)
+            .unwrap_or(false);
+        if !is_current_instance {
+            return gst::glib::ControlFlow::Break;
+        }
+
+        state_for_schedule.run_schedule(&events_for_schedule);
+        gst::glib::ControlFlow::Continue
+    });
 
     // Start position polling timer
     let events_for_timer = events;
@@ -373,6 +586,8 @@ fn connect_main_pipeline_handler(
                 return gst::glib::ControlFlow::Break;
             }
 
+            state_for_timer.check_crossfade();
+
             let position = state_for_timer.position().unwrap_or(0);
             let duration = state_for_timer.duration().unwrap_or(0);
             let current_index = state_for_timer.current_index();
@@ -72,6 +72,49 @@ pub fn media_player_definition() -> BlockDefinition {
                 },
                 live: false,
             },
+            ExposedProperty {
+                name: "transition".to_string(),
+                label: "Transition".to_string(),
+                description: "How items follow each other: cut (reload, short gap), gapless (switch to the prerolled next item at the frame boundary) or crossfade (decode only)"
+                    .to_string(),
+                property_type: PropertyType::Enum {
+                    values: vec![
+                        EnumValue {
+                            value: "cut".to_string(),
+                            label: Some("Cut".to_string()),
+                        },
+                        EnumValue {
+                            value: "gapless".to_string(),
+                            label: Some("Gapless".to_string()),
+                        },
+                        EnumValue {
+                            value: "crossfade".to_string(),
+                            label: Some("Crossfade".to_string()),
+                        },
+                    ],
+                },
+                default_value: Some(PropertyValue::String("cut".to_string())),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "transition".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "crossfade_duration".to_string(),
+                label: "Crossfade Duration (ms)".to_string(),
+                description: "Length of the audio and video crossfade between items"
+                    .to_string(),
+                property_type: PropertyType::Int,
+                default_value: Some(PropertyValue::Int(500)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "crossfade_duration".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
         ],
         external_pads: ExternalPads {
             inputs: vec![],
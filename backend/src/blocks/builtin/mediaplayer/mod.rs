@@ -7,19 +7,49 @@
 //! Supports two modes:
 //! - **Decode** (`decode=true`): `uridecodebin` → raw video/audio
 //! - **Passthrough** (`decode=false`): `urisourcebin` → encoded elementary streams
+//!
+//! Playlist items carry playout settings: in/out points (a segment seek once
+//! the item has prerolled), a loop count, hold-last-frame or advance at the
+//! end, and cue-on-load (preroll paused until play).
+//!
+//! With the `gapless` or `crossfade` transition a second decode slot prerolls
+//! the next item, so the switch happens at the frame boundary or as an A/V
+//! crossfade through a compositor and audiomixer in the main pipeline.
+//!
+//! A wall-clock playout schedule (`schedule` property) can drive the player:
+//! events put files or live breakaways to Inter Output channels on air at
+//! their start time, filler covers the gaps, and an as-run log is written to
+//! the data directory.
 
 mod bridge;
 mod builder;
 mod definition;
+mod schedule;
+mod slot;
 mod state;
 
 pub use builder::MediaPlayerBuilder;
 pub use definition::get_blocks;
+pub use schedule::{parse_schedule, prepare_schedule, read_as_run};
 pub use state::{MediaPlayerKey, MediaPlayerState, MEDIA_PLAYER_REGISTRY};
 
 use std::path::Path;
+use strom_types::mediaplayer::PlaylistItem;
 use tracing::debug;
 
+/// Parse a playlist stored in the `playlist` block property.
+///
+/// Accepts items as well as the plain URI list written by earlier versions.
+pub fn parse_playlist(json: &str) -> Option<Vec<PlaylistItem>> {
+    serde_json::from_str::<Vec<PlaylistItem>>(json)
+        .ok()
+        .or_else(|| {
+            serde_json::from_str::<Vec<String>>(json)
+                .ok()
+                .map(|files| files.into_iter().map(PlaylistItem::from).collect())
+        })
+}
+
 /// Normalize a file path to a proper URI.
 ///
 /// Converts relative paths to absolute file:// URIs resolved against `media_path`.
@@ -64,13 +94,22 @@ pub fn normalize_uri(path: &str, media_path: &Path) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blocks::builtin::mediaplayer::schedule::{
+        PlayerProgress, ScheduleAction, Scheduler,
+    };
+    use crate::blocks::builtin::mediaplayer::slot::ItemTransition;
     use crate::blocks::builtin::mediaplayer::state::{
-        MediaPlayerKey, MediaPlayerRegistry, MediaPlayerState, Playlist,
+        ItemEnd, MediaPlayerKey, MediaPlayerRegistry, MediaPlayerState, Playlist,
     };
+    use chrono::{TimeDelta, Utc};
     use gstreamer as gst;
-    use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
-    use std::sync::{Arc, RwLock};
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, RwLock};
     use strom_types::block::PropertyType;
+    use strom_types::mediaplayer::{
+        AsRunKind, AsRunStatus, EndAction, PlayoutSchedule, ScheduleEvent, ScheduleSource,
+        StartMode,
+    };
     use strom_types::PropertyValue;
     use uuid::Uuid;
 
@@ -78,26 +117,33 @@ mod tests {
     fn test_state(flow_id: Uuid, block_id: &str, playlist: Vec<String>) -> MediaPlayerState {
         MediaPlayerState {
             instance_id: Uuid::new_v4(),
-            source_element: gst::glib::WeakRef::new(),
-            internal_pipeline: RwLock::new(None),
+            active: RwLock::new(None),
+            standby: RwLock::new(None),
+            transition: ItemTransition::Cut,
+            crossfade: None,
             video_appsrc: None,
             audio_appsrc: None,
             playlist: RwLock::new(Playlist {
-                files: playlist,
+                items: playlist.into_iter().map(PlaylistItem::from).collect(),
                 current_index: 0,
+                loop_pass: 0,
             }),
             is_paused: AtomicBool::new(false),
             loop_playlist: AtomicBool::new(true),
             block_id: block_id.to_string(),
             flow_id,
             switching_file: AtomicBool::new(false),
-            video_linked: AtomicBool::new(false),
-            audio_linked: AtomicBool::new(false),
             decode: false,
             sync: true,
             media_path: std::path::PathBuf::from("/media"),
-            ts_offset: Arc::new(AtomicI64::new(i64::MIN)),
+            output_end_ns: Arc::new(AtomicU64::new(0)),
             main_pipeline: gst::glib::WeakRef::new(),
+            play_after_preroll: AtomicBool::new(false),
+            cued: AtomicBool::new(false),
+            held: AtomicBool::new(false),
+            ended: AtomicBool::new(false),
+            schedule: Mutex::new(Scheduler::new(PlayoutSchedule::default(), Utc::now())),
+            live: Mutex::new(None),
         }
     }
 
@@ -192,6 +238,209 @@ mod tests {
         assert_eq!(state.current_file(), Some("x.mp4".to_string()));
     }
 
+    #[test]
+    fn test_parse_playlist_accepts_items_and_uris() {
+        assert_eq!(
+            parse_playlist(r#"["a.mp4","b.mp4"]"#),
+            Some(vec![PlaylistItem::new("a.mp4"), PlaylistItem::new("b.mp4")])
+        );
+        let items = parse_playlist(
+            r#"[{"uri":"a.mp4","in_point_ns":1000,"loop_count":2,"end_action":"hold"}]"#,
+        )
+        .unwrap();
+        assert_eq!(items[0].in_point_ns, Some(1000));
+        assert_eq!(items[0].loop_count, 2);
+        assert_eq!(items[0].end_action, EndAction::Hold);
+        assert!(!items[0].cue_on_load);
+        assert_eq!(parse_playlist("not json"), None);
+    }
+
+    #[test]
+    fn test_end_of_item_loops_then_applies_end_action() {
+        let mut looped = PlaylistItem::new("a.mp4");
+        looped.loop_count = 2;
+        looped.end_action = EndAction::Hold;
+        let mut playlist = Playlist {
+            items: vec![looped, PlaylistItem::new("b.mp4")],
+            current_index: 0,
+            loop_pass: 0,
+        };
+        assert_eq!(playlist.end_of_item(), ItemEnd::Loop);
+        assert_eq!(playlist.end_of_item(), ItemEnd::Loop);
+        assert_eq!(playlist.loop_pass, 2);
+        assert_eq!(playlist.end_of_item(), ItemEnd::Hold);
+
+        playlist.current_index = 1;
+        playlist.loop_pass = 0;
+        assert_eq!(playlist.end_of_item(), ItemEnd::Next);
+    }
+
+    #[test]
+    fn test_next_index_wraps_only_when_looping() {
+        let mut playlist = Playlist {
+            items: vec![PlaylistItem::new("a.mp4"), PlaylistItem::new("b.mp4")],
+            current_index: 0,
+            loop_pass: 0,
+        };
+        assert_eq!(playlist.next_index(false), Ok(1));
+
+        playlist.current_index = 1;
+        assert!(playlist.next_index(false).is_err());
+        assert_eq!(playlist.next_index(true), Ok(0));
+
+        playlist.items.clear();
+        assert!(playlist.next_index(true).is_err());
+    }
+
+    #[test]
+    fn test_transition_from_property() {
+        assert_eq!(
+            ItemTransition::from_property("gapless"),
+            ItemTransition::Gapless
+        );
+        assert_eq!(
+            ItemTransition::from_property("crossfade"),
+            ItemTransition::Crossfade
+        );
+        assert_eq!(ItemTransition::from_property("cut"), ItemTransition::Cut);
+        assert_eq!(ItemTransition::from_property("bogus"), ItemTransition::Cut);
+    }
+
+    fn schedule_event(id: &str, start: chrono::DateTime<Utc>, mode: StartMode) -> ScheduleEvent {
+        ScheduleEvent {
+            id: id.to_string(),
+            title: None,
+            start: start.to_rfc3339(),
+            start_mode: mode,
+            source: ScheduleSource::File {
+                item: PlaylistItem::new(format!("{}.mp4", id)),
+            },
+        }
+    }
+
+    #[test]
+    fn test_prepare_schedule_assigns_ids_and_sorts() {
+        let t0 = Utc::now();
+        let mut late = schedule_event("", t0 + TimeDelta::minutes(5), StartMode::Hard);
+        late.title = Some("late".to_string());
+        let early = schedule_event("early", t0, StartMode::Soft);
+        let schedule = prepare_schedule(PlayoutSchedule {
+            events: vec![late, early.clone()],
+            filler: vec![],
+        })
+        .unwrap();
+        assert_eq!(schedule.events[0], early);
+        assert!(!schedule.events[1].id.is_empty());
+
+        let mut bad_start = early.clone();
+        bad_start.start = "tomorrow".to_string();
+        assert!(prepare_schedule(PlayoutSchedule {
+            events: vec![bad_start],
+            filler: vec![],
+        })
+        .is_err());
+        assert!(prepare_schedule(PlayoutSchedule {
+            events: vec![early.clone(), early],
+            filler: vec![],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_scheduler_hard_soft_and_filler() {
+        let t0 = Utc::now();
+        let playing = PlayerProgress {
+            ended: false,
+            index: 0,
+        };
+        let mut scheduler = Scheduler::new(
+            PlayoutSchedule {
+                events: vec![
+                    schedule_event("a", t0 + TimeDelta::seconds(10), StartMode::Hard),
+                    schedule_event("b", t0 + TimeDelta::seconds(20), StartMode::Soft),
+                ],
+                filler: vec![PlaylistItem::new("f0.mp4"), PlaylistItem::new("f1.mp4")],
+            },
+            t0,
+        );
+
+        // Filler covers the gap before the first event
+        let (action, log) = scheduler.tick(t0, playing);
+        assert!(matches!(
+            action,
+            Some(ScheduleAction::Filler { index: 0, .. })
+        ));
+        assert_eq!(log[0].kind, AsRunKind::Filler);
+
+        // The filler advancing is recorded
+        let (action, log) = scheduler.tick(
+            t0,
+            PlayerProgress {
+                ended: false,
+                index: 1,
+            },
+        );
+        assert!(action.is_none());
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[1].source, "f1.mp4");
+
+        // Hard start cuts the filler
+        let (action, log) = scheduler.tick(
+            t0 + TimeDelta::seconds(10),
+            PlayerProgress {
+                ended: false,
+                index: 1,
+            },
+        );
+        assert!(matches!(action, Some(ScheduleAction::Start(_))));
+        assert_eq!(log[0].status, AsRunStatus::Ended);
+        assert_eq!(log[1].event_id.as_deref(), Some("a"));
+
+        // Soft start waits for the file on air to end
+        let (action, _) = scheduler.tick(t0 + TimeDelta::seconds(21), playing);
+        assert!(action.is_none());
+        assert_eq!(scheduler.schedule().events.len(), 1);
+        let ended = PlayerProgress {
+            ended: true,
+            index: 0,
+        };
+        let (action, log) = scheduler.tick(t0 + TimeDelta::seconds(25), ended);
+        assert!(matches!(action, Some(ScheduleAction::Start(_))));
+        assert_eq!(log[1].event_id.as_deref(), Some("b"));
+
+        // Filler continues with the item after the one it was cut at
+        let (action, _) = scheduler.tick(t0 + TimeDelta::seconds(30), ended);
+        assert!(matches!(
+            action,
+            Some(ScheduleAction::Filler { index: 0, .. })
+        ));
+        assert!(scheduler.on_air().is_some());
+    }
+
+    #[test]
+    fn test_scheduler_misses_superseded_events() {
+        let t0 = Utc::now();
+        let mut scheduler = Scheduler::new(PlayoutSchedule::default(), t0);
+        scheduler.set_schedule(PlayoutSchedule {
+            events: vec![
+                schedule_event("a", t0, StartMode::Soft),
+                schedule_event("b", t0 + TimeDelta::seconds(1), StartMode::Hard),
+            ],
+            filler: vec![],
+        });
+        let (action, log) = scheduler.tick(
+            t0 + TimeDelta::seconds(2),
+            PlayerProgress {
+                ended: false,
+                index: 0,
+            },
+        );
+        assert!(matches!(action, Some(ScheduleAction::Start(_))));
+        assert_eq!(log[0].status, AsRunStatus::Missed);
+        assert_eq!(log[0].event_id.as_deref(), Some("a"));
+        assert_eq!(log[1].status, AsRunStatus::Started);
+    }
+
     #[test]
     fn test_player_state() {
         use strom_types::mediaplayer::PlayerState;
@@ -213,7 +462,7 @@ mod tests {
         assert_eq!(def.id, "builtin.media_player");
         assert_eq!(def.category, "Inputs");
         assert!(def.built_in);
-        assert_eq!(def.exposed_properties.len(), 4);
+        assert_eq!(def.exposed_properties.len(), 6);
 
         let decode = def
             .exposed_properties
@@ -241,6 +490,24 @@ mod tests {
             .iter()
             .any(|p| p.name == "loop_playlist"));
 
+        let transition = def
+            .exposed_properties
+            .iter()
+            .find(|p| p.name == "transition")
+            .unwrap();
+        assert!(matches!(
+            transition.property_type,
+            PropertyType::Enum { ref values } if values.len() == 3
+        ));
+        assert!(matches!(
+            transition.default_value,
+            Some(PropertyValue::String(ref s)) if s == "cut"
+        ));
+        assert!(def
+            .exposed_properties
+            .iter()
+            .any(|p| p.name == "crossfade_duration"));
+
         assert_eq!(def.external_pads.inputs.len(), 0);
         assert_eq!(def.external_pads.outputs.len(), 2);
         assert!(def
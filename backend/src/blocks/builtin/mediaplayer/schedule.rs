@@ -0,0 +1,368 @@
+//! Wall-clock playout schedule (channel automation).
+//!
+//! On every tick the scheduler decides what the player puts on air: a due
+//! event (hard starts cut in, soft starts wait for the scheduled file on air
+//! to end), the filler loop in the gaps between events, or nothing. Every
+//! change is recorded in a daily as-run log under `as-run/` in the data
+//! directory.
+
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use strom_types::mediaplayer::{
+    AsRunEntry, AsRunKind, AsRunStatus, PlaylistItem, PlayoutSchedule, ScheduleEvent,
+    ScheduleSource, StartMode,
+};
+use strom_types::FlowId;
+use uuid::Uuid;
+
+/// Interval between schedule checks. Events go on air within one tick of
+/// their start time.
+pub const SCHEDULE_TICK_MS: u64 = 100;
+
+/// Parse a schedule stored in the `schedule` block property.
+pub fn parse_schedule(json: &str) -> Option<PlayoutSchedule> {
+    serde_json::from_str(json).ok()
+}
+
+/// Parse the start time of an event.
+pub fn parse_start(event: &ScheduleEvent) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(&event.start)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| format!("Event '{}': invalid start time: {}", event.id, e))
+}
+
+/// Check a schedule from the API, assign IDs to new events and sort the
+/// events by start time.
+pub fn prepare_schedule(mut schedule: PlayoutSchedule) -> Result<PlayoutSchedule, String> {
+    for event in &mut schedule.events {
+        if event.id.is_empty() {
+            event.id = Uuid::new_v4().to_string();
+        }
+    }
+    schedule.check()?;
+
+    let mut ids = HashSet::new();
+    let mut events = Vec::with_capacity(schedule.events.len());
+    for event in schedule.events {
+        if !ids.insert(event.id.clone()) {
+            return Err(format!("Duplicate event ID '{}'", event.id));
+        }
+        events.push((parse_start(&event)?, event));
+    }
+    events.sort_by_key(|(start, _)| *start);
+    schedule.events = events.into_iter().map(|(_, event)| event).collect();
+    Ok(schedule)
+}
+
+/// State of the player's progress through what is on air, read on every tick.
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerProgress {
+    /// The scheduled file on air has ended (or holds its last frame)
+    pub ended: bool,
+    /// Playlist index being played
+    pub index: usize,
+}
+
+/// What the player must do after a tick.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleAction {
+    /// Put a scheduled event on air.
+    Start(ScheduleSource),
+    /// Loop the filler items, starting at `index`.
+    Filler {
+        items: Vec<PlaylistItem>,
+        index: usize,
+    },
+    /// Take a live breakaway off air, with nothing to follow it.
+    Idle,
+}
+
+/// What is on air.
+enum OnAir {
+    Idle,
+    Filler {
+        items: Vec<PlaylistItem>,
+        index: usize,
+        entry: AsRunEntry,
+    },
+    Event {
+        entry: AsRunEntry,
+        live: bool,
+        until: Option<DateTime<Utc>>,
+    },
+}
+
+/// Upcoming events and what is on air.
+pub struct Scheduler {
+    events: Vec<(DateTime<Utc>, ScheduleEvent)>,
+    filler: Vec<PlaylistItem>,
+    on_air: OnAir,
+    /// Filler item to continue with when the filler comes back on air
+    filler_next: usize,
+}
+
+impl Scheduler {
+    /// Create a scheduler for the events of `schedule` starting at or after `now`.
+    pub fn new(schedule: PlayoutSchedule, now: DateTime<Utc>) -> Self {
+        let mut scheduler = Self {
+            events: Vec::new(),
+            filler: Vec::new(),
+            on_air: OnAir::Idle,
+            filler_next: 0,
+        };
+        scheduler.set_schedule(schedule);
+        scheduler.events.retain(|(start, _)| *start >= now);
+        scheduler
+    }
+
+    /// Replace the upcoming events and the filler. Events with an invalid
+    /// start time are dropped; what is on air stays.
+    pub fn set_schedule(&mut self, schedule: PlayoutSchedule) {
+        let mut events: Vec<_> = schedule
+            .events
+            .into_iter()
+            .filter_map(|event| parse_start(&event).ok().map(|start| (start, event)))
+            .collect();
+        events.sort_by_key(|(start, _)| *start);
+        self.events = events;
+        self.filler = schedule.filler;
+    }
+
+    /// Upcoming events (including due soft starts still waiting) and the filler.
+    pub fn schedule(&self) -> PlayoutSchedule {
+        PlayoutSchedule {
+            events: self.events.iter().map(|(_, e)| e.clone()).collect(),
+            filler: self.filler.clone(),
+        }
+    }
+
+    /// The as-run record of what is on air.
+    pub fn on_air(&self) -> Option<AsRunEntry> {
+        match &self.on_air {
+            OnAir::Idle => None,
+            OnAir::Filler { entry, .. } | OnAir::Event { entry, .. } => Some(entry.clone()),
+        }
+    }
+
+    /// Decide what goes on air at `now`.
+    ///
+    /// Returns the action for the player, if any, and the as-run records of
+    /// the changes.
+    pub fn tick(
+        &mut self,
+        now: DateTime<Utc>,
+        progress: PlayerProgress,
+    ) -> (Option<ScheduleAction>, Vec<AsRunEntry>) {
+        let mut log = Vec::new();
+        if self.events.is_empty() && self.filler.is_empty() && matches!(self.on_air, OnAir::Idle) {
+            return (None, log);
+        }
+
+        // Record filler items as the filler loop advances
+        if let OnAir::Filler {
+            items,
+            index,
+            entry,
+        } = &mut self.on_air
+        {
+            if *index != progress.index {
+                if let Some(item) = items.get(progress.index) {
+                    log.push(record(now, AsRunStatus::Ended, entry.clone()));
+                    *entry = filler_entry(now, item);
+                    *index = progress.index;
+                    log.push(entry.clone());
+                }
+            }
+        }
+
+        // Of several due events only the latest goes on air
+        let due = self
+            .events
+            .iter()
+            .take_while(|(start, _)| *start <= now)
+            .count();
+        if due > 1 {
+            for (start, event) in self.events.drain(..due - 1) {
+                log.push(event_entry(now, AsRunStatus::Missed, &event, start));
+            }
+        }
+
+        let finished = match &self.on_air {
+            OnAir::Idle | OnAir::Filler { .. } => false,
+            OnAir::Event { live: false, .. } => progress.ended,
+            OnAir::Event { until, .. } => until.is_some_and(|until| now >= until),
+        };
+        let interruptible = match &self.on_air {
+            OnAir::Idle | OnAir::Filler { .. } => true,
+            OnAir::Event {
+                live: true,
+                until: None,
+                ..
+            } => true,
+            OnAir::Event { .. } => finished,
+        };
+
+        let starts = self.events.first().is_some_and(|(start, event)| {
+            *start <= now && (event.start_mode == StartMode::Hard || interruptible)
+        });
+        if starts {
+            let (start, event) = self.events.remove(0);
+            self.take_off_air(now, &mut log);
+            let entry = event_entry(now, AsRunStatus::Started, &event, start);
+            log.push(entry.clone());
+            let (live, until) = match &event.source {
+                ScheduleSource::File { .. } => (false, None),
+                ScheduleSource::Live { duration_ns, .. } => (
+                    true,
+                    duration_ns.map(|ns| now + TimeDelta::nanoseconds(ns as i64)),
+                ),
+            };
+            self.on_air = OnAir::Event { entry, live, until };
+            return (Some(ScheduleAction::Start(event.source)), log);
+        }
+
+        if !finished && !matches!(self.on_air, OnAir::Idle) {
+            return (None, log);
+        }
+        let was_live = matches!(self.on_air, OnAir::Event { live: true, .. });
+        self.take_off_air(now, &mut log);
+        if self.filler.is_empty() {
+            // A file that ended stays on its last frame
+            let action = was_live.then_some(ScheduleAction::Idle);
+            return (action, log);
+        }
+
+        let index = self.filler_next % self.filler.len();
+        let entry = filler_entry(now, &self.filler[index]);
+        log.push(entry.clone());
+        self.on_air = OnAir::Filler {
+            items: self.filler.clone(),
+            index,
+            entry,
+        };
+        let action = ScheduleAction::Filler {
+            items: self.filler.clone(),
+            index,
+        };
+        (Some(action), log)
+    }
+
+    /// Record the end of what is on air.
+    fn take_off_air(&mut self, now: DateTime<Utc>, log: &mut Vec<AsRunEntry>) {
+        match std::mem::replace(&mut self.on_air, OnAir::Idle) {
+            OnAir::Idle => {}
+            OnAir::Filler { index, entry, .. } => {
+                self.filler_next = index + 1;
+                log.push(record(now, AsRunStatus::Ended, entry));
+            }
+            OnAir::Event { entry, .. } => log.push(record(now, AsRunStatus::Ended, entry)),
+        }
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// `entry` re-recorded at `now` with `status`.
+fn record(now: DateTime<Utc>, status: AsRunStatus, entry: AsRunEntry) -> AsRunEntry {
+    AsRunEntry {
+        time: timestamp(now),
+        status,
+        ..entry
+    }
+}
+
+fn filler_entry(now: DateTime<Utc>, item: &PlaylistItem) -> AsRunEntry {
+    AsRunEntry {
+        time: timestamp(now),
+        status: AsRunStatus::Started,
+        kind: AsRunKind::Filler,
+        event_id: None,
+        title: None,
+        scheduled_start: None,
+        source: item.uri.clone(),
+    }
+}
+
+fn event_entry(
+    now: DateTime<Utc>,
+    status: AsRunStatus,
+    event: &ScheduleEvent,
+    start: DateTime<Utc>,
+) -> AsRunEntry {
+    let (kind, source) = match &event.source {
+        ScheduleSource::File { item } => (AsRunKind::File, item.uri.clone()),
+        ScheduleSource::Live {
+            video_channel,
+            audio_channel,
+            ..
+        } => {
+            let channels: Vec<&str> = [video_channel, audio_channel]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            (AsRunKind::Live, channels.join(", "))
+        }
+    };
+    AsRunEntry {
+        time: timestamp(now),
+        status,
+        kind,
+        event_id: Some(event.id.clone()),
+        title: event.title.clone(),
+        scheduled_start: Some(timestamp(start)),
+        source,
+    }
+}
+
+/// Directory of the as-run logs.
+pub fn as_run_dir() -> PathBuf {
+    crate::paths::data_dir()
+        .unwrap_or(Path::new("."))
+        .join("as-run")
+}
+
+/// As-run log file of a player for one day (YYYY-MM-DD, UTC).
+fn as_run_path(flow_id: &FlowId, block_id: &str, date: &str) -> PathBuf {
+    as_run_dir().join(format!("{}_{}_{}.jsonl", flow_id, block_id, date))
+}
+
+/// Append records to the as-run logs of their day, one JSON object per line.
+pub fn append_as_run(
+    flow_id: &FlowId,
+    block_id: &str,
+    entries: &[AsRunEntry],
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(as_run_dir())?;
+    for entry in entries {
+        let date = entry.time.get(..10).unwrap_or("unknown");
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(as_run_path(flow_id, block_id, date))?;
+        let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Read the as-run log of a player for one day. Days without a log are empty.
+pub fn read_as_run(
+    flow_id: &FlowId,
+    block_id: &str,
+    date: &str,
+) -> std::io::Result<Vec<AsRunEntry>> {
+    let content = match std::fs::read_to_string(as_run_path(flow_id, block_id, date)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
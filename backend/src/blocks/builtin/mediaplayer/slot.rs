@@ -0,0 +1,404 @@
+//! Decode slots and the crossfade mixer.
+//!
+//! A decode slot is one internal pipeline (source → clocksync → appsink) feeding
+//! the bridge. In cut mode the player has a single slot that is reloaded for
+//! every item. For gapless and crossfaded transitions a second slot prerolls the
+//! next item while the active one plays, and the two swap roles at the transition.
+
+use crate::gst::transitions::PropertyBinding;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+/// `DecodeSlot::item_index` of a slot without a loaded item.
+pub const NO_ITEM: usize = usize::MAX;
+
+/// Time past the end of a fade before its control bindings are removed.
+const FADE_SETTLE_MS: u64 = 100;
+
+/// How the player moves from one playlist item to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemTransition {
+    /// Reload the single decode slot, with a short gap.
+    Cut,
+    /// Switch to the prerolled next item at the frame boundary.
+    Gapless,
+    /// Crossfade audio and video into the prerolled next item (decode mode only).
+    Crossfade,
+}
+
+impl ItemTransition {
+    /// Parse the `transition` block property. Unknown values fall back to cut.
+    pub fn from_property(value: &str) -> Self {
+        match value {
+            "gapless" => Self::Gapless,
+            "crossfade" => Self::Crossfade,
+            _ => Self::Cut,
+        }
+    }
+}
+
+/// One internal pipeline playing an item into the bridge.
+pub struct DecodeSlot {
+    /// Slot number (0 or 1). Slot 1 feeds its own appsrcs when crossfading.
+    pub index: usize,
+    /// The isolated internal pipeline (owned by this slot)
+    pub pipeline: gst::Pipeline,
+    /// Weak reference to the source element (uridecodebin or urisourcebin)
+    pub source_element: gst::glib::WeakRef<gst::Element>,
+    /// Whether video pad has been linked (reset on file switch)
+    pub video_linked: AtomicBool,
+    /// Whether audio pad has been linked (reset on file switch)
+    pub audio_linked: AtomicBool,
+    /// Timestamp offset (ns) for this slot's appsink→appsrc bridge.
+    /// Computed once from the first buffer: `main_running_time - buffer_pts`.
+    /// Set to `i64::MIN` to signal "needs recomputation" (on startup, file switch, resume).
+    pub ts_offset: Arc<AtomicI64>,
+    /// Main pipeline running time (ns) the first buffer continues from after a
+    /// gapless switch, instead of the current running time. 0 when unset.
+    pub continue_at_ns: Arc<AtomicU64>,
+    /// Playlist index of the loaded item, or [`NO_ITEM`]
+    pub item_index: AtomicUsize,
+    /// Segment (start, stop) to seek to once the loaded item has prerolled.
+    pub pending_segment: Mutex<Option<(u64, Option<u64>)>>,
+}
+
+impl DecodeSlot {
+    pub fn new(index: usize, pipeline: gst::Pipeline, source: &gst::Element) -> Self {
+        let source_element = gst::glib::WeakRef::new();
+        source_element.set(Some(source));
+        Self {
+            index,
+            pipeline,
+            source_element,
+            video_linked: AtomicBool::new(false),
+            audio_linked: AtomicBool::new(false),
+            ts_offset: Arc::new(AtomicI64::new(i64::MIN)),
+            continue_at_ns: Arc::new(AtomicU64::new(0)),
+            item_index: AtomicUsize::new(NO_ITEM),
+            pending_segment: Mutex::new(None),
+        }
+    }
+
+    /// Stop the slot and set `uri` on its source, leaving the pipeline in READY.
+    ///
+    /// Removes dynamically-created elements (clocksync, appsink) from the previous
+    /// file. The pad-added callback recreates the clocksync→appsink chain for the
+    /// new file's pads once the pipeline starts.
+    pub fn load(&self, uri: &str, item_index: usize) -> Result<(), String> {
+        let source = self
+            .source_element
+            .upgrade()
+            .ok_or("Source element no longer exists")?;
+        self.reset()?;
+        info!("Loading file into slot {}: {}", self.index, uri);
+        source.set_property("uri", uri);
+        self.item_index.store(item_index, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Stop the slot and forget its item.
+    pub fn clear(&self) {
+        if let Err(e) = self.reset() {
+            error!("Failed to clear decode slot {}: {}", self.index, e);
+        }
+        self.item_index.store(NO_ITEM, Ordering::SeqCst);
+    }
+
+    fn reset(&self) -> Result<(), String> {
+        // Set internal pipeline to READY to flush the old stream
+        self.pipeline.set_state(gst::State::Ready).map_err(|e| {
+            error!("Failed to set internal pipeline to Ready: {:?}", e);
+            "Failed to prepare pipeline for file switch".to_string()
+        })?;
+
+        // The source element (uridecodebin/urisourcebin) stays — only the bridge
+        // chain is recreated by pad-added when the new file starts.
+        let source_name = self.source_element.upgrade().map(|s| s.name());
+        let dynamic_elements: Vec<gst::Element> = self
+            .pipeline
+            .iterate_elements()
+            .into_iter()
+            .flatten()
+            .filter(|e| Some(e.name()) != source_name)
+            .collect();
+        for elem in &dynamic_elements {
+            let _ = elem.set_state(gst::State::Null);
+        }
+        for elem in &dynamic_elements {
+            let _ = self.pipeline.remove(elem);
+        }
+
+        // Reset linked flags and timestamp offset so new pads get linked
+        // and the bridge recomputes the offset from the first buffer
+        self.video_linked.store(false, Ordering::SeqCst);
+        self.audio_linked.store(false, Ordering::SeqCst);
+        self.ts_offset.store(i64::MIN, Ordering::SeqCst);
+        self.continue_at_ns.store(0, Ordering::SeqCst);
+        if let Ok(mut pending) = self.pending_segment.lock() {
+            *pending = None;
+        }
+        Ok(())
+    }
+
+    /// Whether the slot holds item `item_index`, prerolled and trimmed, ready to play.
+    pub fn is_ready(&self, item_index: usize) -> bool {
+        self.item_index.load(Ordering::SeqCst) == item_index
+            && self.pipeline.current_state() == gst::State::Paused
+            && self.pipeline.pending_state() == gst::State::VoidPending
+            && self.pending_segment.lock().is_ok_and(|p| p.is_none())
+    }
+
+    /// Seek the loaded file to the segment `start_ns`..`stop_ns`.
+    ///
+    /// The stream ends at `stop_ns`, so EOS marks the out point. Accurate seeks
+    /// are for decoded streams; passthrough streams seek to the keyframe before
+    /// `start_ns` so they start decodable.
+    pub fn seek_segment(
+        &self,
+        start_ns: u64,
+        stop_ns: Option<u64>,
+        accurate: bool,
+    ) -> Result<(), String> {
+        let source = self
+            .source_element
+            .upgrade()
+            .ok_or("Source element no longer exists")?;
+        let accuracy = if accurate {
+            gst::SeekFlags::ACCURATE
+        } else {
+            gst::SeekFlags::KEY_UNIT
+        };
+        let stop_type = if stop_ns.is_some() {
+            gst::SeekType::Set
+        } else {
+            gst::SeekType::None
+        };
+        debug!(
+            "Decode slot {}: Seeking to segment {} - {:?} ns",
+            self.index, start_ns, stop_ns
+        );
+        self.ts_offset.store(i64::MIN, Ordering::SeqCst);
+        source
+            .seek(
+                1.0,
+                gst::SeekFlags::FLUSH | accuracy,
+                gst::SeekType::Set,
+                Some(gst::ClockTime::from_nseconds(start_ns)),
+                stop_type,
+                stop_ns.map(gst::ClockTime::from_nseconds),
+            )
+            .map_err(|e| format!("Segment seek failed: {:?}", e))
+    }
+
+    /// Current position in the loaded file, in nanoseconds.
+    pub fn position(&self) -> Option<u64> {
+        if let Some(source) = self.source_element.upgrade() {
+            if let Some(position) = source.query_position::<gst::ClockTime>() {
+                return Some(position.nseconds());
+            }
+            debug!("Decode slot {}: Source position query failed", self.index);
+        }
+
+        // Fallback: query internal pipeline
+        self.pipeline
+            .query_position::<gst::ClockTime>()
+            .map(|p| p.nseconds())
+    }
+
+    /// Duration of the loaded file, in nanoseconds.
+    pub fn duration(&self) -> Option<u64> {
+        self.pipeline
+            .query_duration::<gst::ClockTime>()
+            .or_else(|| {
+                self.source_element
+                    .upgrade()
+                    .and_then(|s| s.query_duration::<gst::ClockTime>())
+            })
+            .map(|d| d.nseconds())
+    }
+}
+
+/// Internal pipeline of a live breakaway: `intersrc` → `clocksync` → `appsink`,
+/// feeding the bridge in place of the decode slots.
+pub struct LiveSource {
+    /// The isolated internal pipeline
+    pub pipeline: gst::Pipeline,
+    /// Timestamp offset (ns) for the bridge, as in [`DecodeSlot::ts_offset`]
+    pub ts_offset: Arc<AtomicI64>,
+    /// Running time (ns) the first buffer continues from, as in [`DecodeSlot::continue_at_ns`]
+    pub continue_at_ns: Arc<AtomicU64>,
+}
+
+impl LiveSource {
+    pub fn new(pipeline: gst::Pipeline) -> Self {
+        Self {
+            pipeline,
+            ts_offset: Arc::new(AtomicI64::new(i64::MIN)),
+            continue_at_ns: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+/// Main pipeline mixer inputs of the two slots, for crossfades.
+///
+/// Slot 0 feeds the player's appsrcs and slot 1 the appsrcs here. Both go
+/// through a compositor and an audiomixer whose pad alpha and volume select
+/// or blend the slots. Fades are linear control bindings on those pads,
+/// evaluated by the mixers at stream time like the transitions in
+/// `gst/transitions.rs`.
+pub struct Crossfade {
+    /// Fade duration
+    pub duration: Duration,
+    /// Video appsrc of slot 1
+    pub video_appsrc: gst_app::AppSrc,
+    /// Audio appsrc of slot 1
+    pub audio_appsrc: gst_app::AppSrc,
+    /// Compositor sink pads of slot 0 and 1
+    pub video_pads: [gst::Pad; 2],
+    /// Audiomixer sink pads of slot 0 and 1
+    pub audio_pads: [gst::Pad; 2],
+    /// Control bindings of the running fade
+    bindings: Mutex<Vec<PropertyBinding>>,
+    /// Bumped on every fade or cut so older fades don't settle
+    generation: AtomicU64,
+}
+
+impl Crossfade {
+    pub fn new(
+        duration: Duration,
+        video_appsrc: gst_app::AppSrc,
+        audio_appsrc: gst_app::AppSrc,
+        video_pads: [gst::Pad; 2],
+        audio_pads: [gst::Pad; 2],
+    ) -> Self {
+        let crossfade = Self {
+            duration,
+            video_appsrc,
+            audio_appsrc,
+            video_pads,
+            audio_pads,
+            bindings: Mutex::new(Vec::new()),
+            generation: AtomicU64::new(0),
+        };
+        crossfade.mix(0, 1.0);
+        crossfade
+    }
+
+    /// Show slot `to` only, cancelling any running fade.
+    pub fn cut_to(&self, to: usize) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.clear_bindings();
+        self.mix(to, 1.0);
+    }
+
+    /// Fade from the other slot to slot `to`, then call `done`.
+    ///
+    /// The incoming video is composited on top of the outgoing one with rising
+    /// alpha, so the picture never dips. Audio is faded linearly.
+    pub fn fade_to(self: &Arc<Self>, to: usize, done: impl FnOnce() + Send + 'static) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.clear_bindings();
+        self.mix(to, 0.0);
+        if self.duration.is_zero() {
+            self.mix(to, 1.0);
+            done();
+            return;
+        }
+
+        let mut bindings = Vec::new();
+        bindings.extend(ramp_pad(
+            &self.video_pads[to],
+            "alpha",
+            0.0,
+            1.0,
+            self.duration,
+        ));
+        for (i, pad) in self.audio_pads.iter().enumerate() {
+            let (from, target) = if i == to { (0.0, 1.0) } else { (1.0, 0.0) };
+            bindings.extend(ramp_pad(pad, "volume", from, target, self.duration));
+        }
+        if let Ok(mut active) = self.bindings.lock() {
+            *active = bindings;
+        }
+
+        // Once the ramps have run, settle on the final levels
+        let crossfade = Arc::clone(self);
+        gst::glib::timeout_add_once(
+            self.duration + Duration::from_millis(FADE_SETTLE_MS),
+            move || {
+                if crossfade.generation.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                crossfade.clear_bindings();
+                crossfade.mix(to, 1.0);
+                done();
+            },
+        );
+    }
+
+    fn clear_bindings(&self) {
+        if let Ok(mut bindings) = self.bindings.lock() {
+            bindings.clear();
+        }
+    }
+
+    /// Blend slot `to` in at `level` (0.0-1.0) over the other slot.
+    fn mix(&self, to: usize, level: f64) {
+        for (i, pad) in self.video_pads.iter().enumerate() {
+            if i == to {
+                pad.set_property("zorder", 1u32);
+                pad.set_property("alpha", level);
+            } else {
+                pad.set_property("zorder", 0u32);
+                pad.set_property("alpha", if level < 1.0 { 1.0 } else { 0.0 });
+            }
+        }
+        for (i, pad) in self.audio_pads.iter().enumerate() {
+            pad.set_property("volume", if i == to { level } else { 1.0 - level });
+        }
+    }
+}
+
+/// Ramp a double property of a mixer pad linearly from `from` to `to`.
+///
+/// The ramp starts at the mixer's position, the stream time its pad
+/// control bindings are evaluated at. If the position is unknown the
+/// property stays at `from` until the fade settles.
+fn ramp_pad(
+    pad: &gst::Pad,
+    property: &str,
+    from: f64,
+    to: f64,
+    duration: Duration,
+) -> Option<PropertyBinding> {
+    let Some(start) = pad
+        .parent_element()
+        .and_then(|mixer| mixer.query_position::<gst::ClockTime>())
+    else {
+        debug!("Crossfade: no mixer position for {}", pad.name());
+        return None;
+    };
+    let binding = match PropertyBinding::bind(pad, property) {
+        Ok(binding) => binding,
+        Err(e) => {
+            debug!(
+                "Crossfade: failed to bind {} on {}: {}",
+                property,
+                pad.name(),
+                e
+            );
+            return None;
+        }
+    };
+    binding.set(start, from);
+    binding.set(
+        start + gst::ClockTime::from_nseconds(duration.as_nanos() as u64),
+        to,
+    );
+    Some(binding)
+}
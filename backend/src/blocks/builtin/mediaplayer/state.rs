@@ -1,13 +1,18 @@
 //! Media player runtime state, global registry, and lifecycle methods.
 
+use super::bridge;
 use super::normalize_uri;
+use super::schedule::{self, PlayerProgress, ScheduleAction, Scheduler};
+use super::slot::{Crossfade, DecodeSlot, ItemTransition, LiveSource};
+use crate::events::EventBroadcaster;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
-use std::sync::{Arc, LazyLock, RwLock};
-use strom_types::FlowId;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use strom_types::mediaplayer::{EndAction, PlaylistItem, ScheduleSource};
+use strom_types::{FlowId, StromEvent};
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
@@ -24,18 +29,67 @@ pub struct MediaPlayerKey {
 
 /// Playlist with current index, protected by a single lock for consistency.
 pub struct Playlist {
-    pub files: Vec<String>,
+    pub items: Vec<PlaylistItem>,
     pub current_index: usize,
+    /// Repeat of the current item being played (0 = first play)
+    pub loop_pass: u32,
+}
+
+/// What happens when the current item reaches its end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemEnd {
+    /// Play the item again from its in point.
+    Loop,
+    /// Pause on the last frame.
+    Hold,
+    /// Advance to the next item.
+    Next,
+}
+
+impl Playlist {
+    /// Index of the item after the current one, wrapping if `loop_playlist`.
+    pub fn next_index(&self, loop_playlist: bool) -> Result<usize, String> {
+        if self.items.is_empty() {
+            return Err("Playlist is empty".to_string());
+        }
+        let next = self.current_index + 1;
+        if next < self.items.len() {
+            Ok(next)
+        } else if loop_playlist {
+            Ok(0)
+        } else {
+            Err("End of playlist".to_string())
+        }
+    }
+
+    /// Decide what follows the end of the current item, counting the repeat.
+    pub fn end_of_item(&mut self) -> ItemEnd {
+        let Some(item) = self.items.get(self.current_index) else {
+            return ItemEnd::Next;
+        };
+        if self.loop_pass < item.loop_count {
+            self.loop_pass += 1;
+            return ItemEnd::Loop;
+        }
+        match item.end_action {
+            EndAction::Next => ItemEnd::Next,
+            EndAction::Hold => ItemEnd::Hold,
+        }
+    }
 }
 
 /// Runtime state for a media player instance.
 pub struct MediaPlayerState {
     /// Unique instance ID (to detect stale timers after restart)
     pub instance_id: Uuid,
-    /// Weak reference to the source element (uridecodebin or urisourcebin) in the internal pipeline
-    pub source_element: gst::glib::WeakRef<gst::Element>,
-    /// The isolated internal pipeline (owned by this block)
-    pub internal_pipeline: RwLock<Option<gst::Pipeline>>,
+    /// Decode slot playing the current item
+    pub active: RwLock<Option<Arc<DecodeSlot>>>,
+    /// Second decode slot prerolling the next item (gapless and crossfade modes)
+    pub standby: RwLock<Option<Arc<DecodeSlot>>>,
+    /// How items follow each other
+    pub transition: ItemTransition,
+    /// Mixer inputs of both slots (crossfade mode)
+    pub crossfade: Option<Arc<Crossfade>>,
     /// Video appsrc in the main pipeline (bridge target)
     pub video_appsrc: Option<gst_app::AppSrc>,
     /// Audio appsrc in the main pipeline (bridge target)
@@ -52,34 +106,51 @@ pub struct MediaPlayerState {
     pub flow_id: FlowId,
     /// True while load_current_file() is in progress — bus watch should ignore EOS.
     pub switching_file: AtomicBool,
-    /// Whether video pad has been linked (reset on file switch)
-    pub video_linked: AtomicBool,
-    /// Whether audio pad has been linked (reset on file switch)
-    pub audio_linked: AtomicBool,
     /// Whether to decode streams (true) or pass through encoded (false)
     pub decode: bool,
     /// Whether clocksync pacing is enabled
     pub sync: bool,
     /// Configured media files directory (for resolving relative playlist paths)
     pub media_path: std::path::PathBuf,
-    /// Shared timestamp offset (ns) for the appsink→appsrc bridge.
-    /// Computed once from the first buffer: `main_running_time - buffer_pts`.
-    /// Set to `i64::MIN` to signal "needs recomputation" (on startup, file switch, resume).
-    pub ts_offset: Arc<AtomicI64>,
+    /// Main pipeline running time (ns) at the end of the last buffer pushed by
+    /// the bridge, where a gapless switch continues.
+    pub output_end_ns: Arc<AtomicU64>,
     /// Weak reference to the main pipeline (for computing running time in the bridge).
     pub main_pipeline: gst::glib::WeakRef<gst::Pipeline>,
+    /// Whether to start playing once the loaded item has prerolled and been trimmed.
+    pub play_after_preroll: AtomicBool,
+    /// Whether the current item is prerolled and waiting for play (cue-on-load).
+    pub cued: AtomicBool,
+    /// Whether playback is holding the last frame of an item with `EndAction::Hold`.
+    pub held: AtomicBool,
+    /// Whether playback stopped at the end of the playlist.
+    pub ended: AtomicBool,
+    /// Wall-clock playout schedule driving the playlist
+    pub schedule: Mutex<Scheduler>,
+    /// Live breakaway on air instead of the decode slots
+    pub live: Mutex<Option<LiveSource>>,
 }
 
 impl MediaPlayerState {
     /// Get the current file URI, if any.
     pub fn current_file(&self) -> Option<String> {
+        self.current_item().map(|item| item.uri)
+    }
+
+    /// Get the current playlist item, if any.
+    pub fn current_item(&self) -> Option<PlaylistItem> {
         let pl = self.playlist.read().ok()?;
-        pl.files.get(pl.current_index).cloned()
+        pl.items.get(pl.current_index).cloned()
     }
 
     /// Get the playlist length.
     pub fn playlist_len(&self) -> usize {
-        self.playlist.read().map(|pl| pl.files.len()).unwrap_or(0)
+        self.playlist.read().map(|pl| pl.items.len()).unwrap_or(0)
+    }
+
+    /// Get the repeat of the current item being played.
+    pub fn loop_pass(&self) -> u32 {
+        self.playlist.read().map(|pl| pl.loop_pass).unwrap_or(0)
     }
 
     /// Get the current file index.
@@ -91,17 +162,63 @@ impl MediaPlayerState {
     pub fn playlist_files(&self) -> Vec<String> {
         self.playlist
             .read()
-            .map(|pl| pl.files.clone())
+            .map(|pl| pl.items.iter().map(|item| item.uri.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get playlist snapshot (items with playout settings).
+    pub fn playlist_items(&self) -> Vec<PlaylistItem> {
+        self.playlist
+            .read()
+            .map(|pl| pl.items.clone())
             .unwrap_or_default()
     }
 
-    /// Set the playlist, clamping current index to remain valid.
+    /// Set the playlist from plain file URIs.
     pub fn set_playlist(&self, files: Vec<String>) {
+        self.set_items(files.into_iter().map(PlaylistItem::from).collect());
+    }
+
+    /// Set the playlist items, clamping current index to remain valid.
+    ///
+    /// The settings of the current item take effect the next time it is loaded.
+    /// The standby slot is prerolled again, as the next item may have changed.
+    pub fn set_items(&self, items: Vec<PlaylistItem>) {
         if let Ok(mut pl) = self.playlist.write() {
-            if !files.is_empty() && pl.current_index >= files.len() {
+            if !items.is_empty() && pl.current_index >= items.len() {
                 pl.current_index = 0;
             }
-            pl.files = files;
+            pl.items = items;
+        }
+        self.prepare_standby();
+    }
+
+    /// The decode slot playing the current item.
+    pub fn active_slot(&self) -> Option<Arc<DecodeSlot>> {
+        self.active.read().ok()?.clone()
+    }
+
+    fn standby_slot(&self) -> Option<Arc<DecodeSlot>> {
+        self.standby.read().ok()?.clone()
+    }
+
+    /// Whether `slot` plays the current item.
+    pub fn is_active(&self, slot: &DecodeSlot) -> bool {
+        self.active_slot().is_some_and(|s| s.index == slot.index)
+    }
+
+    /// Main pipeline appsrc fed by decode slot `slot_index`.
+    ///
+    /// Both slots feed the same appsrcs, except when crossfading.
+    pub fn appsrc_for(&self, slot_index: usize, video: bool) -> Option<gst_app::AppSrc> {
+        match &self.crossfade {
+            Some(crossfade) if slot_index == 1 => Some(if video {
+                crossfade.video_appsrc.clone()
+            } else {
+                crossfade.audio_appsrc.clone()
+            }),
+            _ if video => self.video_appsrc.clone(),
+            _ => self.audio_appsrc.clone(),
         }
     }
 
@@ -112,11 +229,11 @@ impl MediaPlayerState {
                 .playlist
                 .write()
                 .map_err(|e| format!("Lock error: {}", e))?;
-            if index >= pl.files.len() {
+            if index >= pl.items.len() {
                 return Err(format!(
                     "Index {} out of range (playlist has {} files)",
                     index,
-                    pl.files.len()
+                    pl.items.len()
                 ));
             }
             pl.current_index = index;
@@ -125,25 +242,22 @@ impl MediaPlayerState {
     }
 
     /// Advance to the next file.
-    pub fn next(&self) -> Result<(), String> {
-        {
-            let mut pl = self
+    ///
+    /// Switches to the standby slot if it has the next item prerolled, and
+    /// reloads the active slot otherwise.
+    pub fn next(self: &Arc<Self>) -> Result<(), String> {
+        let next = {
+            let pl = self
                 .playlist
-                .write()
+                .read()
                 .map_err(|e| format!("Lock error: {}", e))?;
-            if pl.files.is_empty() {
-                return Err("Playlist is empty".to_string());
-            }
-            let next = pl.current_index + 1;
-            if next >= pl.files.len() {
-                if self.loop_playlist.load(Ordering::SeqCst) {
-                    pl.current_index = 0;
-                } else {
-                    return Err("End of playlist".to_string());
-                }
-            } else {
-                pl.current_index = next;
-            }
+            pl.next_index(self.loop_playlist.load(Ordering::SeqCst))?
+        };
+        if self.take_standby(next, false) {
+            return Ok(());
+        }
+        if let Ok(mut pl) = self.playlist.write() {
+            pl.current_index = next;
         }
         self.load_current_file()
     }
@@ -155,12 +269,12 @@ impl MediaPlayerState {
                 .playlist
                 .write()
                 .map_err(|e| format!("Lock error: {}", e))?;
-            if pl.files.is_empty() {
+            if pl.items.is_empty() {
                 return Err("Playlist is empty".to_string());
             }
             if pl.current_index == 0 {
                 if self.loop_playlist.load(Ordering::SeqCst) {
-                    pl.current_index = pl.files.len() - 1;
+                    pl.current_index = pl.items.len() - 1;
                 } else {
                     return Err("Already at start of playlist".to_string());
                 }
@@ -171,91 +285,293 @@ impl MediaPlayerState {
         self.load_current_file()
     }
 
-    /// Load the current file into the internal pipeline's source element.
-    ///
-    /// Removes dynamically-created elements (clocksync, appsink) from the previous
-    /// file, then sets the new URI and restarts. The pad-added callback will recreate
-    /// the clocksync→appsink chain for the new file's pads.
+    /// Load the current file into the active slot and restart it, ending a
+    /// live breakaway.
     fn load_current_file(&self) -> Result<(), String> {
+        self.end_live();
         self.switching_file.store(true, Ordering::SeqCst);
         let result = self.load_current_file_inner();
         self.switching_file.store(false, Ordering::SeqCst);
+        if result.is_ok() {
+            self.prepare_standby();
+        }
         result
     }
 
     fn load_current_file_inner(&self) -> Result<(), String> {
         let file_path = self.current_file().ok_or("No file to load")?;
-        let source_element = self
-            .source_element
-            .upgrade()
-            .ok_or("Source element no longer exists")?;
+        let slot = self.active_slot().ok_or("Internal pipeline not created")?;
 
         let uri = normalize_uri(&file_path, &self.media_path);
-        info!("Loading file: {}", uri);
+        slot.load(&uri, self.current_index())?;
+        if let Some(ref crossfade) = self.crossfade {
+            crossfade.cut_to(slot.index);
+        }
 
-        let pipeline_guard = self
-            .internal_pipeline
-            .read()
-            .map_err(|e| format!("Failed to lock internal pipeline: {}", e))?;
-        let pipeline = pipeline_guard
-            .as_ref()
-            .ok_or("Internal pipeline not created")?;
-
-        // Set internal pipeline to READY to flush the old stream
-        pipeline.set_state(gst::State::Ready).map_err(|e| {
-            error!("Failed to set internal pipeline to Ready: {:?}", e);
-            "Failed to prepare pipeline for file switch".to_string()
+        // Start playing again, or preroll first to trim or cue the item
+        let target = self.prepare_current_item(&slot);
+        slot.pipeline.set_state(target).map_err(|e| {
+            error!("Failed to start internal pipeline: {:?}", e);
+            "Failed to start playback".to_string()
         })?;
 
-        // Remove dynamically-created clocksync and appsink elements from previous file.
-        // The source element (uridecodebin/urisourcebin) stays — only the bridge chain
-        // is recreated by pad-added when the new file starts.
-        let dynamic_elements: Vec<gst::Element> = pipeline
-            .iterate_elements()
-            .into_iter()
-            .flatten()
-            .filter(|e| e.name() != source_element.name())
-            .collect();
-        for elem in &dynamic_elements {
-            let _ = elem.set_state(gst::State::Null);
+        Ok(())
+    }
+
+    /// Apply the playout settings of the current item before `slot` starts it.
+    ///
+    /// Returns the state to set the slot's pipeline to: `Paused` when the
+    /// item must preroll to be trimmed or cued, `Playing` otherwise. Prerolled
+    /// items continue in [`Self::on_preroll`].
+    pub fn prepare_current_item(&self, slot: &DecodeSlot) -> gst::State {
+        let item = self.current_item();
+        if let Ok(mut pl) = self.playlist.write() {
+            pl.loop_pass = 0;
+        }
+        let segment = item.as_ref().and_then(PlaylistItem::segment);
+        let cue = item.as_ref().is_some_and(|i| i.cue_on_load);
+        slot.item_index
+            .store(self.current_index(), Ordering::SeqCst);
+        if let Ok(mut pending) = slot.pending_segment.lock() {
+            *pending = segment;
         }
-        for elem in &dynamic_elements {
-            let _ = pipeline.remove(elem);
+        self.play_after_preroll
+            .store(segment.is_some() && !cue, Ordering::SeqCst);
+        self.cued.store(cue, Ordering::SeqCst);
+        self.held.store(false, Ordering::SeqCst);
+        self.ended.store(false, Ordering::SeqCst);
+        self.is_paused.store(cue, Ordering::SeqCst);
+
+        if segment.is_some() || cue {
+            gst::State::Paused
+        } else {
+            gst::State::Playing
         }
+    }
 
-        // Reset linked flags and timestamp offset so new pads get linked
-        // and the bridge recomputes the offset from the first buffer
-        self.video_linked.store(false, Ordering::SeqCst);
-        self.audio_linked.store(false, Ordering::SeqCst);
-        self.ts_offset.store(i64::MIN, Ordering::SeqCst);
+    /// Preroll the item after the current one in the standby slot.
+    ///
+    /// The slot is paused on the item's in point, ready for a gapless switch
+    /// or a crossfade. Without a next item the slot is cleared.
+    pub fn prepare_standby(&self) {
+        let Some(slot) = self.standby_slot() else {
+            return;
+        };
+        let next = self.playlist.read().ok().and_then(|pl| {
+            let index = pl
+                .next_index(self.loop_playlist.load(Ordering::SeqCst))
+                .ok()?;
+            Some((index, pl.items.get(index)?.clone()))
+        });
+        let Some((index, item)) = next else {
+            slot.clear();
+            return;
+        };
+
+        let uri = normalize_uri(&item.uri, &self.media_path);
+        if let Err(e) = slot.load(&uri, index) {
+            error!("Media Player {}: {}", self.block_id, e);
+            return;
+        }
+        if let Ok(mut pending) = slot.pending_segment.lock() {
+            *pending = item.segment();
+        }
+        if let Err(e) = slot.pipeline.set_state(gst::State::Paused) {
+            error!(
+                "Media Player {}: Failed to preroll next item: {:?}",
+                self.block_id, e
+            );
+            slot.clear();
+        }
+    }
 
-        // Set the new URI on source element
-        source_element.set_property("uri", &uri);
+    /// Switch to the standby slot if it has item `index` prerolled.
+    ///
+    /// Without `fade` the new item continues where the bridge output ended,
+    /// at the frame boundary. With `fade` both slots play while the mixer
+    /// crossfades, and the old slot stops afterwards. Returns false if the
+    /// standby slot is not ready.
+    fn take_standby(self: &Arc<Self>, index: usize, fade: bool) -> bool {
+        let Some(incoming) = self.standby_slot() else {
+            return false;
+        };
+        if !incoming.is_ready(index) {
+            debug!(
+                "Media Player {}: Standby slot not ready for item {}",
+                self.block_id, index
+            );
+            return false;
+        }
+        let Some(outgoing) = self.active_slot() else {
+            return false;
+        };
 
-        // Start playing again
-        pipeline.set_state(gst::State::Playing).map_err(|e| {
-            error!("Failed to start internal pipeline: {:?}", e);
-            "Failed to start playback".to_string()
-        })?;
+        if let (Ok(mut active), Ok(mut standby)) = (self.active.write(), self.standby.write()) {
+            *active = Some(Arc::clone(&incoming));
+            *standby = Some(Arc::clone(&outgoing));
+        }
+        if let Ok(mut pl) = self.playlist.write() {
+            pl.current_index = index;
+            pl.loop_pass = 0;
+        }
+        let cue = self.current_item().is_some_and(|i| i.cue_on_load);
+        self.play_after_preroll.store(false, Ordering::SeqCst);
+        self.cued.store(cue, Ordering::SeqCst);
+        self.held.store(false, Ordering::SeqCst);
+        self.ended.store(false, Ordering::SeqCst);
+        self.is_paused.store(cue, Ordering::SeqCst);
+        info!(
+            "Media Player {}: {} to item {} in slot {}",
+            self.block_id,
+            if fade { "Crossfading" } else { "Switching" },
+            index,
+            incoming.index
+        );
 
-        self.is_paused.store(false, Ordering::SeqCst);
+        let fade = fade && !cue;
+        if !fade {
+            incoming
+                .continue_at_ns
+                .store(self.output_end_ns.load(Ordering::SeqCst), Ordering::SeqCst);
+        }
+        if !cue {
+            if let Err(e) = incoming.pipeline.set_state(gst::State::Playing) {
+                error!(
+                    "Media Player {}: Failed to start next item: {:?}",
+                    self.block_id, e
+                );
+            }
+        }
 
-        Ok(())
+        match &self.crossfade {
+            Some(crossfade) if fade => {
+                let state = Arc::downgrade(self);
+                crossfade.fade_to(incoming.index, move || {
+                    if let Some(state) = state.upgrade() {
+                        // Skip if the slots were swapped again during the fade
+                        if !state.is_active(&outgoing) {
+                            outgoing.clear();
+                            state.prepare_standby();
+                        }
+                    }
+                });
+            }
+            crossfade => {
+                if let Some(crossfade) = crossfade {
+                    crossfade.cut_to(incoming.index);
+                }
+                outgoing.clear();
+                self.prepare_standby();
+            }
+        }
+        true
+    }
+
+    /// Start the crossfade into the next item when the current one is about
+    /// to end. Called from the position timer.
+    pub fn check_crossfade(self: &Arc<Self>) {
+        let Some(ref crossfade) = self.crossfade else {
+            return;
+        };
+        if self.is_paused.load(Ordering::SeqCst) || self.switching_file.load(Ordering::SeqCst) {
+            return;
+        }
+        let (item, loop_pass, next) = {
+            let Ok(pl) = self.playlist.read() else {
+                return;
+            };
+            let Some(item) = pl.items.get(pl.current_index).cloned() else {
+                return;
+            };
+            let next = pl.next_index(self.loop_playlist.load(Ordering::SeqCst));
+            (item, pl.loop_pass, next)
+        };
+        let Ok(next) = next else {
+            return;
+        };
+        // Loops and holds end the item without a transition
+        if loop_pass < item.loop_count || item.end_action != EndAction::Next {
+            return;
+        }
+        let Some(slot) = self.active_slot() else {
+            return;
+        };
+        let Some(end_ns) = item.out_point_ns.or_else(|| slot.duration()) else {
+            return;
+        };
+        let Some(position_ns) = slot.position() else {
+            return;
+        };
+        let fade_ns = crossfade.duration.as_nanos() as u64;
+        if position_ns > 0 && end_ns.saturating_sub(position_ns) <= fade_ns {
+            self.take_standby(next, true);
+        }
+    }
+
+    /// Continue loading an item once `slot` has prerolled.
+    ///
+    /// Seeks to the item's in and out points first; the seek prerolls again
+    /// and calls back here. Then starts the current item playing unless it is
+    /// cued. Standby slots stay paused.
+    pub fn on_preroll(self: &Arc<Self>, slot: &DecodeSlot) {
+        let segment = slot
+            .pending_segment
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take());
+        if let Some((start_ns, stop_ns)) = segment {
+            match slot.seek_segment(start_ns, stop_ns, self.decode) {
+                Ok(()) => return,
+                Err(e) => error!("Media Player {}: {}", self.block_id, e),
+            }
+        }
+        if self.is_active(slot) && self.play_after_preroll.swap(false, Ordering::SeqCst) {
+            if let Err(e) = self.play() {
+                error!("Media Player {}: {}", self.block_id, e);
+            }
+        }
+    }
+
+    /// Handle the end of the current item (EOS of the active slot).
+    ///
+    /// Returns what was done, so the caller can report the end of the playlist.
+    pub fn on_item_end(self: &Arc<Self>) -> Result<ItemEnd, String> {
+        let end = self
+            .playlist
+            .write()
+            .map_err(|e| format!("Lock error: {}", e))?
+            .end_of_item();
+        match end {
+            ItemEnd::Loop => {
+                let item = self.current_item().ok_or("No current item")?;
+                let slot = self.active_slot().ok_or("Internal pipeline not created")?;
+                let (start_ns, stop_ns) = item.segment().unwrap_or((0, None));
+                slot.seek_segment(start_ns, stop_ns, self.decode)?;
+            }
+            ItemEnd::Hold => {
+                self.pause()?;
+                self.held.store(true, Ordering::SeqCst);
+            }
+            ItemEnd::Next => self.next().inspect_err(|_| {
+                self.ended.store(true, Ordering::SeqCst);
+            })?,
+        }
+        Ok(end)
     }
 
     /// Play the media.
-    pub fn play(&self) -> Result<(), String> {
-        let pipeline_guard = self
-            .internal_pipeline
-            .read()
-            .map_err(|e| format!("Lock error: {}", e))?;
-        let pipeline = pipeline_guard
-            .as_ref()
-            .ok_or("Internal pipeline not created")?;
+    ///
+    /// Takes a cued item, and continues with the next item after a hold.
+    pub fn play(self: &Arc<Self>) -> Result<(), String> {
+        if self.held.load(Ordering::SeqCst) {
+            return self.next();
+        }
+        self.cued.store(false, Ordering::SeqCst);
+        let slot = self.active_slot().ok_or("Internal pipeline not created")?;
         // Reset timestamp offset so the bridge recomputes from the first buffer
         // after resume — prevents accumulated drift from pause duration.
-        self.ts_offset.store(i64::MIN, Ordering::SeqCst);
-        pipeline.set_state(gst::State::Playing).map_err(|e| {
+        slot.ts_offset.store(i64::MIN, Ordering::SeqCst);
+        slot.pipeline.set_state(gst::State::Playing).map_err(|e| {
             error!("Failed to resume playback: {:?}", e);
             "Failed to resume playback".to_string()
         })?;
@@ -265,14 +581,8 @@ impl MediaPlayerState {
 
     /// Pause the media.
     pub fn pause(&self) -> Result<(), String> {
-        let pipeline_guard = self
-            .internal_pipeline
-            .read()
-            .map_err(|e| format!("Lock error: {}", e))?;
-        let pipeline = pipeline_guard
-            .as_ref()
-            .ok_or("Internal pipeline not created")?;
-        pipeline.set_state(gst::State::Paused).map_err(|e| {
+        let slot = self.active_slot().ok_or("Internal pipeline not created")?;
+        slot.pipeline.set_state(gst::State::Paused).map_err(|e| {
             error!("Failed to pause playback: {:?}", e);
             "Failed to pause playback".to_string()
         })?;
@@ -280,16 +590,20 @@ impl MediaPlayerState {
         Ok(())
     }
 
-    /// Stop playback: pause and seek to the beginning.
+    /// Stop playback: pause and seek to the beginning (the in point of a trimmed item).
     pub fn stop(&self) -> Result<(), String> {
         self.pause()?;
-        self.seek(0)?;
+        let start_ns = self
+            .current_item()
+            .and_then(|item| item.in_point_ns)
+            .unwrap_or(0);
+        self.seek(start_ns)?;
         Ok(())
     }
 
     /// Seek to a position in nanoseconds.
     ///
-    /// Seeks on the internal pipeline's source element. The timestamp offset is reset
+    /// Seeks on the active slot's source element. The timestamp offset is reset
     /// so the bridge recomputes it from the first buffer at the new position.
     pub fn seek(&self, position_ns: u64) -> Result<(), String> {
         let secs = position_ns / 1_000_000_000;
@@ -301,14 +615,15 @@ impl MediaPlayerState {
             position_ns, hours, mins, secs_rem
         );
 
-        let source = self
+        let slot = self.active_slot().ok_or("Internal pipeline not created")?;
+        let source = slot
             .source_element
             .upgrade()
             .ok_or("Source element no longer exists")?;
 
         // Reset timestamp offset so the bridge recomputes from the first buffer
         // after the seek — the file PTS jumps but main pipeline running time doesn't.
-        self.ts_offset.store(i64::MIN, Ordering::SeqCst);
+        slot.ts_offset.store(i64::MIN, Ordering::SeqCst);
 
         let seek_result = source.seek_simple(
             gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
@@ -329,50 +644,162 @@ impl MediaPlayerState {
 
     /// Get current position in nanoseconds.
     pub fn position(&self) -> Option<u64> {
-        if let Some(source) = self.source_element.upgrade() {
-            if let Some(position) = source.query_position::<gst::ClockTime>() {
-                return Some(position.nseconds());
-            }
+        let position = self.active_slot()?.position();
+        if position.is_none() {
             debug!(
-                "Media Player {}: Source element position query failed",
+                "Media Player {}: Position query failed on active slot",
                 self.block_id
             );
         }
-
-        // Fallback: query internal pipeline
-        if let Ok(guard) = self.internal_pipeline.read() {
-            if let Some(ref pipeline) = *guard {
-                if let Some(position) = pipeline.query_position::<gst::ClockTime>() {
-                    return Some(position.nseconds());
-                }
-                debug!(
-                    "Media Player {}: Internal pipeline position query also failed",
-                    self.block_id
-                );
-            }
-        }
-        None
+        position
     }
 
     /// Get duration in nanoseconds.
     pub fn duration(&self) -> Option<u64> {
-        // Try internal pipeline first
-        if let Ok(guard) = self.internal_pipeline.read() {
-            if let Some(ref pipeline) = *guard {
-                if let Some(duration) = pipeline.query_duration::<gst::ClockTime>() {
-                    return Some(duration.nseconds());
-                }
-            }
+        self.active_slot()?.duration()
+    }
+
+    /// Replace the playlist with `items` and play from `index`.
+    ///
+    /// Used by the schedule to put files and the filler on air.
+    pub fn play_items(
+        &self,
+        items: Vec<PlaylistItem>,
+        index: usize,
+        loop_playlist: bool,
+    ) -> Result<(), String> {
+        self.loop_playlist.store(loop_playlist, Ordering::SeqCst);
+        if let Ok(mut pl) = self.playlist.write() {
+            pl.current_index = index.min(items.len().saturating_sub(1));
+            pl.items = items;
         }
+        self.load_current_file()
+    }
 
-        // Fallback: try querying the source element directly
-        if let Some(source) = self.source_element.upgrade() {
-            if let Some(duration) = source.query_duration::<gst::ClockTime>() {
-                return Some(duration.nseconds());
+    /// Break away to live streams from Inter Output channels.
+    ///
+    /// Stops the decode slots and bridges the channels to the active slot's
+    /// appsrcs, continuing where the file output ended.
+    pub fn start_live(
+        &self,
+        video_channel: Option<&str>,
+        audio_channel: Option<&str>,
+    ) -> Result<(), String> {
+        self.end_live();
+        self.switching_file.store(true, Ordering::SeqCst);
+        for slot in [self.active_slot(), self.standby_slot()]
+            .into_iter()
+            .flatten()
+        {
+            slot.clear();
+        }
+        self.switching_file.store(false, Ordering::SeqCst);
+        if let (Some(crossfade), Some(active)) = (&self.crossfade, self.active_slot()) {
+            crossfade.cut_to(active.index);
+        }
+
+        let live = bridge::create_live_pipeline(self, video_channel, audio_channel)?;
+        live.continue_at_ns
+            .store(self.output_end_ns.load(Ordering::SeqCst), Ordering::SeqCst);
+        live.pipeline.set_state(gst::State::Playing).map_err(|e| {
+            error!("Failed to start live pipeline: {:?}", e);
+            "Failed to start live breakaway".to_string()
+        })?;
+        self.cued.store(false, Ordering::SeqCst);
+        self.held.store(false, Ordering::SeqCst);
+        self.ended.store(false, Ordering::SeqCst);
+        self.is_paused.store(false, Ordering::SeqCst);
+        if let Ok(mut guard) = self.live.lock() {
+            *guard = Some(live);
+        }
+        info!("Media Player {}: Live breakaway on air", self.block_id);
+        Ok(())
+    }
+
+    /// Stop the live breakaway, if one is on air.
+    pub fn end_live(&self) {
+        let live = self.live.lock().ok().and_then(|mut guard| guard.take());
+        if let Some(live) = live {
+            let _ = live.pipeline.set_state(gst::State::Null);
+            info!("Media Player {}: Live breakaway off air", self.block_id);
+        }
+    }
+
+    /// Run the playout schedule: put due events or the filler on air and
+    /// record the changes in the as-run log. Called from the schedule timer.
+    pub fn run_schedule(&self, events: &EventBroadcaster) {
+        let progress = PlayerProgress {
+            ended: self.ended.load(Ordering::SeqCst) || self.held.load(Ordering::SeqCst),
+            index: self.current_index(),
+        };
+        let Ok((action, entries)) = self
+            .schedule
+            .lock()
+            .map(|mut scheduler| scheduler.tick(chrono::Utc::now(), progress))
+        else {
+            return;
+        };
+
+        let result = match action {
+            Some(ScheduleAction::Start(ScheduleSource::File { item })) => {
+                self.play_items(vec![item], 0, false)
+            }
+            Some(ScheduleAction::Start(ScheduleSource::Live {
+                video_channel,
+                audio_channel,
+                ..
+            })) => self.start_live(video_channel.as_deref(), audio_channel.as_deref()),
+            Some(ScheduleAction::Filler { items, index }) => self.play_items(items, index, true),
+            Some(ScheduleAction::Idle) => {
+                self.end_live();
+                Ok(())
             }
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            error!("Media Player {}: Schedule: {}", self.block_id, e);
+        }
+
+        if entries.is_empty() {
+            return;
         }
+        if let Err(e) = schedule::append_as_run(&self.flow_id, &self.block_id, &entries) {
+            error!(
+                "Media Player {}: Failed to write as-run log: {}",
+                self.block_id, e
+            );
+        }
+        for entry in entries {
+            info!(
+                "Media Player {}: As-run {:?} {:?} {}",
+                self.block_id, entry.status, entry.kind, entry.source
+            );
+            events.broadcast(StromEvent::MediaPlayerAsRun {
+                flow_id: self.flow_id,
+                block_id: self.block_id.clone(),
+                entry,
+            });
+        }
+    }
 
-        None
+    /// Build a `MediaPlayerStateChanged` event for `state`, reporting the
+    /// current item unless the playlist has ended.
+    pub fn state_changed_event(
+        &self,
+        state: strom_types::mediaplayer::PlayerState,
+        ended: bool,
+    ) -> StromEvent {
+        let current_item = if ended { None } else { self.current_item() };
+        StromEvent::MediaPlayerStateChanged {
+            flow_id: self.flow_id,
+            block_id: self.block_id.clone(),
+            state,
+            current_file: current_item.as_ref().map(|item| item.uri.clone()),
+            current_file_index: self.current_index(),
+            current_item,
+            loop_pass: self.loop_pass(),
+            cued: self.cued.load(Ordering::SeqCst),
+        }
     }
 
     /// Get the current playback state.
@@ -390,14 +817,14 @@ impl MediaPlayerState {
 
 impl Drop for MediaPlayerState {
     fn drop(&mut self) {
-        if let Ok(guard) = self.internal_pipeline.read() {
-            if let Some(ref pipeline) = *guard {
-                debug!(
-                    "Media Player {}: Stopping internal pipeline on drop",
-                    self.block_id
-                );
-                let _ = pipeline.set_state(gst::State::Null);
-            }
+        self.end_live();
+        let slots = [self.active_slot(), self.standby_slot()];
+        for slot in slots.into_iter().flatten() {
+            debug!(
+                "Media Player {}: Stopping internal pipeline of slot {} on drop",
+                self.block_id, slot.index
+            );
+            let _ = slot.pipeline.set_state(gst::State::Null);
         }
     }
 }
@@ -0,0 +1,467 @@
+//! Mixer automation: recording and playback of fader and pan moves.
+//!
+//! Lanes are stored on the flow, one per automated parameter. When the flow
+//! starts, each lane is armed on its element: lanes that play back get a
+//! [`PropertyBinding`] on the element property. While another binding, such
+//! as a snapshot ramp, drives the property, the lane is re-armed once it is
+//! released. Live property updates pass through [`record`], which stores
+//! them against the stream time of the lane's element according to the
+//! lane's mode. Recorded points are written back to the flow when it stops.
+//!
+//! Fader lanes drive the fader's volume element, which also implements the
+//! mute (volume 0), so mute moves land on the fader lane.
+
+use super::definition::mixer_definition;
+use super::snapshot::{in_range, SnapshotError};
+use crate::gst::transitions::PropertyBinding;
+use gstreamer as gst;
+use gstreamer::glib;
+use gstreamer::prelude::*;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use strom_types::mixer::{AutomationLane, AutomationMode, AutomationPoint};
+use strom_types::{FlowId, PropertyValue};
+use tracing::debug;
+
+/// A touched control is released after this long without a move.
+pub(super) const TOUCH_RELEASE_MS: u64 = 500;
+/// Moves further apart than this are steps: the previous value is held
+/// until just before the new point instead of being ramped across the gap.
+pub(super) const STEP_GAP_MS: u64 = 200;
+/// Length of the ramp into a step.
+pub(super) const STEP_RAMP_MS: u64 = 10;
+/// Interval at which touched lanes are checked for release.
+const RELEASE_POLL_MS: u64 = 100;
+
+/// Global registry of running mixers with automation lanes.
+pub static AUTOMATION_REGISTRY: LazyLock<AutomationRegistry> =
+    LazyLock::new(AutomationRegistry::new);
+
+/// Errors from automation operations.
+#[derive(Debug, thiserror::Error)]
+pub enum AutomationError {
+    #[error("Flow not found: {0}")]
+    FlowNotFound(FlowId),
+    #[error("Block {0} is not an audio mixer")]
+    NotMixer(String),
+    #[error("Automation lane not found: {0}")]
+    NotFound(String),
+    #[error("Parameter {0} cannot be automated")]
+    InvalidParameter(String),
+}
+
+impl From<SnapshotError> for AutomationError {
+    fn from(e: SnapshotError) -> Self {
+        match e {
+            SnapshotError::FlowNotFound(id) => Self::FlowNotFound(id),
+            SnapshotError::NotMixer(block_id) => Self::NotMixer(block_id),
+            SnapshotError::NotFound(name) => Self::NotFound(name),
+        }
+    }
+}
+
+/// Element and GStreamer property driven by a lane parameter.
+///
+/// Only faders and pans that exist with the block's current channel and
+/// bus counts can be automated.
+pub fn lane_target(
+    properties: &HashMap<String, PropertyValue>,
+    parameter: &str,
+) -> Option<(String, String)> {
+    if !(parameter.ends_with("_fader") || parameter.ends_with("_pan"))
+        || !in_range(properties, parameter)
+    {
+        return None;
+    }
+    mixer_definition()
+        .exposed_properties
+        .into_iter()
+        .find(|p| p.name == parameter)
+        .map(|p| (p.mapping.element_id, p.mapping.property_name))
+}
+
+/// Lane value at a time, interpolated linearly and held past the ends.
+pub(super) fn value_at(points: &[AutomationPoint], time_ms: u64) -> Option<f64> {
+    let after = points.partition_point(|p| p.time_ms <= time_ms);
+    match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+        (Some(a), Some(b)) => {
+            let t = (time_ms - a.time_ms) as f64 / (b.time_ms - a.time_ms) as f64;
+            Some(a.value + (b.value - a.value) * t)
+        }
+        (Some(a), None) => Some(a.value),
+        (None, Some(b)) => Some(b.value),
+        (None, None) => None,
+    }
+}
+
+/// Recording state of one lane.
+#[derive(Debug)]
+pub(super) struct LaneRecorder {
+    pub mode: AutomationMode,
+    pub points: Vec<AutomationPoint>,
+    /// Time of the last move of the current pass, if one is in progress
+    pass: Option<u64>,
+}
+
+impl LaneRecorder {
+    pub(super) fn new(mode: AutomationMode, mut points: Vec<AutomationPoint>) -> Self {
+        points.sort_by_key(|p| p.time_ms);
+        Self {
+            mode,
+            points,
+            pass: None,
+        }
+    }
+
+    /// Whether the lane should drive its element right now.
+    pub(super) fn plays_back(&self) -> bool {
+        !self.points.is_empty()
+            && match self.mode {
+                AutomationMode::Read => true,
+                AutomationMode::Touch | AutomationMode::Latch => self.pass.is_none(),
+                AutomationMode::Off | AutomationMode::Write => false,
+            }
+    }
+
+    /// Record a move.
+    ///
+    /// Returns `true` when the move takes the control away from playback.
+    pub(super) fn record(&mut self, time_ms: u64, value: f64) -> bool {
+        if matches!(self.mode, AutomationMode::Off | AutomationMode::Read) {
+            return false;
+        }
+        let starts = self.pass.is_none();
+
+        match (self.mode, self.pass) {
+            // Write and latch replace everything from the first move on
+            (AutomationMode::Write | AutomationMode::Latch, None) => {
+                self.points.retain(|p| p.time_ms < time_ms)
+            }
+            // Otherwise replace what was there since the previous move
+            (_, Some(last)) => self
+                .points
+                .retain(|p| p.time_ms <= last || p.time_ms > time_ms),
+            (_, None) => self.points.retain(|p| p.time_ms != time_ms),
+        }
+
+        // Hold the previous value up to a step instead of ramping across the gap
+        let ramp_start = time_ms.saturating_sub(STEP_RAMP_MS);
+        let previous = self
+            .points
+            .iter()
+            .rev()
+            .find(|p| p.time_ms < ramp_start)
+            .map(|p| p.time_ms);
+        if let Some(previous) = previous {
+            if time_ms - previous > STEP_GAP_MS {
+                if let Some(held) = value_at(&self.points, ramp_start) {
+                    self.insert(ramp_start, held);
+                }
+            }
+        }
+        self.insert(time_ms, value);
+        self.pass = Some(time_ms);
+
+        starts && matches!(self.mode, AutomationMode::Touch | AutomationMode::Latch)
+    }
+
+    fn insert(&mut self, time_ms: u64, value: f64) {
+        let index = self.points.partition_point(|p| p.time_ms < time_ms);
+        if self.points.get(index).is_some_and(|p| p.time_ms == time_ms) {
+            self.points[index].value = value;
+        } else {
+            self.points
+                .insert(index, AutomationPoint { time_ms, value });
+        }
+    }
+
+    /// End a touch pass once the control has been left alone.
+    ///
+    /// Returns `true` when playback resumes.
+    pub(super) fn poll_release(&mut self, now_ms: u64) -> bool {
+        match (self.mode, self.pass) {
+            (AutomationMode::Touch, Some(last)) if now_ms >= last + TOUCH_RELEASE_MS => {
+                self.pass = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Change the mode, ending any pass in progress.
+    pub(super) fn set_mode(&mut self, mode: AutomationMode) {
+        self.mode = mode;
+        self.pass = None;
+    }
+
+    /// Replace the points, ending any pass in progress.
+    pub(super) fn set_points(&mut self, mut points: Vec<AutomationPoint>) {
+        points.sort_by_key(|p| p.time_ms);
+        self.points = points;
+        self.pass = None;
+    }
+}
+
+struct LiveLane {
+    parameter: String,
+    recorder: LaneRecorder,
+    element: glib::WeakRef<gst::Element>,
+    /// `"{block}:{element}"`, as used for live property updates
+    element_id: String,
+    property: String,
+    binding: Option<PropertyBinding>,
+}
+
+impl LiveLane {
+    /// Stream time of the element, the time its control binding is
+    /// evaluated at. The pipeline position lags it by the downstream
+    /// latency, see `gst/transitions.rs`.
+    fn position_ms(&self) -> Option<u64> {
+        self.element
+            .upgrade()?
+            .query_position::<gst::ClockTime>()
+            .map(|t| t.mseconds())
+    }
+
+    /// Bind or unbind the control source to match the recorder.
+    fn sync_binding(&mut self) {
+        self.binding = None;
+        if !self.recorder.plays_back() {
+            return;
+        }
+        let Some(element) = self.element.upgrade() else {
+            return;
+        };
+
+        match PropertyBinding::bind(&element, &self.property) {
+            Ok(binding) => {
+                for point in &self.recorder.points {
+                    binding.set(gst::ClockTime::from_mseconds(point.time_ms), point.value);
+                }
+                self.binding = Some(binding);
+            }
+            Err(e) => debug!(
+                "Automation: cannot bind {}.{} yet: {}",
+                self.element_id, self.property, e
+            ),
+        }
+    }
+
+    fn to_lane(&self, block_id: &str) -> AutomationLane {
+        AutomationLane {
+            block_id: block_id.to_string(),
+            parameter: self.parameter.clone(),
+            mode: self.recorder.mode,
+            points: self.recorder.points.clone(),
+        }
+    }
+}
+
+/// Automation state of one running mixer block.
+pub struct MixerAutomation {
+    block_id: String,
+    lanes: Mutex<Vec<LiveLane>>,
+}
+
+impl MixerAutomation {
+    fn new(block_id: &str) -> Arc<Self> {
+        let automation = Arc::new(Self {
+            block_id: block_id.to_string(),
+            lanes: Mutex::new(Vec::new()),
+        });
+
+        let weak = Arc::downgrade(&automation);
+        glib::timeout_add(
+            std::time::Duration::from_millis(RELEASE_POLL_MS),
+            move || match weak.upgrade() {
+                Some(automation) => {
+                    automation.poll();
+                    glib::ControlFlow::Continue
+                }
+                None => glib::ControlFlow::Break,
+            },
+        );
+        automation
+    }
+
+    /// Add or update a lane driving `element.property`.
+    pub fn set_lane(
+        &self,
+        lane: &AutomationLane,
+        element_id: &str,
+        element: &gst::Element,
+        property: &str,
+    ) {
+        let mut lanes = self.lanes.lock();
+        let live = match lanes.iter_mut().position(|l| l.parameter == lane.parameter) {
+            Some(index) => {
+                let live = &mut lanes[index];
+                live.recorder.set_mode(lane.mode);
+                live.recorder.set_points(lane.points.clone());
+                live
+            }
+            None => {
+                lanes.push(LiveLane {
+                    parameter: lane.parameter.clone(),
+                    recorder: LaneRecorder::new(lane.mode, lane.points.clone()),
+                    element: element.downgrade(),
+                    element_id: element_id.to_string(),
+                    property: property.to_string(),
+                    binding: None,
+                });
+                lanes.last_mut().expect("lane was just added")
+            }
+        };
+        live.sync_binding();
+    }
+
+    /// Remove a lane, leaving its element at the current value.
+    pub fn remove_lane(&self, parameter: &str) {
+        let mut lanes = self.lanes.lock();
+        if let Some(index) = lanes.iter().position(|l| l.parameter == parameter) {
+            let mut live = lanes.remove(index);
+            live.recorder.set_mode(AutomationMode::Off);
+            live.sync_binding();
+        }
+    }
+
+    /// Current lanes, including points recorded since the flow started.
+    pub fn lanes(&self) -> Vec<AutomationLane> {
+        self.lanes
+            .lock()
+            .iter()
+            .map(|l| l.to_lane(&self.block_id))
+            .collect()
+    }
+
+    fn record(&self, element_id: &str, property: &str, value: f64) {
+        let mut lanes = self.lanes.lock();
+        let Some(live) = lanes
+            .iter_mut()
+            .find(|l| l.element_id == element_id && l.property == property)
+        else {
+            return;
+        };
+        if matches!(
+            live.recorder.mode,
+            AutomationMode::Off | AutomationMode::Read
+        ) {
+            return;
+        }
+        let Some(now) = live.position_ms() else {
+            return;
+        };
+        if live.recorder.record(now, value) {
+            debug!(
+                "Automation: {} on {} taken over at {}ms",
+                live.parameter, self.block_id, now
+            );
+            live.sync_binding();
+        }
+    }
+
+    fn poll(&self) {
+        for live in self.lanes.lock().iter_mut() {
+            let Some(now) = live.position_ms() else {
+                continue;
+            };
+            if live.recorder.poll_release(now) {
+                debug!(
+                    "Automation: {} on {} released at {}ms",
+                    live.parameter, self.block_id, now
+                );
+                live.sync_binding();
+            } else if live.recorder.plays_back() && live.binding.is_none() {
+                live.sync_binding();
+            }
+        }
+    }
+}
+
+/// Registry key for looking up automation state.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AutomationKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// Global registry for mixer automation state.
+pub struct AutomationRegistry {
+    blocks: RwLock<HashMap<AutomationKey, Arc<MixerAutomation>>>,
+}
+
+impl AutomationRegistry {
+    pub fn new() -> Self {
+        Self {
+            blocks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &AutomationKey) -> Option<Arc<MixerAutomation>> {
+        self.blocks.read().get(key).cloned()
+    }
+
+    /// Get the automation state of a running block, creating it on first use.
+    pub fn get_or_create(&self, key: AutomationKey) -> Arc<MixerAutomation> {
+        self.blocks
+            .write()
+            .entry(key)
+            .or_insert_with_key(|key| MixerAutomation::new(&key.block_id))
+            .clone()
+    }
+
+    /// Remove all automation state for a flow and return its lanes.
+    pub fn take_flow(&self, flow_id: &FlowId) -> Vec<AutomationLane> {
+        let mut blocks = self.blocks.write();
+        let keys: Vec<_> = blocks
+            .keys()
+            .filter(|k| k.flow_id == *flow_id)
+            .cloned()
+            .collect();
+        keys.iter()
+            .filter_map(|key| blocks.remove(key))
+            .flat_map(|automation| automation.lanes())
+            .collect()
+    }
+}
+
+impl Default for AutomationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replace stored lanes with their live versions.
+pub fn merge_lanes(stored: &mut Vec<AutomationLane>, live: Vec<AutomationLane>) {
+    for lane in live {
+        match stored
+            .iter_mut()
+            .find(|l| l.block_id == lane.block_id && l.parameter == lane.parameter)
+        {
+            Some(existing) => *existing = lane,
+            None => stored.push(lane),
+        }
+    }
+}
+
+/// Record a live property update on a mixer element.
+///
+/// `element_id` is `"{block}:{element}"`. Updates on elements without an
+/// automation lane are ignored.
+pub fn record(flow_id: &FlowId, element_id: &str, property_name: &str, value: &PropertyValue) {
+    let value = match value {
+        PropertyValue::Float(f) => *f,
+        PropertyValue::Int(i) => *i as f64,
+        _ => return,
+    };
+    let Some((block_id, _)) = element_id.split_once(':') else {
+        return;
+    };
+    if let Some(automation) = AUTOMATION_REGISTRY.get(&AutomationKey {
+        flow_id: *flow_id,
+        block_id: block_id.to_string(),
+    }) {
+        automation.record(element_id, property_name, value);
+    }
+}
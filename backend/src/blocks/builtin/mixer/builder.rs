@@ -3,9 +3,14 @@ use crate::events::EventBroadcaster;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use std::collections::HashMap;
+use std::sync::Arc;
 use strom_types::{block::*, element::ElementPadRef, FlowId, MediaType, PropertyValue};
 use tracing::{debug, info, warn};
 
+use super::ducking::{
+    parse_duck_key, DuckKey, DuckParams, DuckingKey, MixerDucking, DUCKING_REGISTRY,
+    DUCK_KEY_INTERVAL_NS,
+};
 use super::elements::*;
 use super::metering::connect_mixer_meter_handler;
 use super::properties::*;
@@ -77,6 +82,17 @@ impl BlockBuilder for MixerBuilder {
             });
         }
 
+        // Add mix-minus outputs
+        for mm in 0..parse_num_mix_minus(properties) {
+            outputs.push(ExternalPad {
+                name: format!("mm_out_{}", mm + 1),
+                label: Some(format!("MM{}", mm + 1)),
+                media_type: MediaType::Audio,
+                internal_element_id: format!("mm{}_out_tee", mm),
+                internal_pad_name: "src_%u".to_string(),
+            });
+        }
+
         Some(ExternalPads { inputs, outputs })
     }
 
@@ -91,6 +107,10 @@ impl BlockBuilder for MixerBuilder {
         let num_channels = parse_num_channels(properties);
         let num_aux_buses = parse_num_aux_buses(properties);
         let num_groups = parse_num_groups(properties);
+        let num_mix_minus = parse_num_mix_minus(properties);
+        let mix_minus_exclude: Vec<Vec<usize>> = (0..num_mix_minus)
+            .map(|mm| parse_mix_minus_exclude(properties, mm, num_channels))
+            .collect();
         let dsp_backend = get_string_prop(properties, "dsp_backend", "rust");
         if dsp_backend != "rust" && dsp_backend != "lv2" {
             warn!(
@@ -105,10 +125,11 @@ impl BlockBuilder for MixerBuilder {
         };
         let solo_mode_afl = get_string_prop(properties, "solo_mode", "pfl") == "afl";
         info!(
-            "Mixer config: {} channels, {} aux buses, {} groups, solo={}, dsp={}",
+            "Mixer config: {} channels, {} aux buses, {} groups, {} mix-minus, solo={}, dsp={}",
             num_channels,
             num_aux_buses,
             num_groups,
+            num_mix_minus,
             if solo_mode_afl { "afl" } else { "pfl" },
             dsp_backend,
         );
@@ -242,11 +263,33 @@ impl BlockBuilder for MixerBuilder {
             .map_err(|e| BlockBuildError::ElementCreation(format!("main_out_tee: {}", e)))?;
         elements.push((main_out_tee_id.clone(), main_out_tee));
 
-        // Link: mixer → main_comp → main_eq → main_limiter → main_volume → main_level → main_out_tee
-        internal_links.push((
-            ElementPadRef::pad(&mixer_id, "src"),
-            ElementPadRef::pad(&main_comp_id, "sink"),
-        ));
+        // Mix-minus buses tap the main sum before any bus processing, so the
+        // phase-inverted channel copies cancel exactly.
+        let main_sum_tee_id = format!("{}:main_sum_tee", instance_id);
+        if num_mix_minus > 0 {
+            let main_sum_tee = gst::ElementFactory::make("tee")
+                .name(&main_sum_tee_id)
+                .property("allow-not-linked", true)
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("main_sum_tee: {}", e)))?;
+            elements.push((main_sum_tee_id.clone(), main_sum_tee));
+
+            internal_links.push((
+                ElementPadRef::pad(&mixer_id, "src"),
+                ElementPadRef::pad(&main_sum_tee_id, "sink"),
+            ));
+            internal_links.push((
+                ElementPadRef::element(&main_sum_tee_id),
+                ElementPadRef::pad(&main_comp_id, "sink"),
+            ));
+        } else {
+            internal_links.push((
+                ElementPadRef::pad(&mixer_id, "src"),
+                ElementPadRef::pad(&main_comp_id, "sink"),
+            ));
+        }
+
+        // Link: mixer → [main_sum_tee →] main_comp → main_eq → main_limiter → main_volume → main_level → main_out_tee
         internal_links.push((
             ElementPadRef::pad(&main_comp_id, "src"),
             ElementPadRef::pad(&main_eq_id, "sink"),
@@ -471,6 +514,94 @@ impl BlockBuilder for MixerBuilder {
             ));
         }
 
+        // ========================================================================
+        // Create mix-minus buses
+        // ========================================================================
+        for (mm, excluded) in mix_minus_exclude.iter().enumerate() {
+            let mm_mixer_id = format!("{}:mm{}_mixer", instance_id, mm);
+            let mm_mixer = make_audiomixer(
+                &mm_mixer_id,
+                force_live,
+                latency_ms,
+                min_upstream_latency_ms,
+            )?;
+            elements.push((mm_mixer_id.clone(), mm_mixer));
+
+            let mm_fader = get_float_prop(properties, &format!("mm{}_fader", mm + 1), 1.0);
+            let mm_mute = get_bool_prop(properties, &format!("mm{}_mute", mm + 1), false);
+            let mm_volume_val = if mm_mute { 0.0 } else { mm_fader };
+
+            let mm_volume_id = format!("{}:mm{}_volume", instance_id, mm);
+            let mm_volume = gst::ElementFactory::make("volume")
+                .name(&mm_volume_id)
+                .property("volume", mm_volume_val)
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("mm{}_volume: {}", mm, e)))?;
+            elements.push((mm_volume_id.clone(), mm_volume));
+
+            let mm_level_id = format!("{}:mm{}_level", instance_id, mm);
+            let mm_level = gst::ElementFactory::make("level")
+                .name(&mm_level_id)
+                .property("interval", METER_INTERVAL_NS)
+                .property("post-messages", true)
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("mm{}_level: {}", mm, e)))?;
+            elements.push((mm_level_id.clone(), mm_level));
+
+            // Mix-minus output tee (allow-not-linked so unconnected mm_out doesn't stall pipeline)
+            let mm_out_tee_id = format!("{}:mm{}_out_tee", instance_id, mm);
+            let mm_out_tee = gst::ElementFactory::make("tee")
+                .name(&mm_out_tee_id)
+                .property("allow-not-linked", true)
+                .build()
+                .map_err(|e| {
+                    BlockBuildError::ElementCreation(format!("mm{}_out_tee: {}", mm, e))
+                })?;
+            elements.push((mm_out_tee_id.clone(), mm_out_tee));
+
+            // Queue between main sum tee and mix-minus mixer
+            let mm_main_queue_id = format!("{}:mm{}_main_queue", instance_id, mm);
+            let mm_main_queue = gst::ElementFactory::make("queue")
+                .name(&mm_main_queue_id)
+                .build()
+                .map_err(|e| {
+                    BlockBuildError::ElementCreation(format!("mm{}_main_queue: {}", mm, e))
+                })?;
+            elements.push((mm_main_queue_id.clone(), mm_main_queue));
+
+            // Link: main_sum_tee → queue → mm_mixer → mm_volume → mm_level → mm_out_tee
+            // (the excluded channels are linked to mm_mixer in the channel loop)
+            internal_links.push((
+                ElementPadRef::element(&main_sum_tee_id),
+                ElementPadRef::pad(&mm_main_queue_id, "sink"),
+            ));
+            internal_links.push((
+                ElementPadRef::pad(&mm_main_queue_id, "src"),
+                ElementPadRef::element(&mm_mixer_id),
+            ));
+            internal_links.push((
+                ElementPadRef::pad(&mm_mixer_id, "src"),
+                ElementPadRef::pad(&mm_volume_id, "sink"),
+            ));
+            internal_links.push((
+                ElementPadRef::pad(&mm_volume_id, "src"),
+                ElementPadRef::pad(&mm_level_id, "sink"),
+            ));
+            internal_links.push((
+                ElementPadRef::pad(&mm_level_id, "src"),
+                ElementPadRef::pad(&mm_out_tee_id, "sink"),
+            ));
+
+            debug!(
+                "Mix-minus {} excludes channels {:?}",
+                mm + 1,
+                excluded.iter().map(|ch| ch + 1).collect::<Vec<_>>()
+            );
+        }
+
+        // Ducked channels register here as they are built
+        let ducking = Arc::new(MixerDucking::default());
+
         // ========================================================================
         // Create per-channel processing
         // ========================================================================
@@ -834,10 +965,94 @@ impl BlockBuilder for MixerBuilder {
                 ElementPadRef::pad(&comp_id, "src"),
                 ElementPadRef::pad(&eq_id, "sink"),
             ));
-            internal_links.push((
-                ElementPadRef::pad(&eq_id, "src"),
-                ElementPadRef::pad(&pre_fader_tee_id, "sink"),
-            ));
+
+            // Ducking: eq → duck → pre_fader_tee, with the key tapped post-fader
+            // (or from the group output) into a fast level meter.
+            if let Some(key) = parse_duck_key(properties, ch, num_channels, num_groups) {
+                let duck_id = format!("{}:duck_{}", instance_id, ch);
+                let duck = gst::ElementFactory::make("volume")
+                    .name(&duck_id)
+                    .build()
+                    .map_err(|e| {
+                        BlockBuildError::ElementCreation(format!("duck ch{}: {}", ch_num, e))
+                    })?;
+                ducking.add_channel(ch, DuckParams::from_properties(properties, ch), &duck);
+                elements.push((duck_id.clone(), duck));
+
+                let key_queue_id = format!("{}:duck_key_queue_{}", instance_id, ch);
+                let key_queue = gst::ElementFactory::make("queue")
+                    .name(&key_queue_id)
+                    .build()
+                    .map_err(|e| {
+                        BlockBuildError::ElementCreation(format!(
+                            "duck_key_queue ch{}: {}",
+                            ch_num, e
+                        ))
+                    })?;
+                elements.push((key_queue_id.clone(), key_queue));
+
+                let key_level_id = format!("{}:duck_key_level_{}", instance_id, ch);
+                let key_level = gst::ElementFactory::make("level")
+                    .name(&key_level_id)
+                    .property("interval", DUCK_KEY_INTERVAL_NS)
+                    .property("post-messages", true)
+                    .build()
+                    .map_err(|e| {
+                        BlockBuildError::ElementCreation(format!(
+                            "duck_key_level ch{}: {}",
+                            ch_num, e
+                        ))
+                    })?;
+                elements.push((key_level_id.clone(), key_level));
+
+                let key_sink_id = format!("{}:duck_key_sink_{}", instance_id, ch);
+                let key_sink = gst::ElementFactory::make("fakesink")
+                    .name(&key_sink_id)
+                    .property("sync", false)
+                    .property("async", false)
+                    .build()
+                    .map_err(|e| {
+                        BlockBuildError::ElementCreation(format!(
+                            "duck_key_sink ch{}: {}",
+                            ch_num, e
+                        ))
+                    })?;
+                elements.push((key_sink_id.clone(), key_sink));
+
+                let key_tee_id = match key {
+                    DuckKey::Channel(k) => format!("{}:post_fader_tee_{}", instance_id, k),
+                    DuckKey::Group(g) => format!("{}:group{}_out_tee", instance_id, g),
+                };
+
+                // Link: eq → duck → pre_fader_tee
+                //       key tee → duck_key_queue → duck_key_level → duck_key_sink
+                internal_links.push((
+                    ElementPadRef::pad(&eq_id, "src"),
+                    ElementPadRef::pad(&duck_id, "sink"),
+                ));
+                internal_links.push((
+                    ElementPadRef::pad(&duck_id, "src"),
+                    ElementPadRef::pad(&pre_fader_tee_id, "sink"),
+                ));
+                internal_links.push((
+                    ElementPadRef::element(&key_tee_id),
+                    ElementPadRef::pad(&key_queue_id, "sink"),
+                ));
+                internal_links.push((
+                    ElementPadRef::pad(&key_queue_id, "src"),
+                    ElementPadRef::pad(&key_level_id, "sink"),
+                ));
+                internal_links.push((
+                    ElementPadRef::pad(&key_level_id, "src"),
+                    ElementPadRef::pad(&key_sink_id, "sink"),
+                ));
+                debug!("Channel {} ducked by {:?}", ch_num, key);
+            } else {
+                internal_links.push((
+                    ElementPadRef::pad(&eq_id, "src"),
+                    ElementPadRef::pad(&pre_fader_tee_id, "sink"),
+                ));
+            }
 
             // pre_fader_tee → pan → volume → post_fader_tee → level → routing_tee
             internal_links.push((
@@ -906,15 +1121,88 @@ impl BlockBuilder for MixerBuilder {
                 })?;
             elements.push((to_main_queue_id.clone(), to_main_queue));
 
-            // Link: routing_tee → to_main_vol → to_main_queue → main_mixer
+            // Link: routing_tee → to_main_vol → [to_main_tee →] to_main_queue → main_mixer
             internal_links.push((
                 ElementPadRef::element(&routing_tee_id),
                 ElementPadRef::pad(&to_main_vol_id, "sink"),
             ));
-            internal_links.push((
-                ElementPadRef::pad(&to_main_vol_id, "src"),
-                ElementPadRef::pad(&to_main_queue_id, "sink"),
-            ));
+
+            // Mix-minus buses excluding this channel subtract exactly what it
+            // feeds into main, so the to_main routing is tracked live.
+            let excluded_from: Vec<usize> = (0..num_mix_minus)
+                .filter(|&mm| mix_minus_exclude[mm].contains(&ch))
+                .collect();
+            if excluded_from.is_empty() {
+                internal_links.push((
+                    ElementPadRef::pad(&to_main_vol_id, "src"),
+                    ElementPadRef::pad(&to_main_queue_id, "sink"),
+                ));
+            } else {
+                let to_main_tee_id = format!("{}:to_main_tee_{}", instance_id, ch);
+                let to_main_tee = gst::ElementFactory::make("tee")
+                    .name(&to_main_tee_id)
+                    .property("allow-not-linked", true)
+                    .build()
+                    .map_err(|e| {
+                        BlockBuildError::ElementCreation(format!("to_main_tee ch{}: {}", ch_num, e))
+                    })?;
+                elements.push((to_main_tee_id.clone(), to_main_tee));
+
+                internal_links.push((
+                    ElementPadRef::pad(&to_main_vol_id, "src"),
+                    ElementPadRef::pad(&to_main_tee_id, "sink"),
+                ));
+                internal_links.push((
+                    ElementPadRef::element(&to_main_tee_id),
+                    ElementPadRef::pad(&to_main_queue_id, "sink"),
+                ));
+
+                for mm in excluded_from {
+                    let invert_id = format!("{}:mm_invert_{}_{}", instance_id, mm, ch);
+                    let invert = make_phase_invert(&invert_id)?;
+                    elements.push((invert_id.clone(), invert));
+
+                    let mm_queue_id = format!("{}:mm_queue_{}_{}", instance_id, mm, ch);
+                    let mm_queue = gst::ElementFactory::make("queue")
+                        .name(&mm_queue_id)
+                        .build()
+                        .map_err(|e| {
+                            BlockBuildError::ElementCreation(format!(
+                                "mm_queue ch{} mm{}: {}",
+                                ch_num,
+                                mm + 1,
+                                e
+                            ))
+                        })?;
+                    elements.push((mm_queue_id.clone(), mm_queue));
+
+                    // Link: to_main_tee → mm_invert → mm_queue → mm_mixer
+                    let mm_mixer_id = format!("{}:mm{}_mixer", instance_id, mm);
+                    internal_links.push((
+                        ElementPadRef::element(&to_main_tee_id),
+                        ElementPadRef::pad(&invert_id, "sink"),
+                    ));
+                    internal_links.push((
+                        ElementPadRef::pad(&invert_id, "src"),
+                        ElementPadRef::pad(&mm_queue_id, "sink"),
+                    ));
+                    internal_links.push((
+                        ElementPadRef::pad(&mm_queue_id, "src"),
+                        ElementPadRef::element(&mm_mixer_id),
+                    ));
+                }
+
+                // A group return carries this channel into main too, but only
+                // the direct route can be subtracted.
+                if (0..num_groups).any(|sg| {
+                    get_bool_prop(properties, &format!("ch{}_to_grp{}", ch_num, sg + 1), false)
+                }) {
+                    warn!(
+                        "Channel {} is routed to a group; mix-minus only removes its direct route to main",
+                        ch_num
+                    );
+                }
+            }
             internal_links.push((
                 ElementPadRef::pad(&to_main_queue_id, "src"),
                 ElementPadRef::element(&mixer_id),
@@ -982,11 +1270,37 @@ impl BlockBuilder for MixerBuilder {
 
         info!("Mixer block created with {} channels", num_channels);
 
-        // Create bus message handler for metering
+        // Register ducking state for live parameter changes
+        let ducking = if ducking.is_empty() {
+            None
+        } else {
+            match properties.get("_flow_id").and_then(|v| match v {
+                PropertyValue::String(s) => s.parse::<FlowId>().ok(),
+                _ => None,
+            }) {
+                Some(flow_id) => DUCKING_REGISTRY.register(
+                    DuckingKey {
+                        flow_id,
+                        block_id: instance_id.to_string(),
+                    },
+                    Arc::clone(&ducking),
+                ),
+                None => warn!("Mixer {} has no flow ID, ducking is not live", instance_id),
+            }
+            Some(ducking)
+        };
+
+        // Create bus message handler for metering (and the ducking envelopes)
         let handler_instance_id = instance_id.to_string();
         let bus_message_handler = Some(Box::new(
             move |bus: &gst::Bus, flow_id: FlowId, events: EventBroadcaster| {
-                connect_mixer_meter_handler(bus, flow_id, events, handler_instance_id.clone())
+                connect_mixer_meter_handler(
+                    bus,
+                    flow_id,
+                    events,
+                    handler_instance_id.clone(),
+                    ducking.clone(),
+                )
             },
         ) as crate::blocks::BusMessageConnectFn);
 
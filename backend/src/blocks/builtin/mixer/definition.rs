@@ -1,3 +1,4 @@
+use super::snapshot::SNAPSHOTS_PROPERTY;
 use strom_types::mixer::*;
 use strom_types::{block::*, EnumValue, MediaType, PropertyValue};
 
@@ -95,6 +96,20 @@ pub(super) fn mixer_definition() -> BlockDefinition {
             },
             live: false,
         },
+        // Main mute
+        ExposedProperty {
+            name: "main_mute".to_string(),
+            label: "Main Mute".to_string(),
+            description: "Mute the main output".to_string(),
+            property_type: PropertyType::Bool,
+            default_value: Some(PropertyValue::Bool(false)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "main_mute".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
         // Number of aux buses
         ExposedProperty {
             name: "num_aux_buses".to_string(),
@@ -169,6 +184,43 @@ pub(super) fn mixer_definition() -> BlockDefinition {
             },
             live: false,
         },
+        // Number of mix-minus buses
+        ExposedProperty {
+            name: "num_mix_minus".to_string(),
+            label: "Mix-Minus Buses".to_string(),
+            description: "Number of mix-minus (N-1) buses (0-4)".to_string(),
+            property_type: PropertyType::Enum {
+                values: vec![
+                    EnumValue {
+                        value: "0".to_string(),
+                        label: Some("None".to_string()),
+                    },
+                    EnumValue {
+                        value: "1".to_string(),
+                        label: Some("1".to_string()),
+                    },
+                    EnumValue {
+                        value: "2".to_string(),
+                        label: Some("2".to_string()),
+                    },
+                    EnumValue {
+                        value: "3".to_string(),
+                        label: Some("3".to_string()),
+                    },
+                    EnumValue {
+                        value: "4".to_string(),
+                        label: Some("4".to_string()),
+                    },
+                ],
+            },
+            default_value: Some(PropertyValue::String("0".to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "num_mix_minus".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
         // PFL master level
         ExposedProperty {
             name: "pfl_level".to_string(),
@@ -253,6 +305,21 @@ pub(super) fn mixer_definition() -> BlockDefinition {
         live: false,
     });
 
+    // Snapshots (JSON format), saved and recalled with the snapshot API
+    exposed_properties.push(ExposedProperty {
+        name: SNAPSHOTS_PROPERTY.to_string(),
+        label: "Snapshots".to_string(),
+        description: "JSON map of named console snapshots: {\"name\": {\"ch1_fader\": 1.0, \"ch1_mute\": false}}. Saved and recalled with the snapshot API".to_string(),
+        property_type: PropertyType::Multiline,
+        default_value: Some(PropertyValue::String("{}".to_string())),
+        mapping: PropertyMapping {
+            element_id: "_block".to_string(),
+            property_name: SNAPSHOTS_PROPERTY.to_string(),
+            transform: None,
+        },
+        live: false,
+    });
+
     // ========================================================================
     // Main bus processing properties
     // ========================================================================
@@ -310,6 +377,14 @@ pub(super) fn mixer_definition() -> BlockDefinition {
             "Main bus compressor makeup gain in dB (0 to 24)",
             Some("db_to_linear"),
         ),
+        (
+            "main_comp_knee",
+            "Main Comp Knee",
+            "kn",
+            DEFAULT_COMP_KNEE as f64,
+            "Main bus compressor knee in dB (-24 to 0)",
+            Some("db_to_linear"),
+        ),
     ] {
         exposed_properties.push(ExposedProperty {
             name: prop_suffix.to_string(),
@@ -476,6 +551,52 @@ pub(super) fn mixer_definition() -> BlockDefinition {
         });
     }
 
+    // Add mix-minus bus properties
+    for mm in 1..=MAX_MIX_MINUS {
+        exposed_properties.push(ExposedProperty {
+            name: format!("mm{}_fader", mm),
+            label: format!("Mix-Minus {} Fader", mm),
+            description: format!("Mix-minus bus {} level (0.0 to 2.0)", mm),
+            property_type: PropertyType::Float,
+            default_value: Some(PropertyValue::Float(1.0)),
+            mapping: PropertyMapping {
+                element_id: format!("mm{}_volume", mm - 1),
+                property_name: "volume".to_string(),
+                transform: None,
+            },
+            live: false,
+        });
+        exposed_properties.push(ExposedProperty {
+            name: format!("mm{}_mute", mm),
+            label: format!("Mix-Minus {} Mute", mm),
+            description: format!("Mute mix-minus bus {}", mm),
+            property_type: PropertyType::Bool,
+            default_value: Some(PropertyValue::Bool(false)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("mm{}_mute", mm),
+                transform: None,
+            },
+            live: false,
+        });
+        exposed_properties.push(ExposedProperty {
+            name: format!("mm{}_exclude", mm),
+            label: format!("Mix-Minus {} Excludes", mm),
+            description: format!(
+                "Channels left out of mix-minus bus {}, comma-separated (e.g. \"1,3\")",
+                mm
+            ),
+            property_type: PropertyType::String,
+            default_value: Some(PropertyValue::String(mm.to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("mm{}_exclude", mm),
+                transform: None,
+            },
+            live: false,
+        });
+    }
+
     // Add per-channel properties (we'll generate for max channels, UI will show based on num_channels)
     for ch in 1..=MAX_CHANNELS {
         // Channel label
@@ -493,6 +614,21 @@ pub(super) fn mixer_definition() -> BlockDefinition {
             live: false,
         });
 
+        // Recall safe (excluded from snapshot recall)
+        exposed_properties.push(ExposedProperty {
+            name: format!("ch{}_recall_safe", ch),
+            label: format!("Ch {} Recall Safe", ch),
+            description: format!("Leave channel {} untouched when recalling a snapshot", ch),
+            property_type: PropertyType::Bool,
+            default_value: Some(PropertyValue::Bool(false)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("ch{}_recall_safe", ch),
+                transform: None,
+            },
+            live: false,
+        });
+
         // Input gain
         exposed_properties.push(ExposedProperty {
             name: format!("ch{}_gain", ch),
@@ -630,6 +766,97 @@ pub(super) fn mixer_definition() -> BlockDefinition {
             });
         }
 
+        // ============================================================
+        // Ducking (sidechain) properties
+        // ============================================================
+        let mut duck_keys = vec![EnumValue {
+            value: "none".to_string(),
+            label: Some("None".to_string()),
+        }];
+        duck_keys.extend((1..=MAX_CHANNELS).filter(|&k| k != ch).map(|k| EnumValue {
+            value: format!("ch{}", k),
+            label: Some(format!("Ch {}", k)),
+        }));
+        duck_keys.extend((1..=MAX_GROUPS).map(|g| EnumValue {
+            value: format!("group{}", g),
+            label: Some(format!("Group {}", g)),
+        }));
+        exposed_properties.push(ExposedProperty {
+            name: format!("ch{}_duck_key", ch),
+            label: format!("Ch {} Duck Key", ch),
+            description: format!(
+                "Channel or group whose level ducks channel {} (set when the flow starts)",
+                ch
+            ),
+            property_type: PropertyType::Enum { values: duck_keys },
+            default_value: Some(PropertyValue::String("none".to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("ch{}_duck_key", ch),
+                transform: None,
+            },
+            live: false,
+        });
+        exposed_properties.push(ExposedProperty {
+            name: format!("ch{}_duck_enabled", ch),
+            label: format!("Ch {} Duck", ch),
+            description: format!("Enable ducking on channel {}", ch),
+            property_type: PropertyType::Bool,
+            default_value: Some(PropertyValue::Bool(false)),
+            mapping: PropertyMapping {
+                element_id: format!("duck_{}", ch - 1),
+                property_name: "duck-enabled".to_string(),
+                transform: None,
+            },
+            live: false,
+        });
+        for (suffix, label, description, default) in [
+            (
+                "threshold",
+                "Thresh",
+                "key level that triggers ducking in dB (-60 to 0)",
+                DEFAULT_DUCK_THRESHOLD,
+            ),
+            (
+                "depth",
+                "Depth",
+                "attenuation while ducked in dB (0 to 60)",
+                DEFAULT_DUCK_DEPTH,
+            ),
+            (
+                "attack",
+                "Attack",
+                "ducking attack time in ms (0 to 1000)",
+                DEFAULT_DUCK_ATTACK,
+            ),
+            (
+                "hold",
+                "Hold",
+                "time ducking is held after the key drops in ms (0 to 5000)",
+                DEFAULT_DUCK_HOLD,
+            ),
+            (
+                "release",
+                "Release",
+                "ducking release time in ms (0 to 5000)",
+                DEFAULT_DUCK_RELEASE,
+            ),
+        ] {
+            exposed_properties.push(ExposedProperty {
+                name: format!("ch{}_duck_{}", ch, suffix),
+                label: format!("Ch {} Duck {}", ch, label),
+                description: format!("Channel {} {}", ch, description),
+                property_type: PropertyType::Float,
+                default_value: Some(PropertyValue::Float(default as f64)),
+                mapping: PropertyMapping {
+                    element_id: format!("duck_{}", ch - 1),
+                    property_name: format!("duck-{}", suffix),
+                    transform: None,
+                },
+                live: false,
+            });
+        }
+
         // ============================================================
         // HPF properties
         // ============================================================
@@ -0,0 +1,303 @@
+//! Sidechain ducking: a channel is attenuated while a key channel or group is
+//! above a threshold.
+//!
+//! The key is tapped post-fader into a fast `level` element. Its messages
+//! drive an attack/hold/release envelope on the bus watch, which sets the
+//! `duck_N` volume inserted after the ducked channel's EQ. The key source is
+//! fixed when the pipeline is built; everything else can be changed live
+//! through the `duck-*` pseudo-properties of `duck_N`.
+//!
+//! Gain reduction is reported as `MeterData` with element ID
+//! `"{block}:meter:grN"` and a single (negative) dB value.
+
+use super::properties::{db_to_linear, get_bool_prop, get_float_prop, get_string_prop};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use strom_types::mixer::{
+    DEFAULT_DUCK_ATTACK, DEFAULT_DUCK_DEPTH, DEFAULT_DUCK_HOLD, DEFAULT_DUCK_RELEASE,
+    DEFAULT_DUCK_THRESHOLD,
+};
+use strom_types::{FlowId, PropertyValue};
+
+/// Key level interval in nanoseconds (10ms), the envelope's time step.
+pub(super) const DUCK_KEY_INTERVAL_NS: u64 = 10_000_000;
+const DUCK_KEY_INTERVAL_MS: f64 = DUCK_KEY_INTERVAL_NS as f64 / 1_000_000.0;
+/// Gain reduction is reported every this many key readings (100ms).
+const GR_REPORT_EVERY: u32 = 10;
+
+/// Global registry of running mixers with ducked channels, used to apply live
+/// parameter changes.
+pub static DUCKING_REGISTRY: LazyLock<DuckingRegistry> = LazyLock::new(DuckingRegistry::new);
+
+/// Sidechain key source of a ducked channel (0-based indices).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DuckKey {
+    Channel(usize),
+    Group(usize),
+}
+
+/// Parse `chN_duck_key` for a channel (0-based).
+///
+/// Accepts `chK` or `groupG` (1-based). Unknown, out-of-range and self keys
+/// disable ducking for the channel.
+pub(super) fn parse_duck_key(
+    properties: &HashMap<String, PropertyValue>,
+    ch: usize,
+    num_channels: usize,
+    num_groups: usize,
+) -> Option<DuckKey> {
+    let key = get_string_prop(properties, &format!("ch{}_duck_key", ch + 1), "none");
+    if let Some(k) = key.strip_prefix("ch").and_then(|n| n.parse::<usize>().ok()) {
+        return (k >= 1 && k <= num_channels && k - 1 != ch).then(|| DuckKey::Channel(k - 1));
+    }
+    if let Some(g) = key
+        .strip_prefix("group")
+        .and_then(|n| n.parse::<usize>().ok())
+    {
+        return (g >= 1 && g <= num_groups).then(|| DuckKey::Group(g - 1));
+    }
+    None
+}
+
+/// Ducking parameters of one channel.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct DuckParams {
+    pub enabled: bool,
+    /// Key level above which the channel is ducked (dBFS)
+    pub threshold_db: f64,
+    /// Attenuation while ducked (dB, positive)
+    pub depth_db: f64,
+    pub attack_ms: f64,
+    /// Time the duck is held after the key drops below threshold
+    pub hold_ms: f64,
+    pub release_ms: f64,
+}
+
+impl DuckParams {
+    /// Read the parameters of a channel (0-based) from block properties.
+    pub(super) fn from_properties(properties: &HashMap<String, PropertyValue>, ch: usize) -> Self {
+        let n = ch + 1;
+        Self {
+            enabled: get_bool_prop(properties, &format!("ch{}_duck_enabled", n), false),
+            threshold_db: get_float_prop(
+                properties,
+                &format!("ch{}_duck_threshold", n),
+                DEFAULT_DUCK_THRESHOLD as f64,
+            ),
+            depth_db: get_float_prop(
+                properties,
+                &format!("ch{}_duck_depth", n),
+                DEFAULT_DUCK_DEPTH as f64,
+            )
+            .max(0.0),
+            attack_ms: get_float_prop(
+                properties,
+                &format!("ch{}_duck_attack", n),
+                DEFAULT_DUCK_ATTACK as f64,
+            ),
+            hold_ms: get_float_prop(
+                properties,
+                &format!("ch{}_duck_hold", n),
+                DEFAULT_DUCK_HOLD as f64,
+            ),
+            release_ms: get_float_prop(
+                properties,
+                &format!("ch{}_duck_release", n),
+                DEFAULT_DUCK_RELEASE as f64,
+            ),
+        }
+    }
+
+    /// Apply a `duck-*` pseudo-property.
+    pub(super) fn set(&mut self, property: &str, value: &PropertyValue) -> Result<(), String> {
+        let float = || match value {
+            PropertyValue::Float(f) => Ok(*f),
+            PropertyValue::Int(i) => Ok(*i as f64),
+            PropertyValue::UInt(u) => Ok(*u as f64),
+            _ => Err(format!(
+                "Expected a number for {}, got {:?}",
+                property, value
+            )),
+        };
+        match property {
+            "duck-enabled" => match value {
+                PropertyValue::Bool(b) => self.enabled = *b,
+                _ => return Err(format!("Expected a bool for {}, got {:?}", property, value)),
+            },
+            "duck-threshold" => self.threshold_db = float()?,
+            "duck-depth" => self.depth_db = float()?.max(0.0),
+            "duck-attack" => self.attack_ms = float()?.max(0.0),
+            "duck-hold" => self.hold_ms = float()?.max(0.0),
+            "duck-release" => self.release_ms = float()?.max(0.0),
+            _ => return Err(format!("Unknown ducking property {}", property)),
+        }
+        Ok(())
+    }
+}
+
+/// Attack/hold/release envelope producing the gain reduction in dB.
+///
+/// Attack and release are the times for a full-depth change; the reduction
+/// moves linearly in dB.
+#[derive(Debug, Default)]
+pub(super) struct Envelope {
+    reduction_db: f64,
+    hold_left_ms: f64,
+}
+
+impl Envelope {
+    /// Advance by `dt_ms` with the key at `key_db`. Returns the reduction (dB, positive).
+    pub(super) fn step(&mut self, params: &DuckParams, key_db: f64, dt_ms: f64) -> f64 {
+        let target = if !params.enabled {
+            self.hold_left_ms = 0.0;
+            0.0
+        } else if key_db >= params.threshold_db {
+            self.hold_left_ms = params.hold_ms;
+            params.depth_db
+        } else if self.hold_left_ms > 0.0 {
+            self.hold_left_ms = (self.hold_left_ms - dt_ms).max(0.0);
+            params.depth_db
+        } else {
+            0.0
+        };
+
+        let span = params.depth_db.max(self.reduction_db);
+        if target > self.reduction_db {
+            self.reduction_db = if params.attack_ms <= 0.0 {
+                target
+            } else {
+                (self.reduction_db + span * dt_ms / params.attack_ms).min(target)
+            };
+        } else if target < self.reduction_db {
+            self.reduction_db = if params.release_ms <= 0.0 {
+                target
+            } else {
+                (self.reduction_db - span * dt_ms / params.release_ms).max(target)
+            };
+        }
+        self.reduction_db
+    }
+}
+
+struct DuckChannel {
+    params: DuckParams,
+    envelope: Envelope,
+    volume: gst::glib::WeakRef<gst::Element>,
+    readings: u32,
+}
+
+/// Ducking state of one mixer block.
+#[derive(Default)]
+pub struct MixerDucking {
+    channels: Mutex<HashMap<usize, DuckChannel>>,
+}
+
+impl MixerDucking {
+    /// Add a ducked channel (0-based) driving the given `duck_N` volume.
+    pub(super) fn add_channel(&self, ch: usize, params: DuckParams, volume: &gst::Element) {
+        self.channels.lock().insert(
+            ch,
+            DuckChannel {
+                params,
+                envelope: Envelope::default(),
+                volume: volume.downgrade(),
+                readings: 0,
+            },
+        );
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.channels.lock().is_empty()
+    }
+
+    /// Feed a key reading for a channel (0-based) and update its volume.
+    ///
+    /// Returns the gain reduction (dB, positive) when it is due to be reported.
+    pub(super) fn process_key(&self, ch: usize, key_db: f64) -> Option<f64> {
+        let mut channels = self.channels.lock();
+        let duck = channels.get_mut(&ch)?;
+        let previous = duck.envelope.reduction_db;
+        let reduction = duck
+            .envelope
+            .step(&duck.params, key_db, DUCK_KEY_INTERVAL_MS);
+        if reduction != previous {
+            if let Some(volume) = duck.volume.upgrade() {
+                volume.set_property("volume", db_to_linear(-reduction));
+            }
+        }
+        duck.readings = (duck.readings + 1) % GR_REPORT_EVERY;
+        (duck.readings == 0).then_some(reduction)
+    }
+
+    fn update(&self, ch: usize, property: &str, value: &PropertyValue) -> Result<(), String> {
+        let mut channels = self.channels.lock();
+        let duck = channels
+            .get_mut(&ch)
+            .ok_or_else(|| format!("Channel {} has no ducking key", ch + 1))?;
+        duck.params.set(property, value)
+    }
+}
+
+/// Registry key for looking up ducking state.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DuckingKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// Global registry for mixer ducking state.
+pub struct DuckingRegistry {
+    blocks: RwLock<HashMap<DuckingKey, Arc<MixerDucking>>>,
+}
+
+impl DuckingRegistry {
+    pub fn new() -> Self {
+        Self {
+            blocks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, key: DuckingKey, ducking: Arc<MixerDucking>) {
+        self.blocks.write().insert(key, ducking);
+    }
+
+    pub fn get(&self, key: &DuckingKey) -> Option<Arc<MixerDucking>> {
+        self.blocks.read().get(key).cloned()
+    }
+
+    /// Remove all ducking entries for a given flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        self.blocks.write().retain(|k, _| k.flow_id != *flow_id);
+    }
+}
+
+impl Default for DuckingRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply a live `duck-*` property change to a running mixer.
+///
+/// Returns `None` if `element_id`/`property_name` is not a ducking parameter,
+/// so the caller can fall through to the regular property path.
+pub fn update_property(
+    flow_id: &FlowId,
+    element_id: &str,
+    property_name: &str,
+    value: &PropertyValue,
+) -> Option<Result<(), String>> {
+    if !property_name.starts_with("duck-") {
+        return None;
+    }
+    let (block_id, ch) = element_id.rsplit_once(":duck_")?;
+    let ch = ch.parse::<usize>().ok()?;
+    let ducking = DUCKING_REGISTRY.get(&DuckingKey {
+        flow_id: *flow_id,
+        block_id: block_id.to_string(),
+    })?;
+    Some(ducking.update(ch, property_name, value))
+}
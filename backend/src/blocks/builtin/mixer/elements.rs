@@ -313,3 +313,17 @@ pub(super) fn make_hpf_element(
         .build()
         .map_err(|e| BlockBuildError::ElementCreation(format!("hpf fallback {}: {}", name, e)))
 }
+
+/// Create a phase inverter (gain -1) for mix-minus subtraction.
+///
+/// Clipping is disabled: the inverted copy must cancel the channel exactly,
+/// including samples above full scale in the float mix.
+pub(super) fn make_phase_invert(name: &str) -> Result<gst::Element, BlockBuildError> {
+    let invert = gst::ElementFactory::make("audioamplify")
+        .name(name)
+        .property("amplification", -1.0f32)
+        .build()
+        .map_err(|e| BlockBuildError::ElementCreation(format!("audioamplify {}: {}", name, e)))?;
+    invert.set_property_from_str("clipping-method", "none");
+    Ok(invert)
+}
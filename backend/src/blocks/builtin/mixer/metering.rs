@@ -1,6 +1,8 @@
+use super::ducking::MixerDucking;
 use crate::events::EventBroadcaster;
 use gstreamer as gst;
 use gstreamer::prelude::*;
+use std::sync::Arc;
 use strom_types::{FlowId, StromEvent};
 use tracing::{debug, trace};
 
@@ -16,11 +18,15 @@ pub(super) fn extract_level_values(structure: &gst::StructureRef, field_name: &s
 }
 
 /// Connect message handler for all level elements in this mixer block.
+///
+/// Ducking key levels are fed to `ducking`, which also yields the gain
+/// reduction meters.
 pub(super) fn connect_mixer_meter_handler(
     bus: &gst::Bus,
     flow_id: FlowId,
     events: EventBroadcaster,
     instance_id: String,
+    ducking: Option<Arc<MixerDucking>>,
 ) -> gst::glib::SignalHandlerId {
     use gst::MessageView;
 
@@ -36,6 +42,8 @@ pub(super) fn connect_mixer_meter_handler(
     let pfl_level_id = format!("{}:pfl_level", instance_id);
     let aux_level_prefix = format!("{}:aux", instance_id);
     let group_level_prefix = format!("{}:group", instance_id);
+    let mm_level_prefix = format!("{}:mm", instance_id);
+    let duck_key_prefix = format!("{}:duck_key_level_", instance_id);
 
     bus.connect_message(None, move |_bus, msg| {
         if let MessageView::Element(element_msg) = msg.view() {
@@ -52,6 +60,28 @@ pub(super) fn connect_mixer_meter_handler(
                             return;
                         }
 
+                        // Ducking key: drive the envelope, report gain reduction
+                        if let Some(ch) = source_name
+                            .strip_prefix(&duck_key_prefix)
+                            .and_then(|num| num.parse::<usize>().ok())
+                        {
+                            let key_db = peak.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                            let reduction = ducking
+                                .as_ref()
+                                .and_then(|ducking| ducking.process_key(ch, key_db));
+                            if let Some(reduction) = reduction {
+                                trace!("Mixer ch{} ducking: {:.1} dB", ch + 1, reduction);
+                                events.broadcast(StromEvent::MeterData {
+                                    flow_id,
+                                    element_id: format!("{}:meter:gr{}", instance_id, ch + 1),
+                                    rms: vec![-reduction],
+                                    peak: vec![-reduction],
+                                    decay: vec![-reduction],
+                                });
+                            }
+                            return;
+                        }
+
                         // Check if this is the main level meter
                         if source_name == main_level_id {
                             trace!("Mixer main meter: rms={:?}, peak={:?}", rms, peak);
@@ -143,6 +173,30 @@ pub(super) fn connect_mixer_meter_handler(
                             }
                         }
 
+                        // Check if this is a mix-minus level meter
+                        // Format: "instance_id:mmN_level"
+                        if let Some(mm_num) = source_name
+                            .strip_prefix(&mm_level_prefix)
+                            .and_then(|part| part.strip_suffix("_level"))
+                            .and_then(|num| num.parse::<usize>().ok())
+                        {
+                            trace!(
+                                "Mixer mix-minus{} meter: rms={:?}, peak={:?}",
+                                mm_num + 1,
+                                rms,
+                                peak
+                            );
+                            let element_id = format!("{}:meter:mm{}", instance_id, mm_num + 1);
+                            events.broadcast(StromEvent::MeterData {
+                                flow_id,
+                                element_id,
+                                rms,
+                                peak,
+                                decay,
+                            });
+                            return;
+                        }
+
                         // Check if this is a channel level meter
                         if !source_name.starts_with(&level_prefix) {
                             return;
@@ -5,6 +5,9 @@
 //! - Per-channel: input gain, gate, compressor, 4-band parametric EQ, pan, fader, mute
 //! - Aux sends (0-4 configurable aux buses, switchable pre/post fader)
 //! - Groups (0-4 configurable, with output pads)
+//! - Mix-minus (N-1) buses (0-4 configurable, with output pads)
+//! - Per-channel sidechain ducking keyed from another channel or a group
+//! - Fader and pan automation with read, write, touch and latch modes
 //! - PFL (Pre-Fader Listen) bus with master level
 //! - Main stereo bus with compressor, EQ, limiter, and master fader
 //! - Per-channel and bus metering
@@ -22,23 +25,41 @@
 //!
 //! Main bus: audiomixer → main_comp → main_eq → main_limiter → main_volume → main_level → main_out_tee
 //!
+//! Mix-minus buses subtract rather than re-sum: each one mixes the unprocessed
+//! main sum with phase-inverted copies of its excluded channels, so a bus costs
+//! one input per excluded channel instead of one per included channel.
+//! ```text
+//! audiomixer → main_sum_tee → mmM_main_queue ──────────────────────┐
+//! to_main_vol_N → to_main_tee_N → mm_invert_M_N → mm_queue_M_N → mmM_mixer →
+//!                                         mmM_volume → mmM_level → mmM_out_tee
+//! ```
+//!
 //! All output buses terminate in a tee with allow-not-linked=true, so unconnected
 //! output pads don't cause NOT_LINKED flow errors. Audiomixer elements use
 //! force-live=true so unconnected input pads don't stall the pipeline.
 //!
+//! A ducked channel gets a `duck_N` volume between EQ and pre_fader_tee,
+//! driven by the level of its key (see [`ducking`]).
+//!
+//! Named snapshots of the live properties can be stored with the flow and
+//! recalled over REST (see [`snapshot`]).
+//!
 //! Processing uses LSP LV2 plugins when available. Falls back to identity passthrough
 //! when LV2 plugins are not installed.
 
+pub mod automation;
 mod builder;
 mod definition;
+pub mod ducking;
 mod elements;
 mod metering;
 mod properties;
+pub mod snapshot;
 #[cfg(test)]
 mod tests;
 
 use strom_types::mixer::{
-    DEFAULT_CHANNELS, MAX_AUX_BUSES, MAX_CHANNELS, MAX_GROUPS, MIN_KNEE_LINEAR,
+    DEFAULT_CHANNELS, MAX_AUX_BUSES, MAX_CHANNELS, MAX_GROUPS, MAX_MIX_MINUS, MIN_KNEE_LINEAR,
 };
 /// Level meter interval in nanoseconds (100ms)
 const METER_INTERVAL_NS: u64 = 100_000_000;
@@ -52,6 +73,8 @@ pub use properties::translate_property_for_element;
 
 // Crate-internal re-imports (accessible via super::* in tests)
 #[cfg(test)]
+use automation::*;
+#[cfg(test)]
 use definition::mixer_definition;
 #[cfg(test)]
 use elements::*;
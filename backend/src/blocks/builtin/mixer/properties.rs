@@ -4,7 +4,7 @@ use gstreamer as gst;
 use gstreamer::prelude::*;
 use strom_types::PropertyValue;
 
-use super::{DEFAULT_CHANNELS, MAX_AUX_BUSES, MAX_CHANNELS, MAX_GROUPS};
+use super::{DEFAULT_CHANNELS, MAX_AUX_BUSES, MAX_CHANNELS, MAX_GROUPS, MAX_MIX_MINUS};
 
 /// Parse number of channels from properties.
 pub(super) fn parse_num_channels(properties: &HashMap<String, PropertyValue>) -> usize {
@@ -48,6 +48,43 @@ pub(super) fn parse_num_groups(properties: &HashMap<String, PropertyValue>) -> u
         .clamp(0, MAX_GROUPS)
 }
 
+/// Parse number of mix-minus buses from properties.
+pub(super) fn parse_num_mix_minus(properties: &HashMap<String, PropertyValue>) -> usize {
+    properties
+        .get("num_mix_minus")
+        .and_then(|v| match v {
+            PropertyValue::Int(i) => Some(*i as usize),
+            PropertyValue::UInt(u) => Some(*u as usize),
+            PropertyValue::String(s) => s.parse::<usize>().ok(),
+            _ => None,
+        })
+        .unwrap_or(0)
+        .clamp(0, MAX_MIX_MINUS)
+}
+
+/// Parse the channels excluded from a mix-minus bus (0-indexed bus).
+///
+/// `mmN_exclude` is a comma-separated list of 1-based channel numbers and
+/// defaults to channel N, the classic N-1 return. Returns sorted, unique,
+/// 0-based channel indices below `num_channels`.
+pub(super) fn parse_mix_minus_exclude(
+    properties: &HashMap<String, PropertyValue>,
+    bus: usize,
+    num_channels: usize,
+) -> Vec<usize> {
+    let default = (bus + 1).to_string();
+    let list = get_string_prop(properties, &format!("mm{}_exclude", bus + 1), &default);
+    let mut channels: Vec<usize> = list
+        .split(',')
+        .filter_map(|s| s.trim().parse::<usize>().ok())
+        .filter(|&ch| ch >= 1 && ch <= num_channels)
+        .map(|ch| ch - 1)
+        .collect();
+    channels.sort_unstable();
+    channels.dedup();
+    channels
+}
+
 /// Get a float property with default.
 pub(super) fn get_float_prop(
     properties: &HashMap<String, PropertyValue>,
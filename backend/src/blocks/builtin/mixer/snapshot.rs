@@ -0,0 +1,322 @@
+//! Mixer snapshots: capture and recall of a console state.
+//!
+//! A snapshot stores the value of every live mixer property (everything in
+//! the block definition except structural, construction-time settings).
+//! Snapshots are kept with the block instance in its `snapshots` property.
+//! Recalling one updates the block properties and, when the flow is running,
+//! the matching elements. Channels marked `chN_recall_safe` are left alone.
+//! Faders, aux sends and pans can be crossfaded; everything else switches
+//! instantly.
+
+use super::definition::mixer_definition;
+use super::properties::{
+    db_to_linear, get_bool_prop, get_string_prop, parse_num_aux_buses, parse_num_channels,
+    parse_num_groups, parse_num_mix_minus,
+};
+use crate::blocks::common::parse_json_property;
+use std::collections::{BTreeMap, HashMap};
+use strom_types::block::PropertyMapping;
+use strom_types::mixer::MIN_KNEE_LINEAR;
+use strom_types::{FlowId, PropertyValue};
+
+/// Properties fixed when the pipeline is built. Never part of a snapshot.
+const STRUCTURAL_PROPERTIES: &[&str] = &[
+    "num_channels",
+    "dsp_backend",
+    "num_aux_buses",
+    "num_groups",
+    "num_mix_minus",
+    "solo_mode",
+    "force_live",
+    "latency",
+    "min_upstream_latency",
+];
+
+/// Block property holding the stored snapshots.
+pub const SNAPSHOTS_PROPERTY: &str = "snapshots";
+
+/// Property values of each stored snapshot, keyed by snapshot name.
+pub type StoredSnapshots = BTreeMap<String, BTreeMap<String, PropertyValue>>;
+
+/// Errors from snapshot operations.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("Flow not found: {0}")]
+    FlowNotFound(FlowId),
+    #[error("Block {0} is not an audio mixer")]
+    NotMixer(String),
+    #[error("Snapshot not found: {0}")]
+    NotFound(String),
+}
+
+/// A property write on a running mixer element.
+#[derive(Debug, Clone)]
+pub struct ElementWrite {
+    /// Element name within the block (e.g. `volume_0`).
+    pub element: String,
+    /// GStreamer property name.
+    pub property: String,
+    /// Value to write, already transformed for the element.
+    pub value: PropertyValue,
+    /// Ramp to the value over the crossfade time instead of switching.
+    pub fade: bool,
+}
+
+/// What a recall changes.
+#[derive(Debug, Clone, Default)]
+pub struct RecallPlan {
+    /// Block properties that change, in property name order.
+    pub changes: Vec<(String, PropertyValue)>,
+    /// Element writes needed to apply the changes to a running pipeline.
+    pub writes: Vec<ElementWrite>,
+}
+
+/// Channel, aux bus, group and mix-minus bus (1-based) a property belongs to.
+#[derive(Debug, Default, PartialEq)]
+struct Scope {
+    channel: Option<usize>,
+    aux: Option<usize>,
+    group: Option<usize>,
+    mix_minus: Option<usize>,
+}
+
+/// Split a leading decimal number off a string.
+fn leading_number(s: &str) -> Option<(usize, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s[..end].parse().ok().map(|n| (n, &s[end..]))
+}
+
+fn scope(name: &str) -> Scope {
+    let mut scope = Scope::default();
+    let mut rest = name;
+    if let Some((ch, r)) = name.strip_prefix("ch").and_then(leading_number) {
+        scope.channel = Some(ch);
+        rest = r.trim_start_matches('_');
+    }
+    if let Some((aux, _)) = rest.strip_prefix("aux").and_then(leading_number) {
+        scope.aux = Some(aux);
+    } else if let Some((group, _)) = rest
+        .strip_prefix("to_grp")
+        .or_else(|| rest.strip_prefix("group"))
+        .and_then(leading_number)
+    {
+        scope.group = Some(group);
+    } else if let Some((mm, _)) = rest.strip_prefix("mm").and_then(leading_number) {
+        scope.mix_minus = Some(mm);
+    }
+    scope
+}
+
+/// Whether a property exists with the block's current channel/aux/group counts.
+pub(super) fn in_range(properties: &HashMap<String, PropertyValue>, name: &str) -> bool {
+    let scope = scope(name);
+    scope
+        .channel
+        .is_none_or(|ch| ch <= parse_num_channels(properties))
+        && scope
+            .aux
+            .is_none_or(|aux| aux <= parse_num_aux_buses(properties))
+        && scope
+            .group
+            .is_none_or(|group| group <= parse_num_groups(properties))
+        && scope
+            .mix_minus
+            .is_none_or(|mm| mm <= parse_num_mix_minus(properties))
+}
+
+fn is_snapshot_property(name: &str) -> bool {
+    name != SNAPSHOTS_PROPERTY
+        && !STRUCTURAL_PROPERTIES.contains(&name)
+        && !name.ends_with("_recall_safe")
+        // Pre/post-fader aux sends, mix-minus exclusions and ducking keys are
+        // wired when the pipeline is built
+        && !name.ends_with("_pre")
+        && !name.ends_with("_exclude")
+        && !name.ends_with("_duck_key")
+}
+
+/// Mapping and default value of every snapshot property.
+fn snapshot_properties() -> HashMap<String, (PropertyMapping, PropertyValue)> {
+    mixer_definition()
+        .exposed_properties
+        .into_iter()
+        .filter(|p| is_snapshot_property(&p.name))
+        .filter_map(|p| {
+            let default = p.default_value?;
+            Some((p.name, (p.mapping, default)))
+        })
+        .collect()
+}
+
+fn float_value(value: &PropertyValue) -> f64 {
+    match value {
+        PropertyValue::Float(f) => *f,
+        PropertyValue::Int(i) => *i as f64,
+        PropertyValue::UInt(u) => *u as f64,
+        _ => 0.0,
+    }
+}
+
+fn same_value(a: &PropertyValue, b: &PropertyValue) -> bool {
+    match (a, b) {
+        (PropertyValue::String(a), PropertyValue::String(b)) => a == b,
+        (PropertyValue::Bool(a), PropertyValue::Bool(b)) => a == b,
+        (PropertyValue::String(_) | PropertyValue::Bool(_), _)
+        | (_, PropertyValue::String(_) | PropertyValue::Bool(_)) => false,
+        _ => (float_value(a) - float_value(b)).abs() < 1e-9,
+    }
+}
+
+/// Snapshots stored in the `snapshots` property.
+pub fn stored_snapshots(properties: &HashMap<String, PropertyValue>) -> StoredSnapshots {
+    parse_json_property(properties, SNAPSHOTS_PROPERTY)
+}
+
+/// Capture the current console state of a mixer block.
+///
+/// Properties that are not set take their definition default, so a recall
+/// also restores values that were at default when the snapshot was taken.
+pub fn capture(properties: &HashMap<String, PropertyValue>) -> BTreeMap<String, PropertyValue> {
+    snapshot_properties()
+        .into_iter()
+        .filter(|(name, _)| in_range(properties, name))
+        .map(|(name, (_, default))| {
+            let value = properties.get(&name).cloned().unwrap_or(default);
+            (name, value)
+        })
+        .collect()
+}
+
+/// Work out which properties and elements a recall changes.
+pub fn plan_recall(
+    current: &HashMap<String, PropertyValue>,
+    snapshot: &BTreeMap<String, PropertyValue>,
+) -> RecallPlan {
+    let defs = snapshot_properties();
+    let mut merged = current.clone();
+    let mut plan = RecallPlan::default();
+
+    for (name, value) in snapshot {
+        let Some((_, default)) = defs.get(name) else {
+            continue;
+        };
+        if !in_range(current, name) {
+            continue;
+        }
+        if let Some(ch) = scope(name).channel {
+            if get_bool_prop(current, &format!("ch{}_recall_safe", ch), false) {
+                continue;
+            }
+        }
+        if same_value(current.get(name).unwrap_or(default), value) {
+            continue;
+        }
+        merged.insert(name.clone(), value.clone());
+        plan.changes.push((name.clone(), value.clone()));
+    }
+
+    for (name, _) in &plan.changes {
+        let Some(write) = element_write(&defs, &merged, name) else {
+            continue;
+        };
+        match plan
+            .writes
+            .iter_mut()
+            .find(|w| w.element == write.element && w.property == write.property)
+        {
+            // Same target (e.g. fader and mute): the value already reflects both
+            Some(existing) => existing.fade &= write.fade,
+            None => plan.writes.push(write),
+        }
+    }
+
+    plan
+}
+
+/// Element write for one property, computed from the post-recall properties.
+fn element_write(
+    defs: &HashMap<String, (PropertyMapping, PropertyValue)>,
+    properties: &HashMap<String, PropertyValue>,
+    name: &str,
+) -> Option<ElementWrite> {
+    let value_of = |name: &str| {
+        defs.get(name)
+            .map(|(_, default)| properties.get(name).unwrap_or(default))
+    };
+
+    // Faders and mutes share one volume element: muted means volume 0
+    if let Some(base) = name
+        .strip_suffix("_fader")
+        .or_else(|| name.strip_suffix("_mute"))
+    {
+        let fader_name = format!("{}_fader", base);
+        let (mapping, _) = defs.get(&fader_name)?;
+        let muted = get_bool_prop(properties, &format!("{}_mute", base), false);
+        let volume = if muted {
+            0.0
+        } else {
+            float_value(value_of(&fader_name)?)
+        };
+        return Some(ElementWrite {
+            element: mapping.element_id.clone(),
+            property: mapping.property_name.clone(),
+            value: PropertyValue::Float(volume),
+            fade: name.ends_with("_fader"),
+        });
+    }
+
+    // HPF cutoff 0 puts the filter in passthrough
+    if let Some(base) = name
+        .strip_suffix("_hpf_enabled")
+        .or_else(|| name.strip_suffix("_hpf_freq"))
+    {
+        let freq_name = format!("{}_hpf_freq", base);
+        let (mapping, _) = defs.get(&freq_name)?;
+        let enabled = get_bool_prop(properties, &format!("{}_hpf_enabled", base), false);
+        let cutoff = if enabled {
+            float_value(value_of(&freq_name)?)
+        } else {
+            0.0
+        };
+        return Some(ElementWrite {
+            element: mapping.element_id.clone(),
+            property: mapping.property_name.clone(),
+            value: PropertyValue::Float(cutoff),
+            fade: false,
+        });
+    }
+
+    let (mapping, _) = defs.get(name)?;
+    if mapping.element_id == "_block" {
+        return None;
+    }
+    // Ducking elements only exist on channels with a key
+    if mapping.element_id.starts_with("duck_") {
+        let ch = scope(name).channel?;
+        let key = get_string_prop(properties, &format!("ch{}_duck_key", ch), "none");
+        if key == "none" {
+            return None;
+        }
+    }
+    let value = value_of(name)?;
+    let value = match (mapping.transform.as_deref(), value) {
+        (Some("db_to_linear"), v) => {
+            let linear = db_to_linear(float_value(v));
+            if name.ends_with("_comp_knee") {
+                PropertyValue::Float(linear.clamp(MIN_KNEE_LINEAR, 1.0))
+            } else {
+                PropertyValue::Float(linear)
+            }
+        }
+        (Some("bool_to_volume"), PropertyValue::Bool(b)) => {
+            PropertyValue::Float(if *b { 1.0 } else { 0.0 })
+        }
+        (_, v) => v.clone(),
+    };
+    Some(ElementWrite {
+        element: mapping.element_id.clone(),
+        property: mapping.property_name.clone(),
+        value,
+        fade: name.ends_with("_pan") || name.ends_with("_level"),
+    })
+}
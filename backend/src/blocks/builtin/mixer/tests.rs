@@ -2,6 +2,7 @@ use super::*;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use std::collections::HashMap;
+use strom_types::mixer::AutomationMode;
 use strom_types::{block::PropertyType, PropertyValue};
 
 fn init_gst() {
@@ -115,6 +116,36 @@ fn test_parse_num_groups_default() {
     assert_eq!(parse_num_groups(&props), 0);
 }
 
+#[test]
+fn test_parse_num_mix_minus_clamped() {
+    let mut props = HashMap::new();
+    assert_eq!(parse_num_mix_minus(&props), 0);
+    props.insert(
+        "num_mix_minus".to_string(),
+        PropertyValue::String("9".to_string()),
+    );
+    assert_eq!(parse_num_mix_minus(&props), MAX_MIX_MINUS);
+}
+
+#[test]
+fn test_parse_mix_minus_exclude_defaults_to_own_channel() {
+    let props = HashMap::new();
+    assert_eq!(parse_mix_minus_exclude(&props, 0, 8), vec![0]);
+    assert_eq!(parse_mix_minus_exclude(&props, 2, 8), vec![2]);
+    // Bus 4 has no channel 4 on a 2-channel mixer
+    assert!(parse_mix_minus_exclude(&props, 3, 2).is_empty());
+}
+
+#[test]
+fn test_parse_mix_minus_exclude_list() {
+    let mut props = HashMap::new();
+    props.insert(
+        "mm1_exclude".to_string(),
+        PropertyValue::String(" 3, 1,x,3,0,9".to_string()),
+    );
+    assert_eq!(parse_mix_minus_exclude(&props, 0, 8), vec![0, 2]);
+}
+
 #[test]
 fn test_get_float_prop_default() {
     let props = HashMap::new();
@@ -328,6 +359,28 @@ fn test_mixer_definition_aux_group_outputs() {
     );
 }
 
+#[test]
+fn test_mix_minus_output_pads() {
+    use crate::blocks::BlockBuilder;
+
+    let mut props = HashMap::new();
+    props.insert(
+        "num_mix_minus".to_string(),
+        PropertyValue::String("2".to_string()),
+    );
+    let pads = MixerBuilder.get_external_pads(&props).unwrap();
+    let mm: Vec<_> = pads
+        .outputs
+        .iter()
+        .filter(|p| p.name.starts_with("mm_out_"))
+        .map(|p| (p.name.as_str(), p.internal_element_id.as_str()))
+        .collect();
+    assert_eq!(
+        mm,
+        vec![("mm_out_1", "mm0_out_tee"), ("mm_out_2", "mm1_out_tee")]
+    );
+}
+
 // ---- GStreamer element tests (conditional on plugin availability) ----
 
 #[test]
@@ -823,3 +876,390 @@ fn test_mixer_definition_has_dsp_backend_property() {
         other => panic!("Expected Enum type, got {:?}", other),
     }
 }
+
+// ---- Snapshot tests ----
+
+#[test]
+fn test_snapshot_capture_respects_counts() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    props.insert("num_aux_buses".to_string(), PropertyValue::Int(1));
+    props.insert("ch2_fader".to_string(), PropertyValue::Float(0.5));
+
+    let snap = snapshot::capture(&props);
+    assert!(matches!(snap.get("ch2_fader"), Some(PropertyValue::Float(v)) if *v == 0.5));
+    assert!(matches!(snap.get("ch1_fader"), Some(PropertyValue::Float(v)) if *v == 1.0));
+    assert!(snap.contains_key("ch1_aux1_level"));
+    assert!(!snap.contains_key("ch1_aux2_level"));
+    assert!(!snap.contains_key("ch3_fader"));
+    assert!(!snap.contains_key("ch1_to_grp1"));
+    assert!(!snap.contains_key("num_channels"));
+    assert!(!snap.contains_key("ch1_aux1_pre"));
+    assert!(!snap.contains_key("ch1_recall_safe"));
+    assert!(!snap.contains_key("mm1_fader"));
+}
+
+#[test]
+fn test_snapshot_capture_mix_minus() {
+    let mut props = HashMap::new();
+    props.insert("num_mix_minus".to_string(), PropertyValue::Int(1));
+    let snap = snapshot::capture(&props);
+    assert!(snap.contains_key("mm1_fader"));
+    assert!(snap.contains_key("mm1_mute"));
+    assert!(!snap.contains_key("mm2_fader"));
+    assert!(!snap.contains_key("mm1_exclude"));
+    assert!(!snap.contains_key("num_mix_minus"));
+}
+
+#[test]
+fn test_snapshots_stored_in_block_property() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    props.insert("ch1_fader".to_string(), PropertyValue::Float(0.25));
+
+    let mut stored = snapshot::stored_snapshots(&props);
+    assert!(stored.is_empty());
+    stored.insert("intro".to_string(), snapshot::capture(&props));
+    props.insert(
+        snapshot::SNAPSHOTS_PROPERTY.to_string(),
+        crate::blocks::common::json_property(&stored),
+    );
+
+    let stored = snapshot::stored_snapshots(&props);
+    assert!(
+        matches!(stored["intro"].get("ch1_fader"), Some(PropertyValue::Float(v)) if *v == 0.25)
+    );
+    // Snapshots never capture each other
+    assert!(!snapshot::capture(&props).contains_key(snapshot::SNAPSHOTS_PROPERTY));
+}
+
+#[test]
+fn test_snapshot_recall_skips_safe_channels() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    let mut snap = snapshot::capture(&props);
+    snap.insert("ch1_pan".to_string(), PropertyValue::Float(-1.0));
+    snap.insert("ch2_pan".to_string(), PropertyValue::Float(1.0));
+
+    props.insert("ch2_recall_safe".to_string(), PropertyValue::Bool(true));
+    let plan = snapshot::plan_recall(&props, &snap);
+    let changed: Vec<&str> = plan.changes.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(changed, vec!["ch1_pan"]);
+    assert_eq!(plan.writes.len(), 1);
+    assert_eq!(plan.writes[0].element, "pan_0");
+    assert_eq!(plan.writes[0].property, "panorama");
+    assert!(plan.writes[0].fade);
+}
+
+#[test]
+fn test_snapshot_recall_fader_and_mute_share_volume() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    let mut snap = snapshot::capture(&props);
+    snap.insert("ch1_fader".to_string(), PropertyValue::Float(0.5));
+    snap.insert("ch2_fader".to_string(), PropertyValue::Float(0.25));
+    snap.insert("ch2_mute".to_string(), PropertyValue::Bool(true));
+
+    let plan = snapshot::plan_recall(&props, &snap);
+    assert_eq!(plan.changes.len(), 3);
+    assert_eq!(plan.writes.len(), 2);
+    let ch1 = plan
+        .writes
+        .iter()
+        .find(|w| w.element == "volume_0")
+        .unwrap();
+    assert!(matches!(ch1.value, PropertyValue::Float(v) if v == 0.5));
+    assert!(ch1.fade);
+    // Mute switches instantly
+    let ch2 = plan
+        .writes
+        .iter()
+        .find(|w| w.element == "volume_1")
+        .unwrap();
+    assert!(matches!(ch2.value, PropertyValue::Float(v) if v == 0.0));
+    assert!(!ch2.fade);
+}
+
+#[test]
+fn test_snapshot_recall_transforms_values() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    let mut snap = snapshot::capture(&props);
+    snap.insert("ch1_gain".to_string(), PropertyValue::Float(-6.0));
+    snap.insert("ch1_hpf_freq".to_string(), PropertyValue::Float(120.0));
+    snap.insert("ch1_to_main".to_string(), PropertyValue::Bool(false));
+
+    let plan = snapshot::plan_recall(&props, &snap);
+    let gain = plan.writes.iter().find(|w| w.element == "gain_0").unwrap();
+    assert!(matches!(gain.value, PropertyValue::Float(v) if (v - 0.501).abs() < 0.001));
+    // HPF stays in passthrough while disabled
+    let hpf = plan.writes.iter().find(|w| w.element == "hpf_0").unwrap();
+    assert!(matches!(hpf.value, PropertyValue::Float(v) if v == 0.0));
+    let to_main = plan
+        .writes
+        .iter()
+        .find(|w| w.element == "to_main_vol_0")
+        .unwrap();
+    assert!(matches!(to_main.value, PropertyValue::Float(v) if v == 0.0));
+
+    // Nothing changes when recalling the current state
+    assert!(snapshot::plan_recall(&props, &snapshot::capture(&props))
+        .changes
+        .is_empty());
+}
+
+// ---- Ducking tests ----
+
+#[test]
+fn test_parse_duck_key() {
+    let mut props = HashMap::new();
+    assert_eq!(ducking::parse_duck_key(&props, 0, 8, 2), None);
+
+    props.insert(
+        "ch1_duck_key".to_string(),
+        PropertyValue::String("ch3".to_string()),
+    );
+    props.insert(
+        "ch2_duck_key".to_string(),
+        PropertyValue::String("group2".to_string()),
+    );
+    props.insert(
+        "ch3_duck_key".to_string(),
+        PropertyValue::String("ch3".to_string()),
+    );
+    props.insert(
+        "ch4_duck_key".to_string(),
+        PropertyValue::String("ch9".to_string()),
+    );
+    assert_eq!(
+        ducking::parse_duck_key(&props, 0, 8, 2),
+        Some(ducking::DuckKey::Channel(2))
+    );
+    assert_eq!(
+        ducking::parse_duck_key(&props, 1, 8, 2),
+        Some(ducking::DuckKey::Group(1))
+    );
+    // A channel cannot key itself, and keys must exist
+    assert_eq!(ducking::parse_duck_key(&props, 2, 8, 2), None);
+    assert_eq!(ducking::parse_duck_key(&props, 3, 8, 2), None);
+    assert_eq!(ducking::parse_duck_key(&props, 1, 8, 1), None);
+}
+
+#[test]
+fn test_duck_envelope_attack_hold_release() {
+    let params = ducking::DuckParams {
+        enabled: true,
+        threshold_db: -30.0,
+        depth_db: 20.0,
+        attack_ms: 20.0,
+        hold_ms: 30.0,
+        release_ms: 40.0,
+    };
+    let mut env = ducking::Envelope::default();
+
+    // Attack: full depth over 20ms
+    assert_eq!(env.step(&params, -10.0, 10.0), 10.0);
+    assert_eq!(env.step(&params, -10.0, 10.0), 20.0);
+    assert_eq!(env.step(&params, -10.0, 10.0), 20.0);
+
+    // Hold: key gone, still ducked for 30ms
+    for _ in 0..3 {
+        assert_eq!(env.step(&params, -50.0, 10.0), 20.0);
+    }
+
+    // Release: back to unity over 40ms
+    assert_eq!(env.step(&params, -50.0, 10.0), 15.0);
+    for _ in 0..3 {
+        env.step(&params, -50.0, 10.0);
+    }
+    assert_eq!(env.step(&params, -50.0, 10.0), 0.0);
+}
+
+#[test]
+fn test_duck_envelope_disabled_releases() {
+    let mut params = ducking::DuckParams::from_properties(&HashMap::new(), 0);
+    params.enabled = true;
+    params.attack_ms = 0.0;
+    let mut env = ducking::Envelope::default();
+    assert_eq!(env.step(&params, 0.0, 10.0), params.depth_db);
+
+    params
+        .set("duck-enabled", &PropertyValue::Bool(false))
+        .unwrap();
+    params
+        .set("duck-release", &PropertyValue::Float(0.0))
+        .unwrap();
+    assert_eq!(env.step(&params, 0.0, 10.0), 0.0);
+    assert!(params
+        .set("duck-depth", &PropertyValue::String("x".to_string()))
+        .is_err());
+}
+
+#[test]
+fn test_snapshot_skips_duck_key_and_unkeyed_channels() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    props.insert(
+        "ch1_duck_key".to_string(),
+        PropertyValue::String("ch2".to_string()),
+    );
+    let mut snap = snapshot::capture(&props);
+    assert!(!snap.contains_key("ch1_duck_key"));
+    snap.insert("ch1_duck_depth".to_string(), PropertyValue::Float(6.0));
+    snap.insert("ch2_duck_depth".to_string(), PropertyValue::Float(6.0));
+
+    let plan = snapshot::plan_recall(&props, &snap);
+    assert_eq!(plan.changes.len(), 2);
+    assert_eq!(plan.writes.len(), 1);
+    assert_eq!(plan.writes[0].element, "duck_0");
+    assert_eq!(plan.writes[0].property, "duck-depth");
+}
+
+// ---- Automation tests ----
+
+fn point(time_ms: u64, value: f64) -> strom_types::mixer::AutomationPoint {
+    strom_types::mixer::AutomationPoint { time_ms, value }
+}
+
+#[test]
+fn test_automation_lane_target() {
+    let mut props = HashMap::new();
+    props.insert("num_channels".to_string(), PropertyValue::Int(2));
+    assert_eq!(
+        lane_target(&props, "ch1_fader"),
+        Some(("volume_0".to_string(), "volume".to_string()))
+    );
+    assert_eq!(
+        lane_target(&props, "ch2_pan"),
+        Some(("pan_1".to_string(), "panorama".to_string()))
+    );
+    assert!(lane_target(&props, "main_fader").is_some());
+    // Out of range, not automatable, or unknown
+    assert_eq!(lane_target(&props, "ch3_fader"), None);
+    assert_eq!(lane_target(&props, "ch1_mute"), None);
+    assert_eq!(lane_target(&props, "ch1_gain"), None);
+    assert_eq!(lane_target(&props, "aux1_fader"), None);
+}
+
+#[test]
+fn test_automation_value_at() {
+    let points = vec![point(1000, 0.0), point(2000, 1.0)];
+    assert_eq!(value_at(&[], 0), None);
+    assert_eq!(value_at(&points, 0), Some(0.0));
+    assert_eq!(value_at(&points, 1500), Some(0.5));
+    assert_eq!(value_at(&points, 5000), Some(1.0));
+}
+
+#[test]
+fn test_automation_read_ignores_moves() {
+    let mut lane = LaneRecorder::new(AutomationMode::Read, vec![point(0, 1.0)]);
+    assert!(!lane.record(100, 0.5));
+    assert_eq!(lane.points, vec![point(0, 1.0)]);
+    assert!(lane.plays_back());
+}
+
+#[test]
+fn test_automation_write_replaces_from_first_move() {
+    let mut lane = LaneRecorder::new(
+        AutomationMode::Write,
+        vec![point(0, 1.0), point(1000, 0.8), point(3000, 0.2)],
+    );
+    assert!(!lane.plays_back());
+    assert!(!lane.record(1050, 0.5));
+    assert!(!lane.record(1100, 0.4));
+    assert_eq!(
+        lane.points,
+        vec![
+            point(0, 1.0),
+            point(1000, 0.8),
+            point(1050, 0.5),
+            point(1100, 0.4)
+        ]
+    );
+}
+
+#[test]
+fn test_automation_step_holds_previous_value() {
+    let mut lane = LaneRecorder::new(AutomationMode::Write, vec![point(0, 1.0)]);
+    // A mute long after the last point must not ramp across the gap
+    lane.record(5000, 0.0);
+    assert_eq!(
+        lane.points,
+        vec![
+            point(0, 1.0),
+            point(5000 - STEP_RAMP_MS, 1.0),
+            point(5000, 0.0)
+        ]
+    );
+}
+
+#[test]
+fn test_automation_touch_records_and_releases() {
+    let mut lane = LaneRecorder::new(
+        AutomationMode::Touch,
+        vec![
+            point(0, 1.0),
+            point(1000, 1.0),
+            point(1100, 1.0),
+            point(2000, 1.0),
+        ],
+    );
+    assert!(lane.plays_back());
+
+    // First move takes over playback
+    assert!(lane.record(1000, 0.5));
+    assert!(!lane.plays_back());
+    assert!(!lane.record(1150, 0.4));
+    // The played-back value is held into the touch, points between moves
+    // are replaced and later ones kept
+    assert_eq!(
+        lane.points,
+        vec![
+            point(0, 1.0),
+            point(1000 - STEP_RAMP_MS, 1.0),
+            point(1000, 0.5),
+            point(1150, 0.4),
+            point(2000, 1.0)
+        ]
+    );
+
+    assert!(!lane.poll_release(1150 + TOUCH_RELEASE_MS - 1));
+    assert!(lane.poll_release(1150 + TOUCH_RELEASE_MS));
+    assert!(lane.plays_back());
+}
+
+#[test]
+fn test_automation_latch_keeps_recording() {
+    let mut lane = LaneRecorder::new(AutomationMode::Latch, vec![point(0, 1.0), point(3000, 0.2)]);
+    assert!(lane.plays_back());
+    assert!(lane.record(100, 0.5));
+    assert_eq!(lane.points, vec![point(0, 1.0), point(100, 0.5)]);
+    // Latch is never released by inactivity
+    assert!(!lane.poll_release(10_000));
+    assert!(!lane.plays_back());
+
+    lane.set_mode(AutomationMode::Read);
+    assert!(lane.plays_back());
+}
+
+#[test]
+fn test_automation_merge_lanes() {
+    use strom_types::mixer::AutomationLane;
+    let lane = |parameter: &str, mode| AutomationLane {
+        block_id: "mixer".to_string(),
+        parameter: parameter.to_string(),
+        mode,
+        points: Vec::new(),
+    };
+    let mut stored = vec![lane("ch1_fader", AutomationMode::Read)];
+    merge_lanes(
+        &mut stored,
+        vec![
+            lane("ch1_fader", AutomationMode::Touch),
+            lane("ch2_pan", AutomationMode::Write),
+        ],
+    );
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[0].mode, AutomationMode::Touch);
+    assert_eq!(stored[1].parameter, "ch2_pan");
+}
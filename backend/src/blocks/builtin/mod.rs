@@ -5,12 +5,15 @@ pub mod audioanalyzer;
 pub mod audioformat;
 pub mod audiogain;
 pub mod audiorouter;
+pub mod cartplayer;
 pub mod compositor;
 pub mod decklink;
+pub mod device;
 #[cfg(feature = "efp")]
 pub mod efpsrt;
 #[cfg(feature = "efp")]
 pub mod efpsrt_input;
+pub mod image_source;
 pub mod inter;
 pub mod latency;
 pub mod loudness;
@@ -52,12 +55,18 @@ pub fn get_all_builtin_blocks() -> Vec<BlockDefinition> {
     // Add AudioRouter blocks
     blocks.extend(audiorouter::get_blocks());
 
+    // Add Cart Player blocks
+    blocks.extend(cartplayer::get_blocks());
+
     // Add Compositor blocks (unified CPU/GPU)
     blocks.extend(compositor::get_blocks());
 
     // Add DeckLink blocks
     blocks.extend(decklink::get_blocks());
 
+    // Add Device blocks
+    blocks.extend(device::get_blocks());
+
     // Add EFP/SRT blocks
     #[cfg(feature = "efp")]
     blocks.extend(efpsrt::get_blocks());
@@ -66,6 +75,9 @@ pub fn get_all_builtin_blocks() -> Vec<BlockDefinition> {
     #[cfg(feature = "efp")]
     blocks.extend(efpsrt_input::get_blocks());
 
+    // Add Image Source blocks
+    blocks.extend(image_source::get_blocks());
+
     // Add Inter-pipeline blocks
     blocks.extend(inter::get_blocks());
 
@@ -133,11 +145,16 @@ pub fn get_builder(block_definition_id: &str) -> Option<Arc<dyn BlockBuilder>> {
         "builtin.audioformat" => Some(Arc::new(audioformat::AudioFormatBuilder)),
         "builtin.audiogain" => Some(Arc::new(audiogain::AudioGainBuilder)),
         "builtin.audiorouter" => Some(Arc::new(audiorouter::AudioRouterBuilder)),
+        "builtin.cart_player" => Some(Arc::new(cartplayer::CartPlayerBuilder)),
         "builtin.compositor" => Some(Arc::new(compositor::CompositorBuilder)),
         "builtin.decklink_video_input" => Some(Arc::new(decklink::DeckLinkVideoInputBuilder)),
         "builtin.decklink_audio_input" => Some(Arc::new(decklink::DeckLinkAudioInputBuilder)),
         "builtin.decklink_video_output" => Some(Arc::new(decklink::DeckLinkVideoOutputBuilder)),
         "builtin.decklink_audio_output" => Some(Arc::new(decklink::DeckLinkAudioOutputBuilder)),
+        "builtin.image_source" => Some(Arc::new(image_source::ImageSourceBuilder)),
+        "builtin.device_video_input" => Some(Arc::new(device::DeviceVideoInputBuilder)),
+        "builtin.device_audio_input" => Some(Arc::new(device::DeviceAudioInputBuilder)),
+        "builtin.device_audio_output" => Some(Arc::new(device::DeviceAudioOutputBuilder)),
         "builtin.inter_output" => Some(Arc::new(inter::InterOutputBuilder)),
         "builtin.inter_input" => Some(Arc::new(inter::InterInputBuilder)),
         "builtin.latency" => Some(Arc::new(latency::LatencyBuilder)),
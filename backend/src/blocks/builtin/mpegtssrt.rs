@@ -9,6 +9,7 @@
 //! - Configurable inputs: 1 video input + 1-32 audio inputs (default: 1 audio)
 //! - Optimized for UDP streaming (alignment=7 on mpegtsmux)
 //! - SRT with auto-reconnect and configurable latency
+//! - SCTE-35 PID for cue injection via the `/scte35` block endpoint
 //!
 //! Input handling:
 //! - Video: Dynamically detects codec (H.264, H.265) and inserts appropriate parser
@@ -29,6 +30,7 @@
 //! Audio (encoded) -> identity -> [dynamic: parser based on codec] -> mpegtsmux
 //! ```
 
+use crate::blocks::common::parse_scte35_pid;
 use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
 use gstreamer as gst;
 use gstreamer::prelude::*;
@@ -182,7 +184,17 @@ impl BlockBuilder for MpegTsSrtOutputBuilder {
             mux.set_property("bitrate", 0u64); // 0 = auto-detect from streams
         }
 
-        info!("MPEG-TS muxer configured: alignment=7, pcr-interval=40ms");
+        // SCTE-35 PID (0 = disabled). Cues are injected at runtime via
+        // PipelineManager::insert_scte35 and carried on this PID.
+        let scte35_pid = parse_scte35_pid(properties, strom_types::scte35::DEFAULT_SCTE35_PID)?;
+        if scte35_pid > 0 && mux.has_property("scte-35-pid") {
+            mux.set_property("scte-35-pid", scte35_pid);
+        }
+
+        info!(
+            "MPEG-TS muxer configured: alignment=7, pcr-interval=40ms, scte-35-pid={}",
+            scte35_pid
+        );
 
         // Create srtsink
         let sink_id = format!("{}:srtsink", instance_id);
@@ -933,6 +945,21 @@ fn mpegtssrt_output_definition() -> BlockDefinition {
                 },
                 live: false,
             },
+            ExposedProperty {
+                name: "scte35_pid".to_string(),
+                label: "SCTE-35 PID".to_string(),
+                description: "PID carrying SCTE-35 cues injected via the API (0 = disabled)".to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(
+                    strom_types::scte35::DEFAULT_SCTE35_PID as u64,
+                )),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "scte35_pid".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
         ],
         // External pads are now computed dynamically based on num_video_tracks and num_audio_tracks properties
         // This is just the default/fallback configuration
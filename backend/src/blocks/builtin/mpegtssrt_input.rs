@@ -17,6 +17,11 @@
 //!
 //! Both `decodebin` and `tsdemux` have dynamic pads — uses `connect_pad_added`
 //! to link to identity elements based on caps (video/ or audio/).
+//!
+//! SCTE-35 cues: `tsdemux` is configured with `send-scte35-events=true` so
+//! splice sections travel downstream as events (and are re-muxed by a
+//! downstream `mpegtsmux` with an SCTE-35 PID). Cues seen on the first output
+//! are also broadcast as `StromEvent::Scte35CueReceived`.
 
 use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
 use gstreamer as gst;
@@ -95,7 +100,7 @@ impl BlockBuilder for MpegTsSrtInputBuilder {
         &self,
         instance_id: &str,
         properties: &HashMap<String, PropertyValue>,
-        _ctx: &BlockBuildContext,
+        ctx: &BlockBuildContext,
     ) -> Result<BlockBuildResult, BlockBuildError> {
         let decode = properties
             .get("decode")
@@ -184,6 +189,18 @@ impl BlockBuilder for MpegTsSrtInputBuilder {
             (id, element)
         };
 
+        // Forward SCTE-35 sections as downstream events. In decode mode the
+        // tsdemux lives inside decodebin, so configure it when it is created.
+        if decode {
+            demux_element.connect("deep-element-added", false, |values| {
+                let element = values[2].get::<gst::Element>().unwrap();
+                enable_scte35_events(&element);
+                None
+            });
+        } else {
+            enable_scte35_events(&demux_element);
+        }
+
         let mut elements = vec![
             (src_id.clone(), srtsrc),
             (demux_id.clone(), demux_element.clone()),
@@ -227,6 +244,12 @@ impl BlockBuilder for MpegTsSrtInputBuilder {
             elements.push((element_id, identity));
         }
 
+        // SCTE-35 events are sent on every stream; only report them from the first output
+        let cue_tap = video_guards
+            .first()
+            .or(audio_guards.first())
+            .and_then(|(weak_identity, _)| weak_identity.upgrade());
+
         // Setup dynamic pad linking
         // Both decodebin and tsdemux emit pad-added signals for their output pads.
         // - decode mode (decodebin): outputs raw pads, we insert videoconvert/audioconvert
@@ -376,6 +399,41 @@ impl BlockBuilder for MpegTsSrtInputBuilder {
             ElementPadRef::pad(&demux_id, "sink"),
         )];
 
+        if let Some(cue_tap) = cue_tap {
+            let block_id = instance_id.to_string();
+            ctx.register_element_setup(Box::new(move |flow_id, events| {
+                let Some(src_pad) = cue_tap.static_pad("src") else {
+                    return;
+                };
+                src_pad.add_probe(gst::PadProbeType::EVENT_DOWNSTREAM, move |_pad, info| {
+                    let Some(gst::PadProbeData::Event(event)) = &info.data else {
+                        return gst::PadProbeReturn::Ok;
+                    };
+                    match crate::gst::scte35::cue_from_event(event) {
+                        Some(Ok(cue)) => {
+                            debug!(
+                                "MPEGTSSRT Input {}: SCTE-35 {} received (pts {:?})",
+                                block_id, cue.command, cue.pts_time
+                            );
+                            events.broadcast(StromEvent::Scte35CueReceived {
+                                flow_id,
+                                block_id: block_id.clone(),
+                                cue,
+                            });
+                        }
+                        Some(Err(e)) => {
+                            warn!(
+                                "MPEGTSSRT Input {}: Ignoring malformed SCTE-35 section: {}",
+                                block_id, e
+                            );
+                        }
+                        None => {}
+                    }
+                    gst::PadProbeReturn::Ok
+                });
+            }));
+        }
+
         info!(
             "Created MPEG-TS/SRT Input block ({}) with {} video output(s) and {} audio output(s)",
             mode_label, num_video_tracks, num_audio_tracks
@@ -390,6 +448,14 @@ impl BlockBuilder for MpegTsSrtInputBuilder {
     }
 }
 
+/// Enable SCTE-35 event forwarding on a tsdemux element (no-op for other elements).
+fn enable_scte35_events(element: &gst::Element) {
+    if element.has_property("send-scte35-events") {
+        element.set_property("send-scte35-events", true);
+        debug!("Enabled SCTE-35 event forwarding on {}", element.name());
+    }
+}
+
 /// Dynamically insert videoconvert between a decoded video pad and an identity element.
 /// decodebin pad -> videoconvert -> identity
 fn link_decoded_video(
@@ -0,0 +1,748 @@
+//! Recorder runtime control and global registry.
+//!
+//! Every input track passes a gate (a buffer probe on its identity src pad)
+//! that drops media unless recording; video tracks also wait for a keyframe
+//! after each start or resume. Stopping sends EOS through the parsers so
+//! splitmuxsink finalizes the file, then splitmuxsink is reset to NULL (and
+//! locked there) until the next start, which gives the file a new name.
+//! Paused time is removed from the timestamps with pad offsets, so a
+//! resumed recording continues the same file without a gap.
+//!
+//! With a pre-roll depth, the gates of an idle recorder keep the last
+//! seconds of every track (see [`Preroll`]) and push them ahead of the
+//! live media when the next recording starts.
+
+use super::preroll::{Entry, Preroll};
+use crate::events::EventBroadcaster;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use strom_types::recorder::{
+    RecorderAction, RecorderState, RecorderStatusResponse, RecorderTrigger,
+};
+use strom_types::{FlowId, StromEvent};
+use tracing::{debug, info, warn};
+
+/// How long splitmuxsink gets to report the file closed after a stop
+/// before it is reset anyway.
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timestamp in file names.
+pub const STAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// Global registry of recorder instances for API access.
+pub static RECORDER_REGISTRY: LazyLock<RecorderRegistry> = LazyLock::new(RecorderRegistry::new);
+
+/// Registry key for looking up recorder instances.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RecorderKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// An input track of the recorder.
+pub struct Track {
+    /// Source pad of the input identity, linked to the parser once caps are known
+    pub pad: gst::Pad,
+    /// splitmuxsink request pad of the track
+    pub sink_pad: gst::Pad,
+    pub video: bool,
+}
+
+/// What the recorder does when the flow starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartMode {
+    /// Record right away.
+    Immediate,
+    /// Stay idle until started.
+    Manual,
+    /// Stay idle with the trigger armed.
+    Armed,
+}
+
+impl StartMode {
+    /// Parse the `start_mode` property value.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "immediate" => Some(Self::Immediate),
+            "manual" => Some(Self::Manual),
+            "armed" => Some(Self::Armed),
+            _ => None,
+        }
+    }
+}
+
+/// Recording settings of a recorder block.
+pub struct Options {
+    pub start_mode: StartMode,
+    pub trigger: Option<RecorderTrigger>,
+    /// Pre-roll depth (0 disables the pre-roll)
+    pub preroll_ns: u64,
+}
+
+/// What a track gate does with a buffer.
+pub enum Gate {
+    Pass,
+    Drop,
+    /// Push these pre-roll buffers first, then pass the buffer
+    Preroll(Vec<gst::Buffer>),
+}
+
+/// Output file name parts; each recording gets a new timestamp.
+pub struct FileNaming {
+    /// Absolute output directory
+    pub output_path: String,
+    /// Output directory relative to the media directory
+    pub output_dir: String,
+    pub prefix: String,
+    pub ext: String,
+    /// Timestamp of the first recording
+    pub stamp: String,
+}
+
+impl FileNaming {
+    /// Absolute and media-relative file templates ("%05d" is the file index).
+    pub fn templates(&self, stamp: &str) -> (String, String) {
+        let name = format!("{}_{}_%05d.{}", self.prefix, stamp, self.ext);
+        (
+            format!("{}/{}", self.output_path, name),
+            format!("{}/{}", self.output_dir, name),
+        )
+    }
+}
+
+/// Recording state shared with the track gates.
+struct Control {
+    state: RecorderState,
+    armed: bool,
+    /// Last state of the trigger condition
+    trigger_on: bool,
+    /// The recording was started by the trigger (and is stopped by it)
+    triggered: bool,
+    /// Start again (triggered or not) once the file being finalized is closed
+    pending_start: Option<bool>,
+    /// EOS went through the parsers, which must be flushed before the next start
+    needs_flush: bool,
+    /// Per track: dropping media until the next keyframe
+    waiting_keyframe: Vec<bool>,
+    /// Running time the recording was paused at
+    paused_at: Option<u64>,
+    /// Time paused in the current recording, cut from the timestamps
+    paused_total: u64,
+    /// Recordings started since the flow started
+    takes: u32,
+    /// Bumped on every stop, so a stale finalize timeout is ignored
+    stop_id: u64,
+    /// Timestamp of the last file name and how often it was used
+    stamp: (String, u32),
+    /// Absolute and relative file templates of the current recording
+    location: (String, String),
+    /// File being written, relative to the media directory
+    filename: Option<String>,
+    /// Media kept while idle, written at the start of the next recording
+    preroll: Preroll<gst::Buffer>,
+}
+
+/// Runtime state for a recorder instance.
+pub struct RecorderControl {
+    pub block_id: String,
+    pub flow_id: FlowId,
+    sink: gst::Element,
+    tracks: Vec<Track>,
+    naming: FileNaming,
+    control: Mutex<Control>,
+    /// Serializes start, stop and finalize, which change pads and the sink state
+    ops: Mutex<()>,
+    /// What starts and stops recordings while armed
+    pub trigger: Mutex<Option<RecorderTrigger>>,
+    /// Event broadcaster, set when the pipeline starts
+    pub events: OnceLock<EventBroadcaster>,
+}
+
+impl RecorderControl {
+    /// Create the control of a recorder writing to `sink`.
+    ///
+    /// Unless recording right away, splitmuxsink is locked in NULL so the
+    /// flow starts without opening a file.
+    pub fn new(
+        block_id: &str,
+        flow_id: FlowId,
+        sink: gst::Element,
+        tracks: Vec<Track>,
+        naming: FileNaming,
+        options: Options,
+    ) -> Self {
+        let Options {
+            start_mode,
+            trigger,
+            preroll_ns,
+        } = options;
+        let recording = start_mode == StartMode::Immediate;
+        if !recording {
+            sink.set_locked_state(true);
+        }
+        let stamp = naming.stamp.clone();
+        let location = naming.templates(&stamp);
+        let waiting_keyframe = vec![false; tracks.len()];
+        let video: Vec<bool> = tracks.iter().map(|t| t.video).collect();
+        Self {
+            block_id: block_id.to_string(),
+            flow_id,
+            sink,
+            tracks,
+            naming,
+            control: Mutex::new(Control {
+                state: if recording {
+                    RecorderState::Recording
+                } else {
+                    RecorderState::Idle
+                },
+                armed: start_mode == StartMode::Armed,
+                trigger_on: false,
+                triggered: false,
+                pending_start: None,
+                needs_flush: false,
+                waiting_keyframe,
+                paused_at: None,
+                paused_total: 0,
+                takes: u32::from(recording),
+                stop_id: 0,
+                stamp: (stamp, 1),
+                location,
+                filename: None,
+                preroll: Preroll::new(preroll_ns, &video),
+            }),
+            ops: Mutex::new(()),
+            trigger: Mutex::new(trigger),
+            events: OnceLock::new(),
+        }
+    }
+
+    /// Add the gate probes on the track pads.
+    pub fn add_track_gates(self: &Arc<Self>) {
+        for (index, track) in self.tracks.iter().enumerate() {
+            let control = Arc::downgrade(self);
+            track.pad.add_probe(
+                gst::PadProbeType::BUFFER | gst::PadProbeType::BUFFER_LIST,
+                move |pad, info| {
+                    let Some(control) = control.upgrade() else {
+                        return gst::PadProbeReturn::Ok;
+                    };
+                    let gate = match info.data.as_ref() {
+                        Some(gst::PadProbeData::Buffer(buffer)) => {
+                            control.gate(index, pad, &[buffer.as_ref()])
+                        }
+                        Some(gst::PadProbeData::BufferList(list)) => {
+                            let buffers: Vec<&gst::BufferRef> = list.iter().collect();
+                            control.gate(index, pad, &buffers)
+                        }
+                        _ => Gate::Pass,
+                    };
+                    match gate {
+                        Gate::Pass => gst::PadProbeReturn::Ok,
+                        Gate::Drop => gst::PadProbeReturn::Drop,
+                        Gate::Preroll(buffers) => {
+                            debug!(
+                                "Recorder {}: writing {} pre-roll buffer(s) of track {}",
+                                control.block_id,
+                                buffers.len(),
+                                index
+                            );
+                            // Pushed through this probe again, where they pass
+                            for buffer in buffers {
+                                if pad.push(buffer).is_err() {
+                                    break;
+                                }
+                            }
+                            gst::PadProbeReturn::Ok
+                        }
+                    }
+                },
+            );
+        }
+    }
+
+    /// Whether buffers of a track go to the file, or to the pre-roll
+    /// (called from the track's buffer probe).
+    pub fn gate(&self, track: usize, pad: &gst::Pad, buffers: &[&gst::BufferRef]) -> Gate {
+        let Some(first) = buffers.first() else {
+            return Gate::Pass;
+        };
+        let Ok(mut control) = self.control.lock() else {
+            return Gate::Drop;
+        };
+        match control.state {
+            RecorderState::Recording => {
+                let preroll = control.preroll.take(track);
+                if !preroll.is_empty() {
+                    // Video pre-roll starts at a keyframe
+                    if let Some(waiting) = control.waiting_keyframe.get_mut(track) {
+                        *waiting = false;
+                    }
+                    return Gate::Preroll(preroll);
+                }
+                if control
+                    .waiting_keyframe
+                    .get(track)
+                    .copied()
+                    .unwrap_or(false)
+                {
+                    if first.flags().contains(gst::BufferFlags::DELTA_UNIT) {
+                        return Gate::Drop;
+                    }
+                    control.waiting_keyframe[track] = false;
+                }
+                Gate::Pass
+            }
+            RecorderState::Idle | RecorderState::Stopping if control.preroll.enabled() => {
+                for buffer in buffers {
+                    let Some(running_time) = buffer_running_time(pad, buffer) else {
+                        continue;
+                    };
+                    // Copied, so upstream buffer pools are not held up
+                    let Ok(item) = buffer.copy_deep() else {
+                        continue;
+                    };
+                    control.preroll.push(
+                        track,
+                        Entry {
+                            running_time,
+                            keyframe: !buffer.flags().contains(gst::BufferFlags::DELTA_UNIT),
+                            size: buffer.size(),
+                            item,
+                        },
+                    );
+                }
+                Gate::Drop
+            }
+            _ => Gate::Drop,
+        }
+    }
+
+    /// Pre-roll depth, time buffered and bytes buffered, for block stats.
+    pub fn preroll_stats(&self) -> (u64, u64, usize) {
+        self.control
+            .lock()
+            .map(|c| {
+                (
+                    c.preroll.depth_ns(),
+                    c.preroll.buffered_ns(),
+                    c.preroll.buffered_bytes(),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// File name for splitmuxsink's `format-location`; also broadcast as
+    /// the file being written.
+    pub fn format_location(&self, index: u32) -> String {
+        let Ok(mut control) = self.control.lock() else {
+            return String::new();
+        };
+        let index = format!("{:05}", index);
+        let filename = control.location.0.replace("%05d", &index);
+        let relative = control.location.1.replace("%05d", &index);
+        debug!("Recorder {}: writing file {}", self.block_id, filename);
+        control.filename = Some(relative.clone());
+        drop(control);
+
+        if let Some(events) = self.events.get() {
+            events.broadcast(StromEvent::RecorderFileChanged {
+                flow_id: self.flow_id,
+                block_id: self.block_id.clone(),
+                filename: relative,
+            });
+        }
+        filename
+    }
+
+    /// Start a new recording. While a stop is being finalized, the start
+    /// happens once the file is closed.
+    pub fn start(&self, triggered: bool) -> Result<(), String> {
+        let _ops = self.ops.lock().map_err(|e| e.to_string())?;
+        let needs_flush = {
+            let mut control = self.control.lock().map_err(|e| e.to_string())?;
+            match control.state {
+                RecorderState::Recording | RecorderState::Paused => {
+                    return Err("Already recording".to_string());
+                }
+                RecorderState::Stopping => {
+                    control.pending_start = Some(triggered);
+                    return Ok(());
+                }
+                RecorderState::Idle => {}
+            }
+            let stamp = chrono::Local::now().format(STAMP_FORMAT).to_string();
+            let stamp = if control.stamp.0 == stamp {
+                control.stamp.1 += 1;
+                format!("{}-{}", stamp, control.stamp.1)
+            } else {
+                control.stamp = (stamp.clone(), 1);
+                stamp
+            };
+            control.location = self.naming.templates(&stamp);
+            control.needs_flush
+        };
+
+        if needs_flush {
+            self.flush_tracks();
+        }
+        for track in &self.tracks {
+            track.pad.set_offset(0);
+        }
+
+        self.sink.set_locked_state(false);
+        if let Err(e) = self.sink.sync_state_with_parent() {
+            self.sink.set_locked_state(true);
+            let _ = self.sink.set_state(gst::State::Null);
+            return Err(format!("Failed to start splitmuxsink: {}", e));
+        }
+
+        let mut control = self.control.lock().map_err(|e| e.to_string())?;
+        control.state = RecorderState::Recording;
+        control.triggered = triggered;
+        control.needs_flush = false;
+        control.paused_at = None;
+        control.paused_total = 0;
+        control.takes += 1;
+        control.waiting_keyframe = self.tracks.iter().map(|t| t.video).collect();
+        control.preroll.start();
+        info!(
+            "Recorder {}: recording started{}",
+            self.block_id,
+            if triggered { " by trigger" } else { "" }
+        );
+        self.broadcast_state(&control);
+        Ok(())
+    }
+
+    /// Stop the recording and finalize the file.
+    pub fn stop(self: &Arc<Self>) -> Result<(), String> {
+        let _ops = self.ops.lock().map_err(|e| e.to_string())?;
+        let stop_id = {
+            let mut control = self.control.lock().map_err(|e| e.to_string())?;
+            match control.state {
+                RecorderState::Recording | RecorderState::Paused => {}
+                RecorderState::Stopping => {
+                    control.pending_start = None;
+                    return Ok(());
+                }
+                RecorderState::Idle => return Err("Not recording".to_string()),
+            }
+            control.state = RecorderState::Stopping;
+            control.stop_id += 1;
+            self.broadcast_state(&control);
+            control.stop_id
+        };
+
+        // EOS goes through the parser where there is one, so it follows the
+        // last buffer it parsed
+        for track in &self.tracks {
+            let sent = match track.pad.peer() {
+                Some(_) => track.pad.push_event(gst::event::Eos::new()),
+                None => track.sink_pad.send_event(gst::event::Eos::new()),
+            };
+            if !sent {
+                debug!("Recorder {}: EOS not accepted by a track", self.block_id);
+            }
+        }
+        info!("Recorder {}: stopping, finalizing file", self.block_id);
+
+        let control = Arc::clone(self);
+        tokio::spawn(async move {
+            tokio::time::sleep(FINALIZE_TIMEOUT).await;
+            if control.finalize(Some(stop_id)) {
+                warn!(
+                    "Recorder {}: file not reported closed within {:?}, reset anyway",
+                    control.block_id, FINALIZE_TIMEOUT
+                );
+            }
+        });
+        Ok(())
+    }
+
+    /// Reset splitmuxsink once the stopped file is closed (on its
+    /// `splitmuxsink-fragment-closed` message, or the timeout of stop
+    /// `stop_id`). Returns whether a stop was pending.
+    pub fn finalize(&self, stop_id: Option<u64>) -> bool {
+        let pending_start = {
+            let Ok(_ops) = self.ops.lock() else {
+                return false;
+            };
+            let Ok(mut control) = self.control.lock() else {
+                return false;
+            };
+            if control.state != RecorderState::Stopping
+                || stop_id.is_some_and(|id| id != control.stop_id)
+            {
+                return false;
+            }
+            self.sink.set_locked_state(true);
+            if let Err(e) = self.sink.set_state(gst::State::Null) {
+                warn!(
+                    "Recorder {}: failed to reset splitmuxsink: {}",
+                    self.block_id, e
+                );
+            }
+            control.state = RecorderState::Idle;
+            control.needs_flush = true;
+            control.triggered = false;
+            control.filename = None;
+            info!("Recorder {}: recording stopped", self.block_id);
+            self.broadcast_state(&control);
+            // Restart if asked meanwhile, or if the trigger came back on
+            control
+                .pending_start
+                .take()
+                .or_else(|| (control.armed && control.trigger_on).then_some(true))
+        };
+        if let Some(triggered) = pending_start {
+            if let Err(e) = self.start(triggered) {
+                warn!("Recorder {}: restart failed: {}", self.block_id, e);
+            }
+        }
+        true
+    }
+
+    /// Pause the recording, keeping the file open.
+    pub fn pause(&self) -> Result<(), String> {
+        let mut control = self.control.lock().map_err(|e| e.to_string())?;
+        if control.state != RecorderState::Recording {
+            return Err("Not recording".to_string());
+        }
+        control.state = RecorderState::Paused;
+        control.paused_at = self.running_time();
+        info!("Recorder {}: paused", self.block_id);
+        self.broadcast_state(&control);
+        Ok(())
+    }
+
+    /// Resume a paused recording in the same file.
+    pub fn resume(&self) -> Result<(), String> {
+        let mut control = self.control.lock().map_err(|e| e.to_string())?;
+        if control.state != RecorderState::Paused {
+            return Err("Not paused".to_string());
+        }
+        if let (Some(paused_at), Some(now)) = (control.paused_at.take(), self.running_time()) {
+            control.paused_total += now.saturating_sub(paused_at);
+        }
+        let offset = -(control.paused_total.min(i64::MAX as u64) as i64);
+        for track in &self.tracks {
+            track.pad.set_offset(offset);
+        }
+        control.waiting_keyframe = self.tracks.iter().map(|t| t.video).collect();
+        control.state = RecorderState::Recording;
+        info!(
+            "Recorder {}: resumed ({:.1}s paused in total)",
+            self.block_id,
+            control.paused_total as f64 / 1e9
+        );
+        self.broadcast_state(&control);
+        Ok(())
+    }
+
+    /// Let the trigger start and stop recordings, or not.
+    pub fn set_armed(&self, armed: bool) -> Result<(), String> {
+        let start = {
+            let mut control = self.control.lock().map_err(|e| e.to_string())?;
+            if armed && self.trigger.lock().map(|t| t.is_none()).unwrap_or(true) {
+                return Err("No trigger configured".to_string());
+            }
+            control.armed = armed;
+            if !armed {
+                // The recording in progress is now only stopped manually
+                control.triggered = false;
+            }
+            self.broadcast_state(&control);
+            armed && control.trigger_on && control.state == RecorderState::Idle
+        };
+        info!(
+            "Recorder {}: {}",
+            self.block_id,
+            if armed { "armed" } else { "disarmed" }
+        );
+        if start {
+            self.start(true)?;
+        }
+        Ok(())
+    }
+
+    /// Apply a control action.
+    pub fn apply(self: &Arc<Self>, action: RecorderAction) -> Result<(), String> {
+        match action {
+            RecorderAction::Arm => self.set_armed(true),
+            RecorderAction::Disarm => self.set_armed(false),
+            RecorderAction::Start => self.start(false),
+            RecorderAction::Stop => self.stop(),
+            RecorderAction::Pause => self.pause(),
+            RecorderAction::Resume => self.resume(),
+        }
+    }
+
+    /// Replace the trigger. Clearing it disarms the recorder.
+    pub fn set_trigger(&self, trigger: Option<RecorderTrigger>) -> Result<(), String> {
+        let cleared = trigger.is_none();
+        *self.trigger.lock().map_err(|e| e.to_string())? = trigger;
+        if cleared {
+            self.set_armed(false)?;
+        }
+        Ok(())
+    }
+
+    /// The trigger condition changed (after its hold when going off).
+    pub fn on_trigger(self: &Arc<Self>, on: bool) {
+        let (start, stop) = {
+            let Ok(mut control) = self.control.lock() else {
+                return;
+            };
+            control.trigger_on = on;
+            if !control.armed {
+                return;
+            }
+            if !on && control.pending_start == Some(true) {
+                control.pending_start = None;
+            }
+            let recording = matches!(
+                control.state,
+                RecorderState::Recording | RecorderState::Paused
+            );
+            (on && !recording, !on && recording && control.triggered)
+        };
+        let result = if start {
+            self.start(true)
+        } else if stop {
+            self.stop()
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            warn!("Recorder {}: trigger action failed: {}", self.block_id, e);
+        }
+    }
+
+    /// Current state, for the API.
+    pub fn status(&self) -> RecorderStatusResponse {
+        let trigger = self.trigger.lock().ok().and_then(|t| t.clone());
+        let Ok(control) = self.control.lock() else {
+            return RecorderStatusResponse {
+                state: RecorderState::Idle,
+                armed: false,
+                trigger,
+                filename: None,
+                takes: 0,
+            };
+        };
+        RecorderStatusResponse {
+            state: control.state,
+            armed: control.armed,
+            trigger,
+            filename: control.filename.clone(),
+            takes: control.takes,
+        }
+    }
+
+    /// Flush EOS out of the track chains and relink them to the (stopped)
+    /// splitmuxsink, so the next recording resends caps and segment.
+    fn flush_tracks(&self) {
+        for track in &self.tracks {
+            let Some(parser_src) = track
+                .pad
+                .peer()
+                .and_then(|p| p.parent_element())
+                .and_then(|parser| parser.static_pad("src"))
+            else {
+                continue;
+            };
+            let segment = track.pad.sticky_event::<gst::event::Segment>(0);
+            track.pad.push_event(gst::event::FlushStart::new());
+            track.pad.push_event(gst::event::FlushStop::new(false));
+            if parser_src.unlink(&track.sink_pad).is_ok() {
+                if let Err(e) = parser_src.link(&track.sink_pad) {
+                    warn!(
+                        "Recorder {}: failed to relink {}: {:?}",
+                        self.block_id,
+                        track.sink_pad.name(),
+                        e
+                    );
+                }
+            }
+            if let Some(segment) = segment {
+                track.pad.push_event(segment);
+            }
+        }
+    }
+
+    /// Running time of the pipeline.
+    fn running_time(&self) -> Option<u64> {
+        let pipeline = self.sink.parent()?.downcast::<gst::Element>().ok()?;
+        let clock = pipeline.clock()?;
+        let base_time = pipeline.base_time()?;
+        Some(clock.time().saturating_sub(base_time).nseconds())
+    }
+
+    fn broadcast_state(&self, control: &Control) {
+        if let Some(events) = self.events.get() {
+            events.broadcast(StromEvent::RecorderStateChanged {
+                flow_id: self.flow_id,
+                block_id: self.block_id.clone(),
+                state: control.state,
+                armed: control.armed,
+            });
+        }
+    }
+}
+
+/// Running time of a buffer (DTS, or PTS when there is none) in the
+/// segment of its pad.
+fn buffer_running_time(pad: &gst::Pad, buffer: &gst::BufferRef) -> Option<u64> {
+    let timestamp = buffer.dts_or_pts()?;
+    let event = pad.sticky_event::<gst::event::Segment>(0)?;
+    let gst::EventView::Segment(segment) = event.view() else {
+        return None;
+    };
+    let segment = segment.segment().downcast_ref::<gst::ClockTime>()?;
+    segment.to_running_time(timestamp).map(|t| t.nseconds())
+}
+
+/// Thread-safe registry of active recorder instances.
+pub struct RecorderRegistry {
+    recorders: RwLock<HashMap<RecorderKey, Arc<RecorderControl>>>,
+}
+
+impl RecorderRegistry {
+    pub fn new() -> Self {
+        Self {
+            recorders: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, key: RecorderKey, control: Arc<RecorderControl>) {
+        if let Ok(mut recorders) = self.recorders.write() {
+            recorders.insert(key, control);
+        }
+    }
+
+    pub fn get(&self, key: &RecorderKey) -> Option<Arc<RecorderControl>> {
+        self.recorders.read().ok()?.get(key).cloned()
+    }
+
+    /// Remove all recorder entries for a given flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        if let Ok(mut recorders) = self.recorders.write() {
+            let before = recorders.len();
+            recorders.retain(|k, _| k.flow_id != *flow_id);
+            let removed = before - recorders.len();
+            if removed > 0 {
+                info!("Unregistered {} recorder(s) for flow {}", removed, flow_id);
+            }
+        }
+    }
+}
+
+impl Default for RecorderRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
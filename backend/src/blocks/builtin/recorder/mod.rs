@@ -17,16 +17,35 @@
 //! audio_in_N (identity) --[pad probe]--> [parser chain] --> splitmuxsink:audio_0..N
 //! ```
 //!
-//! Output files are written to: {media_path}/{output_dir}/{filename_prefix}_%05d.{ext}
-
-use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
+//! Output files are written to: {media_path}/{output_dir}/{filename_prefix}_{timestamp}_%05d.{ext}
+//!
+//! Recording can be started, paused and stopped while the flow runs, and
+//! an armed recorder follows a trigger (tally or audio level), see
+//! [`control`] and [`trigger`]. The `start_mode` property decides whether
+//! the recorder records, stays idle or is armed when the flow starts.
+//! With `preroll_secs` set, an idle recorder keeps the last seconds of
+//! media and writes them at the start of the next file, see [`preroll`].
+
+mod control;
+mod preroll;
+mod trigger;
+
+pub use control::{RecorderControl, RecorderKey, RECORDER_REGISTRY};
+
+use crate::blocks::common::parse_scte35_pid;
+use crate::blocks::{
+    BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder, BusMessageConnectFn,
+};
+use crate::events::EventBroadcaster;
 use chrono;
+use control::{FileNaming, Options, StartMode, Track};
 use gst::glib::prelude::ToValue;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use strom_types::recorder::RecorderTrigger;
 use strom_types::{
     block::{EnumValue, *},
     PropertyValue, *,
@@ -44,10 +63,18 @@ const DEFAULT_MAX_SIZE_BYTES: u64 = 0; // 0 = unlimited
 const DEFAULT_MAX_DURATION_MINS: u64 = 0; // 0 = disabled
 const DEFAULT_NUM_VIDEO_TRACKS: usize = 1;
 const DEFAULT_NUM_AUDIO_TRACKS: usize = 1;
+const DEFAULT_START_MODE: &str = "immediate";
+const DEFAULT_PREROLL_SECS: u64 = 0; // 0 = disabled
+const MAX_PREROLL_SECS: u64 = 60;
 
 /// Element ID suffix for splitmuxsink, used by the API to look it up via PipelineManager.
 pub const SPLITMUXSINK_SUFFIX: &str = "splitmuxsink";
 
+/// Parse the trigger stored in the `trigger` block property.
+pub fn parse_trigger(json: &str) -> Option<RecorderTrigger> {
+    serde_json::from_str(json).ok()
+}
+
 impl BlockBuilder for RecorderBuilder {
     fn get_external_pads(
         &self,
@@ -222,6 +249,40 @@ impl BlockBuilder for RecorderBuilder {
             })
             .unwrap_or(DEFAULT_NUM_AUDIO_TRACKS);
 
+        let start_mode = properties
+            .get("start_mode")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => StartMode::parse(s),
+                _ => None,
+            })
+            .unwrap_or(StartMode::Immediate);
+
+        let trigger = properties.get("trigger").and_then(|v| match v {
+            PropertyValue::String(s) => parse_trigger(s),
+            _ => None,
+        });
+
+        let preroll_secs = properties
+            .get("preroll_secs")
+            .and_then(|v| match v {
+                PropertyValue::UInt(u) => Some(*u),
+                PropertyValue::Int(i) if *i >= 0 => Some(*i as u64),
+                _ => None,
+            })
+            .unwrap_or(DEFAULT_PREROLL_SECS)
+            .min(MAX_PREROLL_SECS);
+
+        // SCTE-35 is opt-in for recordings (0 = disabled)
+        let scte35_pid = parse_scte35_pid(properties, 0)?;
+
+        let flow_id: FlowId = properties
+            .get("_flow_id")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => uuid::Uuid::parse_str(s).ok(),
+                _ => None,
+            })
+            .unwrap_or_else(uuid::Uuid::nil);
+
         // --- Validate track counts ---
         if num_video_tracks == 0 && num_audio_tracks == 0 {
             return Err(BlockBuildError::InvalidProperty(
@@ -247,19 +308,17 @@ impl BlockBuilder for RecorderBuilder {
         }
 
         // Include a timestamp in the filename to avoid collisions across recording sessions.
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let location = format!(
-            "{}/{}_{}_%05d.{}",
-            output_path.to_string_lossy(),
-            filename_prefix,
-            timestamp,
-            file_ext
-        );
-        // Relative path template (relative to media root) — used in download URLs.
-        let relative_location = format!(
-            "{}/{}_{}_%05d.{}",
-            output_dir, filename_prefix, timestamp, file_ext
-        );
+        // The relative path template (relative to media root) is used in download URLs.
+        let naming = FileNaming {
+            output_path: output_path.to_string_lossy().to_string(),
+            output_dir,
+            prefix: filename_prefix,
+            ext: file_ext.to_string(),
+            stamp: chrono::Local::now()
+                .format(control::STAMP_FORMAT)
+                .to_string(),
+        };
+        let (location, _) = naming.templates(&naming.stamp);
 
         info!(
             "Recorder {}: output location template: {}, container: {}, max_size_time: {}s",
@@ -268,6 +327,12 @@ impl BlockBuilder for RecorderBuilder {
 
         // --- TS passthrough mode: raw MPEG-TS bytes directly to file ---
         if container == "ts_passthrough" {
+            if start_mode != StartMode::Immediate || preroll_secs > 0 {
+                warn!(
+                    "Recorder {}: recording control and pre-roll need splitmuxsink, ts_passthrough records right away",
+                    instance_id
+                );
+            }
             return build_ts_passthrough(
                 instance_id,
                 &location,
@@ -292,6 +357,10 @@ impl BlockBuilder for RecorderBuilder {
                     .build()
                     .map_err(|e| BlockBuildError::ElementCreation(format!("mpegtsmux: {}", e)))?;
                 m.set_property("alignment", 7i32);
+                // Carry SCTE-35 cues (injected via the API or passed through from inputs)
+                if scte35_pid > 0 && m.has_property("scte-35-pid") {
+                    m.set_property("scte-35-pid", scte35_pid);
+                }
                 m
             }
             _ => {
@@ -343,6 +412,7 @@ impl BlockBuilder for RecorderBuilder {
 
         let mut elements: Vec<(String, gst::Element)> =
             vec![(sink_id.clone(), splitmuxsink.clone())];
+        let mut tracks: Vec<Track> = Vec::new();
 
         // --- Request pads from splitmuxsink ---
         // All splitmuxsink sink pads are "On request". Pads must be requested before
@@ -562,11 +632,17 @@ impl BlockBuilder for RecorderBuilder {
                 },
             );
 
+            tracks.push(Track {
+                pad: src_pad.clone(),
+                sink_pad: requested_pad(&splitmuxsink, video_sink_pad_name_for_chain)?,
+                video: true,
+            });
             elements.push((video_input_id, video_input));
         }
 
         // --- Create audio input chains ---
         for (i, audio_sink_pad_name) in audio_sink_pad_names.iter().enumerate() {
+            let track_sink_pad = requested_pad(&splitmuxsink, audio_sink_pad_name)?;
             let audio_sink_pad_name = audio_sink_pad_name.clone();
             let audio_input_id = format!("{}:audio_input_{}", instance_id, i);
             let audio_input = gst::ElementFactory::make("identity")
@@ -717,6 +793,11 @@ impl BlockBuilder for RecorderBuilder {
                 },
             );
 
+            tracks.push(Track {
+                pad: src_pad.clone(),
+                sink_pad: track_sink_pad,
+                video: false,
+            });
             elements.push((audio_input_id, audio_input));
         }
 
@@ -725,36 +806,44 @@ impl BlockBuilder for RecorderBuilder {
             instance_id, num_video_tracks, num_audio_tracks, container
         );
 
+        // --- Recording control: gate every track, register for the API ---
+        let control = Arc::new(RecorderControl::new(
+            instance_id,
+            flow_id,
+            splitmuxsink.clone(),
+            tracks,
+            naming,
+            Options {
+                start_mode,
+                trigger,
+                preroll_ns: preroll_secs * 1_000_000_000,
+            },
+        ));
+        control.add_track_gates();
+        RECORDER_REGISTRY.register(
+            RecorderKey {
+                flow_id,
+                block_id: instance_id.to_string(),
+            },
+            Arc::clone(&control),
+        );
+
         // Register element setup to connect format-location signal at pipeline start.
         // The signal fires each time splitmuxsink opens a new file, giving us the actual filename.
-        // Also starts the auto-stop timer if max_duration_mins > 0.
+        // Also starts the trigger task and the auto-stop timer if max_duration_mins > 0.
         let splitmuxsink_for_signal = splitmuxsink.clone();
-        let location_template = location.clone();
-        let relative_location_template = relative_location.clone();
+        let control_for_setup = Arc::clone(&control);
         let block_id_for_signal = instance_id.to_string();
         ctx.register_element_setup(Box::new(move |flow_id, events| {
-            let events_clone = events.clone();
-            let block_id_clone = block_id_for_signal.clone();
-            let location_clone = location_template.clone();
-            let relative_location_clone = relative_location_template.clone();
+            let _ = control_for_setup.events.set(events.clone());
+            let control_weak = Arc::downgrade(&control_for_setup);
             splitmuxsink_for_signal.connect("format-location", false, move |args| {
                 let index = args[1].get::<u32>().unwrap_or(0);
-                // Reproduce the filename that splitmuxsink uses (same %05d format)
-                let filename = location_clone.replace("%05d", &format!("{:05}", index));
-                let relative_path =
-                    relative_location_clone.replace("%05d", &format!("{:05}", index));
-                debug!(
-                    "Recorder {}: writing file index {}: {}",
-                    block_id_clone, index, filename
-                );
-                events_clone.broadcast(strom_types::StromEvent::RecorderFileChanged {
-                    flow_id,
-                    block_id: block_id_clone.clone(),
-                    filename: relative_path,
-                });
+                let filename = control_weak.upgrade()?.format_location(index);
                 // Return the filename — the signal requires a gchararray return value
                 Some(filename.to_value())
             });
+            trigger::spawn_trigger_task(&control_for_setup, &events);
 
             if max_duration_mins > 0 {
                 let events_for_timer = events.clone();
@@ -778,15 +867,38 @@ impl BlockBuilder for RecorderBuilder {
             }
         }));
 
+        // A stopped recording is reset once splitmuxsink reports its file closed
+        let bus_message_handler: BusMessageConnectFn = Box::new(
+            move |bus: &gst::Bus, _flow_id: FlowId, _events: EventBroadcaster| {
+                bus.add_signal_watch();
+                bus.connect_message(Some("element"), move |_bus, msg| {
+                    let from_sink = msg.src().is_some_and(|src| src.name() == sink_id.as_str());
+                    let closed = msg
+                        .structure()
+                        .is_some_and(|s| s.name() == "splitmuxsink-fragment-closed");
+                    if from_sink && closed {
+                        control.finalize(None);
+                    }
+                })
+            },
+        );
+
         Ok(BlockBuildResult {
             elements,
             internal_links: vec![],
-            bus_message_handler: None,
+            bus_message_handler: Some(bus_message_handler),
             pad_properties: HashMap::new(),
         })
     }
 }
 
+/// A splitmuxsink pad requested by name.
+fn requested_pad(splitmuxsink: &gst::Element, name: &str) -> Result<gst::Pad, BlockBuildError> {
+    splitmuxsink.static_pad(name).ok_or_else(|| {
+        BlockBuildError::ElementCreation(format!("splitmuxsink: no requested pad {}", name))
+    })
+}
+
 /// Build a TS passthrough pipeline: identity -> multifilesink.
 ///
 /// The raw MPEG-TS bitstream is written directly to file without any demux/remux.
@@ -860,7 +972,7 @@ fn recorder_definition() -> BlockDefinition {
     BlockDefinition {
         id: "builtin.recorder".to_string(),
         name: "Recorder".to_string(),
-        description: "Records audio/video streams to file. Supports MP4, MKV, and MPEG-TS containers with optional time/size-based file splitting.".to_string(),
+        description: "Records audio/video streams to file. Supports MP4, MKV, and MPEG-TS containers with optional time/size-based file splitting. Recording can be started, paused and stopped while the flow runs, or triggered by tally or audio level.".to_string(),
         category: "Outputs".to_string(),
         exposed_properties: vec![
             ExposedProperty {
@@ -909,6 +1021,19 @@ fn recorder_definition() -> BlockDefinition {
                 },
                 live: false,
             },
+            ExposedProperty {
+                name: "scte35_pid".to_string(),
+                label: "SCTE-35 PID".to_string(),
+                description: "PID carrying SCTE-35 cues in MPEG-TS (remux) recordings (0 = disabled)".to_string(),
+                property_type: PropertyType::UInt,
+                default_value: Some(PropertyValue::UInt(0)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "scte35_pid".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
             ExposedProperty {
                 name: "output_dir".to_string(),
                 label: "Output Directory".to_string(),
@@ -961,6 +1086,38 @@ fn recorder_definition() -> BlockDefinition {
                 },
                 live: false,
             },
+            ExposedProperty {
+                name: "start_mode".to_string(),
+                label: "Start Mode".to_string(),
+                description: "What the recorder does when the flow starts: record right away, stay idle until started via the API, or stay idle with its trigger armed (not available in TS passthrough mode)".to_string(),
+                property_type: PropertyType::Enum {
+                    values: vec![
+                        EnumValue { value: "immediate".to_string(), label: Some("Record".to_string()) },
+                        EnumValue { value: "manual".to_string(), label: Some("Idle".to_string()) },
+                        EnumValue { value: "armed".to_string(), label: Some("Armed (trigger)".to_string()) },
+                    ],
+                },
+                default_value: Some(PropertyValue::String(DEFAULT_START_MODE.to_string())),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "start_mode".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
+            ExposedProperty {
+                name: "preroll_secs".to_string(),
+                label: "Pre-roll (s)".to_string(),
+                description: "Seconds of media kept in memory while not recording and written at the start of each file, from the keyframe at or before that depth (max 60). 0 = disabled.".to_string(),
+                property_type: PropertyType::Int,
+                default_value: Some(PropertyValue::Int(DEFAULT_PREROLL_SECS as i64)),
+                mapping: PropertyMapping {
+                    element_id: "_block".to_string(),
+                    property_name: "preroll_secs".to_string(),
+                    transform: None,
+                },
+                live: false,
+            },
             ExposedProperty {
                 name: "max_duration_mins".to_string(),
                 label: "Auto-stop After (min)".to_string(),
@@ -0,0 +1,217 @@
+//! Pre-roll of an idle recorder: the last seconds of encoded media of every
+//! track, kept in memory and written at the start of the next recording.
+//!
+//! Video rings start at a keyframe, at or before the configured depth, so
+//! they hold at least the depth (up to one GOP more). Audio rings are cut
+//! to the start of the video when recording starts.
+
+use std::collections::VecDeque;
+
+/// Longest GOP kept beyond the depth; longer ones are cut at the next
+/// keyframe, or dropped if the ring has none.
+const MAX_GOP_NS: u64 = 10_000_000_000;
+
+/// A buffered unit of a track.
+#[derive(Debug)]
+pub struct Entry<T> {
+    /// Running time (DTS, or PTS when there is none)
+    pub running_time: u64,
+    pub keyframe: bool,
+    pub size: usize,
+    pub item: T,
+}
+
+#[derive(Debug)]
+struct Ring<T> {
+    video: bool,
+    entries: VecDeque<Entry<T>>,
+}
+
+impl<T> Ring<T> {
+    fn span(&self) -> u64 {
+        match (self.entries.front(), self.entries.back()) {
+            (Some(first), Some(last)) => last.running_time.saturating_sub(first.running_time),
+            _ => 0,
+        }
+    }
+
+    /// Drop entries until the first one left is a keyframe.
+    fn drop_to_keyframe(&mut self) {
+        while self.entries.front().is_some_and(|e| !e.keyframe) {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Drop the entries before the last keyframe at or before `cutoff`.
+    fn trim(&mut self, cutoff: u64) {
+        let start = self
+            .entries
+            .iter()
+            .rposition(|e| e.keyframe && e.running_time <= cutoff);
+        if let Some(start) = start {
+            self.entries.drain(..start);
+        }
+    }
+}
+
+/// Pre-roll rings of all tracks of a recorder.
+#[derive(Debug)]
+pub struct Preroll<T> {
+    depth_ns: u64,
+    rings: Vec<Ring<T>>,
+}
+
+impl<T> Preroll<T> {
+    /// Rings for tracks of the given kinds (true for video).
+    pub fn new(depth_ns: u64, video: &[bool]) -> Self {
+        Self {
+            depth_ns,
+            rings: video
+                .iter()
+                .map(|&video| Ring {
+                    video,
+                    entries: VecDeque::new(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn depth_ns(&self) -> u64 {
+        self.depth_ns
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.depth_ns > 0
+    }
+
+    /// Buffer a unit of a track, dropping what is no longer needed.
+    pub fn push(&mut self, track: usize, mut entry: Entry<T>) {
+        let depth_ns = self.depth_ns;
+        let Some(ring) = self.rings.get_mut(track) else {
+            return;
+        };
+        if !ring.video {
+            entry.keyframe = true;
+        }
+        if ring.entries.is_empty() && !entry.keyframe {
+            return;
+        }
+        let cutoff = entry.running_time.saturating_sub(depth_ns);
+        ring.entries.push_back(entry);
+        ring.trim(cutoff);
+        while ring.span() > depth_ns + MAX_GOP_NS {
+            ring.entries.pop_front();
+            ring.drop_to_keyframe();
+        }
+    }
+
+    /// Align the rings for a recording starting now: audio is cut to the
+    /// start of the earliest video ring.
+    pub fn start(&mut self) {
+        let video_start = self
+            .rings
+            .iter()
+            .filter(|r| r.video)
+            .filter_map(|r| r.entries.front().map(|e| e.running_time))
+            .min();
+        if let Some(video_start) = video_start {
+            for ring in self.rings.iter_mut().filter(|r| !r.video) {
+                while ring
+                    .entries
+                    .front()
+                    .is_some_and(|e| e.running_time < video_start)
+                {
+                    ring.entries.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Take the buffered units of a track, oldest first.
+    pub fn take(&mut self, track: usize) -> Vec<T> {
+        self.rings
+            .get_mut(track)
+            .map(|r| r.entries.drain(..).map(|e| e.item).collect())
+            .unwrap_or_default()
+    }
+
+    /// Longest time span buffered by a track.
+    pub fn buffered_ns(&self) -> u64 {
+        self.rings.iter().map(Ring::span).max().unwrap_or(0)
+    }
+
+    /// Bytes buffered by all tracks.
+    pub fn buffered_bytes(&self) -> usize {
+        self.rings
+            .iter()
+            .flat_map(|r| r.entries.iter())
+            .map(|e| e.size)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1_000_000;
+
+    fn entry(running_time_ms: u64, keyframe: bool) -> Entry<u64> {
+        Entry {
+            running_time: running_time_ms * MS,
+            keyframe,
+            size: 100,
+            item: running_time_ms,
+        }
+    }
+
+    #[test]
+    fn test_preroll_video_starts_at_keyframe() {
+        // 1 s depth, keyframe every 400 ms, frames every 100 ms
+        let mut preroll = Preroll::new(1000 * MS, &[true]);
+        preroll.push(0, entry(0, false));
+        assert_eq!(preroll.buffered_bytes(), 0);
+        for t in (100..=2500).step_by(100) {
+            preroll.push(0, entry(t, t % 400 == 0));
+        }
+        // Cutoff at 1500 ms: the last keyframe at or before it is 1200 ms
+        assert_eq!(preroll.buffered_ns(), 1300 * MS);
+        assert_eq!(preroll.buffered_bytes(), 1400);
+        let items = preroll.take(0);
+        assert_eq!(items.first(), Some(&1200));
+        assert_eq!(items.last(), Some(&2500));
+        assert_eq!(preroll.buffered_bytes(), 0);
+    }
+
+    #[test]
+    fn test_preroll_audio_aligned_to_video() {
+        let mut preroll = Preroll::new(1000 * MS, &[true, false]);
+        for t in (0..=3000).step_by(100) {
+            preroll.push(0, entry(t, t % 2000 == 0));
+            preroll.push(1, entry(t, false));
+        }
+        // Video keeps 2000.. (keyframe before the 2000 ms cutoff), audio 2000..
+        preroll.start();
+        assert_eq!(preroll.take(0).first(), Some(&2000));
+        assert_eq!(preroll.take(1).first(), Some(&2000));
+
+        // Audio only: cut at the depth
+        let mut preroll = Preroll::new(1000 * MS, &[false]);
+        for t in (0..=3000).step_by(100) {
+            preroll.push(0, entry(t, false));
+        }
+        preroll.start();
+        assert_eq!(preroll.take(0).first(), Some(&2000));
+    }
+
+    #[test]
+    fn test_preroll_caps_long_gops() {
+        let mut preroll = Preroll::new(1000 * MS, &[true]);
+        preroll.push(0, entry(0, true));
+        for t in (1000..=20000).step_by(1000) {
+            preroll.push(0, entry(t, false));
+        }
+        // No keyframe left within depth + max GOP
+        assert_eq!(preroll.buffered_bytes(), 0);
+    }
+}
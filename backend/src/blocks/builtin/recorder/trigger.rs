@@ -0,0 +1,238 @@
+//! Event-triggered recording: follows tally and meter events and starts
+//! and stops an armed recorder.
+
+use super::control::RecorderControl;
+use crate::events::EventBroadcaster;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use strom_types::recorder::RecorderTriggerSource;
+use strom_types::{FlowId, StromEvent};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Trigger condition with the hold applied when it goes off.
+#[derive(Debug, Default)]
+struct Hold {
+    active: bool,
+    /// When the trigger goes off, if the condition is off
+    off_at: Option<Instant>,
+}
+
+impl Hold {
+    /// Feed the condition; returns the trigger state when it changes.
+    fn update(&mut self, on: bool, hold: Duration, now: Instant) -> Option<bool> {
+        if on {
+            self.off_at = None;
+            if self.active {
+                return None;
+            }
+            self.active = true;
+            return Some(true);
+        }
+        if self.active && self.off_at.is_none() {
+            self.off_at = Some(now + hold);
+        }
+        self.expire(now)
+    }
+
+    /// Turn the trigger off once the hold has passed.
+    fn expire(&mut self, now: Instant) -> Option<bool> {
+        match self.off_at {
+            Some(at) if now >= at => {
+                self.off_at = None;
+                self.active = false;
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The trigger condition carried by an event, if the event concerns it.
+fn condition(source: &RecorderTriggerSource, flow_id: FlowId, event: &StromEvent) -> Option<bool> {
+    match (source, event) {
+        (
+            RecorderTriggerSource::Tally {
+                flow_id: mixer_flow,
+                block_id,
+                input,
+            },
+            StromEvent::VisionMixerStateChanged {
+                flow_id: event_flow,
+                block_id: event_block,
+                program_inputs,
+                ..
+            },
+        ) if mixer_flow.unwrap_or(flow_id) == *event_flow && block_id == event_block => {
+            Some(program_inputs.contains(input))
+        }
+        (
+            RecorderTriggerSource::Audio {
+                block_id,
+                threshold_db,
+            },
+            StromEvent::MeterData {
+                flow_id: event_flow,
+                element_id,
+                peak,
+                ..
+            },
+        ) if flow_id == *event_flow && block_id == element_id => {
+            Some(peak.iter().any(|level| *level > *threshold_db))
+        }
+        _ => None,
+    }
+}
+
+/// Follow the events for the recorder's trigger until its flow stops.
+pub fn spawn_trigger_task(control: &Arc<RecorderControl>, events: &EventBroadcaster) {
+    let weak: Weak<RecorderControl> = Arc::downgrade(control);
+    let flow_id = control.flow_id;
+    let mut rx = events.subscribe();
+    tokio::spawn(async move {
+        let mut hold = Hold::default();
+        let mut source: Option<RecorderTriggerSource> = None;
+        loop {
+            let deadline = hold.off_at;
+            let received = tokio::select! {
+                received = rx.recv() => Some(received),
+                _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)),
+                    if deadline.is_some() => None,
+            };
+            let Some(control) = weak.upgrade() else {
+                break;
+            };
+
+            let changed = match received {
+                None => hold.expire(Instant::now()),
+                Some(Ok(StromEvent::FlowStopped { flow_id: stopped })) if stopped == flow_id => {
+                    break;
+                }
+                Some(Ok(event)) => {
+                    let trigger = control.trigger.lock().ok().and_then(|t| t.clone());
+                    let current = trigger.as_ref().map(|t| t.source.clone());
+                    if current != source {
+                        // A new trigger starts off
+                        source = current;
+                        let was_active = hold.active;
+                        hold = Hold::default();
+                        if was_active {
+                            control.on_trigger(false);
+                        }
+                    }
+                    match trigger {
+                        Some(trigger) => {
+                            condition(&trigger.source, flow_id, &event).and_then(|on| {
+                                hold.update(
+                                    on,
+                                    Duration::from_millis(trigger.hold_ms),
+                                    Instant::now(),
+                                )
+                            })
+                        }
+                        None => None,
+                    }
+                }
+                Some(Err(RecvError::Lagged(skipped))) => {
+                    debug!(
+                        "Recorder {} trigger skipped {} events",
+                        control.block_id, skipped
+                    );
+                    None
+                }
+                Some(Err(RecvError::Closed)) => break,
+            };
+            if let Some(on) = changed {
+                debug!(
+                    "Recorder {}: trigger {}",
+                    control.block_id,
+                    if on { "on" } else { "off" }
+                );
+                control.on_trigger(on);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_trigger_condition() {
+        let flow_id = Uuid::new_v4();
+        let tally = RecorderTriggerSource::Tally {
+            flow_id: None,
+            block_id: "vm".to_string(),
+            input: 2,
+        };
+        let mixer =
+            |block_id: &str, program_inputs: Vec<usize>| StromEvent::VisionMixerStateChanged {
+                flow_id,
+                block_id: block_id.to_string(),
+                preview_input: 0,
+                program_input: program_inputs.first().copied().unwrap_or(0),
+                preview_inputs: vec![0],
+                program_inputs,
+            };
+        assert_eq!(
+            condition(&tally, flow_id, &mixer("vm", vec![1, 2])),
+            Some(true)
+        );
+        assert_eq!(
+            condition(&tally, flow_id, &mixer("vm", vec![1])),
+            Some(false)
+        );
+        assert_eq!(condition(&tally, flow_id, &mixer("other", vec![2])), None);
+        assert_eq!(
+            condition(&tally, Uuid::new_v4(), &mixer("vm", vec![2])),
+            None
+        );
+
+        let audio = RecorderTriggerSource::Audio {
+            block_id: "meter".to_string(),
+            threshold_db: -50.0,
+        };
+        let meter = |peak: Vec<f64>| StromEvent::MeterData {
+            flow_id,
+            element_id: "meter".to_string(),
+            rms: peak.clone(),
+            peak,
+            decay: vec![],
+        };
+        assert_eq!(
+            condition(&audio, flow_id, &meter(vec![-70.0, -20.0])),
+            Some(true)
+        );
+        assert_eq!(
+            condition(&audio, flow_id, &meter(vec![-70.0, -60.0])),
+            Some(false)
+        );
+        assert_eq!(condition(&audio, flow_id, &mixer("meter", vec![])), None);
+    }
+
+    #[test]
+    fn test_trigger_hold() {
+        let hold_for = Duration::from_secs(2);
+        let start = Instant::now();
+        let mut hold = Hold::default();
+        assert_eq!(hold.update(false, hold_for, start), None);
+        assert_eq!(hold.update(true, hold_for, start), Some(true));
+        assert_eq!(hold.update(true, hold_for, start), None);
+
+        // Going off is held, and coming back on within the hold cancels it
+        let t = start + Duration::from_secs(1);
+        assert_eq!(hold.update(false, hold_for, t), None);
+        assert_eq!(hold.update(true, hold_for, t), None);
+        assert_eq!(hold.update(false, hold_for, t), None);
+        assert_eq!(hold.expire(t + Duration::from_secs(1)), None);
+        assert_eq!(hold.expire(t + hold_for), Some(false));
+        assert!(!hold.active);
+
+        // Without a hold it goes off right away
+        assert_eq!(hold.update(true, Duration::ZERO, t), Some(true));
+        assert_eq!(hold.update(false, Duration::ZERO, t), Some(false));
+    }
+}
@@ -0,0 +1,293 @@
+//! Audio-follow-video (AFV) section for the vision mixer.
+//!
+//! When audio is enabled, every video input gets a paired audio input that is
+//! summed by an audiomixer into a PGM audio output:
+//!
+//! ```text
+//! audio_queue_i → audioconvert_i → audioresample_i → audio_mixer.sink_i
+//! audio_mixer → audio_capsfilter → queue_audio_out → [pgm_audio_out]
+//! ```
+//!
+//! Inputs in AFV mode are audible only while their video is on PGM; the
+//! audiomixer pad volume is animated in step with the video transition.
+//! Inputs in "always on" mode stay at unity gain regardless of PGM.
+
+use crate::blocks::BlockBuildError;
+use crate::gst::transitions::TransitionType;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_controller::prelude::*;
+use gstreamer_controller::{DirectControlBinding, InterpolationControlSource, InterpolationMode};
+use strom_types::element::ElementPadRef;
+use strom_types::vision_mixer;
+use tracing::{debug, info};
+
+/// Internal element name of the PGM audiomixer.
+pub const AUDIO_MIXER_ELEMENT: &str = "audio_mixer";
+
+/// Audio behaviour of a single vision mixer input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioMode {
+    /// Audio follows video: audible only while the input is on PGM.
+    #[default]
+    FollowVideo,
+    /// Audio is always mixed into PGM at unity gain.
+    AlwaysOn,
+}
+
+impl AudioMode {
+    /// Parse a mode from its property value ("afv" or "on").
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "afv" => Some(Self::FollowVideo),
+            "on" => Some(Self::AlwaysOn),
+            _ => None,
+        }
+    }
+}
+
+/// Compute the target PGM audio volume for each input given the PGM group.
+pub fn input_volumes(modes: &[AudioMode], pgm_group: &[usize]) -> Vec<f64> {
+    modes
+        .iter()
+        .enumerate()
+        .map(|(i, mode)| match mode {
+            AudioMode::AlwaysOn => 1.0,
+            AudioMode::FollowVideo if pgm_group.contains(&i) => 1.0,
+            AudioMode::FollowVideo => 0.0,
+        })
+        .collect()
+}
+
+/// Create the audio elements and internal links for the AFV section.
+///
+/// Links into `audio_mixer` are created in input order so that the
+/// auto-requested sink pads line up with the video input indices.
+pub(super) fn build_audio_section(
+    instance_id: &str,
+    num_inputs: usize,
+    latency_ms: u64,
+    elems: &mut Vec<(String, gst::Element)>,
+    links: &mut Vec<(ElementPadRef, ElementPadRef)>,
+) -> Result<(), BlockBuildError> {
+    let id = |name: &str| format!("{}:{}", instance_id, name);
+
+    let mixer_id = id(AUDIO_MIXER_ELEMENT);
+    let audio_mixer = gst::ElementFactory::make("audiomixer")
+        .name(&mixer_id)
+        .build()
+        .map_err(|e| BlockBuildError::ElementCreation(format!("audiomixer: {}", e)))?;
+    audio_mixer.set_property_from_str("start-time-selection", "first");
+    audio_mixer.set_property("latency", gst::ClockTime::from_mseconds(latency_ms));
+    if audio_mixer.find_property("ignore-inactive-pads").is_some() {
+        audio_mixer.set_property("ignore-inactive-pads", true);
+    }
+    elems.push((mixer_id.clone(), audio_mixer));
+
+    for i in 0..num_inputs {
+        let q_id = id(&format!("audio_queue_{}", i));
+        let ac_id = id(&format!("audioconvert_{}", i));
+        let ar_id = id(&format!("audioresample_{}", i));
+
+        elems.push((q_id.clone(), super::elements::make_queue(&q_id)?));
+        elems.push((
+            ac_id.clone(),
+            super::elements::make_element("audioconvert", &ac_id)?,
+        ));
+        elems.push((
+            ar_id.clone(),
+            super::elements::make_element("audioresample", &ar_id)?,
+        ));
+
+        links.push((
+            ElementPadRef::pad(&q_id, "src"),
+            ElementPadRef::pad(&ac_id, "sink"),
+        ));
+        links.push((
+            ElementPadRef::pad(&ac_id, "src"),
+            ElementPadRef::pad(&ar_id, "sink"),
+        ));
+        links.push((
+            ElementPadRef::pad(&ar_id, "src"),
+            ElementPadRef::pad(&mixer_id, format!("sink_{}", i)),
+        ));
+    }
+
+    // Fixed output format so downstream encoders see stable caps
+    let cf_id = id("audio_capsfilter");
+    let capsfilter = gst::ElementFactory::make("capsfilter")
+        .name(&cf_id)
+        .property(
+            "caps",
+            gst::Caps::builder("audio/x-raw")
+                .field("rate", 48000i32)
+                .field("channels", 2i32)
+                .build(),
+        )
+        .build()
+        .map_err(|e| BlockBuildError::ElementCreation(format!("audio_capsfilter: {}", e)))?;
+    let q_out_id = id("queue_audio_out");
+    elems.push((cf_id.clone(), capsfilter));
+    elems.push((q_out_id.clone(), super::elements::make_queue(&q_out_id)?));
+
+    links.push((
+        ElementPadRef::pad(&mixer_id, "src"),
+        ElementPadRef::pad(&cf_id, "sink"),
+    ));
+    links.push((
+        ElementPadRef::pad(&cf_id, "src"),
+        ElementPadRef::pad(&q_out_id, "sink"),
+    ));
+
+    info!(
+        "Vision mixer audio section built: {} inputs, latency={}ms",
+        num_inputs, latency_ms
+    );
+
+    Ok(())
+}
+
+/// Animate the PGM audio volumes to follow a video transition.
+///
+/// Cuts switch instantly, dip-to-black fades out during the first half and in
+/// during the second half, every other transition cross-fades over the full
+/// duration. Inputs whose target volume doesn't change are set directly.
+pub fn follow_transition(
+    audio_mixer: &gst::Element,
+    modes: &[AudioMode],
+    old_pgm_group: &[usize],
+    new_pgm_group: &[usize],
+    transition_type: TransitionType,
+    duration_ms: u64,
+) {
+    let from = input_volumes(modes, old_pgm_group);
+    let to = input_volumes(modes, new_pgm_group);
+
+    let start_time = audio_mixer.query_position::<gst::ClockTime>();
+    let animate = transition_type != TransitionType::Cut && duration_ms > 0;
+
+    let pads = audio_mixer.sink_pads();
+    for (i, (&start, &end)) in from.iter().zip(to.iter()).enumerate() {
+        let pad_name = format!("sink_{}", i);
+        let Some(pad) = pads.iter().find(|p| p.name() == pad_name.as_str()) else {
+            continue;
+        };
+        if let Some(binding) = pad.control_binding("volume") {
+            pad.remove_control_binding(&binding);
+        }
+
+        match start_time {
+            Some(start_time) if animate && (start - end).abs() > f64::EPSILON => {
+                let (ramp_start, ramp_len) = ramp_window(
+                    transition_type,
+                    start_time,
+                    gst::ClockTime::from_mseconds(duration_ms),
+                    end > start,
+                );
+                setup_volume_animation(pad, ramp_start, ramp_len, start, end);
+            }
+            _ => pad.set_property("volume", end),
+        }
+    }
+
+    debug!(
+        "Audio follows {:?}: {:?} -> {:?} ({}ms)",
+        transition_type, old_pgm_group, new_pgm_group, duration_ms
+    );
+}
+
+/// Compute the (start, length) of a volume ramp for one input.
+fn ramp_window(
+    transition_type: TransitionType,
+    start_time: gst::ClockTime,
+    duration: gst::ClockTime,
+    fading_in: bool,
+) -> (gst::ClockTime, gst::ClockTime) {
+    if transition_type == TransitionType::DipToBlack {
+        let half = duration / 2;
+        if fading_in {
+            (start_time + half, half)
+        } else {
+            (start_time, half)
+        }
+    } else {
+        (start_time, duration)
+    }
+}
+
+/// Bind an eased volume ramp to an audiomixer sink pad.
+///
+/// The pad's `volume` range is 0–10, so keyframe values are normalized.
+fn setup_volume_animation(
+    pad: &gst::Pad,
+    start_time: gst::ClockTime,
+    duration: gst::ClockTime,
+    start_value: f64,
+    end_value: f64,
+) {
+    let max = pad
+        .find_property("volume")
+        .and_then(|pspec| {
+            pspec
+                .downcast_ref::<gst::glib::ParamSpecDouble>()
+                .map(|p| p.maximum())
+        })
+        .unwrap_or(10.0);
+
+    let cs = InterpolationControlSource::new();
+    cs.set_mode(InterpolationMode::Linear);
+
+    let num_keyframes = vision_mixer::TRANSITION_KEYFRAMES;
+    for i in 0..=num_keyframes {
+        let t = i as f64 / num_keyframes as f64;
+        let eased = (1.0 - (t * std::f64::consts::PI).cos()) / 2.0;
+        let value = start_value + (end_value - start_value) * eased;
+        let time =
+            start_time + gst::ClockTime::from_nseconds((duration.nseconds() as f64 * t) as u64);
+        cs.set(time, value / max);
+    }
+
+    let binding = DirectControlBinding::new(pad, "volume", &cs);
+    let _ = pad.add_control_binding(&binding);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_volumes_follow_pgm() {
+        let modes = vec![AudioMode::FollowVideo; 3];
+        assert_eq!(input_volumes(&modes, &[1]), vec![0.0, 1.0, 0.0]);
+        assert_eq!(input_volumes(&modes, &[0, 2]), vec![1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_input_volumes_always_on() {
+        let modes = vec![
+            AudioMode::FollowVideo,
+            AudioMode::AlwaysOn,
+            AudioMode::FollowVideo,
+        ];
+        assert_eq!(input_volumes(&modes, &[0]), vec![1.0, 1.0, 0.0]);
+        assert_eq!(input_volumes(&modes, &[2]), vec![0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_ramp_window_dip_splits_halves() {
+        let start = gst::ClockTime::from_seconds(10);
+        let duration = gst::ClockTime::from_mseconds(1000);
+        let out = ramp_window(TransitionType::DipToBlack, start, duration, false);
+        let inn = ramp_window(TransitionType::DipToBlack, start, duration, true);
+        assert_eq!(out, (start, gst::ClockTime::from_mseconds(500)));
+        assert_eq!(
+            inn,
+            (
+                start + gst::ClockTime::from_mseconds(500),
+                gst::ClockTime::from_mseconds(500)
+            )
+        );
+        let fade = ramp_window(TransitionType::Fade, start, duration, true);
+        assert_eq!(fade, (start, duration));
+    }
+}
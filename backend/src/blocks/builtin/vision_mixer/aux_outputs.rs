@@ -0,0 +1,83 @@
+//! AUX outputs for the vision mixer.
+//!
+//! Each AUX output has its own compositor (`aux_comp_k`) fed from every input
+//! tee plus the PGM tee and, when DSK layers exist, the clean-feed tee.
+//! Switching a source only changes pad alpha on that compositor, so AUX
+//! outputs can cut or fade independently of PGM:
+//!
+//! ```text
+//! tee_i.src_{3+k}     → queue_to_aux_k_i   → aux_comp_k.sink_i
+//! tee_pgm.src_{2+k}   → queue_pgm_aux_k    → aux_comp_k.sink_N
+//! tee_clean.src_{1+k} → queue_clean_aux_k  → aux_comp_k.sink_{N+1}   (DSK stage only)
+//! aux_comp_k → [download/caps] → queue_aux_out_k → [aux_out_k]
+//! ```
+//!
+//! Clean feed is PGM without DSK layers. With AUX outputs and DSK inputs, the
+//! DSK layers move off the distribution compositor to a separate `dsk_comp`
+//! stage so the signal before it can be tapped:
+//!
+//! ```text
+//! mixer → tee_clean.src_0 → queue_clean_dsk → dsk_comp.sink_0
+//! dsk_i → dsk_comp.sink_{1+i}
+//! dsk_comp → tee_pgm
+//! ```
+//!
+//! Without DSK inputs the clean feed is identical to PGM and uses the PGM pad.
+
+use strom_types::vision_mixer::AuxSource;
+
+/// Element name of the compositor for AUX output `k` (0-based).
+pub fn aux_comp_name(k: usize) -> String {
+    format!("aux_comp_{}", k)
+}
+
+/// Element name of the downstream keyer compositor (DSK stage only).
+pub const DSK_COMP_ELEMENT: &str = "dsk_comp";
+
+/// Inputs visible on an AUX output (empty for PGM and clean feed).
+pub fn source_inputs(source: AuxSource, pvw_group: &[usize]) -> Vec<usize> {
+    match source {
+        AuxSource::Input { input } => vec![input],
+        AuxSource::Preview => pvw_group.to_vec(),
+        AuxSource::Program | AuxSource::CleanFeed => Vec::new(),
+    }
+}
+
+/// AUX compositor sink pads that show a source.
+///
+/// Inputs use `sink_0..N-1`, PGM uses `sink_N` and the clean feed
+/// `sink_{N+1}` when the DSK stage exists (PGM otherwise).
+pub fn source_pads(
+    source: AuxSource,
+    num_inputs: usize,
+    pvw_group: &[usize],
+    dsk_stage: bool,
+) -> Vec<usize> {
+    match source {
+        AuxSource::Input { .. } | AuxSource::Preview => source_inputs(source, pvw_group),
+        AuxSource::Program => vec![num_inputs],
+        AuxSource::CleanFeed if dsk_stage => vec![num_inputs + 1],
+        AuxSource::CleanFeed => vec![num_inputs],
+    }
+}
+
+/// Inputs visible on any of the AUX outputs, sorted and deduplicated.
+pub fn all_inputs(sources: &[AuxSource], pvw_group: &[usize]) -> Vec<usize> {
+    let mut inputs: Vec<usize> = sources
+        .iter()
+        .flat_map(|&s| source_inputs(s, pvw_group))
+        .collect();
+    inputs.sort_unstable();
+    inputs.dedup();
+    inputs
+}
+
+/// AUX output numbers (1-based) that currently show an input.
+pub fn outputs_showing(sources: &[AuxSource], pvw_group: &[usize], input: usize) -> Vec<usize> {
+    sources
+        .iter()
+        .enumerate()
+        .filter(|(_, &s)| source_inputs(s, pvw_group).contains(&input))
+        .map(|(k, _)| k + 1)
+        .collect()
+}
@@ -1,22 +1,29 @@
 //! Pipeline construction for the vision mixer block.
 
+use super::audio::{self, AudioMode};
+use super::aux_outputs;
 use super::elements::{self, CompositorBackend};
+use super::keyers;
 use super::layout;
 use super::overlay::{self, OverlayRenderer, VisionMixerOverlayState};
 use super::properties;
+use super::stinger::{self, StingerPlayer};
 use crate::blocks::{BlockBuildContext, BlockBuildError, BlockBuildResult, BlockBuilder};
+use crate::gst::keying::{self, KeySettings, Keyer};
+use crate::gst::wipes;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
-use strom_types::vision_mixer;
+use strom_types::vision_mixer::{self, AuxSource};
 use strom_types::{
     block::{ExternalPad, ExternalPads},
     element::ElementPadRef,
     MediaType, PropertyValue,
 };
-use tracing::info;
+use tracing::{info, warn};
 
 /// Vision Mixer block builder.
 pub struct VisionMixerBuilder;
@@ -50,7 +57,7 @@ impl BlockBuilder for VisionMixerBuilder {
             ));
         }
 
-        let outputs = vec![
+        let mut outputs = vec![
             ExternalPad::with_label("pgm_out", "PGM", MediaType::Video, "queue_dist_out", "src"),
             ExternalPad::with_label(
                 "multiview_out",
@@ -61,6 +68,48 @@ impl BlockBuilder for VisionMixerBuilder {
             ),
         ];
 
+        // AUX output pads
+        for k in 0..properties::parse_num_aux_outputs(props) {
+            outputs.push(ExternalPad::with_label(
+                format!("aux_out_{}", k),
+                format!("AUX{}", k + 1),
+                MediaType::Video,
+                format!("queue_aux_out_{}", k),
+                "src",
+            ));
+        }
+
+        // Key preview of the USK layers
+        if properties::parse_num_upstream_keyers(props) > 0 {
+            outputs.push(ExternalPad::with_label(
+                "key_preview_out",
+                "KEY",
+                MediaType::Video,
+                "queue_key_preview_out",
+                "src",
+            ));
+        }
+
+        // Audio-follow-video pads (one audio input per video input + PGM audio)
+        if properties::parse_bool(props, "enable_audio", false) {
+            for i in 0..num_inputs {
+                inputs.push(ExternalPad::with_label(
+                    format!("audio_in_{}", i),
+                    format!("A{}", i),
+                    MediaType::Audio,
+                    format!("audio_queue_{}", i),
+                    "sink",
+                ));
+            }
+            outputs.push(ExternalPad::with_label(
+                "pgm_audio_out",
+                "PGM A",
+                MediaType::Audio,
+                "queue_audio_out",
+                "src",
+            ));
+        }
+
         Some(ExternalPads { inputs, outputs })
     }
 
@@ -74,6 +123,7 @@ impl BlockBuilder for VisionMixerBuilder {
         let pgm_input = properties::parse_initial_pgm(props, num_inputs);
         let pvw_input = properties::parse_initial_pvw(props, num_inputs);
         let labels = properties::parse_input_labels(props, num_inputs);
+        let audio_modes = properties::parse_audio_modes(props, num_inputs);
         let latency_ms = properties::parse_u64(props, "latency", vision_mixer::DEFAULT_LATENCY_MS);
         let min_upstream_ms = properties::parse_u64(
             props,
@@ -102,10 +152,14 @@ impl BlockBuilder for VisionMixerBuilder {
         );
 
         let num_dsk_inputs = properties::parse_num_dsk_inputs(props);
+        let num_usk = properties::parse_num_upstream_keyers(props);
+        let num_aux_outputs = properties::parse_num_aux_outputs(props);
+        let aux_sources = properties::parse_aux_sources(props, num_aux_outputs, num_inputs);
 
         let output_format = properties::parse_output_format(props);
         let gl_download =
             properties::parse_bool(props, "gl_download", vision_mixer::DEFAULT_GL_DOWNLOAD);
+        let enable_wipes = properties::parse_bool(props, "enable_wipes", false);
 
         let pref = props
             .get("compositor_preference")
@@ -127,9 +181,13 @@ impl BlockBuilder for VisionMixerBuilder {
             instance_id,
             num_inputs,
             num_dsk_inputs,
+            aux_sources: &aux_sources,
+            dsk_stage: num_aux_outputs > 0 && num_dsk_inputs > 0,
+            num_usk,
             pgm_input,
             pvw_input,
             labels: &labels,
+            audio_modes: &audio_modes,
             latency_ms,
             min_upstream_ms,
             pgm_w,
@@ -141,12 +199,40 @@ impl BlockBuilder for VisionMixerBuilder {
             backend,
             output_format,
             gl_download,
+            enable_wipes,
         };
 
-        match backend {
+        let mut result = match backend {
             CompositorBackend::OpenGL => build_gpu_pipeline(&p, ctx),
             CompositorBackend::Software => build_cpu_pipeline(&p, ctx),
+        }?;
+
+        // USK links must precede the stinger link (mixer pad order)
+        if num_usk > 0 {
+            add_upstream_keyers(&p, props, ctx, &mut result)?;
+        }
+
+        if let Some(clip) = properties::parse_stinger_clip(props) {
+            let cut_frame = properties::parse_u64(
+                props,
+                "stinger_cut_frame",
+                vision_mixer::DEFAULT_STINGER_CUT_FRAME,
+            );
+            add_stinger_section(&p, &clip, cut_frame, ctx, &mut result)?;
+        }
+
+        if !audio_modes.is_empty() {
+            audio::build_audio_section(
+                instance_id,
+                num_inputs,
+                latency_ms,
+                &mut result.elements,
+                &mut result.internal_links,
+            )?;
+            add_audio_pad_properties(&p, &mut result.pad_properties);
         }
+
+        Ok(result)
     }
 }
 
@@ -155,9 +241,18 @@ struct PipelineParams<'a> {
     instance_id: &'a str,
     num_inputs: usize,
     num_dsk_inputs: usize,
+    /// Initial source of each AUX output.
+    aux_sources: &'a [AuxSource],
+    /// DSK layers go on a separate `dsk_comp` after the clean-feed tap
+    /// (only when there are both AUX outputs and DSK inputs).
+    dsk_stage: bool,
+    /// Number of USK layers on the distribution compositor.
+    num_usk: usize,
     pgm_input: usize,
     pvw_input: usize,
     labels: &'a [String],
+    /// Per-input audio modes; empty when the audio section is disabled.
+    audio_modes: &'a [AudioMode],
     latency_ms: u64,
     min_upstream_ms: u64,
     pgm_w: u32,
@@ -169,6 +264,8 @@ struct PipelineParams<'a> {
     backend: CompositorBackend,
     output_format: Option<String>,
     gl_download: bool,
+    /// Insert a wipe stage in front of each distribution compositor pad.
+    enable_wipes: bool,
 }
 
 impl<'a> PipelineParams<'a> {
@@ -240,6 +337,7 @@ fn build_gpu_pipeline(
     // queue_post_dist decouples the compositor from downstream processing.
     // With gl_download=true:  mixer → queue_post_dist → tee_pgm → gldownload → capsfilter → queue_dist_out
     // With gl_download=false: mixer → queue_post_dist → tee_pgm → queue_dist_out (GL memory passthrough)
+    // With the DSK stage, tee_clean and dsk_comp sit between queue_post_dist and tee_pgm.
     let q_post_dist_id = p.id("queue_post_dist");
     let queue_post_dist = elements::make_queue(&q_post_dist_id)?;
     let tee_pgm_id = p.id("tee_pgm");
@@ -253,10 +351,13 @@ fn build_gpu_pipeline(
         ElementPadRef::pad(&mixer_id, "src"),
         ElementPadRef::pad(&q_post_dist_id, "sink"),
     ));
-    links.push((
+    let pgm_feed = add_dsk_stage(
+        p,
         ElementPadRef::pad(&q_post_dist_id, "src"),
-        ElementPadRef::pad(&tee_pgm_id, "sink"),
-    ));
+        &mut elems,
+        &mut links,
+    )?;
+    links.push((pgm_feed, ElementPadRef::pad(&tee_pgm_id, "sink")));
     if p.gl_download {
         let dl_dist_id = p.id("gldownload_dist");
         let gldownload_dist = elements::make_element("gldownload", "gldownload_dist")?;
@@ -464,18 +565,13 @@ fn build_gpu_pipeline(
             ElementPadRef::pad(&tee_id, "src_0"),
             ElementPadRef::pad(&q_dist_id, "sink"),
         ));
-        links.push((
-            ElementPadRef::pad(&q_dist_id, "src"),
-            ElementPadRef::pad(&mixer_id, format!("sink_{}", i)),
-        ));
+        let feed = add_wipe_stage(p, i, &q_dist_id, &mut elems, &mut links)?;
+        links.push((feed, ElementPadRef::pad(&mixer_id, format!("sink_{}", i))));
     }
-    // DSK inputs on dist compositor (after video inputs)
+    // DSK inputs on dist compositor (after video inputs) or on the DSK stage
     for i in 0..p.num_dsk_inputs {
         let cc_id = p.id(&format!("glcolorconvert_dsk_{}", i));
-        links.push((
-            ElementPadRef::pad(&cc_id, "src"),
-            ElementPadRef::pad(&mixer_id, format!("sink_{}", p.num_inputs + i)),
-        ));
+        links.push((ElementPadRef::pad(&cc_id, "src"), dsk_sink(p, i)));
     }
 
     // Multiview compositor thumbnails: tee_i.src_1 → queue → mv_comp
@@ -523,6 +619,8 @@ fn build_gpu_pipeline(
         ElementPadRef::pad(&mv_comp_id, format!("sink_{}", overlay_pad_idx)),
     ));
 
+    add_aux_outputs(p, &mut elems, &mut links)?;
+
     // --- Pad properties (applied after linking when auto-created pads exist) ---
     let pad_properties = build_pad_properties(p, &mv_layout);
 
@@ -567,7 +665,8 @@ fn build_cpu_pipeline(
     let mv_layout = layout::compute_layout(p.mv_w, p.mv_h, p.num_inputs);
 
     // --- Distribution output chain: mixer → capsfilter_dist → tee_pgm → queue_dist_out ---
-    // DSK inputs are composited on the main mixer (same as GPU path).
+    // DSK inputs are composited on the main mixer, or on the DSK stage between
+    // capsfilter_dist and tee_pgm (same as GPU path).
     // capsfilter_dist forces resolution (and optional pixel format) on the compositor output.
     let cf_dist_id = p.id("capsfilter_dist");
     let capsfilter_dist = gst::ElementFactory::make("capsfilter")
@@ -587,10 +686,13 @@ fn build_cpu_pipeline(
         ElementPadRef::pad(&mixer_id, "src"),
         ElementPadRef::pad(&cf_dist_id, "sink"),
     ));
-    links.push((
+    let pgm_feed = add_dsk_stage(
+        p,
         ElementPadRef::pad(&cf_dist_id, "src"),
-        ElementPadRef::pad(&tee_pgm_id, "sink"),
-    ));
+        &mut elems,
+        &mut links,
+    )?;
+    links.push((pgm_feed, ElementPadRef::pad(&tee_pgm_id, "sink")));
     links.push((
         ElementPadRef::pad(&tee_pgm_id, "src_0"),
         ElementPadRef::pad(&q_dist_out_id, "sink"),
@@ -828,10 +930,8 @@ fn build_cpu_pipeline(
             ElementPadRef::pad(&tee_id, "src_0"),
             ElementPadRef::pad(&q_dist_id, "sink"),
         ));
-        links.push((
-            ElementPadRef::pad(&q_dist_id, "src"),
-            ElementPadRef::pad(&mixer_id, format!("sink_{}", i)),
-        ));
+        let feed = add_wipe_stage(p, i, &q_dist_id, &mut elems, &mut links)?;
+        links.push((feed, ElementPadRef::pad(&mixer_id, format!("sink_{}", i))));
     }
     for i in 0..p.num_dsk_inputs {
         let last_dsk_elem = if p.output_format.is_some() {
@@ -839,10 +939,7 @@ fn build_cpu_pipeline(
         } else {
             p.id(&format!("videoconvert_dsk_{}", i))
         };
-        links.push((
-            ElementPadRef::pad(&last_dsk_elem, "src"),
-            ElementPadRef::pad(&mixer_id, format!("sink_{}", p.num_inputs + i)),
-        ));
+        links.push((ElementPadRef::pad(&last_dsk_elem, "src"), dsk_sink(p, i)));
     }
 
     // Multiview compositor thumbnails: tee_i.src_1 → queue → mv_comp
@@ -880,6 +977,8 @@ fn build_cpu_pipeline(
         ElementPadRef::pad(&mv_comp_id, format!("sink_{}", overlay_pad_idx)),
     ));
 
+    add_aux_outputs(p, &mut elems, &mut links)?;
+
     // Multiview PGM big display: tee_pgm.src_1 → queue_pgm_mv → capsfilter_pgm_mv → mv_comp.sink_N
     // (capsfilter breaks caps query cycle back to PGM compositor)
     links.push((
@@ -946,10 +1045,27 @@ fn build_pad_properties(
         );
     }
 
-    // --- DSK pads on dist compositor (high zorder, above video inputs) ---
+    // --- DSK stage: clean feed fullscreen underneath the DSK layers ---
+    if p.dsk_stage {
+        let props = pad_props
+            .entry(p.id(aux_outputs::DSK_COMP_ELEMENT))
+            .or_default()
+            .entry("sink_0".to_string())
+            .or_default();
+        props.insert("width".to_string(), PropertyValue::Int(p.pgm_w as i64));
+        props.insert("height".to_string(), PropertyValue::Int(p.pgm_h as i64));
+        props.insert("alpha".to_string(), PropertyValue::Float(1.0));
+        props.insert("zorder".to_string(), PropertyValue::UInt(0));
+    }
+
+    // --- DSK pads on dist compositor or DSK stage (high zorder, above video inputs) ---
     for i in 0..p.num_dsk_inputs {
-        let pad_name = format!("sink_{}", p.num_inputs + i);
-        let props = dist_pads.entry(pad_name).or_default();
+        let (element, pad) = dsk_pad(p, i);
+        let props = pad_props
+            .entry(element)
+            .or_default()
+            .entry(pad)
+            .or_default();
         props.insert("width".to_string(), PropertyValue::Int(p.pgm_w as i64));
         props.insert("height".to_string(), PropertyValue::Int(p.pgm_h as i64));
         props.insert("alpha".to_string(), PropertyValue::Float(0.0));
@@ -963,6 +1079,27 @@ fn build_pad_properties(
         );
     }
 
+    // --- AUX compositor pads: fullscreen, only the initial source visible ---
+    let pvw_group = [p.pvw_input];
+    let num_aux_pads = p.num_inputs + 1 + usize::from(p.dsk_stage);
+    for (k, &source) in p.aux_sources.iter().enumerate() {
+        let visible = aux_outputs::source_pads(source, p.num_inputs, &pvw_group, p.dsk_stage);
+        let aux_pads = pad_props
+            .entry(p.id(&aux_outputs::aux_comp_name(k)))
+            .or_default();
+        for idx in 0..num_aux_pads {
+            let props = aux_pads.entry(format!("sink_{}", idx)).or_default();
+            let alpha = if visible.contains(&idx) { 1.0 } else { 0.0 };
+            props.insert("alpha".to_string(), PropertyValue::Float(alpha));
+            props.insert("width".to_string(), PropertyValue::Int(p.pgm_w as i64));
+            props.insert("height".to_string(), PropertyValue::Int(p.pgm_h as i64));
+            props.insert(
+                "sizing-policy".to_string(),
+                PropertyValue::String("keep-aspect-ratio".to_string()),
+            );
+        }
+    }
+
     // --- Multiview compositor pad properties ---
     let mv_pads = pad_props.entry(mv_comp_id).or_default();
 
@@ -1051,6 +1188,620 @@ fn build_pad_properties(
     pad_props
 }
 
+/// Insert the wipe stage between `queue_to_dist_i` and the distribution compositor.
+///
+/// GPU: glshader running the wipe mask. CPU: videoconvert + capsfilter forcing
+/// a packed alpha format for the wipe probe. Returns the pad that feeds the
+/// compositor (the dist queue itself when wipes are disabled).
+fn add_wipe_stage(
+    p: &PipelineParams,
+    i: usize,
+    q_dist_id: &str,
+    elems: &mut Vec<(String, gst::Element)>,
+    links: &mut Vec<(ElementPadRef, ElementPadRef)>,
+) -> Result<ElementPadRef, BlockBuildError> {
+    if !p.enable_wipes {
+        return Ok(ElementPadRef::pad(q_dist_id, "src"));
+    }
+
+    match p.backend {
+        CompositorBackend::OpenGL => {
+            let wipe_id = p.id(&format!("wipe_{}", i));
+            let wipe = wipes::make_gl_wipe(&wipe_id)
+                .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", wipe_id, e)))?;
+            elems.push((wipe_id.clone(), wipe));
+            links.push((
+                ElementPadRef::pad(q_dist_id, "src"),
+                ElementPadRef::pad(&wipe_id, "sink"),
+            ));
+            Ok(ElementPadRef::pad(&wipe_id, "src"))
+        }
+        CompositorBackend::Software => {
+            let vc_id = p.id(&format!("wipe_convert_{}", i));
+            let cf_id = p.id(&format!("wipe_caps_{}", i));
+            elems.push((
+                vc_id.clone(),
+                elements::make_element("videoconvert", &vc_id)?,
+            ));
+            let capsfilter = gst::ElementFactory::make("capsfilter")
+                .name(&cf_id)
+                .property("caps", wipes::cpu_wipe_caps())
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", cf_id, e)))?;
+            elems.push((cf_id.clone(), capsfilter));
+            links.push((
+                ElementPadRef::pad(q_dist_id, "src"),
+                ElementPadRef::pad(&vc_id, "sink"),
+            ));
+            links.push((
+                ElementPadRef::pad(&vc_id, "src"),
+                ElementPadRef::pad(&cf_id, "sink"),
+            ));
+            Ok(ElementPadRef::pad(&cf_id, "src"))
+        }
+    }
+}
+
+/// Compositor element and sink pad of DSK layer `i`.
+fn dsk_pad(p: &PipelineParams, i: usize) -> (String, String) {
+    if p.dsk_stage {
+        (
+            p.id(aux_outputs::DSK_COMP_ELEMENT),
+            format!("sink_{}", 1 + i),
+        )
+    } else {
+        (p.id("mixer"), format!("sink_{}", p.num_inputs + i))
+    }
+}
+
+/// Link target for DSK layer `i` (see [`dsk_pad`]).
+fn dsk_sink(p: &PipelineParams, i: usize) -> ElementPadRef {
+    let (element, pad) = dsk_pad(p, i);
+    ElementPadRef::pad(element, pad)
+}
+
+/// Link a compositor output tee into another compositor through a queue.
+///
+/// CPU: a capsfilter after the queue breaks the caps query cycle back to the
+/// upstream compositor, same as `capsfilter_pgm_mv`.
+fn link_tee_to_compositor(
+    p: &PipelineParams,
+    tee_pad: ElementPadRef,
+    name: &str,
+    sink: ElementPadRef,
+    elems: &mut Vec<(String, gst::Element)>,
+    links: &mut Vec<(ElementPadRef, ElementPadRef)>,
+) -> Result<(), BlockBuildError> {
+    let q_id = p.id(&format!("queue_{}", name));
+    elems.push((q_id.clone(), elements::make_queue(&q_id)?));
+    links.push((tee_pad, ElementPadRef::pad(&q_id, "sink")));
+
+    match p.backend {
+        CompositorBackend::OpenGL => {
+            links.push((ElementPadRef::pad(&q_id, "src"), sink));
+        }
+        CompositorBackend::Software => {
+            let cf_id = p.id(&format!("capsfilter_{}", name));
+            let capsfilter = gst::ElementFactory::make("capsfilter")
+                .name(&cf_id)
+                .property("caps", p.pgm_caps())
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", cf_id, e)))?;
+            elems.push((cf_id.clone(), capsfilter));
+            links.push((
+                ElementPadRef::pad(&q_id, "src"),
+                ElementPadRef::pad(&cf_id, "sink"),
+            ));
+            links.push((ElementPadRef::pad(&cf_id, "src"), sink));
+        }
+    }
+    Ok(())
+}
+
+/// Insert the clean-feed tap and DSK stage between the mixer output and `tee_pgm`.
+///
+/// `feed` is the mixer output pad (after its queue or capsfilter). Returns the
+/// pad that feeds `tee_pgm`, which is `feed` itself without the DSK stage.
+fn add_dsk_stage(
+    p: &PipelineParams,
+    feed: ElementPadRef,
+    elems: &mut Vec<(String, gst::Element)>,
+    links: &mut Vec<(ElementPadRef, ElementPadRef)>,
+) -> Result<ElementPadRef, BlockBuildError> {
+    if !p.dsk_stage {
+        return Ok(feed);
+    }
+
+    let tee_clean_id = p.id("tee_clean");
+    elems.push((tee_clean_id.clone(), elements::make_tee(&tee_clean_id)?));
+    links.push((feed, ElementPadRef::pad(&tee_clean_id, "sink")));
+
+    let dsk_comp_id = p.id(aux_outputs::DSK_COMP_ELEMENT);
+    let dsk_comp = elements::make_dist_compositor(p.backend, p.latency_ms, p.min_upstream_ms)?;
+    dsk_comp.set_property("name", &dsk_comp_id);
+    elems.push((dsk_comp_id.clone(), dsk_comp));
+
+    // Clean feed must be linked first so it lands on sink_0 (DSK layers follow)
+    link_tee_to_compositor(
+        p,
+        ElementPadRef::pad(&tee_clean_id, "src_0"),
+        "clean_dsk",
+        ElementPadRef::pad(&dsk_comp_id, "sink_0"),
+        elems,
+        links,
+    )?;
+
+    match p.backend {
+        CompositorBackend::OpenGL => {
+            let q_id = p.id("queue_post_dsk");
+            elems.push((q_id.clone(), elements::make_queue(&q_id)?));
+            links.push((
+                ElementPadRef::pad(&dsk_comp_id, "src"),
+                ElementPadRef::pad(&q_id, "sink"),
+            ));
+            Ok(ElementPadRef::pad(&q_id, "src"))
+        }
+        CompositorBackend::Software => {
+            let cf_id = p.id("capsfilter_dsk_out");
+            let capsfilter = gst::ElementFactory::make("capsfilter")
+                .name(&cf_id)
+                .property("caps", p.pgm_caps())
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", cf_id, e)))?;
+            elems.push((cf_id.clone(), capsfilter));
+            links.push((
+                ElementPadRef::pad(&dsk_comp_id, "src"),
+                ElementPadRef::pad(&cf_id, "sink"),
+            ));
+            Ok(ElementPadRef::pad(&cf_id, "src"))
+        }
+    }
+}
+
+/// Add one compositor and output chain per AUX output.
+///
+/// Input tees use `src_3..`, `tee_pgm` uses `src_2..` and `tee_clean` uses
+/// `src_1..`, one pad per AUX output (see [`aux_outputs`] for the pad layout).
+fn add_aux_outputs(
+    p: &PipelineParams,
+    elems: &mut Vec<(String, gst::Element)>,
+    links: &mut Vec<(ElementPadRef, ElementPadRef)>,
+) -> Result<(), BlockBuildError> {
+    for k in 0..p.aux_sources.len() {
+        let comp_id = p.id(&aux_outputs::aux_comp_name(k));
+        let comp = elements::make_dist_compositor(p.backend, p.latency_ms, p.min_upstream_ms)?;
+        comp.set_property("name", &comp_id);
+        elems.push((comp_id.clone(), comp));
+
+        // Inputs: sink_0..N-1
+        for i in 0..p.num_inputs {
+            let tee_id = p.id(&format!("tee_{}", i));
+            let q_id = p.id(&format!("queue_to_aux_{}_{}", k, i));
+            elems.push((q_id.clone(), elements::make_queue(&q_id)?));
+            links.push((
+                ElementPadRef::pad(&tee_id, format!("src_{}", 3 + k)),
+                ElementPadRef::pad(&q_id, "sink"),
+            ));
+            links.push((
+                ElementPadRef::pad(&q_id, "src"),
+                ElementPadRef::pad(&comp_id, format!("sink_{}", i)),
+            ));
+        }
+
+        // PGM: sink_N, clean feed: sink_{N+1}
+        link_tee_to_compositor(
+            p,
+            ElementPadRef::pad(p.id("tee_pgm"), format!("src_{}", 2 + k)),
+            &format!("pgm_aux_{}", k),
+            ElementPadRef::pad(&comp_id, format!("sink_{}", p.num_inputs)),
+            elems,
+            links,
+        )?;
+        if p.dsk_stage {
+            link_tee_to_compositor(
+                p,
+                ElementPadRef::pad(p.id("tee_clean"), format!("src_{}", 1 + k)),
+                &format!("clean_aux_{}", k),
+                ElementPadRef::pad(&comp_id, format!("sink_{}", p.num_inputs + 1)),
+                elems,
+                links,
+            )?;
+        }
+
+        // Output: aux_comp → [queue_post_aux → gldownload] → capsfilter → queue_aux_out
+        let q_out_id = p.id(&format!("queue_aux_out_{}", k));
+        elems.push((q_out_id.clone(), elements::make_queue(&q_out_id)?));
+        let cf_id = p.id(&format!("capsfilter_aux_{}", k));
+        let make_capsfilter = || {
+            gst::ElementFactory::make("capsfilter")
+                .name(&cf_id)
+                .property("caps", p.pgm_caps())
+                .build()
+                .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", cf_id, e)))
+        };
+        match p.backend {
+            CompositorBackend::OpenGL => {
+                let q_post_id = p.id(&format!("queue_post_aux_{}", k));
+                elems.push((q_post_id.clone(), elements::make_queue(&q_post_id)?));
+                links.push((
+                    ElementPadRef::pad(&comp_id, "src"),
+                    ElementPadRef::pad(&q_post_id, "sink"),
+                ));
+                if p.gl_download {
+                    let dl_id = p.id(&format!("gldownload_aux_{}", k));
+                    elems.push((dl_id.clone(), elements::make_element("gldownload", &dl_id)?));
+                    elems.push((cf_id.clone(), make_capsfilter()?));
+                    links.push((
+                        ElementPadRef::pad(&q_post_id, "src"),
+                        ElementPadRef::pad(&dl_id, "sink"),
+                    ));
+                    links.push((
+                        ElementPadRef::pad(&dl_id, "src"),
+                        ElementPadRef::pad(&cf_id, "sink"),
+                    ));
+                    links.push((
+                        ElementPadRef::pad(&cf_id, "src"),
+                        ElementPadRef::pad(&q_out_id, "sink"),
+                    ));
+                } else {
+                    links.push((
+                        ElementPadRef::pad(&q_post_id, "src"),
+                        ElementPadRef::pad(&q_out_id, "sink"),
+                    ));
+                }
+            }
+            CompositorBackend::Software => {
+                elems.push((cf_id.clone(), make_capsfilter()?));
+                links.push((
+                    ElementPadRef::pad(&comp_id, "src"),
+                    ElementPadRef::pad(&cf_id, "sink"),
+                ));
+                links.push((
+                    ElementPadRef::pad(&cf_id, "src"),
+                    ElementPadRef::pad(&q_out_id, "sink"),
+                ));
+            }
+        }
+
+        info!(
+            "Vision mixer {} AUX {}: {:?}",
+            p.instance_id,
+            k + 1,
+            p.aux_sources[k]
+        );
+    }
+    Ok(())
+}
+
+/// Add the USK layers and the key preview (see [`keyers`] for the pad layout).
+///
+/// Each USK is linked to the mixer after the video and DSK pads, starts
+/// disabled (alpha 0) and shows the input in `usk_{k}_source`.
+fn add_upstream_keyers(
+    p: &PipelineParams,
+    props: &HashMap<String, PropertyValue>,
+    ctx: &BlockBuildContext,
+    result: &mut BlockBuildResult,
+) -> Result<(), BlockBuildError> {
+    let num_dsk_on_mixer = if p.dsk_stage { 0 } else { p.num_dsk_inputs };
+    let select_input_of = |key: &str, count: usize| {
+        properties::parse_u64(props, key, 0).min(count.saturating_sub(1) as u64) as usize
+    };
+    let mut keyer_list = Vec::new();
+    let mut selections = Vec::new();
+
+    for k in 0..p.num_usk {
+        // Fill selector fed by every input tee
+        let select_id = p.id(&keyers::usk_select_name(k));
+        let selector = keying::make_selector(&select_id)
+            .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", select_id, e)))?;
+        result.elements.push((select_id.clone(), selector.clone()));
+        for i in 0..p.num_inputs {
+            result.internal_links.push((
+                ElementPadRef::pad(
+                    p.id(&format!("tee_{}", i)),
+                    format!("src_{}", keyers::input_tee_pad(p.aux_sources.len(), k)),
+                ),
+                ElementPadRef::pad(&select_id, format!("sink_{}", i)),
+            ));
+        }
+        selections.push((
+            selector,
+            select_input_of(&format!("usk_{}_source", k), p.num_inputs),
+        ));
+
+        let q_id = p.id(&format!("queue_usk_{}", k));
+        result
+            .elements
+            .push((q_id.clone(), elements::make_queue(&q_id)?));
+        result.internal_links.push((
+            ElementPadRef::pad(&select_id, "src"),
+            ElementPadRef::pad(&q_id, "sink"),
+        ));
+
+        // Key stage: GPU shader, CPU videoconvert + BGRA capsfilter with the key probe
+        let settings = KeySettings::from_properties(props, &format!("usk_{}_", k));
+        let key_id = p.id(&keying::key_stage_name(k));
+        let key_feed = match p.backend {
+            CompositorBackend::OpenGL => {
+                let shader = keying::make_gl_key(&key_id, &settings)
+                    .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", key_id, e)))?;
+                keyer_list.push(Keyer::new(
+                    Arc::new(parking_lot::Mutex::new(settings)),
+                    Some(shader.clone()),
+                ));
+                result.elements.push((key_id.clone(), shader));
+                ElementPadRef::pad(&q_id, "src")
+            }
+            CompositorBackend::Software => {
+                let vc_id = p.id(&format!("key_convert_{}", k));
+                result.elements.push((
+                    vc_id.clone(),
+                    elements::make_element("videoconvert", &vc_id)?,
+                ));
+                let capsfilter = gst::ElementFactory::make("capsfilter")
+                    .name(&key_id)
+                    .property("caps", keying::cpu_key_caps())
+                    .build()
+                    .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", key_id, e)))?;
+                let settings = Arc::new(parking_lot::Mutex::new(settings));
+                capsfilter
+                    .static_pad("src")
+                    .and_then(|pad| keying::add_cpu_probe(&pad, Arc::clone(&settings)))
+                    .ok_or_else(|| {
+                        BlockBuildError::ElementCreation(format!("{}: key probe", key_id))
+                    })?;
+                keyer_list.push(Keyer::new(settings, None));
+                result.elements.push((key_id.clone(), capsfilter));
+                result.internal_links.push((
+                    ElementPadRef::pad(&q_id, "src"),
+                    ElementPadRef::pad(&vc_id, "sink"),
+                ));
+                ElementPadRef::pad(&vc_id, "src")
+            }
+        };
+        result
+            .internal_links
+            .push((key_feed, ElementPadRef::pad(&key_id, "sink")));
+
+        let tee_id = p.id(&keyers::usk_tee_name(k));
+        result
+            .elements
+            .push((tee_id.clone(), elements::make_tee(&tee_id)?));
+        result.internal_links.push((
+            ElementPadRef::pad(&key_id, "src"),
+            ElementPadRef::pad(&tee_id, "sink"),
+        ));
+
+        let pad_name = format!(
+            "sink_{}",
+            keyers::usk_pad_index(p.num_inputs, num_dsk_on_mixer, k)
+        );
+        result.internal_links.push((
+            ElementPadRef::pad(&tee_id, "src_0"),
+            ElementPadRef::pad(p.id("mixer"), &pad_name),
+        ));
+        let pad_props = result
+            .pad_properties
+            .entry(p.id("mixer"))
+            .or_default()
+            .entry(pad_name)
+            .or_default();
+        pad_props.insert("width".to_string(), PropertyValue::Int(p.pgm_w as i64));
+        pad_props.insert("height".to_string(), PropertyValue::Int(p.pgm_h as i64));
+        pad_props.insert("alpha".to_string(), PropertyValue::Float(0.0));
+        pad_props.insert(
+            "zorder".to_string(),
+            PropertyValue::UInt(vision_mixer::DIST_USK_BASE_ZORDER as u64 + k as u64),
+        );
+        pad_props.insert(
+            "sizing-policy".to_string(),
+            PropertyValue::String("keep-aspect-ratio".to_string()),
+        );
+    }
+
+    // Key preview: usk_tee_k.src_1 → key_preview_select → matte → queue_key_preview_out
+    let preview_select_id = p.id(keying::KEY_PREVIEW_SELECT);
+    let preview_select = keying::make_selector(&preview_select_id)
+        .map_err(|e| BlockBuildError::ElementCreation(format!("{}: {}", preview_select_id, e)))?;
+    result
+        .elements
+        .push((preview_select_id.clone(), preview_select.clone()));
+    for k in 0..p.num_usk {
+        result.internal_links.push((
+            ElementPadRef::pad(p.id(&keyers::usk_tee_name(k)), "src_1"),
+            ElementPadRef::pad(&preview_select_id, format!("sink_{}", k)),
+        ));
+    }
+    selections.push((
+        preview_select,
+        select_input_of("key_preview_usk", p.num_usk),
+    ));
+
+    let stages = keying::make_key_preview_stages(
+        p.instance_id,
+        p.backend == CompositorBackend::OpenGL,
+        !p.gl_download,
+    )
+    .map_err(|e| BlockBuildError::ElementCreation(format!("key preview: {}", e)))?;
+    let mut feed = ElementPadRef::pad(&preview_select_id, "src");
+    for (stage_id, stage) in stages {
+        result.elements.push((stage_id.clone(), stage));
+        result
+            .internal_links
+            .push((feed, ElementPadRef::pad(&stage_id, "sink")));
+        feed = ElementPadRef::pad(&stage_id, "src");
+    }
+    let q_out_id = p.id("queue_key_preview_out");
+    result
+        .elements
+        .push((q_out_id.clone(), elements::make_queue(&q_out_id)?));
+    result
+        .internal_links
+        .push((feed, ElementPadRef::pad(&q_out_id, "sink")));
+
+    keying::register_keyers(p.instance_id, props, keyer_list);
+    ctx.register_element_setup(Box::new(move |_flow_id, _events| {
+        for (selector, index) in selections {
+            if let Err(e) = keying::select_input(&selector, index) {
+                warn!("USK: {}", e);
+            }
+        }
+    }));
+
+    info!("Vision mixer {} USK layers: {}", p.instance_id, p.num_usk);
+    Ok(())
+}
+
+/// Add the stinger appsrc chain to the distribution compositor.
+///
+/// The stinger pad is linked after the video, DSK and USK pads, so it lands on
+/// `sink_{N+dsk+usk}` (`sink_{N+usk}` when the DSK layers are on the DSK
+/// stage). It stays at alpha 1 and shows a transparent frame while idle.
+/// A clip that fails to load is logged and the mixer is built without a stinger.
+fn add_stinger_section(
+    p: &PipelineParams,
+    clip: &Path,
+    cut_frame: u64,
+    ctx: &BlockBuildContext,
+    result: &mut BlockBuildResult,
+) -> Result<(), BlockBuildError> {
+    let src_id = p.id("stinger_src");
+    let appsrc = gst_app::AppSrc::builder()
+        .name(&src_id)
+        .caps(
+            &gst::Caps::builder("video/x-raw")
+                .field("format", "RGBA")
+                .field("width", p.pgm_w as i32)
+                .field("height", p.pgm_h as i32)
+                .field(
+                    "framerate",
+                    gst::Fraction::new(p.pgm_framerate.0, p.pgm_framerate.1),
+                )
+                .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+                .build(),
+        )
+        .format(gst::Format::Time)
+        .is_live(true)
+        .automatic_eos(false)
+        .do_timestamp(true)
+        .build();
+
+    let player = match StingerPlayer::new(
+        clip,
+        (p.pgm_w, p.pgm_h),
+        p.pgm_framerate,
+        cut_frame,
+        appsrc.clone(),
+    ) {
+        Ok(player) => player,
+        Err(e) => {
+            warn!("Vision mixer {}: stinger disabled: {}", p.instance_id, e);
+            return Ok(());
+        }
+    };
+
+    let q_id = p.id("queue_stinger");
+    let queue = elements::make_queue(&q_id)?;
+    result.elements.push((src_id.clone(), appsrc.upcast()));
+    result.elements.push((q_id.clone(), queue));
+    result.internal_links.push((
+        ElementPadRef::pad(&src_id, "src"),
+        ElementPadRef::pad(&q_id, "sink"),
+    ));
+
+    let last_id = match p.backend {
+        CompositorBackend::OpenGL => {
+            let up_id = p.id("glupload_stinger");
+            let cc_id = p.id("glcolorconvert_stinger");
+            result
+                .elements
+                .push((up_id.clone(), elements::make_element("glupload", &up_id)?));
+            result.elements.push((
+                cc_id.clone(),
+                elements::make_element("glcolorconvert", &cc_id)?,
+            ));
+            result.internal_links.push((
+                ElementPadRef::pad(&q_id, "src"),
+                ElementPadRef::pad(&up_id, "sink"),
+            ));
+            result.internal_links.push((
+                ElementPadRef::pad(&up_id, "src"),
+                ElementPadRef::pad(&cc_id, "sink"),
+            ));
+            cc_id
+        }
+        CompositorBackend::Software => {
+            // Keep the alpha channel through conversion; compositor blends it
+            let vc_id = p.id("videoconvert_stinger");
+            result.elements.push((
+                vc_id.clone(),
+                elements::make_element("videoconvert", &vc_id)?,
+            ));
+            result.internal_links.push((
+                ElementPadRef::pad(&q_id, "src"),
+                ElementPadRef::pad(&vc_id, "sink"),
+            ));
+            vc_id
+        }
+    };
+
+    let pad_idx = if p.dsk_stage {
+        p.num_inputs + p.num_usk
+    } else {
+        p.num_inputs + p.num_dsk_inputs + p.num_usk
+    };
+    result.internal_links.push((
+        ElementPadRef::pad(&last_id, "src"),
+        ElementPadRef::pad(p.id("mixer"), format!("sink_{}", pad_idx)),
+    ));
+
+    let props = result
+        .pad_properties
+        .entry(p.id("mixer"))
+        .or_default()
+        .entry(format!("sink_{}", pad_idx))
+        .or_default();
+    props.insert("width".to_string(), PropertyValue::Int(p.pgm_w as i64));
+    props.insert("height".to_string(), PropertyValue::Int(p.pgm_h as i64));
+    props.insert("alpha".to_string(), PropertyValue::Float(1.0));
+    props.insert(
+        "zorder".to_string(),
+        PropertyValue::UInt(vision_mixer::DIST_STINGER_ZORDER as u64),
+    );
+
+    stinger::register_stinger(p.instance_id, Arc::clone(&player));
+    ctx.register_element_setup(Box::new(move |_flow_id, _events| {
+        player.preload();
+    }));
+
+    info!(
+        "Vision mixer {} stinger: {} (cut at frame {})",
+        p.instance_id,
+        clip.display(),
+        cut_frame
+    );
+
+    Ok(())
+}
+
+/// Set initial audiomixer pad volumes so only the PGM and always-on inputs are heard.
+fn add_audio_pad_properties(
+    p: &PipelineParams,
+    pad_props: &mut HashMap<String, HashMap<String, HashMap<String, PropertyValue>>>,
+) {
+    let audio_pads = pad_props
+        .entry(p.id(audio::AUDIO_MIXER_ELEMENT))
+        .or_default();
+    let volumes = audio::input_volumes(p.audio_modes, &[p.pgm_input]);
+    for (i, volume) in volumes.into_iter().enumerate() {
+        audio_pads
+            .entry(format!("sink_{}", i))
+            .or_default()
+            .insert("volume".to_string(), PropertyValue::Float(volume));
+    }
+}
+
 /// Set up the overlay renderer: creates shared state, registers it, and starts
 /// a 1Hz timer that pushes overlay frames via appsrc when state changes.
 fn setup_overlay_renderer(
@@ -1060,14 +1811,19 @@ fn setup_overlay_renderer(
     mv_layout: &layout::OverlayLayout,
     ctx: &BlockBuildContext,
 ) {
-    let overlay_state = Arc::new(VisionMixerOverlayState::new(
-        p.num_inputs,
-        p.num_dsk_inputs,
-        p.pgm_input,
-        p.pvw_input,
-        p.labels.to_vec(),
-        mv_layout.clone(),
-    ));
+    let overlay_state = Arc::new(
+        VisionMixerOverlayState::new(
+            p.num_inputs,
+            p.num_dsk_inputs,
+            p.pgm_input,
+            p.pvw_input,
+            p.labels.to_vec(),
+            p.audio_modes.to_vec(),
+            mv_layout.clone(),
+        )
+        .with_aux_outputs(p.aux_sources, p.dsk_stage)
+        .with_upstream_keyers(p.num_usk),
+    );
 
     // Register the overlay state so the API layer can access it
     overlay::register_overlay_state(p.instance_id, Arc::clone(&overlay_state));
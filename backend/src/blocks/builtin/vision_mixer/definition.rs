@@ -1,5 +1,8 @@
 //! Block definition for the Vision Mixer block.
 
+use super::keyers;
+use crate::blocks::builtin::compositor::key_properties;
+use crate::gst::keying;
 use strom_types::block::*;
 use strom_types::vision_mixer::*;
 use strom_types::{
@@ -264,8 +267,167 @@ fn vision_mixer_definition() -> BlockDefinition {
             },
             live: false,
         },
+        // Number of USK layers
+        ExposedProperty {
+            name: "num_upstream_keyers".to_string(),
+            label: "USK Layers".to_string(),
+            description: "Number of Upstream Keyers layering a chroma- or luma-keyed input over PGM, below the DSK layers (0-2). Adds a key preview output".to_string(),
+            property_type: PropertyType::Enum {
+                values: (0..=MAX_UPSTREAM_KEYERS)
+                    .map(|n| EnumValue {
+                        value: n.to_string(),
+                        label: Some(if n == 0 {
+                            "None".to_string()
+                        } else {
+                            format!("{} USK", n)
+                        }),
+                    })
+                    .collect(),
+            },
+            default_value: Some(PropertyValue::String(DEFAULT_UPSTREAM_KEYERS.to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "num_upstream_keyers".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        // Number of AUX outputs
+        ExposedProperty {
+            name: "num_aux_outputs".to_string(),
+            label: "AUX Outputs".to_string(),
+            description: "Number of AUX outputs, each with its own source selection (0-4). Clean feed is PGM without DSK layers".to_string(),
+            property_type: PropertyType::Enum {
+                values: (0..=MAX_AUX_OUTPUTS)
+                    .map(|n| EnumValue {
+                        value: n.to_string(),
+                        label: Some(if n == 0 {
+                            "None".to_string()
+                        } else {
+                            format!("{} AUX", n)
+                        }),
+                    })
+                    .collect(),
+            },
+            default_value: Some(PropertyValue::String(DEFAULT_AUX_OUTPUTS.to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "num_aux_outputs".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        // Stinger clip (media library path; empty disables stinger transitions)
+        ExposedProperty {
+            name: "stinger_clip".to_string(),
+            label: "Stinger Clip".to_string(),
+            description: "Clip with alpha from the media library for stinger transitions (ProRes 4444, WebM VP9 alpha, or a PNG sequence like stingers/logo_%05d.png). Empty disables stingers.".to_string(),
+            property_type: PropertyType::String,
+            default_value: Some(PropertyValue::String(String::new())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "stinger_clip".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        // Stinger cut point
+        ExposedProperty {
+            name: "stinger_cut_frame".to_string(),
+            label: "Stinger Cut Frame".to_string(),
+            description: "Clip frame at which PGM switches underneath the stinger".to_string(),
+            property_type: PropertyType::UInt,
+            default_value: Some(PropertyValue::UInt(DEFAULT_STINGER_CUT_FRAME)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "stinger_cut_frame".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        // Audio-follow-video section
+        ExposedProperty {
+            name: "enable_audio".to_string(),
+            label: "Enable Audio".to_string(),
+            description: "Add an audio input per video input and a PGM audio output that follows the cut/fade".to_string(),
+            property_type: PropertyType::Bool,
+            default_value: Some(PropertyValue::Bool(false)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "enable_audio".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        // TSL UMD tally output
+        ExposedProperty {
+            name: "tally_destinations".to_string(),
+            label: "Tally Destinations".to_string(),
+            description: "Comma-separated TSL UMD receivers (udp://host:port or tcp://host:port). Empty disables tally output".to_string(),
+            property_type: PropertyType::String,
+            default_value: Some(PropertyValue::String(String::new())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "tally_destinations".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        ExposedProperty {
+            name: "tally_protocol".to_string(),
+            label: "Tally Protocol".to_string(),
+            description: "TSL UMD protocol version".to_string(),
+            property_type: PropertyType::Enum {
+                values: vec![
+                    EnumValue {
+                        value: "5.0".to_string(),
+                        label: Some("TSL v5.0".to_string()),
+                    },
+                    EnumValue {
+                        value: "3.1".to_string(),
+                        label: Some("TSL v3.1".to_string()),
+                    },
+                ],
+            },
+            default_value: Some(PropertyValue::String("5.0".to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "tally_protocol".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        ExposedProperty {
+            name: "tally_address_offset".to_string(),
+            label: "Tally Address Offset".to_string(),
+            description: "TSL display address of input 1; input N uses offset + N - 1 (v3.1 addresses stop at 126)".to_string(),
+            property_type: PropertyType::UInt,
+            default_value: Some(PropertyValue::UInt(1)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "tally_address_offset".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
+        ExposedProperty {
+            name: "tally_screen".to_string(),
+            label: "Tally Screen".to_string(),
+            description: "TSL v5.0 screen index".to_string(),
+            property_type: PropertyType::UInt,
+            default_value: Some(PropertyValue::UInt(0)),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: "tally_screen".to_string(),
+                transform: None,
+            },
+            live: false,
+        },
     ];
 
+    // SMPTE pattern wipes (same settings as the compositor block)
+    exposed_properties.extend(crate::blocks::builtin::compositor::wipe_properties());
+
     // Per-input labels
     for i in 0..MAX_NUM_INPUTS {
         exposed_properties.push(ExposedProperty {
@@ -283,6 +445,120 @@ fn vision_mixer_definition() -> BlockDefinition {
         });
     }
 
+    // Per-input audio modes (only used when enable_audio is set)
+    for i in 0..MAX_NUM_INPUTS {
+        exposed_properties.push(ExposedProperty {
+            name: format!("input_{}_audio", i),
+            label: format!("Input {} Audio", i + 1),
+            description: format!(
+                "Audio mode for input {}: follow video (heard only on PGM) or always on",
+                i + 1
+            ),
+            property_type: PropertyType::Enum {
+                values: vec![
+                    EnumValue {
+                        value: "afv".to_string(),
+                        label: Some("Audio Follow Video".to_string()),
+                    },
+                    EnumValue {
+                        value: "on".to_string(),
+                        label: Some("Always On".to_string()),
+                    },
+                ],
+            },
+            default_value: Some(PropertyValue::String("afv".to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("input_{}_audio", i),
+                transform: None,
+            },
+            live: false,
+        });
+    }
+
+    // Per-AUX initial sources (updated when switched via the API)
+    let aux_source_values: Vec<EnumValue> = [
+        ("pgm", "PGM".to_string()),
+        ("pvw", "PVW".to_string()),
+        ("clean", "Clean Feed".to_string()),
+    ]
+    .into_iter()
+    .map(|(value, label)| (value.to_string(), label))
+    .chain((0..MAX_NUM_INPUTS).map(|i| (format!("input_{}", i), format!("Input {}", i + 1))))
+    .map(|(value, label)| EnumValue {
+        value,
+        label: Some(label),
+    })
+    .collect();
+    for k in 0..MAX_AUX_OUTPUTS {
+        exposed_properties.push(ExposedProperty {
+            name: format!("aux_{}_source", k),
+            label: format!("AUX {} Source", k + 1),
+            description: format!("Source routed to AUX output {}", k + 1),
+            property_type: PropertyType::Enum {
+                values: aux_source_values.clone(),
+            },
+            default_value: Some(PropertyValue::String("pgm".to_string())),
+            mapping: PropertyMapping {
+                element_id: "_block".to_string(),
+                property_name: format!("aux_{}_source", k),
+                transform: None,
+            },
+            live: false,
+        });
+    }
+
+    // Per-USK fill source and key, adjustable while running
+    let usk_source_values: Vec<EnumValue> = (0..MAX_NUM_INPUTS)
+        .map(|i| EnumValue {
+            value: i.to_string(),
+            label: Some(format!("Input {}", i + 1)),
+        })
+        .collect();
+    for k in 0..MAX_UPSTREAM_KEYERS {
+        exposed_properties.push(ExposedProperty {
+            name: format!("usk_{}_source", k),
+            label: format!("USK {} Source", k + 1),
+            description: format!("Input keyed by USK {}", k + 1),
+            property_type: PropertyType::Enum {
+                values: usk_source_values.clone(),
+            },
+            default_value: Some(PropertyValue::String("0".to_string())),
+            mapping: PropertyMapping {
+                element_id: keyers::usk_select_name(k),
+                property_name: keying::SELECT_PROPERTY.to_string(),
+                transform: None,
+            },
+            live: true,
+        });
+        exposed_properties.extend(key_properties(
+            &format!("usk_{}_", k),
+            &format!("USK {}", k + 1),
+            k,
+            true,
+        ));
+    }
+    exposed_properties.push(ExposedProperty {
+        name: "key_preview_usk".to_string(),
+        label: "Key Preview USK".to_string(),
+        description: "USK whose key matte is shown on the key preview output".to_string(),
+        property_type: PropertyType::Enum {
+            values: (0..MAX_UPSTREAM_KEYERS)
+                .map(|k| EnumValue {
+                    value: k.to_string(),
+                    label: Some(format!("USK {}", k + 1)),
+                })
+                .collect(),
+        },
+        default_value: Some(PropertyValue::String("0".to_string())),
+        mapping: PropertyMapping {
+            element_id: keying::KEY_PREVIEW_SELECT.to_string(),
+            property_name: keying::SELECT_PROPERTY.to_string(),
+            transform: None,
+        },
+        live: true,
+    });
+
     BlockDefinition {
         id: "builtin.vision_mixer".to_string(),
         name: "Vision Mixer".to_string(),
@@ -314,24 +590,61 @@ fn vision_mixer_definition() -> BlockDefinition {
                         "sink".to_string(),
                     ));
                 }
+                // Audio input pads (audio-follow-video)
+                for i in 0..MAX_NUM_INPUTS {
+                    pads.push(ExternalPad::with_label(
+                        format!("audio_in_{}", i),
+                        format!("A{}", i),
+                        MediaType::Audio,
+                        format!("audio_queue_{}", i),
+                        "sink".to_string(),
+                    ));
+                }
                 pads
             },
-            outputs: vec![
-                ExternalPad::with_label(
-                    "pgm_out",
-                    "PGM",
-                    MediaType::Video,
-                    "queue_dist_out",
-                    "src",
-                ),
-                ExternalPad::with_label(
-                    "multiview_out",
-                    "MV",
+            outputs: {
+                let mut pads = vec![
+                    ExternalPad::with_label(
+                        "pgm_out",
+                        "PGM",
+                        MediaType::Video,
+                        "queue_dist_out",
+                        "src",
+                    ),
+                    ExternalPad::with_label(
+                        "multiview_out",
+                        "MV",
+                        MediaType::Video,
+                        "queue_mv_out",
+                        "src",
+                    ),
+                    ExternalPad::with_label(
+                        "pgm_audio_out",
+                        "PGM A",
+                        MediaType::Audio,
+                        "queue_audio_out",
+                        "src",
+                    ),
+                ];
+                // AUX output pads
+                for k in 0..MAX_AUX_OUTPUTS {
+                    pads.push(ExternalPad::with_label(
+                        format!("aux_out_{}", k),
+                        format!("AUX{}", k + 1),
+                        MediaType::Video,
+                        format!("queue_aux_out_{}", k),
+                        "src".to_string(),
+                    ));
+                }
+                pads.push(ExternalPad::with_label(
+                    "key_preview_out",
+                    "KEY",
                     MediaType::Video,
-                    "queue_mv_out",
+                    "queue_key_preview_out",
                     "src",
-                ),
-            ],
+                ));
+                pads
+            },
         },
         built_in: true,
         ui_metadata: Some(BlockUIMetadata {
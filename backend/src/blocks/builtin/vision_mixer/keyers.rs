@@ -0,0 +1,71 @@
+//! Upstream keyers (USK) for the vision mixer.
+//!
+//! Each USK picks its fill from any input through an `input-selector`, keys
+//! it (chroma or luma, see [`crate::gst::keying`]) and layers it over PGM on
+//! the distribution compositor, below the stinger and DSK layers:
+//!
+//! ```text
+//! tee_i.src_{3+A+k} → usk_select_k.sink_i
+//! usk_select_k → queue_usk_k → key_k → usk_tee_k
+//!   usk_tee_k.src_0 → mixer.sink_{N+D+k}         (D = 0 with the DSK stage)
+//!   usk_tee_k.src_1 → key_preview_select.sink_k → [matte] → queue_key_preview_out
+//! ```
+//!
+//! `A` is the number of AUX outputs. The input tees feed the selectors
+//! without a queue: the inactive inputs return not-linked, which the tees
+//! ignore. The key preview output shows the matte of one USK.
+
+/// Element name of the fill selector of USK `k` (0-based).
+pub fn usk_select_name(k: usize) -> String {
+    format!("usk_select_{}", k)
+}
+
+/// Element name of the tee after the key stage of USK `k`.
+pub fn usk_tee_name(k: usize) -> String {
+    format!("usk_tee_{}", k)
+}
+
+/// Input tee pad feeding USK `k`, after the mixer, multiview and AUX pads.
+pub fn input_tee_pad(num_aux_outputs: usize, k: usize) -> usize {
+    3 + num_aux_outputs + k
+}
+
+/// Distribution compositor pad of USK `k`, after the inputs and any DSK
+/// layers on the mixer.
+pub fn usk_pad_index(num_inputs: usize, num_dsk_on_mixer: usize, k: usize) -> usize {
+    num_inputs + num_dsk_on_mixer + k
+}
+
+/// What a distribution compositor sink pad carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistLayer {
+    Input(usize),
+    Dsk(usize),
+    Usk(usize),
+    Stinger,
+}
+
+/// Layer on distribution compositor pad `sink_{idx}`.
+///
+/// Pads are linked as inputs, DSK layers (only when they are on the mixer),
+/// USK layers and finally the stinger.
+pub fn dist_layer(
+    idx: usize,
+    num_inputs: usize,
+    num_dsk_on_mixer: usize,
+    num_usk: usize,
+) -> DistLayer {
+    if idx < num_inputs {
+        return DistLayer::Input(idx);
+    }
+    let idx = idx - num_inputs;
+    if idx < num_dsk_on_mixer {
+        return DistLayer::Dsk(idx);
+    }
+    let idx = idx - num_dsk_on_mixer;
+    if idx < num_usk {
+        DistLayer::Usk(idx)
+    } else {
+        DistLayer::Stinger
+    }
+}
@@ -19,13 +19,38 @@
 //!
 //! Distribution output: `dist_comp → capsfilter_dist → [pgm_out]`
 //! Multiview output: `mv_comp → gldownload_mv → videoconvert_pre_cairo → cairooverlay → capsfilter_mv → [multiview_out]`
+//!
+//! With `enable_audio`, each video input gets a paired audio input and the
+//! PGM audio output follows the cut/fade (see [`audio`]).
+//!
+//! With a `stinger_clip`, a preloaded alpha clip can be played over PGM as a
+//! stinger transition (see [`stinger`]).
+//!
+//! With `enable_wipes`, a wipe stage (GPU: glshader, CPU: videoconvert +
+//! capsfilter) sits between `tee_i.src_0` and `dist_comp.sink_i` so SMPTE
+//! pattern wipes can be used (see [`crate::gst::wipes`]).
+//!
+//! With `num_aux_outputs`, each AUX output gets its own compositor and can be
+//! switched to any input, PGM, PVW or clean feed (PGM without DSK) with a cut
+//! or fade (see [`aux_outputs`]).
+//!
+//! With `num_upstream_keyers`, each USK layers a chroma- or luma-keyed input
+//! over PGM, below the stinger and DSK layers, with a key preview output
+//! (see [`keyers`]).
+//!
+//! With `tally_destinations`, PGM/PVW tally and input labels are sent to TSL
+//! UMD receivers on every state change (see [`crate::tally`]).
 
+pub mod audio;
+pub mod aux_outputs;
 mod builder;
 mod definition;
 mod elements;
+pub mod keyers;
 pub(crate) mod layout;
 pub mod overlay;
 pub(crate) mod properties;
+pub mod stinger;
 #[cfg(test)]
 mod tests;
 
@@ -5,6 +5,8 @@
 //! in GPU/software as a texture at high zorder. Rendering only happens when
 //! state changes (~1/sec for clock, rare PGM/PVW switches).
 
+use super::audio::AudioMode;
+use super::keyers;
 use super::layout::OverlayLayout;
 use gstreamer as gst;
 use gstreamer::prelude::*;
@@ -13,7 +15,7 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Instant, SystemTime};
-use strom_types::vision_mixer::{self, TIMEZONE_REFRESH_SECS};
+use strom_types::vision_mixer::{self, AuxSource, TIMEZONE_REFRESH_SECS};
 use tracing::{debug, warn};
 
 /// Global registry of vision mixer overlay states, keyed by block instance ID.
@@ -63,10 +65,18 @@ pub struct VisionMixerOverlayState {
     pub dsk_enabled: Vec<AtomicBool>,
     /// Number of DSK inputs.
     pub num_dsk_inputs: usize,
+    /// DSK layers are on the separate `dsk_comp` stage instead of the mixer.
+    pub dsk_stage: bool,
+    /// USK enabled states (one per USK layer).
+    pub usk_enabled: Vec<AtomicBool>,
+    /// Packed source of each AUX output. See `AuxSource::pack`.
+    aux_sources: Vec<AtomicU64>,
     /// Pre-computed layout (immutable after construction).
     pub layout: OverlayLayout,
     /// Input labels (set at build time, read-only after).
     pub labels: Vec<String>,
+    /// Per-input audio modes (empty when the audio section is disabled).
+    pub audio_modes: Vec<AudioMode>,
     /// Monotonic instant captured at construction for wall-clock derivation.
     instant_base: Instant,
     /// UTC seconds at `instant_base` (no timezone offset applied).
@@ -86,6 +96,7 @@ impl VisionMixerOverlayState {
         pgm_input: usize,
         pvw_input: usize,
         labels: Vec<String>,
+        audio_modes: Vec<AudioMode>,
         layout: OverlayLayout,
     ) -> Self {
         let now_sys = SystemTime::now();
@@ -107,8 +118,12 @@ impl VisionMixerOverlayState {
                 .map(|_| AtomicBool::new(false))
                 .collect(),
             num_dsk_inputs,
+            dsk_stage: false,
+            usk_enabled: Vec::new(),
+            aux_sources: Vec::new(),
             layout,
             labels,
+            audio_modes,
             instant_base: now_instant,
             base_utc_secs: utc_secs,
             tz_offset_secs: AtomicI64::new(offset_secs),
@@ -117,6 +132,78 @@ impl VisionMixerOverlayState {
         }
     }
 
+    /// Add AUX outputs with their initial sources.
+    ///
+    /// `dsk_stage` is set when the DSK layers were moved to `dsk_comp` to
+    /// provide a clean feed.
+    pub fn with_aux_outputs(mut self, sources: &[AuxSource], dsk_stage: bool) -> Self {
+        self.aux_sources = sources.iter().map(|s| AtomicU64::new(s.pack())).collect();
+        self.dsk_stage = dsk_stage;
+        self
+    }
+
+    /// Add USK layers, all initially disabled.
+    pub fn with_upstream_keyers(mut self, num_usk: usize) -> Self {
+        self.usk_enabled = (0..num_usk).map(|_| AtomicBool::new(false)).collect();
+        self
+    }
+
+    /// Whether DSK layer `dsk_index` (0-based) is enabled.
+    pub fn dsk_is_enabled(&self, dsk_index: usize) -> bool {
+        self.dsk_enabled
+            .get(dsk_index)
+            .is_some_and(|e| e.load(Ordering::Relaxed))
+    }
+
+    /// Whether USK layer `usk_index` (0-based) is enabled.
+    pub fn usk_is_enabled(&self, usk_index: usize) -> bool {
+        self.usk_enabled
+            .get(usk_index)
+            .is_some_and(|e| e.load(Ordering::Relaxed))
+    }
+
+    /// Enable or disable a USK layer (0-based). Out-of-range indices are ignored.
+    pub fn set_usk_enabled(&self, usk_index: usize, enabled: bool) {
+        if let Some(slot) = self.usk_enabled.get(usk_index) {
+            slot.store(enabled, Ordering::Relaxed);
+        }
+    }
+
+    /// Layer on distribution compositor pad `sink_{idx}`.
+    pub fn dist_layer(&self, idx: usize) -> keyers::DistLayer {
+        let num_dsk_on_mixer = if self.dsk_stage {
+            0
+        } else {
+            self.num_dsk_inputs
+        };
+        keyers::dist_layer(
+            idx,
+            self.num_inputs,
+            num_dsk_on_mixer,
+            self.usk_enabled.len(),
+        )
+    }
+
+    /// Number of AUX outputs.
+    pub fn num_aux_outputs(&self) -> usize {
+        self.aux_sources.len()
+    }
+
+    /// Current source of every AUX output.
+    pub fn aux_sources(&self) -> Vec<AuxSource> {
+        self.aux_sources
+            .iter()
+            .map(|s| AuxSource::unpack(s.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Set the source of an AUX output (0-based). Out-of-range indices are ignored.
+    pub fn set_aux_source(&self, aux_index: usize, source: AuxSource) {
+        if let Some(slot) = self.aux_sources.get(aux_index) {
+            slot.store(source.pack(), Ordering::Relaxed);
+        }
+    }
+
     /// Get the PGM source group as a Vec of indices.
     pub fn pgm_group(&self) -> Vec<usize> {
         vision_mixer::unpack_source_group(self.pgm_group.load(Ordering::Relaxed))
@@ -264,6 +351,11 @@ const BG_R: f64 = 0.0; // fed to cairo R channel → outputs as B=0
 const BG_G: f64 = 0.8;
 const BG_B: f64 = 1.0; // fed to cairo B channel → outputs as R=1.0
 
+// Cyan for AUX indicator: want output R=0, G=0.7, B=1.0
+const AUX_R: f64 = 1.0; // fed to cairo R channel → outputs as B=1.0
+const AUX_G: f64 = 0.7;
+const AUX_B: f64 = 0.0; // fed to cairo B channel → outputs as R=0
+
 const GRAY: f64 = 0.5;
 
 /// Helper to get text extents, returning (width, height) with a fallback.
@@ -323,6 +415,7 @@ pub struct OverlayRenderer {
     last_pvw: u64,
     last_bg: u64,
     last_ftb: bool,
+    last_aux: Vec<AuxSource>,
     last_clock_secs: u64,
 }
 
@@ -350,6 +443,7 @@ impl OverlayRenderer {
             last_pvw: u64::MAX,
             last_bg: u64::MAX - 1,
             last_ftb: false,
+            last_aux: Vec::new(),
             last_clock_secs: u64::MAX,
         }
     }
@@ -360,6 +454,7 @@ impl OverlayRenderer {
         let pvw_packed = self.state.pvw_group_packed();
         let bg_packed = self.state.background_input_packed();
         let ftb = self.state.ftb_active.load(Ordering::Relaxed);
+        let aux = self.state.aux_sources();
         let (h, m, s) = self.state.wall_clock_hms();
         let clock_secs = h as u64 * 3600 + m as u64 * 60 + s as u64;
 
@@ -367,6 +462,7 @@ impl OverlayRenderer {
             && self.last_pvw == pvw_packed
             && self.last_bg == bg_packed
             && self.last_ftb == ftb
+            && self.last_aux == aux
             && self.last_clock_secs == clock_secs
         {
             return false;
@@ -393,6 +489,7 @@ impl OverlayRenderer {
             self.last_pvw = pvw_packed;
             self.last_bg = bg_packed;
             self.last_ftb = ftb;
+            self.last_aux = aux;
             self.last_clock_secs = clock_secs;
             true
         } else {
@@ -674,6 +771,32 @@ fn render_overlay(
         }
     }
 
+    // --- AUX indicator on thumbnails of inputs routed to an AUX output ---
+    let aux_sources = state.aux_sources();
+    if !aux_sources.is_empty() {
+        cr.set_font_size(layout.label_font_size * 0.8);
+        for i in 0..layout.num_inputs.min(layout.thumbnail_rects.len()) {
+            let outputs = super::aux_outputs::outputs_showing(&aux_sources, pvw_group, i);
+            if outputs.is_empty() {
+                continue;
+            }
+            let numbers: Vec<String> = outputs.iter().map(|k| k.to_string()).collect();
+            let r = &layout.thumbnail_rects[i];
+            draw_label_centered(
+                cr,
+                &format!("AUX {}", numbers.join(",")),
+                r.x + r.w / 2.0,
+                r.y + r.h - layout.label_font_size * 0.5,
+                AUX_R,
+                AUX_G,
+                AUX_B,
+                0.7,
+                2.0 * sc,
+                1.0 * sc,
+            );
+        }
+    }
+
     // --- PVW / PGM header labels ---
     cr.set_font_size(layout.header_font_size);
 
@@ -1,8 +1,12 @@
 //! Property parsing helpers for vision mixer block.
 
+use super::audio::AudioMode;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use strom_types::vision_mixer::{
-    DEFAULT_DSK_INPUTS, DEFAULT_NUM_INPUTS, MAX_DSK_INPUTS, MAX_NUM_INPUTS, MIN_NUM_INPUTS,
+    AuxSource, DEFAULT_AUX_OUTPUTS, DEFAULT_DSK_INPUTS, DEFAULT_NUM_INPUTS,
+    DEFAULT_UPSTREAM_KEYERS, MAX_AUX_OUTPUTS, MAX_DSK_INPUTS, MAX_NUM_INPUTS, MAX_UPSTREAM_KEYERS,
+    MIN_NUM_INPUTS,
 };
 use strom_types::PropertyValue;
 
@@ -20,6 +24,57 @@ pub fn parse_num_dsk_inputs(properties: &HashMap<String, PropertyValue>) -> usiz
         .min(MAX_DSK_INPUTS)
 }
 
+/// Parse the number of USK layers from block properties (0-2).
+pub fn parse_num_upstream_keyers(properties: &HashMap<String, PropertyValue>) -> usize {
+    properties
+        .get("num_upstream_keyers")
+        .and_then(|v| match v {
+            PropertyValue::String(s) => s.parse::<usize>().ok(),
+            PropertyValue::UInt(n) => Some(*n as usize),
+            PropertyValue::Int(n) => Some(*n as usize),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_UPSTREAM_KEYERS)
+        .min(MAX_UPSTREAM_KEYERS)
+}
+
+/// Parse the number of AUX outputs from block properties (0-4).
+pub fn parse_num_aux_outputs(properties: &HashMap<String, PropertyValue>) -> usize {
+    properties
+        .get("num_aux_outputs")
+        .and_then(|v| match v {
+            PropertyValue::String(s) => s.parse::<usize>().ok(),
+            PropertyValue::UInt(n) => Some(*n as usize),
+            PropertyValue::Int(n) => Some(*n as usize),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_AUX_OUTPUTS)
+        .min(MAX_AUX_OUTPUTS)
+}
+
+/// Parse the initial AUX sources, falling back to PGM for missing or invalid ones.
+pub fn parse_aux_sources(
+    properties: &HashMap<String, PropertyValue>,
+    num_aux: usize,
+    num_inputs: usize,
+) -> Vec<AuxSource> {
+    (0..num_aux)
+        .map(|k| {
+            properties
+                .get(&format!("aux_{}_source", k))
+                .and_then(|v| match v {
+                    PropertyValue::String(s) => AuxSource::parse(s),
+                    _ => None,
+                })
+                .filter(|source| match source {
+                    AuxSource::Input { input } => *input < num_inputs,
+                    _ => true,
+                })
+                .unwrap_or(AuxSource::Program)
+        })
+        .collect()
+}
+
 /// Parse the number of inputs from block properties, clamped to valid range.
 pub fn parse_num_inputs(properties: &HashMap<String, PropertyValue>) -> usize {
     properties
@@ -80,6 +135,43 @@ pub fn parse_input_labels(
         .collect()
 }
 
+/// Parse per-input audio modes, or an empty list when audio is disabled.
+pub fn parse_audio_modes(
+    properties: &HashMap<String, PropertyValue>,
+    num_inputs: usize,
+) -> Vec<AudioMode> {
+    if !parse_bool(properties, "enable_audio", false) {
+        return Vec::new();
+    }
+    (0..num_inputs)
+        .map(|i| {
+            properties
+                .get(&format!("input_{}_audio", i))
+                .and_then(|v| match v {
+                    PropertyValue::String(s) => AudioMode::parse(s),
+                    _ => None,
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Parse the stinger clip path (relative to the media library), if configured.
+pub fn parse_stinger_clip(properties: &HashMap<String, PropertyValue>) -> Option<PathBuf> {
+    let clip = properties.get("stinger_clip").and_then(|v| match v {
+        PropertyValue::String(s) if !s.trim().is_empty() => Some(s.trim()),
+        _ => None,
+    })?;
+    let media_path = properties
+        .get("_media_path")
+        .and_then(|v| match v {
+            PropertyValue::String(s) => Some(PathBuf::from(s)),
+            _ => None,
+        })
+        .unwrap_or_else(|| PathBuf::from("./media"));
+    Some(super::stinger::resolve_clip_path(&media_path, clip))
+}
+
 /// Parse a resolution string property, returning (width, height).
 pub fn parse_resolution(
     properties: &HashMap<String, PropertyValue>,
@@ -0,0 +1,436 @@
+//! Stinger transitions for the vision mixer.
+//!
+//! A stinger is a short clip with alpha (ProRes 4444, WebM VP9 alpha or a PNG
+//! sequence) from the media library played over the programme. The switch
+//! underneath happens at a configurable cut-point frame while the clip covers
+//! the picture.
+//!
+//! The clip is decoded by a separate pipeline that is prerolled in PAUSED at
+//! flow start, so firing only needs a state change to PLAYING:
+//!
+//! ```text
+//! (decoder) filesrc → decodebin → videoconvert → videoscale → capsfilter → appsink
+//! (mixer)   stinger_src → queue_stinger → [upload/convert] → mixer.sink_{N+dsk}
+//! ```
+//!
+//! Decoded frames are copied into `stinger_src`. At end of clip a transparent
+//! frame is pushed and the decoder is rewound and paused again. A decoder
+//! error, or a run outlasting the clip by [`FINISH_MARGIN`], resets the
+//! decoder the same way so the next transition can fire.
+
+use gstreamer as gst;
+use gstreamer::glib;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Time a run may outlast the clip before the stinger is reset.
+const FINISH_MARGIN: Duration = Duration::from_secs(2);
+
+/// Run length assumed when the clip duration is unknown (image sequences).
+const FALLBACK_CLIP_DURATION: Duration = Duration::from_secs(30);
+
+/// Global registry of stinger players, keyed by block instance ID.
+fn stingers() -> &'static Mutex<HashMap<String, Arc<StingerPlayer>>> {
+    static INSTANCE: OnceLock<Mutex<HashMap<String, Arc<StingerPlayer>>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Register a stinger player for a block instance.
+pub fn register_stinger(block_id: &str, player: Arc<StingerPlayer>) {
+    if let Ok(mut map) = stingers().lock() {
+        map.insert(block_id.to_string(), player);
+    }
+}
+
+/// Get the stinger player for a block instance (if a clip is configured).
+pub fn get_stinger(block_id: &str) -> Option<Arc<StingerPlayer>> {
+    stingers().lock().ok()?.get(block_id).cloned()
+}
+
+/// Unregister the stinger player for a block instance (call on flow stop).
+pub fn unregister_stinger(block_id: &str) {
+    if let Ok(mut map) = stingers().lock() {
+        map.remove(block_id);
+    }
+}
+
+/// Resolve a clip path relative to the media directory.
+pub fn resolve_clip_path(media_path: &Path, clip: &str) -> PathBuf {
+    let clean = clip
+        .strip_prefix("./media/")
+        .or_else(|| clip.strip_prefix("media/"))
+        .unwrap_or(clip);
+    if Path::new(clean).is_absolute() {
+        PathBuf::from(clean)
+    } else {
+        media_path.join(clean)
+    }
+}
+
+/// Whether a clip path is a printf-style image sequence (e.g. `sting_%05d.png`).
+pub fn is_image_sequence(path: &Path) -> bool {
+    path.to_string_lossy().contains('%')
+}
+
+/// Convert a frame number to a clip offset at the given framerate.
+pub fn frame_to_time(frame: u64, framerate: (i32, i32)) -> gst::ClockTime {
+    let (num, den) = framerate;
+    if num <= 0 || den <= 0 {
+        return gst::ClockTime::ZERO;
+    }
+    let nanos =
+        frame as u128 * den as u128 * gst::ClockTime::SECOND.nseconds() as u128 / num as u128;
+    gst::ClockTime::from_nseconds(nanos as u64)
+}
+
+/// Decoder for one stinger clip, feeding the vision mixer's stinger appsrc.
+pub struct StingerPlayer {
+    decoder: gst::Pipeline,
+    appsink: gst_app::AppSink,
+    appsrc: gst_app::AppSrc,
+    cut_frame: u64,
+    /// Framerate assumed when the clip's caps don't carry one.
+    fallback_framerate: (i32, i32),
+    /// Framerate negotiated on the decoder output (known after preroll).
+    framerate: Mutex<Option<(i32, i32)>>,
+    /// Fully transparent frame pushed while idle.
+    blank: Arc<Vec<u8>>,
+    /// Set from firing until the decoder is re-armed.
+    running: AtomicBool,
+    /// ID of the run in progress (0 when the clip is not playing).
+    active_run: AtomicU64,
+    next_run: AtomicU64,
+}
+
+impl StingerPlayer {
+    /// Build the decoder pipeline for `clip`, scaled to `size`.
+    pub fn new(
+        clip: &Path,
+        size: (u32, u32),
+        framerate: (i32, i32),
+        cut_frame: u64,
+        appsrc: gst_app::AppSrc,
+    ) -> Result<Arc<Self>, String> {
+        let decoder = gst::Pipeline::with_name("stinger-decoder");
+
+        let convert = make("videoconvert")?;
+        let scale = make("videoscale")?;
+        let capsfilter = gst::ElementFactory::make("capsfilter")
+            .property(
+                "caps",
+                gst::Caps::builder("video/x-raw")
+                    .field("format", "RGBA")
+                    .field("width", size.0 as i32)
+                    .field("height", size.1 as i32)
+                    .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+                    .build(),
+            )
+            .build()
+            .map_err(|e| format!("capsfilter: {}", e))?;
+        let appsink = gst_app::AppSink::builder().sync(true).build();
+
+        decoder
+            .add_many([&convert, &scale, &capsfilter, appsink.upcast_ref()])
+            .map_err(|e| e.to_string())?;
+        gst::Element::link_many([&convert, &scale, &capsfilter, appsink.upcast_ref()])
+            .map_err(|e| e.to_string())?;
+
+        if is_image_sequence(clip) {
+            let src = gst::ElementFactory::make("multifilesrc")
+                .property("location", clip.to_string_lossy().to_string())
+                .property(
+                    "caps",
+                    gst::Caps::builder("image/png")
+                        .field("framerate", gst::Fraction::new(framerate.0, framerate.1))
+                        .build(),
+                )
+                .build()
+                .map_err(|e| format!("multifilesrc: {}", e))?;
+            let pngdec = make("pngdec")?;
+            decoder
+                .add_many([&src, &pngdec])
+                .map_err(|e| e.to_string())?;
+            gst::Element::link_many([&src, &pngdec, &convert]).map_err(|e| e.to_string())?;
+        } else {
+            if !clip.exists() {
+                return Err(format!("Stinger clip not found: {}", clip.display()));
+            }
+            let src = gst::ElementFactory::make("filesrc")
+                .property("location", clip.to_string_lossy().to_string())
+                .build()
+                .map_err(|e| format!("filesrc: {}", e))?;
+            let decodebin = make("decodebin")?;
+            decoder
+                .add_many([&src, &decodebin])
+                .map_err(|e| e.to_string())?;
+            src.link(&decodebin).map_err(|e| e.to_string())?;
+
+            // Only the video stream is used; audio pads stay unlinked
+            let convert_weak = convert.downgrade();
+            decodebin.connect_pad_added(move |_, pad| {
+                let is_video = pad
+                    .current_caps()
+                    .and_then(|caps| caps.structure(0).map(|s| s.name().starts_with("video/")))
+                    .unwrap_or(false);
+                let Some(convert) = convert_weak.upgrade() else {
+                    return;
+                };
+                if let Some(sink) = convert.static_pad("sink") {
+                    if is_video && !sink.is_linked() {
+                        if let Err(e) = pad.link(&sink) {
+                            warn!("Stinger: failed to link decoded video: {:?}", e);
+                        }
+                    }
+                }
+            });
+        }
+
+        let player = Arc::new(Self {
+            decoder,
+            appsink: appsink.clone(),
+            appsrc,
+            cut_frame,
+            fallback_framerate: framerate,
+            framerate: Mutex::new(None),
+            blank: Arc::new(vec![0; (size.0 * size.1 * 4) as usize]),
+            running: AtomicBool::new(false),
+            active_run: AtomicU64::new(0),
+            next_run: AtomicU64::new(1),
+        });
+
+        let weak = Arc::downgrade(&player);
+        appsink.set_callbacks(
+            gst_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                    let Some(player) = weak.upgrade() else {
+                        return Err(gst::FlowError::Flushing);
+                    };
+                    player.push_sample(&sample);
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+
+        // End of clip re-arms the decoder, an error resets it
+        if let Some(bus) = player.decoder.bus() {
+            bus.add_signal_watch();
+            let weak: Weak<Self> = Arc::downgrade(&player);
+            bus.connect_message(None, move |_bus, msg| {
+                let Some(player) = weak.upgrade() else {
+                    return;
+                };
+                match msg.view() {
+                    gst::MessageView::Eos(_) => {
+                        let run = player.active_run.load(Ordering::SeqCst);
+                        player.finish(run, false);
+                    }
+                    gst::MessageView::Error(err) => {
+                        warn!("Stinger decoder error: {} ({:?})", err.error(), err.debug());
+                        let run = player.active_run.load(Ordering::SeqCst);
+                        player.finish(run, true);
+                    }
+                    _ => {}
+                }
+            });
+        }
+
+        Ok(player)
+    }
+
+    /// Push a transparent frame and preroll the decoder so it can fire instantly.
+    pub fn preload(&self) {
+        self.push_blank();
+        match self.decoder.set_state(gst::State::Paused) {
+            Ok(_) => info!("Stinger preloaded"),
+            Err(e) => warn!("Stinger failed to preload: {:?}", e),
+        }
+    }
+
+    /// Whether the stinger clip is currently playing.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+
+    /// Offset from the start of the clip at which the switch underneath happens.
+    pub fn cut_point(&self) -> gst::ClockTime {
+        let framerate = self
+            .framerate
+            .lock()
+            .ok()
+            .and_then(|f| *f)
+            .or_else(|| self.negotiated_framerate())
+            .unwrap_or(self.fallback_framerate);
+        frame_to_time(self.cut_frame, framerate)
+    }
+
+    /// Start playing the clip.
+    pub fn fire(self: &Arc<Self>) -> Result<(), String> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return Err("Stinger is already running".to_string());
+        }
+        let run = self.next_run.fetch_add(1, Ordering::SeqCst);
+        self.active_run.store(run, Ordering::SeqCst);
+        if let Err(e) = self.decoder.set_state(gst::State::Playing) {
+            self.active_run.store(0, Ordering::SeqCst);
+            self.running.store(false, Ordering::SeqCst);
+            return Err(format!("Failed to start stinger: {:?}", e));
+        }
+
+        // Safety net in case neither EOS nor an error arrives
+        let timeout = self
+            .decoder
+            .query_duration::<gst::ClockTime>()
+            .map(|d| Duration::from_nanos(d.nseconds()))
+            .unwrap_or(FALLBACK_CLIP_DURATION)
+            + FINISH_MARGIN;
+        let weak = Arc::downgrade(self);
+        glib::timeout_add_once(timeout, move || {
+            if let Some(player) = weak.upgrade() {
+                if player.active_run.load(Ordering::SeqCst) == run {
+                    warn!("Stinger did not finish within {:?}, resetting", timeout);
+                    player.finish(run, true);
+                }
+            }
+        });
+
+        debug!("Stinger fired (cut point {})", self.cut_point());
+        Ok(())
+    }
+
+    fn negotiated_framerate(&self) -> Option<(i32, i32)> {
+        let caps = self.appsink.static_pad("sink")?.current_caps()?;
+        let fr = caps.structure(0)?.get::<gst::Fraction>("framerate").ok()?;
+        (fr.numer() > 0).then_some((fr.numer(), fr.denom()))
+    }
+
+    fn push_sample(&self, sample: &gst::Sample) {
+        if let Ok(mut fr) = self.framerate.lock() {
+            if fr.is_none() {
+                *fr = sample
+                    .caps()
+                    .and_then(|c| c.structure(0))
+                    .and_then(|s| s.get::<gst::Fraction>("framerate").ok())
+                    .filter(|f| f.numer() > 0)
+                    .map(|f| (f.numer(), f.denom()));
+            }
+        }
+        let Some(buffer) = sample.buffer_owned() else {
+            return;
+        };
+        // Let the live appsrc timestamp the frame on arrival
+        let mut buffer = buffer.copy();
+        {
+            let buffer = buffer.get_mut().expect("copied buffer is writable");
+            buffer.set_pts(gst::ClockTime::NONE);
+            buffer.set_dts(gst::ClockTime::NONE);
+            buffer.set_duration(gst::ClockTime::NONE);
+        }
+        if let Err(e) = self.appsrc.push_buffer(buffer) {
+            debug!("Stinger appsrc rejected frame: {:?}", e);
+        }
+    }
+
+    fn push_blank(&self) {
+        let buffer = gst::Buffer::from_slice(BlankFrame(Arc::clone(&self.blank)));
+        if let Err(e) = self.appsrc.push_buffer(buffer) {
+            debug!("Stinger appsrc rejected blank frame: {:?}", e);
+        }
+    }
+
+    /// Clear the overlay and rewind the decoder for the next run.
+    ///
+    /// Only the first call for a run has an effect. With `reset` the decoder
+    /// goes through READY and prerolls again instead of seeking, which also
+    /// recovers it after an error.
+    fn finish(self: Arc<Self>, run: u64, reset: bool) {
+        if run == 0
+            || self
+                .active_run
+                .compare_exchange(run, 0, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+        {
+            return;
+        }
+        self.push_blank();
+        // State changes must not happen on the streaming thread
+        std::thread::spawn(move || {
+            if reset {
+                let _ = self.decoder.set_state(gst::State::Ready);
+            }
+            let _ = self.decoder.set_state(gst::State::Paused);
+            let _ = self.decoder.state(gst::ClockTime::from_seconds(5));
+            if !reset {
+                if let Err(e) = self.decoder.seek_simple(
+                    gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                    gst::ClockTime::ZERO,
+                ) {
+                    warn!("Stinger failed to rewind: {}", e);
+                }
+            }
+            self.running.store(false, Ordering::SeqCst);
+            debug!("Stinger finished and re-armed");
+        });
+    }
+}
+
+impl Drop for StingerPlayer {
+    fn drop(&mut self) {
+        let _ = self.decoder.set_state(gst::State::Null);
+        if let Some(bus) = self.decoder.bus() {
+            bus.remove_signal_watch();
+        }
+    }
+}
+
+/// Shared frame bytes wrapped for zero-copy `gst::Buffer::from_slice`.
+struct BlankFrame(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for BlankFrame {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+fn make(factory: &str) -> Result<gst::Element, String> {
+    gst::ElementFactory::make(factory)
+        .build()
+        .map_err(|e| format!("{}: {}", factory, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_to_time() {
+        assert_eq!(frame_to_time(0, (25, 1)), gst::ClockTime::ZERO);
+        assert_eq!(frame_to_time(25, (25, 1)), gst::ClockTime::SECOND);
+        assert_eq!(
+            frame_to_time(12, (30000, 1001)),
+            gst::ClockTime::from_nseconds(400_400_000)
+        );
+        assert_eq!(frame_to_time(10, (0, 1)), gst::ClockTime::ZERO);
+    }
+
+    #[test]
+    fn test_resolve_clip_path() {
+        let media = Path::new("/data/media");
+        assert_eq!(
+            resolve_clip_path(media, "media/stingers/logo.mov"),
+            PathBuf::from("/data/media/stingers/logo.mov")
+        );
+        assert_eq!(
+            resolve_clip_path(media, "/abs/sting.webm"),
+            PathBuf::from("/abs/sting.webm")
+        );
+        assert!(is_image_sequence(Path::new("sting_%05d.png")));
+        assert!(!is_image_sequence(Path::new("sting.mov")));
+    }
+}
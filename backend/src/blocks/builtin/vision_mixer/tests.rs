@@ -111,3 +111,146 @@ fn test_parse_initial_pgm_clamped() {
     props.insert("initial_pgm_input".to_string(), PropertyValue::UInt(99));
     assert_eq!(properties::parse_initial_pgm(&props, 4), 3); // max index = 3
 }
+
+#[test]
+fn test_parse_audio_modes_disabled_by_default() {
+    let props = HashMap::new();
+    assert!(properties::parse_audio_modes(&props, 4).is_empty());
+}
+
+#[test]
+fn test_parse_audio_modes() {
+    use super::audio::AudioMode;
+    let mut props = HashMap::new();
+    props.insert("enable_audio".to_string(), PropertyValue::Bool(true));
+    props.insert(
+        "input_1_audio".to_string(),
+        PropertyValue::String("on".to_string()),
+    );
+    props.insert(
+        "input_2_audio".to_string(),
+        PropertyValue::String("bogus".to_string()),
+    );
+    assert_eq!(
+        properties::parse_audio_modes(&props, 3),
+        vec![
+            AudioMode::FollowVideo,
+            AudioMode::AlwaysOn,
+            AudioMode::FollowVideo
+        ]
+    );
+}
+
+#[test]
+fn test_parse_aux_sources() {
+    use strom_types::vision_mixer::AuxSource;
+    let mut props = HashMap::new();
+    props.insert(
+        "num_aux_outputs".to_string(),
+        PropertyValue::String("3".to_string()),
+    );
+    props.insert(
+        "aux_0_source".to_string(),
+        PropertyValue::String("clean".to_string()),
+    );
+    props.insert(
+        "aux_1_source".to_string(),
+        PropertyValue::String("input_7".to_string()),
+    );
+    props.insert(
+        "aux_2_source".to_string(),
+        PropertyValue::String("input_2".to_string()),
+    );
+    let num_aux = properties::parse_num_aux_outputs(&props);
+    assert_eq!(num_aux, 3);
+    assert_eq!(
+        properties::parse_aux_sources(&props, num_aux, 4),
+        vec![
+            AuxSource::CleanFeed,
+            AuxSource::Program, // input 7 out of range
+            AuxSource::Input { input: 2 },
+        ]
+    );
+}
+
+#[test]
+fn test_aux_source_pads() {
+    use super::aux_outputs as aux;
+    use strom_types::vision_mixer::AuxSource;
+    let pvw = [2, 3];
+    assert_eq!(
+        aux::source_pads(AuxSource::Input { input: 1 }, 4, &pvw, false),
+        vec![1]
+    );
+    assert_eq!(
+        aux::source_pads(AuxSource::Preview, 4, &pvw, false),
+        vec![2, 3]
+    );
+    assert_eq!(aux::source_pads(AuxSource::Program, 4, &pvw, true), vec![4]);
+    assert_eq!(
+        aux::source_pads(AuxSource::CleanFeed, 4, &pvw, true),
+        vec![5]
+    );
+    // Without DSK layers clean feed and PGM are the same signal
+    assert_eq!(
+        aux::source_pads(AuxSource::CleanFeed, 4, &pvw, false),
+        vec![4]
+    );
+
+    let sources = [
+        AuxSource::Preview,
+        AuxSource::Input { input: 3 },
+        AuxSource::Program,
+    ];
+    assert_eq!(aux::all_inputs(&sources, &pvw), vec![2, 3]);
+    assert_eq!(aux::outputs_showing(&sources, &pvw, 3), vec![1, 2]);
+    assert!(aux::outputs_showing(&sources, &pvw, 0).is_empty());
+}
+
+#[test]
+fn test_parse_num_upstream_keyers() {
+    let mut props = HashMap::new();
+    assert_eq!(properties::parse_num_upstream_keyers(&props), 0);
+    props.insert(
+        "num_upstream_keyers".to_string(),
+        PropertyValue::String("5".to_string()),
+    );
+    assert_eq!(
+        properties::parse_num_upstream_keyers(&props),
+        strom_types::vision_mixer::MAX_UPSTREAM_KEYERS
+    );
+}
+
+#[test]
+fn test_dist_layers_follow_link_order() {
+    use super::keyers::{dist_layer, usk_pad_index, DistLayer};
+
+    // 3 inputs, 2 DSK on the mixer, 1 USK, stinger last
+    let layers: Vec<DistLayer> = (0..7).map(|idx| dist_layer(idx, 3, 2, 1)).collect();
+    assert_eq!(
+        layers,
+        vec![
+            DistLayer::Input(0),
+            DistLayer::Input(1),
+            DistLayer::Input(2),
+            DistLayer::Dsk(0),
+            DistLayer::Dsk(1),
+            DistLayer::Usk(0),
+            DistLayer::Stinger,
+        ]
+    );
+    assert_eq!(usk_pad_index(3, 2, 0), 5);
+
+    // DSK stage: USK layers follow the inputs directly
+    assert_eq!(dist_layer(2, 2, 0, 2), DistLayer::Usk(0));
+    assert_eq!(dist_layer(4, 2, 0, 2), DistLayer::Stinger);
+    assert_eq!(usk_pad_index(2, 0, 1), 3);
+}
+
+#[test]
+fn test_usk_input_tee_pads_follow_aux_outputs() {
+    use super::keyers::input_tee_pad;
+
+    assert_eq!(input_tee_pad(0, 0), 3);
+    assert_eq!(input_tee_pad(2, 1), 6);
+}
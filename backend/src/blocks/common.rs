@@ -0,0 +1,72 @@
+//! Helpers shared by built-in blocks.
+
+use super::BlockBuildError;
+use std::collections::HashMap;
+use strom_types::scte35::SCTE35_PID_RANGE;
+use strom_types::PropertyValue;
+use tracing::warn;
+
+/// Property value holding a structured value in JSON form.
+pub fn json_property<T: serde::Serialize>(value: &T) -> PropertyValue {
+    PropertyValue::String(serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string()))
+}
+
+/// Parse a JSON string property, falling back to the default when the
+/// property is missing, empty or malformed.
+pub fn parse_json_property<T: serde::de::DeserializeOwned + Default>(
+    properties: &HashMap<String, PropertyValue>,
+    name: &str,
+) -> T {
+    match properties.get(name) {
+        Some(PropertyValue::String(s)) if !s.is_empty() => {
+            serde_json::from_str(s).unwrap_or_else(|e| {
+                warn!("Failed to parse {} JSON: {}", name, e);
+                T::default()
+            })
+        }
+        _ => T::default(),
+    }
+}
+
+/// Read the `scte35_pid` property of an MPEG-TS block.
+///
+/// 0 disables SCTE-35, any other value must be a valid elementary stream
+/// PID.
+pub fn parse_scte35_pid(
+    properties: &HashMap<String, PropertyValue>,
+    default: u32,
+) -> Result<u32, BlockBuildError> {
+    let pid = match properties.get("scte35_pid") {
+        Some(PropertyValue::UInt(u)) => u32::try_from(*u).ok(),
+        Some(PropertyValue::Int(i)) => u32::try_from(*i).ok(),
+        _ => Some(default),
+    };
+    match pid {
+        Some(pid) if pid == 0 || SCTE35_PID_RANGE.contains(&pid) => Ok(pid),
+        _ => Err(BlockBuildError::InvalidProperty(format!(
+            "scte35_pid must be 0 (disabled) or between {:#x} and {:#x}",
+            SCTE35_PID_RANGE.start(),
+            SCTE35_PID_RANGE.end()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pid(value: PropertyValue) -> Result<u32, BlockBuildError> {
+        parse_scte35_pid(&HashMap::from([("scte35_pid".to_string(), value)]), 500)
+    }
+
+    #[test]
+    fn test_parse_scte35_pid() {
+        assert_eq!(parse_scte35_pid(&HashMap::new(), 500).unwrap(), 500);
+        assert_eq!(pid(PropertyValue::UInt(0)).unwrap(), 0);
+        assert_eq!(pid(PropertyValue::Int(0x1FFE)).unwrap(), 0x1FFE);
+        assert!(pid(PropertyValue::Int(-1)).is_err());
+        assert!(pid(PropertyValue::UInt(0x0F)).is_err());
+        assert!(pid(PropertyValue::UInt(0x1FFF)).is_err());
+        assert!(pid(PropertyValue::UInt(u64::MAX)).is_err());
+    }
+}
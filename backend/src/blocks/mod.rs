@@ -2,6 +2,7 @@
 
 pub mod builder;
 pub mod builtin;
+pub mod common;
 pub mod registry;
 pub mod sdp;
 pub mod storage;
@@ -3,17 +3,79 @@
 //! This module uses GStreamer's DeviceMonitor to discover various types of
 //! devices including audio devices, video devices, and network sources like NDI.
 
+use crate::events::EventBroadcaster;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
+use strom_types::StromEvent;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
 pub use strom_types::discovery::{DeviceCategory, DeviceResponse};
 
+/// Device classes of local capture/playback devices.
+///
+/// Not monitored on Windows: enumeration triggers heavy hardware probing
+/// (WASAPI, DirectSound, etc.) that is slow there.
+#[cfg(not(target_os = "windows"))]
+const LOCAL_DEVICE_CLASSES: &[&str] = &["Audio/Source", "Audio/Sink", "Video/Source"];
+#[cfg(target_os = "windows")]
+const LOCAL_DEVICE_CLASSES: &[&str] = &[];
+
+/// List the modes a device advertises as individual caps strings.
+///
+/// Structures with a framerate list are expanded into one entry per
+/// framerate so each entry can be selected on its own.
+pub fn device_caps(device: &gst::Device) -> Vec<String> {
+    let Some(caps) = device.caps() else {
+        return Vec::new();
+    };
+
+    let mut modes: Vec<String> = Vec::new();
+    for structure in caps.iter() {
+        let expanded: Vec<gst::Structure> = match structure.value("framerate") {
+            Ok(value) => match value.get::<gst::List>() {
+                Ok(list) => list
+                    .iter()
+                    .map(|rate| {
+                        let mut s = structure.to_owned();
+                        s.set_value("framerate", rate.clone());
+                        s
+                    })
+                    .collect(),
+                Err(_) => vec![structure.to_owned()],
+            },
+            Err(_) => vec![structure.to_owned()],
+        };
+        for s in expanded {
+            let mode = s.to_string();
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+    }
+    modes
+}
+
+/// Look up a local device by its discovery ID.
+///
+/// Probes the device providers directly rather than going through the running
+/// [`DeviceDiscovery`], so it can be used synchronously from block builders.
+pub fn find_device(id: &str) -> Option<gst::Device> {
+    let monitor = gst::DeviceMonitor::new();
+    for class in LOCAL_DEVICE_CLASSES {
+        monitor.add_filter(Some(class), None);
+    }
+    monitor.devices().into_iter().find(|device| {
+        let device_class = device.device_class().to_string();
+        let provider = DeviceDiscovery::get_provider_name(device, &device_class);
+        DiscoveredDevice::generate_id(&device_class, &provider, &device.display_name()) == id
+    })
+}
+
 /// Discovered device from GStreamer DeviceMonitor.
 #[derive(Debug, Clone)]
 pub struct DiscoveredDevice {
@@ -29,6 +91,8 @@ pub struct DiscoveredDevice {
     pub provider: String,
     /// Additional properties from the device.
     pub properties: HashMap<String, String>,
+    /// Modes advertised by the device (see [`device_caps`]).
+    pub caps: Vec<String>,
     /// When this device was first seen.
     pub first_seen: Instant,
     /// When this device was last seen.
@@ -55,6 +119,7 @@ impl DiscoveredDevice {
             category: self.category,
             provider: self.provider.clone(),
             properties: self.properties.clone(),
+            caps: self.caps.clone(),
             first_seen_secs_ago: self.first_seen.elapsed().as_secs(),
             last_seen_secs_ago: self.last_seen.elapsed().as_secs(),
         }
@@ -77,6 +142,8 @@ pub struct DeviceDiscovery {
     shutdown: Arc<AtomicBool>,
     /// Whether discovery is running.
     running: bool,
+    /// Broadcaster for hot-plug events.
+    events: Option<EventBroadcaster>,
 }
 
 impl DeviceDiscovery {
@@ -87,9 +154,17 @@ impl DeviceDiscovery {
             monitor: None,
             shutdown: Arc::new(AtomicBool::new(false)),
             running: false,
+            events: None,
         }
     }
 
+    /// Create a device discovery service that broadcasts hot-plug events.
+    pub fn with_events(events: EventBroadcaster) -> Self {
+        let mut discovery = Self::new();
+        discovery.events = Some(events);
+        discovery
+    }
+
     /// Check if a specific device provider is available.
     pub fn is_provider_available(provider_name: &str) -> bool {
         let registry = gst::Registry::get();
@@ -120,12 +195,9 @@ impl DeviceDiscovery {
         let monitor = gst::DeviceMonitor::new();
 
         // Add filters for device types we care about.
-        // Audio/Video device enumeration is disabled — it triggers heavy
-        // hardware probing (WASAPI, DirectSound, etc.) that is slow on Windows
-        // and not needed for our use case (network source discovery).
-        // monitor.add_filter(Some("Audio/Source"), None);
-        // monitor.add_filter(Some("Audio/Sink"), None);
-        // monitor.add_filter(Some("Video/Source"), None);
+        for class in LOCAL_DEVICE_CLASSES {
+            monitor.add_filter(Some(class), None);
+        }
         monitor.add_filter(Some("Source/Network"), None);
 
         // Get the bus for device events
@@ -154,8 +226,9 @@ impl DeviceDiscovery {
         // Spawn task to handle device events
         let devices = self.devices.clone();
         let shutdown = self.shutdown.clone();
+        let events = self.events.clone();
         tokio::spawn(async move {
-            Self::run_event_loop(bus, devices, shutdown).await;
+            Self::run_event_loop(bus, devices, shutdown, events).await;
         });
 
         Ok(())
@@ -295,6 +368,7 @@ impl DeviceDiscovery {
             category,
             provider: provider.clone(),
             properties,
+            caps: device_caps(device),
             first_seen: now,
             last_seen: now,
         };
@@ -321,6 +395,7 @@ impl DeviceDiscovery {
     async fn handle_device_removed(
         devices: &Arc<RwLock<HashMap<String, DiscoveredDevice>>>,
         device: &gst::Device,
+        events: Option<&EventBroadcaster>,
     ) {
         let display_name = device.display_name().to_string();
         let device_class = device.device_class().to_string();
@@ -329,8 +404,15 @@ impl DeviceDiscovery {
         let id = DiscoveredDevice::generate_id(&device_class, &provider, &display_name);
 
         let mut devices = devices.write().await;
-        if devices.remove(&id).is_some() {
+        if let Some(removed) = devices.remove(&id) {
             info!("Device removed: {}", display_name);
+            if let Some(events) = events {
+                events.broadcast(StromEvent::DeviceRemoved {
+                    device_id: id,
+                    name: display_name,
+                    category: removed.category,
+                });
+            }
         }
     }
 
@@ -339,6 +421,7 @@ impl DeviceDiscovery {
         bus: gst::Bus,
         devices: Arc<RwLock<HashMap<String, DiscoveredDevice>>>,
         shutdown: Arc<AtomicBool>,
+        events: Option<EventBroadcaster>,
     ) {
         loop {
             // Check if shutdown was requested
@@ -400,6 +483,7 @@ impl DeviceDiscovery {
                         category,
                         provider: provider.clone(),
                         properties,
+                        caps: device_caps(&device),
                         first_seen: now,
                         last_seen: now,
                     };
@@ -412,6 +496,13 @@ impl DeviceDiscovery {
                             "Discovered new device: {} (provider: {})",
                             display_name, provider
                         );
+                        if let Some(events) = &events {
+                            events.broadcast(StromEvent::DeviceAdded {
+                                device_id: id.clone(),
+                                name: display_name.clone(),
+                                category,
+                            });
+                        }
                     }
 
                     devices_guard
@@ -421,7 +512,7 @@ impl DeviceDiscovery {
                 }
                 gst::MessageView::DeviceRemoved(device_removed) => {
                     let device = device_removed.device();
-                    Self::handle_device_removed(&devices, &device).await;
+                    Self::handle_device_removed(&devices, &device, events.as_ref()).await;
                 }
                 _ => {}
             }
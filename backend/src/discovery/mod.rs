@@ -1334,7 +1334,7 @@ impl DiscoveryService {
     async fn start_device_discovery(&self) -> anyhow::Result<()> {
         info!("Starting device discovery");
 
-        let mut device = DeviceDiscovery::new();
+        let mut device = DeviceDiscovery::with_events(self.inner.events.clone());
         device.start().await?;
 
         // Store for later use
@@ -0,0 +1,486 @@
+//! Keyframed animation of numeric element and pad properties.
+//!
+//! The keyframes of an animation become a [`PropertyBinding`] on the
+//! property, so the element applies the values at buffer time. Eased
+//! segments are sampled and interpolated linearly between the samples.
+//! A property already driven by automation or a ramp cannot be animated.
+//!
+//! Keyframe times are offset by the stream time of the animated element
+//! when the animation starts. A timer arms looping animations one pass
+//! ahead and releases the binding of finished ones, leaving the property
+//! at its last value.
+
+use super::transitions::{property_range, PropertyBinding};
+use crate::events::EventBroadcaster;
+use gstreamer as gst;
+use gstreamer::glib;
+use gstreamer::prelude::*;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use strom_types::animation::{
+    AnimationState, Easing, Keyframe, PropertyAnimation, SetPropertyAnimationRequest,
+};
+use strom_types::{vision_mixer, FlowId, StromEvent};
+use tracing::{debug, info};
+
+/// Samples per eased segment, as for transitions.
+const EASING_SAMPLES: usize = vision_mixer::TRANSITION_KEYFRAMES;
+/// Interval at which running animations are checked.
+const POLL_MS: u64 = 50;
+
+/// Global registry of animations on running flows.
+pub static ANIMATION_REGISTRY: LazyLock<AnimationRegistry> = LazyLock::new(AnimationRegistry::new);
+
+/// Errors from animation operations.
+#[derive(Debug, thiserror::Error)]
+pub enum AnimationError {
+    #[error("Pipeline not running for flow: {0}")]
+    NotRunning(FlowId),
+    #[error("Element not found: {0}")]
+    ElementNotFound(String),
+    #[error("Pad not found: {0}")]
+    PadNotFound(String),
+    #[error("Animation not found: {0}")]
+    NotFound(String),
+    #[error("Property {0} cannot be animated: {1}")]
+    NotAnimatable(String, String),
+    #[error("Invalid keyframes: {0}")]
+    InvalidKeyframes(String),
+    #[error("Failed to query stream position")]
+    PositionQueryFailed,
+}
+
+/// Control points of one pass starting at `offset_ns`, clamped to the
+/// property range.
+///
+/// The pass starts at the first keyframe's value so a looping animation
+/// jumps back at each pass boundary.
+pub fn control_points(
+    keyframes: &[Keyframe],
+    offset_ns: u64,
+    range: (f64, f64),
+) -> Vec<(u64, f64)> {
+    let (min, max) = range;
+    let at = |time_ms: u64| offset_ns + time_ms * 1_000_000;
+    let mut points = Vec::new();
+    if let Some(first) = keyframes.first() {
+        points.push((offset_ns, first.value.clamp(min, max)));
+    }
+    for (i, key) in keyframes.iter().enumerate() {
+        points.push((at(key.time_ms), key.value.clamp(min, max)));
+        let Some(next) = keyframes.get(i + 1) else {
+            break;
+        };
+        let (start, end) = (at(key.time_ms), at(next.time_ms));
+        match key.easing {
+            Easing::Linear => {}
+            Easing::Hold => {
+                if end > start + 1 {
+                    points.push((end - 1, key.value.clamp(min, max)));
+                }
+            }
+            easing => {
+                for s in 1..EASING_SAMPLES {
+                    let t = s as f64 / EASING_SAMPLES as f64;
+                    let value = key.value + (next.value - key.value) * easing.apply(t);
+                    let time = start + ((end - start) as f64 * t) as u64;
+                    points.push((time, value.clamp(min, max)));
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Set a numeric property from a control value.
+fn set_numeric(object: &gst::Object, property: &str, value: f64) {
+    let Some(pspec) = object.find_property(property) else {
+        return;
+    };
+    let value = match pspec.value_type() {
+        t if t == f32::static_type() => (value as f32).to_value(),
+        t if t == i32::static_type() => (value.round() as i32).to_value(),
+        t if t == u32::static_type() => (value.round() as u32).to_value(),
+        t if t == i64::static_type() => (value.round() as i64).to_value(),
+        t if t == u64::static_type() => (value.round() as u64).to_value(),
+        _ => value.to_value(),
+    };
+    object.set_property_from_value(property, &value);
+}
+
+struct Run {
+    state: AnimationState,
+    /// Stream time of the start of the first pass.
+    start_ns: u64,
+    /// Passes added to the control source.
+    passes_armed: u64,
+    binding: Option<PropertyBinding>,
+    /// Bumped on every start so timers of earlier runs stop.
+    generation: u64,
+}
+
+/// One animation on a running flow.
+pub struct Animation {
+    id: String,
+    flow_id: FlowId,
+    element_id: String,
+    pad_name: Option<String>,
+    property: String,
+    keyframes: Vec<Keyframe>,
+    looping: bool,
+    range: (f64, f64),
+    target: glib::WeakRef<gst::Object>,
+    /// Element whose stream time the keyframes follow.
+    clock_element: glib::WeakRef<gst::Element>,
+    pipeline: glib::WeakRef<gst::Pipeline>,
+    events: EventBroadcaster,
+    run: Mutex<Run>,
+}
+
+impl Animation {
+    /// Prepare an animation of a property of `element`, or of its pad
+    /// `request.pad_name`.
+    pub fn new(
+        flow_id: FlowId,
+        id: &str,
+        request: &SetPropertyAnimationRequest,
+        element: &gst::Element,
+        pipeline: &gst::Pipeline,
+        events: EventBroadcaster,
+    ) -> Result<Arc<Self>, AnimationError> {
+        let target: gst::Object = match &request.pad_name {
+            Some(pad_name) => element
+                .static_pad(pad_name)
+                .ok_or_else(|| {
+                    AnimationError::PadNotFound(format!("{}:{}", request.element_id, pad_name))
+                })?
+                .upcast(),
+            None => element.clone().upcast(),
+        };
+        let range = property_range(&target, &request.property_name).map_err(|e| {
+            AnimationError::NotAnimatable(request.property_name.clone(), e.to_string())
+        })?;
+
+        let mut keyframes = request.keyframes.clone();
+        keyframes.sort_by_key(|k| k.time_ms);
+        if request.looping && keyframes.last().is_none_or(|k| k.time_ms == 0) {
+            return Err(AnimationError::InvalidKeyframes(
+                "a looping animation needs a keyframe after 0 ms".to_string(),
+            ));
+        }
+
+        Ok(Arc::new(Self {
+            id: id.to_string(),
+            flow_id,
+            element_id: request.element_id.clone(),
+            pad_name: request.pad_name.clone(),
+            property: request.property_name.clone(),
+            keyframes,
+            looping: request.looping,
+            range,
+            target: target.downgrade(),
+            clock_element: element.downgrade(),
+            pipeline: pipeline.downgrade(),
+            events,
+            run: Mutex::new(Run {
+                state: AnimationState::Ready,
+                start_ns: 0,
+                passes_armed: 0,
+                binding: None,
+                generation: 0,
+            }),
+        }))
+    }
+
+    /// Length of one pass in nanoseconds.
+    fn duration_ns(&self) -> u64 {
+        self.keyframes.last().map_or(0, |k| k.time_ms * 1_000_000)
+    }
+
+    /// Current stream time of the animated element.
+    fn position_ns(&self) -> Option<u64> {
+        self.clock_element
+            .upgrade()
+            .and_then(|e| e.query_position::<gst::ClockTime>())
+            .or_else(|| self.pipeline.upgrade()?.query_position::<gst::ClockTime>())
+            .map(|t| t.nseconds())
+    }
+
+    /// Remove the control binding, keeping the current property value.
+    fn unbind(&self, run: &mut Run) {
+        run.binding = None;
+    }
+
+    fn arm_pass(&self, run: &mut Run) {
+        if let Some(binding) = &run.binding {
+            let offset = run.start_ns + run.passes_armed * self.duration_ns();
+            for (time, value) in control_points(&self.keyframes, offset, self.range) {
+                binding.set(gst::ClockTime::from_nseconds(time), value);
+            }
+        }
+        run.passes_armed += 1;
+    }
+
+    fn set_state(&self, run: &mut Run, state: AnimationState) {
+        run.state = state;
+        self.events.broadcast(StromEvent::PropertyAnimationChanged {
+            flow_id: self.flow_id,
+            animation_id: self.id.clone(),
+            element_id: self.element_id.clone(),
+            property_name: self.property.clone(),
+            state,
+        });
+    }
+
+    /// Start, or restart, from the first keyframe.
+    pub fn start(self: &Arc<Self>) -> Result<(), AnimationError> {
+        let target = self
+            .target
+            .upgrade()
+            .ok_or_else(|| AnimationError::ElementNotFound(self.element_id.clone()))?;
+        let start_ns = self
+            .position_ns()
+            .ok_or(AnimationError::PositionQueryFailed)?;
+
+        let mut run = self.run.lock();
+        self.unbind(&mut run);
+        run.start_ns = start_ns;
+        run.passes_armed = 0;
+        run.generation += 1;
+
+        let binding = PropertyBinding::bind(&target, &self.property)
+            .map_err(|e| AnimationError::NotAnimatable(self.property.clone(), e.to_string()))?;
+        run.binding = Some(binding);
+        self.arm_pass(&mut run);
+        if self.looping {
+            self.arm_pass(&mut run);
+        }
+        self.set_state(&mut run, AnimationState::Running);
+
+        let generation = run.generation;
+        let weak = Arc::downgrade(self);
+        glib::timeout_add(
+            std::time::Duration::from_millis(POLL_MS),
+            move || match weak.upgrade() {
+                Some(animation) if animation.poll(generation) => glib::ControlFlow::Continue,
+                _ => glib::ControlFlow::Break,
+            },
+        );
+
+        info!(
+            "Animation {} started on {}.{} ({} keyframes{})",
+            self.id,
+            self.element_id,
+            self.property,
+            self.keyframes.len(),
+            if self.looping { ", looping" } else { "" }
+        );
+        Ok(())
+    }
+
+    /// Advance a running animation. Returns false once the timer of
+    /// `generation` is no longer needed.
+    fn poll(&self, generation: u64) -> bool {
+        let mut run = self.run.lock();
+        if run.generation != generation || run.state != AnimationState::Running {
+            return false;
+        }
+        let Some(now) = self.position_ns() else {
+            return true;
+        };
+        let duration = self.duration_ns();
+
+        if self.looping {
+            // Keep the next pass armed and drop points of finished passes
+            while run.start_ns + (run.passes_armed - 1) * duration <= now {
+                self.arm_pass(&mut run);
+                if let (Some(binding), Some(old)) = (&run.binding, run.passes_armed.checked_sub(4))
+                {
+                    let pass_start = run.start_ns + old * duration;
+                    for (time, _) in control_points(&self.keyframes, pass_start, self.range) {
+                        if time < pass_start + duration {
+                            binding.unset(gst::ClockTime::from_nseconds(time));
+                        }
+                    }
+                }
+            }
+            return true;
+        }
+
+        if now < run.start_ns + duration {
+            return true;
+        }
+        self.unbind(&mut run);
+        if let (Some(target), Some(last)) = (self.target.upgrade(), self.keyframes.last()) {
+            set_numeric(
+                &target,
+                &self.property,
+                last.value.clamp(self.range.0, self.range.1),
+            );
+        }
+        self.set_state(&mut run, AnimationState::Finished);
+        debug!("Animation {} finished", self.id);
+        false
+    }
+
+    /// Stop, leaving the property at its current value.
+    pub fn cancel(&self) {
+        let mut run = self.run.lock();
+        self.unbind(&mut run);
+        if run.state == AnimationState::Running || run.state == AnimationState::Ready {
+            self.set_state(&mut run, AnimationState::Cancelled);
+        }
+        info!("Animation {} cancelled", self.id);
+    }
+
+    /// Whether this animation drives the same property as `other`.
+    fn same_target(&self, other: &Animation) -> bool {
+        self.element_id == other.element_id
+            && self.pad_name == other.pad_name
+            && self.property == other.property
+    }
+
+    /// API view of the animation.
+    pub fn info(&self) -> PropertyAnimation {
+        let run = self.run.lock();
+        let position_ms = (run.state == AnimationState::Running)
+            .then(|| self.position_ns())
+            .flatten()
+            .map(|now| {
+                let elapsed = now.saturating_sub(run.start_ns);
+                match self.duration_ns() {
+                    d if self.looping && d > 0 => elapsed % d,
+                    d => elapsed.min(d),
+                }
+            })
+            .map(|ns| ns / 1_000_000);
+        PropertyAnimation {
+            id: self.id.clone(),
+            element_id: self.element_id.clone(),
+            pad_name: self.pad_name.clone(),
+            property_name: self.property.clone(),
+            keyframes: self.keyframes.clone(),
+            looping: self.looping,
+            state: run.state,
+            position_ms,
+        }
+    }
+}
+
+/// Registry key for looking up animations.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AnimationKey {
+    pub flow_id: FlowId,
+    pub animation_id: String,
+}
+
+/// Global registry of animations, by flow and animation ID.
+pub struct AnimationRegistry {
+    animations: RwLock<HashMap<AnimationKey, Arc<Animation>>>,
+}
+
+impl AnimationRegistry {
+    pub fn new() -> Self {
+        Self {
+            animations: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Add an animation, cancelling the one it replaces and any other
+    /// animation of the same property.
+    pub fn register(&self, key: AnimationKey, animation: Arc<Animation>) {
+        let mut animations = self.animations.write();
+        animations.retain(|k, existing| {
+            let replaced =
+                *k == key || (k.flow_id == key.flow_id && existing.same_target(&animation));
+            if replaced {
+                existing.cancel();
+            }
+            !replaced
+        });
+        animations.insert(key, animation);
+    }
+
+    pub fn get(&self, key: &AnimationKey) -> Option<Arc<Animation>> {
+        self.animations.read().get(key).cloned()
+    }
+
+    /// Cancel and remove an animation.
+    pub fn remove(&self, key: &AnimationKey) -> Option<Arc<Animation>> {
+        let animation = self.animations.write().remove(key)?;
+        animation.cancel();
+        Some(animation)
+    }
+
+    /// Animations of a flow, sorted by ID.
+    pub fn list(&self, flow_id: &FlowId) -> Vec<PropertyAnimation> {
+        let mut animations: Vec<_> = self
+            .animations
+            .read()
+            .iter()
+            .filter(|(k, _)| k.flow_id == *flow_id)
+            .map(|(_, a)| a.info())
+            .collect();
+        animations.sort_by(|a, b| a.id.cmp(&b.id));
+        animations
+    }
+
+    /// Remove all animations of a flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        self.animations.write().retain(|k, _| k.flow_id != *flow_id);
+    }
+}
+
+impl Default for AnimationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kf(time_ms: u64, value: f64, easing: Easing) -> Keyframe {
+        Keyframe {
+            time_ms,
+            value,
+            easing,
+        }
+    }
+
+    #[test]
+    fn test_control_points_linear_and_hold() {
+        let keyframes = [
+            kf(100, 0.5, Easing::Hold),
+            kf(200, 2.0, Easing::Linear),
+            kf(300, 20.0, Easing::Linear),
+        ];
+        let points = control_points(&keyframes, 1_000_000_000, (0.0, 10.0));
+        assert_eq!(
+            points,
+            vec![
+                (1_000_000_000, 0.5),
+                (1_100_000_000, 0.5),
+                (1_199_999_999, 0.5),
+                (1_200_000_000, 2.0),
+                (1_300_000_000, 10.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_control_points_sample_eased_segments() {
+        let keyframes = [kf(0, 0.0, Easing::EaseInOut), kf(1000, 1.0, Easing::Linear)];
+        let points = control_points(&keyframes, 0, (0.0, 1.0));
+        // Pass start, first keyframe, inner samples and the last keyframe
+        assert_eq!(points.len(), EASING_SAMPLES + 2);
+        assert_eq!(points[EASING_SAMPLES / 2 + 1].0, 500_000_000);
+        assert!((points[EASING_SAMPLES / 2 + 1].1 - 0.5).abs() < 1e-9);
+        assert!(points
+            .windows(2)
+            .all(|w| w[0].0 <= w[1].0 && w[0].1 <= w[1].1));
+        assert_eq!(points.last(), Some(&(1_000_000_000, 1.0)));
+    }
+}
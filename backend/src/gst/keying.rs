@@ -0,0 +1,839 @@
+//! Chroma and luma keying for compositor inputs and vision mixer upstream
+//! keyers.
+//!
+//! A key stage makes parts of a picture transparent before it reaches the
+//! mixer:
+//!
+//! - **Chroma key**: pixels whose BT.709 CbCr is within `tolerance` of the key
+//!   colour become transparent, with a `softness` wide edge ramp. `spill`
+//!   removes the key colour cast from what is left.
+//! - **Luma key**: alpha follows brightness around `clip`, `gain` sets the
+//!   steepness of the ramp and `invert` keys out bright instead of dark areas.
+//!
+//! Both backends evaluate the same [`key_pixel`]:
+//!
+//! - **GPU**: a `glshader` element running [`KEY_FRAGMENT_SHADER`].
+//! - **CPU**: the stage is fed packed BGRA ([`cpu_key_caps`]) and a buffer
+//!   probe keys every frame in place.
+//!
+//! The key preview shows the matte of one key stage as an opaque greyscale
+//! picture (white = visible). Its source is picked by an `input-selector`
+//! whose `active-input` property (input index) is handled by
+//! [`update_property`].
+//!
+//! Running key stages are kept in [`KEYER_REGISTRY`] so settings apply live:
+//! as mixer pad properties on the compositor (`key-mode`, `key-color`, ...)
+//! and as element properties of the `key_N` stages.
+
+use super::styling::parse_mixer_pad;
+use super::wipes::parse_hex_color;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use strom_types::{FlowId, PropertyValue};
+use tracing::{debug, warn};
+
+/// Element factory used for the GPU key and matte stages.
+pub const GL_KEY_FACTORY: &str = "glshader";
+
+/// Key property names, as used on the mixer `sink_N` pads and `key_N` stages.
+///
+/// The matching block properties are `<prefix><name>` with `-` replaced by
+/// `_`, e.g. `input_2_key_mode` or `usk_0_key_mode`.
+pub const KEY_PROPERTIES: [&str; 8] = [
+    "key-mode",
+    "key-color",
+    "key-tolerance",
+    "key-softness",
+    "key-spill",
+    "key-clip",
+    "key-gain",
+    "key-invert",
+];
+
+/// Property selecting an `input-selector` input by index.
+pub const SELECT_PROPERTY: &str = "active-input";
+
+/// Element name of key stage `index` (without the block prefix).
+pub fn key_stage_name(index: usize) -> String {
+    format!("key_{}", index)
+}
+
+/// Element name of the key preview selector (without the block prefix).
+pub const KEY_PREVIEW_SELECT: &str = "key_preview_select";
+
+/// Element name of the last key preview stage (without the block prefix).
+pub const KEY_PREVIEW: &str = "key_preview";
+
+/// Keyer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyMode {
+    #[default]
+    None,
+    Chroma,
+    Luma,
+}
+
+impl KeyMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(Self::None),
+            "chroma" => Some(Self::Chroma),
+            "luma" => Some(Self::Luma),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Chroma => "chroma",
+            Self::Luma => "luma",
+        }
+    }
+}
+
+/// Key settings of one input or upstream keyer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeySettings {
+    pub mode: KeyMode,
+    /// Chroma key colour (RGB, 0.0-1.0).
+    pub color: [f32; 3],
+    /// CbCr distance from the key colour that is fully keyed (0.0-1.0).
+    pub tolerance: f32,
+    /// Width of the chroma edge ramp beyond the tolerance (0.0-1.0).
+    pub softness: f32,
+    /// Amount of key colour removed from the kept picture (0.0-1.0).
+    pub spill: f32,
+    /// Luma level at the middle of the luma key ramp (0.0-1.0).
+    pub clip: f32,
+    /// Steepness of the luma key ramp.
+    pub gain: f32,
+    /// Key out bright instead of dark areas.
+    pub invert: bool,
+}
+
+impl Default for KeySettings {
+    fn default() -> Self {
+        Self {
+            mode: KeyMode::None,
+            color: [0.0, 1.0, 0.0],
+            tolerance: 0.15,
+            softness: 0.1,
+            spill: 0.5,
+            clip: 0.5,
+            gain: 4.0,
+            invert: false,
+        }
+    }
+}
+
+fn as_f64(value: &PropertyValue) -> Option<f64> {
+    match value {
+        PropertyValue::Float(f) => Some(*f),
+        PropertyValue::Int(i) => Some(*i as f64),
+        PropertyValue::UInt(u) => Some(*u as f64),
+        PropertyValue::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+impl KeySettings {
+    /// Read the `<prefix>key_*` properties of a block, falling back to
+    /// defaults for missing or invalid values.
+    pub fn from_properties(properties: &HashMap<String, PropertyValue>, prefix: &str) -> Self {
+        let mut settings = Self::default();
+        for name in KEY_PROPERTIES {
+            let key = format!("{}{}", prefix, name.replace('-', "_"));
+            if let Some(value) = properties.get(&key) {
+                if let Err(e) = settings.set(name, value) {
+                    warn!("Ignoring {}: {}", key, e);
+                }
+            }
+        }
+        settings
+    }
+
+    /// Set one key property by its pad-level name.
+    pub fn set(&mut self, property: &str, value: &PropertyValue) -> Result<(), String> {
+        let unit = || {
+            as_f64(value)
+                .filter(|v| (0.0..=1.0).contains(v))
+                .map(|v| v as f32)
+                .ok_or_else(|| format!("{} must be between 0.0 and 1.0", property))
+        };
+        match property {
+            "key-mode" => {
+                self.mode = match value {
+                    PropertyValue::String(s) => KeyMode::parse(s),
+                    _ => None,
+                }
+                .ok_or_else(|| "key-mode must be none, chroma or luma".to_string())?
+            }
+            "key-color" => {
+                self.color = match value {
+                    PropertyValue::String(s) => parse_hex_color(s),
+                    _ => None,
+                }
+                .ok_or_else(|| "key-color must be #RRGGBB".to_string())?
+            }
+            "key-tolerance" => self.tolerance = unit()?,
+            "key-softness" => self.softness = unit()?,
+            "key-spill" => self.spill = unit()?,
+            "key-clip" => self.clip = unit()?,
+            "key-gain" => {
+                self.gain = as_f64(value)
+                    .filter(|v| v.is_finite() && *v >= 0.0)
+                    .ok_or_else(|| "key-gain must be a non-negative number".to_string())?
+                    as f32
+            }
+            "key-invert" => {
+                self.invert = match value {
+                    PropertyValue::Bool(b) => Some(*b),
+                    PropertyValue::String(s) => s.parse::<bool>().ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| "key-invert must be true or false".to_string())?
+            }
+            _ => return Err(format!("Unknown key property '{}'", property)),
+        }
+        Ok(())
+    }
+
+    /// Current value of one key property by its pad-level name.
+    pub fn get(&self, property: &str) -> Option<PropertyValue> {
+        let value = match property {
+            "key-mode" => PropertyValue::String(self.mode.as_str().to_string()),
+            "key-color" => {
+                let [r, g, b] = self.color.map(|c| (c * 255.0).round() as u8);
+                PropertyValue::String(format!("#{:02X}{:02X}{:02X}", r, g, b))
+            }
+            "key-tolerance" => PropertyValue::Float(self.tolerance as f64),
+            "key-softness" => PropertyValue::Float(self.softness as f64),
+            "key-spill" => PropertyValue::Float(self.spill as f64),
+            "key-clip" => PropertyValue::Float(self.clip as f64),
+            "key-gain" => PropertyValue::Float(self.gain as f64),
+            "key-invert" => PropertyValue::Bool(self.invert),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// BT.709 luma and CbCr (each -0.5..0.5) of an RGB colour.
+fn to_ycbcr(rgb: [f32; 3]) -> (f32, [f32; 2]) {
+    let y = 0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2];
+    (y, [(rgb[2] - y) / 1.8556, (rgb[0] - y) / 1.5748])
+}
+
+/// Inverse of [`to_ycbcr`], clamped to 0.0-1.0.
+fn from_ycbcr(y: f32, c: [f32; 2]) -> [f32; 3] {
+    let r = y + 1.5748 * c[1];
+    let b = y + 1.8556 * c[0];
+    let g = (y - 0.2126 * r - 0.0722 * b) / 0.7152;
+    [r, g, b].map(|v| v.clamp(0.0, 1.0))
+}
+
+/// Key one pixel (RGB and alpha, 0.0-1.0).
+pub fn key_pixel(rgb: [f32; 3], alpha: f32, settings: &KeySettings) -> ([f32; 3], f32) {
+    match settings.mode {
+        KeyMode::None => (rgb, alpha),
+        KeyMode::Chroma => {
+            let (y, mut c) = to_ycbcr(rgb);
+            let (_, key) = to_ycbcr(settings.color);
+            let d = (c[0] - key[0]).hypot(c[1] - key[1]);
+            let a = if settings.softness > 0.0 {
+                ((d - settings.tolerance) / settings.softness).clamp(0.0, 1.0)
+            } else if d > settings.tolerance {
+                1.0
+            } else {
+                0.0
+            };
+            let len = key[0].hypot(key[1]);
+            if settings.spill > 0.0 && len > 1e-4 {
+                let u = [key[0] / len, key[1] / len];
+                let p = c[0] * u[0] + c[1] * u[1];
+                if p > 0.0 {
+                    c = [
+                        c[0] - u[0] * p * settings.spill,
+                        c[1] - u[1] * p * settings.spill,
+                    ];
+                }
+            }
+            (from_ycbcr(y, c), alpha * a)
+        }
+        KeyMode::Luma => {
+            let (y, _) = to_ycbcr(rgb);
+            let a = ((y - settings.clip) * settings.gain + 0.5).clamp(0.0, 1.0);
+            let a = if settings.invert { 1.0 - a } else { a };
+            (rgb, alpha * a)
+        }
+    }
+}
+
+/// GLSL fragment shader implementing [`key_pixel`] for the `glshader` element.
+///
+/// `mode` is 0 (none), 1 (chroma) or 2 (luma); `key_cb`/`key_cr` is the
+/// key colour in CbCr.
+pub const KEY_FRAGMENT_SHADER: &str = r#"#version 100
+#ifdef GL_ES
+precision mediump float;
+#endif
+varying vec2 v_texcoord;
+uniform sampler2D tex;
+uniform float mode;
+uniform float key_cb;
+uniform float key_cr;
+uniform float tolerance;
+uniform float softness;
+uniform float spill;
+uniform float clip;
+uniform float gain;
+uniform float invert;
+
+void main () {
+  vec4 c = texture2D(tex, v_texcoord);
+  if (mode < 0.5) {
+    gl_FragColor = c;
+    return;
+  }
+  float y = dot(c.rgb, vec3(0.2126, 0.7152, 0.0722));
+  float a = 1.0;
+  if (mode < 1.5) {
+    vec2 chroma = vec2((c.b - y) / 1.8556, (c.r - y) / 1.5748);
+    vec2 key = vec2(key_cb, key_cr);
+    float d = distance(chroma, key);
+    if (softness > 0.0) {
+      a = clamp((d - tolerance) / softness, 0.0, 1.0);
+    } else {
+      a = d > tolerance ? 1.0 : 0.0;
+    }
+    float len = length(key);
+    if (spill > 0.0 && len > 0.0001) {
+      vec2 u = key / len;
+      float p = dot(chroma, u);
+      if (p > 0.0) {
+        chroma -= u * p * spill;
+      }
+    }
+    float r = y + 1.5748 * chroma.y;
+    float b = y + 1.8556 * chroma.x;
+    float g = (y - 0.2126 * r - 0.0722 * b) / 0.7152;
+    c.rgb = clamp(vec3(r, g, b), 0.0, 1.0);
+  } else {
+    a = clamp((y - clip) * gain + 0.5, 0.0, 1.0);
+    if (invert > 0.5) {
+      a = 1.0 - a;
+    }
+  }
+  gl_FragColor = vec4(c.rgb, c.a * a);
+}
+"#;
+
+/// GLSL fragment shader drawing the alpha of a keyed picture as opaque grey.
+pub const MATTE_FRAGMENT_SHADER: &str = r#"#version 100
+#ifdef GL_ES
+precision mediump float;
+#endif
+varying vec2 v_texcoord;
+uniform sampler2D tex;
+
+void main () {
+  float a = texture2D(tex, v_texcoord).a;
+  gl_FragColor = vec4(a, a, a, 1.0);
+}
+"#;
+
+/// Build the `glshader` uniforms structure for key settings.
+pub fn gl_uniforms(settings: &KeySettings) -> gst::Structure {
+    let (_, key) = to_ycbcr(settings.color);
+    let mode = match settings.mode {
+        KeyMode::None => 0.0f32,
+        KeyMode::Chroma => 1.0,
+        KeyMode::Luma => 2.0,
+    };
+    gst::Structure::builder("uniforms")
+        .field("mode", mode)
+        .field("key_cb", key[0])
+        .field("key_cr", key[1])
+        .field("tolerance", settings.tolerance)
+        .field("softness", settings.softness)
+        .field("spill", settings.spill)
+        .field("clip", settings.clip)
+        .field("gain", settings.gain)
+        .field("invert", if settings.invert { 1.0f32 } else { 0.0 })
+        .build()
+}
+
+/// Create the GPU key stage.
+pub fn make_gl_key(
+    name: &str,
+    settings: &KeySettings,
+) -> Result<gst::Element, gst::glib::BoolError> {
+    gst::ElementFactory::make(GL_KEY_FACTORY)
+        .name(name)
+        .property("fragment", KEY_FRAGMENT_SHADER)
+        .property("uniforms", gl_uniforms(settings))
+        .build()
+}
+
+/// Create the GPU key preview matte stage.
+pub fn make_gl_matte(name: &str) -> Result<gst::Element, gst::glib::BoolError> {
+    gst::ElementFactory::make(GL_KEY_FACTORY)
+        .name(name)
+        .property("fragment", MATTE_FRAGMENT_SHADER)
+        .build()
+}
+
+/// Caps forced in front of a CPU key stage or key preview so the probes can
+/// work on the pixels.
+pub fn cpu_key_caps() -> gst::Caps {
+    gst::Caps::builder("video/x-raw")
+        .field("format", "BGRA")
+        .build()
+}
+
+/// Key one BGRA frame in place.
+fn apply_cpu(data: &mut [u8], settings: &KeySettings) {
+    for px in data.chunks_exact_mut(4) {
+        let rgb = [px[2], px[1], px[0]].map(|c| c as f32 / 255.0);
+        let (rgb, alpha) = key_pixel(rgb, px[3] as f32 / 255.0, settings);
+        let out = [rgb[2], rgb[1], rgb[0], alpha].map(|c| (c * 255.0).round() as u8);
+        px.copy_from_slice(&out);
+    }
+}
+
+/// Replace one BGRA frame by its matte in place.
+fn apply_cpu_matte(data: &mut [u8]) {
+    for px in data.chunks_exact_mut(4) {
+        let a = px[3];
+        px.copy_from_slice(&[a, a, a, 255]);
+    }
+}
+
+/// Map a probed buffer writable and run `f` on its bytes.
+fn probe_frame(info: &mut gst::PadProbeInfo, what: &str, f: impl FnOnce(&mut [u8])) {
+    if let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data {
+        let buffer = buffer.make_mut();
+        match buffer.map_writable() {
+            Ok(mut map) => f(map.as_mut_slice()),
+            Err(_) => warn!("{} probe could not map buffer writable", what),
+        }
+    }
+}
+
+/// Add the CPU key probe to `pad`, which must carry [`cpu_key_caps`].
+pub fn add_cpu_probe(pad: &gst::Pad, settings: Arc<Mutex<KeySettings>>) -> Option<gst::PadProbeId> {
+    pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+        let settings = *settings.lock();
+        if settings.mode != KeyMode::None {
+            probe_frame(info, "Key", |data| apply_cpu(data, &settings));
+        }
+        gst::PadProbeReturn::Ok
+    })
+}
+
+/// Add the CPU key preview probe to `pad`, which must carry [`cpu_key_caps`].
+pub fn add_cpu_matte_probe(pad: &gst::Pad) -> Option<gst::PadProbeId> {
+    pad.add_probe(gst::PadProbeType::BUFFER, |_pad, info| {
+        probe_frame(info, "Key preview", apply_cpu_matte);
+        gst::PadProbeReturn::Ok
+    })
+}
+
+/// Create an `input-selector` that drops the buffers of inactive inputs
+/// instead of holding them back.
+pub fn make_selector(name: &str) -> Result<gst::Element, gst::glib::BoolError> {
+    gst::ElementFactory::make("input-selector")
+        .name(name)
+        .property("sync-streams", false)
+        .build()
+}
+
+/// Create the key preview stages that follow the selector of `block_id`, in
+/// link order. The last one is always the `key_preview` capsfilter.
+///
+/// GPU: matte shader, `gldownload` unless `gl_output` and a capsfilter.
+/// CPU: `videoconvert` and a BGRA capsfilter carrying the matte probe.
+pub fn make_key_preview_stages(
+    block_id: &str,
+    gl: bool,
+    gl_output: bool,
+) -> Result<Vec<(String, gst::Element)>, gst::glib::BoolError> {
+    let id = |name: &str| format!("{}:{}", block_id, name);
+    let mut stages = Vec::new();
+    let caps = if gl {
+        let matte_id = id("key_matte");
+        stages.push((matte_id.clone(), make_gl_matte(&matte_id)?));
+        if gl_output {
+            gst::Caps::builder("video/x-raw")
+                .features(["memory:GLMemory"])
+                .build()
+        } else {
+            let download_id = id("key_preview_download");
+            let download = gst::ElementFactory::make("gldownload")
+                .name(&download_id)
+                .build()?;
+            stages.push((download_id, download));
+            gst::Caps::new_empty_simple("video/x-raw")
+        }
+    } else {
+        let convert_id = id("key_preview_convert");
+        let convert = gst::ElementFactory::make("videoconvert")
+            .name(&convert_id)
+            .build()?;
+        stages.push((convert_id, convert));
+        cpu_key_caps()
+    };
+    let preview_id = id(KEY_PREVIEW);
+    let preview = gst::ElementFactory::make("capsfilter")
+        .name(&preview_id)
+        .property("caps", caps)
+        .build()?;
+    if !gl {
+        if let Some(pad) = preview.static_pad("src") {
+            add_cpu_matte_probe(&pad);
+        }
+    }
+    stages.push((preview_id, preview));
+    Ok(stages)
+}
+
+/// Select input `index` (`sink_{index}`) of an `input-selector`.
+pub fn select_input(selector: &gst::Element, index: usize) -> Result<(), String> {
+    let pad_name = format!("sink_{}", index);
+    let pad = selector
+        .static_pad(&pad_name)
+        .ok_or_else(|| format!("{} has no input {}", selector.name(), index))?;
+    selector.set_property("active-pad", &pad);
+    Ok(())
+}
+
+/// A running key stage.
+pub struct Keyer {
+    settings: Arc<Mutex<KeySettings>>,
+    /// GPU: key shader.
+    shader: Option<gst::Element>,
+}
+
+impl Keyer {
+    pub fn new(settings: Arc<Mutex<KeySettings>>, shader: Option<gst::Element>) -> Self {
+        Self { settings, shader }
+    }
+
+    pub fn settings(&self) -> KeySettings {
+        *self.settings.lock()
+    }
+
+    fn update(&self, property: &str, value: &PropertyValue) -> Result<(), String> {
+        let settings = {
+            let mut settings = self.settings.lock();
+            settings.set(property, value)?;
+            *settings
+        };
+        if let Some(shader) = &self.shader {
+            shader.set_property("uniforms", gl_uniforms(&settings));
+        }
+        debug!("Key updated: {} = {:?}", property, value);
+        Ok(())
+    }
+}
+
+/// Registry key for looking up key stages.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct KeyerKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// Global registry for the key stages of running blocks, by stage index.
+pub struct KeyerRegistry {
+    blocks: RwLock<HashMap<KeyerKey, Arc<Vec<Keyer>>>>,
+}
+
+impl KeyerRegistry {
+    pub fn new() -> Self {
+        Self {
+            blocks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, key: KeyerKey, keyers: Vec<Keyer>) {
+        self.blocks.write().insert(key, Arc::new(keyers));
+    }
+
+    pub fn get(&self, key: &KeyerKey) -> Option<Arc<Vec<Keyer>>> {
+        self.blocks.read().get(key).cloned()
+    }
+
+    /// Remove all key entries for a given flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        self.blocks.write().retain(|k, _| k.flow_id != *flow_id);
+    }
+}
+
+impl Default for KeyerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global key stage registry.
+pub static KEYER_REGISTRY: LazyLock<KeyerRegistry> = LazyLock::new(KeyerRegistry::new);
+
+/// Register the key stages of a running block, using the `_flow_id` injected
+/// into its properties.
+pub fn register_keyers(
+    block_id: &str,
+    properties: &HashMap<String, PropertyValue>,
+    keyers: Vec<Keyer>,
+) {
+    match properties.get("_flow_id").and_then(|v| match v {
+        PropertyValue::String(s) => s.parse::<FlowId>().ok(),
+        _ => None,
+    }) {
+        Some(flow_id) => KEYER_REGISTRY.register(
+            KeyerKey {
+                flow_id,
+                block_id: block_id.to_string(),
+            },
+            keyers,
+        ),
+        None => warn!(
+            "Block {} has no flow ID, key settings are not live",
+            block_id
+        ),
+    }
+}
+
+fn keyers(flow_id: &FlowId, block_id: &str) -> Option<Arc<Vec<Keyer>>> {
+    KEYER_REGISTRY.get(&KeyerKey {
+        flow_id: *flow_id,
+        block_id: block_id.to_string(),
+    })
+}
+
+fn update_keyer(
+    flow_id: &FlowId,
+    block_id: &str,
+    index: usize,
+    property_name: &str,
+    value: &PropertyValue,
+) -> Result<(), String> {
+    let Some(keyers) = keyers(flow_id, block_id) else {
+        return Err(format!("Keying is not enabled on block {}", block_id));
+    };
+    match keyers.get(index) {
+        Some(keyer) => keyer.update(property_name, value),
+        None => Err(format!("Block {} has no key stage {}", block_id, index)),
+    }
+}
+
+/// Split `{block}:key_N` into block ID and key stage index.
+fn parse_key_stage(element_id: &str) -> Option<(&str, usize)> {
+    let (block_id, name) = element_id.rsplit_once(':')?;
+    let index = name.strip_prefix("key_")?.parse::<usize>().ok()?;
+    Some((block_id, index))
+}
+
+/// Apply a live key property change on a compositor mixer pad.
+///
+/// Returns `None` if `property_name` is not a key property of a mixer
+/// `sink_N` pad, so the caller can fall through to the regular pad property
+/// path.
+pub fn update_pad_property(
+    flow_id: &FlowId,
+    element_id: &str,
+    pad_name: &str,
+    property_name: &str,
+    value: &PropertyValue,
+) -> Option<Result<(), String>> {
+    if !KEY_PROPERTIES.contains(&property_name) {
+        return None;
+    }
+    let (block_id, index) = parse_mixer_pad(element_id, pad_name)?;
+    Some(update_keyer(flow_id, block_id, index, property_name, value))
+}
+
+/// Apply a live key property change on a `key_N` stage, or select an
+/// `input-selector` input through [`SELECT_PROPERTY`].
+///
+/// Returns `None` for any other element property, so the caller can fall
+/// through to the regular property path.
+pub fn update_property(
+    flow_id: &FlowId,
+    element_id: &str,
+    element: &gst::Element,
+    property_name: &str,
+    value: &PropertyValue,
+) -> Option<Result<(), String>> {
+    if property_name == SELECT_PROPERTY {
+        if element.factory().map(|f| f.name()).as_deref() != Some("input-selector") {
+            return None;
+        }
+        return Some(
+            as_f64(value)
+                .filter(|v| *v >= 0.0)
+                .ok_or_else(|| format!("{} must be an input index", SELECT_PROPERTY))
+                .and_then(|index| select_input(element, index as usize)),
+        );
+    }
+    if !KEY_PROPERTIES.contains(&property_name) {
+        return None;
+    }
+    let (block_id, index) = parse_key_stage(element_id)?;
+    Some(update_keyer(flow_id, block_id, index, property_name, value))
+}
+
+/// Current key properties of a compositor mixer pad, if keying is enabled.
+pub fn pad_properties(
+    flow_id: &FlowId,
+    element_id: &str,
+    pad_name: &str,
+) -> Vec<(String, PropertyValue)> {
+    let Some((block_id, index)) = parse_mixer_pad(element_id, pad_name) else {
+        return Vec::new();
+    };
+    let Some(settings) =
+        keyers(flow_id, block_id).and_then(|keyers| keyers.get(index).map(Keyer::settings))
+    else {
+        return Vec::new();
+    };
+    KEY_PROPERTIES
+        .iter()
+        .filter_map(|name| Some((name.to_string(), settings.get(name)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chroma() -> KeySettings {
+        KeySettings {
+            mode: KeyMode::Chroma,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_settings_from_properties() {
+        let mut props = HashMap::new();
+        props.insert(
+            "input_1_key_mode".to_string(),
+            PropertyValue::String("luma".to_string()),
+        );
+        props.insert(
+            "input_1_key_color".to_string(),
+            PropertyValue::String("#0000ff".to_string()),
+        );
+        props.insert("input_1_key_invert".to_string(), PropertyValue::Bool(true));
+        // Invalid values fall back to defaults
+        props.insert(
+            "input_1_key_tolerance".to_string(),
+            PropertyValue::Float(2.0),
+        );
+
+        let settings = KeySettings::from_properties(&props, "input_1_");
+        assert_eq!(settings.mode, KeyMode::Luma);
+        assert_eq!(settings.color, [0.0, 0.0, 1.0]);
+        assert!(settings.invert);
+        assert_eq!(settings.tolerance, KeySettings::default().tolerance);
+        assert_eq!(
+            KeySettings::from_properties(&props, "usk_0_"),
+            KeySettings::default()
+        );
+    }
+
+    #[test]
+    fn test_settings_set_and_get_round_trip() {
+        let mut settings = KeySettings::default();
+        assert!(settings
+            .set("key-mode", &PropertyValue::String("alpha".to_string()))
+            .is_err());
+        assert!(settings
+            .set("key-spill", &PropertyValue::Float(-0.1))
+            .is_err());
+        assert!(settings.set("key-gain", &PropertyValue::Int(-1)).is_err());
+        for name in KEY_PROPERTIES {
+            let value = settings.get(name).unwrap();
+            settings.set(name, &value).unwrap();
+        }
+        assert_eq!(settings, KeySettings::default());
+    }
+
+    #[test]
+    fn test_chroma_key_removes_key_colour() {
+        let settings = chroma();
+        // Pure green is keyed, other colours are kept
+        assert_eq!(key_pixel([0.0, 1.0, 0.0], 1.0, &settings).1, 0.0);
+        assert_eq!(key_pixel([1.0, 0.0, 0.0], 1.0, &settings).1, 1.0);
+        assert_eq!(key_pixel([0.5, 0.5, 0.5], 1.0, &settings).1, 1.0);
+        // Source alpha is kept on opaque key results
+        assert_eq!(key_pixel([1.0, 0.0, 0.0], 0.5, &settings).1, 0.5);
+        // Nothing happens without a key mode
+        let off = KeySettings::default();
+        assert_eq!(
+            key_pixel([0.0, 1.0, 0.0], 1.0, &off),
+            ([0.0, 1.0, 0.0], 1.0)
+        );
+    }
+
+    #[test]
+    fn test_spill_suppression_reduces_key_cast() {
+        let greenish = [0.5, 0.6, 0.5];
+        let no_spill = KeySettings {
+            spill: 0.0,
+            ..chroma()
+        };
+        let (kept, _) = key_pixel(greenish, 1.0, &no_spill);
+        let (suppressed, alpha) = key_pixel(greenish, 1.0, &chroma());
+        assert_eq!(alpha, 1.0);
+        assert!((kept[1] - 0.6).abs() < 1e-3);
+        assert!(suppressed[1] < kept[1]);
+        // Luma is preserved
+        let luma = |c: [f32; 3]| 0.2126 * c[0] + 0.7152 * c[1] + 0.0722 * c[2];
+        assert!((luma(suppressed) - luma(kept)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_luma_key_clip_gain_invert() {
+        let settings = KeySettings {
+            mode: KeyMode::Luma,
+            clip: 0.5,
+            gain: 4.0,
+            ..Default::default()
+        };
+        assert_eq!(key_pixel([0.0; 3], 1.0, &settings).1, 0.0);
+        assert_eq!(key_pixel([1.0; 3], 1.0, &settings).1, 1.0);
+        assert!((key_pixel([0.5; 3], 1.0, &settings).1 - 0.5).abs() < 1e-3);
+        let inverted = KeySettings {
+            invert: true,
+            ..settings
+        };
+        assert_eq!(key_pixel([0.0; 3], 1.0, &inverted).1, 1.0);
+        assert_eq!(key_pixel([1.0; 3], 1.0, &inverted).1, 0.0);
+    }
+
+    #[test]
+    fn test_apply_cpu_and_matte() {
+        // BGRA: one green pixel, one red pixel
+        let mut frame = vec![0u8, 255, 0, 255, 0, 0, 255, 255];
+        apply_cpu(&mut frame, &chroma());
+        assert_eq!(frame[3], 0);
+        assert_eq!(frame[7], 255);
+        apply_cpu_matte(&mut frame);
+        assert_eq!(frame, vec![0, 0, 0, 255, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_parse_stage_names() {
+        assert_eq!(parse_key_stage("vm1:key_2"), Some(("vm1", 2)));
+        assert_eq!(parse_key_stage("vm1:key_preview"), None);
+        assert_eq!(parse_key_stage("vm1:mixer"), None);
+        assert_eq!(parse_mixer_pad("comp:mixer", "sink_3"), Some(("comp", 3)));
+        assert_eq!(parse_mixer_pad("comp:mixer", "src"), None);
+    }
+}
@@ -1,16 +1,21 @@
 //! GStreamer integration.
 
+pub mod animation;
 mod block_expansion;
 pub mod buffer_age_probe;
 pub mod discovery;
+pub mod keying;
 pub mod pipeline;
 pub mod pipeline_monitor;
+pub mod scte35;
+pub mod styling;
 pub mod thread_priority;
 pub mod thumbnail;
 pub mod thumbnail_tap;
 pub mod transitions;
 pub mod video_frame;
 pub mod whep_probe;
+pub mod wipes;
 
 pub use discovery::ElementDiscovery;
 pub use pipeline::{PipelineError, PipelineManager};
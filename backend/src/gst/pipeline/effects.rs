@@ -1,7 +1,10 @@
 use super::{PipelineError, PipelineManager};
+use crate::blocks::builtin::vision_mixer::keyers::{self, DistLayer};
+use crate::blocks::builtin::vision_mixer::{aux_outputs, stinger};
 use gstreamer as gst;
 use gstreamer::prelude::*;
-use tracing::{debug, info};
+use strom_types::vision_mixer::AuxSource;
+use tracing::{debug, info, warn};
 
 impl PipelineManager {
     /// Get the distribution compositor canvas size from its capsfilter.
@@ -25,6 +28,15 @@ impl PipelineManager {
             .unwrap_or(default)
     }
 
+    /// Get the wipe edge/border settings configured on a block.
+    fn wipe_settings(&self, block_instance_id: &str) -> crate::gst::wipes::WipeSettings {
+        self.blocks
+            .iter()
+            .find(|b| b.id == block_instance_id)
+            .map(|b| crate::gst::wipes::WipeSettings::from_properties(&b.properties))
+            .unwrap_or_default()
+    }
+
     /// Trigger a transition on a compositor/mixer block.
     ///
     /// Uses the server's authoritative PGM/PVW groups from overlay state.
@@ -70,6 +82,13 @@ impl PipelineManager {
             .map(|s| s.pvw_group())
             .unwrap_or_else(|| vec![to_input]);
 
+        // A running stinger owns the pads until its cut point has passed
+        if stinger::get_stinger(block_instance_id).is_some_and(|p| p.is_running()) {
+            return Err(PipelineError::TransitionError(
+                "Stinger transition in progress".to_string(),
+            ));
+        }
+
         // Auto-cancel FTB if active
         let was_ftb = overlay_state
             .as_ref()
@@ -142,10 +161,12 @@ impl PipelineManager {
                             pad.set_property("height", canvas_height);
                         }
                     } else if let Some(state) = overlay_state.as_ref() {
-                        let dsk_idx = idx - num_video_inputs;
-                        let enabled = dsk_idx < state.dsk_enabled.len()
-                            && state.dsk_enabled[dsk_idx]
-                                .load(std::sync::atomic::Ordering::Relaxed);
+                        let enabled = match state.dist_layer(idx) {
+                            DistLayer::Dsk(dsk_idx) => state.dsk_is_enabled(dsk_idx),
+                            DistLayer::Usk(usk_idx) => state.usk_is_enabled(usk_idx),
+                            // Stinger pad: always visible, transparent while idle
+                            DistLayer::Stinger | DistLayer::Input(_) => true,
+                        };
                         let alpha = if enabled { 1.0f64 } else { 0.0f64 };
                         pad.set_property("alpha", alpha);
                     }
@@ -164,42 +185,32 @@ impl PipelineManager {
 
         // Check if this is a single-to-single transition (use existing optimized path)
         let single_to_single = old_pgm_group.len() == 1 && new_pgm_group.len() == 1;
+        let mut audio_duration_ms = duration_ms;
 
-        if single_to_single {
+        if trans_type == TransitionType::Stinger {
+            let cut_point = self.trigger_stinger(
+                block_instance_id,
+                mixer,
+                &old_pgm_group,
+                &new_pgm_group,
+                canvas_width,
+                canvas_height,
+            )?;
+            // Audio dissolves while the stinger covers the picture
+            audio_duration_ms = cut_point.mseconds();
+        } else if single_to_single {
             let from = old_pgm_group[0];
             let to = new_pgm_group[0];
-            let controller = TransitionController::new(mixer.clone(), canvas_width, canvas_height);
+            let controller = TransitionController::new(mixer.clone(), canvas_width, canvas_height)
+                .with_wipe_settings(self.wipe_settings(block_instance_id));
             controller
                 .transition(from, to, trans_type, duration_ms, &self.pipeline)
                 .map_err(|e| PipelineError::TransitionError(e.to_string()))?;
         } else {
             // Group transition: position incoming pads at target sub-rects, then cross-fade
-            let new_rects = strom_types::vision_mixer::compute_group_rects(
-                0,
-                0,
-                canvas_width,
-                canvas_height,
-                new_pgm_group.len(),
-            );
+            position_incoming_group(mixer, &new_pgm_group, canvas_width, canvas_height);
 
-            // Set incoming pad positions (invisible at alpha=0, above background)
-            for (slot, &idx) in new_pgm_group.iter().enumerate() {
-                if let Some(pad) = mixer.static_pad(&format!("sink_{}", idx)) {
-                    let (x, y, w, h) =
-                        new_rects
-                            .get(slot)
-                            .copied()
-                            .unwrap_or((0, 0, canvas_width, canvas_height));
-                    pad.set_property("xpos", x);
-                    pad.set_property("ypos", y);
-                    pad.set_property("width", w);
-                    pad.set_property("height", h);
-                    pad.set_property("alpha", 0.0f64);
-                    pad.set_property("zorder", strom_types::vision_mixer::DIST_PGM_ZORDER);
-                }
-            }
-
-            // For group transitions, always use fade (slides don't make sense)
+            // For group transitions, always use fade (slides and wipes don't make sense)
             let effective_type = if matches!(trans_type, TransitionType::Cut) {
                 TransitionType::Cut
             } else {
@@ -228,9 +239,114 @@ impl PipelineManager {
             }
         }
 
+        // Audio follows video: ramp the PGM audio in step with the video transition
+        let audio_mixer_id = format!(
+            "{}:{}",
+            block_instance_id,
+            crate::blocks::builtin::vision_mixer::audio::AUDIO_MIXER_ELEMENT
+        );
+        if let (Some(audio_mixer), Some(state)) =
+            (self.elements.get(&audio_mixer_id), overlay_state.as_ref())
+        {
+            crate::blocks::builtin::vision_mixer::audio::follow_transition(
+                audio_mixer,
+                &state.audio_modes,
+                &old_pgm_group,
+                &new_pgm_group,
+                // Group transitions always fade (see above)
+                if single_to_single
+                    || matches!(trans_type, TransitionType::Cut | TransitionType::Stinger)
+                {
+                    trans_type
+                } else {
+                    TransitionType::Fade
+                },
+                audio_duration_ms,
+            );
+        }
+
         Ok((was_ftb, old_pgm_group, new_pgm_group))
     }
 
+    /// Fire the stinger clip and schedule the cut underneath at its cut point.
+    ///
+    /// The outgoing and incoming PGM pads get step control bindings that swap
+    /// alpha at `now + cut_point`, so the switch lands on the configured frame
+    /// without a timer. Returns the cut-point offset.
+    fn trigger_stinger(
+        &self,
+        block_instance_id: &str,
+        mixer: &gst::Element,
+        old_pgm_group: &[usize],
+        new_pgm_group: &[usize],
+        canvas_width: i32,
+        canvas_height: i32,
+    ) -> Result<gst::ClockTime, PipelineError> {
+        use gstreamer_controller::prelude::*;
+        use gstreamer_controller::{
+            DirectControlBinding, InterpolationControlSource, InterpolationMode,
+        };
+
+        let player = stinger::get_stinger(block_instance_id).ok_or_else(|| {
+            PipelineError::TransitionError(format!(
+                "No stinger clip configured on {}",
+                block_instance_id
+            ))
+        })?;
+
+        position_incoming_group(mixer, new_pgm_group, canvas_width, canvas_height);
+
+        let now = mixer.query_position::<gst::ClockTime>().ok_or_else(|| {
+            PipelineError::TransitionError("Failed to query mixer position".to_string())
+        })?;
+        let cut_point = player.cut_point();
+        let cut_time = now + cut_point;
+
+        // Fire before scheduling the cut, so a clip that fails to start
+        // leaves no step bindings behind
+        player.fire().map_err(PipelineError::TransitionError)?;
+
+        let mut affected: Vec<usize> = old_pgm_group.to_vec();
+        affected.extend(new_pgm_group.iter().filter(|i| !old_pgm_group.contains(i)));
+
+        for idx in affected {
+            let before = if old_pgm_group.contains(&idx) {
+                1.0
+            } else {
+                0.0
+            };
+            let after = if new_pgm_group.contains(&idx) {
+                1.0
+            } else {
+                0.0
+            };
+            let Some(pad) = find_pad(mixer, &format!("sink_{}", idx)) else {
+                continue;
+            };
+            if let Some(binding) = pad.control_binding("alpha") {
+                pad.remove_control_binding(&binding);
+            }
+            let cs = InterpolationControlSource::new();
+            cs.set_mode(InterpolationMode::None);
+            cs.set(now, before);
+            cs.set(cut_time, after);
+            let binding = DirectControlBinding::new(&pad, "alpha", &cs);
+            pad.add_control_binding(&binding).map_err(|e| {
+                PipelineError::TransitionError(format!("Failed to add control binding: {}", e))
+            })?;
+        }
+
+        info!(
+            "Stinger transition on {}: {:?} -> {:?}, cut at +{}ms",
+            block_instance_id,
+            old_pgm_group,
+            new_pgm_group,
+            cut_point.mseconds()
+        );
+
+        Ok(cut_point)
+    }
+
     /// Animate a single input's position/size on a compositor block.
     #[allow(clippy::too_many_arguments)]
     pub fn animate_input(
@@ -282,6 +398,27 @@ impl PipelineManager {
         Ok(())
     }
 
+    /// Move the inputs of a compositor block to a new layout.
+    pub fn animate_layout(
+        &self,
+        block_instance_id: &str,
+        layout: &[strom_types::compositor::InputLayout],
+        duration_ms: u64,
+    ) -> Result<(), PipelineError> {
+        use crate::gst::transitions::TransitionController;
+
+        let mixer_id = format!("{}:mixer", block_instance_id);
+        let mixer = self
+            .elements
+            .get(&mixer_id)
+            .ok_or_else(|| PipelineError::ElementNotFound(mixer_id.clone()))?;
+
+        let (canvas_width, canvas_height) = self.dist_canvas_size(block_instance_id);
+        TransitionController::new(mixer.clone(), canvas_width, canvas_height)
+            .animate_layout(layout, duration_ms, &self.pipeline)
+            .map_err(|e| PipelineError::TransitionError(e.to_string()))
+    }
+
     /// Reset accumulated loudness measurements on an EBU R128 meter block.
     pub fn reset_loudness(&self, block_instance_id: &str) -> Result<(), PipelineError> {
         let element_id = format!("{}:ebur128level", block_instance_id);
@@ -315,6 +452,66 @@ impl PipelineManager {
         Ok(())
     }
 
+    /// Inject an SCTE-35 cue into the MPEG-TS muxer of a block.
+    ///
+    /// Works for any block with an `mpegtsmux` inside (MPEG-TS/SRT output,
+    /// recorder in TS mode, including muxers nested in splitmuxsink) and for
+    /// standalone `mpegtsmux` elements. The muxer must have a non-zero
+    /// `scte-35-pid`.
+    pub fn insert_scte35(
+        &self,
+        block_instance_id: &str,
+        cue: &strom_types::scte35::Scte35Cue,
+    ) -> Result<(), PipelineError> {
+        let prefix = format!("{}:", block_instance_id);
+        let mux = self
+            .pipeline
+            .iterate_recurse()
+            .into_iter()
+            .flatten()
+            .find(|e| {
+                let name = e.name();
+                (name == block_instance_id || name.starts_with(&prefix))
+                    && e.factory().is_some_and(|f| f.name() == "mpegtsmux")
+            })
+            .ok_or_else(|| {
+                PipelineError::ElementNotFound(format!(
+                    "{} (block has no MPEG-TS muxer)",
+                    block_instance_id
+                ))
+            })?;
+
+        let pid = if mux.has_property("scte-35-pid") {
+            mux.property::<u32>("scte-35-pid")
+        } else {
+            0
+        };
+        if pid == 0 {
+            return Err(PipelineError::InvalidProperty {
+                element: mux.name().to_string(),
+                property: "scte-35-pid".to_string(),
+                reason: "SCTE-35 is not enabled on this muxer".to_string(),
+            });
+        }
+
+        crate::gst::scte35::send_cue(&mux, pid as u16, cue).map_err(|reason| {
+            PipelineError::InvalidProperty {
+                element: mux.name().to_string(),
+                property: "scte-35".to_string(),
+                reason,
+            }
+        })?;
+
+        info!(
+            "Inserted SCTE-35 {} on {} (pid {}, pts {:?})",
+            cue.command,
+            mux.name(),
+            pid,
+            cue.pts_time
+        );
+        Ok(())
+    }
+
     /// Capture a thumbnail from a block's tee element at the given index.
     ///
     /// Lazily attaches a GStreamer-native processing branch to the block's tee
@@ -497,6 +694,7 @@ impl PipelineManager {
         }
 
         state.set_pvw_group(&new_pvw_group);
+        self.follow_preview_on_aux(block_instance_id, &state, &old_pvw_group, &new_pvw_group);
         overlay::trigger_overlay_update(block_instance_id);
 
         info!(
@@ -580,8 +778,10 @@ impl PipelineManager {
         }
 
         // Update state
+        let old_pvw_group = state.pvw_group();
         state.set_pgm_group(new_pgm_group);
         state.set_pvw_group(new_pvw_group);
+        self.follow_preview_on_aux(block_instance_id, &state, &old_pvw_group, new_pvw_group);
 
         overlay::trigger_overlay_update(block_instance_id);
 
@@ -668,6 +868,146 @@ impl PipelineManager {
         Ok(())
     }
 
+    /// Route a source to an AUX output (0-based) of a vision mixer block.
+    ///
+    /// Cuts or fades between the old and new source pads on `aux_comp_k`.
+    /// Returns the inputs now visible on the AUX output.
+    pub fn set_vision_mixer_aux(
+        &self,
+        block_instance_id: &str,
+        aux_index: usize,
+        source: AuxSource,
+        transition_type: &str,
+        duration_ms: u64,
+    ) -> Result<Vec<usize>, PipelineError> {
+        use crate::blocks::builtin::vision_mixer::overlay;
+        use crate::gst::transitions::TransitionType;
+
+        let state = overlay::get_overlay_state(block_instance_id).ok_or_else(|| {
+            PipelineError::ElementNotFound(format!(
+                "Vision mixer overlay state not found for {}",
+                block_instance_id
+            ))
+        })?;
+
+        if aux_index >= state.num_aux_outputs() {
+            return Err(PipelineError::InvalidProperty {
+                element: block_instance_id.to_string(),
+                property: "aux".to_string(),
+                reason: format!(
+                    "AUX {} out of range ({} AUX outputs configured)",
+                    aux_index + 1,
+                    state.num_aux_outputs()
+                ),
+            });
+        }
+        if let AuxSource::Input { input } = source {
+            if input >= state.num_inputs {
+                return Err(PipelineError::InvalidProperty {
+                    element: block_instance_id.to_string(),
+                    property: "source".to_string(),
+                    reason: format!(
+                        "Input {} out of range (max {})",
+                        input,
+                        state.num_inputs - 1
+                    ),
+                });
+            }
+        }
+        let trans_type = match transition_type.parse::<TransitionType>() {
+            Ok(t @ (TransitionType::Cut | TransitionType::Fade)) => t,
+            _ => {
+                return Err(PipelineError::InvalidProperty {
+                    element: block_instance_id.to_string(),
+                    property: "transition_type".to_string(),
+                    reason: format!("AUX outputs support cut and fade, got {}", transition_type),
+                })
+            }
+        };
+
+        let comp_id = format!(
+            "{}:{}",
+            block_instance_id,
+            aux_outputs::aux_comp_name(aux_index)
+        );
+        let comp = self
+            .elements
+            .get(&comp_id)
+            .ok_or_else(|| PipelineError::ElementNotFound(comp_id.clone()))?;
+
+        let pvw_group = state.pvw_group();
+        let old_source = state.aux_sources()[aux_index];
+        let old_pads =
+            aux_outputs::source_pads(old_source, state.num_inputs, &pvw_group, state.dsk_stage);
+        let new_pads =
+            aux_outputs::source_pads(source, state.num_inputs, &pvw_group, state.dsk_stage);
+        let (canvas_width, canvas_height) = self.dist_canvas_size(block_instance_id);
+        switch_aux_pads(
+            comp,
+            &old_pads,
+            &new_pads,
+            trans_type,
+            duration_ms,
+            (canvas_width, canvas_height),
+            &self.pipeline,
+        )?;
+
+        state.set_aux_source(aux_index, source);
+        overlay::trigger_overlay_update(block_instance_id);
+
+        info!(
+            "Vision mixer {} AUX {}: {:?} -> {:?} ({})",
+            block_instance_id,
+            aux_index + 1,
+            old_source,
+            source,
+            transition_type
+        );
+
+        Ok(aux_outputs::source_inputs(source, &pvw_group))
+    }
+
+    /// Cut AUX outputs that follow the preview to the new PVW group.
+    fn follow_preview_on_aux(
+        &self,
+        block_instance_id: &str,
+        state: &crate::blocks::builtin::vision_mixer::VisionMixerOverlayState,
+        old_pvw_group: &[usize],
+        new_pvw_group: &[usize],
+    ) {
+        use crate::gst::transitions::TransitionType;
+
+        if old_pvw_group == new_pvw_group {
+            return;
+        }
+        let canvas = self.dist_canvas_size(block_instance_id);
+        for (k, source) in state.aux_sources().into_iter().enumerate() {
+            if source != AuxSource::Preview {
+                continue;
+            }
+            let comp_id = format!("{}:{}", block_instance_id, aux_outputs::aux_comp_name(k));
+            let Some(comp) = self.elements.get(&comp_id) else {
+                continue;
+            };
+            if let Err(e) = switch_aux_pads(
+                comp,
+                old_pvw_group,
+                new_pvw_group,
+                TransitionType::Cut,
+                0,
+                canvas,
+                &self.pipeline,
+            ) {
+                warn!(
+                    "Vision mixer {} AUX {} failed to follow preview: {}",
+                    block_instance_id,
+                    k + 1,
+                    e
+                );
+            }
+        }
+    }
+
     /// Toggle a DSK (Downstream Keyer) layer on or off.
     pub fn set_dsk_enabled(
         &self,
@@ -676,14 +1016,22 @@ impl PipelineManager {
         num_inputs: usize,
         enabled: bool,
     ) -> Result<(), PipelineError> {
-        // DSK pads are on the dist compositor (mixer) at sink_{num_inputs + dsk_index}
-        let mixer_id = format!("{}:mixer", block_instance_id);
+        // DSK pads are on the dist compositor (mixer) at sink_{num_inputs + dsk_index},
+        // or on the DSK stage at sink_{1 + dsk_index} when the mixer has a clean feed
+        let dsk_comp_id = format!("{}:{}", block_instance_id, aux_outputs::DSK_COMP_ELEMENT);
+        let (mixer_id, pad_name) = if self.elements.contains_key(&dsk_comp_id) {
+            (dsk_comp_id, format!("sink_{}", 1 + dsk_index))
+        } else {
+            (
+                format!("{}:mixer", block_instance_id),
+                format!("sink_{}", num_inputs + dsk_index),
+            )
+        };
         let mixer = self
             .elements
             .get(&mixer_id)
             .ok_or_else(|| PipelineError::ElementNotFound(mixer_id.clone()))?;
 
-        let pad_name = format!("sink_{}", num_inputs + dsk_index);
         if let Some(pad) = find_pad(mixer, &pad_name) {
             let alpha = if enabled { 1.0f64 } else { 0.0f64 };
             pad.set_property("alpha", alpha);
@@ -711,6 +1059,60 @@ impl PipelineManager {
         }
     }
 
+    /// Toggle a USK (Upstream Keyer) layer on or off.
+    pub fn set_usk_enabled(
+        &self,
+        block_instance_id: &str,
+        usk_index: usize,
+        enabled: bool,
+    ) -> Result<(), PipelineError> {
+        use crate::blocks::builtin::vision_mixer::overlay;
+
+        let state = overlay::get_overlay_state(block_instance_id).ok_or_else(|| {
+            PipelineError::ElementNotFound(format!(
+                "Vision mixer overlay state not found for {}",
+                block_instance_id
+            ))
+        })?;
+        let mixer_id = format!("{}:mixer", block_instance_id);
+        if usk_index >= state.usk_enabled.len() {
+            return Err(PipelineError::PadNotFound {
+                element: mixer_id,
+                pad: format!("USK {}", usk_index + 1),
+            });
+        }
+        let mixer = self
+            .elements
+            .get(&mixer_id)
+            .ok_or_else(|| PipelineError::ElementNotFound(mixer_id.clone()))?;
+
+        // USK pads follow the inputs and any DSK pads on the mixer
+        let num_dsk_on_mixer = if state.dsk_stage {
+            0
+        } else {
+            state.num_dsk_inputs
+        };
+        let pad_name = format!(
+            "sink_{}",
+            keyers::usk_pad_index(state.num_inputs, num_dsk_on_mixer, usk_index)
+        );
+        let pad = find_pad(mixer, &pad_name).ok_or_else(|| PipelineError::PadNotFound {
+            element: mixer_id.clone(),
+            pad: pad_name.clone(),
+        })?;
+        // Stay dark during Fade to Black; the fade-in restores enabled layers
+        let on_air = enabled && !state.ftb_active.load(std::sync::atomic::Ordering::Relaxed);
+        pad.set_property("alpha", if on_air { 1.0f64 } else { 0.0f64 });
+        state.set_usk_enabled(usk_index, enabled);
+        info!(
+            "Vision mixer {} USK {} {}",
+            block_instance_id,
+            usk_index,
+            if enabled { "enabled" } else { "disabled" }
+        );
+        Ok(())
+    }
+
     /// Set the multiview overlay alpha on a vision mixer block.
     pub fn set_overlay_alpha(
         &self,
@@ -785,9 +1187,8 @@ impl PipelineManager {
             .unwrap_or(gst::ClockTime::ZERO);
         let end_time = current_time + gst::ClockTime::from_mseconds(duration_ms);
 
-        // Collect control sources so they stay alive for the duration of the animation
-        let mut control_sources: Vec<InterpolationControlSource> = Vec::new();
-
+        // Pads to animate with their (start, end) alpha
+        let mut fades: Vec<(gst::Pad, f64, f64)> = Vec::new();
         for pad in mixer.sink_pads() {
             let name = pad.name();
             if name.starts_with("sink_") {
@@ -797,61 +1198,81 @@ impl PipelineManager {
                         // FTB on: fade current alpha to 0
                         let current = pad.property::<f64>("alpha");
                         (current, 0.0)
-                    } else if pgm_group.contains(&idx) || bg == Some(idx) {
-                        (0.0, 1.0)
-                    } else if idx >= state.num_inputs {
-                        let dsk_idx = idx - state.num_inputs;
-                        let enabled = dsk_idx < state.dsk_enabled.len()
-                            && state.dsk_enabled[dsk_idx]
-                                .load(std::sync::atomic::Ordering::Relaxed);
-                        if enabled {
-                            (0.0, 1.0)
-                        } else {
-                            continue;
+                    } else if pgm_group.contains(&idx)
+                        || bg == Some(idx)
+                        || match state.dist_layer(idx) {
+                            DistLayer::Dsk(dsk_idx) => state.dsk_is_enabled(dsk_idx),
+                            DistLayer::Usk(usk_idx) => state.usk_is_enabled(usk_idx),
+                            DistLayer::Input(_) | DistLayer::Stinger => false,
                         }
+                    {
+                        (0.0, 1.0)
                     } else {
                         continue;
                     };
+                    fades.push((pad, start_alpha, end_alpha));
+                }
+            }
+        }
 
-                    if (start_alpha - end_alpha).abs() < f64::EPSILON {
-                        continue;
-                    }
+        // DSK layers on the DSK stage (sink_0 is the clean feed, already black)
+        let dsk_comp_id = format!("{}:{}", block_instance_id, aux_outputs::DSK_COMP_ELEMENT);
+        let dsk_comp = self.elements.get(&dsk_comp_id);
+        if let Some(dsk_comp) = dsk_comp {
+            for dsk_idx in 0..state.num_dsk_inputs {
+                let Some(pad) = find_pad(dsk_comp, &format!("sink_{}", 1 + dsk_idx)) else {
+                    continue;
+                };
+                if now_active {
+                    let current = pad.property::<f64>("alpha");
+                    fades.push((pad, current, 0.0));
+                } else if state.dsk_is_enabled(dsk_idx) {
+                    fades.push((pad, 0.0, 1.0));
+                }
+            }
+        }
 
-                    // Clear any existing alpha control binding
-                    if let Some(binding) = pad.control_binding("alpha") {
-                        pad.remove_control_binding(&binding);
-                    }
+        // Collect control sources so they stay alive for the duration of the animation
+        let mut control_sources: Vec<InterpolationControlSource> = Vec::new();
 
-                    let cs = InterpolationControlSource::new();
-                    cs.set_mode(InterpolationMode::Linear);
-
-                    // Ease-in-out keyframes
-                    let duration_ns = (end_time - current_time).nseconds() as f64;
-                    let num_keyframes = strom_types::vision_mixer::TRANSITION_KEYFRAMES as u32;
-                    for i in 0..=num_keyframes {
-                        let t = i as f64 / num_keyframes as f64;
-                        let eased = (1.0 - (t * std::f64::consts::PI).cos()) / 2.0;
-                        let value = start_alpha + (end_alpha - start_alpha) * eased;
-                        let time =
-                            current_time + gst::ClockTime::from_nseconds((duration_ns * t) as u64);
-                        cs.set(time, value);
-                    }
+        for (pad, start_alpha, end_alpha) in fades {
+            if (start_alpha - end_alpha).abs() < f64::EPSILON {
+                continue;
+            }
 
-                    let binding = DirectControlBinding::new(&pad, "alpha", &cs);
-                    let _ = pad.add_control_binding(&binding);
-                    control_sources.push(cs);
-                }
+            // Clear any existing alpha control binding
+            if let Some(binding) = pad.control_binding("alpha") {
+                pad.remove_control_binding(&binding);
             }
+
+            let cs = InterpolationControlSource::new();
+            cs.set_mode(InterpolationMode::Linear);
+
+            // Ease-in-out keyframes
+            let duration_ns = (end_time - current_time).nseconds() as f64;
+            let num_keyframes = strom_types::vision_mixer::TRANSITION_KEYFRAMES as u32;
+            for i in 0..=num_keyframes {
+                let t = i as f64 / num_keyframes as f64;
+                let eased = (1.0 - (t * std::f64::consts::PI).cos()) / 2.0;
+                let value = start_alpha + (end_alpha - start_alpha) * eased;
+                let time = current_time + gst::ClockTime::from_nseconds((duration_ns * t) as u64);
+                cs.set(time, value);
+            }
+
+            let binding = DirectControlBinding::new(&pad, "alpha", &cs);
+            let _ = pad.add_control_binding(&binding);
+            control_sources.push(cs);
         }
 
         // Keep control sources alive until the animation completes, then clean up bindings
         if !control_sources.is_empty() {
-            let cleanup_mixer = mixer.clone();
+            let mut cleanup_elements = vec![mixer.clone()];
+            cleanup_elements.extend(dsk_comp.cloned());
             let cleanup_duration = duration_ms + 100; // small margin
             gst::glib::timeout_add_once(
                 std::time::Duration::from_millis(cleanup_duration),
                 move || {
-                    for pad in cleanup_mixer.sink_pads() {
+                    for pad in cleanup_elements.iter().flat_map(|e| e.sink_pads()) {
                         if let Some(binding) = pad.control_binding("alpha") {
                             pad.remove_control_binding(&binding);
                         }
@@ -881,6 +1302,107 @@ impl PipelineManager {
     }
 }
 
+/// Position incoming PGM group pads at their target sub-rects (hidden at alpha=0,
+/// above the background) ahead of a group or stinger transition.
+fn position_incoming_group(
+    mixer: &gst::Element,
+    group: &[usize],
+    canvas_width: i32,
+    canvas_height: i32,
+) {
+    let rects = strom_types::vision_mixer::compute_group_rects(
+        0,
+        0,
+        canvas_width,
+        canvas_height,
+        group.len(),
+    );
+    for (slot, &idx) in group.iter().enumerate() {
+        if let Some(pad) = mixer.static_pad(&format!("sink_{}", idx)) {
+            let (x, y, w, h) =
+                rects
+                    .get(slot)
+                    .copied()
+                    .unwrap_or((0, 0, canvas_width, canvas_height));
+            pad.set_property("xpos", x);
+            pad.set_property("ypos", y);
+            pad.set_property("width", w);
+            pad.set_property("height", h);
+            pad.set_property("alpha", 0.0f64);
+            pad.set_property("zorder", strom_types::vision_mixer::DIST_PGM_ZORDER);
+        }
+    }
+}
+
+/// Switch an AUX compositor from one set of source pads to another.
+///
+/// Incoming pads are laid out like a PGM group (fullscreen for a single
+/// source). Pads shown before and after stay visible; the rest cut or fade.
+fn switch_aux_pads(
+    comp: &gst::Element,
+    old_pads: &[usize],
+    new_pads: &[usize],
+    trans_type: crate::gst::transitions::TransitionType,
+    duration_ms: u64,
+    (canvas_width, canvas_height): (i32, i32),
+    pipeline: &gst::Pipeline,
+) -> Result<(), PipelineError> {
+    use crate::gst::transitions::{TransitionController, TransitionType};
+
+    let rects = strom_types::vision_mixer::compute_group_rects(
+        0,
+        0,
+        canvas_width,
+        canvas_height,
+        new_pads.len(),
+    );
+    let fade = trans_type == TransitionType::Fade && duration_ms > 0;
+
+    // Clear leftover fades and settle every pad before the switch
+    for pad in comp.sink_pads() {
+        let Ok(idx) = pad.name().trim_start_matches("sink_").parse::<usize>() else {
+            continue;
+        };
+        if let Some(binding) = pad.control_binding("alpha") {
+            pad.remove_control_binding(&binding);
+        }
+        let visible = if fade {
+            old_pads.contains(&idx)
+        } else {
+            new_pads.contains(&idx)
+        };
+        pad.set_property("alpha", if visible { 1.0f64 } else { 0.0f64 });
+        if let Some(slot) = new_pads.iter().position(|&p| p == idx) {
+            let (x, y, w, h) =
+                rects
+                    .get(slot)
+                    .copied()
+                    .unwrap_or((0, 0, canvas_width, canvas_height));
+            pad.set_property("xpos", x);
+            pad.set_property("ypos", y);
+            pad.set_property("width", w);
+            pad.set_property("height", h);
+        }
+    }
+
+    if fade {
+        let leaving: Vec<usize> = old_pads
+            .iter()
+            .copied()
+            .filter(|idx| !new_pads.contains(idx))
+            .collect();
+        let entering: Vec<usize> = new_pads
+            .iter()
+            .copied()
+            .filter(|idx| !old_pads.contains(idx))
+            .collect();
+        TransitionController::new(comp.clone(), canvas_width, canvas_height)
+            .transition_groups(&leaving, &entering, duration_ms, pipeline)
+            .map_err(|e| PipelineError::TransitionError(e.to_string()))?;
+    }
+    Ok(())
+}
+
 /// Find a pad by name on an element, checking both static and request pads.
 /// `static_pad()` doesn't find request pads on aggregator elements like glvideomixer.
 fn find_pad(element: &gst::Element, pad_name: &str) -> Option<gst::Pad> {
@@ -1,11 +1,21 @@
 use super::{PipelineError, PipelineManager};
+use crate::gst::transitions::PropertyBinding;
 use gstreamer as gst;
 use gstreamer::glib;
 use gstreamer::prelude::*;
+use parking_lot::Mutex;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
 use strom_types::{PipelineState, PropertyValue};
 use tracing::{debug, info};
 
+/// Running property ramps by element and property, with the ID of the
+/// ramp that owns each binding.
+static ACTIVE_RAMPS: LazyLock<Mutex<HashMap<(gst::Element, String), (u64, PropertyBinding)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_RAMP_ID: AtomicU64 = AtomicU64::new(0);
+
 impl PipelineManager {
     /// Set a property on an element.
     pub(super) fn set_property(
@@ -159,6 +169,58 @@ impl PipelineManager {
             .get(element_id)
             .ok_or_else(|| PipelineError::ElementNotFound(element_id.to_string()))?;
 
+        // Image source properties re-render the still frame instead of setting
+        // a GStreamer property.
+        if let Some(result) = crate::blocks::builtin::image_source::update_property(
+            &self.flow_id,
+            element_id,
+            property_name,
+            value,
+        ) {
+            return result.map_err(|reason| PipelineError::InvalidProperty {
+                element: element_id.to_string(),
+                property: property_name.to_string(),
+                reason,
+            });
+        }
+
+        // Key settings and key preview selection on compositors and vision mixers
+        if let Some(result) = crate::gst::keying::update_property(
+            &self.flow_id,
+            element_id,
+            element,
+            property_name,
+            value,
+        ) {
+            return result.map_err(|reason| PipelineError::InvalidProperty {
+                element: element_id.to_string(),
+                property: property_name.to_string(),
+                reason,
+            });
+        }
+
+        // Mixer ducking parameters drive the envelope on the bus watch
+        if let Some(result) = crate::blocks::builtin::mixer::ducking::update_property(
+            &self.flow_id,
+            element_id,
+            property_name,
+            value,
+        ) {
+            return result.map_err(|reason| PipelineError::InvalidProperty {
+                element: element_id.to_string(),
+                property: property_name.to_string(),
+                reason,
+            });
+        }
+
+        // Mixer automation records moves on automated faders and pans
+        crate::blocks::builtin::mixer::automation::record(
+            &self.flow_id,
+            element_id,
+            property_name,
+            value,
+        );
+
         // Get current pipeline state
         let state = self.get_state();
 
@@ -201,6 +263,113 @@ impl PipelineManager {
         Ok(())
     }
 
+    /// Ramp double properties on running elements to new values.
+    ///
+    /// Each entry is `(element_id, property_name, target)`. The ramp is a
+    /// linear [`PropertyBinding`] starting at the element's current position;
+    /// once it has run, the binding is released and the target set directly.
+    /// A ramp takes over a property from an earlier ramp still running on it,
+    /// but properties driven by another binding, such as an automation lane
+    /// or an animation, cannot be ramped.
+    pub fn ramp_element_properties(
+        &self,
+        ramps: &[(String, String, f64)],
+        duration_ms: u64,
+    ) -> Result<(), PipelineError> {
+        let mut active = ACTIVE_RAMPS.lock();
+
+        // Resolve everything first so a bad entry leaves no bindings behind
+        let mut resolved = Vec::with_capacity(ramps.len());
+        for (element_id, property_name, target) in ramps {
+            let element = self
+                .elements
+                .get(element_id)
+                .ok_or_else(|| PipelineError::ElementNotFound(element_id.clone()))?;
+            let invalid = |reason: &str| PipelineError::InvalidProperty {
+                element: element_id.clone(),
+                property: property_name.clone(),
+                reason: reason.to_string(),
+            };
+            let (min, max) = element
+                .find_property(property_name)
+                .and_then(|pspec| {
+                    pspec
+                        .downcast_ref::<glib::ParamSpecDouble>()
+                        .map(|p| (p.minimum(), p.maximum()))
+                })
+                .ok_or_else(|| invalid("Only double properties can be ramped"))?;
+            let key = (element.clone(), property_name.clone());
+            let ramped = active.get(&key).is_some_and(|(_, b)| b.is_bound());
+            if element.control_binding(property_name).is_some() && !ramped {
+                return Err(invalid("Property is driven by automation or an animation"));
+            }
+            resolved.push((element_id, key, target.clamp(min, max)));
+        }
+
+        // One start time for all bindings, so the ramps run in step
+        let Some((_, (first, _), _)) = resolved.first() else {
+            return Ok(());
+        };
+        let start = first
+            .query_position::<gst::ClockTime>()
+            .or_else(|| self.pipeline.query_position::<gst::ClockTime>())
+            .unwrap_or(gst::ClockTime::ZERO);
+        let end = start + gst::ClockTime::from_mseconds(duration_ms);
+
+        let id = NEXT_RAMP_ID.fetch_add(1, Ordering::Relaxed);
+        let mut settle = Vec::with_capacity(resolved.len());
+        for (element_id, key, target) in resolved {
+            // Release an earlier ramp first, the new one starts where it left off
+            active.remove(&key);
+            let (element, property_name) = &key;
+            let start_value = element.property::<f64>(property_name);
+            let binding = match PropertyBinding::bind(element, property_name) {
+                Ok(binding) => binding,
+                Err(e) => {
+                    active.retain(|_, (owner, _)| *owner != id);
+                    return Err(PipelineError::InvalidProperty {
+                        element: element_id.clone(),
+                        property: property_name.clone(),
+                        reason: format!("Cannot ramp: {}", e),
+                    });
+                }
+            };
+            binding.set(start, start_value);
+            binding.set(end, target);
+            active.insert(key.clone(), (id, binding));
+            settle.push((key, target));
+        }
+        drop(active);
+
+        if settle.is_empty() {
+            return Ok(());
+        }
+
+        debug!(
+            "Ramping {} element properties over {}ms",
+            settle.len(),
+            duration_ms
+        );
+
+        // Once the ramp has run, release the bindings it still owns and
+        // settle on the targets; properties taken over by a later ramp are
+        // left to that ramp
+        glib::timeout_add_once(
+            std::time::Duration::from_millis(duration_ms + 100),
+            move || {
+                let mut active = ACTIVE_RAMPS.lock();
+                for (key, target) in settle {
+                    if active.get(&key).is_some_and(|(owner, _)| *owner == id) {
+                        active.remove(&key);
+                        key.0.set_property(&key.1, target);
+                    }
+                }
+            },
+        );
+
+        Ok(())
+    }
+
     /// Get current value of a property from a live element.
     pub fn get_element_property(
         &self,
@@ -366,6 +535,36 @@ impl PipelineManager {
             .get(element_id)
             .ok_or_else(|| PipelineError::ElementNotFound(element_id.to_string()))?;
 
+        // Compositor input styles live on the style stages, not the pad
+        if let Some(result) = crate::gst::styling::update_pad_property(
+            &self.flow_id,
+            element_id,
+            pad_name,
+            property_name,
+            value,
+        ) {
+            return result.map_err(|reason| PipelineError::InvalidProperty {
+                element: format!("{}:{}", element_id, pad_name),
+                property: property_name.to_string(),
+                reason,
+            });
+        }
+
+        // Compositor input keys live on the key stages as well
+        if let Some(result) = crate::gst::keying::update_pad_property(
+            &self.flow_id,
+            element_id,
+            pad_name,
+            property_name,
+            value,
+        ) {
+            return result.map_err(|reason| PipelineError::InvalidProperty {
+                element: format!("{}:{}", element_id, pad_name),
+                property: property_name.to_string(),
+                reason,
+            });
+        }
+
         // Get pad reference - try static pad first, then request pad
         let pad = if let Some(p) = element.static_pad(pad_name) {
             p
@@ -387,6 +586,11 @@ impl PipelineManager {
         // Set the property on the pad
         self.set_pad_property(&pad, element_id, pad_name, property_name, value)?;
 
+        // The GPU style stage draws in canvas pixels and follows the pad size
+        if property_name == "width" || property_name == "height" {
+            crate::gst::styling::pad_resized(&self.flow_id, element_id, pad_name);
+        }
+
         info!(
             "Successfully updated pad property {}:{}:{} to {:?}",
             element_id, pad_name, property_name, value
@@ -546,6 +750,18 @@ impl PipelineManager {
             }
         }
 
+        // Compositor input styles and keys, when enabled
+        properties.extend(crate::gst::styling::pad_properties(
+            &self.flow_id,
+            element_id,
+            pad_name,
+        ));
+        properties.extend(crate::gst::keying::pad_properties(
+            &self.flow_id,
+            element_id,
+            pad_name,
+        ));
+
         Ok(properties)
     }
 
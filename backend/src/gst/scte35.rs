@@ -0,0 +1,113 @@
+//! SCTE-35 sections carried as GStreamer events.
+//!
+//! `mpegtsmux` accepts SCTE-35 sections sent to the element as
+//! `GstMpegtsSection` events and writes them on its `scte-35-pid`.
+//! `tsdemux` (with `send-scte35-events=true`) forwards received sections
+//! downstream as the same kind of event.
+//!
+//! The gstreamer-rs bindings do not cover libgstmpegts, so the handful of
+//! functions needed are declared here. Section encoding and parsing is done
+//! in [`strom_types::scte35`].
+
+use gstreamer as gst;
+use gstreamer::glib;
+use gstreamer::glib::translate::ToGlibPtr;
+use gstreamer::prelude::*;
+use std::sync::Once;
+use strom_types::scte35::{Scte35Cue, SPLICE_INFO_TABLE_ID};
+
+/// Structure name of custom events carrying an MPEG-TS section.
+const SECTION_EVENT_NAME: &str = "mpeg-ts-section";
+
+#[repr(C)]
+struct GstMpegtsSection {
+    _private: [u8; 0],
+}
+
+#[link(name = "gstmpegts-1.0")]
+extern "C" {
+    fn gst_mpegts_initialize();
+    fn gst_mpegts_section_new(pid: u16, data: *mut u8, data_size: usize) -> *mut GstMpegtsSection;
+    fn gst_mpegts_section_send_event(
+        section: *mut GstMpegtsSection,
+        element: *mut gst::ffi::GstElement,
+    ) -> glib::ffi::gboolean;
+    fn gst_event_parse_mpegts_section(event: *mut gst::ffi::GstEvent) -> *mut GstMpegtsSection;
+    fn gst_mpegts_section_get_data(section: *mut GstMpegtsSection) -> *mut glib::ffi::GBytes;
+}
+
+/// Register the libgstmpegts types (required before creating or parsing sections).
+fn ensure_initialized() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| unsafe { gst_mpegts_initialize() });
+}
+
+/// Encode `cue` and send it to an `mpegtsmux` element.
+///
+/// `pid` should match the muxer's `scte-35-pid` property.
+pub fn send_cue(element: &gst::Element, pid: u16, cue: &Scte35Cue) -> Result<(), String> {
+    let bytes = cue.encode()?;
+    ensure_initialized();
+
+    unsafe {
+        // gst_mpegts_section_new takes ownership of the data, which must be g_malloc'ed
+        let data = glib::ffi::g_malloc(bytes.len()) as *mut u8;
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), data, bytes.len());
+        let section = gst_mpegts_section_new(pid, data, bytes.len());
+        if section.is_null() {
+            return Err("failed to create SCTE-35 section".to_string());
+        }
+
+        let sent = gst_mpegts_section_send_event(section, element.to_glib_none().0);
+        gst::ffi::gst_mini_object_unref(section as *mut gst::ffi::GstMiniObject);
+
+        if sent == glib::ffi::GFALSE {
+            return Err(format!(
+                "{} did not accept the SCTE-35 section",
+                element.name()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract an SCTE-35 cue from a section event forwarded by `tsdemux`.
+///
+/// Returns `None` if the event does not carry a `splice_info_section`.
+pub fn cue_from_event(event: &gst::EventRef) -> Option<Result<Scte35Cue, String>> {
+    if event.type_() != gst::EventType::CustomDownstream {
+        return None;
+    }
+    if event.structure()?.name() != SECTION_EVENT_NAME {
+        return None;
+    }
+    ensure_initialized();
+
+    let data = unsafe {
+        let section = gst_event_parse_mpegts_section(event.as_mut_ptr());
+        if section.is_null() {
+            return None;
+        }
+        let bytes = gst_mpegts_section_get_data(section);
+        gst::ffi::gst_mini_object_unref(section as *mut gst::ffi::GstMiniObject);
+        if bytes.is_null() {
+            return None;
+        }
+
+        let mut size = 0usize;
+        let ptr = glib::ffi::g_bytes_get_data(bytes, &mut size) as *const u8;
+        let data = if ptr.is_null() {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(ptr, size).to_vec()
+        };
+        glib::ffi::g_bytes_unref(bytes);
+        data
+    };
+
+    if data.first() != Some(&SPLICE_INFO_TABLE_ID) {
+        return None;
+    }
+    Some(Scte35Cue::parse(&data))
+}
@@ -0,0 +1,963 @@
+//! Per-input styling for compositor inputs: crop, rotation, border, drop
+//! shadow and rounded corners.
+//!
+//! Crop and rotation run on dedicated elements in front of the thumbnail tee
+//! (`videocrop` + `videoflip` on the CPU backend, `glvideoflip` on the GPU
+//! backend). The look is drawn by a style stage in front of the mixer pad:
+//!
+//! - **GPU**: a `glshader` element running [`STYLE_FRAGMENT_SHADER`]. It also
+//!   does the cropping, as `videocrop` cannot handle GL memory.
+//! - **CPU**: the mixer pad is fed packed BGRA ([`cpu_style_caps`]) and a
+//!   buffer probe draws the look into every frame.
+//!
+//! Border width, corner radius and shadow size are in canvas pixels so the
+//! look does not change when an input is resized. Both backends evaluate the
+//! same [`StyleGeometry`], which assumes the picture fills the pad rectangle.
+//! With a drop shadow the picture is scaled into the top-left of the pad
+//! rectangle and the shadow fills the remaining margin.
+//!
+//! Running styles are kept in [`STYLE_REGISTRY`] so pad property changes on
+//! the mixer (`crop-top`, `rotation`, `border-width`, ...) apply live.
+
+use super::wipes::parse_hex_color;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use strom_types::{FlowId, PropertyValue};
+use tracing::{debug, warn};
+
+/// Element factory used for the GPU style stage.
+pub const GL_STYLE_FACTORY: &str = "glshader";
+
+/// Pad-level style property names, as used on the mixer `sink_N` pads.
+///
+/// The matching block properties are `input_N_<name>` with `-` replaced by `_`.
+pub const STYLE_PROPERTIES: [&str; 10] = [
+    "crop-top",
+    "crop-bottom",
+    "crop-left",
+    "crop-right",
+    "rotation",
+    "border-width",
+    "border-color",
+    "corner-radius",
+    "shadow-size",
+    "shadow-opacity",
+];
+
+/// Clockwise rotation of an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl Rotation {
+    /// Look up a rotation by degrees (0, 90, 180 or 270).
+    pub fn from_degrees(degrees: i64) -> Option<Self> {
+        match degrees {
+            0 => Some(Self::None),
+            90 => Some(Self::Cw90),
+            180 => Some(Self::Cw180),
+            270 => Some(Self::Cw270),
+            _ => None,
+        }
+    }
+
+    /// Clockwise rotation in degrees.
+    pub fn degrees(self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Cw90 => 90,
+            Self::Cw180 => 180,
+            Self::Cw270 => 270,
+        }
+    }
+
+    /// `video-direction` nick for `videoflip`/`glvideoflip`.
+    pub fn video_direction(self) -> &'static str {
+        match self {
+            Self::None => "identity",
+            Self::Cw90 => "90r",
+            Self::Cw180 => "180",
+            Self::Cw270 => "90l",
+        }
+    }
+}
+
+/// Pixels cut from each side of the source picture (before rotation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Crop {
+    pub top: u32,
+    pub bottom: u32,
+    pub left: u32,
+    pub right: u32,
+}
+
+impl Crop {
+    /// The same crop expressed on the rotated picture.
+    pub fn rotated(self, rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::None => self,
+            Rotation::Cw90 => Self {
+                top: self.left,
+                right: self.top,
+                bottom: self.right,
+                left: self.bottom,
+            },
+            Rotation::Cw180 => Self {
+                top: self.bottom,
+                right: self.left,
+                bottom: self.top,
+                left: self.right,
+            },
+            Rotation::Cw270 => Self {
+                top: self.right,
+                right: self.bottom,
+                bottom: self.left,
+                left: self.top,
+            },
+        }
+    }
+}
+
+/// Style of one compositor input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputStyle {
+    pub crop: Crop,
+    pub rotation: Rotation,
+    /// Border width in canvas pixels (0 = no border).
+    pub border_width: f32,
+    /// Border colour (RGB, 0.0-1.0).
+    pub border_color: [f32; 3],
+    /// Corner radius in canvas pixels (0 = square corners).
+    pub corner_radius: f32,
+    /// Drop shadow offset and blur in canvas pixels (0 = no shadow).
+    pub shadow_size: f32,
+    /// Drop shadow opacity (0.0-1.0).
+    pub shadow_opacity: f32,
+}
+
+impl Default for InputStyle {
+    fn default() -> Self {
+        Self {
+            crop: Crop::default(),
+            rotation: Rotation::None,
+            border_width: 0.0,
+            border_color: [1.0, 1.0, 1.0],
+            corner_radius: 0.0,
+            shadow_size: 0.0,
+            shadow_opacity: 0.5,
+        }
+    }
+}
+
+fn as_f64(value: &PropertyValue) -> Option<f64> {
+    match value {
+        PropertyValue::Float(f) => Some(*f),
+        PropertyValue::Int(i) => Some(*i as f64),
+        PropertyValue::UInt(u) => Some(*u as f64),
+        PropertyValue::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+impl InputStyle {
+    /// Read the `input_{index}_*` style properties of a compositor block,
+    /// falling back to defaults for missing or invalid values.
+    pub fn from_properties(properties: &HashMap<String, PropertyValue>, index: usize) -> Self {
+        let mut style = Self::default();
+        for name in STYLE_PROPERTIES {
+            let key = format!("input_{}_{}", index, name.replace('-', "_"));
+            if let Some(value) = properties.get(&key) {
+                if let Err(e) = style.set(name, value) {
+                    warn!("Ignoring {}: {}", key, e);
+                }
+            }
+        }
+        style
+    }
+
+    /// Set one style property by its pad-level name.
+    pub fn set(&mut self, property: &str, value: &PropertyValue) -> Result<(), String> {
+        let number = || {
+            as_f64(value)
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("{} must be a non-negative number", property))
+        };
+        match property {
+            "crop-top" => self.crop.top = number()? as u32,
+            "crop-bottom" => self.crop.bottom = number()? as u32,
+            "crop-left" => self.crop.left = number()? as u32,
+            "crop-right" => self.crop.right = number()? as u32,
+            "rotation" => {
+                self.rotation = as_f64(value)
+                    .and_then(|v| Rotation::from_degrees(v as i64))
+                    .ok_or_else(|| "rotation must be 0, 90, 180 or 270".to_string())?
+            }
+            "border-width" => self.border_width = number()? as f32,
+            "border-color" => {
+                self.border_color = match value {
+                    PropertyValue::String(s) => parse_hex_color(s),
+                    _ => None,
+                }
+                .ok_or_else(|| "border-color must be #RRGGBB".to_string())?
+            }
+            "corner-radius" => self.corner_radius = number()? as f32,
+            "shadow-size" => self.shadow_size = number()? as f32,
+            "shadow-opacity" => {
+                let opacity = number()?;
+                if opacity > 1.0 {
+                    return Err("shadow-opacity must be between 0.0 and 1.0".to_string());
+                }
+                self.shadow_opacity = opacity as f32;
+            }
+            _ => return Err(format!("Unknown style property '{}'", property)),
+        }
+        Ok(())
+    }
+
+    /// Current value of one style property by its pad-level name.
+    pub fn get(&self, property: &str) -> Option<PropertyValue> {
+        let value = match property {
+            "crop-top" => PropertyValue::UInt(self.crop.top as u64),
+            "crop-bottom" => PropertyValue::UInt(self.crop.bottom as u64),
+            "crop-left" => PropertyValue::UInt(self.crop.left as u64),
+            "crop-right" => PropertyValue::UInt(self.crop.right as u64),
+            "rotation" => PropertyValue::String(self.rotation.degrees().to_string()),
+            "border-width" => PropertyValue::Float(self.border_width as f64),
+            "border-color" => {
+                let [r, g, b] = self.border_color.map(|c| (c * 255.0).round() as u8);
+                PropertyValue::String(format!("#{:02X}{:02X}{:02X}", r, g, b))
+            }
+            "corner-radius" => PropertyValue::Float(self.corner_radius as f64),
+            "shadow-size" => PropertyValue::Float(self.shadow_size as f64),
+            "shadow-opacity" => PropertyValue::Float(self.shadow_opacity as f64),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// True if the style draws anything on top of crop and rotation.
+    pub fn has_look(&self) -> bool {
+        self.border_width > 0.0 || self.corner_radius > 0.0 || self.shadow_size > 0.0
+    }
+}
+
+/// Signed distance from `(x, y)` to a rounded box (negative inside).
+fn rounded_box_distance(x: f32, y: f32, center: [f32; 2], half: [f32; 2], radius: f32) -> f32 {
+    let qx = (x - center[0]).abs() - half[0] + radius;
+    let qy = (y - center[1]).abs() - half[1] + radius;
+    qx.max(0.0).hypot(qy.max(0.0)) + qx.max(qy).min(0.0) - radius
+}
+
+/// How the style shapes one pixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StyleSample {
+    /// Alpha multiplier of the picture (rounded corners, 1px antialiased).
+    pub coverage: f32,
+    /// How much of the picture shows through the border (1.0 = no border).
+    pub inner: f32,
+    /// Alpha of the black drop shadow under the picture.
+    pub shadow: f32,
+}
+
+/// Style layout of an input on a pad rectangle of `width` x `height` canvas
+/// pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StyleGeometry {
+    /// Size of the picture box; smaller than the rectangle by the shadow size.
+    pub content: [f32; 2],
+    radius: f32,
+    border_width: f32,
+    shadow_size: f32,
+    shadow_opacity: f32,
+}
+
+impl StyleGeometry {
+    pub fn new(style: &InputStyle, width: f32, height: f32) -> Self {
+        let shadow_size = style.shadow_size.min(0.5 * width.min(height));
+        let content = [
+            (width - shadow_size).max(1.0),
+            (height - shadow_size).max(1.0),
+        ];
+        Self {
+            content,
+            radius: style.corner_radius.min(0.5 * content[0].min(content[1])),
+            border_width: style.border_width,
+            shadow_size,
+            shadow_opacity: style.shadow_opacity,
+        }
+    }
+
+    /// Evaluate the style at canvas point `(x, y)` relative to the pad
+    /// rectangle's top-left corner.
+    pub fn sample(&self, x: f32, y: f32) -> StyleSample {
+        let half = [0.5 * self.content[0], 0.5 * self.content[1]];
+        let d = rounded_box_distance(x, y, half, half, self.radius);
+        let coverage = (0.5 - d).clamp(0.0, 1.0);
+        let inner = if self.border_width > 0.0 {
+            (0.5 - (d + self.border_width)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let shadow = if self.shadow_size > 0.0 {
+            let offset = 0.5 * self.shadow_size;
+            let center = [half[0] + offset, half[1] + offset];
+            let ds = rounded_box_distance(x, y, center, half, self.radius);
+            self.shadow_opacity * (0.5 - ds / self.shadow_size).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        StyleSample {
+            coverage,
+            inner,
+            shadow,
+        }
+    }
+
+    /// Pixels `lo..hi` of a frame axis of `len` pixels at `scale` canvas
+    /// pixels each whose centres lie inside the picture box, clear of the
+    /// corners and the border, where the picture is fully covered.
+    fn interior_span(&self, axis: usize, len: usize, scale: f32) -> (usize, usize) {
+        let margin = self.radius.max(self.border_width) + 1.0;
+        let hi = ((self.content[axis] - margin) / scale - 0.5).floor() + 1.0;
+        let hi = hi.clamp(0.0, len as f32) as usize;
+        let lo = (margin / scale - 0.5).ceil().max(0.0) as usize;
+        (lo.min(hi), hi)
+    }
+}
+
+/// Composite a picture pixel (RGB and alpha, 0.0-1.0) with its style sample.
+pub fn compose(
+    rgb: [f32; 3],
+    alpha: f32,
+    sample: StyleSample,
+    border: [f32; 3],
+) -> ([f32; 3], f32) {
+    let inner = sample.inner;
+    let fg = (1.0 - inner + alpha * inner) * sample.coverage;
+    let out = fg + (1.0 - fg) * sample.shadow;
+    if out <= 0.0 {
+        return ([0.0; 3], 0.0);
+    }
+    let rgb = std::array::from_fn(|i| (border[i] + (rgb[i] - border[i]) * inner) * fg / out);
+    (rgb, out)
+}
+
+/// GLSL fragment shader implementing the style for the `glshader` element.
+///
+/// Mirrors [`StyleGeometry`] and [`compose`]. Crop values are in frame
+/// pixels of the rotated picture; `width`/`height` are provided by
+/// `glshader`, `rect_w`/`rect_h` are the mixer pad size (0 = frame size).
+pub const STYLE_FRAGMENT_SHADER: &str = r#"#version 100
+#ifdef GL_ES
+precision mediump float;
+#endif
+varying vec2 v_texcoord;
+uniform sampler2D tex;
+uniform float width;
+uniform float height;
+uniform float rect_w;
+uniform float rect_h;
+uniform float crop_top;
+uniform float crop_bottom;
+uniform float crop_left;
+uniform float crop_right;
+uniform float border_width;
+uniform float border_r;
+uniform float border_g;
+uniform float border_b;
+uniform float radius;
+uniform float shadow_size;
+uniform float shadow_opacity;
+
+float box_distance(vec2 p, vec2 center, vec2 half_size, float r) {
+  vec2 q = abs(p - center) - half_size + r;
+  return length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - r;
+}
+
+void main () {
+  vec2 size = vec2(width, height);
+  if (rect_w > 0.0) {
+    size.x = rect_w;
+  }
+  if (rect_h > 0.0) {
+    size.y = rect_h;
+  }
+  float s = min(shadow_size, 0.5 * min(size.x, size.y));
+  vec2 content = max(size - s, vec2(1.0));
+  vec2 p = v_texcoord * size;
+  vec2 lo = vec2(crop_left / width, crop_top / height);
+  vec2 hi = vec2(1.0 - crop_right / width, 1.0 - crop_bottom / height);
+  vec4 c = texture2D(tex, mix(lo, hi, clamp(p / content, 0.0, 1.0)));
+
+  vec2 half_size = 0.5 * content;
+  float r = min(radius, min(half_size.x, half_size.y));
+  float d = box_distance(p, half_size, half_size, r);
+  float coverage = clamp(0.5 - d, 0.0, 1.0);
+  float inner = 1.0;
+  if (border_width > 0.0) {
+    inner = clamp(0.5 - (d + border_width), 0.0, 1.0);
+  }
+  float shadow = 0.0;
+  if (s > 0.0) {
+    float ds = box_distance(p, half_size + 0.5 * s, half_size, r);
+    shadow = shadow_opacity * clamp(0.5 - ds / s, 0.0, 1.0);
+  }
+
+  vec3 rgb = mix(vec3(border_r, border_g, border_b), c.rgb, inner);
+  float fg = mix(1.0, c.a, inner) * coverage;
+  float a = fg + (1.0 - fg) * shadow;
+  if (a <= 0.0) {
+    gl_FragColor = vec4(0.0);
+  } else {
+    gl_FragColor = vec4(rgb * fg / a, a);
+  }
+}
+"#;
+
+/// Read the size of a mixer pad (0 = use the frame size).
+fn pad_rect(mixer_pad: &gst::Pad) -> (i32, i32) {
+    (
+        mixer_pad.property::<i32>("width").max(0),
+        mixer_pad.property::<i32>("height").max(0),
+    )
+}
+
+/// Build the `glshader` uniforms structure for a style on a pad rectangle.
+pub fn gl_uniforms(style: &InputStyle, rect: (i32, i32)) -> gst::Structure {
+    let crop = style.crop.rotated(style.rotation);
+    gst::Structure::builder("uniforms")
+        .field("rect_w", rect.0 as f32)
+        .field("rect_h", rect.1 as f32)
+        .field("crop_top", crop.top as f32)
+        .field("crop_bottom", crop.bottom as f32)
+        .field("crop_left", crop.left as f32)
+        .field("crop_right", crop.right as f32)
+        .field("border_width", style.border_width)
+        .field("border_r", style.border_color[0])
+        .field("border_g", style.border_color[1])
+        .field("border_b", style.border_color[2])
+        .field("radius", style.corner_radius)
+        .field("shadow_size", style.shadow_size)
+        .field("shadow_opacity", style.shadow_opacity)
+        .build()
+}
+
+/// Create the GPU style stage.
+pub fn make_gl_style(
+    name: &str,
+    style: &InputStyle,
+    rect: (i32, i32),
+) -> Result<gst::Element, gst::glib::BoolError> {
+    gst::ElementFactory::make(GL_STYLE_FACTORY)
+        .name(name)
+        .property("fragment", STYLE_FRAGMENT_SHADER)
+        .property("uniforms", gl_uniforms(style, rect))
+        .build()
+}
+
+/// Create the rotation stage (`videoflip` or `glvideoflip`).
+pub fn make_flip(
+    name: &str,
+    style: &InputStyle,
+    gl: bool,
+) -> Result<gst::Element, gst::glib::BoolError> {
+    let flip = gst::ElementFactory::make(if gl { "glvideoflip" } else { "videoflip" })
+        .name(name)
+        .build()?;
+    flip.set_property_from_str("video-direction", style.rotation.video_direction());
+    Ok(flip)
+}
+
+/// Create the CPU crop stage.
+pub fn make_crop(name: &str, style: &InputStyle) -> Result<gst::Element, gst::glib::BoolError> {
+    let crop = gst::ElementFactory::make("videocrop").name(name).build()?;
+    set_crop(&crop, style.crop);
+    Ok(crop)
+}
+
+fn set_crop(videocrop: &gst::Element, crop: Crop) {
+    videocrop.set_property("top", crop.top as i32);
+    videocrop.set_property("bottom", crop.bottom as i32);
+    videocrop.set_property("left", crop.left as i32);
+    videocrop.set_property("right", crop.right as i32);
+}
+
+/// Caps forced in front of a CPU mixer pad so the style probe can draw.
+pub fn cpu_style_caps() -> gst::Caps {
+    gst::Caps::builder("video/x-raw")
+        .field("format", "BGRA")
+        .build()
+}
+
+/// Draw the style into one BGRA frame in place.
+///
+/// `rect` is the pad rectangle in canvas pixels the frame is scaled to.
+/// Only the corner, border and shadow bands are evaluated per pixel; the
+/// interior is left as it is, or with a shadow copied into the shrunken
+/// picture box.
+fn apply_cpu(data: &mut [u8], width: usize, height: usize, style: &InputStyle, rect: [f32; 2]) {
+    let stride = width * 4;
+    if data.len() < stride * height {
+        return;
+    }
+    let geometry = StyleGeometry::new(style, rect[0], rect[1]);
+    let scale = [rect[0] / width as f32, rect[1] / height as f32];
+    // With a shadow the picture shrinks into the content box. Every pixel
+    // then reads from itself or a pixel after it, so the frame can be
+    // resampled in place front to back.
+    let shrink = geometry.shadow_size > 0.0;
+    let source = |axis: usize, i: usize, len: usize| {
+        if shrink {
+            let c = (i as f32 + 0.5) * scale[axis];
+            ((c / geometry.content[axis] * len as f32) as usize).min(len - 1)
+        } else {
+            i
+        }
+    };
+    let source_x: Vec<usize> = (0..width).map(|x| source(0, x, width)).collect();
+    let (x0, x1) = geometry.interior_span(0, width, scale[0]);
+    let (y0, y1) = geometry.interior_span(1, height, scale[1]);
+
+    let shade = |data: &mut [u8], x: usize, y: usize, at: usize| {
+        let sample = geometry.sample((x as f32 + 0.5) * scale[0], (y as f32 + 0.5) * scale[1]);
+        let src = [data[at], data[at + 1], data[at + 2], data[at + 3]];
+        let rgb = [src[2], src[1], src[0]].map(|c| c as f32 / 255.0);
+        let (rgb, alpha) = compose(rgb, src[3] as f32 / 255.0, sample, style.border_color);
+        let out = [rgb[2], rgb[1], rgb[0], alpha].map(|c| (c * 255.0).round() as u8);
+        let offset = y * stride + x * 4;
+        data[offset..offset + 4].copy_from_slice(&out);
+    };
+
+    for y in 0..height {
+        let row = source(1, y, height) * stride;
+        let interior = if (y0..y1).contains(&y) { x0..x1 } else { 0..0 };
+        for (x, &sx) in source_x.iter().enumerate().take(interior.start) {
+            shade(data, x, y, row + sx * 4);
+        }
+        if shrink {
+            let span = source_x.iter().enumerate().take(interior.end);
+            for (x, &sx) in span.skip(interior.start) {
+                let at = row + sx * 4;
+                let offset = y * stride + x * 4;
+                data.copy_within(at..at + 4, offset);
+                // A translucent picture still shows the shadow underneath
+                if data[offset + 3] < 255 {
+                    shade(data, x, y, offset);
+                }
+            }
+        }
+        for (x, &sx) in source_x.iter().enumerate().skip(interior.end) {
+            shade(data, x, y, row + sx * 4);
+        }
+    }
+}
+
+/// Add the CPU style probe to `pad`, which must carry [`cpu_style_caps`].
+pub fn add_cpu_probe(
+    pad: &gst::Pad,
+    style: Arc<Mutex<InputStyle>>,
+    mixer_pad: &gst::Pad,
+) -> Option<gst::PadProbeId> {
+    let mixer_pad = mixer_pad.downgrade();
+    pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+        let style = *style.lock();
+        if !style.has_look() {
+            return gst::PadProbeReturn::Ok;
+        }
+        let Some((width, height)) = pad.current_caps().and_then(|caps| {
+            let s = caps.structure(0)?;
+            Some((s.get::<i32>("width").ok()?, s.get::<i32>("height").ok()?))
+        }) else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let (width, height) = (width.max(1) as usize, height.max(1) as usize);
+        let (rect_w, rect_h) = mixer_pad
+            .upgrade()
+            .map(|p| pad_rect(&p))
+            .unwrap_or_default();
+        let rect = [
+            if rect_w > 0 {
+                rect_w as f32
+            } else {
+                width as f32
+            },
+            if rect_h > 0 {
+                rect_h as f32
+            } else {
+                height as f32
+            },
+        ];
+        if let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data {
+            let buffer = buffer.make_mut();
+            match buffer.map_writable() {
+                Ok(mut map) => apply_cpu(map.as_mut_slice(), width, height, &style, rect),
+                Err(_) => warn!("Style probe could not map buffer writable"),
+            }
+        }
+        gst::PadProbeReturn::Ok
+    })
+}
+
+/// Running style stages of one compositor input.
+pub struct StyledInput {
+    style: Arc<Mutex<InputStyle>>,
+    mixer_pad: gst::Pad,
+    /// CPU: `videocrop` in front of the flip.
+    crop: Option<gst::Element>,
+    flip: gst::Element,
+    /// GPU: style shader (also does the cropping).
+    shader: Option<gst::Element>,
+}
+
+impl StyledInput {
+    pub fn new(
+        style: Arc<Mutex<InputStyle>>,
+        mixer_pad: gst::Pad,
+        crop: Option<gst::Element>,
+        flip: gst::Element,
+        shader: Option<gst::Element>,
+    ) -> Self {
+        Self {
+            style,
+            mixer_pad,
+            crop,
+            flip,
+            shader,
+        }
+    }
+
+    fn update(&self, property: &str, value: &PropertyValue) -> Result<(), String> {
+        let style = {
+            let mut style = self.style.lock();
+            style.set(property, value)?;
+            *style
+        };
+        if let Some(crop) = &self.crop {
+            set_crop(crop, style.crop);
+        }
+        self.flip
+            .set_property_from_str("video-direction", style.rotation.video_direction());
+        self.refresh();
+        debug!(
+            "Style of {} updated: {} = {:?}",
+            self.mixer_pad.name(),
+            property,
+            value
+        );
+        Ok(())
+    }
+
+    /// Push the current style and pad size to the GPU shader.
+    fn refresh(&self) {
+        if let Some(shader) = &self.shader {
+            let style = *self.style.lock();
+            shader.set_property("uniforms", gl_uniforms(&style, pad_rect(&self.mixer_pad)));
+        }
+    }
+}
+
+/// Registry key for looking up compositor styles.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct StyleKey {
+    pub flow_id: FlowId,
+    pub block_id: String,
+}
+
+/// Global registry for the style stages of running compositors.
+pub struct StyleRegistry {
+    blocks: RwLock<HashMap<StyleKey, Arc<Vec<StyledInput>>>>,
+}
+
+impl StyleRegistry {
+    pub fn new() -> Self {
+        Self {
+            blocks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, key: StyleKey, inputs: Vec<StyledInput>) {
+        self.blocks.write().insert(key, Arc::new(inputs));
+    }
+
+    pub fn get(&self, key: &StyleKey) -> Option<Arc<Vec<StyledInput>>> {
+        self.blocks.read().get(key).cloned()
+    }
+
+    /// Remove all style entries for a given flow.
+    pub fn unregister_flow(&self, flow_id: &FlowId) {
+        self.blocks.write().retain(|k, _| k.flow_id != *flow_id);
+    }
+}
+
+impl Default for StyleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global compositor style registry.
+pub static STYLE_REGISTRY: LazyLock<StyleRegistry> = LazyLock::new(StyleRegistry::new);
+
+/// Split `{block}:mixer` / `sink_N` into block ID and input index.
+pub(crate) fn parse_mixer_pad<'a>(element_id: &'a str, pad_name: &str) -> Option<(&'a str, usize)> {
+    let block_id = element_id.strip_suffix(":mixer")?;
+    let index = pad_name.strip_prefix("sink_")?.parse::<usize>().ok()?;
+    Some((block_id, index))
+}
+
+fn styled_inputs(flow_id: &FlowId, block_id: &str) -> Option<Arc<Vec<StyledInput>>> {
+    STYLE_REGISTRY.get(&StyleKey {
+        flow_id: *flow_id,
+        block_id: block_id.to_string(),
+    })
+}
+
+/// Apply a live style property change on a compositor mixer pad.
+///
+/// Returns `None` if `property_name` is not a style property of a mixer
+/// `sink_N` pad, so the caller can fall through to the regular pad property
+/// path.
+pub fn update_pad_property(
+    flow_id: &FlowId,
+    element_id: &str,
+    pad_name: &str,
+    property_name: &str,
+    value: &PropertyValue,
+) -> Option<Result<(), String>> {
+    if !STYLE_PROPERTIES.contains(&property_name) {
+        return None;
+    }
+    let (block_id, index) = parse_mixer_pad(element_id, pad_name)?;
+    let Some(inputs) = styled_inputs(flow_id, block_id) else {
+        return Some(Err(format!(
+            "Input styling is not enabled on block {}",
+            block_id
+        )));
+    };
+    Some(match inputs.get(index) {
+        Some(input) => input.update(property_name, value),
+        None => Err(format!("Block {} has no input {}", block_id, index)),
+    })
+}
+
+/// Re-sync the GPU style stage after the mixer pad was resized.
+pub fn pad_resized(flow_id: &FlowId, element_id: &str, pad_name: &str) {
+    let Some((block_id, index)) = parse_mixer_pad(element_id, pad_name) else {
+        return;
+    };
+    if let Some(inputs) = styled_inputs(flow_id, block_id) {
+        if let Some(input) = inputs.get(index) {
+            input.refresh();
+        }
+    }
+}
+
+/// Current style properties of a compositor mixer pad, if styling is enabled.
+pub fn pad_properties(
+    flow_id: &FlowId,
+    element_id: &str,
+    pad_name: &str,
+) -> Vec<(String, PropertyValue)> {
+    let Some((block_id, index)) = parse_mixer_pad(element_id, pad_name) else {
+        return Vec::new();
+    };
+    let Some(style) = styled_inputs(flow_id, block_id)
+        .and_then(|inputs| inputs.get(index).map(|input| *input.style.lock()))
+    else {
+        return Vec::new();
+    };
+    STYLE_PROPERTIES
+        .iter()
+        .filter_map(|name| Some((name.to_string(), style.get(name)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_from_properties() {
+        let mut props = HashMap::new();
+        props.insert("input_1_crop_left".to_string(), PropertyValue::UInt(40));
+        props.insert(
+            "input_1_rotation".to_string(),
+            PropertyValue::String("270".to_string()),
+        );
+        props.insert(
+            "input_1_border_color".to_string(),
+            PropertyValue::String("#ff0000".to_string()),
+        );
+        props.insert("input_1_shadow_size".to_string(), PropertyValue::Int(12));
+        // Invalid values fall back to defaults
+        props.insert("input_1_corner_radius".to_string(), PropertyValue::Int(-3));
+
+        let style = InputStyle::from_properties(&props, 1);
+        assert_eq!(style.crop.left, 40);
+        assert_eq!(style.rotation, Rotation::Cw270);
+        assert_eq!(style.border_color, [1.0, 0.0, 0.0]);
+        assert_eq!(style.shadow_size, 12.0);
+        assert_eq!(style.corner_radius, 0.0);
+        assert_eq!(
+            InputStyle::from_properties(&props, 0),
+            InputStyle::default()
+        );
+    }
+
+    #[test]
+    fn test_style_set_and_get_round_trip() {
+        let mut style = InputStyle::default();
+        assert!(style.set("rotation", &PropertyValue::UInt(45)).is_err());
+        assert!(style
+            .set("shadow-opacity", &PropertyValue::Float(1.5))
+            .is_err());
+        assert!(style
+            .set("border-color", &PropertyValue::String("red".to_string()))
+            .is_err());
+        for name in STYLE_PROPERTIES {
+            let value = style.get(name).unwrap();
+            style.set(name, &value).unwrap();
+        }
+        assert_eq!(style, InputStyle::default());
+        assert!(!style.has_look());
+    }
+
+    #[test]
+    fn test_crop_follows_rotation() {
+        let crop = Crop {
+            top: 1,
+            bottom: 2,
+            left: 3,
+            right: 4,
+        };
+        assert_eq!(crop.rotated(Rotation::None), crop);
+        let cw90 = crop.rotated(Rotation::Cw90);
+        assert_eq!((cw90.top, cw90.right, cw90.bottom, cw90.left), (3, 1, 4, 2));
+        // Rotating four times by 90 degrees is the identity
+        let full = (0..4).fold(crop, |c, _| c.rotated(Rotation::Cw90));
+        assert_eq!(full, crop);
+        assert_eq!(
+            crop.rotated(Rotation::Cw90).rotated(Rotation::Cw180),
+            crop.rotated(Rotation::Cw270)
+        );
+    }
+
+    #[test]
+    fn test_geometry_corners_border_and_shadow() {
+        let style = InputStyle {
+            border_width: 4.0,
+            corner_radius: 10.0,
+            ..Default::default()
+        };
+        let g = StyleGeometry::new(&style, 100.0, 50.0);
+        // Centre shows the picture, the edge is border, the corner is cut
+        assert_eq!(g.sample(50.0, 25.0).coverage, 1.0);
+        assert_eq!(g.sample(50.0, 25.0).inner, 1.0);
+        assert_eq!(g.sample(50.0, 1.0).inner, 0.0);
+        assert_eq!(g.sample(0.5, 0.5).coverage, 0.0);
+
+        let shadowed = InputStyle {
+            shadow_size: 10.0,
+            shadow_opacity: 0.5,
+            ..Default::default()
+        };
+        let g = StyleGeometry::new(&shadowed, 100.0, 50.0);
+        assert_eq!(g.content, [90.0, 40.0]);
+        // Outside the picture at the bottom right lies the shadow
+        let s = g.sample(94.0, 44.0);
+        assert_eq!(s.coverage, 0.0);
+        assert!(s.shadow > 0.0 && s.shadow <= 0.5);
+        // The top-right corner of the margin has no shadow
+        assert_eq!(g.sample(99.5, 0.5).shadow, 0.0);
+    }
+
+    #[test]
+    fn test_compose() {
+        let border = [1.0, 0.0, 0.0];
+        let full = StyleSample {
+            coverage: 1.0,
+            inner: 1.0,
+            shadow: 0.0,
+        };
+        assert_eq!(
+            compose([0.25, 0.5, 0.75], 1.0, full, border),
+            ([0.25, 0.5, 0.75], 1.0)
+        );
+        let on_border = StyleSample { inner: 0.0, ..full };
+        assert_eq!(
+            compose([0.25, 0.5, 0.75], 0.0, on_border, border),
+            (border, 1.0)
+        );
+        let shadow_only = StyleSample {
+            coverage: 0.0,
+            inner: 1.0,
+            shadow: 0.5,
+        };
+        assert_eq!(
+            compose([0.25, 0.5, 0.75], 1.0, shadow_only, border),
+            ([0.0; 3], 0.5)
+        );
+    }
+
+    #[test]
+    fn test_apply_cpu_rounds_corners() {
+        let style = InputStyle {
+            corner_radius: 2.0,
+            ..Default::default()
+        };
+        let mut frame = [10u8, 20, 30, 255].repeat(16);
+        apply_cpu(&mut frame, 4, 4, &style, [4.0, 4.0]);
+        // Corner pixel is cut away, the centre is untouched
+        assert!(frame[3] < 255);
+        assert_eq!(&frame[(4 + 1) * 4..(4 + 1) * 4 + 4], &[10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_apply_cpu_matches_per_pixel_style() {
+        let style = InputStyle {
+            border_width: 3.0,
+            corner_radius: 6.0,
+            shadow_size: 8.0,
+            ..Default::default()
+        };
+        let (width, height) = (48, 27);
+        let rect = [96.0, 54.0];
+        let frame: Vec<u8> = (0..width * height)
+            .flat_map(|i| [i as u8, (i / 3) as u8, (i * 7) as u8, 255])
+            .collect();
+
+        // Every pixel sampled and composited from an untouched copy
+        let geometry = StyleGeometry::new(&style, rect[0], rect[1]);
+        let mut expected = frame.clone();
+        for y in 0..height {
+            for x in 0..width {
+                let (cx, cy) = ((x as f32 + 0.5) * 2.0, (y as f32 + 0.5) * 2.0);
+                let sx = ((cx / geometry.content[0] * width as f32) as usize).min(width - 1);
+                let sy = ((cy / geometry.content[1] * height as f32) as usize).min(height - 1);
+                let at = (sy * width + sx) * 4;
+                let rgb = [frame[at + 2], frame[at + 1], frame[at]].map(|c| c as f32 / 255.0);
+                let (rgb, alpha) = compose(
+                    rgb,
+                    frame[at + 3] as f32 / 255.0,
+                    geometry.sample(cx, cy),
+                    style.border_color,
+                );
+                let out = [rgb[2], rgb[1], rgb[0], alpha].map(|c| (c * 255.0).round() as u8);
+                let offset = (y * width + x) * 4;
+                expected[offset..offset + 4].copy_from_slice(&out);
+            }
+        }
+
+        let mut actual = frame;
+        apply_cpu(&mut actual, width, height, &style, rect);
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,12 +3,15 @@
 //! This module provides animated transitions between compositor inputs using
 //! GStreamer's interpolation control source to animate pad properties over time.
 
+use super::wipes::{self, WipePattern, WipeSettings};
 use gstreamer as gst;
+use gstreamer::glib;
 use gstreamer::prelude::*;
 use gstreamer_controller::prelude::*;
 use gstreamer_controller::{DirectControlBinding, InterpolationControlSource, InterpolationMode};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use strom_types::compositor::InputLayout;
 use strom_types::vision_mixer;
 use tracing::{debug, info};
 
@@ -37,13 +40,22 @@ pub enum TransitionType {
     PushDown,
     /// Dip to black then reveal new source.
     DipToBlack,
+    /// Play a stinger clip over the programme and cut underneath at its cut point
+    /// (vision mixer only).
+    Stinger,
+    /// SMPTE pattern wipe revealing the new source, optionally reversed.
+    Wipe { pattern: WipePattern, reverse: bool },
 }
 
 impl std::str::FromStr for TransitionType {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        let s = s.to_lowercase();
+        if let Some((pattern, reverse)) = wipes::parse_wipe(&s) {
+            return Ok(Self::Wipe { pattern, reverse });
+        }
+        match s.as_str() {
             "cut" => Ok(Self::Cut),
             "fade" | "dissolve" | "crossfade" => Ok(Self::Fade),
             "slide_left" | "slideleft" => Ok(Self::SlideLeft),
@@ -55,6 +67,7 @@ impl std::str::FromStr for TransitionType {
             "push_up" | "pushup" => Ok(Self::PushUp),
             "push_down" | "pushdown" => Ok(Self::PushDown),
             "dip_to_black" | "diptoblack" | "dip" => Ok(Self::DipToBlack),
+            "stinger" | "sting" => Ok(Self::Stinger),
             _ => Err(format!("Unknown transition type: {}", s)),
         }
     }
@@ -77,6 +90,123 @@ pub enum TransitionError {
     ControlSourceError(String),
     #[error("GStreamer error: {0}")]
     GstError(String),
+    #[error("Unsupported transition: {0}")]
+    Unsupported(String),
+}
+
+/// Error binding a control source to a property.
+#[derive(Debug, thiserror::Error)]
+pub enum BindingError {
+    #[error("no such property")]
+    NoSuchProperty,
+    #[error("not controllable")]
+    NotControllable,
+    #[error("not numeric")]
+    NotNumeric,
+    #[error("already driven by another control binding")]
+    Busy,
+    #[error("{0}")]
+    GstError(String),
+}
+
+/// Range of a numeric, controllable property.
+pub fn property_range(object: &gst::Object, property: &str) -> Result<(f64, f64), BindingError> {
+    let pspec = object
+        .find_property(property)
+        .ok_or(BindingError::NoSuchProperty)?;
+    if !pspec.flags().contains(gst::PARAM_FLAG_CONTROLLABLE) {
+        return Err(BindingError::NotControllable);
+    }
+    if let Some(p) = pspec.downcast_ref::<glib::ParamSpecDouble>() {
+        Ok((p.minimum(), p.maximum()))
+    } else if let Some(p) = pspec.downcast_ref::<glib::ParamSpecFloat>() {
+        Ok((p.minimum() as f64, p.maximum() as f64))
+    } else if let Some(p) = pspec.downcast_ref::<glib::ParamSpecInt>() {
+        Ok((p.minimum() as f64, p.maximum() as f64))
+    } else if let Some(p) = pspec.downcast_ref::<glib::ParamSpecUInt>() {
+        Ok((p.minimum() as f64, p.maximum() as f64))
+    } else if let Some(p) = pspec.downcast_ref::<glib::ParamSpecInt64>() {
+        Ok((p.minimum() as f64, p.maximum() as f64))
+    } else if let Some(p) = pspec.downcast_ref::<glib::ParamSpecUInt64>() {
+        Ok((p.minimum() as f64, p.maximum() as f64))
+    } else {
+        Err(BindingError::NotNumeric)
+    }
+}
+
+/// A linear control binding on a numeric property, owned by its creator.
+///
+/// Points are given in property units and normalized to the property
+/// range. Binding fails while another binding drives the property, and
+/// dropping the `PropertyBinding` removes its own binding only, so
+/// automation lanes, property ramps and animations sharing a property
+/// never unbind each other.
+pub struct PropertyBinding {
+    object: glib::WeakRef<gst::Object>,
+    property: String,
+    binding: gst::ControlBinding,
+    control_source: InterpolationControlSource,
+    range: (f64, f64),
+}
+
+impl PropertyBinding {
+    /// Bind a linear control source to `object.property`.
+    pub fn bind(object: &impl IsA<gst::Object>, property: &str) -> Result<Self, BindingError> {
+        let object = object.upcast_ref::<gst::Object>();
+        let range = property_range(object, property)?;
+        if object.control_binding(property).is_some() {
+            return Err(BindingError::Busy);
+        }
+
+        let control_source = InterpolationControlSource::new();
+        control_source.set_mode(InterpolationMode::Linear);
+        let binding = DirectControlBinding::new(object, property, &control_source);
+        object
+            .add_control_binding(&binding)
+            .map_err(|e| BindingError::GstError(e.to_string()))?;
+
+        Ok(Self {
+            object: object.downgrade(),
+            property: property.to_string(),
+            binding: binding.upcast(),
+            control_source,
+            range,
+        })
+    }
+
+    /// Add a control point, clamping `value` to the property range.
+    pub fn set(&self, time: gst::ClockTime, value: f64) -> bool {
+        let (min, max) = self.range;
+        let normalized = if max > min {
+            (value.clamp(min, max) - min) / (max - min)
+        } else {
+            0.0
+        };
+        self.control_source.set(time, normalized)
+    }
+
+    /// Remove the control point at `time`.
+    pub fn unset(&self, time: gst::ClockTime) -> bool {
+        self.control_source.unset(time)
+    }
+
+    /// Whether this binding still drives the property.
+    pub fn is_bound(&self) -> bool {
+        self.object
+            .upgrade()
+            .and_then(|object| object.control_binding(&self.property))
+            .is_some_and(|binding| binding == self.binding)
+    }
+}
+
+impl Drop for PropertyBinding {
+    fn drop(&mut self) {
+        if self.is_bound() {
+            if let Some(object) = self.object.upgrade() {
+                object.remove_control_binding(&self.binding);
+            }
+        }
+    }
 }
 
 /// Manages transitions for a compositor element.
@@ -90,6 +220,8 @@ pub struct TransitionController {
     /// Active control sources for ongoing transitions (pad_name -> control_sources).
     /// We keep references to prevent them from being dropped during animation.
     active_transitions: Arc<Mutex<HashMap<String, Vec<InterpolationControlSource>>>>,
+    /// Edge and border look used by wipe transitions.
+    wipe_settings: WipeSettings,
 }
 
 impl TransitionController {
@@ -100,9 +232,16 @@ impl TransitionController {
             canvas_width,
             canvas_height,
             active_transitions: Arc::new(Mutex::new(HashMap::new())),
+            wipe_settings: WipeSettings::default(),
         }
     }
 
+    /// Use the given soft edge and border for wipe transitions.
+    pub fn with_wipe_settings(mut self, settings: WipeSettings) -> Self {
+        self.wipe_settings = settings;
+        self
+    }
+
     /// Get a sink pad by input index.
     fn get_sink_pad(&self, input_index: usize) -> Result<gst::Pad, TransitionError> {
         // Try sink_0, sink_1, etc.
@@ -169,6 +308,7 @@ impl TransitionController {
         if let Ok(mut transitions) = self.active_transitions.lock() {
             transitions.clear();
         }
+        wipes::cancel(&self.mixer);
 
         // Adjust for pipeline latency so keyframes align with compositor processing
         let current_time = self.query_stream_time(pipeline)?;
@@ -212,7 +352,54 @@ impl TransitionController {
             TransitionType::DipToBlack => {
                 self.transition_dip_to_black(from_input, to_input, current_time, end_time)
             }
+            TransitionType::Stinger => Err(TransitionError::Unsupported(
+                "stinger transitions require a vision mixer with a stinger clip".to_string(),
+            )),
+            TransitionType::Wipe { pattern, reverse } => self.transition_wipe(
+                from_input,
+                to_input,
+                pattern,
+                reverse,
+                current_time,
+                end_time,
+            ),
+        }
+    }
+
+    /// Perform a pattern wipe.
+    ///
+    /// Wipes shape the incoming pad's alpha per pixel rather than animating pad
+    /// properties, but follow the same stream-time window as the control
+    /// bindings of the other transitions.
+    fn transition_wipe(
+        &self,
+        from_input: usize,
+        to_input: usize,
+        pattern: WipePattern,
+        reverse: bool,
+        start_time: gst::ClockTime,
+        end_time: gst::ClockTime,
+    ) -> Result<(), TransitionError> {
+        if end_time <= start_time {
+            return self.transition_cut(from_input, to_input);
         }
+
+        let from_pad = self.get_sink_pad(from_input)?;
+        let to_pad = self.get_sink_pad(to_input)?;
+
+        self.clear_control_bindings(&from_pad);
+        self.clear_control_bindings(&to_pad);
+
+        wipes::start(
+            &self.mixer,
+            &from_pad,
+            &to_pad,
+            pattern,
+            reverse,
+            self.wipe_settings,
+            start_time,
+            end_time,
+        )
     }
 
     /// Perform an instant cut transition.
@@ -675,6 +862,7 @@ impl TransitionController {
         if let Ok(mut transitions) = self.active_transitions.lock() {
             transitions.clear();
         }
+        wipes::cancel(&self.mixer);
 
         // Adjust for pipeline latency so keyframes align with compositor processing
         let current_time = self.query_stream_time(pipeline)?;
@@ -824,6 +1012,96 @@ impl TransitionController {
 
         Ok(())
     }
+
+    /// Move every input in `layout` to its placement in one animation.
+    ///
+    /// Position, size and alpha glide from their current values with the
+    /// same easing as the other transitions; z-order has no in-between
+    /// values and changes when the move starts. A zero duration cuts.
+    pub fn animate_layout(
+        &self,
+        layout: &[InputLayout],
+        duration_ms: u64,
+        pipeline: &gst::Pipeline,
+    ) -> Result<(), TransitionError> {
+        let pads = layout
+            .iter()
+            .map(|entry| Ok((self.get_sink_pad(entry.input)?, entry)))
+            .collect::<Result<Vec<_>, TransitionError>>()?;
+
+        // Clean up previous animations
+        if let Ok(mut transitions) = self.active_transitions.lock() {
+            transitions.clear();
+        }
+        wipes::cancel(&self.mixer);
+        for (pad, _) in &pads {
+            self.clear_control_bindings(pad);
+        }
+
+        if duration_ms == 0 {
+            for (pad, entry) in &pads {
+                pad.set_property("xpos", entry.xpos);
+                pad.set_property("ypos", entry.ypos);
+                pad.set_property("width", entry.width);
+                pad.set_property("height", entry.height);
+                pad.set_property("alpha", entry.alpha);
+                pad.set_property("zorder", entry.zorder);
+            }
+            info!("Cut to layout of {} inputs", pads.len());
+            return Ok(());
+        }
+
+        // Adjust for pipeline latency so keyframes align with compositor processing
+        let current_time = self.query_stream_time(pipeline)?;
+        let end_time = current_time + gst::ClockTime::from_mseconds(duration_ms);
+
+        let mut control_sources = Vec::new();
+        for (pad, entry) in &pads {
+            pad.set_property("zorder", entry.zorder);
+
+            for (prop, target) in [
+                ("xpos", entry.xpos),
+                ("ypos", entry.ypos),
+                ("width", entry.width),
+                ("height", entry.height),
+            ] {
+                let current = pad.property::<i32>(prop);
+                if current != target {
+                    control_sources.push(self.setup_int_animation(
+                        pad,
+                        prop,
+                        current_time,
+                        end_time,
+                        current,
+                        target,
+                    )?);
+                }
+            }
+
+            let current = pad.property::<f64>("alpha");
+            if (current - entry.alpha).abs() > f64::EPSILON {
+                control_sources.push(self.setup_alpha_animation(
+                    pad,
+                    current_time,
+                    end_time,
+                    current,
+                    entry.alpha,
+                )?);
+            }
+        }
+
+        if let Ok(mut transitions) = self.active_transitions.lock() {
+            transitions.insert("animate_layout".to_string(), control_sources);
+        }
+
+        info!(
+            "Animating layout of {} inputs over {}ms",
+            pads.len(),
+            duration_ms
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -864,6 +1142,50 @@ mod tests {
             "dip".parse::<TransitionType>().ok(),
             Some(TransitionType::DipToBlack)
         );
+        assert_eq!(
+            "stinger".parse::<TransitionType>().ok(),
+            Some(TransitionType::Stinger)
+        );
+        assert_eq!(
+            "wipe_clock".parse::<TransitionType>().ok(),
+            Some(TransitionType::Wipe {
+                pattern: WipePattern::Clock,
+                reverse: false
+            })
+        );
+        assert_eq!(
+            "SMPTE_119_reverse".parse::<TransitionType>().ok(),
+            Some(TransitionType::Wipe {
+                pattern: WipePattern::Iris,
+                reverse: true
+            })
+        );
         assert!("unknown".parse::<TransitionType>().is_err());
     }
+
+    #[test]
+    fn test_property_binding_ownership() {
+        gst::init().unwrap();
+        let volume = gst::ElementFactory::make("volume").build().unwrap();
+
+        let first = PropertyBinding::bind(&volume, "volume").unwrap();
+        assert!(first.is_bound());
+        assert!(matches!(
+            PropertyBinding::bind(&volume, "volume"),
+            Err(BindingError::Busy)
+        ));
+        assert!(matches!(
+            PropertyBinding::bind(&volume, "no-such-property"),
+            Err(BindingError::NoSuchProperty)
+        ));
+
+        // A binding removed by someone else is left alone on drop
+        let foreign = volume.control_binding("volume").unwrap();
+        volume.remove_control_binding(&foreign);
+        let second = PropertyBinding::bind(&volume, "volume").unwrap();
+        drop(first);
+        assert!(second.is_bound());
+        drop(second);
+        assert!(volume.control_binding("volume").is_none());
+    }
 }
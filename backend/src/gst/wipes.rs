@@ -0,0 +1,800 @@
+//! SMPTE pattern wipes for compositor transitions.
+//!
+//! A wipe reveals the incoming source through a moving pattern mask instead of
+//! animating pad properties. The incoming pad is stacked above the outgoing
+//! one and its alpha is shaped per pixel:
+//!
+//! - **GPU**: a `glshader` element in front of each mixer pad runs
+//!   [`WIPE_FRAGMENT_SHADER`]; a buffer probe on its sink pad updates the
+//!   uniforms for every frame while the wipe runs, and they are left at
+//!   `progress = 1.0` (passthrough) otherwise.
+//! - **CPU**: the mixer pad is fed packed RGB with alpha ([`cpu_wipe_caps`])
+//!   and a buffer probe applies a precomputed mask to every frame during the
+//!   wipe.
+//!
+//! Both backends evaluate the same mask (see [`mask_value`]) so a wipe looks
+//! identical regardless of where it runs. Progress follows the timeline the
+//! other transitions animate on: each frame's running time against the
+//! mixer's stream time when the wipe started (see [`WipeClock`]), so a wipe
+//! is frame-accurate and unaffected by load or pipeline latency.
+
+use super::transitions::TransitionError;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use strom_types::PropertyValue;
+use tracing::{debug, info, warn};
+
+/// Element factory used for the GPU wipe stage.
+pub const GL_WIPE_FACTORY: &str = "glshader";
+
+/// Time past the end of a wipe before it is completed even if no frame
+/// reached the end (e.g. the incoming source stalled).
+const WIPE_SETTLE_MARGIN: Duration = Duration::from_secs(1);
+
+/// Wipe pattern, identified by its SMPTE 258M wipe code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WipePattern {
+    /// Horizontal bar wiping left to right (SMPTE 1).
+    BarHorizontal,
+    /// Vertical bar wiping top to bottom (SMPTE 2).
+    BarVertical,
+    /// Box growing from the top-left corner (SMPTE 3).
+    Box,
+    /// Diamond iris opening from the centre (SMPTE 102).
+    Diamond,
+    /// Circular iris opening from the centre (SMPTE 119).
+    Iris,
+    /// Clock hand sweeping clockwise from 12 o'clock (SMPTE 201).
+    Clock,
+}
+
+impl WipePattern {
+    /// All supported patterns.
+    pub const ALL: [WipePattern; 6] = [
+        Self::BarHorizontal,
+        Self::BarVertical,
+        Self::Box,
+        Self::Diamond,
+        Self::Iris,
+        Self::Clock,
+    ];
+
+    /// SMPTE wipe code of this pattern.
+    pub fn smpte_code(self) -> u32 {
+        match self {
+            Self::BarHorizontal => 1,
+            Self::BarVertical => 2,
+            Self::Box => 3,
+            Self::Diamond => 102,
+            Self::Iris => 119,
+            Self::Clock => 201,
+        }
+    }
+
+    /// Look up a pattern by SMPTE wipe code.
+    pub fn from_smpte_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.smpte_code() == code)
+    }
+
+    /// Look up a pattern by name ("bar", "bar_v", "box", "diamond", "iris",
+    /// "clock") or by SMPTE code ("smpte_119").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bar" | "bar_h" => Some(Self::BarHorizontal),
+            "bar_v" => Some(Self::BarVertical),
+            "box" => Some(Self::Box),
+            "diamond" => Some(Self::Diamond),
+            "iris" | "circle" => Some(Self::Iris),
+            "clock" => Some(Self::Clock),
+            _ => name
+                .strip_prefix("smpte_")
+                .and_then(|code| code.parse::<u32>().ok())
+                .and_then(Self::from_smpte_code),
+        }
+    }
+}
+
+/// Parse a wipe transition name into (pattern, reverse).
+///
+/// Accepts `wipe_<pattern>` and `smpte_<code>`, each with an optional
+/// `_reverse` suffix, e.g. `wipe_iris_reverse` or `smpte_201`.
+pub fn parse_wipe(name: &str) -> Option<(WipePattern, bool)> {
+    let (base, reverse) = match name.strip_suffix("_reverse") {
+        Some(base) => (base, true),
+        None => (name, false),
+    };
+    let pattern = if let Some(pattern) = base.strip_prefix("wipe_") {
+        WipePattern::from_name(pattern)?
+    } else if base.starts_with("smpte_") {
+        WipePattern::from_name(base)?
+    } else {
+        return None;
+    };
+    Some((pattern, reverse))
+}
+
+/// Edge and border look of a wipe, shared by every wipe on a block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WipeSettings {
+    /// Soft-edge width as a fraction of the pattern travel (0.0 = hard edge).
+    pub softness: f32,
+    /// Border width as a fraction of the pattern travel (0.0 = no border).
+    pub border_width: f32,
+    /// Border colour (RGB, 0.0-1.0).
+    pub border_color: [f32; 3],
+}
+
+impl Default for WipeSettings {
+    fn default() -> Self {
+        Self {
+            softness: 0.02,
+            border_width: 0.0,
+            border_color: [1.0, 1.0, 1.0],
+        }
+    }
+}
+
+impl WipeSettings {
+    /// Read `wipe_softness`, `wipe_border_width` and `wipe_border_color` from
+    /// block properties, falling back to defaults for missing values.
+    pub fn from_properties(properties: &HashMap<String, PropertyValue>) -> Self {
+        let default = Self::default();
+        let float = |key: &str, default: f32| {
+            properties
+                .get(key)
+                .and_then(|v| match v {
+                    PropertyValue::Float(f) => Some(*f as f32),
+                    PropertyValue::Int(i) => Some(*i as f32),
+                    PropertyValue::UInt(u) => Some(*u as f32),
+                    PropertyValue::String(s) => s.parse::<f32>().ok(),
+                    _ => None,
+                })
+                .unwrap_or(default)
+                .clamp(0.0, 1.0)
+        };
+        let border_color = properties
+            .get("wipe_border_color")
+            .and_then(|v| match v {
+                PropertyValue::String(s) => parse_hex_color(s),
+                _ => None,
+            })
+            .unwrap_or(default.border_color);
+
+        Self {
+            softness: float("wipe_softness", default.softness),
+            border_width: float("wipe_border_width", default.border_width),
+            border_color,
+        }
+    }
+}
+
+/// Parse a `#RRGGBB` colour into normalized RGB.
+pub(crate) fn parse_hex_color(s: &str) -> Option<[f32; 3]> {
+    let hex = s.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(&hex[i..i + 2], 16)
+            .ok()
+            .map(|c| c as f32 / 255.0)
+    };
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Mask value of a pixel in the range 0.0-1.0.
+///
+/// A pixel is revealed once the wipe front passes its mask value, so 0.0 is
+/// uncovered first and 1.0 last. `u`/`v` are normalized frame coordinates
+/// (0.0 = left/top); `width`/`height` give the aspect for the round patterns.
+pub fn mask_value(pattern: WipePattern, u: f64, v: f64, width: f64, height: f64) -> f64 {
+    let dx = (u - 0.5) * width;
+    let dy = (v - 0.5) * height;
+    let value = match pattern {
+        WipePattern::BarHorizontal => u,
+        WipePattern::BarVertical => v,
+        WipePattern::Box => u.max(v),
+        WipePattern::Diamond => (dx.abs() + dy.abs()) / (0.5 * (width + height)),
+        WipePattern::Iris => (dx * dx + dy * dy).sqrt() / (0.5 * width.hypot(height)),
+        WipePattern::Clock => dx.atan2(-dy).rem_euclid(TAU) / TAU,
+    };
+    value.clamp(0.0, 1.0)
+}
+
+/// Compute (coverage, inner) for a mask value at the given progress.
+///
+/// `coverage` is the incoming source's alpha multiplier; `inner` is how much
+/// of the incoming colour shows through the border (1.0 = no border). The
+/// front travels past 1.0 by the edge and border width so the last pixels
+/// fade in smoothly; progress 1.0 always leaves the source fully visible.
+pub fn edge(mask: f32, progress: f32, settings: &WipeSettings) -> (f32, f32) {
+    if progress >= 1.0 {
+        return (1.0, 1.0);
+    }
+    let softness = settings.softness.max(1e-4);
+    let front = progress * (1.0 + settings.softness + settings.border_width) - mask;
+    let coverage = (front / softness).clamp(0.0, 1.0);
+    let inner = ((front - settings.border_width) / softness).clamp(0.0, 1.0);
+    (coverage, inner)
+}
+
+/// GLSL fragment shader implementing the wipe for the `glshader` element.
+///
+/// `pattern` is the SMPTE code; the mask math mirrors [`mask_value`] and
+/// [`edge`]. The `width`/`height` uniforms are provided by `glshader`.
+pub const WIPE_FRAGMENT_SHADER: &str = r#"#version 100
+#ifdef GL_ES
+precision mediump float;
+#endif
+varying vec2 v_texcoord;
+uniform sampler2D tex;
+uniform float width;
+uniform float height;
+uniform int pattern;
+uniform float reverse;
+uniform float progress;
+uniform float softness;
+uniform float border_width;
+uniform float border_r;
+uniform float border_g;
+uniform float border_b;
+
+float mask_value(vec2 uv) {
+  vec2 d = (uv - 0.5) * vec2(width, height);
+  float m = uv.x;
+  if (pattern == 2) {
+    m = uv.y;
+  } else if (pattern == 3) {
+    m = max(uv.x, uv.y);
+  } else if (pattern == 102) {
+    m = (abs(d.x) + abs(d.y)) / (0.5 * (width + height));
+  } else if (pattern == 119) {
+    m = length(d) / (0.5 * length(vec2(width, height)));
+  } else if (pattern == 201) {
+    float a = atan(d.x, -d.y);
+    if (a < 0.0) {
+      a += 6.28318531;
+    }
+    m = a / 6.28318531;
+  }
+  return clamp(m, 0.0, 1.0);
+}
+
+void main () {
+  vec4 c = texture2D(tex, v_texcoord);
+  if (progress >= 1.0) {
+    gl_FragColor = c;
+    return;
+  }
+  float m = mask_value(v_texcoord);
+  if (reverse > 0.5) {
+    m = 1.0 - m;
+  }
+  float s = max(softness, 0.0001);
+  float front = progress * (1.0 + softness + border_width) - m;
+  float coverage = clamp(front / s, 0.0, 1.0);
+  float inner = clamp((front - border_width) / s, 0.0, 1.0);
+  vec3 rgb = mix(vec3(border_r, border_g, border_b), c.rgb, inner);
+  gl_FragColor = vec4(rgb, c.a * coverage);
+}
+"#;
+
+/// Build the `glshader` uniforms structure for a wipe state.
+pub fn gl_uniforms(
+    pattern: WipePattern,
+    reverse: bool,
+    progress: f32,
+    settings: &WipeSettings,
+) -> gst::Structure {
+    gst::Structure::builder("uniforms")
+        .field("pattern", pattern.smpte_code() as i32)
+        .field("reverse", if reverse { 1.0f32 } else { 0.0f32 })
+        .field("progress", progress)
+        .field("softness", settings.softness)
+        .field("border_width", settings.border_width)
+        .field("border_r", settings.border_color[0])
+        .field("border_g", settings.border_color[1])
+        .field("border_b", settings.border_color[2])
+        .build()
+}
+
+/// Uniforms of an idle GPU wipe stage (passthrough).
+fn idle_uniforms() -> gst::Structure {
+    gl_uniforms(
+        WipePattern::BarHorizontal,
+        false,
+        1.0,
+        &WipeSettings::default(),
+    )
+}
+
+/// Create an idle (passthrough) GPU wipe stage.
+pub fn make_gl_wipe(name: &str) -> Result<gst::Element, gst::glib::BoolError> {
+    gst::ElementFactory::make(GL_WIPE_FACTORY)
+        .name(name)
+        .property("fragment", WIPE_FRAGMENT_SHADER)
+        .property("uniforms", idle_uniforms())
+        .build()
+}
+
+/// Caps forced in front of a CPU mixer pad so the wipe probe can shape alpha.
+pub fn cpu_wipe_caps() -> gst::Caps {
+    gst::Caps::builder("video/x-raw")
+        .field("format", "BGRA")
+        .build()
+}
+
+/// Byte offsets of the colour and alpha channels in a packed 4-byte pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PixelLayout {
+    r: usize,
+    g: usize,
+    b: usize,
+    a: usize,
+}
+
+impl PixelLayout {
+    fn from_format(format: &str) -> Option<Self> {
+        match format {
+            "BGRA" => Some(Self {
+                r: 2,
+                g: 1,
+                b: 0,
+                a: 3,
+            }),
+            "RGBA" => Some(Self {
+                r: 0,
+                g: 1,
+                b: 2,
+                a: 3,
+            }),
+            "ARGB" => Some(Self {
+                r: 1,
+                g: 2,
+                b: 3,
+                a: 0,
+            }),
+            "ABGR" => Some(Self {
+                r: 3,
+                g: 2,
+                b: 1,
+                a: 0,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Precomputed per-pixel mask for CPU wipes.
+struct CpuMask {
+    values: Vec<f32>,
+    width: usize,
+    height: usize,
+    stride: usize,
+    layout: PixelLayout,
+}
+
+impl CpuMask {
+    fn new(
+        pattern: WipePattern,
+        reverse: bool,
+        width: usize,
+        height: usize,
+        layout: PixelLayout,
+    ) -> Self {
+        let mut values = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let v = (y as f64 + 0.5) / height as f64;
+            for x in 0..width {
+                let u = (x as f64 + 0.5) / width as f64;
+                let m = mask_value(pattern, u, v, width as f64, height as f64) as f32;
+                values.push(if reverse { 1.0 - m } else { m });
+            }
+        }
+        Self {
+            values,
+            width,
+            height,
+            // Packed 4-byte formats are always 4-byte aligned
+            stride: width * 4,
+            layout,
+        }
+    }
+
+    /// Shape alpha (and draw the border) of one frame in place.
+    fn apply(&self, data: &mut [u8], progress: f32, settings: &WipeSettings) {
+        let border = settings.border_color.map(|c| c * 255.0);
+        let l = self.layout;
+        for y in 0..self.height {
+            let Some(row) = data.get_mut(y * self.stride..(y + 1) * self.stride) else {
+                return;
+            };
+            let masks = &self.values[y * self.width..(y + 1) * self.width];
+            for (px, &m) in row.chunks_exact_mut(4).zip(masks) {
+                let (coverage, inner) = edge(m, progress, settings);
+                if coverage >= 1.0 && inner >= 1.0 {
+                    continue;
+                }
+                if inner < 1.0 {
+                    for (idx, c) in [(l.r, border[0]), (l.g, border[1]), (l.b, border[2])] {
+                        px[idx] = (c + (px[idx] as f32 - c) * inner) as u8;
+                    }
+                }
+                px[l.a] = (px[l.a] as f32 * coverage) as u8;
+            }
+        }
+    }
+}
+
+/// Maps frame times to wipe progress.
+///
+/// `start` and `end` are stream times of the mixer output, the times its pad
+/// control bindings are evaluated at. The mixer runs from a zero-based
+/// segment and aligns its inputs by running time, so a frame's running time
+/// on the way into the mixer is on the same timeline.
+#[derive(Debug, Clone, Copy)]
+struct WipeClock {
+    start: gst::ClockTime,
+    end: gst::ClockTime,
+}
+
+impl WipeClock {
+    /// Progress (0.0-1.0) of the frame at running time `time`.
+    fn progress(&self, time: gst::ClockTime) -> f32 {
+        if time <= self.start {
+            return 0.0;
+        }
+        let total = (self.end - self.start).nseconds().max(1) as f64;
+        ((time - self.start).nseconds() as f64 / total).min(1.0) as f32
+    }
+
+    /// Progress of the buffer a probe on `pad` is looking at.
+    fn buffer_progress(&self, pad: &gst::Pad, buffer: &gst::BufferRef) -> Option<f32> {
+        let pts = buffer.pts()?;
+        let segment = pad.sticky_event::<gst::event::Segment>(0)?;
+        let time_segment = segment.segment().downcast_ref::<gst::format::Time>()?;
+        Some(self.progress(time_segment.to_running_time(pts)?))
+    }
+}
+
+/// A wipe currently running on a mixer.
+struct ActiveWipe {
+    generation: u64,
+    /// The probe driving the wipe: on the incoming mixer pad for CPU wipes,
+    /// on the shader's sink pad for GPU wipes.
+    probe: Option<(gst::Pad, gst::PadProbeId)>,
+    /// GPU wipes: the shader to reset to passthrough.
+    shader: Option<gst::Element>,
+}
+
+/// Running wipes keyed by mixer element name.
+static ACTIVE_WIPES: LazyLock<Mutex<HashMap<String, ActiveWipe>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Generation counter so a stale wipe can detect it was superseded.
+static WIPE_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Stop any wipe running on `mixer`, leaving the incoming pad unmasked.
+pub fn cancel(mixer: &gst::Element) {
+    let removed = ACTIVE_WIPES
+        .lock()
+        .ok()
+        .and_then(|mut wipes| wipes.remove(mixer.name().as_str()));
+    if let Some(wipe) = removed {
+        release(wipe);
+        debug!("Cancelled running wipe on {}", mixer.name());
+    }
+}
+
+fn release(wipe: ActiveWipe) {
+    if let Some((pad, probe)) = wipe.probe {
+        pad.remove_probe(probe);
+    }
+    if let Some(shader) = wipe.shader {
+        shader.set_property("uniforms", idle_uniforms());
+    }
+}
+
+/// Finish a wipe if it is still the active one: unmask and hide the outgoing pad.
+fn complete(mixer_name: &str, generation: u64, from_pad: &gst::Pad) {
+    let wipe = {
+        let Ok(mut wipes) = ACTIVE_WIPES.lock() else {
+            return;
+        };
+        if wipes.get(mixer_name).map(|w| w.generation) != Some(generation) {
+            return;
+        }
+        wipes.remove(mixer_name)
+    };
+    if let Some(wipe) = wipe {
+        release(wipe);
+        from_pad.set_property("alpha", 0.0f64);
+        debug!("Wipe on {} complete", mixer_name);
+    }
+}
+
+/// Run a pattern wipe from `from_pad` to `to_pad` on `mixer`.
+///
+/// The incoming pad is raised above the outgoing one and revealed through
+/// the pattern between the mixer stream times `start` and `end`; once a
+/// frame at `end` passes, the outgoing pad is hidden. The pad must be fed by
+/// a GPU wipe stage or negotiated to a packed RGBA format.
+#[allow(clippy::too_many_arguments)]
+pub fn start(
+    mixer: &gst::Element,
+    from_pad: &gst::Pad,
+    to_pad: &gst::Pad,
+    pattern: WipePattern,
+    reverse: bool,
+    settings: WipeSettings,
+    start: gst::ClockTime,
+    end: gst::ClockTime,
+) -> Result<(), TransitionError> {
+    cancel(mixer);
+
+    let mixer_name = mixer.name().to_string();
+    let generation = WIPE_GENERATION.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+    let clock = WipeClock { start, end };
+    let finished = Arc::new(AtomicBool::new(false));
+
+    let shader = to_pad
+        .peer()
+        .and_then(|peer| peer.parent_element())
+        .filter(|element| {
+            // Other glshader stages (e.g. input styling) may feed the pad too
+            element
+                .factory()
+                .is_some_and(|f| f.name() == GL_WIPE_FACTORY)
+                && element.property::<Option<String>>("fragment").as_deref()
+                    == Some(WIPE_FRAGMENT_SHADER)
+        });
+
+    let mut active = ActiveWipe {
+        generation,
+        probe: None,
+        shader: shader.clone(),
+    };
+
+    // Completes the wipe once, from the probe that saw the last frame or
+    // from the fallback timer
+    let finish = {
+        let finished = Arc::clone(&finished);
+        let mixer_name = mixer_name.clone();
+        let outgoing = from_pad.clone();
+        move || {
+            if !finished.swap(true, Ordering::SeqCst) {
+                complete(&mixer_name, generation, &outgoing);
+            }
+        }
+    };
+
+    if let Some(shader) = &shader {
+        shader.set_property("uniforms", gl_uniforms(pattern, reverse, 0.0, &settings));
+        let sink = shader
+            .static_pad("sink")
+            .ok_or_else(|| TransitionError::GstError("Wipe shader has no sink pad".into()))?;
+        let shader_weak = shader.downgrade();
+        let finish = finish.clone();
+        // Uniforms set before the frame reaches the shader apply to that frame
+        let probe = sink
+            .add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+                let Some(progress) = info
+                    .buffer()
+                    .and_then(|buffer| clock.buffer_progress(pad, buffer))
+                else {
+                    return gst::PadProbeReturn::Ok;
+                };
+                if let Some(shader) = shader_weak.upgrade() {
+                    shader.set_property(
+                        "uniforms",
+                        gl_uniforms(pattern, reverse, progress, &settings),
+                    );
+                }
+                if progress >= 1.0 {
+                    finish();
+                }
+                gst::PadProbeReturn::Ok
+            })
+            .ok_or_else(|| TransitionError::GstError("Failed to add wipe probe".into()))?;
+        active.probe = Some((sink, probe));
+    } else {
+        let caps = to_pad
+            .current_caps()
+            .ok_or_else(|| TransitionError::GstError("Incoming pad not negotiated".into()))?;
+        let s = caps
+            .structure(0)
+            .ok_or_else(|| TransitionError::GstError("Empty caps on incoming pad".into()))?;
+        let layout = s
+            .get::<&str>("format")
+            .ok()
+            .and_then(PixelLayout::from_format)
+            .filter(|_| {
+                !caps
+                    .features(0)
+                    .is_some_and(|f| f.contains("memory:GLMemory"))
+            })
+            .ok_or_else(|| {
+                TransitionError::Unsupported(format!(
+                    "wipes need enable_wipes on this block (pad {} has caps {})",
+                    to_pad.name(),
+                    caps
+                ))
+            })?;
+        let width = s.get::<i32>("width").unwrap_or(0).max(1) as usize;
+        let height = s.get::<i32>("height").unwrap_or(0).max(1) as usize;
+
+        let mask = Arc::new(CpuMask::new(pattern, reverse, width, height, layout));
+        let finish = finish.clone();
+        let probe = to_pad
+            .add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+                let Some(progress) = info
+                    .buffer()
+                    .and_then(|buffer| clock.buffer_progress(pad, buffer))
+                else {
+                    return gst::PadProbeReturn::Ok;
+                };
+                if progress >= 1.0 {
+                    finish();
+                    return gst::PadProbeReturn::Ok;
+                }
+                if let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data {
+                    let buffer = buffer.make_mut();
+                    match buffer.map_writable() {
+                        Ok(mut map) => mask.apply(map.as_mut_slice(), progress, &settings),
+                        Err(_) => warn!("Wipe probe could not map buffer writable"),
+                    }
+                }
+                gst::PadProbeReturn::Ok
+            })
+            .ok_or_else(|| TransitionError::GstError("Failed to add wipe probe".into()))?;
+        active.probe = Some((to_pad.clone(), probe));
+    }
+
+    // Stack the incoming source above the outgoing one and make it visible;
+    // the mask keeps it hidden until the front reaches each pixel.
+    let from_zorder = from_pad.property::<u32>("zorder");
+    if to_pad.property::<u32>("zorder") <= from_zorder {
+        to_pad.set_property("zorder", from_zorder + 1);
+    }
+    to_pad.set_property("alpha", 1.0f64);
+
+    if let Ok(mut wipes) = ACTIVE_WIPES.lock() {
+        wipes.insert(mixer_name, active);
+    }
+
+    // Fallback in case no frame reaches the end of the wipe
+    let duration = Duration::from_nanos((end - start).nseconds());
+    gst::glib::timeout_add_once(duration + WIPE_SETTLE_MARGIN, finish);
+
+    info!(
+        "{:?} wipe (SMPTE {}{}) started on {}: {} -> {} ({}ms)",
+        pattern,
+        pattern.smpte_code(),
+        if reverse { ", reverse" } else { "" },
+        mixer.name(),
+        from_pad.name(),
+        to_pad.name(),
+        duration.as_millis()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wipe_names() {
+        assert_eq!(
+            parse_wipe("wipe_bar"),
+            Some((WipePattern::BarHorizontal, false))
+        );
+        assert_eq!(
+            parse_wipe("wipe_iris_reverse"),
+            Some((WipePattern::Iris, true))
+        );
+        assert_eq!(parse_wipe("smpte_201"), Some((WipePattern::Clock, false)));
+        assert_eq!(
+            parse_wipe("smpte_102_reverse"),
+            Some((WipePattern::Diamond, true))
+        );
+        assert_eq!(parse_wipe("smpte_999"), None);
+        assert_eq!(parse_wipe("wipe_star"), None);
+        assert_eq!(parse_wipe("fade"), None);
+    }
+
+    #[test]
+    fn test_smpte_codes_round_trip() {
+        for pattern in WipePattern::ALL {
+            assert_eq!(
+                WipePattern::from_smpte_code(pattern.smpte_code()),
+                Some(pattern)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mask_values() {
+        let (w, h) = (1920.0, 1080.0);
+        assert_eq!(
+            mask_value(WipePattern::BarHorizontal, 0.25, 0.9, w, h),
+            0.25
+        );
+        assert_eq!(mask_value(WipePattern::BarVertical, 0.25, 0.9, w, h), 0.9);
+        assert_eq!(mask_value(WipePattern::Box, 0.25, 0.75, w, h), 0.75);
+        assert_eq!(mask_value(WipePattern::Iris, 0.5, 0.5, w, h), 0.0);
+        assert!((mask_value(WipePattern::Iris, 1.0, 1.0, w, h) - 1.0).abs() < 1e-9);
+        assert_eq!(mask_value(WipePattern::Diamond, 0.5, 0.5, w, h), 0.0);
+        // Clock starts at 12 o'clock and sweeps clockwise
+        assert!(mask_value(WipePattern::Clock, 0.5, 0.0, w, h) < 0.01);
+        assert!((mask_value(WipePattern::Clock, 1.0, 0.5, w, h) - 0.25).abs() < 1e-9);
+        assert!((mask_value(WipePattern::Clock, 0.0, 0.5, w, h) - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_edge_endpoints() {
+        let settings = WipeSettings {
+            softness: 0.1,
+            border_width: 0.05,
+            border_color: [1.0, 0.0, 0.0],
+        };
+        for m in [0.0, 0.3, 1.0] {
+            assert_eq!(edge(m, 0.0, &settings).0, 0.0);
+            assert_eq!(edge(m, 1.0, &settings), (1.0, 1.0));
+        }
+        // Hard edge: fully in or out
+        let hard = WipeSettings {
+            softness: 0.0,
+            border_width: 0.0,
+            ..settings
+        };
+        assert_eq!(edge(0.2, 0.5, &hard), (1.0, 1.0));
+        assert_eq!(edge(0.8, 0.5, &hard), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_cpu_mask_applies_border_and_alpha() {
+        let layout = PixelLayout::from_format("BGRA").unwrap();
+        let mask = CpuMask::new(WipePattern::BarHorizontal, false, 4, 1, layout);
+        let settings = WipeSettings {
+            softness: 0.0,
+            border_width: 0.25,
+            border_color: [1.0, 0.0, 0.0],
+        };
+        let mut frame = [10u8, 20, 30, 255].repeat(4);
+        mask.apply(&mut frame, 0.5, &settings);
+        // front = 0.5 * 1.25 = 0.625: pixels at 0.125 shown, 0.375 border, rest hidden
+        assert_eq!(&frame[0..4], &[10, 20, 30, 255]);
+        assert_eq!(&frame[4..8], &[0, 0, 255, 255]);
+        assert_eq!(frame[11], 0);
+        assert_eq!(frame[15], 0);
+    }
+
+    #[test]
+    fn test_wipe_settings_from_properties() {
+        let mut props = HashMap::new();
+        props.insert("wipe_softness".to_string(), PropertyValue::Float(0.2));
+        props.insert("wipe_border_width".to_string(), PropertyValue::Float(5.0));
+        props.insert(
+            "wipe_border_color".to_string(),
+            PropertyValue::String("#ff8000".to_string()),
+        );
+        let settings = WipeSettings::from_properties(&props);
+        assert!((settings.softness - 0.2).abs() < 1e-6);
+        assert_eq!(settings.border_width, 1.0);
+        assert_eq!(settings.border_color[0], 1.0);
+        assert!((settings.border_color[1] - 128.0 / 255.0).abs() < 1e-6);
+        assert_eq!(settings.border_color[2], 0.0);
+
+        assert_eq!(
+            WipeSettings::from_properties(&HashMap::new()),
+            WipeSettings::default()
+        );
+    }
+}
@@ -30,6 +30,7 @@ pub mod gst;
 pub mod gui;
 pub mod json_rejection;
 pub mod layout;
+pub mod macro_registry;
 pub mod mcp;
 pub mod network;
 pub mod openapi;
@@ -41,6 +42,7 @@ pub mod state;
 pub mod stats;
 pub mod storage;
 pub mod system_monitor;
+pub mod tally;
 pub mod thread_registry;
 pub mod tls;
 pub mod version;
@@ -164,6 +166,10 @@ pub async fn create_app_with_config(
             "/flows/{flow_id}/blocks/{block_id}/recorder/split",
             post(api::flows::recorder_split_now),
         )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/scte35",
+            post(api::flows::insert_scte35),
+        )
         .route(
             "/flows/{flow_id}/blocks/{block_id}/transition",
             post(api::flows::trigger_transition),
@@ -180,6 +186,14 @@ pub async fn create_app_with_config(
             "/flows/{flow_id}/blocks/{block_id}/dsk",
             post(api::flows::toggle_dsk),
         )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/usk",
+            post(api::flows::toggle_usk),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/aux",
+            post(api::flows::set_aux_source),
+        )
         .route(
             "/flows/{flow_id}/blocks/{block_id}/background",
             post(api::flows::set_background),
@@ -188,6 +202,88 @@ pub async fn create_app_with_config(
             "/flows/{flow_id}/blocks/{block_id}/ftb",
             post(api::flows::fade_to_black),
         )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/macros",
+            get(api::macros::list_macros),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/macros/record",
+            post(api::macros::start_recording),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/macros/record/stop",
+            post(api::macros::stop_recording),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/macros/stop",
+            post(api::macros::stop_macro),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/macros/{name}",
+            put(api::macros::save_macro).delete(api::macros::delete_macro),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/macros/{name}/run",
+            post(api::macros::run_macro),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/snapshots",
+            get(api::mixer_snapshots::list_snapshots),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/snapshots/{name}",
+            put(api::mixer_snapshots::save_snapshot).delete(api::mixer_snapshots::delete_snapshot),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/snapshots/{name}/recall",
+            post(api::mixer_snapshots::recall_snapshot),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/automation",
+            get(api::mixer_automation::list_automation),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/automation/{parameter}",
+            put(api::mixer_automation::set_automation_lane)
+                .delete(api::mixer_automation::delete_automation_lane),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/routing",
+            get(api::audio_router::get_routing),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/routing/presets/{name}",
+            put(api::audio_router::save_preset).delete(api::audio_router::delete_preset),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/routing/salvo",
+            post(api::audio_router::salvo),
+        )
+        .route(
+            "/flows/{flow_id}/animations",
+            get(api::animations::list_animations),
+        )
+        .route(
+            "/flows/{flow_id}/animations/{animation_id}",
+            put(api::animations::set_animation).delete(api::animations::cancel_animation),
+        )
+        .route(
+            "/flows/{flow_id}/animations/{animation_id}/start",
+            post(api::animations::start_animation),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/layouts",
+            get(api::compositor_layouts::get_layouts),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/layouts/presets/{name}",
+            put(api::compositor_layouts::save_preset)
+                .delete(api::compositor_layouts::delete_preset),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/layouts/presets/{name}/recall",
+            post(api::compositor_layouts::recall_preset),
+        )
         .route(
             "/flows/{flow_id}/blocks/{block_id}/multiview-endpoint",
             get(api::vision_mixer_page::get_multiview_endpoint),
@@ -301,6 +397,56 @@ pub async fn create_app_with_config(
             "/flows/{flow_id}/blocks/{block_id}/player/goto",
             post(api::mediaplayer::goto_file),
         )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/player/schedule",
+            get(api::mediaplayer::get_schedule).put(api::mediaplayer::set_schedule),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/player/schedule/events",
+            post(api::mediaplayer::add_schedule_event),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/player/schedule/events/{event_id}",
+            delete(api::mediaplayer::delete_schedule_event),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/player/as-run",
+            get(api::mediaplayer::get_as_run),
+        )
+        // Cart player controls
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/cart/state",
+            get(api::cartplayer::get_cart_state),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/cart/slots",
+            put(api::cartplayer::set_cart_slots),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/cart/slots/{slot}/fire",
+            post(api::cartplayer::fire_cart_slot),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/cart/slots/{slot}/stop",
+            post(api::cartplayer::stop_cart_slot),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/cart/stop",
+            post(api::cartplayer::stop_cart),
+        )
+        // Recorder controls
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/recorder/state",
+            get(api::recorder::get_recorder_state),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/recorder/control",
+            post(api::recorder::control_recorder),
+        )
+        .route(
+            "/flows/{flow_id}/blocks/{block_id}/recorder/trigger",
+            put(api::recorder::set_recorder_trigger),
+        )
         // Apply authentication middleware to all protected routes
         .layer(middleware::from_fn(auth::auth_middleware));
 
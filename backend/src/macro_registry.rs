@@ -0,0 +1,313 @@
+//! Vision mixer macro recording and playback bookkeeping.
+//!
+//! Tracks which vision mixer blocks are recording a macro and which are
+//! playing one back. Recording captures each switcher action handled by
+//! `AppState` together with the time since the previous action; playback is
+//! driven by `AppState::run_macro`, which replays the steps through the same
+//! methods the REST API uses, inside [`replaying`] so they are not recorded.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use strom_types::macros::{MacroAction, MacroStep, VisionMixerMacro};
+use strom_types::FlowId;
+use tokio::sync::Notify;
+
+tokio::task_local! {
+    /// Set while a macro step is performed.
+    static FROM_MACRO: bool;
+}
+
+/// Run a macro step; the actions it performs are tagged as coming from the
+/// macro (see [`from_macro`]).
+pub async fn replaying<F: Future>(step: F) -> F::Output {
+    FROM_MACRO.scope(true, step).await
+}
+
+/// Whether the current action is performed by a macro being played back.
+pub fn from_macro() -> bool {
+    FROM_MACRO
+        .try_with(|from_macro| *from_macro)
+        .unwrap_or(false)
+}
+
+/// Errors from macro operations.
+#[derive(Debug, thiserror::Error)]
+pub enum MacroError {
+    #[error("Flow not found: {0}")]
+    FlowNotFound(FlowId),
+    #[error("Block {0} is not a vision mixer")]
+    NotVisionMixer(String),
+    #[error("Macro not found: {0}")]
+    NotFound(String),
+    #[error("Already recording macro '{0}' on this block")]
+    AlreadyRecording(String),
+    #[error("No macro is being recorded on this block")]
+    NotRecording,
+    #[error("Macro '{0}' is already running on this block")]
+    AlreadyRunning(String),
+    #[error("No macro is running on this block")]
+    NotRunning,
+    #[error("Flow is not running: {0}")]
+    FlowNotRunning(FlowId),
+}
+
+/// A macro being recorded.
+struct Recording {
+    name: String,
+    steps: Vec<MacroStep>,
+    last_action: Option<Instant>,
+}
+
+/// A macro being played back.
+struct RunningMacro {
+    name: String,
+    cancel: Arc<Notify>,
+}
+
+/// Per-block macro recording and playback state.
+#[derive(Default)]
+pub struct MacroRegistry {
+    recordings: Mutex<HashMap<(FlowId, String), Recording>>,
+    running: Mutex<HashMap<(FlowId, String), RunningMacro>>,
+}
+
+impl MacroRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start recording a macro on a block.
+    pub fn start_recording(
+        &self,
+        flow_id: FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), MacroError> {
+        let mut recordings = self.recordings.lock();
+        let key = (flow_id, block_id.to_string());
+        if let Some(existing) = recordings.get(&key) {
+            return Err(MacroError::AlreadyRecording(existing.name.clone()));
+        }
+        recordings.insert(
+            key,
+            Recording {
+                name: name.to_string(),
+                steps: Vec::new(),
+                last_action: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop recording on a block and return the recorded macro.
+    pub fn stop_recording(
+        &self,
+        flow_id: FlowId,
+        block_id: &str,
+    ) -> Result<VisionMixerMacro, MacroError> {
+        let recording = self
+            .recordings
+            .lock()
+            .remove(&(flow_id, block_id.to_string()))
+            .ok_or(MacroError::NotRecording)?;
+        Ok(VisionMixerMacro {
+            name: recording.name,
+            block_id: block_id.to_string(),
+            steps: recording.steps,
+        })
+    }
+
+    /// Record an action if a macro is being recorded on the block.
+    ///
+    /// Actions replayed by a macro (`from_macro`) are not recorded; manual
+    /// actions are, even while a macro is running. The first step has no
+    /// delay; later steps keep the time since the previous action.
+    pub fn record(&self, flow_id: FlowId, block_id: &str, action: MacroAction, from_macro: bool) {
+        if from_macro {
+            return;
+        }
+        let key = (flow_id, block_id.to_string());
+        if let Some(recording) = self.recordings.lock().get_mut(&key) {
+            let now = Instant::now();
+            let delay_ms = recording
+                .last_action
+                .map(|last| now.duration_since(last).as_millis() as u64)
+                .unwrap_or(0);
+            recording.last_action = Some(now);
+            recording.steps.push(MacroStep { delay_ms, action });
+        }
+    }
+
+    /// Name of the macro being recorded on a block, if any.
+    pub fn recording_name(&self, flow_id: FlowId, block_id: &str) -> Option<String> {
+        self.recordings
+            .lock()
+            .get(&(flow_id, block_id.to_string()))
+            .map(|r| r.name.clone())
+    }
+
+    /// Name of the macro playing on a block, if any.
+    pub fn running_name(&self, flow_id: FlowId, block_id: &str) -> Option<String> {
+        self.running
+            .lock()
+            .get(&(flow_id, block_id.to_string()))
+            .map(|r| r.name.clone())
+    }
+
+    /// Mark a macro as running on a block. Returns the cancel signal.
+    pub fn begin_run(
+        &self,
+        flow_id: FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<Arc<Notify>, MacroError> {
+        let mut running = self.running.lock();
+        let key = (flow_id, block_id.to_string());
+        if let Some(existing) = running.get(&key) {
+            return Err(MacroError::AlreadyRunning(existing.name.clone()));
+        }
+        let cancel = Arc::new(Notify::new());
+        running.insert(
+            key,
+            RunningMacro {
+                name: name.to_string(),
+                cancel: Arc::clone(&cancel),
+            },
+        );
+        Ok(cancel)
+    }
+
+    /// Clear the running state of a block once playback has ended.
+    pub fn end_run(&self, flow_id: FlowId, block_id: &str) {
+        self.running.lock().remove(&(flow_id, block_id.to_string()));
+    }
+
+    /// Cancel the macro playing on a block. Returns false if none was running.
+    pub fn cancel(&self, flow_id: FlowId, block_id: &str) -> bool {
+        match self.running.lock().get(&(flow_id, block_id.to_string())) {
+            Some(running) => {
+                running.cancel.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel playback and drop recordings for every block of a flow.
+    pub fn clear_flow(&self, flow_id: FlowId) {
+        for ((id, _), running) in self.running.lock().iter() {
+            if *id == flow_id {
+                running.cancel.notify_one();
+            }
+        }
+        self.recordings.lock().retain(|(id, _), _| *id != flow_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_steps() {
+        let registry = MacroRegistry::new();
+        let flow_id = FlowId::new_v4();
+        registry.record(
+            flow_id,
+            "vm",
+            MacroAction::FadeToBlack { duration_ms: 0 },
+            false,
+        );
+
+        registry.start_recording(flow_id, "vm", "open").unwrap();
+        assert!(matches!(
+            registry.start_recording(flow_id, "vm", "other"),
+            Err(MacroError::AlreadyRecording(_))
+        ));
+        registry.record(
+            flow_id,
+            "vm",
+            MacroAction::SelectPreview {
+                input: 2,
+                multi: false,
+            },
+            false,
+        );
+        registry.record(
+            flow_id,
+            "other_block",
+            MacroAction::SetDsk {
+                dsk: 1,
+                enabled: true,
+            },
+            false,
+        );
+        registry.record(
+            flow_id,
+            "vm",
+            MacroAction::FadeToBlack { duration_ms: 500 },
+            false,
+        );
+
+        let recorded = registry.stop_recording(flow_id, "vm").unwrap();
+        assert_eq!(recorded.name, "open");
+        assert_eq!(recorded.block_id, "vm");
+        assert_eq!(recorded.steps.len(), 2);
+        assert_eq!(recorded.steps[0].delay_ms, 0);
+        assert_eq!(
+            recorded.steps[1].action,
+            MacroAction::FadeToBlack { duration_ms: 500 }
+        );
+        assert!(matches!(
+            registry.stop_recording(flow_id, "vm"),
+            Err(MacroError::NotRecording)
+        ));
+    }
+
+    #[test]
+    fn test_macro_actions_are_not_recorded() {
+        let registry = MacroRegistry::new();
+        let flow_id = FlowId::new_v4();
+        registry.start_recording(flow_id, "vm", "rec").unwrap();
+        registry.begin_run(flow_id, "vm", "play").unwrap();
+        assert!(matches!(
+            registry.begin_run(flow_id, "vm", "play"),
+            Err(MacroError::AlreadyRunning(_))
+        ));
+        // Replayed by the macro: skipped; manual while it runs: recorded
+        registry.record(
+            flow_id,
+            "vm",
+            MacroAction::FadeToBlack { duration_ms: 0 },
+            true,
+        );
+        registry.record(
+            flow_id,
+            "vm",
+            MacroAction::SetBackground { input: None },
+            false,
+        );
+        assert_eq!(
+            registry.running_name(flow_id, "vm").as_deref(),
+            Some("play")
+        );
+        assert!(registry.cancel(flow_id, "vm"));
+
+        registry.end_run(flow_id, "vm");
+        assert!(!registry.cancel(flow_id, "vm"));
+        let steps = registry.stop_recording(flow_id, "vm").unwrap().steps;
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].action, MacroAction::SetBackground { input: None });
+    }
+
+    #[tokio::test]
+    async fn test_from_macro_scope() {
+        assert!(!from_macro());
+        assert!(replaying(async { from_macro() }).await);
+        assert!(!from_macro());
+    }
+}
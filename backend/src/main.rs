@@ -433,6 +433,9 @@ fn run_with_gui(config: Config, no_auto_restart: bool) -> anyhow::Result<()> {
         // Start pipeline monitor (queue levels, buffer age warnings)
         strom::gst::pipeline_monitor::start(state.clone());
 
+        // Start TSL tally publisher (vision mixer PGM/PVW to UMDs)
+        strom::tally::start(state.clone());
+
         // Start WHIP session auto-cleanup (handles dead ICE connections, pipeline errors)
         state.whip_session_manager().start_cleanup_task();
 
@@ -572,6 +575,9 @@ async fn run_headless(config: Config, no_auto_restart: bool) -> anyhow::Result<(
     // Start pipeline monitor (queue levels, buffer age warnings)
     strom::gst::pipeline_monitor::start(state.clone());
 
+    // Start TSL tally publisher (vision mixer PGM/PVW to UMDs)
+    strom::tally::start(state.clone());
+
     // Start WHIP session auto-cleanup (handles dead ICE connections, pipeline errors)
     state.whip_session_manager().start_cleanup_task();
 
@@ -354,6 +354,64 @@ impl McpHandler {
                             },
                             "required": ["flow_id", "element_id", "property_name", "value"]
                         }
+                    },
+                    {
+                        "name": "list_vision_mixer_macros",
+                        "description": "List the macros stored for a vision mixer block, with the names of any macro being recorded or played",
+                        "inputSchema": {
+                            "type": "object",
+                            "properties": {
+                                "flow_id": {
+                                    "type": "string",
+                                    "description": "The UUID of the flow"
+                                },
+                                "block_id": {
+                                    "type": "string",
+                                    "description": "The vision mixer block instance ID"
+                                }
+                            },
+                            "required": ["flow_id", "block_id"]
+                        }
+                    },
+                    {
+                        "name": "run_vision_mixer_macro",
+                        "description": "Play back a stored vision mixer macro. The flow must be running. Returns immediately; the steps run with their recorded timing.",
+                        "inputSchema": {
+                            "type": "object",
+                            "properties": {
+                                "flow_id": {
+                                    "type": "string",
+                                    "description": "The UUID of the running flow"
+                                },
+                                "block_id": {
+                                    "type": "string",
+                                    "description": "The vision mixer block instance ID"
+                                },
+                                "name": {
+                                    "type": "string",
+                                    "description": "The macro name"
+                                }
+                            },
+                            "required": ["flow_id", "block_id", "name"]
+                        }
+                    },
+                    {
+                        "name": "stop_vision_mixer_macro",
+                        "description": "Cancel the macro playing on a vision mixer block",
+                        "inputSchema": {
+                            "type": "object",
+                            "properties": {
+                                "flow_id": {
+                                    "type": "string",
+                                    "description": "The UUID of the running flow"
+                                },
+                                "block_id": {
+                                    "type": "string",
+                                    "description": "The vision mixer block instance ID"
+                                }
+                            },
+                            "required": ["flow_id", "block_id"]
+                        }
                     }
                 ]
             }),
@@ -558,6 +616,57 @@ impl McpHandler {
                 })
             }
 
+            "list_vision_mixer_macros" => {
+                let flow_id = args["flow_id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("flow_id is required"))?;
+                let block_id = args["block_id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("block_id is required"))?;
+                info!(
+                    "MCP: Listing macros for block {} in flow {}",
+                    block_id, flow_id
+                );
+                let flow_uuid: strom_types::FlowId = flow_id.parse()?;
+                let macros = state.list_macros(&flow_uuid, block_id).await?;
+                serde_json::to_value(&macros)?
+            }
+
+            "run_vision_mixer_macro" => {
+                let flow_id = args["flow_id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("flow_id is required"))?;
+                let block_id = args["block_id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("block_id is required"))?;
+                let name = args["name"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("name is required"))?;
+                info!(
+                    "MCP: Running macro '{}' on block {} in flow {}",
+                    name, block_id, flow_id
+                );
+                let flow_uuid: strom_types::FlowId = flow_id.parse()?;
+                state.run_macro(&flow_uuid, block_id, name).await?;
+                json!({ "success": true, "message": format!("Macro '{}' started", name) })
+            }
+
+            "stop_vision_mixer_macro" => {
+                let flow_id = args["flow_id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("flow_id is required"))?;
+                let block_id = args["block_id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("block_id is required"))?;
+                info!(
+                    "MCP: Stopping macro on block {} in flow {}",
+                    block_id, flow_id
+                );
+                let flow_uuid: strom_types::FlowId = flow_id.parse()?;
+                state.stop_macro(&flow_uuid, block_id)?;
+                json!({ "success": true, "message": "Macro cancelled" })
+            }
+
             _ => {
                 error!("MCP: Unknown tool: {}", tool_params.name);
                 return Err(anyhow::anyhow!("Unknown tool: {}", tool_params.name));
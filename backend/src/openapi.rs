@@ -1,6 +1,10 @@
 //! OpenAPI documentation configuration.
 
 use crate::mcp::handler::JsonRpcRequest;
+use strom_types::animation::{
+    AnimationState, Easing, Keyframe, PropertyAnimation, PropertyAnimationsResponse,
+    SetPropertyAnimationRequest,
+};
 use strom_types::api::{
     ActivateProbeRequest, ActiveProbesResponse, AnimateInputRequest, AuthStatusResponse,
     AvailableOutput, AvailableSourcesResponse, ClientMessage, CodecStats, CreateDirectoryRequest,
@@ -16,24 +20,50 @@ use strom_types::api::{
     UpdateFlowPropertiesRequest, UpdatePadPropertyRequest, UpdatePropertyRequest, VisionMixerState,
     WebRtcConnectionStats, WebRtcStats, WebRtcStatsResponse,
 };
+use strom_types::audio_router::{
+    AudioRouterResponse, AudioRouterSalvoRequest, AudioRoutingResponse,
+    SaveAudioRoutingPresetRequest,
+};
 use strom_types::auth::{LoginRequest, LoginResponse};
 use strom_types::block::{
     BlockCategoriesResponse, BlockDefinition, BlockInstance, BlockListResponse, BlockResponse,
     CreateBlockRequest, ExposedProperty, ExternalPad, ExternalPads, PropertyMapping, PropertyType,
 };
+use strom_types::cartplayer::{
+    CartRetrigger, CartSlot, CartSlotState, CartSlotStatus, CartStateResponse, SetCartSlotsRequest,
+};
+use strom_types::compositor::{
+    CompositorLayoutResponse, CompositorLayoutsResponse, InputLayout, RecallLayoutRequest,
+    SaveLayoutPresetRequest,
+};
 use strom_types::discovery::{
     AnnouncedStreamResponse, DeviceCategory, DeviceCountByCategory, DeviceDiscoveryStatus,
     DeviceResponse, DiscoveredStreamResponse, NdiDiscoveryStatus,
 };
 use strom_types::events::StromEvent;
 use strom_types::flow::{FlowProperties, GStreamerClockType};
+use strom_types::macros::{
+    MacroAction, MacroListResponse, MacroResponse, MacroStep, SaveMacroRequest,
+    StartMacroRecordingRequest, VisionMixerMacro,
+};
 use strom_types::mediaplayer::{
-    GotoRequest, PlayerAction, PlayerControlRequest, PlayerStateResponse, SeekRequest,
-    SetPlaylistRequest,
+    AsRunEntry, AsRunKind, AsRunResponse, AsRunStatus, EndAction, GotoRequest, PlayerAction,
+    PlayerControlRequest, PlayerStateResponse, PlaylistItem, PlayoutSchedule, ScheduleEvent,
+    ScheduleResponse, ScheduleSource, SeekRequest, SetPlaylistRequest, StartMode,
+};
+use strom_types::mixer::{
+    AutomationLane, AutomationMode, AutomationPoint, MixerAutomationResponse, MixerSnapshot,
+    MixerSnapshotListResponse, MixerSnapshotResponse, RecallMixerSnapshotRequest,
+    SetAutomationLaneRequest,
 };
 use strom_types::network::{
     Ipv4AddressInfo, Ipv6AddressInfo, NetworkInterfaceInfo, NetworkInterfacesResponse,
 };
+use strom_types::recorder::{
+    RecorderAction, RecorderControlRequest, RecorderState, RecorderStatusResponse, RecorderTrigger,
+    RecorderTriggerSource, SetRecorderTriggerRequest,
+};
+use strom_types::scte35::{InsertScte35Request, InsertScte35Response, Scte35Command, Scte35Cue};
 use strom_types::stats::{BlockStats, StatMetadata, StatValue, Statistic};
 use strom_types::whep::{IceServer, IceServersResponse, WhepStreamInfo, WhepStreamsResponse};
 use utoipa::openapi::schema::{Discriminator, Schema};
@@ -60,8 +90,36 @@ use utoipa::OpenApi;
         crate::api::flows::select_preview,
         crate::api::flows::set_overlay_alpha,
         crate::api::flows::toggle_dsk,
+        crate::api::flows::toggle_usk,
+        crate::api::flows::set_aux_source,
         crate::api::flows::set_background,
         crate::api::flows::fade_to_black,
+        crate::api::macros::list_macros,
+        crate::api::macros::save_macro,
+        crate::api::macros::delete_macro,
+        crate::api::macros::run_macro,
+        crate::api::macros::stop_macro,
+        crate::api::macros::start_recording,
+        crate::api::macros::stop_recording,
+        crate::api::mixer_snapshots::list_snapshots,
+        crate::api::mixer_snapshots::save_snapshot,
+        crate::api::mixer_snapshots::delete_snapshot,
+        crate::api::mixer_snapshots::recall_snapshot,
+        crate::api::mixer_automation::list_automation,
+        crate::api::mixer_automation::set_automation_lane,
+        crate::api::mixer_automation::delete_automation_lane,
+        crate::api::audio_router::get_routing,
+        crate::api::audio_router::save_preset,
+        crate::api::audio_router::delete_preset,
+        crate::api::audio_router::salvo,
+        crate::api::animations::list_animations,
+        crate::api::animations::set_animation,
+        crate::api::animations::start_animation,
+        crate::api::animations::cancel_animation,
+        crate::api::compositor_layouts::get_layouts,
+        crate::api::compositor_layouts::save_preset,
+        crate::api::compositor_layouts::delete_preset,
+        crate::api::compositor_layouts::recall_preset,
         crate::api::vision_mixer_page::get_multiview_endpoint,
         crate::api::flows::animate_input,
         crate::api::flows::debug_graph,
@@ -75,6 +133,7 @@ use utoipa::OpenApi;
         crate::api::flows::update_pad_property,
         crate::api::flows::reset_loudness,
         crate::api::flows::recorder_split_now,
+        crate::api::flows::insert_scte35,
         crate::api::flows::get_block_thumbnail,
         crate::api::elements::list_elements,
         crate::api::elements::get_element_info,
@@ -138,6 +197,21 @@ use utoipa::OpenApi;
         crate::api::mediaplayer::control_player,
         crate::api::mediaplayer::seek_player,
         crate::api::mediaplayer::goto_file,
+        crate::api::mediaplayer::get_schedule,
+        crate::api::mediaplayer::set_schedule,
+        crate::api::mediaplayer::add_schedule_event,
+        crate::api::mediaplayer::delete_schedule_event,
+        crate::api::mediaplayer::get_as_run,
+        // Cart player endpoints
+        crate::api::cartplayer::get_cart_state,
+        crate::api::cartplayer::set_cart_slots,
+        crate::api::cartplayer::fire_cart_slot,
+        crate::api::cartplayer::stop_cart_slot,
+        crate::api::cartplayer::stop_cart,
+        // Recorder endpoints
+        crate::api::recorder::get_recorder_state,
+        crate::api::recorder::control_recorder,
+        crate::api::recorder::set_recorder_trigger,
         // Probe endpoints
         crate::api::probes::activate_probe,
         crate::api::probes::list_probes,
@@ -216,10 +290,46 @@ use utoipa::OpenApi;
             OverlayAlphaResponse,
             DskToggleRequest,
             DskToggleResponse,
+            strom_types::api::UskToggleRequest,
+            strom_types::api::UskToggleResponse,
+            strom_types::api::SetAuxSourceRequest,
+            strom_types::api::SetAuxSourceResponse,
+            strom_types::vision_mixer::AuxSource,
             SetBackgroundRequest,
             SetBackgroundResponse,
             FadeToBlackRequest,
             FadeToBlackResponse,
+            VisionMixerMacro,
+            MacroStep,
+            MacroAction,
+            MacroListResponse,
+            MacroResponse,
+            SaveMacroRequest,
+            StartMacroRecordingRequest,
+            MixerSnapshot,
+            MixerSnapshotListResponse,
+            MixerSnapshotResponse,
+            RecallMixerSnapshotRequest,
+            AutomationMode,
+            AutomationPoint,
+            AutomationLane,
+            SetAutomationLaneRequest,
+            MixerAutomationResponse,
+            AudioRoutingResponse,
+            SaveAudioRoutingPresetRequest,
+            AudioRouterSalvoRequest,
+            AudioRouterResponse,
+            Easing,
+            Keyframe,
+            SetPropertyAnimationRequest,
+            AnimationState,
+            PropertyAnimation,
+            PropertyAnimationsResponse,
+            InputLayout,
+            CompositorLayoutsResponse,
+            SaveLayoutPresetRequest,
+            RecallLayoutRequest,
+            CompositorLayoutResponse,
             // Discovery types
             DiscoveredStreamResponse,
             DeviceResponse,
@@ -232,9 +342,40 @@ use utoipa::OpenApi;
             PlayerAction,
             PlayerControlRequest,
             SetPlaylistRequest,
+            PlaylistItem,
+            EndAction,
             SeekRequest,
             GotoRequest,
             PlayerStateResponse,
+            StartMode,
+            ScheduleSource,
+            ScheduleEvent,
+            PlayoutSchedule,
+            ScheduleResponse,
+            AsRunKind,
+            AsRunStatus,
+            AsRunEntry,
+            AsRunResponse,
+            // Cart player types
+            CartRetrigger,
+            CartSlot,
+            SetCartSlotsRequest,
+            CartSlotStatus,
+            CartSlotState,
+            CartStateResponse,
+            // Recorder types
+            RecorderState,
+            RecorderAction,
+            RecorderControlRequest,
+            RecorderTriggerSource,
+            RecorderTrigger,
+            SetRecorderTriggerRequest,
+            RecorderStatusResponse,
+            // SCTE-35 types
+            Scte35Command,
+            Scte35Cue,
+            InsertScte35Request,
+            InsertScte35Response,
             // Auth types
             LoginRequest,
             LoginResponse,
@@ -271,6 +412,8 @@ use utoipa::OpenApi;
         (name = "mcp", description = "Model Context Protocol (MCP) endpoints"),
         (name = "discovery", description = "AES67 stream and device discovery endpoints"),
         (name = "media_player", description = "Media player control endpoints"),
+        (name = "cart_player", description = "Cart player slot and trigger endpoints"),
+        (name = "recorder", description = "Recorder start/stop/pause and trigger endpoints"),
         (name = "probes", description = "Buffer age probe endpoints"),
         (name = "websocket", description = "WebSocket real-time communication")
     ),
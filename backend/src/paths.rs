@@ -5,8 +5,18 @@
 
 use directories::ProjectDirs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tracing::{info, warn};
 
+/// Data directory of the first resolved [`DataPaths`], for files written at
+/// runtime (e.g. media player as-run logs).
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Get the resolved data directory, if paths have been resolved.
+pub fn data_dir() -> Option<&'static Path> {
+    DATA_DIR.get().map(PathBuf::as_path)
+}
+
 /// Represents the resolved paths for application data storage.
 #[derive(Debug, Clone)]
 pub struct DataPaths {
@@ -51,6 +61,7 @@ impl DataPaths {
             std::fs::create_dir_all(&base_dir)?;
             info!("Created data directory: {}", base_dir.display());
         }
+        let _ = DATA_DIR.set(base_dir.clone());
 
         // Resolve flows path (individual path overrides base_dir)
         let flows_path = if let Some(path) = config.flows_path {
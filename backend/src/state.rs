@@ -1,10 +1,19 @@
 //! Application state management.
 
 use crate::affinity_manager::AffinityManager;
+use crate::blocks::builtin::audiorouter::{self, RoutingError};
+use crate::blocks::builtin::compositor::{self, LayoutError};
+use crate::blocks::builtin::mixer::automation::{
+    self as mixer_automation, AutomationError, AutomationKey, AUTOMATION_REGISTRY,
+};
+use crate::blocks::builtin::mixer::snapshot::{self as mixer_snapshot, SnapshotError};
+use crate::blocks::common::json_property;
 use crate::blocks::BlockRegistry;
 use crate::discovery::DiscoveryService;
 use crate::events::EventBroadcaster;
+use crate::gst::animation::{Animation, AnimationError, AnimationKey, ANIMATION_REGISTRY};
 use crate::gst::{ElementDiscovery, PipelineError, PipelineManager};
+use crate::macro_registry::{self, MacroError, MacroRegistry};
 use crate::ptp_monitor::PtpMonitor;
 use crate::sharing::ChannelRegistry;
 use crate::storage::{JsonFileStorage, Storage};
@@ -17,7 +26,12 @@ use chrono::Local;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use strom_types::animation::{PropertyAnimation, SetPropertyAnimationRequest};
+use strom_types::audio_router::{AudioRouting, AudioRoutingResponse};
+use strom_types::compositor::{CompositorLayout, CompositorLayoutsResponse};
 use strom_types::element::{ElementInfo, PropertyValue};
+use strom_types::macros::{MacroAction, VisionMixerMacro};
+use strom_types::mixer::{AutomationLane, MixerSnapshot, SetAutomationLaneRequest};
 use strom_types::{Flow, FlowId, PipelineState, StromEvent};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, trace, warn};
@@ -71,6 +85,8 @@ struct AppStateInner {
     ice_transport_policy: String,
     /// Flows pending save (debounced to avoid excessive disk writes)
     pending_saves: RwLock<HashSet<FlowId>>,
+    /// Vision mixer macro recording and playback state
+    macros: MacroRegistry,
 }
 
 impl AppState {
@@ -109,6 +125,7 @@ impl AppState {
                 ice_servers,
                 ice_transport_policy,
                 pending_saves: RwLock::new(HashSet::new()),
+                macros: MacroRegistry::new(),
             }),
         }
     }
@@ -457,10 +474,31 @@ impl AppState {
         })
     }
 
+    /// Keep the block data a flow update cannot change and drop the data
+    /// of blocks the update removed.
+    ///
+    /// Vision mixer macros and mixer automation lanes are edited through
+    /// their own endpoints only, so an update of an existing flow keeps the
+    /// stored ones whatever it carries; a stale editor can neither overwrite
+    /// nor clear them. New flows, such as imports, keep the ones they come
+    /// with.
+    fn reconcile_block_data(existing: Option<&Flow>, flow: &mut Flow) {
+        if let Some(existing) = existing {
+            flow.macros = existing.macros.clone();
+            flow.mixer_automation = existing.mixer_automation.clone();
+        }
+        let block_ids: HashSet<&str> = flow.blocks.iter().map(|b| b.id.as_str()).collect();
+        flow.macros
+            .retain(|m| block_ids.contains(m.block_id.as_str()));
+        flow.mixer_automation
+            .retain(|lane| block_ids.contains(lane.block_id.as_str()));
+    }
+
     /// Add or update a flow and persist to storage.
-    pub async fn upsert_flow(&self, flow: Flow) -> anyhow::Result<()> {
+    pub async fn upsert_flow(&self, mut flow: Flow) -> anyhow::Result<()> {
         let is_new = {
             let flows = self.inner.flows.read().await;
+            Self::reconcile_block_data(flows.get(&flow.id), &mut flow);
             !flows.contains_key(&flow.id)
         };
 
@@ -709,6 +747,20 @@ impl AppState {
             self.inner.pipelines.read().await
         };
 
+        // Arm stored mixer automation lanes on the running elements
+        if let Some(manager) = pipelines_guard.get(id) {
+            for lane in &flow.mixer_automation {
+                let Some(block) = flow.blocks.iter().find(|b| b.id == lane.block_id) else {
+                    continue;
+                };
+                if let Some((element, property)) =
+                    mixer_automation::lane_target(&block.properties, &lane.parameter)
+                {
+                    Self::apply_automation_lane(id, lane, &element, &property, manager);
+                }
+            }
+        }
+
         // Get PTP clock identity from pipeline if available (for SDP generation)
         let ptp_clock_identity = pipelines_guard
             .get(id)
@@ -1153,6 +1205,28 @@ impl AppState {
         // Unregister media player instances for this flow
         crate::blocks::builtin::mediaplayer::MEDIA_PLAYER_REGISTRY.unregister_flow(id);
 
+        // Unregister cart player instances for this flow
+        crate::blocks::builtin::cartplayer::CART_PLAYER_REGISTRY.unregister_flow(id);
+
+        // Unregister recorder instances for this flow
+        crate::blocks::builtin::recorder::RECORDER_REGISTRY.unregister_flow(id);
+
+        // Unregister image source instances for this flow
+        crate::blocks::builtin::image_source::IMAGE_SOURCE_REGISTRY.unregister_flow(id);
+
+        // Unregister mixer ducking state for this flow
+        crate::blocks::builtin::mixer::ducking::DUCKING_REGISTRY.unregister_flow(id);
+
+        // Unregister compositor input styles for this flow
+        crate::gst::styling::STYLE_REGISTRY.unregister_flow(id);
+        crate::gst::keying::KEYER_REGISTRY.unregister_flow(id);
+
+        // Drop keyframed animations, their bindings go with the pipeline
+        crate::gst::animation::ANIMATION_REGISTRY.unregister_flow(id);
+
+        // Collect automation recorded while running, stored with the flow below
+        let recorded_automation = AUTOMATION_REGISTRY.take_flow(id);
+
         // Stop the pipeline
         let state = manager.stop()?;
 
@@ -1199,6 +1273,7 @@ impl AppState {
                         block.runtime_data = None;
                     }
                 }
+                mixer_automation::merge_lanes(&mut flow.mixer_automation, recorded_automation);
                 flow.set_gst_state(Some(state));
                 flow.properties.auto_restart = false; // Disable auto-restart when manually stopped
                 flow.properties.started_at = None; // Clear started_at when stopped
@@ -1250,10 +1325,14 @@ impl AppState {
                     crate::blocks::builtin::vision_mixer::overlay::unregister_overlay_state(
                         &block.id,
                     );
+                    crate::blocks::builtin::vision_mixer::stinger::unregister_stinger(&block.id);
                 }
             }
         }
 
+        // Cancel running macros and drop unfinished recordings
+        self.inner.macros.clear_flow(*id);
+
         // Broadcast events
         // Note: FlowStateChanged is now broadcast from the bus watch on actual GStreamer
         // state transitions, so we don't need to broadcast it here.
@@ -1442,6 +1521,20 @@ impl AppState {
             }
         }
 
+        if let Some(block_id) = block_instance_id.split(':').next() {
+            self.inner.macros.record(
+                *flow_id,
+                block_id,
+                MacroAction::Transition {
+                    from_input,
+                    to_input,
+                    transition_type: transition_type.to_string(),
+                    duration_ms,
+                },
+                macro_registry::from_macro(),
+            );
+        }
+
         // Broadcast transition event
         self.inner
             .events
@@ -1486,6 +1579,13 @@ impl AppState {
 
         drop(pipelines);
 
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::SelectPreview { input, multi },
+            macro_registry::from_macro(),
+        );
+
         // Broadcast state change event
         self.inner
             .events
@@ -1530,6 +1630,13 @@ impl AppState {
         manager.set_vision_mixer_background(block_instance_id, input)?;
         drop(pipelines);
 
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::SetBackground { input },
+            macro_registry::from_macro(),
+        );
+
         self.inner
             .events
             .broadcast(StromEvent::VisionMixerBackgroundChanged {
@@ -1559,6 +1666,16 @@ impl AppState {
         manager.set_dsk_enabled(block_instance_id, dsk_index, num_inputs, enabled)?;
         drop(pipelines);
 
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::SetDsk {
+                dsk: dsk_index + 1,
+                enabled,
+            },
+            macro_registry::from_macro(),
+        );
+
         // Broadcast DSK state change (1-based dsk number)
         self.inner
             .events
@@ -1572,6 +1689,44 @@ impl AppState {
         Ok(())
     }
 
+    /// Toggle a USK (Upstream Keyer) layer on a vision mixer block.
+    pub async fn set_usk_enabled(
+        &self,
+        flow_id: &FlowId,
+        block_instance_id: &str,
+        usk_index: usize,
+        enabled: bool,
+    ) -> Result<(), PipelineError> {
+        let pipelines = self.inner.pipelines.read().await;
+        let manager = pipelines.get(flow_id).ok_or_else(|| {
+            PipelineError::InvalidFlow(format!("Pipeline not running for flow: {}", flow_id))
+        })?;
+        manager.set_usk_enabled(block_instance_id, usk_index, enabled)?;
+        drop(pipelines);
+
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::SetUsk {
+                usk: usk_index + 1,
+                enabled,
+            },
+            macro_registry::from_macro(),
+        );
+
+        // Broadcast USK state change (1-based usk number)
+        self.inner
+            .events
+            .broadcast(StromEvent::VisionMixerUskChanged {
+                flow_id: *flow_id,
+                block_id: block_instance_id.to_string(),
+                usk: usk_index + 1,
+                enabled,
+            });
+
+        Ok(())
+    }
+
     /// Set the multiview overlay alpha on a vision mixer block.
     pub async fn set_overlay_alpha(
         &self,
@@ -1589,6 +1744,13 @@ impl AppState {
         manager.set_overlay_alpha(block_instance_id, num_inputs, alpha)?;
         drop(pipelines);
 
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::SetOverlayAlpha { alpha },
+            macro_registry::from_macro(),
+        );
+
         self.inner
             .events
             .broadcast(StromEvent::VisionMixerOverlayAlphaChanged {
@@ -1614,6 +1776,13 @@ impl AppState {
         let active = manager.fade_to_black(block_instance_id, duration_ms)?;
         drop(pipelines);
 
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::FadeToBlack { duration_ms },
+            macro_registry::from_macro(),
+        );
+
         self.inner
             .events
             .broadcast(StromEvent::VisionMixerFtbChanged {
@@ -1625,35 +1794,1300 @@ impl AppState {
         Ok(active)
     }
 
-    /// Reset accumulated loudness measurements on an EBU R128 meter block.
-    pub async fn reset_loudness(
+    /// Route a source to an AUX output of a vision mixer block.
+    ///
+    /// The chosen source is also stored in the block properties so the AUX
+    /// comes back on the same source after a restart. Returns the inputs now
+    /// visible on the AUX output.
+    pub async fn set_vision_mixer_aux(
         &self,
         flow_id: &FlowId,
-        block_id: &str,
-    ) -> Result<(), PipelineError> {
+        block_instance_id: &str,
+        aux_index: usize,
+        source: strom_types::vision_mixer::AuxSource,
+        transition_type: &str,
+        duration_ms: u64,
+    ) -> Result<Vec<usize>, PipelineError> {
         let pipelines = self.inner.pipelines.read().await;
-
         let manager = pipelines.get(flow_id).ok_or_else(|| {
             PipelineError::InvalidFlow(format!("Pipeline not running for flow: {}", flow_id))
         })?;
+        let inputs = manager.set_vision_mixer_aux(
+            block_instance_id,
+            aux_index,
+            source,
+            transition_type,
+            duration_ms,
+        )?;
+        drop(pipelines);
 
-        manager.reset_loudness(block_id)?;
+        {
+            let mut flows = self.inner.flows.write().await;
+            if let Some(block) = flows
+                .get_mut(flow_id)
+                .and_then(|flow| flow.blocks.iter_mut().find(|b| b.id == block_instance_id))
+            {
+                block.properties.insert(
+                    format!("aux_{}_source", aux_index),
+                    PropertyValue::String(source.as_property()),
+                );
+            }
+        }
+        self.mark_flow_dirty(*flow_id).await;
+
+        self.inner.macros.record(
+            *flow_id,
+            block_instance_id,
+            MacroAction::SetAux {
+                aux: aux_index + 1,
+                source,
+                transition_type: transition_type.to_string(),
+                duration_ms,
+            },
+            macro_registry::from_macro(),
+        );
+
+        self.inner
+            .events
+            .broadcast(StromEvent::VisionMixerAuxChanged {
+                flow_id: *flow_id,
+                block_id: block_instance_id.to_string(),
+                aux: aux_index + 1,
+                source,
+                inputs: inputs.clone(),
+            });
 
+        Ok(inputs)
+    }
+
+    /// Check that a block exists in a flow and is a vision mixer.
+    async fn require_vision_mixer(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<(), MacroError> {
+        let flows = self.inner.flows.read().await;
+        let flow = flows
+            .get(flow_id)
+            .ok_or(MacroError::FlowNotFound(*flow_id))?;
+        match flow.blocks.iter().find(|b| b.id == block_id) {
+            Some(block) if block.block_definition_id == "builtin.vision_mixer" => Ok(()),
+            _ => Err(MacroError::NotVisionMixer(block_id.to_string())),
+        }
+    }
+
+    /// List the macros stored for a vision mixer block.
+    pub async fn list_macros(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<strom_types::macros::MacroListResponse, MacroError> {
+        self.require_vision_mixer(flow_id, block_id).await?;
+        let flows = self.inner.flows.read().await;
+        let macros = flows
+            .get(flow_id)
+            .map(|flow| {
+                flow.macros
+                    .iter()
+                    .filter(|m| m.block_id == block_id)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(strom_types::macros::MacroListResponse {
+            macros,
+            recording: self.inner.macros.recording_name(*flow_id, block_id),
+            running: self.inner.macros.running_name(*flow_id, block_id),
+        })
+    }
+
+    /// Store a macro in the flow, replacing any macro with the same name.
+    async fn store_macro(
+        &self,
+        flow_id: &FlowId,
+        vm_macro: VisionMixerMacro,
+    ) -> Result<(), MacroError> {
+        let mut flows = self.inner.flows.write().await;
+        let flow = flows
+            .get_mut(flow_id)
+            .ok_or(MacroError::FlowNotFound(*flow_id))?;
+        match flow
+            .macros
+            .iter_mut()
+            .find(|m| m.block_id == vm_macro.block_id && m.name == vm_macro.name)
+        {
+            Some(existing) => *existing = vm_macro,
+            None => flow.macros.push(vm_macro),
+        }
+        drop(flows);
+
+        self.mark_flow_dirty(*flow_id).await;
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
         Ok(())
     }
 
-    pub async fn recorder_split_now(
+    /// Create or replace a macro on a vision mixer block.
+    pub async fn save_macro(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+        steps: Vec<strom_types::macros::MacroStep>,
+    ) -> Result<(), MacroError> {
+        self.require_vision_mixer(flow_id, block_id).await?;
+        self.store_macro(
+            flow_id,
+            VisionMixerMacro {
+                name: name.to_string(),
+                block_id: block_id.to_string(),
+                steps,
+            },
+        )
+        .await
+    }
+
+    /// Delete a macro from a vision mixer block.
+    pub async fn delete_macro(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), MacroError> {
+        let mut flows = self.inner.flows.write().await;
+        let flow = flows
+            .get_mut(flow_id)
+            .ok_or(MacroError::FlowNotFound(*flow_id))?;
+        let before = flow.macros.len();
+        flow.macros
+            .retain(|m| !(m.block_id == block_id && m.name == name));
+        if flow.macros.len() == before {
+            return Err(MacroError::NotFound(name.to_string()));
+        }
+        drop(flows);
+
+        self.mark_flow_dirty(*flow_id).await;
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        Ok(())
+    }
+
+    /// Start recording switcher actions on a vision mixer block.
+    pub async fn start_macro_recording(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), MacroError> {
+        self.require_vision_mixer(flow_id, block_id).await?;
+        self.inner
+            .macros
+            .start_recording(*flow_id, block_id, name)?;
+
+        info!(
+            "Recording macro '{}' on block {} in flow {}",
+            name, block_id, flow_id
+        );
+        self.inner
+            .events
+            .broadcast(StromEvent::VisionMixerMacroRecording {
+                flow_id: *flow_id,
+                block_id: block_id.to_string(),
+                name: name.to_string(),
+                recording: true,
+            });
+        Ok(())
+    }
+
+    /// Stop recording and store the recorded macro in the flow.
+    pub async fn stop_macro_recording(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<VisionMixerMacro, MacroError> {
+        let recorded = self.inner.macros.stop_recording(*flow_id, block_id)?;
+
+        info!(
+            "Recorded macro '{}' with {} steps on block {} in flow {}",
+            recorded.name,
+            recorded.steps.len(),
+            block_id,
+            flow_id
+        );
+        self.inner
+            .events
+            .broadcast(StromEvent::VisionMixerMacroRecording {
+                flow_id: *flow_id,
+                block_id: block_id.to_string(),
+                name: recorded.name.clone(),
+                recording: false,
+            });
+
+        self.store_macro(flow_id, recorded.clone()).await?;
+        Ok(recorded)
+    }
+
+    /// Start playing back a stored macro.
+    ///
+    /// Steps run in a background task; progress is reported with
+    /// `VisionMixerMacroRunning` events. Playback stops at the first failing
+    /// step, when cancelled, or when the flow is stopped.
+    pub async fn run_macro(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), MacroError> {
+        self.require_vision_mixer(flow_id, block_id).await?;
+        if !self.inner.pipelines.read().await.contains_key(flow_id) {
+            return Err(MacroError::FlowNotRunning(*flow_id));
+        }
+        let vm_macro = self
+            .inner
+            .flows
+            .read()
+            .await
+            .get(flow_id)
+            .and_then(|flow| {
+                flow.macros
+                    .iter()
+                    .find(|m| m.block_id == block_id && m.name == name)
+                    .cloned()
+            })
+            .ok_or_else(|| MacroError::NotFound(name.to_string()))?;
+
+        let cancel = self.inner.macros.begin_run(*flow_id, block_id, name)?;
+
+        info!(
+            "Running macro '{}' ({} steps) on block {} in flow {}",
+            name,
+            vm_macro.steps.len(),
+            block_id,
+            flow_id
+        );
+
+        let state = self.clone();
+        let flow_id = *flow_id;
+        tokio::spawn(async move {
+            let total_steps = vm_macro.steps.len();
+            let block_id = vm_macro.block_id.clone();
+            let running_event = |step: usize, running: bool| StromEvent::VisionMixerMacroRunning {
+                flow_id,
+                block_id: block_id.clone(),
+                name: vm_macro.name.clone(),
+                step,
+                total_steps,
+                running,
+            };
+            state.inner.events.broadcast(running_event(0, true));
+
+            let mut completed = 0;
+            for (index, step) in vm_macro.steps.iter().enumerate() {
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_millis(step.delay_ms)) => {}
+                    _ = cancel.notified() => {
+                        info!("Macro '{}' on block {} cancelled", vm_macro.name, block_id);
+                        break;
+                    }
+                }
+
+                state.inner.events.broadcast(running_event(index + 1, true));
+                if let Err(e) = macro_registry::replaying(state.run_macro_action(
+                    &flow_id,
+                    &block_id,
+                    &step.action,
+                ))
+                .await
+                {
+                    warn!(
+                        "Macro '{}' step {} failed, stopping playback: {}",
+                        vm_macro.name,
+                        index + 1,
+                        e
+                    );
+                    break;
+                }
+                completed = index + 1;
+            }
+
+            state.inner.macros.end_run(flow_id, &block_id);
+            state
+                .inner
+                .events
+                .broadcast(running_event(completed, false));
+        });
+
+        Ok(())
+    }
+
+    /// Cancel the macro playing on a vision mixer block.
+    pub fn stop_macro(&self, flow_id: &FlowId, block_id: &str) -> Result<(), MacroError> {
+        if self.inner.macros.cancel(*flow_id, block_id) {
+            Ok(())
+        } else {
+            Err(MacroError::NotRunning)
+        }
+    }
+
+    /// Perform a single macro action through the regular vision mixer methods.
+    async fn run_macro_action(
         &self,
         flow_id: &FlowId,
         block_id: &str,
+        action: &MacroAction,
     ) -> Result<(), PipelineError> {
-        let pipelines = self.inner.pipelines.read().await;
+        match action {
+            MacroAction::Transition {
+                from_input,
+                to_input,
+                transition_type,
+                duration_ms,
+            } => {
+                self.trigger_transition(
+                    flow_id,
+                    block_id,
+                    *from_input,
+                    *to_input,
+                    transition_type,
+                    *duration_ms,
+                )
+                .await
+            }
+            MacroAction::SelectPreview { input, multi } => self
+                .select_vision_mixer_preview(flow_id, block_id, *input, *multi)
+                .await
+                .map(|_| ()),
+            MacroAction::SetBackground { input } => self
+                .set_vision_mixer_background(flow_id, block_id, *input)
+                .await
+                .map(|_| ()),
+            MacroAction::SetDsk { dsk, enabled } => {
+                if *dsk == 0 {
+                    return Err(PipelineError::InvalidFlow(
+                        "DSK numbers start at 1".to_string(),
+                    ));
+                }
+                self.set_dsk_enabled(flow_id, block_id, dsk - 1, *enabled)
+                    .await
+            }
+            MacroAction::SetUsk { usk, enabled } => {
+                if *usk == 0 {
+                    return Err(PipelineError::InvalidFlow(
+                        "USK numbers start at 1".to_string(),
+                    ));
+                }
+                self.set_usk_enabled(flow_id, block_id, usk - 1, *enabled)
+                    .await
+            }
+            MacroAction::FadeToBlack { duration_ms } => self
+                .fade_to_black(flow_id, block_id, *duration_ms)
+                .await
+                .map(|_| ()),
+            MacroAction::SetOverlayAlpha { alpha } => {
+                self.set_overlay_alpha(flow_id, block_id, *alpha).await
+            }
+            MacroAction::SetAux {
+                aux,
+                source,
+                transition_type,
+                duration_ms,
+            } => {
+                if *aux == 0 {
+                    return Err(PipelineError::InvalidFlow(
+                        "AUX numbers start at 1".to_string(),
+                    ));
+                }
+                self.set_vision_mixer_aux(
+                    flow_id,
+                    block_id,
+                    aux - 1,
+                    *source,
+                    transition_type,
+                    *duration_ms,
+                )
+                .await
+                .map(|_| ())
+            }
+        }
+    }
 
-        let manager = pipelines.get(flow_id).ok_or_else(|| {
-            PipelineError::InvalidFlow(format!("Pipeline not running for flow: {}", flow_id))
-        })?;
+    /// Current properties of an audio mixer block.
+    async fn mixer_block_properties(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<HashMap<String, PropertyValue>, SnapshotError> {
+        let flows = self.inner.flows.read().await;
+        let flow = flows
+            .get(flow_id)
+            .ok_or(SnapshotError::FlowNotFound(*flow_id))?;
+        match flow.blocks.iter().find(|b| b.id == block_id) {
+            Some(block) if block.block_definition_id == "builtin.mixer" => {
+                Ok(block.properties.clone())
+            }
+            _ => Err(SnapshotError::NotMixer(block_id.to_string())),
+        }
+    }
 
-        manager.recorder_split_now(block_id)?;
+    /// Store the snapshots of an audio mixer block and persist the flow.
+    async fn set_mixer_snapshots(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        snapshots: &mixer_snapshot::StoredSnapshots,
+    ) {
+        {
+            let mut flows = self.inner.flows.write().await;
+            if let Some(block) = flows
+                .get_mut(flow_id)
+                .and_then(|flow| flow.blocks.iter_mut().find(|b| b.id == block_id))
+            {
+                block.properties.insert(
+                    mixer_snapshot::SNAPSHOTS_PROPERTY.to_string(),
+                    json_property(snapshots),
+                );
+            }
+        }
+        self.mark_flow_dirty(*flow_id).await;
+    }
+
+    /// List the snapshots stored for an audio mixer block.
+    pub async fn list_mixer_snapshots(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<Vec<MixerSnapshot>, SnapshotError> {
+        let properties = self.mixer_block_properties(flow_id, block_id).await?;
+        Ok(mixer_snapshot::stored_snapshots(&properties)
+            .into_iter()
+            .map(|(name, properties)| MixerSnapshot {
+                name,
+                block_id: block_id.to_string(),
+                properties,
+            })
+            .collect())
+    }
+
+    /// Capture the current state of an audio mixer block as a named snapshot,
+    /// replacing any snapshot with the same name. Returns the number of
+    /// properties stored.
+    pub async fn save_mixer_snapshot(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<usize, SnapshotError> {
+        let properties = self.mixer_block_properties(flow_id, block_id).await?;
+        let captured = mixer_snapshot::capture(&properties);
+        let count = captured.len();
+
+        let mut snapshots = mixer_snapshot::stored_snapshots(&properties);
+        snapshots.insert(name.to_string(), captured);
+        self.set_mixer_snapshots(flow_id, block_id, &snapshots)
+            .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        info!(
+            "Saved mixer snapshot '{}' on {} ({} properties)",
+            name, block_id, count
+        );
+        Ok(count)
+    }
+
+    /// Delete a snapshot from an audio mixer block.
+    pub async fn delete_mixer_snapshot(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), SnapshotError> {
+        let properties = self.mixer_block_properties(flow_id, block_id).await?;
+        let mut snapshots = mixer_snapshot::stored_snapshots(&properties);
+        if snapshots.remove(name).is_none() {
+            return Err(SnapshotError::NotFound(name.to_string()));
+        }
+        self.set_mixer_snapshots(flow_id, block_id, &snapshots)
+            .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        Ok(())
+    }
+
+    /// Recall a snapshot on an audio mixer block.
+    ///
+    /// Recall-safe channels are skipped. The block properties are always
+    /// updated; when the flow is running the elements follow, with faders,
+    /// sends and pans ramped over `crossfade_ms`. Each changed property is
+    /// broadcast as a `PropertyChanged` event for the block. Returns the
+    /// number of properties changed.
+    pub async fn recall_mixer_snapshot(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+        crossfade_ms: u64,
+    ) -> Result<usize, SnapshotError> {
+        let current = self.mixer_block_properties(flow_id, block_id).await?;
+        let snapshot = mixer_snapshot::stored_snapshots(&current)
+            .remove(name)
+            .ok_or_else(|| SnapshotError::NotFound(name.to_string()))?;
+        let plan = mixer_snapshot::plan_recall(&current, &snapshot);
+
+        {
+            let mut flows = self.inner.flows.write().await;
+            if let Some(block) = flows
+                .get_mut(flow_id)
+                .and_then(|flow| flow.blocks.iter_mut().find(|b| b.id == block_id))
+            {
+                block.properties.extend(plan.changes.iter().cloned());
+            }
+        }
+        self.mark_flow_dirty(*flow_id).await;
+
+        let pipelines = self.inner.pipelines.read().await;
+        if let Some(manager) = pipelines.get(flow_id) {
+            let mut ramps = Vec::new();
+            for write in &plan.writes {
+                let element_id = format!("{}:{}", block_id, write.element);
+                match &write.value {
+                    PropertyValue::Float(target) if write.fade && crossfade_ms > 0 => {
+                        ramps.push((element_id, write.property.clone(), *target));
+                    }
+                    value => {
+                        if let Err(e) =
+                            manager.update_element_property(&element_id, &write.property, value)
+                        {
+                            warn!(
+                                "Snapshot recall: failed to set {}.{}: {}",
+                                element_id, write.property, e
+                            );
+                        }
+                    }
+                }
+            }
+            if let Err(e) = manager.ramp_element_properties(&ramps, crossfade_ms) {
+                warn!("Snapshot recall: crossfade failed: {}", e);
+            }
+        }
+        drop(pipelines);
+
+        for (property_name, value) in &plan.changes {
+            self.inner.events.broadcast(StromEvent::PropertyChanged {
+                flow_id: *flow_id,
+                element_id: block_id.to_string(),
+                property_name: property_name.clone(),
+                value: value.clone(),
+            });
+        }
+        // Editors holding a copy of the flow must not save stale properties back
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+
+        info!(
+            "Recalled mixer snapshot '{}' on {} ({} properties, {}ms crossfade)",
+            name,
+            block_id,
+            plan.changes.len(),
+            crossfade_ms
+        );
+        Ok(plan.changes.len())
+    }
+
+    /// Element and property driven by an automation lane parameter.
+    async fn mixer_automation_target(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        parameter: &str,
+    ) -> Result<(String, String), AutomationError> {
+        let properties = self.mixer_block_properties(flow_id, block_id).await?;
+        mixer_automation::lane_target(&properties, parameter)
+            .ok_or_else(|| AutomationError::InvalidParameter(parameter.to_string()))
+    }
+
+    /// Apply an automation lane to the matching element of a running pipeline.
+    fn apply_automation_lane(
+        flow_id: &FlowId,
+        lane: &AutomationLane,
+        element: &str,
+        property: &str,
+        manager: &PipelineManager,
+    ) {
+        let element_id = format!("{}:{}", lane.block_id, element);
+        let Some(gst_element) = manager.find_gst_element(&element_id) else {
+            warn!(
+                "Automation: element {} not found for {}",
+                element_id, lane.parameter
+            );
+            return;
+        };
+        AUTOMATION_REGISTRY
+            .get_or_create(AutomationKey {
+                flow_id: *flow_id,
+                block_id: lane.block_id.clone(),
+            })
+            .set_lane(lane, &element_id, gst_element, property);
+    }
+
+    /// List the automation lanes of an audio mixer block.
+    ///
+    /// While the flow runs this includes the points recorded so far.
+    pub async fn list_mixer_automation(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<Vec<AutomationLane>, AutomationError> {
+        self.mixer_block_properties(flow_id, block_id).await?;
+
+        let mut lanes: Vec<AutomationLane> = {
+            let flows = self.inner.flows.read().await;
+            flows
+                .get(flow_id)
+                .map(|flow| {
+                    flow.mixer_automation
+                        .iter()
+                        .filter(|l| l.block_id == block_id)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        if let Some(automation) = AUTOMATION_REGISTRY.get(&AutomationKey {
+            flow_id: *flow_id,
+            block_id: block_id.to_string(),
+        }) {
+            mixer_automation::merge_lanes(&mut lanes, automation.lanes());
+        }
+        Ok(lanes)
+    }
+
+    /// Create or update the automation lane of a mixer parameter.
+    ///
+    /// Points are kept (including any recorded while running) unless the
+    /// request replaces them. Running flows switch mode immediately.
+    pub async fn set_mixer_automation_lane(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        parameter: &str,
+        req: SetAutomationLaneRequest,
+    ) -> Result<AutomationLane, AutomationError> {
+        let (element, property) = self
+            .mixer_automation_target(flow_id, block_id, parameter)
+            .await?;
+
+        let mut lane = self
+            .list_mixer_automation(flow_id, block_id)
+            .await?
+            .into_iter()
+            .find(|l| l.parameter == parameter)
+            .unwrap_or_else(|| AutomationLane {
+                block_id: block_id.to_string(),
+                parameter: parameter.to_string(),
+                mode: req.mode,
+                points: Vec::new(),
+            });
+        lane.mode = req.mode;
+        if let Some(mut points) = req.points {
+            points.sort_by_key(|p| p.time_ms);
+            lane.points = points;
+        }
+
+        {
+            let mut flows = self.inner.flows.write().await;
+            let flow = flows
+                .get_mut(flow_id)
+                .ok_or(AutomationError::FlowNotFound(*flow_id))?;
+            mixer_automation::merge_lanes(&mut flow.mixer_automation, vec![lane.clone()]);
+        }
+
+        let pipelines = self.inner.pipelines.read().await;
+        if let Some(manager) = pipelines.get(flow_id) {
+            Self::apply_automation_lane(flow_id, &lane, &element, &property, manager);
+        }
+        drop(pipelines);
+
+        self.mark_flow_dirty(*flow_id).await;
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        info!(
+            "Set mixer automation {} on {} to {:?} ({} points)",
+            parameter,
+            block_id,
+            lane.mode,
+            lane.points.len()
+        );
+        Ok(lane)
+    }
+
+    /// Delete the automation lane of a mixer parameter.
+    pub async fn delete_mixer_automation_lane(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        parameter: &str,
+    ) -> Result<(), AutomationError> {
+        {
+            let mut flows = self.inner.flows.write().await;
+            let flow = flows
+                .get_mut(flow_id)
+                .ok_or(AutomationError::FlowNotFound(*flow_id))?;
+            let before = flow.mixer_automation.len();
+            flow.mixer_automation
+                .retain(|l| !(l.block_id == block_id && l.parameter == parameter));
+            let live = AUTOMATION_REGISTRY.get(&AutomationKey {
+                flow_id: *flow_id,
+                block_id: block_id.to_string(),
+            });
+            let was_live = live
+                .as_ref()
+                .is_some_and(|a| a.lanes().iter().any(|l| l.parameter == parameter));
+            if flow.mixer_automation.len() == before && !was_live {
+                return Err(AutomationError::NotFound(parameter.to_string()));
+            }
+            if let Some(automation) = live {
+                automation.remove_lane(parameter);
+            }
+        }
+
+        self.mark_flow_dirty(*flow_id).await;
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        Ok(())
+    }
+
+    /// Current properties of an audio router block.
+    async fn router_block_properties(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<HashMap<String, PropertyValue>, RoutingError> {
+        let flows = self.inner.flows.read().await;
+        let flow = flows
+            .get(flow_id)
+            .ok_or(RoutingError::FlowNotFound(*flow_id))?;
+        match flow.blocks.iter().find(|b| b.id == block_id) {
+            Some(block) if block.block_definition_id == "builtin.audiorouter" => {
+                Ok(block.properties.clone())
+            }
+            _ => Err(RoutingError::NotRouter(block_id.to_string())),
+        }
+    }
+
+    /// Store a block property of an audio router and persist the flow.
+    async fn set_router_property(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+        value: PropertyValue,
+    ) {
+        {
+            let mut flows = self.inner.flows.write().await;
+            if let Some(block) = flows
+                .get_mut(flow_id)
+                .and_then(|flow| flow.blocks.iter_mut().find(|b| b.id == block_id))
+            {
+                block.properties.insert(name.to_string(), value);
+            }
+        }
+        self.mark_flow_dirty(*flow_id).await;
+    }
+
+    /// Active routing and stored presets of an audio router block.
+    pub async fn get_audio_routing(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<AudioRoutingResponse, RoutingError> {
+        let properties = self.router_block_properties(flow_id, block_id).await?;
+        let routing = audiorouter::current_routing(&properties);
+        let presets = audiorouter::routing_presets(&properties);
+        let active = audiorouter::validate_routing(&properties, &routing).unwrap_or_default();
+        Ok(AudioRoutingResponse {
+            active_preset: audiorouter::active_preset(&active, &presets),
+            routing,
+            presets,
+        })
+    }
+
+    /// Store a named routing preset on an audio router block, replacing any
+    /// preset with the same name. Without a routing the active one is
+    /// captured. Returns the stored routing.
+    ///
+    /// Crosspoints that only the new preset uses are built on the next
+    /// flow start.
+    pub async fn save_audio_routing_preset(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+        routing: Option<AudioRouting>,
+    ) -> Result<AudioRouting, RoutingError> {
+        let properties = self.router_block_properties(flow_id, block_id).await?;
+        let routing = routing.unwrap_or_else(|| audiorouter::current_routing(&properties));
+        let routing = audiorouter::routing_from_crosspoints(&audiorouter::validate_routing(
+            &properties,
+            &routing,
+        )?);
+
+        let mut presets = audiorouter::routing_presets(&properties);
+        presets.insert(name.to_string(), routing.clone());
+        self.set_router_property(
+            flow_id,
+            block_id,
+            "routing_presets",
+            json_property(&presets),
+        )
+        .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        info!("Saved routing preset '{}' on {}", name, block_id);
+        Ok(routing)
+    }
+
+    /// Delete a routing preset from an audio router block.
+    pub async fn delete_audio_routing_preset(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), RoutingError> {
+        let properties = self.router_block_properties(flow_id, block_id).await?;
+        let mut presets = audiorouter::routing_presets(&properties);
+        if presets.remove(name).is_none() {
+            return Err(RoutingError::PresetNotFound(name.to_string()));
+        }
+        self.set_router_property(
+            flow_id,
+            block_id,
+            "routing_presets",
+            json_property(&presets),
+        )
+        .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        Ok(())
+    }
+
+    /// Apply a complete routing to an audio router block in one step.
+    ///
+    /// The target is either a stored preset or an explicit routing. When the
+    /// flow runs, all crosspoint volumes crossfade together over `fade_ms`;
+    /// a target needing crosspoints that were not built is refused. When the
+    /// flow is stopped only the `routing_matrix` property changes and the
+    /// routing applies on the next start. Returns the applied routing and
+    /// whether it was switched live.
+    pub async fn audio_router_salvo(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        preset: Option<&str>,
+        routing: Option<AudioRouting>,
+        fade_ms: u64,
+    ) -> Result<(AudioRouting, bool), RoutingError> {
+        let properties = self.router_block_properties(flow_id, block_id).await?;
+        let presets = audiorouter::routing_presets(&properties);
+        let target = match (preset, routing) {
+            (Some(name), None) => presets
+                .get(name)
+                .cloned()
+                .ok_or_else(|| RoutingError::PresetNotFound(name.to_string()))?,
+            (None, Some(routing)) => routing,
+            _ => {
+                return Err(RoutingError::InvalidRouting(
+                    "exactly one of preset and routing is required".to_string(),
+                ))
+            }
+        };
+        let target = audiorouter::validate_routing(&properties, &target)?;
+
+        let mut live = false;
+        let pipelines = self.inner.pipelines.read().await;
+        if let Some(manager) = pipelines.get(flow_id) {
+            let prefix = format!("{}:", block_id);
+            let built = manager
+                .find_block_elements(block_id)
+                .into_iter()
+                .filter_map(|(id, _)| {
+                    audiorouter::Crosspoint::from_element_name(id.strip_prefix(&prefix)?)
+                })
+                .collect();
+            match audiorouter::plan_salvo(&built, &target) {
+                Some(plan) => {
+                    let ramps: Vec<_> = plan
+                        .into_iter()
+                        .map(|(xp, gain)| {
+                            (
+                                format!("{}{}", prefix, xp.element_name()),
+                                "volume".to_string(),
+                                gain,
+                            )
+                        })
+                        .collect();
+                    manager
+                        .ramp_element_properties(&ramps, fade_ms)
+                        .map_err(|e| RoutingError::NotLive(e.to_string()))?;
+                    live = true;
+                }
+                None => {
+                    return Err(RoutingError::NotLive(
+                        "it needs crosspoints that were not built, restart the flow to apply it"
+                            .to_string(),
+                    ))
+                }
+            }
+        }
+        drop(pipelines);
+
+        let routing = audiorouter::routing_from_crosspoints(&target);
+        let value = json_property(&routing);
+        self.set_router_property(flow_id, block_id, "routing_matrix", value.clone())
+            .await;
+
+        let active_preset = audiorouter::active_preset(&target, &presets);
+        self.inner.events.broadcast(StromEvent::PropertyChanged {
+            flow_id: *flow_id,
+            element_id: block_id.to_string(),
+            property_name: "routing_matrix".to_string(),
+            value,
+        });
+        self.inner
+            .events
+            .broadcast(StromEvent::AudioRoutingChanged {
+                flow_id: *flow_id,
+                block_id: block_id.to_string(),
+                preset: active_preset,
+                routing: routing.clone(),
+                live,
+            });
+        // Editors holding a copy of the flow must not save the old matrix back
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+
+        info!(
+            "Routing salvo on {}: {} crosspoints ({}, {}ms fade)",
+            block_id,
+            target.len(),
+            if live { "live" } else { "next start" },
+            fade_ms
+        );
+        Ok((routing, live))
+    }
+
+    /// Create or replace a keyframed animation of an element or pad
+    /// property on a running flow, cancelling any other animation of the
+    /// same property. Starts it unless `autostart` is off.
+    pub async fn set_property_animation(
+        &self,
+        flow_id: &FlowId,
+        animation_id: &str,
+        request: SetPropertyAnimationRequest,
+    ) -> Result<PropertyAnimation, AnimationError> {
+        let animation = {
+            let pipelines = self.inner.pipelines.read().await;
+            let manager = pipelines
+                .get(flow_id)
+                .ok_or(AnimationError::NotRunning(*flow_id))?;
+            let element = manager
+                .find_gst_element(&request.element_id)
+                .ok_or_else(|| AnimationError::ElementNotFound(request.element_id.clone()))?;
+            Animation::new(
+                *flow_id,
+                animation_id,
+                &request,
+                element,
+                manager.pipeline(),
+                self.inner.events.clone(),
+            )?
+        };
+
+        ANIMATION_REGISTRY.register(
+            AnimationKey {
+                flow_id: *flow_id,
+                animation_id: animation_id.to_string(),
+            },
+            animation.clone(),
+        );
+        if request.autostart {
+            animation.start()?;
+        }
+        Ok(animation.info())
+    }
+
+    /// Start an animation from its first keyframe, restarting it if it runs.
+    pub fn start_property_animation(
+        &self,
+        flow_id: &FlowId,
+        animation_id: &str,
+    ) -> Result<PropertyAnimation, AnimationError> {
+        let animation = ANIMATION_REGISTRY
+            .get(&AnimationKey {
+                flow_id: *flow_id,
+                animation_id: animation_id.to_string(),
+            })
+            .ok_or_else(|| AnimationError::NotFound(animation_id.to_string()))?;
+        animation.start()?;
+        Ok(animation.info())
+    }
+
+    /// Cancel and remove an animation. The property keeps its current value.
+    pub fn cancel_property_animation(
+        &self,
+        flow_id: &FlowId,
+        animation_id: &str,
+    ) -> Result<(), AnimationError> {
+        ANIMATION_REGISTRY
+            .remove(&AnimationKey {
+                flow_id: *flow_id,
+                animation_id: animation_id.to_string(),
+            })
+            .map(|_| ())
+            .ok_or_else(|| AnimationError::NotFound(animation_id.to_string()))
+    }
+
+    /// Animations of a flow, including finished and ready ones.
+    pub fn list_property_animations(&self, flow_id: &FlowId) -> Vec<PropertyAnimation> {
+        ANIMATION_REGISTRY.list(flow_id)
+    }
+
+    /// Current properties of a compositor block.
+    async fn compositor_block_properties(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<HashMap<String, PropertyValue>, LayoutError> {
+        let flows = self.inner.flows.read().await;
+        let flow = flows
+            .get(flow_id)
+            .ok_or(LayoutError::FlowNotFound(*flow_id))?;
+        match flow.blocks.iter().find(|b| b.id == block_id) {
+            Some(block) if block.block_definition_id == "builtin.compositor" => {
+                Ok(block.properties.clone())
+            }
+            _ => Err(LayoutError::NotCompositor(block_id.to_string())),
+        }
+    }
+
+    /// Store block properties of a compositor and persist the flow.
+    async fn set_compositor_properties(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        values: Vec<(String, PropertyValue)>,
+    ) {
+        {
+            let mut flows = self.inner.flows.write().await;
+            if let Some(block) = flows
+                .get_mut(flow_id)
+                .and_then(|flow| flow.blocks.iter_mut().find(|b| b.id == block_id))
+            {
+                block.properties.extend(values);
+            }
+        }
+        self.mark_flow_dirty(*flow_id).await;
+    }
+
+    /// Current layout and stored presets of a compositor block.
+    pub async fn get_compositor_layouts(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<CompositorLayoutsResponse, LayoutError> {
+        let properties = self.compositor_block_properties(flow_id, block_id).await?;
+        let layout = compositor::current_layout(&properties);
+        let presets = compositor::layout_presets(&properties);
+        Ok(CompositorLayoutsResponse {
+            active_preset: compositor::active_preset(&layout, &presets),
+            layout,
+            presets,
+        })
+    }
+
+    /// Store a named layout preset on a compositor block, replacing any
+    /// preset with the same name. Without a layout the current one is
+    /// captured; inputs missing from a partial layout keep their current
+    /// placement. Returns the stored layout.
+    pub async fn save_compositor_layout_preset(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+        layout: Option<CompositorLayout>,
+    ) -> Result<CompositorLayout, LayoutError> {
+        let properties = self.compositor_block_properties(flow_id, block_id).await?;
+        let layout = match layout {
+            Some(layout) => compositor::complete_layout(&properties, &layout)?,
+            None => compositor::current_layout(&properties),
+        };
+
+        let mut presets = compositor::layout_presets(&properties);
+        presets.insert(name.to_string(), layout.clone());
+        self.set_compositor_properties(
+            flow_id,
+            block_id,
+            vec![("layout_presets".to_string(), json_property(&presets))],
+        )
+        .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        info!("Saved layout preset '{}' on {}", name, block_id);
+        Ok(layout)
+    }
+
+    /// Delete a layout preset from a compositor block.
+    pub async fn delete_compositor_layout_preset(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+    ) -> Result<(), LayoutError> {
+        let properties = self.compositor_block_properties(flow_id, block_id).await?;
+        let mut presets = compositor::layout_presets(&properties);
+        if presets.remove(name).is_none() {
+            return Err(LayoutError::PresetNotFound(name.to_string()));
+        }
+        self.set_compositor_properties(
+            flow_id,
+            block_id,
+            vec![("layout_presets".to_string(), json_property(&presets))],
+        )
+        .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+        Ok(())
+    }
+
+    /// Recall a layout preset on a compositor block.
+    ///
+    /// On a running flow every input glides to its new placement over
+    /// `duration_ms`. The `input_N_*` properties are updated either way, so
+    /// a stopped flow starts with the recalled layout. Returns the layout
+    /// and whether it was moved live.
+    pub async fn recall_compositor_layout(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        name: &str,
+        duration_ms: u64,
+    ) -> Result<(CompositorLayout, bool), LayoutError> {
+        let properties = self.compositor_block_properties(flow_id, block_id).await?;
+        let preset = compositor::layout_presets(&properties)
+            .remove(name)
+            .ok_or_else(|| LayoutError::PresetNotFound(name.to_string()))?;
+        // Presets saved before inputs were added leave the new ones in place
+        let layout = compositor::complete_layout(&properties, &preset)?;
+
+        let mut live = false;
+        let pipelines = self.inner.pipelines.read().await;
+        if let Some(manager) = pipelines.get(flow_id) {
+            match manager.animate_layout(block_id, &layout, duration_ms) {
+                Ok(()) => live = true,
+                Err(e) => warn!("Layout recall on {} failed: {}", block_id, e),
+            }
+        }
+        drop(pipelines);
+
+        self.set_compositor_properties(flow_id, block_id, compositor::layout_properties(&layout))
+            .await;
+
+        self.inner
+            .events
+            .broadcast(StromEvent::CompositorLayoutRecalled {
+                flow_id: *flow_id,
+                block_id: block_id.to_string(),
+                preset: name.to_string(),
+                duration_ms,
+                live,
+            });
+        // Editors holding a copy of the flow must not save the old layout back
+        self.inner
+            .events
+            .broadcast(StromEvent::FlowUpdated { flow_id: *flow_id });
+
+        info!(
+            "Recalled layout '{}' on {} ({}, {}ms)",
+            name,
+            block_id,
+            if live { "live" } else { "next start" },
+            duration_ms
+        );
+        Ok((layout, live))
+    }
+
+    /// Reset accumulated loudness measurements on an EBU R128 meter block.
+    pub async fn reset_loudness(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<(), PipelineError> {
+        let pipelines = self.inner.pipelines.read().await;
+
+        let manager = pipelines.get(flow_id).ok_or_else(|| {
+            PipelineError::InvalidFlow(format!("Pipeline not running for flow: {}", flow_id))
+        })?;
+
+        manager.reset_loudness(block_id)?;
+
+        Ok(())
+    }
+
+    pub async fn recorder_split_now(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+    ) -> Result<(), PipelineError> {
+        let pipelines = self.inner.pipelines.read().await;
+
+        let manager = pipelines.get(flow_id).ok_or_else(|| {
+            PipelineError::InvalidFlow(format!("Pipeline not running for flow: {}", flow_id))
+        })?;
+
+        manager.recorder_split_now(block_id)?;
+
+        Ok(())
+    }
+
+    /// Inject an SCTE-35 cue into an MPEG-TS output block.
+    pub async fn insert_scte35(
+        &self,
+        flow_id: &FlowId,
+        block_id: &str,
+        cue: strom_types::scte35::Scte35Cue,
+    ) -> Result<(), PipelineError> {
+        let pipelines = self.inner.pipelines.read().await;
+
+        let manager = pipelines.get(flow_id).ok_or_else(|| {
+            PipelineError::InvalidFlow(format!("Pipeline not running for flow: {}", flow_id))
+        })?;
+
+        manager.insert_scte35(block_id, &cue)?;
+
+        drop(pipelines);
+
+        self.inner.events.broadcast(StromEvent::Scte35CueInserted {
+            flow_id: *flow_id,
+            block_id: block_id.to_string(),
+            cue,
+        });
 
         Ok(())
     }
@@ -1,12 +1,13 @@
 //! Statistics collector for running pipelines.
 
+use crate::blocks::builtin::recorder::{RecorderKey, RECORDER_REGISTRY};
 use crate::stats::rtp::collect_all_jitterbuffer_stats;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use std::time::{SystemTime, UNIX_EPOCH};
 use strom_types::block::BlockInstance;
-use strom_types::stats::{BlockStats, FlowStats, Statistic};
-use strom_types::Flow;
+use strom_types::stats::{BlockStats, FlowStats, StatMetadata, StatValue, Statistic};
+use strom_types::{Flow, FlowId};
 use tracing::{debug, trace, warn};
 
 /// Collector for pipeline statistics.
@@ -24,7 +25,7 @@ impl StatsCollector {
 
         // Collect stats for each block in the flow
         for block in &flow.blocks {
-            if let Some(stats) = Self::collect_block_stats(pipeline, block) {
+            if let Some(stats) = Self::collect_block_stats(pipeline, flow.id, block) {
                 block_stats.push(stats);
             }
         }
@@ -38,7 +39,11 @@ impl StatsCollector {
     }
 
     /// Collect statistics for a specific block.
-    fn collect_block_stats(pipeline: &gst::Pipeline, block: &BlockInstance) -> Option<BlockStats> {
+    fn collect_block_stats(
+        pipeline: &gst::Pipeline,
+        flow_id: FlowId,
+        block: &BlockInstance,
+    ) -> Option<BlockStats> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
@@ -51,6 +56,7 @@ impl StatsCollector {
                 // AES67 output doesn't have jitterbuffer stats, could add other stats later
                 vec![]
             }
+            "builtin.recorder" => Self::collect_recorder_stats(flow_id, &block.id),
             "builtin.meter" => {
                 // Meter block stats could be added here
                 vec![]
@@ -113,4 +119,50 @@ impl StatsCollector {
 
         all_stats
     }
+
+    /// Collect statistics for Recorder block (pre-roll buffer).
+    fn collect_recorder_stats(flow_id: FlowId, instance_id: &str) -> Vec<Statistic> {
+        let key = RecorderKey {
+            flow_id,
+            block_id: instance_id.to_string(),
+        };
+        let Some(recorder) = RECORDER_REGISTRY.get(&key) else {
+            return vec![];
+        };
+        let (depth_ns, buffered_ns, buffered_bytes) = recorder.preroll_stats();
+
+        vec![
+            Statistic {
+                id: "preroll_depth_ns".to_string(),
+                value: StatValue::DurationNs(depth_ns),
+                metadata: StatMetadata {
+                    display_name: "Pre-roll Depth".to_string(),
+                    description: "Media kept in memory while not recording".to_string(),
+                    unit: Some("ns".to_string()),
+                    category: Some("Recorder".to_string()),
+                },
+            },
+            Statistic {
+                id: "preroll_buffered_ns".to_string(),
+                value: StatValue::DurationNs(buffered_ns),
+                metadata: StatMetadata {
+                    display_name: "Pre-roll Buffered".to_string(),
+                    description: "Media currently in the pre-roll buffer, from its first keyframe"
+                        .to_string(),
+                    unit: Some("ns".to_string()),
+                    category: Some("Recorder".to_string()),
+                },
+            },
+            Statistic {
+                id: "preroll_buffered_bytes".to_string(),
+                value: StatValue::Gauge(buffered_bytes as i64),
+                metadata: StatMetadata {
+                    display_name: "Pre-roll Size".to_string(),
+                    description: "Memory used by the pre-roll buffer".to_string(),
+                    unit: Some("bytes".to_string()),
+                    category: Some("Recorder".to_string()),
+                },
+            },
+        ]
+    }
 }
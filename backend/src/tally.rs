@@ -0,0 +1,706 @@
+//! TSL UMD tally output for vision mixer blocks.
+//!
+//! Vision mixer PGM/PVW state is published as TSL UMD messages so camera
+//! CCUs, multiviewers and under-monitor displays can show red (program),
+//! green (preview) and amber (routed to an AUX output) tally together with
+//! the input label. Each vision mixer
+//! input maps to a TSL address starting at `tally_address_offset`.
+//!
+//! Both protocol versions are supported:
+//! - **v3.1**: one 18-byte message per display (address, tally bits, 16-char text)
+//! - **v5.0**: one packet per screen carrying every display, with DLE/STX
+//!   framing when sent over TCP
+//!
+//! Messages are sent to every configured destination whenever the flow
+//! starts, on each `VisionMixerStateChanged` and `VisionMixerAuxChanged`
+//! event, and (all off) when the flow stops.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use strom_types::{FlowId, PropertyValue, StromEvent};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::blocks::builtin::vision_mixer::{aux_outputs, overlay, properties as vm_props};
+use crate::state::AppState;
+
+/// Maximum TSL v3.1 display address.
+const V31_MAX_ADDRESS: u16 = 126;
+
+/// TSL v3.1 display text length.
+const V31_TEXT_LEN: usize = 16;
+
+/// TSL v5.0 data link escape (TCP framing).
+const V5_DLE: u8 = 0xFE;
+
+/// TSL v5.0 start of packet (TCP framing).
+const V5_STX: u8 = 0x02;
+
+/// Full brightness (2-bit field, both versions).
+const BRIGHTNESS_FULL: u8 = 3;
+
+/// How long to wait for a TCP destination to accept a connection.
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// TSL UMD protocol version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TslVersion {
+    V31,
+    V50,
+}
+
+impl TslVersion {
+    /// Parse a version property value ("3.1" or "5.0").
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().trim_start_matches(['v', 'V']) {
+            "3.1" | "3" => Some(Self::V31),
+            "5.0" | "5" => Some(Self::V50),
+            _ => None,
+        }
+    }
+}
+
+/// Transport for a tally destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TallyTransport {
+    Udp,
+    Tcp,
+}
+
+/// A TSL receiver, e.g. `udp://10.0.0.5:8900`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TallyDestination {
+    pub transport: TallyTransport,
+    /// `host:port`
+    pub address: String,
+}
+
+impl TallyDestination {
+    /// Parse `udp://host:port`, `tcp://host:port` or a bare `host:port` (UDP).
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (transport, address) = if let Some(rest) = s.strip_prefix("udp://") {
+            (TallyTransport::Udp, rest)
+        } else if let Some(rest) = s.strip_prefix("tcp://") {
+            (TallyTransport::Tcp, rest)
+        } else {
+            (TallyTransport::Udp, s)
+        };
+        let (host, port) = address.rsplit_once(':')?;
+        if host.is_empty() || port.parse::<u16>().is_err() {
+            return None;
+        }
+        Some(Self {
+            transport,
+            address: address.to_string(),
+        })
+    }
+}
+
+/// Tally colour of a display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tally {
+    #[default]
+    Off,
+    /// Program (red).
+    Program,
+    /// Preview (green).
+    Preview,
+    /// Routed to an AUX output (amber).
+    Aux,
+}
+
+impl Tally {
+    /// TSL v5.0 2-bit colour code (0 off, 1 red, 2 green, 3 amber).
+    fn v5_color(self) -> u16 {
+        match self {
+            Tally::Off => 0,
+            Tally::Program => 1,
+            Tally::Preview => 2,
+            Tally::Aux => 3,
+        }
+    }
+}
+
+/// One display (UMD) to publish.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TallyDisplay {
+    pub address: u16,
+    pub tally: Tally,
+    pub label: String,
+}
+
+/// Tally settings of a vision mixer block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TallyConfig {
+    pub version: TslVersion,
+    pub destinations: Vec<TallyDestination>,
+    /// TSL address of input 1.
+    pub address_offset: u16,
+    /// TSL v5.0 screen index.
+    pub screen: u16,
+}
+
+impl TallyConfig {
+    /// Read tally settings from vision mixer block properties.
+    ///
+    /// Returns `None` when no valid destination is configured.
+    pub fn from_properties(properties: &HashMap<String, PropertyValue>) -> Option<Self> {
+        let destinations: Vec<TallyDestination> = match properties.get("tally_destinations") {
+            Some(PropertyValue::String(s)) => s
+                .split(',')
+                .filter(|d| !d.trim().is_empty())
+                .filter_map(|d| {
+                    let parsed = TallyDestination::parse(d);
+                    if parsed.is_none() {
+                        warn!("Ignoring invalid tally destination '{}'", d.trim());
+                    }
+                    parsed
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        if destinations.is_empty() {
+            return None;
+        }
+
+        let version = match properties.get("tally_protocol") {
+            Some(PropertyValue::String(s)) => TslVersion::parse(s).unwrap_or(TslVersion::V50),
+            _ => TslVersion::V50,
+        };
+
+        Some(Self {
+            version,
+            destinations,
+            address_offset: vm_props::parse_u64(properties, "tally_address_offset", 1)
+                .min(u16::MAX as u64) as u16,
+            screen: vm_props::parse_u64(properties, "tally_screen", 0).min(u16::MAX as u64) as u16,
+        })
+    }
+
+    /// Build the displays for the given PGM/PVW groups and AUX-routed inputs.
+    /// PGM wins over PVW, PVW over AUX.
+    pub fn displays(
+        &self,
+        labels: &[String],
+        pgm: &[usize],
+        pvw: &[usize],
+        aux: &[usize],
+    ) -> Vec<TallyDisplay> {
+        labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| TallyDisplay {
+                address: self.address_offset.saturating_add(i as u16),
+                tally: if pgm.contains(&i) {
+                    Tally::Program
+                } else if pvw.contains(&i) {
+                    Tally::Preview
+                } else if aux.contains(&i) {
+                    Tally::Aux
+                } else {
+                    Tally::Off
+                },
+                label: label.clone(),
+            })
+            .collect()
+    }
+
+    /// Encode displays into the packets to send to one destination.
+    pub fn encode(&self, displays: &[TallyDisplay], transport: TallyTransport) -> Vec<Vec<u8>> {
+        match self.version {
+            TslVersion::V31 => displays
+                .iter()
+                .filter(|d| d.address <= V31_MAX_ADDRESS)
+                .map(|d| encode_v31(d).to_vec())
+                .collect(),
+            TslVersion::V50 => {
+                let packet = encode_v50(self.screen, displays);
+                match transport {
+                    TallyTransport::Udp => vec![packet],
+                    TallyTransport::Tcp => vec![frame_v50_tcp(&packet)],
+                }
+            }
+        }
+    }
+}
+
+/// Replace non-printable and non-ASCII characters (TSL text is ASCII).
+fn ascii_text(label: &str) -> impl Iterator<Item = u8> + '_ {
+    label.chars().map(|c| {
+        if c.is_ascii() && !c.is_ascii_control() {
+            c as u8
+        } else {
+            b'?'
+        }
+    })
+}
+
+/// Encode a TSL v3.1 display message.
+///
+/// Layout: header (0x80 + address), control (bits 0-3 tally 1-4, bits 4-5
+/// brightness), then 16 characters of space-padded text.
+/// Tally 1 is program (red), tally 2 preview (green) and tally 3 AUX.
+pub fn encode_v31(display: &TallyDisplay) -> [u8; 2 + V31_TEXT_LEN] {
+    let mut msg = [b' '; 2 + V31_TEXT_LEN];
+    msg[0] = 0x80 + display.address.min(V31_MAX_ADDRESS) as u8;
+    let tally_bits = match display.tally {
+        Tally::Off => 0,
+        Tally::Program => 0x01,
+        Tally::Preview => 0x02,
+        Tally::Aux => 0x04,
+    };
+    msg[1] = tally_bits | (BRIGHTNESS_FULL << 4);
+    for (dst, src) in msg[2..].iter_mut().zip(ascii_text(&display.label)) {
+        *dst = src;
+    }
+    msg
+}
+
+/// Encode a TSL v5.0 packet for one screen (little-endian, ASCII text).
+///
+/// Layout: PBC (byte count after this field), VER, FLAGS, SCREEN, then per
+/// display INDEX, CONTROL (RH tally bits 0-1, text tally 2-3, LH tally 4-5,
+/// brightness 6-7), LENGTH and TEXT.
+pub fn encode_v50(screen: u16, displays: &[TallyDisplay]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.push(0); // VER
+    body.push(0); // FLAGS: ASCII text, display data
+    body.extend_from_slice(&screen.to_le_bytes());
+    for display in displays {
+        let color = display.tally.v5_color();
+        let control = color | (color << 2) | (color << 4) | ((BRIGHTNESS_FULL as u16) << 6);
+        let text: Vec<u8> = ascii_text(&display.label).collect();
+        body.extend_from_slice(&display.address.to_le_bytes());
+        body.extend_from_slice(&control.to_le_bytes());
+        body.extend_from_slice(&(text.len() as u16).to_le_bytes());
+        body.extend_from_slice(&text);
+    }
+
+    let mut packet = Vec::with_capacity(body.len() + 2);
+    packet.extend_from_slice(&(body.len() as u16).to_le_bytes());
+    packet.extend_from_slice(&body);
+    packet
+}
+
+/// Wrap a TSL v5.0 packet in DLE/STX framing, escaping DLE bytes.
+pub fn frame_v50_tcp(packet: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(packet.len() + 4);
+    framed.push(V5_DLE);
+    framed.push(V5_STX);
+    for &b in packet {
+        framed.push(b);
+        if b == V5_DLE {
+            framed.push(V5_DLE);
+        }
+    }
+    framed
+}
+
+/// Sends encoded tally packets, with one task per destination.
+///
+/// A destination task only sends the latest packets it was handed, so a
+/// slow or unreachable receiver neither holds up the others nor builds up
+/// stale tally. TCP connections stay open between updates and are
+/// re-established on the next update after a failure.
+#[derive(Default)]
+pub struct TallySender {
+    destinations: HashMap<TallyDestination, watch::Sender<Vec<Vec<u8>>>>,
+}
+
+impl TallySender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish displays to every destination of a config.
+    pub fn publish(&mut self, config: &TallyConfig, displays: &[TallyDisplay]) {
+        for dest in &config.destinations {
+            let packets = config.encode(displays, dest.transport);
+            self.destinations
+                .entry(dest.clone())
+                .or_insert_with(|| spawn_destination(dest.clone()))
+                .send_replace(packets);
+        }
+    }
+}
+
+/// Start the task sending to one destination.
+fn spawn_destination(dest: TallyDestination) -> watch::Sender<Vec<Vec<u8>>> {
+    let (tx, mut rx) = watch::channel(Vec::new());
+    tokio::spawn(async move {
+        let mut tcp = None;
+        while rx.changed().await.is_ok() {
+            let packets = rx.borrow_and_update().clone();
+            let result = match dest.transport {
+                TallyTransport::Udp => send_udp(&dest.address, &packets).await,
+                TallyTransport::Tcp => send_tcp(&mut tcp, &dest.address, &packets).await,
+            };
+            if let Err(e) = result {
+                warn!("Failed to send TSL tally to {}: {}", dest.address, e);
+            }
+        }
+    });
+    tx
+}
+
+async fn send_tcp(
+    connection: &mut Option<TcpStream>,
+    address: &str,
+    packets: &[Vec<u8>],
+) -> std::io::Result<()> {
+    // Retry once with a fresh connection if the cached one has gone away
+    for attempt in 0..2 {
+        if connection.is_none() {
+            let stream = tokio::time::timeout(TCP_CONNECT_TIMEOUT, TcpStream::connect(address))
+                .await
+                .map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::TimedOut, "connect timed out")
+                })??;
+            stream.set_nodelay(true)?;
+            *connection = Some(stream);
+        }
+        let stream = connection.as_mut().expect("connection just made");
+        let mut result = Ok(());
+        for packet in packets {
+            result = stream.write_all(packet).await;
+            if result.is_err() {
+                break;
+            }
+        }
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                *connection = None;
+                if attempt == 1 {
+                    return Err(e);
+                }
+                debug!("TSL tally connection to {} lost, reconnecting", address);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn send_udp(address: &str, packets: &[Vec<u8>]) -> std::io::Result<()> {
+    let target = tokio::net::lookup_host(address)
+        .await?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address"))?;
+    let bind = if target.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(bind).await?;
+    for packet in packets {
+        socket.send_to(packet, target).await?;
+    }
+    Ok(())
+}
+
+/// Start the tally publisher task.
+pub fn start(state: AppState) {
+    let mut events = state.events().subscribe();
+    tokio::spawn(async move {
+        let mut sender = TallySender::new();
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!("Tally publisher skipped {} events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            match event {
+                StromEvent::VisionMixerStateChanged {
+                    flow_id,
+                    block_id,
+                    program_inputs,
+                    preview_inputs,
+                    ..
+                } => {
+                    publish_block(
+                        &state,
+                        &mut sender,
+                        &flow_id,
+                        Some(&block_id),
+                        Some((program_inputs, preview_inputs)),
+                    )
+                    .await;
+                }
+                StromEvent::VisionMixerAuxChanged {
+                    flow_id, block_id, ..
+                } => {
+                    publish_block(&state, &mut sender, &flow_id, Some(&block_id), None).await;
+                }
+                StromEvent::FlowStarted { flow_id } => {
+                    publish_block(&state, &mut sender, &flow_id, None, None).await;
+                }
+                StromEvent::FlowStopped { flow_id } => {
+                    publish_block(&state, &mut sender, &flow_id, None, Some((vec![], vec![])))
+                        .await;
+                }
+                _ => {}
+            }
+        }
+    });
+    info!("Started TSL tally publisher");
+}
+
+/// Publish tally for one vision mixer block, or all of them in a flow.
+///
+/// Without explicit groups the live overlay state (or the configured initial
+/// PGM/PVW) is used.
+async fn publish_block(
+    state: &AppState,
+    sender: &mut TallySender,
+    flow_id: &FlowId,
+    block_id: Option<&str>,
+    groups: Option<(Vec<usize>, Vec<usize>)>,
+) {
+    let Some(flow) = state.get_flow(flow_id).await else {
+        return;
+    };
+    for block in flow.blocks.iter().filter(|b| {
+        b.block_definition_id == "builtin.vision_mixer" && block_id.is_none_or(|id| b.id == id)
+    }) {
+        let Some(config) = TallyConfig::from_properties(&block.properties) else {
+            continue;
+        };
+        let num_inputs = vm_props::parse_num_inputs(&block.properties);
+        let labels = vm_props::parse_input_labels(&block.properties, num_inputs);
+        let overlay_state = overlay::get_overlay_state(&block.id);
+        let (pgm, pvw) = groups.clone().unwrap_or_else(|| match &overlay_state {
+            Some(s) => (s.pgm_group(), s.pvw_group()),
+            None => (
+                vec![vm_props::parse_initial_pgm(&block.properties, num_inputs)],
+                vec![vm_props::parse_initial_pvw(&block.properties, num_inputs)],
+            ),
+        });
+        // A stopped flow has an empty PGM group: everything goes off, AUX included
+        let aux = if pgm.is_empty() {
+            Vec::new()
+        } else {
+            let sources = match &overlay_state {
+                Some(s) => s.aux_sources(),
+                None => vm_props::parse_aux_sources(
+                    &block.properties,
+                    vm_props::parse_num_aux_outputs(&block.properties),
+                    num_inputs,
+                ),
+            };
+            aux_outputs::all_inputs(&sources, &pvw)
+        };
+        debug!(
+            "Publishing TSL tally for block {} (PGM {:?}, PVW {:?}, AUX {:?})",
+            block.id, pgm, pvw, aux
+        );
+        let displays = config.displays(&labels, &pgm, &pvw, &aux);
+        sender.publish(&config, &displays);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(address: u16, tally: Tally, label: &str) -> TallyDisplay {
+        TallyDisplay {
+            address,
+            tally,
+            label: label.to_string(),
+        }
+    }
+
+    fn config(version: TslVersion, destination: &str) -> TallyConfig {
+        TallyConfig {
+            version,
+            destinations: vec![TallyDestination::parse(destination).unwrap()],
+            address_offset: 1,
+            screen: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_destination() {
+        assert_eq!(
+            TallyDestination::parse("tcp://10.0.0.5:9000"),
+            Some(TallyDestination {
+                transport: TallyTransport::Tcp,
+                address: "10.0.0.5:9000".to_string(),
+            })
+        );
+        assert_eq!(
+            TallyDestination::parse(" 10.0.0.5:8900 ")
+                .unwrap()
+                .transport,
+            TallyTransport::Udp
+        );
+        assert!(TallyDestination::parse("udp://10.0.0.5").is_none());
+        assert!(TallyDestination::parse("udp://:8900").is_none());
+    }
+
+    #[test]
+    fn test_config_from_properties() {
+        let mut props = HashMap::new();
+        assert!(TallyConfig::from_properties(&props).is_none());
+
+        props.insert(
+            "tally_destinations".to_string(),
+            PropertyValue::String("udp://127.0.0.1:8900, bogus, tcp://umd:9000".to_string()),
+        );
+        props.insert(
+            "tally_protocol".to_string(),
+            PropertyValue::String("3.1".to_string()),
+        );
+        props.insert("tally_address_offset".to_string(), PropertyValue::UInt(10));
+        let config = TallyConfig::from_properties(&props).unwrap();
+        assert_eq!(config.version, TslVersion::V31);
+        assert_eq!(config.destinations.len(), 2);
+        assert_eq!(config.address_offset, 10);
+        assert_eq!(config.screen, 0);
+    }
+
+    #[test]
+    fn test_displays_pgm_wins() {
+        let config = config(TslVersion::V50, "127.0.0.1:8900");
+        let labels: Vec<String> = ["Cam 1", "Cam 2", "Cam 3"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let displays = config.displays(&labels, &[0], &[0, 2], &[0, 1, 2]);
+        assert_eq!(displays[0], display(1, Tally::Program, "Cam 1"));
+        assert_eq!(displays[1], display(2, Tally::Aux, "Cam 2"));
+        assert_eq!(displays[2], display(3, Tally::Preview, "Cam 3"));
+    }
+
+    #[test]
+    fn test_encode_v31() {
+        let msg = encode_v31(&display(5, Tally::Program, "Camera One Long Label"));
+        assert_eq!(msg[0], 0x85);
+        assert_eq!(msg[1], 0x31);
+        assert_eq!(&msg[2..], b"Camera One Long ");
+
+        let msg = encode_v31(&display(0, Tally::Preview, "Cäm"));
+        assert_eq!(msg[0], 0x80);
+        assert_eq!(msg[1], 0x32);
+        assert_eq!(&msg[2..], b"C?m             ");
+    }
+
+    #[test]
+    fn test_encode_aux() {
+        let msg = encode_v31(&display(2, Tally::Aux, "Cam 3"));
+        assert_eq!(msg[1], 0x34);
+        assert_eq!(Tally::Aux.v5_color(), 3);
+    }
+
+    #[test]
+    fn test_encode_v50() {
+        let packet = encode_v50(
+            2,
+            &[
+                display(1, Tally::Program, "A"),
+                display(2, Tally::Preview, "BC"),
+            ],
+        );
+        #[rustfmt::skip]
+        let expected: Vec<u8> = vec![
+            19, 0,              // PBC
+            0, 0,               // VER, FLAGS
+            2, 0,               // SCREEN
+            1, 0, 0xD5, 0, 1, 0, b'A',
+            2, 0, 0xEA, 0, 2, 0, b'B', b'C',
+        ];
+        assert_eq!(packet, expected);
+    }
+
+    #[test]
+    fn test_frame_v50_tcp_escapes_dle() {
+        assert_eq!(
+            frame_v50_tcp(&[1, 0xFE, 2]),
+            vec![0xFE, 0x02, 1, 0xFE, 0xFE, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_udp_v31() {
+        let listener = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = config(TslVersion::V31, &format!("udp://{}", addr));
+        let labels = vec!["Cam 1".to_string(), "Cam 2".to_string()];
+        let displays = config.displays(&labels, &[1], &[0], &[]);
+
+        let mut sender = TallySender::new();
+        sender.publish(&config, &displays);
+
+        let mut buf = [0u8; 64];
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let n = tokio::time::timeout(Duration::from_secs(2), listener.recv(&mut buf))
+                .await
+                .expect("tally packet not received")
+                .unwrap();
+            received.push(buf[..n].to_vec());
+        }
+        assert_eq!(
+            received[0],
+            encode_v31(&display(1, Tally::Preview, "Cam 1"))
+        );
+        assert_eq!(
+            received[1],
+            encode_v31(&display(2, Tally::Program, "Cam 2"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_udp_v50() {
+        let listener = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = config(TslVersion::V50, &addr.to_string());
+        let labels = vec!["Cam 1".to_string(), "Cam 2".to_string()];
+        let displays = config.displays(&labels, &[0], &[1], &[]);
+
+        let mut sender = TallySender::new();
+        sender.publish(&config, &displays);
+
+        let mut buf = [0u8; 256];
+        let n = tokio::time::timeout(Duration::from_secs(2), listener.recv(&mut buf))
+            .await
+            .expect("tally packet not received")
+            .unwrap();
+        assert_eq!(&buf[..n], encode_v50(0, &displays).as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_publish_tcp_v50() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = config(TslVersion::V50, &format!("tcp://{}", addr));
+        let labels = vec!["Cam 1".to_string(), "Cam 2".to_string()];
+        let displays = config.displays(&labels, &[1], &[0], &[]);
+
+        // Publishing returns without waiting for the connection
+        let mut sender = TallySender::new();
+        sender.publish(&config, &displays);
+
+        let (mut stream, _) = tokio::time::timeout(Duration::from_secs(2), listener.accept())
+            .await
+            .expect("tally connection not made")
+            .unwrap();
+        let expected = frame_v50_tcp(&encode_v50(0, &displays));
+        let mut buf = vec![0u8; expected.len()];
+        tokio::time::timeout(
+            Duration::from_secs(2),
+            tokio::io::AsyncReadExt::read_exact(&mut stream, &mut buf),
+        )
+        .await
+        .expect("tally packet not received")
+        .unwrap();
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,238 @@
+//! Keyframe timeline editor for animating live block properties.
+
+use egui::{Color32, Ui};
+use egui_plot::{Line, Plot, PlotPoints, Points};
+use strom_types::animation::{value_at, Easing, Keyframe, SetPropertyAnimationRequest};
+use strom_types::block::{ExposedProperty, PropertyType};
+use strom_types::{BlockDefinition, BlockInstance, PropertyValue};
+
+/// Curve color in the timeline preview.
+const COLOR_CURVE: Color32 = Color32::from_rgb(0, 180, 180);
+/// Points sampled along the curve for the preview.
+const PREVIEW_SAMPLES: usize = 200;
+
+/// Request from the timeline editor.
+pub enum TimelineAction {
+    /// Create and start the animation with this ID.
+    Start(String, SetPropertyAnimationRequest),
+    /// Cancel the animation with this ID.
+    Cancel(String),
+}
+
+/// Editor state of one block, kept in egui memory between frames.
+#[derive(Clone, Default)]
+struct TimelineState {
+    /// Exposed property being edited
+    property: Option<String>,
+    keyframes: Vec<Keyframe>,
+    looping: bool,
+}
+
+/// Live numeric properties of a block that can be animated.
+fn animatable(definition: &BlockDefinition) -> Vec<&ExposedProperty> {
+    definition
+        .exposed_properties
+        .iter()
+        .filter(|p| {
+            p.live
+                && p.mapping.element_id != "_block"
+                && matches!(
+                    p.property_type,
+                    PropertyType::Int | PropertyType::UInt | PropertyType::Float
+                )
+        })
+        .collect()
+}
+
+/// Numeric value of a block property, or its default.
+fn current_value(block: &BlockInstance, prop: &ExposedProperty) -> f64 {
+    match block
+        .properties
+        .get(&prop.name)
+        .or(prop.default_value.as_ref())
+    {
+        Some(PropertyValue::Float(f)) => *f,
+        Some(PropertyValue::Int(i)) => *i as f64,
+        Some(PropertyValue::UInt(u)) => *u as f64,
+        _ => 0.0,
+    }
+}
+
+/// Show the timeline editor for the live properties of a block.
+///
+/// Returns an action when Start or Cancel was clicked.
+pub fn show(
+    ui: &mut Ui,
+    block: &BlockInstance,
+    definition: &BlockDefinition,
+) -> Option<TimelineAction> {
+    let props = animatable(definition);
+    if props.is_empty() {
+        return None;
+    }
+
+    let state_id = ui.id().with(("animation_timeline", &block.id));
+    let mut state: TimelineState = ui.data_mut(|d| d.get_temp(state_id).unwrap_or_default());
+    let mut action = None;
+
+    egui::CollapsingHeader::new("Automation")
+        .id_salt(("animation_timeline_header", &block.id))
+        .default_open(false)
+        .show(ui, |ui| {
+            let selected = state
+                .property
+                .as_ref()
+                .and_then(|name| props.iter().find(|p| &p.name == name))
+                .copied();
+            egui::ComboBox::from_id_salt(("animation_property", &block.id))
+                .selected_text(selected.map_or("Select property", |p| p.label.as_str()))
+                .show_ui(ui, |ui| {
+                    for prop in &props {
+                        if ui
+                            .selectable_label(
+                                selected.is_some_and(|s| s.name == prop.name),
+                                &prop.label,
+                            )
+                            .clicked()
+                            && state.property.as_ref() != Some(&prop.name)
+                        {
+                            state.property = Some(prop.name.clone());
+                            state.keyframes = vec![Keyframe {
+                                time_ms: 0,
+                                value: current_value(block, prop),
+                                easing: Easing::Linear,
+                            }];
+                        }
+                    }
+                });
+            let Some(prop) = selected else {
+                ui.small("Pick a live property to animate while the flow is running.");
+                return;
+            };
+
+            show_preview(ui, &block.id, &state.keyframes, state.looping);
+
+            let mut remove = None;
+            egui::Grid::new(("animation_keyframes", &block.id))
+                .num_columns(4)
+                .spacing([6.0, 2.0])
+                .show(ui, |ui| {
+                    ui.small("Time (ms)");
+                    ui.small("Value");
+                    ui.small("Easing to next");
+                    ui.end_row();
+                    for (i, key) in state.keyframes.iter_mut().enumerate() {
+                        ui.add(egui::DragValue::new(&mut key.time_ms).speed(10.0));
+                        ui.add(egui::DragValue::new(&mut key.value).speed(0.01));
+                        egui::ComboBox::from_id_salt(("animation_easing", &block.id, i))
+                            .selected_text(key.easing.label())
+                            .width(90.0)
+                            .show_ui(ui, |ui| {
+                                for easing in Easing::all() {
+                                    ui.selectable_value(&mut key.easing, *easing, easing.label());
+                                }
+                            });
+                        if ui
+                            .small_button(egui_phosphor::regular::X)
+                            .on_hover_text("Remove keyframe")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                state.keyframes.remove(i);
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!("{} Keyframe", egui_phosphor::regular::PLUS))
+                    .clicked()
+                {
+                    let (time_ms, value) = state
+                        .keyframes
+                        .iter()
+                        .max_by_key(|k| k.time_ms)
+                        .map_or((0, current_value(block, prop)), |k| {
+                            (k.time_ms + 1000, k.value)
+                        });
+                    state.keyframes.push(Keyframe {
+                        time_ms,
+                        value,
+                        easing: Easing::Linear,
+                    });
+                }
+                ui.checkbox(&mut state.looping, "Loop");
+            });
+
+            let animation_id = format!("{}.{}", block.id, prop.name);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !state.keyframes.is_empty(),
+                        egui::Button::new(format!("{} Start", egui_phosphor::regular::PLAY)),
+                    )
+                    .clicked()
+                {
+                    action = Some(TimelineAction::Start(
+                        animation_id.clone(),
+                        SetPropertyAnimationRequest {
+                            element_id: format!("{}:{}", block.id, prop.mapping.element_id),
+                            pad_name: None,
+                            property_name: prop.mapping.property_name.clone(),
+                            keyframes: state.keyframes.clone(),
+                            looping: state.looping,
+                            autostart: true,
+                        },
+                    ));
+                }
+                if ui
+                    .button(format!("{} Cancel", egui_phosphor::regular::STOP))
+                    .on_hover_text("Stop the animation, keeping the current value")
+                    .clicked()
+                {
+                    action = Some(TimelineAction::Cancel(animation_id));
+                }
+            });
+        });
+
+    ui.data_mut(|d| d.insert_temp(state_id, state));
+    action
+}
+
+/// Plot the animation curve with its keyframes.
+fn show_preview(ui: &mut Ui, block_id: &str, keyframes: &[Keyframe], looping: bool) {
+    let mut sorted = keyframes.to_vec();
+    sorted.sort_by_key(|k| k.time_ms);
+    let end_ms = sorted.last().map_or(0, |k| k.time_ms).max(1000);
+    // Show a second pass when looping
+    let span_ms = if looping { end_ms * 2 } else { end_ms };
+    let pass_ms = sorted.last().map_or(0, |k| k.time_ms);
+
+    let curve: PlotPoints<'_> = (0..=PREVIEW_SAMPLES)
+        .filter_map(|i| {
+            let t = span_ms * i as u64 / PREVIEW_SAMPLES as u64;
+            let local = if looping && pass_ms > 0 {
+                t % pass_ms
+            } else {
+                t
+            };
+            value_at(&sorted, local).map(|v| [t as f64, v])
+        })
+        .collect();
+    let points: PlotPoints<'_> = sorted.iter().map(|k| [k.time_ms as f64, k.value]).collect();
+
+    Plot::new(("animation_preview", block_id))
+        .height(90.0)
+        .x_axis_label("ms")
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .allow_drag(false)
+        .allow_boxed_zoom(false)
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new("", curve).color(COLOR_CURVE).width(1.5));
+            plot_ui.points(Points::new("", points).radius(3.0).color(COLOR_CURVE));
+        });
+}
@@ -0,0 +1,62 @@
+use strom_types::animation::SetPropertyAnimationRequest;
+use strom_types::FlowId;
+
+use super::*;
+
+impl ApiClient {
+    /// Create or replace a keyframed property animation on a running flow.
+    pub async fn set_property_animation(
+        &self,
+        flow_id: &FlowId,
+        animation_id: &str,
+        request: &SetPropertyAnimationRequest,
+    ) -> ApiResult<()> {
+        let url = format!(
+            "{}/flows/{}/animations/{}",
+            self.base_url,
+            flow_id,
+            urlencoding::encode(animation_id)
+        );
+        let response = self
+            .with_auth(self.client.put(&url))
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Http(status, text));
+        }
+
+        Ok(())
+    }
+
+    /// Cancel and remove a property animation.
+    pub async fn cancel_property_animation(
+        &self,
+        flow_id: &FlowId,
+        animation_id: &str,
+    ) -> ApiResult<()> {
+        let url = format!(
+            "{}/flows/{}/animations/{}",
+            self.base_url,
+            flow_id,
+            urlencoding::encode(animation_id)
+        );
+        let response = self
+            .with_auth(self.client.delete(&url))
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Http(status, text));
+        }
+
+        Ok(())
+    }
+}
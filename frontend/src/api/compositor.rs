@@ -191,4 +191,138 @@ impl ApiClient {
         info!("Successfully started animation");
         Ok(())
     }
+
+    /// Get the current layout and the layout presets of a compositor block.
+    pub async fn get_compositor_layouts(
+        &self,
+        flow_id: &str,
+        block_id: &str,
+    ) -> ApiResult<strom_types::compositor::CompositorLayoutsResponse> {
+        let url = format!(
+            "{}/flows/{}/blocks/{}/layouts",
+            self.base_url, flow_id, block_id
+        );
+
+        let response = self
+            .with_auth(self.client.get(&url))
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Http(status, text));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Decode(e.to_string()))
+    }
+
+    /// Store a layout preset on a compositor block.
+    pub async fn save_layout_preset(
+        &self,
+        flow_id: &str,
+        block_id: &str,
+        name: &str,
+        layout: strom_types::compositor::CompositorLayout,
+    ) -> ApiResult<()> {
+        use strom_types::compositor::SaveLayoutPresetRequest;
+
+        let url = format!(
+            "{}/flows/{}/blocks/{}/layouts/presets/{}",
+            self.base_url,
+            flow_id,
+            block_id,
+            urlencoding::encode(name)
+        );
+        let request = SaveLayoutPresetRequest {
+            layout: Some(layout),
+        };
+
+        let response = self
+            .with_auth(self.client.put(&url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Http(status, text));
+        }
+
+        tracing::info!("Saved layout preset '{}' on {}", name, block_id);
+        Ok(())
+    }
+
+    /// Delete a layout preset from a compositor block.
+    pub async fn delete_layout_preset(
+        &self,
+        flow_id: &str,
+        block_id: &str,
+        name: &str,
+    ) -> ApiResult<()> {
+        let url = format!(
+            "{}/flows/{}/blocks/{}/layouts/presets/{}",
+            self.base_url,
+            flow_id,
+            block_id,
+            urlencoding::encode(name)
+        );
+
+        let response = self
+            .with_auth(self.client.delete(&url))
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Http(status, text));
+        }
+
+        Ok(())
+    }
+
+    /// Recall a layout preset on a compositor block with an animated move.
+    pub async fn recall_layout_preset(
+        &self,
+        flow_id: &str,
+        block_id: &str,
+        name: &str,
+        duration_ms: u64,
+    ) -> ApiResult<strom_types::compositor::CompositorLayoutResponse> {
+        use strom_types::compositor::RecallLayoutRequest;
+
+        let url = format!(
+            "{}/flows/{}/blocks/{}/layouts/presets/{}/recall",
+            self.base_url,
+            flow_id,
+            block_id,
+            urlencoding::encode(name)
+        );
+
+        let response = self
+            .with_auth(self.client.post(&url))
+            .json(&RecallLayoutRequest { duration_ms })
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Http(status, text));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Decode(e.to_string()))
+    }
 }
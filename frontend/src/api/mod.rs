@@ -1,5 +1,6 @@
 //! API client for communicating with the Strom backend.
 
+mod animations;
 mod auth;
 mod blocks;
 mod compositor;
@@ -1,4 +1,5 @@
 use serde::Serialize;
+use strom_types::mediaplayer::PlaylistItem;
 use strom_types::FlowId;
 
 use super::*;
@@ -9,7 +10,7 @@ impl ApiClient {
         &self,
         flow_id: FlowId,
         block_id: &str,
-        files: Vec<String>,
+        items: Vec<PlaylistItem>,
     ) -> ApiResult<()> {
         use tracing::info;
 
@@ -20,17 +21,17 @@ impl ApiClient {
         info!(
             "Setting playlist for player {}: {} files",
             block_id,
-            files.len()
+            items.len()
         );
 
         #[derive(Serialize)]
         struct SetPlaylistRequest {
-            files: Vec<String>,
+            items: Vec<PlaylistItem>,
         }
 
         let response = self
             .with_auth(self.client.post(&url))
-            .json(&SetPlaylistRequest { files })
+            .json(&SetPlaylistRequest { items })
             .send()
             .await
             .map_err(|e| {
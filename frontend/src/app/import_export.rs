@@ -329,6 +329,18 @@ impl StromApp {
             }
         }
 
+        // Keep vision mixer macros and mixer automation attached to their blocks
+        for vm_macro in &mut flow.macros {
+            if let Some(new_id) = block_id_map.get(&vm_macro.block_id) {
+                vm_macro.block_id = new_id.clone();
+            }
+        }
+        for lane in &mut flow.mixer_automation {
+            if let Some(new_id) = block_id_map.get(&lane.block_id) {
+                lane.block_id = new_id.clone();
+            }
+        }
+
         flow
     }
 
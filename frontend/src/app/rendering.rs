@@ -1585,6 +1585,24 @@ impl StromApp {
                             });
                         }
 
+                        // Handle keyframe timeline start/cancel
+                        if let Some((flow_id, action)) = result.animation_action {
+                            let api = self.api.clone();
+                            spawn_task(async move {
+                                let outcome = match &action {
+                                    crate::animation_timeline::TimelineAction::Start(id, request) => {
+                                        api.set_property_animation(&flow_id, id, request).await
+                                    }
+                                    crate::animation_timeline::TimelineAction::Cancel(id) => {
+                                        api.cancel_property_animation(&flow_id, id).await
+                                    }
+                                };
+                                if let Err(e) = outcome {
+                                    tracing::warn!("Property animation request failed: {}", e);
+                                }
+                            });
+                        }
+
                         // Handle recorder file download request
                         if let Some(relative_path) = result.recorder_download_requested {
                             let url = self.api.get_media_download_url(&relative_path);
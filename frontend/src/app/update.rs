@@ -505,6 +505,21 @@ impl eframe::App for StromApp {
                             );
                             tracing::trace!("Meter data stored for element {}", element_id);
                         }
+                        StromEvent::PropertyChanged {
+                            flow_id,
+                            element_id,
+                            property_name,
+                            value,
+                        } => {
+                            // Block-level changes (snapshot recall) on the open mixer
+                            if let Some(editor) = self
+                                .mixer_editor
+                                .as_mut()
+                                .filter(|e| e.flow_id() == flow_id && e.block_id() == element_id)
+                            {
+                                editor.apply_property(&property_name, value);
+                            }
+                        }
                         StromEvent::SpectrumData {
                             flow_id,
                             element_id,
@@ -636,18 +651,22 @@ impl eframe::App for StromApp {
                             block_id,
                             state,
                             current_file,
+                            cued,
+                            ..
                         } => {
                             tracing::debug!(
-                                "Media player state changed: flow={}, block={}, state={}",
+                                "Media player state changed: flow={}, block={}, state={}, cued={}",
                                 flow_id,
                                 block_id,
-                                state
+                                state,
+                                cued
                             );
                             self.mediaplayer_data.update_state(
                                 flow_id,
                                 block_id,
                                 state,
                                 current_file,
+                                cued,
                             );
                         }
                         StromEvent::SystemStats(stats) => {
@@ -851,6 +870,10 @@ impl eframe::App for StromApp {
                         StromEvent::BufferAgeProbeDeactivated { probe_id, .. } => {
                             self.buffer_age_data.probe_deactivated(&probe_id);
                         }
+                        StromEvent::DeviceAdded { .. } | StromEvent::DeviceRemoved { .. } => {
+                            // Device block enums are filled from discovered devices
+                            self.blocks_loaded = false;
+                        }
                         _ => {}
                     }
                 }
@@ -1478,7 +1501,7 @@ impl eframe::App for StromApp {
                     if let Some(strom_types::PropertyValue::String(playlist_json)) =
                         block.properties.get("playlist")
                     {
-                        if let Ok(playlist) = serde_json::from_str::<Vec<String>>(playlist_json) {
+                        if let Some(playlist) = crate::mediaplayer::parse_playlist(playlist_json) {
                             editor.set_playlist(playlist);
                         }
                     }
@@ -1735,8 +1758,8 @@ impl eframe::App for StromApp {
                                 if let Some(strom_types::PropertyValue::String(playlist_json)) =
                                     block.properties.get("playlist")
                                 {
-                                    if let Ok(playlist) =
-                                        serde_json::from_str::<Vec<String>>(playlist_json)
+                                    if let Some(playlist) =
+                                        crate::mediaplayer::parse_playlist(playlist_json)
                                     {
                                         editor.set_playlist(playlist);
                                     }
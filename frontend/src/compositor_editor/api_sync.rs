@@ -1,4 +1,5 @@
 use egui::Context;
+use strom_types::compositor::InputLayout;
 use strom_types::PropertyValue;
 
 use super::*;
@@ -37,6 +38,8 @@ impl CompositorEditor {
                 }
             });
         }
+
+        self.load_layout_presets(&ctx);
     }
 
     /// Check for loaded properties and update inputs.
@@ -69,6 +72,12 @@ impl CompositorEditor {
                     if let Some(PropertyValue::String(v)) = props.get("sizing-policy") {
                         input.sizing_policy = v.clone();
                     }
+                    if let Some(style) = InputStyle::from_properties(&props) {
+                        input.style = Some(style);
+                    }
+                    if let Some(key) = InputKey::from_properties(&props) {
+                        input.key = Some(key);
+                    }
 
                     // Clear the storage key
                     crate::app::remove_local_storage(&key);
@@ -317,6 +326,8 @@ impl CompositorEditor {
             let alpha = input.alpha;
             let zorder = input.zorder;
             let sizing_policy = input.sizing_policy.clone();
+            let style = input.style.clone();
+            let key = input.key.clone();
 
             self.update_pad_property(ctx, idx, "xpos", PropertyValue::Int(xpos as i64));
             self.update_pad_property(ctx, idx, "ypos", PropertyValue::Int(ypos as i64));
@@ -330,6 +341,16 @@ impl CompositorEditor {
                 "sizing-policy",
                 PropertyValue::String(sizing_policy),
             );
+            if let Some(style) = style {
+                for (name, value) in style.properties() {
+                    self.update_pad_property(ctx, idx, name, value);
+                }
+            }
+            if let Some(key) = key {
+                for (name, value) in key.properties() {
+                    self.update_pad_property(ctx, idx, name, value);
+                }
+            }
         }
 
         // Restore live updates setting
@@ -457,4 +478,186 @@ impl CompositorEditor {
             crate::app::remove_local_storage(&key);
         }
     }
+
+    /// Fetch the names of the layout presets stored with the block.
+    pub(super) fn load_layout_presets(&self, ctx: &Context) {
+        let flow_id = self.flow_id;
+        let block_id = self.block_id.clone();
+        let api = self.api.clone();
+        let ctx = ctx.clone();
+
+        crate::app::spawn_task(async move {
+            fetch_layout_presets(&api, flow_id, &block_id).await;
+            ctx.request_repaint();
+        });
+    }
+
+    /// Store the layout shown in the editor as a preset on the block.
+    pub(super) fn save_layout_preset(&mut self, ctx: &Context) {
+        let name = self.layout_preset_name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        let layout = self
+            .inputs
+            .iter()
+            .map(|input| InputLayout {
+                input: input.input_index,
+                xpos: input.xpos,
+                ypos: input.ypos,
+                width: input.width,
+                height: input.height,
+                alpha: input.alpha,
+                zorder: input.zorder,
+            })
+            .collect();
+        let flow_id = self.flow_id;
+        let block_id = self.block_id.clone();
+        let api = self.api.clone();
+        let ctx = ctx.clone();
+
+        self.status = format!("Saving layout '{}'...", name);
+        crate::app::spawn_task(async move {
+            let status = match api
+                .save_layout_preset(&flow_id.to_string(), &block_id, &name, layout)
+                .await
+            {
+                Ok(()) => format!("Layout '{}' saved", name),
+                Err(e) => format!("Failed to save layout '{}': {}", name, e),
+            };
+            crate::app::set_local_storage(
+                &format!("compositor_layout_status_{}_{}", flow_id, block_id),
+                &status,
+            );
+            fetch_layout_presets(&api, flow_id, &block_id).await;
+            ctx.request_repaint();
+        });
+    }
+
+    /// Delete a layout preset from the block.
+    pub(super) fn delete_layout_preset(&mut self, ctx: &Context, name: &str) {
+        let name = name.to_string();
+        let flow_id = self.flow_id;
+        let block_id = self.block_id.clone();
+        let api = self.api.clone();
+        let ctx = ctx.clone();
+
+        crate::app::spawn_task(async move {
+            if let Err(e) = api
+                .delete_layout_preset(&flow_id.to_string(), &block_id, &name)
+                .await
+            {
+                crate::app::set_local_storage(
+                    &format!("compositor_layout_status_{}_{}", flow_id, block_id),
+                    &format!("Failed to delete layout '{}': {}", name, e),
+                );
+            }
+            fetch_layout_presets(&api, flow_id, &block_id).await;
+            ctx.request_repaint();
+        });
+    }
+
+    /// Recall a layout preset, moving the inputs over the transition duration.
+    pub(super) fn recall_layout_preset(&mut self, ctx: &Context, name: &str) {
+        let name = name.to_string();
+        let flow_id = self.flow_id;
+        let block_id = self.block_id.clone();
+        let duration_ms = if self.animate_moves {
+            self.transition_duration_ms
+        } else {
+            0
+        };
+        let api = self.api.clone();
+        let ctx = ctx.clone();
+
+        self.status = format!("Recalling layout '{}'...", name);
+        crate::app::spawn_task(async move {
+            let status_key = format!("compositor_layout_status_{}_{}", flow_id, block_id);
+            match api
+                .recall_layout_preset(&flow_id.to_string(), &block_id, &name, duration_ms)
+                .await
+            {
+                Ok(response) => {
+                    if let Ok(json) = serde_json::to_string(&response.layout) {
+                        crate::app::set_local_storage(
+                            &format!("compositor_layout_recalled_{}_{}", flow_id, block_id),
+                            &json,
+                        );
+                    }
+                    crate::app::set_local_storage(&status_key, &response.message);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to recall layout '{}': {}", name, e);
+                    crate::app::set_local_storage(
+                        &status_key,
+                        &format!("Failed to recall layout '{}': {}", name, e),
+                    );
+                }
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Pick up layout preset names, recalled layouts and status messages.
+    pub(super) fn check_layout_presets(&mut self) {
+        let key = format!(
+            "compositor_layout_presets_{}_{}",
+            self.flow_id, self.block_id
+        );
+        if let Some(json) = crate::app::get_local_storage(&key) {
+            if let Ok(names) = serde_json::from_str(&json) {
+                self.layout_presets = names;
+            }
+            crate::app::remove_local_storage(&key);
+        }
+
+        let key = format!(
+            "compositor_layout_recalled_{}_{}",
+            self.flow_id, self.block_id
+        );
+        if let Some(json) = crate::app::get_local_storage(&key) {
+            if let Ok(layout) = serde_json::from_str::<Vec<InputLayout>>(&json) {
+                for entry in layout {
+                    if let Some(input) = self.inputs.get_mut(entry.input) {
+                        input.xpos = entry.xpos;
+                        input.ypos = entry.ypos;
+                        input.width = entry.width;
+                        input.height = entry.height;
+                        input.alpha = entry.alpha;
+                        input.zorder = entry.zorder;
+                    }
+                }
+            }
+            crate::app::remove_local_storage(&key);
+        }
+
+        let key = format!(
+            "compositor_layout_status_{}_{}",
+            self.flow_id, self.block_id
+        );
+        if let Some(status) = crate::app::get_local_storage(&key) {
+            self.status = status;
+            crate::app::remove_local_storage(&key);
+        }
+    }
+}
+
+/// Fetch the layout preset names of a block for the UI loop to pick up.
+async fn fetch_layout_presets(api: &ApiClient, flow_id: FlowId, block_id: &str) {
+    match api
+        .get_compositor_layouts(&flow_id.to_string(), block_id)
+        .await
+    {
+        Ok(layouts) => {
+            let names: Vec<_> = layouts.presets.into_keys().collect();
+            let key = format!("compositor_layout_presets_{}_{}", flow_id, block_id);
+            if let Ok(json) = serde_json::to_string(&names) {
+                crate::app::set_local_storage(&key, &json);
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to load layout presets for {}: {}", block_id, e);
+        }
+    }
 }
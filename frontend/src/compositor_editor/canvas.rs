@@ -83,6 +83,30 @@ impl CompositorEditor {
             let dimmed = has_selection && !input.selected;
             let opacity_mult = if dimmed { 0.4 } else { 1.0 };
 
+            // Styled inputs: the drop shadow takes a margin at the bottom right
+            // and the picture is drawn into the remaining, rounded box.
+            let style = input.style.clone().unwrap_or_default();
+            let screen_scale = screen_rect.width() / rect.width().max(1.0);
+            let shadow =
+                style.shadow_size.min(0.5 * rect.width().min(rect.height())) * screen_scale;
+            let content_rect = Rect::from_min_size(
+                screen_rect.min,
+                (screen_rect.size() - Vec2::splat(shadow)).max(Vec2::splat(1.0)),
+            );
+            let corner_radius = (style.corner_radius * screen_scale)
+                .min(0.5 * content_rect.width().min(content_rect.height()));
+            if shadow > 0.0 {
+                let shadow_alpha = style.shadow_opacity * opacity_mult as f32;
+                painter.add(
+                    egui::epaint::RectShape::filled(
+                        content_rect.translate(Vec2::splat(0.5 * shadow)),
+                        corner_radius,
+                        Color32::from_black_alpha((255.0 * shadow_alpha) as u8),
+                    )
+                    .with_blur_width(shadow),
+                );
+            }
+
             // Draw thumbnail or fallback to colored box
             if let Some(texture) = self.thumbnails.get(&idx) {
                 // Apply input's alpha and selection dimming
@@ -112,7 +136,10 @@ impl CompositorEditor {
                     }
                 };
 
-                painter.image(texture.id(), screen_rect, uv_rect, tint);
+                painter.add(
+                    egui::epaint::RectShape::filled(content_rect, corner_radius, tint)
+                        .with_texture(texture.id(), uv_rect),
+                );
             } else {
                 // Fallback to colored box - apply input alpha and selection dimming
                 let alpha = (input.alpha * opacity_mult).clamp(0.0, 1.0);
@@ -123,7 +150,21 @@ impl CompositorEditor {
                     color.b(),
                     (color.a() as f64 * alpha) as u8,
                 );
-                painter.rect_filled(screen_rect, 0.0, color);
+                painter.rect_filled(content_rect, corner_radius, color);
+            }
+
+            if style.border_width > 0.0 {
+                let c = style.border_color;
+                let alpha = (255.0 * opacity_mult) as u8;
+                painter.rect_stroke(
+                    content_rect,
+                    corner_radius,
+                    Stroke::new(
+                        style.border_width * screen_scale,
+                        Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), alpha),
+                    ),
+                    StrokeKind::Inside,
+                );
             }
 
             let border_width = if input.selected { 3.0 } else { 1.0 };
@@ -805,6 +846,216 @@ impl CompositorEditor {
                     PropertyValue::String(sizing_policy),
                 );
             }
+
+            if self.inputs[selected_idx].style.is_some() {
+                ui.add_space(4.0);
+                ui.separator();
+                self.show_style_section(ui, selected_idx);
+            }
+
+            if self.inputs[selected_idx].key.is_some() {
+                ui.add_space(4.0);
+                ui.separator();
+                self.show_key_section(ui, selected_idx);
+            }
+        });
+    }
+
+    /// Chroma and luma key controls for the selected input.
+    fn show_key_section(&mut self, ui: &mut egui::Ui, selected_idx: usize) {
+        let Some(mut key) = self.inputs[selected_idx].key.clone() else {
+            return;
+        };
+        let mut changed: Vec<&'static str> = Vec::new();
+
+        ui.horizontal(|ui| {
+            ui.label("Key:");
+            for (mode, label) in [("none", "None"), ("chroma", "Chroma"), ("luma", "Luma")] {
+                if ui.selectable_label(key.mode == mode, label).clicked() && key.mode != mode {
+                    key.mode = mode.to_string();
+                    changed.push("key-mode");
+                }
+            }
+        });
+
+        match key.mode.as_str() {
+            "chroma" => {
+                ui.horizontal(|ui| {
+                    ui.label("Color:");
+                    if ui.color_edit_button_srgba(&mut key.color).changed() {
+                        let c = key.color;
+                        key.color = Color32::from_rgb(c.r(), c.g(), c.b());
+                        changed.push("key-color");
+                    }
+                });
+                for (name, label, value) in [
+                    ("key-tolerance", "tolerance", &mut key.tolerance),
+                    ("key-softness", "softness", &mut key.softness),
+                    ("key-spill", "spill", &mut key.spill),
+                ] {
+                    if ui
+                        .add(egui::Slider::new(value, 0.0..=1.0).text(label))
+                        .changed()
+                    {
+                        changed.push(name);
+                    }
+                }
+            }
+            "luma" => {
+                if ui
+                    .add(egui::Slider::new(&mut key.clip, 0.0..=1.0).text("clip"))
+                    .changed()
+                {
+                    changed.push("key-clip");
+                }
+                if ui
+                    .add(egui::Slider::new(&mut key.gain, 0.0..=20.0).text("gain"))
+                    .changed()
+                {
+                    changed.push("key-gain");
+                }
+                if ui.checkbox(&mut key.invert, "Invert").changed() {
+                    changed.push("key-invert");
+                }
+            }
+            _ => {}
+        }
+
+        if changed.is_empty() {
+            return;
+        }
+        let properties = key.properties();
+        self.inputs[selected_idx].key = Some(key);
+        for (name, value) in properties {
+            if changed.contains(&name) {
+                self.update_pad_property(ui.ctx(), selected_idx, name, value);
+            }
+        }
+    }
+
+    /// Crop, rotation, border, corner and shadow controls for the selected input.
+    fn show_style_section(&mut self, ui: &mut egui::Ui, selected_idx: usize) {
+        let Some(mut style) = self.inputs[selected_idx].style.clone() else {
+            return;
+        };
+        let mut changed: Vec<&'static str> = Vec::new();
+
+        ui.label("Crop:");
+        egui::Grid::new("style_crop").num_columns(4).show(ui, |ui| {
+            ui.label("T:");
+            if ui
+                .add(egui::DragValue::new(&mut style.crop_top).suffix("px"))
+                .changed()
+            {
+                changed.push("crop-top");
+            }
+            ui.label("B:");
+            if ui
+                .add(egui::DragValue::new(&mut style.crop_bottom).suffix("px"))
+                .changed()
+            {
+                changed.push("crop-bottom");
+            }
+            ui.end_row();
+            ui.label("L:");
+            if ui
+                .add(egui::DragValue::new(&mut style.crop_left).suffix("px"))
+                .changed()
+            {
+                changed.push("crop-left");
+            }
+            ui.label("R:");
+            if ui
+                .add(egui::DragValue::new(&mut style.crop_right).suffix("px"))
+                .changed()
+            {
+                changed.push("crop-right");
+            }
+            ui.end_row();
         });
+
+        ui.horizontal(|ui| {
+            ui.label("Rotation:");
+            egui::ComboBox::from_id_salt("style_rotation")
+                .selected_text(format!("{}°", style.rotation))
+                .show_ui(ui, |ui| {
+                    for degrees in [0, 90, 180, 270] {
+                        if ui
+                            .selectable_value(&mut style.rotation, degrees, format!("{}°", degrees))
+                            .clicked()
+                        {
+                            changed.push("rotation");
+                        }
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Border:");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut style.border_width)
+                        .range(0.0..=100.0)
+                        .suffix("px"),
+                )
+                .changed()
+            {
+                changed.push("border-width");
+            }
+            if ui
+                .color_edit_button_srgba(&mut style.border_color)
+                .changed()
+            {
+                // The border is always opaque
+                let c = style.border_color;
+                style.border_color = Color32::from_rgb(c.r(), c.g(), c.b());
+                changed.push("border-color");
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Corners:");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut style.corner_radius)
+                        .range(0.0..=500.0)
+                        .suffix("px"),
+                )
+                .changed()
+            {
+                changed.push("corner-radius");
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Shadow:");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut style.shadow_size)
+                        .range(0.0..=200.0)
+                        .suffix("px"),
+                )
+                .changed()
+            {
+                changed.push("shadow-size");
+            }
+            if ui
+                .add(egui::Slider::new(&mut style.shadow_opacity, 0.0..=1.0).text("opacity"))
+                .changed()
+            {
+                changed.push("shadow-opacity");
+            }
+        });
+
+        if changed.is_empty() {
+            return;
+        }
+        let properties = style.properties();
+        self.inputs[selected_idx].style = Some(style);
+        for (name, value) in properties {
+            if changed.contains(&name) {
+                self.update_pad_property(ui.ctx(), selected_idx, name, value);
+            }
+        }
     }
 }
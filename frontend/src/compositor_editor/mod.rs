@@ -5,6 +5,9 @@
 //! - Resize handles for changing input dimensions
 //! - Real-time updates via API to running pipeline
 //! - Property panel for fine-tuning alpha, zorder, sizing-policy
+//! - Crop, rotation, border, shadow and corner styling when the block has
+//!   `enable_styling` set
+//! - Chroma and luma keys when the block has `enable_keying` set
 
 mod api_sync;
 mod canvas;
@@ -71,6 +74,10 @@ struct InputBox {
     alpha: f64,
     zorder: u32,
     sizing_policy: String,
+    /// Crop, rotation and look; `None` when the block has no style stages
+    style: Option<InputStyle>,
+    /// Chroma or luma key; `None` when the block has no key stages
+    key: Option<InputKey>,
 
     // UI state
     /// Whether this input is currently selected
@@ -94,6 +101,8 @@ impl InputBox {
             alpha: 1.0,
             zorder: input_index as u32,
             sizing_policy: "keep-aspect-ratio".to_string(),
+            style: None,
+            key: None,
             selected: false,
             pending_update: false,
             last_error: None,
@@ -116,6 +125,170 @@ impl InputBox {
     }
 }
 
+/// Crop, rotation and look of an input, mirroring the style pad properties
+/// of a compositor built with `enable_styling`.
+#[derive(Debug, Clone, PartialEq)]
+struct InputStyle {
+    crop_top: u32,
+    crop_bottom: u32,
+    crop_left: u32,
+    crop_right: u32,
+    /// Clockwise rotation in degrees (0, 90, 180 or 270)
+    rotation: u32,
+    border_width: f32,
+    border_color: Color32,
+    corner_radius: f32,
+    shadow_size: f32,
+    shadow_opacity: f32,
+}
+
+impl Default for InputStyle {
+    fn default() -> Self {
+        Self {
+            crop_top: 0,
+            crop_bottom: 0,
+            crop_left: 0,
+            crop_right: 0,
+            rotation: 0,
+            border_width: 0.0,
+            border_color: Color32::WHITE,
+            corner_radius: 0.0,
+            shadow_size: 0.0,
+            shadow_opacity: 0.5,
+        }
+    }
+}
+
+impl InputStyle {
+    /// Read the style from mixer pad properties. Returns `None` if the pad
+    /// reports no style (styling not enabled on the block).
+    fn from_properties(props: &std::collections::HashMap<String, PropertyValue>) -> Option<Self> {
+        let number = |name: &str| match props.get(name) {
+            Some(PropertyValue::Float(v)) => Some(*v),
+            Some(PropertyValue::Int(v)) => Some(*v as f64),
+            Some(PropertyValue::UInt(v)) => Some(*v as f64),
+            Some(PropertyValue::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+        let rotation = number("rotation")? as u32;
+        let default = Self::default();
+        let border_color = match props.get("border-color") {
+            Some(PropertyValue::String(hex)) => Color32::from_hex(hex).ok(),
+            _ => None,
+        };
+        Some(Self {
+            crop_top: number("crop-top").unwrap_or(0.0) as u32,
+            crop_bottom: number("crop-bottom").unwrap_or(0.0) as u32,
+            crop_left: number("crop-left").unwrap_or(0.0) as u32,
+            crop_right: number("crop-right").unwrap_or(0.0) as u32,
+            rotation,
+            border_width: number("border-width").unwrap_or(0.0) as f32,
+            border_color: border_color.unwrap_or(default.border_color),
+            corner_radius: number("corner-radius").unwrap_or(0.0) as f32,
+            shadow_size: number("shadow-size").unwrap_or(0.0) as f32,
+            shadow_opacity: number("shadow-opacity").unwrap_or(default.shadow_opacity as f64)
+                as f32,
+        })
+    }
+
+    /// All style pad properties with their current values.
+    fn properties(&self) -> Vec<(&'static str, PropertyValue)> {
+        vec![
+            ("crop-top", PropertyValue::UInt(self.crop_top as u64)),
+            ("crop-bottom", PropertyValue::UInt(self.crop_bottom as u64)),
+            ("crop-left", PropertyValue::UInt(self.crop_left as u64)),
+            ("crop-right", PropertyValue::UInt(self.crop_right as u64)),
+            ("rotation", PropertyValue::String(self.rotation.to_string())),
+            (
+                "border-width",
+                PropertyValue::Float(self.border_width as f64),
+            ),
+            (
+                "border-color",
+                PropertyValue::String(border_color_hex(self.border_color)),
+            ),
+            (
+                "corner-radius",
+                PropertyValue::Float(self.corner_radius as f64),
+            ),
+            ("shadow-size", PropertyValue::Float(self.shadow_size as f64)),
+            (
+                "shadow-opacity",
+                PropertyValue::Float(self.shadow_opacity as f64),
+            ),
+        ]
+    }
+}
+
+/// Key of an input, mirroring the key pad properties of a compositor built
+/// with `enable_keying`.
+#[derive(Debug, Clone, PartialEq)]
+struct InputKey {
+    /// "none", "chroma" or "luma"
+    mode: String,
+    color: Color32,
+    tolerance: f32,
+    softness: f32,
+    spill: f32,
+    clip: f32,
+    gain: f32,
+    invert: bool,
+}
+
+impl InputKey {
+    /// Read the key from mixer pad properties. Returns `None` if the pad
+    /// reports no key (keying not enabled on the block).
+    fn from_properties(props: &std::collections::HashMap<String, PropertyValue>) -> Option<Self> {
+        let number = |name: &str| match props.get(name) {
+            Some(PropertyValue::Float(v)) => Some(*v as f32),
+            Some(PropertyValue::Int(v)) => Some(*v as f32),
+            Some(PropertyValue::UInt(v)) => Some(*v as f32),
+            Some(PropertyValue::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+        let mode = match props.get("key-mode") {
+            Some(PropertyValue::String(mode)) => mode.clone(),
+            _ => return None,
+        };
+        let color = match props.get("key-color") {
+            Some(PropertyValue::String(hex)) => Color32::from_hex(hex).ok(),
+            _ => None,
+        };
+        Some(Self {
+            mode,
+            color: color.unwrap_or(Color32::GREEN),
+            tolerance: number("key-tolerance").unwrap_or(0.15),
+            softness: number("key-softness").unwrap_or(0.1),
+            spill: number("key-spill").unwrap_or(0.5),
+            clip: number("key-clip").unwrap_or(0.5),
+            gain: number("key-gain").unwrap_or(4.0),
+            invert: matches!(props.get("key-invert"), Some(PropertyValue::Bool(true))),
+        })
+    }
+
+    /// All key pad properties with their current values.
+    fn properties(&self) -> Vec<(&'static str, PropertyValue)> {
+        vec![
+            ("key-mode", PropertyValue::String(self.mode.clone())),
+            (
+                "key-color",
+                PropertyValue::String(border_color_hex(self.color)),
+            ),
+            ("key-tolerance", PropertyValue::Float(self.tolerance as f64)),
+            ("key-softness", PropertyValue::Float(self.softness as f64)),
+            ("key-spill", PropertyValue::Float(self.spill as f64)),
+            ("key-clip", PropertyValue::Float(self.clip as f64)),
+            ("key-gain", PropertyValue::Float(self.gain as f64)),
+            ("key-invert", PropertyValue::Bool(self.invert)),
+        ]
+    }
+}
+
+/// Format a colour as `#RRGGBB`.
+fn border_color_hex(color: Color32) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
+
 /// Convert HSV to RGB (0-255).
 fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
     let c = v * s;
@@ -204,6 +377,12 @@ pub struct CompositorEditor {
     /// Last transition status message
     transition_status: Option<String>,
 
+    // Layout presets
+    /// Names of the layout presets stored with the block
+    layout_presets: Vec<String>,
+    /// Name for saving the current layout as a preset
+    layout_preset_name: String,
+
     // Thumbnail state
     /// Cached thumbnail textures by input index
     thumbnails: std::collections::HashMap<usize, egui::TextureHandle>,
@@ -263,6 +442,9 @@ impl CompositorEditor {
             transition_type: "dip_to_black".to_string(),
             transition_duration_ms: 300,
             transition_status: None,
+            // Layout presets
+            layout_presets: Vec::new(),
+            layout_preset_name: String::new(),
             // Thumbnail state
             thumbnails: std::collections::HashMap::new(),
             thumbnail_fetch_times: std::collections::HashMap::new(),
@@ -8,6 +8,7 @@ impl CompositorEditor {
         self.check_loaded_properties();
         self.check_update_results();
         self.check_transition_status();
+        self.check_layout_presets();
         self.check_loaded_thumbnails(ctx);
         self.refresh_thumbnails(ctx);
 
@@ -192,6 +193,54 @@ impl CompositorEditor {
 
             ui.separator();
 
+            // Layout presets stored with the block, recalled with an animated move
+            let mut recall = None;
+            let mut delete = None;
+            egui::ComboBox::from_id_salt("layout_presets_fullscreen")
+                .selected_text("Presets")
+                .show_ui(ui, |ui| {
+                    if self.layout_presets.is_empty() {
+                        ui.weak("No presets saved");
+                    }
+                    for name in &self.layout_presets {
+                        ui.horizontal(|ui| {
+                            if ui.selectable_label(false, name).clicked() {
+                                recall = Some(name.clone());
+                            }
+                            if ui
+                                .small_button(egui_phosphor::regular::TRASH)
+                                .on_hover_text("Delete preset")
+                                .clicked()
+                            {
+                                delete = Some(name.clone());
+                            }
+                        });
+                    }
+                });
+            if let Some(name) = recall {
+                self.recall_layout_preset(ctx, &name);
+            }
+            if let Some(name) = delete {
+                self.delete_layout_preset(ctx, &name);
+            }
+            ui.add(
+                egui::TextEdit::singleline(&mut self.layout_preset_name)
+                    .hint_text("Preset name")
+                    .desired_width(90.0),
+            );
+            if ui
+                .add_enabled(
+                    !self.layout_preset_name.trim().is_empty(),
+                    egui::Button::new(egui_phosphor::regular::FLOPPY_DISK),
+                )
+                .on_hover_text("Save the current layout as a preset")
+                .clicked()
+            {
+                self.save_layout_preset(ctx);
+            }
+
+            ui.separator();
+
             // Input selection buttons
             for idx in 0..self.inputs.len() {
                 let is_selected = self.selected_input == Some(idx);
@@ -272,6 +321,12 @@ impl CompositorEditor {
                 ("push_right", "Push Right"),
                 ("push_up", "Push Up"),
                 ("push_down", "Push Down"),
+                ("wipe_bar", "Wipe Bar"),
+                ("wipe_bar_v", "Wipe Bar (V)"),
+                ("wipe_box", "Wipe Box"),
+                ("wipe_diamond", "Wipe Diamond"),
+                ("wipe_iris", "Wipe Iris"),
+                ("wipe_clock", "Wipe Clock"),
             ];
             let selected_label: String = TRANSITION_TYPES
                 .iter()
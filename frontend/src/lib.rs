@@ -7,6 +7,7 @@
 // #![allow(dead_code)]
 #![deny(clippy::disallowed_types)]
 
+mod animation_timeline;
 mod api;
 mod app;
 mod audioanalyzer;
@@ -6,6 +6,7 @@
 // Do NOT blanket allow dead_code — use targeted #[allow(dead_code)] or #[cfg] gates instead
 // #![allow(dead_code)]
 
+mod animation_timeline;
 mod api;
 mod app;
 mod audioanalyzer;
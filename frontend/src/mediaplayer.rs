@@ -4,7 +4,7 @@ use egui::{Color32, CornerRadius, Rect, Stroke, Ui, Vec2};
 use instant::Instant;
 use std::collections::HashMap;
 use std::time::Duration;
-use strom_types::mediaplayer::PlayerState;
+use strom_types::mediaplayer::{EndAction, PlayerState, PlaylistItem};
 use strom_types::FlowId;
 
 /// Time-to-live for media player data before it's considered stale.
@@ -81,6 +81,8 @@ pub struct MediaPlayerData {
     pub total_files: usize,
     /// Current file path (if any)
     pub current_file: Option<String>,
+    /// Whether the current item is prerolled and waiting for play
+    pub cued: bool,
 }
 
 impl Default for MediaPlayerData {
@@ -92,6 +94,7 @@ impl Default for MediaPlayerData {
             current_file_index: 0,
             total_files: 0,
             current_file: None,
+            cued: false,
         }
     }
 }
@@ -156,6 +159,7 @@ impl MediaPlayerDataStore {
                         current_file_index,
                         total_files,
                         current_file: None,
+                        cued: false,
                     },
                     updated_at: Instant::now(),
                 },
@@ -170,6 +174,7 @@ impl MediaPlayerDataStore {
         block_id: String,
         state: PlayerState,
         current_file: Option<String>,
+        cued: bool,
     ) {
         let key = MediaPlayerKey {
             flow_id,
@@ -179,6 +184,7 @@ impl MediaPlayerDataStore {
         if let Some(entry) = self.data.get_mut(&key) {
             entry.data.state = state;
             entry.data.current_file = current_file;
+            entry.data.cued = cued;
             entry.updated_at = Instant::now();
         } else {
             // Create new entry if none exists
@@ -192,6 +198,7 @@ impl MediaPlayerDataStore {
                         current_file_index: 0,
                         total_files: 0,
                         current_file,
+                        cued,
                     },
                     updated_at: Instant::now(),
                 },
@@ -216,6 +223,18 @@ impl MediaPlayerDataStore {
     }
 }
 
+/// Parse the `playlist` block property: items, or plain URIs as saved by
+/// earlier versions.
+pub fn parse_playlist(json: &str) -> Option<Vec<PlaylistItem>> {
+    serde_json::from_str::<Vec<PlaylistItem>>(json)
+        .ok()
+        .or_else(|| {
+            serde_json::from_str::<Vec<String>>(json)
+                .ok()
+                .map(|files| files.into_iter().map(PlaylistItem::from).collect())
+        })
+}
+
 /// Format nanoseconds as MM:SS.
 fn format_time(ns: u64) -> String {
     let total_seconds = ns / 1_000_000_000;
@@ -249,8 +268,10 @@ pub fn show_compact(ui: &mut Ui, player_data: &MediaPlayerData) -> Option<(Strin
 
     // Show playback state and file count
     ui.label(format!(
-        "State: {} | Files: {}",
-        player_data.state, player_data.total_files
+        "State: {}{} | Files: {}",
+        player_data.state,
+        if player_data.cued { " (cued)" } else { "" },
+        player_data.total_files
     ));
 
     // Control buttons row: Playlist | Prev | Play/Pause | Next | file count
@@ -582,8 +603,10 @@ pub struct PlaylistEditor {
     pub block_id: String,
     /// Whether the editor window is open
     pub open: bool,
-    /// Current playlist being edited (file URIs)
-    pub playlist: Vec<String>,
+    /// Current playlist being edited
+    pub playlist: Vec<PlaylistItem>,
+    /// Index of the item whose playout settings are shown
+    pub settings_index: Option<usize>,
     /// Whether we need to save changes
     pub dirty: bool,
     /// Current browser path (relative to media folder)
@@ -609,6 +632,7 @@ impl PlaylistEditor {
             block_id,
             open: true,
             playlist: Vec::new(),
+            settings_index: None,
             dirty: false,
             browser_path: String::new(),
             browser_parent: None,
@@ -621,7 +645,7 @@ impl PlaylistEditor {
     }
 
     /// Set the playlist from the current player data.
-    pub fn set_playlist(&mut self, playlist: Vec<String>) {
+    pub fn set_playlist(&mut self, playlist: Vec<PlaylistItem>) {
         self.playlist = playlist;
         self.dirty = false;
     }
@@ -653,7 +677,7 @@ impl PlaylistEditor {
 
     /// Show the playlist editor window.
     /// Returns Some(playlist) if the user clicked Save.
-    pub fn show(&mut self, ctx: &egui::Context) -> Option<Vec<String>> {
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Vec<PlaylistItem>> {
         let mut result = None;
         let mut is_open = self.open;
 
@@ -824,14 +848,14 @@ impl PlaylistEditor {
 
                     // Handle file add (duplicates allowed — same file can appear multiple times)
                     if let Some(file_path) = add_file {
-                        self.playlist.push(file_path);
+                        self.playlist.push(PlaylistItem::new(file_path));
                         self.dirty = true;
                     }
                 });
         }
     }
 
-    fn show_playlist_panel(&mut self, ui: &mut Ui, result: &mut Option<Vec<String>>) {
+    fn show_playlist_panel(&mut self, ui: &mut Ui, result: &mut Option<Vec<PlaylistItem>>) {
         // Action buttons BEFORE scroll area so they don't overflow the cell
         ui.horizontal(|ui| {
             if ui
@@ -850,6 +874,7 @@ impl PlaylistEditor {
                 .clicked()
             {
                 self.playlist.clear();
+                self.settings_index = None;
                 self.dirty = true;
             }
 
@@ -871,8 +896,11 @@ impl PlaylistEditor {
                 let mut to_remove = None;
                 let mut to_move_up = None;
                 let mut to_move_down = None;
+                let mut toggle_settings = None;
+                let playlist_len = self.playlist.len();
 
-                for (i, file) in self.playlist.iter().enumerate() {
+                for (i, item) in self.playlist.iter_mut().enumerate() {
+                    let file = &item.uri;
                     let is_playing = self.current_playing_index == Some(i);
                     ui.horizontal(|ui| {
                         // Fixed-width left: playing indicator + index number
@@ -892,7 +920,7 @@ impl PlaylistEditor {
                                 to_remove = Some(i);
                             }
 
-                            let can_move_down = i < self.playlist.len() - 1;
+                            let can_move_down = i < playlist_len - 1;
                             if ui
                                 .add_enabled(
                                     can_move_down,
@@ -916,6 +944,21 @@ impl PlaylistEditor {
                                 to_move_up = Some(i);
                             }
 
+                            let customized = item.segment().is_some()
+                                || item.loop_count > 0
+                                || item.end_action != EndAction::Next
+                                || item.cue_on_load;
+                            if ui
+                                .add(
+                                    egui::Button::new(egui_phosphor::regular::SLIDERS_HORIZONTAL)
+                                        .selected(customized),
+                                )
+                                .on_hover_text("In/out points, loops, end action and cueing")
+                                .clicked()
+                            {
+                                toggle_settings = Some(i);
+                            }
+
                             // Filename fills the remaining space with truncation
                             let display_name = std::path::Path::new(file)
                                 .file_name()
@@ -934,25 +977,119 @@ impl PlaylistEditor {
                             .on_hover_text(file);
                         });
                     });
+
+                    if self.settings_index == Some(i) && show_item_settings(ui, item, i) {
+                        self.dirty = true;
+                    }
                 }
 
                 // Apply moves/removes
+                if let Some(i) = toggle_settings {
+                    self.settings_index = if self.settings_index == Some(i) {
+                        None
+                    } else {
+                        Some(i)
+                    };
+                }
                 if let Some(i) = to_remove {
                     self.playlist.remove(i);
+                    self.settings_index = None;
                     self.dirty = true;
                 }
                 if let Some(i) = to_move_up {
                     self.playlist.swap(i, i - 1);
+                    self.settings_index = None;
                     self.dirty = true;
                 }
                 if let Some(i) = to_move_down {
                     self.playlist.swap(i, i + 1);
+                    self.settings_index = None;
                     self.dirty = true;
                 }
             });
     }
 }
 
+/// Edit the playout settings of a playlist item. Returns true if changed.
+fn show_item_settings(ui: &mut Ui, item: &mut PlaylistItem, index: usize) -> bool {
+    let mut changed = false;
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        egui::Grid::new(("playlist_item_settings", index))
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("In point");
+                changed |= optional_seconds(ui, &mut item.in_point_ns, "file start");
+                ui.end_row();
+
+                ui.label("Out point");
+                changed |= optional_seconds(ui, &mut item.out_point_ns, "file end");
+                ui.end_row();
+
+                ui.label("Repeats");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut item.loop_count).range(0..=999))
+                    .on_hover_text("Times to play the item again before the end action")
+                    .changed();
+                ui.end_row();
+
+                ui.label("At end");
+                egui::ComboBox::from_id_salt(("playlist_item_end", index))
+                    .selected_text(match item.end_action {
+                        EndAction::Next => "Next item",
+                        EndAction::Hold => "Hold last frame",
+                    })
+                    .show_ui(ui, |ui| {
+                        changed |= ui
+                            .selectable_value(&mut item.end_action, EndAction::Next, "Next item")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(
+                                &mut item.end_action,
+                                EndAction::Hold,
+                                "Hold last frame",
+                            )
+                            .changed();
+                    });
+                ui.end_row();
+
+                ui.label("");
+                changed |= ui
+                    .checkbox(&mut item.cue_on_load, "Cue on load")
+                    .on_hover_text("Preroll paused on the first frame and wait for play")
+                    .changed();
+                ui.end_row();
+            });
+    });
+    changed
+}
+
+/// Optional position in seconds, stored in nanoseconds. Returns true if changed.
+fn optional_seconds(ui: &mut Ui, value_ns: &mut Option<u64>, unset: &str) -> bool {
+    ui.horizontal(|ui| {
+        let mut enabled = value_ns.is_some();
+        let mut changed = ui.checkbox(&mut enabled, "").changed();
+        if !enabled {
+            *value_ns = None;
+            ui.weak(unset);
+            return changed;
+        }
+        let mut secs = value_ns.unwrap_or(0) as f64 / 1_000_000_000.0;
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut secs)
+                    .range(0.0..=86_400.0)
+                    .speed(0.1)
+                    .max_decimals(3)
+                    .suffix(" s"),
+            )
+            .changed();
+        *value_ns = Some((secs * 1_000_000_000.0).round() as u64);
+        changed
+    })
+    .inner
+}
+
 /// Format file size for display.
 fn format_file_size(bytes: u64) -> String {
     const KB: u64 = 1024;
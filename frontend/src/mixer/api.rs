@@ -8,7 +8,7 @@ impl MixerEditor {
         self.last_update = instant::Instant::now() - std::time::Duration::from_millis(100);
     }
 
-    /// Update a processing parameter (gate/comp).
+    /// Update a processing parameter (gate/duck/comp).
     pub(super) fn update_processing_param(
         &mut self,
         ctx: &Context,
@@ -64,6 +64,38 @@ impl MixerEditor {
             // Note: LSP gate does not have a settable range property
             // ("rr" doesn't exist, "gr" is read-only reduction meter)
             ("gate", "range") => return,
+            // The duck_N element only exists when the channel has a key
+            ("duck", _) if channel.duck_key == "none" => return,
+            ("duck", "enabled") => (
+                format!("duck_{}", index),
+                "duck-enabled".to_string(),
+                PropertyValue::Bool(channel.duck_enabled),
+            ),
+            ("duck", "threshold") => (
+                format!("duck_{}", index),
+                "duck-threshold".to_string(),
+                PropertyValue::Float(channel.duck_threshold as f64),
+            ),
+            ("duck", "depth") => (
+                format!("duck_{}", index),
+                "duck-depth".to_string(),
+                PropertyValue::Float(channel.duck_depth as f64),
+            ),
+            ("duck", "attack") => (
+                format!("duck_{}", index),
+                "duck-attack".to_string(),
+                PropertyValue::Float(channel.duck_attack as f64),
+            ),
+            ("duck", "hold") => (
+                format!("duck_{}", index),
+                "duck-hold".to_string(),
+                PropertyValue::Float(channel.duck_hold as f64),
+            ),
+            ("duck", "release") => (
+                format!("duck_{}", index),
+                "duck-release".to_string(),
+                PropertyValue::Float(channel.duck_release as f64),
+            ),
             ("comp", "threshold") => (
                 format!("comp_{}", index),
                 "al".to_string(),
@@ -663,6 +695,42 @@ impl MixerEditor {
         });
     }
 
+    /// Update mix-minus bus fader via API.
+    pub(super) fn update_mix_minus_fader(&mut self, ctx: &Context, mm_idx: usize) {
+        if !self.live_updates || !self.pipeline_running {
+            return;
+        }
+
+        // Throttle updates
+        if self.last_update.elapsed().as_millis() < 50 {
+            return;
+        }
+        self.last_update = instant::Instant::now();
+
+        let mute = self.mix_minus[mm_idx].mute;
+        let effective_volume = if mute {
+            0.0
+        } else {
+            self.mix_minus[mm_idx].fader as f64
+        };
+
+        let api = self.api.clone();
+        let flow_id = self.flow_id;
+        let element_id = format!("{}:mm{}_volume", self.block_id, mm_idx);
+        let value = PropertyValue::Float(effective_volume);
+        let ctx = ctx.clone();
+
+        crate::app::spawn_task(async move {
+            if let Err(e) = api
+                .update_element_property(&flow_id, &element_id, "volume", value)
+                .await
+            {
+                tracing::warn!("Mixer API update failed: {}", e);
+            }
+            ctx.request_repaint();
+        });
+    }
+
     /// Update aux master mute via API.
     pub(super) fn update_aux_master_mute(&mut self, ctx: &Context, aux_idx: usize) {
         if !self.live_updates || !self.pipeline_running {
@@ -692,4 +760,34 @@ impl MixerEditor {
             ctx.request_repaint();
         });
     }
+
+    /// Update mix-minus bus mute via API.
+    pub(super) fn update_mix_minus_mute(&mut self, ctx: &Context, mm_idx: usize) {
+        if !self.live_updates || !self.pipeline_running {
+            return;
+        }
+
+        let mute = self.mix_minus[mm_idx].mute;
+        let effective_volume = if mute {
+            0.0
+        } else {
+            self.mix_minus[mm_idx].fader as f64
+        };
+
+        let api = self.api.clone();
+        let flow_id = self.flow_id;
+        let element_id = format!("{}:mm{}_volume", self.block_id, mm_idx);
+        let value = PropertyValue::Float(effective_volume);
+        let ctx = ctx.clone();
+
+        crate::app::spawn_task(async move {
+            if let Err(e) = api
+                .update_element_property(&flow_id, &element_id, "volume", value)
+                .await
+            {
+                tracing::warn!("Mixer API update failed: {}", e);
+            }
+            ctx.request_repaint();
+        });
+    }
 }
@@ -63,7 +63,7 @@ impl MixerEditor {
         }
     }
 
-    /// Render the detail panel for a selected channel (HPF/Gate/Comp/EQ).
+    /// Render the detail panel for a selected channel (HPF/Gate/Duck/Comp/EQ).
     pub(super) fn render_channel_detail_panel(&mut self, ui: &mut Ui, ctx: &Context, index: usize) {
         let ch_num = index + 1;
         let label = &self.channels[index].label;
@@ -87,18 +87,27 @@ impl MixerEditor {
                         {
                             self.selection = None;
                         }
+                        if ui
+                            .selectable_label(self.channels[index].recall_safe, "Recall safe")
+                            .on_hover_text("Leave this channel untouched when recalling a snapshot")
+                            .clicked()
+                        {
+                            self.channels[index].recall_safe = !self.channels[index].recall_safe;
+                            self.save_requested = true;
+                        }
                     });
                 });
 
                 ui.add_space(8.0);
 
                 egui::Grid::new(format!("ch_processing_{}", index))
-                    .num_columns(5)
+                    .num_columns(6)
                     .spacing([8.0, 0.0])
                     .show(ui, |ui| {
                         self.render_gain_section(ui, ctx, index);
                         self.render_hpf_section(ui, ctx, index);
                         self.render_gate_section(ui, ctx, index);
+                        self.render_duck_section(ui, ctx, index);
                         self.render_comp_section(ui, ctx, index);
                         self.render_eq_section(ui, ctx, index);
                         ui.end_row();
@@ -605,6 +614,106 @@ impl MixerEditor {
         });
     }
 
+    pub(super) fn render_duck_section(&mut self, ui: &mut Ui, ctx: &Context, index: usize) {
+        let color = Color32::from_rgb(200, 120, 40);
+        let enabled = self.channels[index].duck_enabled;
+
+        section_frame(color, enabled).show(ui, |ui| {
+            ui.vertical(|ui| {
+                ui.set_min_height(SECTION_MIN_HEIGHT);
+                if section_toggle(ui, "Duck", color, enabled) {
+                    self.channels[index].duck_enabled = !self.channels[index].duck_enabled;
+                    self.bypass_throttle();
+                    self.update_processing_param(ctx, index, "duck", "enabled");
+                }
+
+                ui.add_space(4.0);
+
+                // The key source is wired when the pipeline is built
+                let mut keys = vec!["none".to_string()];
+                keys.extend(
+                    (1..=self.num_channels)
+                        .filter(|&k| k != index + 1)
+                        .map(|k| format!("ch{}", k)),
+                );
+                keys.extend((1..=self.num_groups).map(|g| format!("group{}", g)));
+                ui.horizontal(|ui| {
+                    ui.label("Key:");
+                    egui::ComboBox::from_id_salt(format!("duck_key_{}", index))
+                        .width(70.0)
+                        .selected_text(self.channels[index].duck_key.clone())
+                        .show_ui(ui, |ui| {
+                            for key in keys {
+                                let selected = self.channels[index].duck_key == key;
+                                if ui.selectable_label(selected, &key).clicked() && !selected {
+                                    self.channels[index].duck_key = key;
+                                    self.status =
+                                        "Duck key changed, applies on next start".to_string();
+                                }
+                            }
+                        });
+                });
+
+                if !enabled {
+                    ui.disable();
+                }
+
+                let params: [(&str, &str, f32, f32, &str, f64, usize); 5] = [
+                    ("Threshold:", "threshold", -60.0, 0.0, " dB", 0.5, 1),
+                    ("Depth:", "depth", 0.0, 60.0, " dB", 0.5, 1),
+                    ("Attack:", "attack", 0.0, 500.0, " ms", 1.0, 0),
+                    ("Hold:", "hold", 0.0, 5000.0, " ms", 10.0, 0),
+                    ("Release:", "release", 0.0, 5000.0, " ms", 10.0, 0),
+                ];
+                for (label, param, min, max, suffix, speed, decimals) in params {
+                    let channel = &mut self.channels[index];
+                    let value = match param {
+                        "threshold" => &mut channel.duck_threshold,
+                        "depth" => &mut channel.duck_depth,
+                        "attack" => &mut channel.duck_attack,
+                        "hold" => &mut channel.duck_hold,
+                        _ => &mut channel.duck_release,
+                    };
+                    let changed = ui
+                        .horizontal(|ui| {
+                            ui.label(label);
+                            ui.add(
+                                egui::DragValue::new(value)
+                                    .range(min..=max)
+                                    .suffix(suffix)
+                                    .speed(speed)
+                                    .fixed_decimals(decimals),
+                            )
+                            .changed()
+                        })
+                        .inner;
+                    if changed {
+                        self.update_processing_param(ctx, index, "duck", param);
+                    }
+                }
+
+                ui.add_space(4.0);
+                if ui
+                    .small_button(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE)
+                    .on_hover_text("Reset")
+                    .clicked()
+                {
+                    let channel = &mut self.channels[index];
+                    channel.duck_enabled = false;
+                    channel.duck_threshold = DEFAULT_DUCK_THRESHOLD;
+                    channel.duck_depth = DEFAULT_DUCK_DEPTH;
+                    channel.duck_attack = DEFAULT_DUCK_ATTACK;
+                    channel.duck_hold = DEFAULT_DUCK_HOLD;
+                    channel.duck_release = DEFAULT_DUCK_RELEASE;
+                    for param in ["enabled", "threshold", "depth", "attack", "hold", "release"] {
+                        self.bypass_throttle();
+                        self.update_processing_param(ctx, index, "duck", param);
+                    }
+                }
+            });
+        });
+    }
+
     pub(super) fn render_comp_section(&mut self, ui: &mut Ui, ctx: &Context, index: usize) {
         let color = Color32::from_rgb(180, 100, 0);
         let enabled = self.channels[index].comp_enabled;
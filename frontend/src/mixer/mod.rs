@@ -5,7 +5,7 @@
 //! - Real-time metering
 //! - Keyboard shortcuts for quick mixing
 //!
-//! Per-channel gate, compressor, 4-band EQ, aux sends, groups, PFL
+//! Per-channel gate, compressor, 4-band EQ, aux sends, groups, mix-minus, PFL
 
 mod api;
 mod detail;
@@ -90,6 +90,20 @@ struct ChannelStrip {
     gate_attack: f32,
     /// Gate release (ms)
     gate_release: f32,
+    /// Ducking key source: "none", "chK" or "groupG" (structural)
+    duck_key: String,
+    /// Ducking enabled
+    duck_enabled: bool,
+    /// Ducking threshold on the key (dB)
+    duck_threshold: f32,
+    /// Ducking depth (dB of attenuation)
+    duck_depth: f32,
+    /// Ducking attack (ms)
+    duck_attack: f32,
+    /// Ducking hold (ms)
+    duck_hold: f32,
+    /// Ducking release (ms)
+    duck_release: f32,
     /// Compressor enabled
     comp_enabled: bool,
     /// Compressor threshold (dB)
@@ -108,6 +122,8 @@ struct ChannelStrip {
     eq_enabled: bool,
     /// EQ bands: (freq, gain_db, q) for 4 bands
     eq_bands: [(f32, f32, f32); 4],
+    /// Skip this channel when a snapshot is recalled
+    recall_safe: bool,
 }
 
 /// Group strip state.
@@ -152,6 +168,13 @@ impl ChannelStrip {
             gate_threshold: DEFAULT_GATE_THRESHOLD,
             gate_attack: DEFAULT_GATE_ATTACK,
             gate_release: DEFAULT_GATE_RELEASE,
+            duck_key: "none".to_string(),
+            duck_enabled: false,
+            duck_threshold: DEFAULT_DUCK_THRESHOLD,
+            duck_depth: DEFAULT_DUCK_DEPTH,
+            duck_attack: DEFAULT_DUCK_ATTACK,
+            duck_hold: DEFAULT_DUCK_HOLD,
+            duck_release: DEFAULT_DUCK_RELEASE,
             comp_enabled: false,
             comp_threshold: DEFAULT_COMP_THRESHOLD,
             comp_ratio: DEFAULT_COMP_RATIO,
@@ -161,6 +184,7 @@ impl ChannelStrip {
             comp_knee: DEFAULT_COMP_KNEE,
             eq_enabled: false,
             eq_bands: DEFAULT_EQ_BANDS,
+            recall_safe: false,
         }
     }
 }
@@ -185,6 +209,30 @@ impl AuxMaster {
     }
 }
 
+/// Mix-minus bus master state.
+#[derive(Debug, Clone)]
+struct MixMinusBus {
+    /// Bus index (0-based)
+    index: usize,
+    /// Master fader level
+    fader: f32,
+    /// Mute state
+    mute: bool,
+    /// Excluded channels as stored in `mmN_exclude` (structural)
+    exclude: String,
+}
+
+impl MixMinusBus {
+    fn new(index: usize) -> Self {
+        Self {
+            index,
+            fader: DEFAULT_FADER,
+            mute: false,
+            exclude: (index + 1).to_string(),
+        }
+    }
+}
+
 /// What control is currently being adjusted (for value display).
 #[derive(Debug, Clone, PartialEq)]
 enum ActiveControl {
@@ -194,6 +242,7 @@ enum ActiveControl {
     AuxSend(usize, usize), // (Channel index, Aux index)
     GroupFader(usize),     // Group index
     AuxMasterFader(usize), // Aux master index
+    MixMinusFader(usize),  // Mix-minus bus index
     MainFader,
 }
 
@@ -217,6 +266,8 @@ pub struct MixerEditor {
     num_aux_buses: usize,
     /// Number of groups
     num_groups: usize,
+    /// Number of mix-minus buses
+    num_mix_minus: usize,
 
     /// Channel strips
     channels: Vec<ChannelStrip>,
@@ -224,6 +275,8 @@ pub struct MixerEditor {
     groups: Vec<GroupStrip>,
     /// Aux masters
     aux_masters: Vec<AuxMaster>,
+    /// Mix-minus bus masters
+    mix_minus: Vec<MixMinusBus>,
 
     /// Currently selected strip (channel or bus)
     selection: Option<Selection>,
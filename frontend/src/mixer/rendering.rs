@@ -47,7 +47,12 @@ impl MixerEditor {
                                                 format!("{}:meter:{}", self.block_id, i + 1);
                                             let meter_data =
                                                 meter_store.get(&self.flow_id, &meter_key);
-                                            self.render_channel_strip(ui, ctx, i, meter_data);
+                                            let gr_key =
+                                                format!("{}:meter:gr{}", self.block_id, i + 1);
+                                            let gr_data = meter_store.get(&self.flow_id, &gr_key);
+                                            self.render_channel_strip(
+                                                ui, ctx, i, meter_data, gr_data,
+                                            );
                                             ui.add_space(STRIP_GAP);
                                         }
                                     });
@@ -81,6 +86,13 @@ impl MixerEditor {
                                             ui.add_space(8.0);
                                         }
 
+                                        if self.num_mix_minus > 0 {
+                                            self.render_mix_minus_strips(ui, ctx, meter_store);
+                                            ui.add_space(8.0);
+                                            ui.separator();
+                                            ui.add_space(8.0);
+                                        }
+
                                         self.render_main_strip(ui, ctx, meter_store);
                                     });
                                 });
@@ -168,6 +180,7 @@ impl MixerEditor {
         ctx: &Context,
         index: usize,
         meter_data: Option<&MeterData>,
+        gr_data: Option<&MeterData>,
     ) {
         let channel_pan = self.channels[index].pan;
         let channel_fader = self.channels[index].fader;
@@ -224,6 +237,34 @@ impl MixerEditor {
                         }
                     }
 
+                    // ── Ducking gain reduction ──
+                    if self.channels[index].duck_key != "none" {
+                        let reduction = gr_data
+                            .and_then(|d| d.peak.first())
+                            .map(|gr| -gr)
+                            .unwrap_or(0.0) as f32;
+                        let (rect, response) = ui
+                            .allocate_exact_size(Vec2::new(strip_inner - 4.0, 3.0), Sense::hover());
+                        let painter = ui.painter();
+                        painter.rect_filled(rect, 1.0, Color32::from_gray(30));
+                        let depth = self.channels[index].duck_depth.max(1.0);
+                        let fraction = (reduction / depth).clamp(0.0, 1.0);
+                        if fraction > 0.0 {
+                            let mut bar = rect;
+                            bar.set_width(rect.width() * fraction);
+                            painter.rect_filled(bar, 1.0, Color32::from_rgb(200, 120, 40));
+                        }
+                        response.on_hover_text(format!(
+                            "Ducked by {} ({})",
+                            self.channels[index].duck_key,
+                            if reduction > 0.05 {
+                                format!("-{:.1} dB", reduction)
+                            } else {
+                                "idle".to_string()
+                            }
+                        ));
+                    }
+
                     // ── H / G / C / E buttons ──
                     let hgce_btn_w = (strip_inner - 10.0) / 4.0;
                     ui.horizontal(|ui| {
@@ -920,4 +961,107 @@ impl MixerEditor {
             ui.add_space(STRIP_GAP);
         }
     }
+
+    /// Render the mix-minus bus masters (compact, for bus row).
+    pub(super) fn render_mix_minus_strips(
+        &mut self,
+        ui: &mut Ui,
+        ctx: &Context,
+        meter_store: &MeterDataStore,
+    ) {
+        for mm_idx in 0..self.num_mix_minus.min(MAX_MIX_MINUS) {
+            while self.mix_minus.len() <= mm_idx {
+                self.mix_minus.push(MixMinusBus::new(self.mix_minus.len()));
+            }
+
+            let meter_key = format!("{}:meter:mm{}", self.block_id, mm_idx + 1);
+            let meter_data = meter_store.get(&self.flow_id, &meter_key);
+
+            let mm_frame = egui::Frame::default()
+                .fill(Color32::from_rgb(38, 48, 42))
+                .corner_radius(CornerRadius::same(3))
+                .inner_margin(STRIP_MARGIN)
+                .show(ui, |ui| {
+                    ui.set_min_width(BUS_STRIP_INNER);
+                    ui.set_max_width(BUS_STRIP_INNER);
+
+                    ui.vertical_centered(|ui| {
+                        ui.spacing_mut().item_spacing.y = 2.0;
+
+                        ui.label(
+                            egui::RichText::new(format!("MM{}", mm_idx + 1))
+                                .strong()
+                                .size(11.0)
+                                .color(Color32::from_rgb(150, 230, 170)),
+                        )
+                        .on_hover_text(format!(
+                            "Main mix without channel(s) {}",
+                            self.mix_minus[mm_idx].exclude
+                        ));
+
+                        self.render_lcd(
+                            ui,
+                            &format_db(self.mix_minus[mm_idx].fader),
+                            BUS_STRIP_INNER - 4.0,
+                            LCD_H,
+                        );
+
+                        ui.add_space(2.0);
+
+                        ui.allocate_ui_with_layout(
+                            Vec2::new(ui.available_width(), BUS_FADER_HEIGHT),
+                            egui::Layout::left_to_right(egui::Align::Center),
+                            |ui| {
+                                self.render_stereo_meter(ui, meter_data, BUS_FADER_HEIGHT);
+                                ui.add_space(1.0);
+                                self.render_db_scale(ui, BUS_FADER_HEIGHT);
+                                ui.add_space(1.0);
+                                let fader_response =
+                                    self.render_mix_minus_fader(ui, mm_idx, BUS_FADER_HEIGHT);
+                                if fader_response.double_clicked() {
+                                    self.bypass_throttle();
+                                    self.update_mix_minus_fader(ctx, mm_idx);
+                                } else if fader_response.dragged() {
+                                    self.active_control = ActiveControl::MixMinusFader(mm_idx);
+                                    self.update_mix_minus_fader(ctx, mm_idx);
+                                } else if fader_response.drag_stopped() {
+                                    self.active_control = ActiveControl::None;
+                                }
+                            },
+                        );
+
+                        ui.add_space(2.0);
+
+                        let mute = self.mix_minus[mm_idx].mute;
+                        let mute_color = if mute {
+                            Color32::from_rgb(200, 50, 50)
+                        } else {
+                            Color32::from_rgb(55, 55, 60)
+                        };
+                        if ui
+                            .add(
+                                egui::Button::new(
+                                    egui::RichText::new("MUTE").small().color(Color32::WHITE),
+                                )
+                                .fill(mute_color)
+                                .min_size(Vec2::new(BUS_STRIP_INNER - 4.0, BTN_H)),
+                            )
+                            .clicked()
+                        {
+                            self.mix_minus[mm_idx].mute = !self.mix_minus[mm_idx].mute;
+                            self.update_mix_minus_mute(ctx, mm_idx);
+                        }
+                    });
+                });
+            if ui.input(|i| i.pointer.any_pressed())
+                && ui
+                    .input(|i| i.pointer.interact_pos())
+                    .is_some_and(|pos| mm_frame.response.rect.contains(pos))
+            {
+                self.strip_interacted = true;
+            }
+
+            ui.add_space(STRIP_GAP);
+        }
+    }
 }
@@ -11,9 +11,11 @@ impl MixerEditor {
             num_channels,
             num_aux_buses: 0,
             num_groups: 0,
+            num_mix_minus: 0,
             channels,
             groups: Vec::new(),
             aux_masters: Vec::new(),
+            mix_minus: Vec::new(),
             selection: None,
             active_control: ActiveControl::None,
             main_fader: DEFAULT_FADER,
@@ -141,6 +143,35 @@ impl MixerEditor {
             })
             .collect();
 
+        // Load mix-minus buses
+        self.num_mix_minus = properties
+            .get("num_mix_minus")
+            .and_then(|v| match v {
+                PropertyValue::Int(i) => Some(*i as usize),
+                PropertyValue::String(s) => s.parse().ok(),
+                _ => None,
+            })
+            .unwrap_or(0)
+            .min(MAX_MIX_MINUS);
+        self.mix_minus = (0..self.num_mix_minus)
+            .map(|i| {
+                let mut mm = MixMinusBus::new(i);
+                if let Some(PropertyValue::Float(f)) = properties.get(&format!("mm{}_fader", i + 1))
+                {
+                    mm.fader = *f as f32;
+                }
+                if let Some(PropertyValue::Bool(b)) = properties.get(&format!("mm{}_mute", i + 1)) {
+                    mm.mute = *b;
+                }
+                if let Some(PropertyValue::String(s)) =
+                    properties.get(&format!("mm{}_exclude", i + 1))
+                {
+                    mm.exclude = s.clone();
+                }
+                mm
+            })
+            .collect();
+
         // Initialize aux masters
         self.aux_masters = (0..self.num_aux_buses)
             .map(|i| {
@@ -166,6 +197,11 @@ impl MixerEditor {
             if let Some(PropertyValue::String(s)) = properties.get(&format!("ch{}_label", ch_num)) {
                 ch.label = s.clone();
             }
+            if let Some(PropertyValue::Bool(b)) =
+                properties.get(&format!("ch{}_recall_safe", ch_num))
+            {
+                ch.recall_safe = *b;
+            }
             // Input gain
             if let Some(PropertyValue::Float(f)) = properties.get(&format!("ch{}_gain", ch_num)) {
                 ch.gain = *f as f32;
@@ -238,6 +274,42 @@ impl MixerEditor {
             {
                 ch.gate_release = *f as f32;
             }
+            // Ducking
+            if let Some(PropertyValue::String(key)) =
+                properties.get(&format!("ch{}_duck_key", ch_num))
+            {
+                ch.duck_key = key.clone();
+            }
+            if let Some(PropertyValue::Bool(b)) =
+                properties.get(&format!("ch{}_duck_enabled", ch_num))
+            {
+                ch.duck_enabled = *b;
+            }
+            if let Some(PropertyValue::Float(f)) =
+                properties.get(&format!("ch{}_duck_threshold", ch_num))
+            {
+                ch.duck_threshold = *f as f32;
+            }
+            if let Some(PropertyValue::Float(f)) =
+                properties.get(&format!("ch{}_duck_depth", ch_num))
+            {
+                ch.duck_depth = *f as f32;
+            }
+            if let Some(PropertyValue::Float(f)) =
+                properties.get(&format!("ch{}_duck_attack", ch_num))
+            {
+                ch.duck_attack = *f as f32;
+            }
+            if let Some(PropertyValue::Float(f)) =
+                properties.get(&format!("ch{}_duck_hold", ch_num))
+            {
+                ch.duck_hold = *f as f32;
+            }
+            if let Some(PropertyValue::Float(f)) =
+                properties.get(&format!("ch{}_duck_release", ch_num))
+            {
+                ch.duck_release = *f as f32;
+            }
             // Compressor
             if let Some(PropertyValue::Bool(b)) =
                 properties.get(&format!("ch{}_comp_enabled", ch_num))
@@ -301,6 +373,14 @@ impl MixerEditor {
         }
     }
 
+    /// Apply a single block property changed on the server (e.g. by a
+    /// snapshot recall) without touching the rest of the state.
+    pub fn apply_property(&mut self, name: &str, value: PropertyValue) {
+        let mut properties = self.collect_properties();
+        properties.insert(name.to_string(), value);
+        self.load_from_properties(&properties);
+    }
+
     /// Collect current mixer state as block properties, omitting values that
     /// match their defaults. Only non-default values and structural keys
     /// are persisted so storage stays minimal and backend defaults take over
@@ -337,6 +417,8 @@ impl MixerEditor {
             "num_groups".to_string(),
             PropertyValue::Int(self.num_groups as i64),
         );
+        self.insert_mix_minus_structure(&mut props);
+        self.insert_duck_keys(&mut props);
 
         // Main bus
         set_f!("main_fader".to_string(), self.main_fader, DEFAULT_FADER);
@@ -409,6 +491,13 @@ impl MixerEditor {
             set_b!(format!("group{}_mute", n), sg.mute, false);
         }
 
+        // Mix-minus buses
+        for mm in &self.mix_minus {
+            let n = mm.index + 1;
+            set_f!(format!("mm{}_fader", n), mm.fader, DEFAULT_FADER);
+            set_b!(format!("mm{}_mute", n), mm.mute, false);
+        }
+
         // Per-channel
         for ch in &self.channels {
             let n = ch.channel_num;
@@ -419,6 +508,7 @@ impl MixerEditor {
                     PropertyValue::String(ch.label.clone()),
                 );
             }
+            set_b!(format!("ch{}_recall_safe", n), ch.recall_safe, false);
             set_f!(format!("ch{}_gain", n), ch.gain, DEFAULT_GAIN);
             set_f!(format!("ch{}_pan", n), ch.pan, DEFAULT_PAN);
             set_f!(format!("ch{}_fader", n), ch.fader, DEFAULT_FADER);
@@ -460,6 +550,33 @@ impl MixerEditor {
                 ch.gate_release,
                 DEFAULT_GATE_RELEASE
             );
+            // Ducking (key source is structural)
+            set_b!(format!("ch{}_duck_enabled", n), ch.duck_enabled, false);
+            set_f!(
+                format!("ch{}_duck_threshold", n),
+                ch.duck_threshold,
+                DEFAULT_DUCK_THRESHOLD
+            );
+            set_f!(
+                format!("ch{}_duck_depth", n),
+                ch.duck_depth,
+                DEFAULT_DUCK_DEPTH
+            );
+            set_f!(
+                format!("ch{}_duck_attack", n),
+                ch.duck_attack,
+                DEFAULT_DUCK_ATTACK
+            );
+            set_f!(
+                format!("ch{}_duck_hold", n),
+                ch.duck_hold,
+                DEFAULT_DUCK_HOLD
+            );
+            set_f!(
+                format!("ch{}_duck_release", n),
+                ch.duck_release,
+                DEFAULT_DUCK_RELEASE
+            );
             // Compressor
             set_b!(format!("ch{}_comp_enabled", n), ch.comp_enabled, false);
             set_f!(
@@ -537,7 +654,13 @@ impl MixerEditor {
             sg.mute = false;
         }
 
-        // Channels
+        // Mix-minus buses (exclusions are structural and kept)
+        for mm in &mut self.mix_minus {
+            mm.fader = DEFAULT_FADER;
+            mm.mute = false;
+        }
+
+        // Channels (ducking keys are structural and kept)
         for ch in &mut self.channels {
             ch.gain = DEFAULT_GAIN;
             ch.pan = DEFAULT_PAN;
@@ -554,6 +677,12 @@ impl MixerEditor {
             ch.gate_threshold = DEFAULT_GATE_THRESHOLD;
             ch.gate_attack = DEFAULT_GATE_ATTACK;
             ch.gate_release = DEFAULT_GATE_RELEASE;
+            ch.duck_enabled = false;
+            ch.duck_threshold = DEFAULT_DUCK_THRESHOLD;
+            ch.duck_depth = DEFAULT_DUCK_DEPTH;
+            ch.duck_attack = DEFAULT_DUCK_ATTACK;
+            ch.duck_hold = DEFAULT_DUCK_HOLD;
+            ch.duck_release = DEFAULT_DUCK_RELEASE;
             ch.comp_enabled = false;
             ch.comp_threshold = DEFAULT_COMP_THRESHOLD;
             ch.comp_ratio = DEFAULT_COMP_RATIO;
@@ -585,6 +714,39 @@ impl MixerEditor {
             "num_groups".to_string(),
             PropertyValue::Int(self.num_groups as i64),
         );
+        self.insert_mix_minus_structure(&mut props);
+        self.insert_duck_keys(&mut props);
         props
     }
+
+    /// Insert the mix-minus bus count and channel exclusions, which are
+    /// fixed when the pipeline is built.
+    fn insert_mix_minus_structure(&self, props: &mut HashMap<String, PropertyValue>) {
+        if self.num_mix_minus == 0 {
+            return;
+        }
+        props.insert(
+            "num_mix_minus".to_string(),
+            PropertyValue::Int(self.num_mix_minus as i64),
+        );
+        for mm in &self.mix_minus {
+            props.insert(
+                format!("mm{}_exclude", mm.index + 1),
+                PropertyValue::String(mm.exclude.clone()),
+            );
+        }
+    }
+
+    /// Insert the ducking key sources, which are fixed when the pipeline is
+    /// built.
+    fn insert_duck_keys(&self, props: &mut HashMap<String, PropertyValue>) {
+        for ch in &self.channels {
+            if ch.duck_key != "none" {
+                props.insert(
+                    format!("ch{}_duck_key", ch.channel_num),
+                    PropertyValue::String(ch.duck_key.clone()),
+                );
+            }
+        }
+    }
 }
@@ -173,6 +173,62 @@ impl MixerEditor {
         response
     }
 
+    /// Render a mix-minus bus fader.
+    pub(super) fn render_mix_minus_fader(
+        &mut self,
+        ui: &mut Ui,
+        mm_idx: usize,
+        height: f32,
+    ) -> Response {
+        let fader_val = self.mix_minus[mm_idx].fader;
+        let mut fader_db = linear_to_db(fader_val as f64) as f32;
+
+        let (rect, response) =
+            ui.allocate_exact_size(Vec2::new(16.0, height), Sense::click_and_drag());
+
+        if response.double_clicked() {
+            if (fader_db - 0.0).abs() < 0.5 {
+                self.mix_minus[mm_idx].fader = 0.0;
+                fader_db = -60.0;
+            } else {
+                self.mix_minus[mm_idx].fader = 1.0;
+                fader_db = 0.0;
+            }
+        } else if response.dragged() {
+            let delta = -response.drag_delta().y;
+            let db_per_pixel = 66.0 / (height - 10.0);
+            fader_db = (fader_db + delta * db_per_pixel).clamp(-60.0, 6.0);
+            self.mix_minus[mm_idx].fader = db_to_linear_f32(fader_db);
+        }
+
+        // Draw fader track
+        let painter = ui.painter();
+        let track_rect = Rect::from_center_size(rect.center(), Vec2::new(4.0, height - 10.0));
+        painter.rect_filled(track_rect, CornerRadius::same(2), Color32::from_gray(60));
+
+        // Draw handle
+        let handle_y = db_to_y(fader_db, rect.min.y, rect.max.y);
+        let handle_rect =
+            Rect::from_center_size(egui::pos2(rect.center().x, handle_y), Vec2::new(12.0, 30.0));
+        let handle_color = if response.dragged() {
+            Color32::from_rgb(150, 200, 255)
+        } else if response.hovered() {
+            Color32::from_rgb(200, 200, 200)
+        } else {
+            Color32::from_rgb(160, 160, 160)
+        };
+        painter.rect_filled(handle_rect, CornerRadius::same(3), handle_color);
+        painter.line_segment(
+            [
+                egui::pos2(handle_rect.left() + 2.0, handle_y),
+                egui::pos2(handle_rect.right() - 2.0, handle_y),
+            ],
+            Stroke::new(1.5, Color32::from_gray(40)),
+        );
+
+        response
+    }
+
     /// Render a pan knob. Center (0.0) at 12 o'clock, L at 7:30, R at 4:30.
     pub(super) fn render_pan_knob(&mut self, ui: &mut Ui, index: usize) -> Response {
         let pan = self.channels[index].pan;
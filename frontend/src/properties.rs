@@ -131,6 +131,8 @@ pub struct BlockInspectorResult {
     pub vision_mixer_url: Option<FlowId>,
     /// Live property updates to send to running pipeline elements
     pub live_property_updates: Vec<LivePropertyUpdate>,
+    /// Start or cancel of a keyframed property animation
+    pub animation_action: Option<(FlowId, crate::animation_timeline::TimelineAction)>,
 }
 
 /// Property inspector panel.
@@ -843,6 +845,16 @@ impl PropertyInspector {
                                     }
                                 }
                             }
+
+                            // Keyframe timeline for the live properties
+                            if let Some(fid) = flow_id {
+                                ui.add_space(4.0);
+                                if let Some(action) =
+                                    crate::animation_timeline::show(ui, block, definition)
+                                {
+                                    result.animation_action = Some((fid, action));
+                                }
+                            }
                         }
                     } else {
                         ui.label("This block has no configurable properties");
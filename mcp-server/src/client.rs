@@ -9,6 +9,7 @@ use strom_types::{
     },
     element::{ElementInfo, PropertyValue},
     flow::{Flow, FlowProperties},
+    macros::{MacroListResponse, MacroResponse},
 };
 
 #[derive(Deserialize)]
@@ -277,4 +278,54 @@ impl StromClient {
             .context("Failed to parse response")?;
         Ok(response.properties)
     }
+
+    /// List the macros of a vision mixer block
+    pub async fn list_macros(&self, flow_id: &str, block_id: &str) -> Result<MacroListResponse> {
+        let url = format!(
+            "{}/api/flows/{}/blocks/{}/macros",
+            self.base_url, flow_id, block_id
+        );
+        self.with_auth(self.client.get(&url))
+            .send()
+            .await
+            .context("Failed to send request")?
+            .json()
+            .await
+            .context("Failed to parse response")
+    }
+
+    /// Start playing back a vision mixer macro
+    pub async fn run_macro(
+        &self,
+        flow_id: &str,
+        block_id: &str,
+        name: &str,
+    ) -> Result<MacroResponse> {
+        let url = format!(
+            "{}/api/flows/{}/blocks/{}/macros/{}/run",
+            self.base_url, flow_id, block_id, name
+        );
+        self.with_auth(self.client.post(&url))
+            .send()
+            .await
+            .context("Failed to send request")?
+            .json()
+            .await
+            .context("Failed to parse response")
+    }
+
+    /// Cancel the macro playing on a vision mixer block
+    pub async fn stop_macro(&self, flow_id: &str, block_id: &str) -> Result<MacroResponse> {
+        let url = format!(
+            "{}/api/flows/{}/blocks/{}/macros/stop",
+            self.base_url, flow_id, block_id
+        );
+        self.with_auth(self.client.post(&url))
+            .send()
+            .await
+            .context("Failed to send request")?
+            .json()
+            .await
+            .context("Failed to parse response")
+    }
 }
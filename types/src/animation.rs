@@ -0,0 +1,212 @@
+//! Keyframed property animation types.
+//!
+//! An animation drives one numeric property of an element or pad in a
+//! running flow through a list of keyframes. Times are relative to the
+//! start of the animation; a looping animation restarts after its last
+//! keyframe.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Maximum number of keyframes in one animation
+pub const MAX_ANIMATION_KEYFRAMES: usize = 256;
+
+/// Curve from a keyframe to the next one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Starts slow and speeds up.
+    EaseIn,
+    /// Starts fast and slows down.
+    EaseOut,
+    /// Slow at both ends, like the vision mixer transitions.
+    EaseInOut,
+    /// Keeps the value until the next keyframe, then jumps.
+    Hold,
+}
+
+impl Easing {
+    /// All easings, in UI order.
+    pub fn all() -> &'static [Easing] {
+        &[
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::Hold,
+        ]
+    }
+
+    /// Display label.
+    pub fn label(self) -> &'static str {
+        match self {
+            Easing::Linear => "Linear",
+            Easing::EaseIn => "Ease in",
+            Easing::EaseOut => "Ease out",
+            Easing::EaseInOut => "Ease in-out",
+            Easing::Hold => "Hold",
+        }
+    }
+
+    /// Progress along the curve for linear progress `t` (0.0-1.0).
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => 1.0 - (t * std::f64::consts::FRAC_PI_2).cos(),
+            Easing::EaseOut => (t * std::f64::consts::FRAC_PI_2).sin(),
+            Easing::EaseInOut => (1.0 - (t * std::f64::consts::PI).cos()) / 2.0,
+            Easing::Hold => 0.0,
+        }
+    }
+}
+
+/// A value at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Keyframe {
+    /// Time from the start of the animation in milliseconds.
+    pub time_ms: u64,
+    pub value: f64,
+    /// Curve towards the next keyframe.
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+/// Value of a keyframe list at `time_ms`.
+///
+/// Keyframes must be sorted by time. Before the first keyframe its value
+/// applies, after the last one the last value. Returns `None` without
+/// keyframes.
+pub fn value_at(keyframes: &[Keyframe], time_ms: u64) -> Option<f64> {
+    let next = keyframes.partition_point(|k| k.time_ms <= time_ms);
+    let Some(prev) = next.checked_sub(1).map(|i| &keyframes[i]) else {
+        return keyframes.first().map(|k| k.value);
+    };
+    let Some(next) = keyframes.get(next) else {
+        return Some(prev.value);
+    };
+    let t = (time_ms - prev.time_ms) as f64 / (next.time_ms - prev.time_ms) as f64;
+    Some(prev.value + (next.value - prev.value) * prev.easing.apply(t))
+}
+
+/// Request to create or replace an animation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SetPropertyAnimationRequest {
+    /// Element ID, `"{block_id}:{element}"` for elements inside blocks.
+    #[cfg_attr(feature = "validation", garde(length(min = 1)))]
+    pub element_id: String,
+    /// Pad of the element whose property is animated, e.g. `sink_0` of a
+    /// compositor. The element property is animated when omitted.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub pad_name: Option<String>,
+    /// GStreamer property name. Must be numeric and controllable.
+    #[cfg_attr(feature = "validation", garde(length(min = 1)))]
+    pub property_name: String,
+    /// Keyframes, in any order.
+    #[cfg_attr(
+        feature = "validation",
+        garde(length(min = 1, max = MAX_ANIMATION_KEYFRAMES))
+    )]
+    pub keyframes: Vec<Keyframe>,
+    /// Restart after the last keyframe until cancelled.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub looping: bool,
+    /// Start right away. Otherwise the animation waits for a start request.
+    #[serde(default = "default_autostart")]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub autostart: bool,
+}
+
+fn default_autostart() -> bool {
+    true
+}
+
+/// Playback state of an animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationState {
+    /// Created without autostart, waiting for a start request.
+    Ready,
+    Running,
+    /// Reached its last keyframe; the property keeps the last value.
+    Finished,
+    /// Cancelled; the property keeps the value it had at that moment.
+    Cancelled,
+}
+
+/// An animation on a running flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PropertyAnimation {
+    pub id: String,
+    pub element_id: String,
+    pub pad_name: Option<String>,
+    pub property_name: String,
+    /// Keyframes sorted by time.
+    pub keyframes: Vec<Keyframe>,
+    pub looping: bool,
+    pub state: AnimationState,
+    /// Position within the animation (within the current pass when
+    /// looping) while running.
+    pub position_ms: Option<u64>,
+}
+
+/// Animations of a running flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PropertyAnimationsResponse {
+    pub animations: Vec<PropertyAnimation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kf(time_ms: u64, value: f64, easing: Easing) -> Keyframe {
+        Keyframe {
+            time_ms,
+            value,
+            easing,
+        }
+    }
+
+    #[test]
+    fn test_value_at_interpolates_with_segment_easing() {
+        let keyframes = [
+            kf(1000, 0.0, Easing::Linear),
+            kf(2000, 1.0, Easing::Hold),
+            kf(3000, 0.5, Easing::EaseInOut),
+            kf(4000, 1.5, Easing::Linear),
+        ];
+        assert_eq!(value_at(&[], 0), None);
+        assert_eq!(value_at(&keyframes, 0), Some(0.0));
+        assert_eq!(value_at(&keyframes, 1500), Some(0.5));
+        assert_eq!(value_at(&keyframes, 2999), Some(1.0));
+        assert_eq!(value_at(&keyframes, 3000), Some(0.5));
+        assert!((value_at(&keyframes, 3500).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(value_at(&keyframes, 9000), Some(1.5));
+    }
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in Easing::all() {
+            assert_eq!(easing.apply(0.0), 0.0);
+            if *easing != Easing::Hold {
+                assert!((easing.apply(1.0) - 1.0).abs() < 1e-9);
+            }
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+}
@@ -96,7 +96,10 @@ pub struct TriggerTransitionRequest {
     /// Index of the input to transition to (0-based)
     #[cfg_attr(feature = "validation", garde(skip))]
     pub to_input: usize,
-    /// Type of transition: "cut", "fade", "slide_left", "slide_right", "slide_up", "slide_down"
+    /// Type of transition: "cut", "fade", "slide_left", "slide_right", "slide_up", "slide_down",
+    /// "push_left", "push_right", "push_up", "push_down", "dip_to_black", "stinger",
+    /// "wipe_bar", "wipe_bar_v", "wipe_box", "wipe_diamond", "wipe_iris", "wipe_clock" or
+    /// "smpte_<code>" (wipes accept a "_reverse" suffix)
     #[serde(default = "default_transition_type")]
     #[cfg_attr(feature = "validation", garde(length(min = 1, max = 50)))]
     pub transition_type: String,
@@ -287,6 +290,21 @@ pub enum ClientMessage {
     },
     /// Ping to keep connection alive
     Ping,
+    /// Fire a cart player slot
+    CartFire {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        slot: usize,
+    },
+    /// Stop a cart player slot, or all slots when `slot` is omitted
+    CartStop {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        #[serde(default)]
+        slot: Option<usize>,
+    },
 }
 
 // ============================================================================
@@ -920,6 +938,63 @@ pub struct DskToggleResponse {
     pub enabled: bool,
 }
 
+/// Request to toggle a USK (Upstream Keyer) layer on a vision mixer block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UskToggleRequest {
+    /// USK layer number (1-based)
+    pub usk: usize,
+    /// Enable or disable the USK layer
+    pub enabled: bool,
+}
+
+/// Response after toggling a USK layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UskToggleResponse {
+    pub message: String,
+    /// USK layer number (1-based)
+    pub usk: usize,
+    pub enabled: bool,
+}
+
+/// Request to route a source to an AUX output of a vision mixer block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SetAuxSourceRequest {
+    /// AUX output number (1-based)
+    #[cfg_attr(feature = "validation", garde(range(min = 1, max = crate::vision_mixer::MAX_AUX_OUTPUTS)))]
+    pub aux: usize,
+    /// Source to route to the AUX output
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub source: crate::vision_mixer::AuxSource,
+    /// Type of transition: "cut" or "fade"
+    #[serde(default = "default_aux_transition_type")]
+    #[cfg_attr(feature = "validation", garde(length(min = 1, max = 50)))]
+    pub transition_type: String,
+    /// Duration of the fade in milliseconds (ignored for "cut")
+    #[serde(default = "default_transition_duration")]
+    #[cfg_attr(feature = "validation", garde(range(max = 60000)))]
+    pub duration_ms: u64,
+}
+
+fn default_aux_transition_type() -> String {
+    "cut".to_string()
+}
+
+/// Response after routing a source to an AUX output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SetAuxSourceResponse {
+    pub message: String,
+    /// AUX output number (1-based)
+    pub aux: usize,
+    pub source: crate::vision_mixer::AuxSource,
+    /// Inputs currently visible on the AUX output (empty for program and clean feed).
+    pub inputs: Vec<usize>,
+}
+
 /// Request to toggle Fade to Black on a vision mixer block.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
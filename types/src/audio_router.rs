@@ -0,0 +1,88 @@
+//! Routing preset and salvo types for the audio router block.
+//!
+//! A routing uses the same JSON format as the block's `routing_matrix`
+//! property: `{"i0c0": ["o0c0", "o1c0"]}` where `iXcY` is input X channel Y
+//! and `oXcY` is output X channel Y.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Routing matrix: input channel key -> output channel keys.
+pub type AudioRouting = BTreeMap<String, Vec<String>>;
+
+/// Default crossfade between the old and new routing of a salvo
+pub const DEFAULT_SALVO_FADE_MS: u64 = 20;
+/// Maximum salvo crossfade in milliseconds
+pub const MAX_SALVO_FADE_MS: u64 = 10_000;
+
+/// Current routing of an audio router block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AudioRoutingResponse {
+    /// Active routing matrix.
+    #[cfg_attr(feature = "openapi", schema(value_type = BTreeMap<String, Vec<String>>))]
+    pub routing: AudioRouting,
+    /// Preset whose routing equals the active routing, if any.
+    pub active_preset: Option<String>,
+    /// Routing presets stored with the block.
+    #[cfg_attr(
+        feature = "openapi",
+        schema(value_type = BTreeMap<String, BTreeMap<String, Vec<String>>>)
+    )]
+    pub presets: BTreeMap<String, AudioRouting>,
+}
+
+/// Request to store a routing preset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SaveAudioRoutingPresetRequest {
+    /// Routing to store. When omitted the active routing is captured.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<BTreeMap<String, Vec<String>>>))]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub routing: Option<AudioRouting>,
+}
+
+/// Request to apply a complete routing in one step.
+///
+/// Exactly one of `preset` and `routing` must be given.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct AudioRouterSalvoRequest {
+    /// Name of a stored preset to recall.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub preset: Option<String>,
+    /// Routing matrix to apply.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<BTreeMap<String, Vec<String>>>))]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub routing: Option<AudioRouting>,
+    /// Crossfade between the old and new routing in milliseconds
+    #[serde(default = "default_salvo_fade_ms")]
+    #[cfg_attr(feature = "validation", garde(range(max = MAX_SALVO_FADE_MS)))]
+    pub fade_ms: u64,
+}
+
+fn default_salvo_fade_ms() -> u64 {
+    DEFAULT_SALVO_FADE_MS
+}
+
+/// Response after a routing operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AudioRouterResponse {
+    pub message: String,
+    /// Routing matrix stored or applied.
+    #[cfg_attr(feature = "openapi", schema(value_type = BTreeMap<String, Vec<String>>))]
+    pub routing: AudioRouting,
+    /// True when the routing was switched on the running pipeline. False
+    /// when the flow is stopped, in which case it takes effect on the next
+    /// start.
+    pub live: bool,
+}
@@ -0,0 +1,146 @@
+//! Cart player API types shared between backend and frontend.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// What firing a slot does while it is already playing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CartRetrigger {
+    /// Play the clip again from the start.
+    #[default]
+    Restart,
+    /// Start another voice on top of the playing one.
+    Overlap,
+    /// Stop the slot (toggle button).
+    Stop,
+}
+
+/// A cart slot bound to a clip.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CartSlot {
+    /// File URI or path relative to the media directory (empty for an unassigned slot).
+    /// Only the audio of the file is played.
+    #[serde(default)]
+    pub uri: String,
+    /// Button label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Clip gain in dB
+    #[serde(default)]
+    pub gain_db: f64,
+    /// What firing the slot does while it is playing
+    #[serde(default)]
+    pub retrigger: CartRetrigger,
+    /// Firing a slot stops the other slots of its choke group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choke_group: Option<String>,
+    /// Play the clip in a loop until stopped
+    #[serde(default)]
+    pub looping: bool,
+}
+
+/// Request to set the slots of a cart player.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SetCartSlotsRequest {
+    /// Slots in button order
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub slots: Vec<CartSlot>,
+}
+
+impl SetCartSlotsRequest {
+    /// The slots of the request, checked for gains and empty choke groups.
+    pub fn into_slots(self) -> Result<Vec<CartSlot>, String> {
+        for (i, slot) in self.slots.iter().enumerate() {
+            if !slot.gain_db.is_finite() || slot.gain_db > 24.0 {
+                return Err(format!("Slot {}: gain must be at most 24 dB", i));
+            }
+            if slot.choke_group.as_deref() == Some("") {
+                return Err(format!("Slot {}: empty choke group", i));
+            }
+        }
+        Ok(self.slots)
+    }
+}
+
+/// Loading state of a cart slot's clip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CartSlotStatus {
+    /// No file assigned
+    Empty,
+    /// The clip is being decoded into memory
+    Loading,
+    /// The clip is in memory and can be fired
+    Ready,
+    /// The clip could not be loaded
+    Error,
+}
+
+/// State of a cart slot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CartSlotState {
+    /// Slot index (0-based)
+    pub index: usize,
+    /// Slot settings
+    pub slot: CartSlot,
+    /// Loading state of the clip
+    pub status: CartSlotStatus,
+    /// Why the clip could not be loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Clip length in nanoseconds (0 until loaded)
+    pub duration_ns: u64,
+    /// Number of voices of the slot playing
+    pub voices: usize,
+}
+
+/// Response with the slots of a cart player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CartStateResponse {
+    /// Slots in button order (settings only when the flow is stopped)
+    pub slots: Vec<CartSlotState>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_cart_slots_request_into_slots() {
+        let slot = CartSlot {
+            uri: "sting.wav".to_string(),
+            choke_group: Some("beds".to_string()),
+            ..Default::default()
+        };
+        let req = SetCartSlotsRequest {
+            slots: vec![slot.clone(), CartSlot::default()],
+        };
+        assert_eq!(req.into_slots().unwrap().len(), 2);
+
+        let loud = SetCartSlotsRequest {
+            slots: vec![CartSlot {
+                gain_db: 30.0,
+                ..slot.clone()
+            }],
+        };
+        assert!(loud.into_slots().is_err());
+
+        let unnamed = SetCartSlotsRequest {
+            slots: vec![CartSlot {
+                choke_group: Some(String::new()),
+                ..slot
+            }],
+        };
+        assert!(unnamed.into_slots().is_err());
+    }
+}
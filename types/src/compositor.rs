@@ -0,0 +1,85 @@
+//! Layout preset types for the compositor block.
+//!
+//! A layout holds the geometry, alpha and z-order of every compositor input.
+//! Presets are stored with the block and recalled with an animated move.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Default duration of the animated move when recalling a layout
+pub const DEFAULT_LAYOUT_RECALL_MS: u64 = 500;
+/// Maximum layout recall duration in milliseconds
+pub const MAX_LAYOUT_RECALL_MS: u64 = 10_000;
+
+/// Placement of one compositor input.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct InputLayout {
+    /// Input index (0-based).
+    pub input: usize,
+    pub xpos: i32,
+    pub ypos: i32,
+    pub width: i32,
+    pub height: i32,
+    /// Opacity from 0.0 to 1.0.
+    pub alpha: f64,
+    pub zorder: u32,
+}
+
+/// Placement of the compositor inputs, one entry per input.
+pub type CompositorLayout = Vec<InputLayout>;
+
+/// Current layout and the presets of a compositor block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CompositorLayoutsResponse {
+    /// Layout from the block properties.
+    pub layout: CompositorLayout,
+    /// Preset whose layout equals the current layout, if any.
+    pub active_preset: Option<String>,
+    /// Layout presets stored with the block.
+    #[cfg_attr(feature = "openapi", schema(value_type = BTreeMap<String, Vec<InputLayout>>))]
+    pub presets: BTreeMap<String, CompositorLayout>,
+}
+
+/// Request to store a layout preset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SaveLayoutPresetRequest {
+    /// Layout to store. When omitted the current layout is captured.
+    /// Inputs left out keep their current placement.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub layout: Option<CompositorLayout>,
+}
+
+/// Request to recall a stored layout preset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct RecallLayoutRequest {
+    /// Duration of the move in milliseconds. Zero cuts to the layout.
+    #[serde(default = "default_layout_recall_ms")]
+    #[cfg_attr(feature = "validation", garde(range(max = MAX_LAYOUT_RECALL_MS)))]
+    pub duration_ms: u64,
+}
+
+fn default_layout_recall_ms() -> u64 {
+    DEFAULT_LAYOUT_RECALL_MS
+}
+
+/// Response after a layout operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CompositorLayoutResponse {
+    pub message: String,
+    /// Layout stored or recalled.
+    pub layout: CompositorLayout,
+    /// True when the layout was moved on the running pipeline. False when
+    /// the flow is stopped, in which case it applies on the next start.
+    pub live: bool,
+}
@@ -105,6 +105,9 @@ pub struct DeviceResponse {
     pub provider: String,
     /// Additional properties from the device.
     pub properties: HashMap<String, String>,
+    /// Capture/playback modes advertised by the device, one caps string per mode.
+    #[serde(default)]
+    pub caps: Vec<String>,
     /// Seconds since first discovery.
     pub first_seen_secs_ago: u64,
     /// Seconds since last seen.
@@ -1,5 +1,6 @@
 //! Events for real-time updates across clients.
 
+use crate::discovery::DeviceCategory;
 use crate::element::PropertyValue;
 use crate::system_monitor::SystemStats;
 use crate::thread_stats::ThreadStats;
@@ -221,6 +222,18 @@ pub enum StromEvent {
     StreamUpdated { stream_id: String },
     /// A discovered stream expired or was deleted
     StreamRemoved { stream_id: String },
+    /// A local or network device appeared (hot-plug)
+    DeviceAdded {
+        device_id: String,
+        name: String,
+        category: DeviceCategory,
+    },
+    /// A previously discovered device disappeared (unplugged)
+    DeviceRemoved {
+        device_id: String,
+        name: String,
+        category: DeviceCategory,
+    },
     /// Media player position update (periodic)
     MediaPlayerPosition {
         #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
@@ -244,6 +257,36 @@ pub enum StromEvent {
         state: crate::mediaplayer::PlayerState,
         /// Current file path (if any)
         current_file: Option<String>,
+        /// Current file index (0-based)
+        #[serde(default)]
+        current_file_index: usize,
+        /// Playout settings of the current item
+        #[serde(default)]
+        current_item: Option<crate::mediaplayer::PlaylistItem>,
+        /// Repeat of the current item being played (0 = first play)
+        #[serde(default)]
+        loop_pass: u32,
+        /// Whether the current item is prerolled and waiting for play
+        #[serde(default)]
+        cued: bool,
+    },
+    /// A media player playout schedule put something on air or took it off
+    MediaPlayerAsRun {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        /// The as-run log record
+        entry: crate::mediaplayer::AsRunEntry,
+    },
+    /// A cart player slot started or stopped playing
+    CartSlotPlaying {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        /// Slot index (0-based)
+        slot: usize,
+        /// Number of voices of the slot playing (0 when stopped)
+        voices: usize,
     },
     /// A transition was triggered on a compositor block
     TransitionTriggered {
@@ -273,6 +316,39 @@ pub enum StromEvent {
         /// Vectorscope R channel samples (base64-encoded i8 samples)
         vectorscope_r: String,
     },
+    /// Audio router routing changed by a salvo
+    AudioRoutingChanged {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        /// Preset whose routing is now active, if any.
+        preset: Option<String>,
+        /// Active routing matrix (`{"i0c0": ["o0c0"]}`).
+        #[cfg_attr(feature = "openapi", schema(value_type = BTreeMap<String, Vec<String>>))]
+        routing: crate::audio_router::AudioRouting,
+        /// False when the routing takes effect on the next flow start.
+        live: bool,
+    },
+    /// Compositor layout preset recalled
+    CompositorLayoutRecalled {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        preset: String,
+        /// Duration of the move in milliseconds.
+        duration_ms: u64,
+        /// False when the layout takes effect on the next flow start.
+        live: bool,
+    },
+    /// Keyframed property animation started, finished or was cancelled
+    PropertyAnimationChanged {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        animation_id: String,
+        element_id: String,
+        property_name: String,
+        state: crate::animation::AnimationState,
+    },
     /// Recorder block started writing a new file
     RecorderFileChanged {
         #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
@@ -287,6 +363,15 @@ pub enum StromEvent {
         flow_id: FlowId,
         block_id: String,
     },
+    /// Recorder block started, paused, stopped or finalized a recording, or was armed
+    RecorderStateChanged {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        state: crate::recorder::RecorderState,
+        /// Whether the trigger starts and stops recordings
+        armed: bool,
+    },
     /// Buffer age warning (buffer is older than threshold)
     BufferAgeWarning {
         #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
@@ -349,6 +434,15 @@ pub enum StromEvent {
         dsk: usize,
         enabled: bool,
     },
+    /// Vision mixer USK layer toggled
+    VisionMixerUskChanged {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        /// USK layer number (1-based)
+        usk: usize,
+        enabled: bool,
+    },
     /// Vision mixer multiview overlay alpha changed
     VisionMixerOverlayAlphaChanged {
         #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
@@ -371,6 +465,53 @@ pub enum StromEvent {
         /// Background source index, or null if cleared.
         background_input: Option<usize>,
     },
+    /// Vision mixer AUX output source changed
+    VisionMixerAuxChanged {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        /// AUX output number (1-based)
+        aux: usize,
+        source: crate::vision_mixer::AuxSource,
+        /// Inputs visible on the AUX output (empty for program and clean feed).
+        inputs: Vec<usize>,
+    },
+    /// Vision mixer macro recording started or stopped
+    VisionMixerMacroRecording {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        name: String,
+        recording: bool,
+    },
+    /// Vision mixer macro playback progress.
+    ///
+    /// Sent when a macro starts, before each step, and with `running = false`
+    /// when it finishes or is cancelled.
+    VisionMixerMacroRunning {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        name: String,
+        /// Index of the step being executed (equals `total_steps` when done).
+        step: usize,
+        total_steps: usize,
+        running: bool,
+    },
+    /// An SCTE-35 cue was received on an MPEG-TS input
+    Scte35CueReceived {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        cue: crate::scte35::Scte35Cue,
+    },
+    /// An SCTE-35 cue was injected into an MPEG-TS output
+    Scte35CueInserted {
+        #[cfg_attr(feature = "openapi", schema(value_type = String, format = Uuid))]
+        flow_id: FlowId,
+        block_id: String,
+        cue: crate::scte35::Scte35Cue,
+    },
 }
 
 impl StromEvent {
@@ -621,6 +762,20 @@ impl StromEvent {
             StromEvent::StreamRemoved { stream_id } => {
                 format!("Removed AES67 stream {}", stream_id)
             }
+            StromEvent::DeviceAdded {
+                device_id,
+                name,
+                category,
+            } => {
+                format!("Device '{}' ({}, {:?}) added", name, device_id, category)
+            }
+            StromEvent::DeviceRemoved {
+                device_id,
+                name,
+                category,
+            } => {
+                format!("Device '{}' ({}, {:?}) removed", name, device_id, category)
+            }
             StromEvent::MediaPlayerPosition {
                 flow_id,
                 block_id,
@@ -646,7 +801,14 @@ impl StromEvent {
                 block_id,
                 state,
                 current_file,
+                cued,
+                ..
             } => {
+                let state = if *cued {
+                    format!("{} (cued)", state)
+                } else {
+                    state.to_string()
+                };
                 if let Some(file) = current_file {
                     format!(
                         "Media player {} in flow {} state: {} ({})",
@@ -659,6 +821,28 @@ impl StromEvent {
                     )
                 }
             }
+            StromEvent::MediaPlayerAsRun {
+                flow_id,
+                block_id,
+                entry,
+            } => {
+                format!(
+                    "Media player {} in flow {} as-run: {:?} {:?} {}",
+                    block_id, flow_id, entry.status, entry.kind, entry.source
+                )
+            }
+            StromEvent::CartSlotPlaying {
+                flow_id,
+                block_id,
+                slot,
+                voices,
+            } => {
+                let state = if *voices > 0 { "playing" } else { "stopped" };
+                format!(
+                    "Cart player {} in flow {} slot {}: {}",
+                    block_id, flow_id, slot, state
+                )
+            }
             StromEvent::TransitionTriggered {
                 flow_id,
                 block_instance_id,
@@ -687,6 +871,53 @@ impl StromEvent {
                     vectorscope_l.len() * 3 / 4
                 )
             }
+            StromEvent::AudioRoutingChanged {
+                flow_id,
+                block_id,
+                preset,
+                routing,
+                live,
+            } => {
+                format!(
+                    "Audio router {} in flow {}: {} routes{}{}",
+                    block_id,
+                    flow_id,
+                    routing.values().map(Vec::len).sum::<usize>(),
+                    preset
+                        .as_ref()
+                        .map(|p| format!(" (preset '{}')", p))
+                        .unwrap_or_default(),
+                    if *live { "" } else { ", applies on next start" }
+                )
+            }
+            StromEvent::CompositorLayoutRecalled {
+                flow_id,
+                block_id,
+                preset,
+                duration_ms,
+                live,
+            } => {
+                format!(
+                    "Compositor {} in flow {}: layout '{}' recalled over {}ms{}",
+                    block_id,
+                    flow_id,
+                    preset,
+                    duration_ms,
+                    if *live { "" } else { ", applies on next start" }
+                )
+            }
+            StromEvent::PropertyAnimationChanged {
+                flow_id,
+                animation_id,
+                element_id,
+                property_name,
+                state,
+            } => {
+                format!(
+                    "Animation {} of {}.{} in flow {}: {:?}",
+                    animation_id, element_id, property_name, flow_id, state
+                )
+            }
             StromEvent::RecorderFileChanged {
                 flow_id,
                 block_id,
@@ -703,6 +934,18 @@ impl StromEvent {
                     block_id, flow_id
                 )
             }
+            StromEvent::RecorderStateChanged {
+                flow_id,
+                block_id,
+                state,
+                armed,
+            } => {
+                let armed = if *armed { " (armed)" } else { "" };
+                format!(
+                    "Recorder {} in flow {}: {}{}",
+                    block_id, flow_id, state, armed
+                )
+            }
             StromEvent::BufferAgeWarning {
                 flow_id,
                 element_id,
@@ -775,6 +1018,20 @@ impl StromEvent {
                     if *enabled { "ON" } else { "OFF" }
                 )
             }
+            StromEvent::VisionMixerUskChanged {
+                flow_id,
+                block_id,
+                usk,
+                enabled,
+            } => {
+                format!(
+                    "Vision mixer {} in flow {}: USK {} {}",
+                    block_id,
+                    flow_id,
+                    usk,
+                    if *enabled { "ON" } else { "OFF" }
+                )
+            }
             StromEvent::VisionMixerOverlayAlphaChanged {
                 flow_id,
                 block_id,
@@ -807,6 +1064,79 @@ impl StromEvent {
                     block_id, flow_id, background_input
                 )
             }
+            StromEvent::VisionMixerAuxChanged {
+                flow_id,
+                block_id,
+                aux,
+                source,
+                ..
+            } => {
+                format!(
+                    "Vision mixer {} in flow {}: AUX {} <- {}",
+                    block_id,
+                    flow_id,
+                    aux,
+                    source.as_property()
+                )
+            }
+            StromEvent::VisionMixerMacroRecording {
+                flow_id,
+                block_id,
+                name,
+                recording,
+            } => {
+                format!(
+                    "Vision mixer {} in flow {}: macro '{}' recording {}",
+                    block_id,
+                    flow_id,
+                    name,
+                    if *recording { "started" } else { "stopped" }
+                )
+            }
+            StromEvent::VisionMixerMacroRunning {
+                flow_id,
+                block_id,
+                name,
+                step,
+                total_steps,
+                running,
+            } => {
+                if *running {
+                    format!(
+                        "Vision mixer {} in flow {}: macro '{}' step {}/{}",
+                        block_id,
+                        flow_id,
+                        name,
+                        step + 1,
+                        total_steps
+                    )
+                } else {
+                    format!(
+                        "Vision mixer {} in flow {}: macro '{}' finished",
+                        block_id, flow_id, name
+                    )
+                }
+            }
+            StromEvent::Scte35CueReceived {
+                flow_id,
+                block_id,
+                cue,
+            } => {
+                format!(
+                    "SCTE-35 {} received on {} in flow {} (pts {:?})",
+                    cue.command, block_id, flow_id, cue.pts_time
+                )
+            }
+            StromEvent::Scte35CueInserted {
+                flow_id,
+                block_id,
+                cue,
+            } => {
+                format!(
+                    "SCTE-35 {} inserted on {} in flow {} (pts {:?})",
+                    cue.command, block_id, flow_id, cue.pts_time
+                )
+            }
         }
     }
 }
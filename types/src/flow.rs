@@ -2,6 +2,8 @@
 
 use crate::block::BlockInstance;
 use crate::element::{Element, Link};
+use crate::macros::VisionMixerMacro;
+use crate::mixer::AutomationLane;
 use crate::state::PipelineState;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -319,6 +321,14 @@ pub struct Flow {
     /// Flow configuration properties
     #[serde(default)]
     pub properties: FlowProperties,
+    /// Vision mixer macros recorded for blocks in this flow.
+    /// Ignored when updating an existing flow; use the macro endpoints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub macros: Vec<VisionMixerMacro>,
+    /// Audio mixer automation lanes stored for blocks in this flow.
+    /// Ignored when updating an existing flow; use the automation endpoints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mixer_automation: Vec<AutomationLane>,
 }
 
 impl Flow {
@@ -333,6 +343,8 @@ impl Flow {
             running: false,
             gst_state: Some(PipelineState::Null),
             properties: FlowProperties::default(),
+            macros: Vec::new(),
+            mixer_automation: Vec::new(),
         }
     }
 
@@ -347,6 +359,8 @@ impl Flow {
             running: false,
             gst_state: Some(PipelineState::Null),
             properties: FlowProperties::default(),
+            macros: Vec::new(),
+            mixer_automation: Vec::new(),
         }
     }
 
@@ -10,16 +10,23 @@ pub const DEFAULT_PORT: u16 = 8080;
 /// Used by automatic buffer age probes and UI color indicators.
 pub const BUFFER_AGE_WARNING_THRESHOLD_MS: u64 = 3000;
 
+pub mod animation;
 pub mod api;
+pub mod audio_router;
 pub mod auth;
 pub mod block;
+pub mod cartplayer;
+pub mod compositor;
 pub mod discovery;
 pub mod element;
 pub mod events;
 pub mod flow;
+pub mod macros;
 pub mod mediaplayer;
 pub mod mixer;
 pub mod network;
+pub mod recorder;
+pub mod scte35;
 pub mod state;
 pub mod stats;
 pub mod system_monitor;
@@ -0,0 +1,145 @@
+//! Vision mixer macros: recorded sequences of switcher actions.
+//!
+//! A macro is a named list of steps stored with the flow. Each step waits
+//! `delay_ms` after the previous one and then performs one switcher action,
+//! the same operations exposed by the vision mixer REST endpoints.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// A switcher action that can be recorded and replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MacroAction {
+    /// Trigger a transition (take).
+    Transition {
+        from_input: usize,
+        to_input: usize,
+        transition_type: String,
+        duration_ms: u64,
+    },
+    /// Select a preview source.
+    SelectPreview {
+        input: usize,
+        #[serde(default)]
+        multi: bool,
+    },
+    /// Set or clear the background source.
+    SetBackground { input: Option<usize> },
+    /// Enable or disable a DSK layer (1-based).
+    SetDsk { dsk: usize, enabled: bool },
+    /// Enable or disable a USK layer (1-based).
+    SetUsk { usk: usize, enabled: bool },
+    /// Toggle Fade to Black.
+    FadeToBlack { duration_ms: u64 },
+    /// Set the multiview overlay alpha.
+    SetOverlayAlpha { alpha: f64 },
+    /// Route a source to an AUX output (1-based).
+    SetAux {
+        aux: usize,
+        source: crate::vision_mixer::AuxSource,
+        transition_type: String,
+        duration_ms: u64,
+    },
+}
+
+/// One macro step: wait, then perform an action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MacroStep {
+    /// Delay before this step in milliseconds, relative to the previous step.
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// The action to perform.
+    pub action: MacroAction,
+}
+
+/// A named macro targeting one vision mixer block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct VisionMixerMacro {
+    /// Macro name, unique per block.
+    pub name: String,
+    /// Vision mixer block instance ID.
+    pub block_id: String,
+    /// Steps in playback order.
+    #[serde(default)]
+    pub steps: Vec<MacroStep>,
+}
+
+impl VisionMixerMacro {
+    /// Total playback time in milliseconds (sum of step delays).
+    pub fn duration_ms(&self) -> u64 {
+        self.steps.iter().map(|s| s.delay_ms).sum()
+    }
+}
+
+/// Request to start recording a macro.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct StartMacroRecordingRequest {
+    /// Name to store the macro under (replaces an existing macro with this name)
+    #[cfg_attr(feature = "validation", garde(length(min = 1, max = 100)))]
+    pub name: String,
+}
+
+/// Request to create or replace a macro.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SaveMacroRequest {
+    /// Steps in playback order
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub steps: Vec<MacroStep>,
+}
+
+/// List of macros on a vision mixer block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MacroListResponse {
+    pub macros: Vec<VisionMixerMacro>,
+    /// Name of the macro being recorded, if any.
+    pub recording: Option<String>,
+    /// Name of the macro currently playing, if any.
+    pub running: Option<String>,
+}
+
+/// Response after a macro operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MacroResponse {
+    pub message: String,
+    /// Macro name.
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macro_duration() {
+        let m = VisionMixerMacro {
+            name: "open".to_string(),
+            block_id: "vm".to_string(),
+            steps: vec![
+                MacroStep {
+                    delay_ms: 0,
+                    action: MacroAction::SetDsk {
+                        dsk: 1,
+                        enabled: true,
+                    },
+                },
+                MacroStep {
+                    delay_ms: 5000,
+                    action: MacroAction::FadeToBlack { duration_ms: 1000 },
+                },
+            ],
+        };
+        assert_eq!(m.duration_ms(), 5000);
+    }
+}
@@ -44,14 +44,105 @@ pub struct PlayerControlRequest {
     pub action: PlayerAction,
 }
 
+/// What the player does when an item reaches its end.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EndAction {
+    /// Advance to the next item.
+    #[default]
+    Next,
+    /// Pause on the last frame. Play then takes the next item.
+    Hold,
+}
+
+/// A playlist entry with its playout settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PlaylistItem {
+    /// File URI or path relative to the media directory
+    pub uri: String,
+    /// Position to start playing from, in nanoseconds (file start when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_point_ns: Option<u64>,
+    /// Position to stop playing at, in nanoseconds (file end when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_point_ns: Option<u64>,
+    /// Times to repeat the item before its end action (0 plays it once)
+    #[serde(default)]
+    pub loop_count: u32,
+    /// What to do after the last repeat
+    #[serde(default)]
+    pub end_action: EndAction,
+    /// Preroll paused on the first frame when loaded and wait for play
+    #[serde(default)]
+    pub cue_on_load: bool,
+}
+
+impl PlaylistItem {
+    /// Item playing the whole file once, then advancing.
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            in_point_ns: None,
+            out_point_ns: None,
+            loop_count: 0,
+            end_action: EndAction::Next,
+            cue_on_load: false,
+        }
+    }
+
+    /// Start and optional stop of the played segment, if the item is trimmed.
+    pub fn segment(&self) -> Option<(u64, Option<u64>)> {
+        if self.in_point_ns.is_none() && self.out_point_ns.is_none() {
+            return None;
+        }
+        Some((self.in_point_ns.unwrap_or(0), self.out_point_ns))
+    }
+}
+
+impl From<String> for PlaylistItem {
+    fn from(uri: String) -> Self {
+        Self::new(uri)
+    }
+}
+
 /// Request to set the playlist.
+///
+/// Either `files` or `items` must be given. When both are, the items
+/// follow the files.
 #[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[cfg_attr(feature = "validation", derive(garde::Validate))]
 pub struct SetPlaylistRequest {
-    /// List of file URIs (e.g., "file:///path/to/video.mp4")
-    #[cfg_attr(feature = "validation", garde(length(min = 1)))]
+    /// List of file URIs (e.g., "file:///path/to/video.mp4"), played with default settings
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
     pub files: Vec<String>,
+    /// Items with in/out points, loops, end action and cueing
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub items: Vec<PlaylistItem>,
+}
+
+impl SetPlaylistRequest {
+    /// All items of the request, checked for an empty list and inverted
+    /// in/out points.
+    pub fn into_items(self) -> Result<Vec<PlaylistItem>, String> {
+        let items: Vec<PlaylistItem> = self
+            .files
+            .into_iter()
+            .map(PlaylistItem::from)
+            .chain(self.items)
+            .collect();
+        if items.is_empty() {
+            return Err("Playlist is empty".to_string());
+        }
+        for (i, item) in items.iter().enumerate() {
+            check_item(item).map_err(|e| format!("Item {}: {}", i, e))?;
+        }
+        Ok(items)
+    }
 }
 
 /// Request to seek to a position.
@@ -88,6 +179,268 @@ pub struct PlayerStateResponse {
     pub current_file: Option<String>,
     /// Full playlist
     pub playlist: Vec<String>,
+    /// Playlist items with their playout settings
+    pub items: Vec<PlaylistItem>,
+    /// Repeat of the current item being played (0 = first play)
+    pub loop_pass: u32,
+    /// Whether the current item is prerolled and waiting for play
+    pub cued: bool,
     /// Whether playlist loops
     pub loop_playlist: bool,
 }
+
+/// How a scheduled event takes over from what is on air.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum StartMode {
+    /// Cut to the event at its start time, whatever is on air.
+    #[default]
+    Hard,
+    /// Wait for the scheduled file on air to end. Filler and live
+    /// breakaways without a duration are cut immediately.
+    Soft,
+}
+
+/// What a scheduled event puts on air.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleSource {
+    /// Play a file.
+    File {
+        /// The item to play, with its playout settings
+        item: PlaylistItem,
+    },
+    /// Break away to live streams published by Inter Output blocks.
+    ///
+    /// The streams must match the player mode: raw in decode mode, encoded
+    /// in passthrough mode.
+    Live {
+        /// Inter channel carrying video
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        video_channel: Option<String>,
+        /// Inter channel carrying audio
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        audio_channel: Option<String>,
+        /// Length of the breakaway in nanoseconds (until the next event when omitted)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ns: Option<u64>,
+    },
+}
+
+/// An event of the playout schedule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ScheduleEvent {
+    /// Unique event ID (assigned when empty)
+    #[serde(default)]
+    pub id: String,
+    /// Title for the as-run log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Wall-clock start time (RFC 3339, e.g. "2026-05-01T18:00:00Z")
+    pub start: String,
+    /// Hard or soft start
+    #[serde(default)]
+    pub start_mode: StartMode,
+    /// What to put on air
+    pub source: ScheduleSource,
+}
+
+/// Wall-clock playout schedule of a media player.
+///
+/// Events replace the playlist at their start time. Filler items are
+/// played in a loop whenever no event is on air.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PlayoutSchedule {
+    /// Upcoming events
+    #[serde(default)]
+    pub events: Vec<ScheduleEvent>,
+    /// Items covering the gaps between events
+    #[serde(default)]
+    pub filler: Vec<PlaylistItem>,
+}
+
+impl PlayoutSchedule {
+    /// Check that files have URIs and valid in/out points and that live
+    /// events name a channel. Start times are checked by the backend.
+    pub fn check(&self) -> Result<(), String> {
+        for (i, item) in self.filler.iter().enumerate() {
+            check_item(item).map_err(|e| format!("Filler item {}: {}", i, e))?;
+        }
+        for event in &self.events {
+            check_event(event).map_err(|e| format!("Event '{}': {}", event.id, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a scheduled event (see [`PlayoutSchedule::check`]).
+pub fn check_event(event: &ScheduleEvent) -> Result<(), String> {
+    if event.start.is_empty() {
+        return Err("no start time".to_string());
+    }
+    match &event.source {
+        ScheduleSource::File { item } => check_item(item),
+        ScheduleSource::Live {
+            video_channel,
+            audio_channel,
+            ..
+        } => {
+            let named = |c: &Option<String>| c.as_deref().is_some_and(|c| !c.is_empty());
+            if named(video_channel) || named(audio_channel) {
+                Ok(())
+            } else {
+                Err("live event needs a video or audio channel".to_string())
+            }
+        }
+    }
+}
+
+fn check_item(item: &PlaylistItem) -> Result<(), String> {
+    if item.uri.is_empty() {
+        return Err("no URI".to_string());
+    }
+    if let (Some(in_ns), Some(out_ns)) = (item.in_point_ns, item.out_point_ns) {
+        if out_ns <= in_ns {
+            return Err("ends before its in point".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// What was on air, for the as-run log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AsRunKind {
+    /// A scheduled file
+    File,
+    /// A scheduled live breakaway
+    Live,
+    /// A filler item
+    Filler,
+}
+
+/// As-run log record type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AsRunStatus {
+    /// Went on air
+    Started,
+    /// Left air
+    Ended,
+    /// Never went on air (superseded by a later event)
+    Missed,
+}
+
+/// One record of the as-run log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AsRunEntry {
+    /// Wall-clock time of the record (RFC 3339)
+    pub time: String,
+    /// What happened
+    pub status: AsRunStatus,
+    /// What was on air
+    pub kind: AsRunKind,
+    /// Scheduled event ID (not set for filler)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    /// Event title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Scheduled start time (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_start: Option<String>,
+    /// File URI or inter channels
+    pub source: String,
+}
+
+/// Response with the playout schedule of a media player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ScheduleResponse {
+    /// Upcoming events, in start order
+    pub events: Vec<ScheduleEvent>,
+    /// Filler items
+    pub filler: Vec<PlaylistItem>,
+    /// Record of what is on air (not set while idle or when the flow is stopped)
+    pub on_air: Option<AsRunEntry>,
+}
+
+/// Response with the as-run log of one day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AsRunResponse {
+    /// Day of the log (YYYY-MM-DD, UTC)
+    pub date: String,
+    /// Records in time order
+    pub entries: Vec<AsRunEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_playlist_request_into_items() {
+        let mut trimmed = PlaylistItem::new("b.mp4");
+        trimmed.in_point_ns = Some(2_000_000_000);
+        let req = SetPlaylistRequest {
+            files: vec!["a.mp4".to_string()],
+            items: vec![trimmed.clone()],
+        };
+        let items = req.into_items().unwrap();
+        assert_eq!(items, vec![PlaylistItem::new("a.mp4"), trimmed.clone()]);
+        assert_eq!(items[1].segment(), Some((2_000_000_000, None)));
+        assert_eq!(items[0].segment(), None);
+
+        let empty = SetPlaylistRequest {
+            files: vec![],
+            items: vec![],
+        };
+        assert!(empty.into_items().is_err());
+
+        trimmed.out_point_ns = Some(1_000_000_000);
+        let inverted = SetPlaylistRequest {
+            files: vec![],
+            items: vec![trimmed],
+        };
+        assert!(inverted.into_items().is_err());
+    }
+
+    #[test]
+    fn test_schedule_check() {
+        let live = ScheduleEvent {
+            id: "news".to_string(),
+            title: None,
+            start: "2026-05-01T18:00:00Z".to_string(),
+            start_mode: StartMode::Soft,
+            source: ScheduleSource::Live {
+                video_channel: Some("strom_flow_studio".to_string()),
+                audio_channel: None,
+                duration_ns: None,
+            },
+        };
+        let mut schedule = PlayoutSchedule {
+            events: vec![live.clone()],
+            filler: vec![PlaylistItem::new("loop.mp4")],
+        };
+        assert!(schedule.check().is_ok());
+
+        schedule.events[0].source = ScheduleSource::Live {
+            video_channel: Some(String::new()),
+            audio_channel: None,
+            duration_ns: None,
+        };
+        assert!(schedule.check().is_err());
+
+        schedule.events[0] = live;
+        schedule.filler.push(PlaylistItem::new(""));
+        assert!(schedule.check().is_err());
+    }
+}
@@ -1,7 +1,14 @@
-//! Default values for the audio mixer block.
+//! Default values, snapshot and automation types for the audio mixer block.
 //!
 //! Single source of truth shared by both backend and frontend.
 
+use crate::PropertyValue;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
 // ── Channel / Main bus processing defaults ──────────────────────────
 pub const DEFAULT_FADER: f32 = 1.0;
 pub const DEFAULT_GAIN: f32 = 0.0;
@@ -34,11 +41,20 @@ pub const DEFAULT_EQ_BANDS: [(f32, f32, f32); 4] = [
 // Limiter
 pub const DEFAULT_LIMITER_THRESHOLD: f32 = -3.0;
 
+// Ducking (sidechain)
+pub const DEFAULT_DUCK_THRESHOLD: f32 = -30.0;
+/// Attenuation applied while the key is above threshold (dB, positive)
+pub const DEFAULT_DUCK_DEPTH: f32 = 15.0;
+pub const DEFAULT_DUCK_ATTACK: f32 = 10.0;
+pub const DEFAULT_DUCK_HOLD: f32 = 500.0;
+pub const DEFAULT_DUCK_RELEASE: f32 = 500.0;
+
 // ── Structural defaults ─────────────────────────────────────────────
 pub const DEFAULT_CHANNELS: usize = 8;
 pub const MAX_CHANNELS: usize = 32;
 pub const MAX_AUX_BUSES: usize = 4;
 pub const MAX_GROUPS: usize = 4;
+pub const MAX_MIX_MINUS: usize = 4;
 
 // ── Routing defaults ──────────────────────────────────────────────
 /// Default aux send pre/post-fader mode per bus (aux 1-2 pre, 3-4 post)
@@ -50,3 +66,123 @@ pub const MIN_KNEE_LINEAR: f64 = 0.0631;
 // ── Latency / live defaults ─────────────────────────────────────────
 pub const DEFAULT_LATENCY_MS: u64 = 30;
 pub const DEFAULT_MIN_UPSTREAM_LATENCY_MS: u64 = 30;
+
+// ── Snapshots ───────────────────────────────────────────────────────
+/// Maximum snapshot crossfade time in milliseconds
+pub const MAX_SNAPSHOT_CROSSFADE_MS: u64 = 60_000;
+
+/// A named console state of one mixer block.
+///
+/// Holds the value of every live mixer property (faders, pans, mutes,
+/// routing, sends and processing). Structural properties such as channel
+/// count or DSP backend are not part of a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MixerSnapshot {
+    /// Snapshot name, unique per block.
+    pub name: String,
+    /// Mixer block instance ID.
+    pub block_id: String,
+    /// Block property values captured by the snapshot.
+    pub properties: BTreeMap<String, PropertyValue>,
+}
+
+/// Request to recall a mixer snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct RecallMixerSnapshotRequest {
+    /// Crossfade time for faders, sends and pans in milliseconds (0 = instant)
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(range(max = MAX_SNAPSHOT_CROSSFADE_MS)))]
+    pub crossfade_ms: u64,
+}
+
+/// List of snapshots on a mixer block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MixerSnapshotListResponse {
+    pub snapshots: Vec<MixerSnapshot>,
+}
+
+/// Response after a snapshot operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MixerSnapshotResponse {
+    pub message: String,
+    /// Snapshot name.
+    pub name: String,
+    /// Number of properties stored (save) or changed (recall).
+    pub properties: usize,
+}
+
+// ── Automation ──────────────────────────────────────────────────────
+/// Maximum number of points in one automation lane
+pub const MAX_AUTOMATION_POINTS: usize = 100_000;
+
+/// How an automation lane behaves while the flow runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationMode {
+    /// Neither played back nor recorded.
+    Off,
+    /// Play the lane back; manual moves are overridden.
+    #[default]
+    Read,
+    /// Record every move, replacing the lane from the first move on.
+    Write,
+    /// Play back, record while the control is being moved and return to
+    /// the lane once it is released.
+    Touch,
+    /// Play back until the control is moved, then record until the flow
+    /// stops.
+    Latch,
+}
+
+/// One value of an automation lane.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AutomationPoint {
+    /// Stream time of the automated element in milliseconds.
+    pub time_ms: u64,
+    /// Element value (linear volume for faders, -1.0 to 1.0 for pans).
+    pub value: f64,
+}
+
+/// Recorded moves of one mixer parameter.
+///
+/// Fader lanes drive the fader's volume element, which also carries the
+/// mute, so mute moves are recorded on the fader lane.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AutomationLane {
+    /// Mixer block instance ID.
+    pub block_id: String,
+    /// Block property automated, e.g. `ch1_fader`, `ch1_pan` or `main_fader`.
+    pub parameter: String,
+    pub mode: AutomationMode,
+    /// Points ordered by time; values are interpolated linearly.
+    #[serde(default)]
+    pub points: Vec<AutomationPoint>,
+}
+
+/// Request to create or update an automation lane.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SetAutomationLaneRequest {
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub mode: AutomationMode,
+    /// Replacement points; the recorded points are kept when omitted.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(length(max = MAX_AUTOMATION_POINTS)))]
+    pub points: Option<Vec<AutomationPoint>>,
+}
+
+/// Automation lanes of a mixer block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MixerAutomationResponse {
+    pub lanes: Vec<AutomationLane>,
+}
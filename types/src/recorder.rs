@@ -0,0 +1,181 @@
+//! Recorder control API types shared between backend and frontend.
+
+use crate::FlowId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Recording state of a recorder block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RecorderState {
+    /// Not writing; the file sink is stopped.
+    Idle,
+    /// Writing to file.
+    Recording,
+    /// File open, incoming media dropped until resumed.
+    Paused,
+    /// The file is being finalized.
+    Stopping,
+}
+
+impl std::fmt::Display for RecorderState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Idle => write!(f, "idle"),
+            Self::Recording => write!(f, "recording"),
+            Self::Paused => write!(f, "paused"),
+            Self::Stopping => write!(f, "stopping"),
+        }
+    }
+}
+
+/// Recorder control action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum RecorderAction {
+    /// Let the trigger start and stop recordings.
+    Arm,
+    /// Ignore the trigger. A recording in progress continues.
+    Disarm,
+    /// Start a new file.
+    Start,
+    /// Stop and finalize the file.
+    Stop,
+    /// Drop incoming media, keeping the file open.
+    Pause,
+    /// Continue a paused recording in the same file.
+    Resume,
+}
+
+/// Request to control a recorder.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RecorderControlRequest {
+    pub action: RecorderAction,
+}
+
+/// Event that makes an armed recorder record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecorderTriggerSource {
+    /// Record while a vision mixer input is on program (tally on-air).
+    Tally {
+        /// Flow of the vision mixer (the recorder's flow when omitted)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, format = Uuid))]
+        flow_id: Option<FlowId>,
+        /// Vision mixer block ID
+        block_id: String,
+        /// Vision mixer input (0-based)
+        input: usize,
+    },
+    /// Record while a meter block's peak level is above a threshold
+    /// (silence has ended).
+    Audio {
+        /// Meter block ID, in the recorder's flow
+        block_id: String,
+        /// Level in dBFS above which the audio is not silent
+        #[serde(default = "default_threshold_db")]
+        threshold_db: f64,
+    },
+}
+
+fn default_threshold_db() -> f64 {
+    -50.0
+}
+
+fn default_hold_ms() -> u64 {
+    2000
+}
+
+/// Trigger of a recorder: what starts and stops recordings while armed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RecorderTrigger {
+    pub source: RecorderTriggerSource,
+    /// Keep recording this long after the trigger goes off (milliseconds)
+    #[serde(default = "default_hold_ms")]
+    pub hold_ms: u64,
+}
+
+impl RecorderTrigger {
+    /// Check the trigger source settings.
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.source {
+            RecorderTriggerSource::Tally { block_id, .. } if block_id.is_empty() => {
+                Err("Tally trigger needs a vision mixer block".to_string())
+            }
+            RecorderTriggerSource::Audio { block_id, .. } if block_id.is_empty() => {
+                Err("Audio trigger needs a meter block".to_string())
+            }
+            RecorderTriggerSource::Audio { threshold_db, .. }
+                if !threshold_db.is_finite() || !(-120.0..=0.0).contains(threshold_db) =>
+            {
+                Err("Audio trigger threshold must be between -120 and 0 dB".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Request to set (or clear) the trigger of a recorder.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(garde::Validate))]
+pub struct SetRecorderTriggerRequest {
+    /// The trigger, or none to record on manual control only
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", garde(skip))]
+    pub trigger: Option<RecorderTrigger>,
+}
+
+/// Recording state of a recorder block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RecorderStatusResponse {
+    pub state: RecorderState,
+    /// Whether the trigger starts and stops recordings
+    pub armed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<RecorderTrigger>,
+    /// File being written, relative to the media directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// Recordings started since the flow started
+    pub takes: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_trigger_validate() {
+        let tally = |block_id: &str| RecorderTrigger {
+            source: RecorderTriggerSource::Tally {
+                flow_id: None,
+                block_id: block_id.to_string(),
+                input: 2,
+            },
+            hold_ms: default_hold_ms(),
+        };
+        assert!(tally("vm").validate().is_ok());
+        assert!(tally("").validate().is_err());
+
+        let audio = |threshold_db: f64| RecorderTrigger {
+            source: RecorderTriggerSource::Audio {
+                block_id: "meter".to_string(),
+                threshold_db,
+            },
+            hold_ms: 0,
+        };
+        assert!(audio(-50.0).validate().is_ok());
+        assert!(audio(3.0).validate().is_err());
+        assert!(audio(f64::NAN).validate().is_err());
+    }
+}
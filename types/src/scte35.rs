@@ -0,0 +1,594 @@
+//! SCTE-35 splice information types and section codec.
+//!
+//! Shared between the backend (injection into `mpegtsmux`, parsing cues from
+//! `tsdemux`) and API clients. Only the subset of SCTE-35 needed for ad
+//! insertion is decoded: `splice_null`, `splice_insert` and `time_signal`.
+//! Other commands are recognised by type but their payload is not parsed.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Default PID used for the SCTE-35 elementary stream on MPEG-TS outputs.
+pub const DEFAULT_SCTE35_PID: u32 = 500;
+
+/// PIDs usable for an SCTE-35 elementary stream (0 disables SCTE-35).
+pub const SCTE35_PID_RANGE: std::ops::RangeInclusive<u32> = 0x10..=0x1FFE;
+
+/// Table ID of a `splice_info_section`.
+pub const SPLICE_INFO_TABLE_ID: u8 = 0xFC;
+
+/// MPEG-TS PTS clock rate (ticks per second).
+pub const PTS_CLOCK_HZ: u64 = 90_000;
+
+/// Largest value representable in a 33-bit PTS field.
+pub const MAX_PTS: u64 = (1 << 33) - 1;
+
+/// SCTE-35 splice command type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Scte35Command {
+    SpliceNull,
+    SpliceSchedule,
+    SpliceInsert,
+    TimeSignal,
+    BandwidthReservation,
+    PrivateCommand,
+}
+
+impl Scte35Command {
+    /// The `splice_command_type` value of this command.
+    pub fn command_type(self) -> u8 {
+        match self {
+            Self::SpliceNull => 0x00,
+            Self::SpliceSchedule => 0x04,
+            Self::SpliceInsert => 0x05,
+            Self::TimeSignal => 0x06,
+            Self::BandwidthReservation => 0x07,
+            Self::PrivateCommand => 0xFF,
+        }
+    }
+
+    /// Look up a command from its `splice_command_type` value.
+    pub fn from_command_type(value: u8) -> Option<Self> {
+        match value {
+            0x00 => Some(Self::SpliceNull),
+            0x04 => Some(Self::SpliceSchedule),
+            0x05 => Some(Self::SpliceInsert),
+            0x06 => Some(Self::TimeSignal),
+            0x07 => Some(Self::BandwidthReservation),
+            0xFF => Some(Self::PrivateCommand),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Scte35Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SpliceNull => write!(f, "splice_null"),
+            Self::SpliceSchedule => write!(f, "splice_schedule"),
+            Self::SpliceInsert => write!(f, "splice_insert"),
+            Self::TimeSignal => write!(f, "time_signal"),
+            Self::BandwidthReservation => write!(f, "bandwidth_reservation"),
+            Self::PrivateCommand => write!(f, "private_command"),
+        }
+    }
+}
+
+/// A decoded SCTE-35 cue (one `splice_info_section`).
+///
+/// All times are in 90 kHz PTS ticks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Scte35Cue {
+    pub command: Scte35Command,
+    /// `pts_adjustment` from the section header
+    #[serde(default)]
+    pub pts_adjustment: u64,
+    /// `splice_event_id` (splice_insert only)
+    #[serde(default)]
+    pub splice_event_id: Option<u32>,
+    /// Cancels a previously sent splice_insert with the same event ID
+    #[serde(default)]
+    pub cancel: bool,
+    /// true = leaving the network feed (ad break start), false = returning
+    #[serde(default)]
+    pub out_of_network: bool,
+    /// Splice at the earliest opportunity instead of at `pts_time`
+    #[serde(default)]
+    pub immediate: bool,
+    /// Splice point, or null for immediate splices
+    #[serde(default)]
+    pub pts_time: Option<u64>,
+    /// Break duration, or null if not signalled
+    #[serde(default)]
+    pub break_duration: Option<u64>,
+    /// Whether the splicer should return to the network after `break_duration`
+    #[serde(default)]
+    pub auto_return: bool,
+    #[serde(default)]
+    pub unique_program_id: u16,
+    #[serde(default)]
+    pub avail_num: u8,
+    #[serde(default)]
+    pub avails_expected: u8,
+}
+
+impl Scte35Cue {
+    /// Create a `splice_insert` cue. `pts_time` of `None` means splice immediately.
+    pub fn splice_insert(
+        splice_event_id: u32,
+        out_of_network: bool,
+        pts_time: Option<u64>,
+        break_duration: Option<u64>,
+        auto_return: bool,
+    ) -> Self {
+        Self {
+            command: Scte35Command::SpliceInsert,
+            pts_adjustment: 0,
+            splice_event_id: Some(splice_event_id),
+            cancel: false,
+            out_of_network,
+            immediate: pts_time.is_none(),
+            pts_time,
+            break_duration,
+            auto_return,
+            unique_program_id: 0,
+            avail_num: 0,
+            avails_expected: 0,
+        }
+    }
+
+    /// Create a `time_signal` cue. `pts_time` of `None` means immediate.
+    pub fn time_signal(pts_time: Option<u64>) -> Self {
+        Self {
+            command: Scte35Command::TimeSignal,
+            pts_adjustment: 0,
+            splice_event_id: None,
+            cancel: false,
+            out_of_network: false,
+            immediate: pts_time.is_none(),
+            pts_time,
+            break_duration: None,
+            auto_return: false,
+            unique_program_id: 0,
+            avail_num: 0,
+            avails_expected: 0,
+        }
+    }
+
+    /// Encode this cue as a complete `splice_info_section` including CRC.
+    ///
+    /// Only `splice_null`, `splice_insert` and `time_signal` can be encoded.
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        if self.pts_adjustment > MAX_PTS {
+            return Err("pts_adjustment exceeds 33 bits".to_string());
+        }
+        if self.pts_time.is_some_and(|p| p > MAX_PTS) {
+            return Err("pts_time exceeds 33 bits".to_string());
+        }
+        if self.break_duration.is_some_and(|d| d > MAX_PTS) {
+            return Err("break_duration exceeds 33 bits".to_string());
+        }
+
+        let mut command = Vec::new();
+        match self.command {
+            Scte35Command::SpliceNull => {}
+            Scte35Command::SpliceInsert => {
+                command.extend_from_slice(&self.splice_event_id.unwrap_or(0).to_be_bytes());
+                // splice_event_cancel_indicator + 7 reserved bits
+                command.push(if self.cancel { 0xFF } else { 0x7F });
+                if !self.cancel {
+                    let immediate = self.immediate || self.pts_time.is_none();
+                    // out_of_network, program_splice_flag=1, duration_flag,
+                    // splice_immediate_flag, event_id_compliance_flag=1, 3 reserved bits
+                    let mut flags = 0x40 | 0x08 | 0x07;
+                    if self.out_of_network {
+                        flags |= 0x80;
+                    }
+                    if self.break_duration.is_some() {
+                        flags |= 0x20;
+                    }
+                    if immediate {
+                        flags |= 0x10;
+                    }
+                    command.push(flags);
+                    if !immediate {
+                        encode_splice_time(&mut command, self.pts_time);
+                    }
+                    if let Some(duration) = self.break_duration {
+                        let auto_return = if self.auto_return { 0x80 } else { 0x00 };
+                        command.push(auto_return | 0x7E | ((duration >> 32) as u8 & 0x01));
+                        command.extend_from_slice(&(duration as u32).to_be_bytes());
+                    }
+                    command.extend_from_slice(&self.unique_program_id.to_be_bytes());
+                    command.push(self.avail_num);
+                    command.push(self.avails_expected);
+                }
+            }
+            Scte35Command::TimeSignal => {
+                encode_splice_time(&mut command, self.pts_time);
+            }
+            other => return Err(format!("encoding {} is not supported", other)),
+        }
+
+        let mut section = Vec::with_capacity(command.len() + 20);
+        section.push(SPLICE_INFO_TABLE_ID);
+        // section_length is filled in below
+        section.extend_from_slice(&[0, 0]);
+        // protocol_version
+        section.push(0);
+        // encrypted_packet=0, encryption_algorithm=0, pts_adjustment (33 bits)
+        section.push((self.pts_adjustment >> 32) as u8 & 0x01);
+        section.extend_from_slice(&(self.pts_adjustment as u32).to_be_bytes());
+        // cw_index
+        section.push(0);
+        // tier (12 bits, 0xFFF = unspecified) + splice_command_length (12 bits)
+        let command_length = command.len() as u16;
+        section.push(0xFF);
+        section.push(0xF0 | ((command_length >> 8) as u8 & 0x0F));
+        section.push(command_length as u8);
+        section.push(self.command.command_type());
+        section.extend_from_slice(&command);
+        // descriptor_loop_length
+        section.extend_from_slice(&[0, 0]);
+
+        // section_length counts everything after the length field, CRC included
+        let section_length = (section.len() - 3 + 4) as u16;
+        // section_syntax_indicator=0, private_indicator=0, sap_type=3
+        section[1] = 0x30 | ((section_length >> 8) as u8 & 0x0F);
+        section[2] = section_length as u8;
+
+        let crc = crc32_mpeg2(&section);
+        section.extend_from_slice(&crc.to_be_bytes());
+        Ok(section)
+    }
+
+    /// Parse a `splice_info_section` (as carried on the SCTE-35 PID).
+    pub fn parse(data: &[u8]) -> Result<Self, String> {
+        if data.len() < 17 {
+            return Err(format!("section too short ({} bytes)", data.len()));
+        }
+        if data[0] != SPLICE_INFO_TABLE_ID {
+            return Err(format!("unexpected table_id 0x{:02X}", data[0]));
+        }
+        let section_length = (((data[1] & 0x0F) as usize) << 8) | data[2] as usize;
+        let total = section_length + 3;
+        if data.len() < total {
+            return Err(format!(
+                "truncated section ({} of {} bytes)",
+                data.len(),
+                total
+            ));
+        }
+        let data = &data[..total];
+        if crc32_mpeg2(data) != 0 {
+            return Err("CRC mismatch".to_string());
+        }
+        if data[4] & 0x80 != 0 {
+            return Err("encrypted sections are not supported".to_string());
+        }
+
+        let pts_adjustment = read_pts(&data[4..9]);
+        let command_length = (((data[11] & 0x0F) as usize) << 8) | data[12] as usize;
+        let command_type = data[13];
+        let command = Scte35Command::from_command_type(command_type)
+            .ok_or_else(|| format!("unknown splice_command_type 0x{:02X}", command_type))?;
+
+        let mut cue = Self {
+            command,
+            pts_adjustment,
+            splice_event_id: None,
+            cancel: false,
+            out_of_network: false,
+            immediate: false,
+            pts_time: None,
+            break_duration: None,
+            auto_return: false,
+            unique_program_id: 0,
+            avail_num: 0,
+            avails_expected: 0,
+        };
+
+        // splice_command_length may be 0xFFF in legacy streams; bound it by the CRC
+        let body_end = if command_length == 0xFFF {
+            total - 4
+        } else {
+            (14 + command_length).min(total - 4)
+        };
+        let mut r = Reader::new(&data[14..body_end]);
+
+        match command {
+            Scte35Command::SpliceInsert => {
+                cue.splice_event_id = Some(r.u32()?);
+                cue.cancel = r.u8()? & 0x80 != 0;
+                if !cue.cancel {
+                    let flags = r.u8()?;
+                    cue.out_of_network = flags & 0x80 != 0;
+                    let program_splice = flags & 0x40 != 0;
+                    let has_duration = flags & 0x20 != 0;
+                    cue.immediate = flags & 0x10 != 0;
+                    if !program_splice {
+                        // Component splice mode: skip per-component splice times
+                        let count = r.u8()?;
+                        for _ in 0..count {
+                            r.u8()?;
+                            if !cue.immediate {
+                                r.splice_time()?;
+                            }
+                        }
+                    } else if !cue.immediate {
+                        cue.pts_time = r.splice_time()?;
+                    }
+                    if has_duration {
+                        let bytes = r.take(5)?;
+                        cue.auto_return = bytes[0] & 0x80 != 0;
+                        cue.break_duration = Some(read_pts(bytes));
+                    }
+                    cue.unique_program_id = r.u16()?;
+                    cue.avail_num = r.u8()?;
+                    cue.avails_expected = r.u8()?;
+                }
+            }
+            Scte35Command::TimeSignal => {
+                cue.pts_time = r.splice_time()?;
+                cue.immediate = cue.pts_time.is_none();
+            }
+            _ => {}
+        }
+
+        Ok(cue)
+    }
+}
+
+/// Request to inject an SCTE-35 cue into an MPEG-TS output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct InsertScte35Request {
+    /// Command to send: "splice_insert" or "time_signal"
+    pub command: Scte35Command,
+    /// Splice point as a 90 kHz PTS value in the output stream, omit to splice immediately
+    #[serde(default)]
+    pub pts_time: Option<u64>,
+    /// Splice event ID (splice_insert only)
+    #[serde(default)]
+    pub splice_event_id: u32,
+    /// true = start of break (out of network), false = return to network (splice_insert only)
+    #[serde(default = "default_true")]
+    pub out_of_network: bool,
+    /// Break duration in milliseconds (splice_insert only)
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Return to network automatically when the break duration ends (splice_insert only)
+    #[serde(default = "default_true")]
+    pub auto_return: bool,
+    /// Cancel a previously sent splice_insert with the same event ID
+    #[serde(default)]
+    pub cancel: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl InsertScte35Request {
+    /// Build the cue described by this request.
+    pub fn to_cue(&self) -> Result<Scte35Cue, String> {
+        if self.pts_time.is_some_and(|p| p > MAX_PTS) {
+            return Err(format!("pts_time must be at most {}", MAX_PTS));
+        }
+        match self.command {
+            Scte35Command::SpliceInsert => {
+                let break_duration = match self.duration_ms {
+                    Some(ms) => {
+                        let ticks = ms.saturating_mul(PTS_CLOCK_HZ) / 1000;
+                        if ticks > MAX_PTS {
+                            return Err("duration_ms is too large".to_string());
+                        }
+                        Some(ticks)
+                    }
+                    None => None,
+                };
+                let mut cue = Scte35Cue::splice_insert(
+                    self.splice_event_id,
+                    self.out_of_network,
+                    self.pts_time,
+                    break_duration,
+                    self.auto_return,
+                );
+                cue.cancel = self.cancel;
+                Ok(cue)
+            }
+            Scte35Command::TimeSignal => Ok(Scte35Cue::time_signal(self.pts_time)),
+            other => Err(format!(
+                "unsupported command '{}' (expected splice_insert or time_signal)",
+                other
+            )),
+        }
+    }
+}
+
+/// Response after injecting an SCTE-35 cue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct InsertScte35Response {
+    pub message: String,
+    pub cue: Scte35Cue,
+}
+
+/// CRC-32/MPEG-2 as used by MPEG-TS PSI sections.
+///
+/// Running it over a section including its trailing CRC yields 0.
+pub fn crc32_mpeg2(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    for &byte in data {
+        crc ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Read a 33-bit value stored in the low bit of `b[0]` and the following 4 bytes.
+fn read_pts(b: &[u8]) -> u64 {
+    (((b[0] & 0x01) as u64) << 32) | u32::from_be_bytes([b[1], b[2], b[3], b[4]]) as u64
+}
+
+/// Append a `splice_time()` structure.
+fn encode_splice_time(out: &mut Vec<u8>, pts_time: Option<u64>) {
+    match pts_time {
+        Some(pts) => {
+            // time_specified_flag=1, 6 reserved bits, 33-bit pts_time
+            out.push(0xFE | ((pts >> 32) as u8 & 0x01));
+            out.extend_from_slice(&(pts as u32).to_be_bytes());
+        }
+        None => out.push(0x7F),
+    }
+}
+
+/// Minimal big-endian byte reader for section parsing.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + n;
+        if end > self.data.len() {
+            return Err("splice command truncated".to_string());
+        }
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn splice_time(&mut self) -> Result<Option<u64>, String> {
+        let first = self
+            .data
+            .get(self.pos)
+            .copied()
+            .ok_or("splice_time missing")?;
+        if first & 0x80 != 0 {
+            Ok(Some(read_pts(self.take(5)?)))
+        } else {
+            self.take(1)?;
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_mpeg2_check_value() {
+        assert_eq!(crc32_mpeg2(b"123456789"), 0x0376_E6E7);
+    }
+
+    #[test]
+    fn test_splice_insert_roundtrip() {
+        let cue = Scte35Cue::splice_insert(42, true, Some(0x1_2345_6789), Some(2_700_000), true);
+        let section = cue.encode().unwrap();
+        assert_eq!(section[0], SPLICE_INFO_TABLE_ID);
+        assert_eq!(crc32_mpeg2(&section), 0);
+        assert_eq!(Scte35Cue::parse(&section).unwrap(), cue);
+    }
+
+    #[test]
+    fn test_splice_insert_immediate_roundtrip() {
+        let cue = Scte35Cue::splice_insert(7, false, None, None, false);
+        let parsed = Scte35Cue::parse(&cue.encode().unwrap()).unwrap();
+        assert!(parsed.immediate);
+        assert_eq!(parsed.pts_time, None);
+        assert_eq!(parsed, cue);
+    }
+
+    #[test]
+    fn test_time_signal_roundtrip() {
+        let cue = Scte35Cue::time_signal(Some(900_000));
+        assert_eq!(Scte35Cue::parse(&cue.encode().unwrap()).unwrap(), cue);
+
+        let immediate = Scte35Cue::time_signal(None);
+        assert_eq!(
+            Scte35Cue::parse(&immediate.encode().unwrap()).unwrap(),
+            immediate
+        );
+    }
+
+    #[test]
+    fn test_parse_reference_time_signal() {
+        // Hand-assembled time_signal section with pts_time 0x72BD0FA5
+        let mut section = vec![
+            0xFC, 0x30, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xF0, 0x05, 0x06,
+            0xFE, 0x72, 0xBD, 0x0F, 0xA5, 0x00, 0x00,
+        ];
+        let crc = crc32_mpeg2(&section);
+        section.extend_from_slice(&crc.to_be_bytes());
+        let cue = Scte35Cue::parse(&section).unwrap();
+        assert_eq!(cue.command, Scte35Command::TimeSignal);
+        assert_eq!(cue.pts_time, Some(0x72BD_0FA5));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_crc() {
+        let mut section = Scte35Cue::time_signal(Some(1)).encode().unwrap();
+        let last = section.len() - 1;
+        section[last] ^= 0xFF;
+        assert!(Scte35Cue::parse(&section).is_err());
+    }
+
+    #[test]
+    fn test_encode_rejects_out_of_range_pts() {
+        assert!(Scte35Cue::time_signal(Some(MAX_PTS + 1)).encode().is_err());
+    }
+
+    #[test]
+    fn test_request_to_cue() {
+        let req = InsertScte35Request {
+            command: Scte35Command::SpliceInsert,
+            pts_time: None,
+            splice_event_id: 1,
+            out_of_network: true,
+            duration_ms: Some(30_000),
+            auto_return: true,
+            cancel: false,
+        };
+        let cue = req.to_cue().unwrap();
+        assert_eq!(cue.break_duration, Some(30 * PTS_CLOCK_HZ));
+        assert!(cue.immediate);
+
+        let req = InsertScte35Request {
+            command: Scte35Command::SpliceNull,
+            ..req
+        };
+        assert!(req.to_cue().is_err());
+    }
+}
@@ -1,5 +1,10 @@
 //! Vision mixer constants and defaults.
 
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
 /// Default number of video inputs.
 pub const DEFAULT_NUM_INPUTS: usize = 4;
 
@@ -39,6 +44,18 @@ pub const MAX_DSK_INPUTS: usize = 4;
 /// Default number of DSK inputs (0 = no DSK).
 pub const DEFAULT_DSK_INPUTS: usize = 0;
 
+/// Maximum number of USK (Upstream Keyer) layers.
+pub const MAX_UPSTREAM_KEYERS: usize = 2;
+
+/// Default number of USK layers (0 = no USK).
+pub const DEFAULT_UPSTREAM_KEYERS: usize = 0;
+
+/// Maximum number of AUX outputs.
+pub const MAX_AUX_OUTPUTS: usize = 4;
+
+/// Default number of AUX outputs.
+pub const DEFAULT_AUX_OUTPUTS: usize = 0;
+
 /// Default compositor latency in milliseconds.
 pub const DEFAULT_LATENCY_MS: u64 = 20;
 
@@ -68,9 +85,19 @@ pub const DIST_BACKGROUND_ZORDER: u32 = 0;
 /// Z-order for PGM group sources on the distribution compositor.
 pub const DIST_PGM_ZORDER: u32 = 1;
 
+/// Base z-order for USK pads on the distribution compositor (+ usk index).
+/// Above PGM, below the stinger and DSK layers.
+pub const DIST_USK_BASE_ZORDER: u32 = 20;
+
+/// Z-order for the stinger clip on the distribution compositor (above PGM, below DSK).
+pub const DIST_STINGER_ZORDER: u32 = 50;
+
 /// Base z-order for DSK pads on the distribution compositor (+ dsk index).
 pub const DIST_DSK_BASE_ZORDER: u32 = 100;
 
+/// Default stinger cut-point frame (where the switch underneath happens).
+pub const DEFAULT_STINGER_CUT_FRAME: u64 = 12;
+
 /// Sentinel value for "no background source".
 pub const NO_BACKGROUND: u64 = u64::MAX;
 
@@ -208,6 +235,77 @@ pub fn compute_group_rects(
     }
 }
 
+// --- AUX outputs ---
+
+/// Source routed to an AUX output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuxSource {
+    /// A video input (0-based).
+    Input { input: usize },
+    /// The program output, DSK layers included.
+    Program,
+    /// The preview group; follows preview changes.
+    Preview,
+    /// The program output without DSK layers.
+    CleanFeed,
+}
+
+/// Packed value of [`AuxSource::Program`] (inputs pack as their index).
+const AUX_PACKED_PROGRAM: u64 = u64::MAX;
+/// Packed value of [`AuxSource::Preview`].
+const AUX_PACKED_PREVIEW: u64 = u64::MAX - 1;
+/// Packed value of [`AuxSource::CleanFeed`].
+const AUX_PACKED_CLEAN_FEED: u64 = u64::MAX - 2;
+
+impl AuxSource {
+    /// Parse the property form: `pgm`, `pvw`, `clean` or `input_N` (0-based).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pgm" => Some(Self::Program),
+            "pvw" => Some(Self::Preview),
+            "clean" => Some(Self::CleanFeed),
+            _ => s
+                .strip_prefix("input_")
+                .and_then(|n| n.parse().ok())
+                .map(|input| Self::Input { input }),
+        }
+    }
+
+    /// Property form of the source (inverse of [`AuxSource::parse`]).
+    pub fn as_property(&self) -> String {
+        match self {
+            Self::Input { input } => format!("input_{}", input),
+            Self::Program => "pgm".to_string(),
+            Self::Preview => "pvw".to_string(),
+            Self::CleanFeed => "clean".to_string(),
+        }
+    }
+
+    /// Pack the source into a u64 for atomic storage.
+    pub fn pack(&self) -> u64 {
+        match self {
+            Self::Input { input } => *input as u64,
+            Self::Program => AUX_PACKED_PROGRAM,
+            Self::Preview => AUX_PACKED_PREVIEW,
+            Self::CleanFeed => AUX_PACKED_CLEAN_FEED,
+        }
+    }
+
+    /// Unpack a value produced by [`AuxSource::pack`].
+    pub fn unpack(val: u64) -> Self {
+        match val {
+            AUX_PACKED_PROGRAM => Self::Program,
+            AUX_PACKED_PREVIEW => Self::Preview,
+            AUX_PACKED_CLEAN_FEED => Self::CleanFeed,
+            input => Self::Input {
+                input: input as usize,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,4 +377,19 @@ mod tests {
         let rects = compute_group_rects(100, 50, 400, 300, 2);
         assert_eq!(rects, vec![(100, 50, 200, 300), (300, 50, 200, 300)]);
     }
+
+    #[test]
+    fn test_aux_source_roundtrip() {
+        for source in [
+            AuxSource::Input { input: 3 },
+            AuxSource::Program,
+            AuxSource::Preview,
+            AuxSource::CleanFeed,
+        ] {
+            assert_eq!(AuxSource::unpack(source.pack()), source);
+            assert_eq!(AuxSource::parse(&source.as_property()), Some(source));
+        }
+        assert_eq!(AuxSource::parse("input_x"), None);
+        assert_eq!(AuxSource::parse(""), None);
+    }
 }